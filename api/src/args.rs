@@ -1,6 +1,7 @@
 use clap::Parser;
 use ferriscord_server::args::{
-    ServerArgs, auth::AuthArgs, database::DatabaseArgs, log::LogArgs, storage::StorageArgs,
+    ServerArgs, auth::AuthArgs, database::DatabaseArgs, gateway::GatewayArgs, log::LogArgs,
+    storage::StorageArgs, webhook::WebhookArgs,
 };
 
 #[derive(Debug, Clone, Parser)]
@@ -19,4 +20,10 @@ pub struct Args {
 
     #[command(flatten)]
     pub storage: StorageArgs,
+
+    #[command(flatten)]
+    pub gateway: GatewayArgs,
+
+    #[command(flatten)]
+    pub webhook: WebhookArgs,
 }
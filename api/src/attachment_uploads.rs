@@ -0,0 +1,43 @@
+//! Attachments uploaded straight to storage. Slots never attached expire;
+//! every replica prunes them now and then, deleting their uploaded files.
+//! Attached files were copied and are kept.
+
+use std::time::Duration;
+
+use ferriscord_core::guild::domain::attachment_upload::ports::AttachmentUploadService;
+use ferriscord_storage::StoragePort;
+use tracing::warn;
+
+use crate::state::AppState;
+
+/// How often expired upload slots are pruned.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+pub async fn prune_attachment_uploads(state: AppState) {
+    let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let bucket = &state.args.storage.bucket;
+
+    loop {
+        interval.tick().await;
+        let expired = match state.attachment_upload_service.prune_uploads().await {
+            Ok(expired) => expired,
+            Err(e) => {
+                warn!("failed to prune attachment uploads: {:?}", e);
+                continue;
+            }
+        };
+        for upload in expired {
+            if let Err(e) = state
+                .storage
+                .delete_object(bucket, &upload.storage_key)
+                .await
+            {
+                warn!(
+                    "failed to delete expired upload '{}': {}",
+                    upload.storage_key, e
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! Files sent in the body of a message request. They are read in full
+//! first and only stored once the message is otherwise ready, then removed
+//! again if the message is refused, so failed requests leave no objects.
+
+use std::future::Future;
+
+use axum::body::Bytes;
+use ferriscord_core::guild::domain::message::ports::AttachmentInput;
+use ferriscord_entities::attachment::AttachmentId;
+use ferriscord_error::ApiError;
+use ferriscord_storage::StoragePort;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+/// A file read from a request, not stored yet.
+pub(crate) struct AttachmentFile {
+    pub filename: String,
+    pub content_type: String,
+    pub data: Bytes,
+}
+
+/// Deletes objects no longer needed, logging failures.
+pub(crate) async fn delete_objects<S: StoragePort>(storage: &S, bucket: &str, keys: &[String]) {
+    for key in keys {
+        if let Err(e) = storage.delete_object(bucket, key).await {
+            warn!("failed to delete '{}': {}", key, e);
+        }
+    }
+}
+
+/// Stores each file under the channel's attachments. Files already stored
+/// are deleted if any of them fails.
+async fn store_files<S: StoragePort>(
+    storage: &S,
+    bucket: &str,
+    channel_id: Uuid,
+    files: Vec<AttachmentFile>,
+) -> Result<Vec<AttachmentInput>, ApiError> {
+    let mut stored: Vec<AttachmentInput> = Vec::with_capacity(files.len());
+    for file in files {
+        let id = AttachmentId::new();
+        let storage_key = format!("attachments/{}/{}", channel_id, id.get_uuid());
+        let size_bytes = file.data.len() as i64;
+
+        if let Err(e) = storage
+            .put_object(bucket, &storage_key, file.data, &file.content_type)
+            .await
+        {
+            error!("failed to upload attachment '{}': {}", file.filename, e);
+            let keys: Vec<String> = stored.into_iter().map(|input| input.storage_key).collect();
+            delete_objects(storage, bucket, &keys).await;
+            return Err(ApiError::Unknown {
+                message: format!("failed to upload attachment: {}", e),
+            });
+        }
+
+        stored.push(AttachmentInput {
+            id,
+            filename: file.filename,
+            content_type: file.content_type,
+            size_bytes,
+            storage_key,
+        });
+    }
+    Ok(stored)
+}
+
+/// Stores the files, then hands them to `post`, which creates the message
+/// they are attached to. The files are deleted if `post` fails.
+pub(crate) async fn post_with_files<S, T, F, Fut>(
+    storage: &S,
+    bucket: &str,
+    channel_id: Uuid,
+    files: Vec<AttachmentFile>,
+    post: F,
+) -> Result<T, ApiError>
+where
+    S: StoragePort,
+    F: FnOnce(Vec<AttachmentInput>) -> Fut,
+    Fut: Future<Output = Result<T, ApiError>>,
+{
+    let attachments = store_files(storage, bucket, channel_id, files).await?;
+    let keys: Vec<String> = attachments
+        .iter()
+        .map(|input| input.storage_key.clone())
+        .collect();
+
+    let result = post(attachments).await;
+    if result.is_err() {
+        delete_objects(storage, bucket, &keys).await;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use ferriscord_storage::{ObjectInfo, StorageError};
+
+    use super::*;
+
+    /// Bodies and content types, by bucket and key.
+    type Objects = HashMap<(String, String), (Bytes, String)>;
+
+    /// Objects kept in memory.
+    #[derive(Clone, Default)]
+    struct MemoryStorage {
+        objects: Arc<Mutex<Objects>>,
+    }
+
+    impl MemoryStorage {
+        fn keys(&self) -> Vec<String> {
+            let objects = self.objects.lock().unwrap();
+            objects.keys().map(|(_, key)| key.clone()).collect()
+        }
+
+        fn info(key: &str, object: &(Bytes, String)) -> ObjectInfo {
+            ObjectInfo {
+                key: key.to_string(),
+                size: Some(object.0.len() as i64),
+                last_modified: None,
+                etag: None,
+                content_type: Some(object.1.clone()),
+            }
+        }
+    }
+
+    impl StoragePort for MemoryStorage {
+        async fn put_object(
+            &self,
+            bucket: &str,
+            key: &str,
+            body: Bytes,
+            content_type: &str,
+        ) -> Result<(), StorageError> {
+            self.objects.lock().unwrap().insert(
+                (bucket.to_string(), key.to_string()),
+                (body, content_type.to_string()),
+            );
+            Ok(())
+        }
+
+        async fn get_object(&self, bucket: &str, key: &str) -> Result<Bytes, StorageError> {
+            let objects = self.objects.lock().unwrap();
+            objects
+                .get(&(bucket.to_string(), key.to_string()))
+                .map(|object| object.0.clone())
+                .ok_or_else(|| StorageError::NotFound {
+                    key: key.to_string(),
+                })
+        }
+
+        async fn delete_object(&self, bucket: &str, key: &str) -> Result<(), StorageError> {
+            let mut objects = self.objects.lock().unwrap();
+            objects.remove(&(bucket.to_string(), key.to_string()));
+            Ok(())
+        }
+
+        async fn object_exists(&self, bucket: &str, key: &str) -> Result<bool, StorageError> {
+            let objects = self.objects.lock().unwrap();
+            Ok(objects.contains_key(&(bucket.to_string(), key.to_string())))
+        }
+
+        async fn head_object(
+            &self,
+            bucket: &str,
+            key: &str,
+        ) -> Result<Option<ObjectInfo>, StorageError> {
+            let objects = self.objects.lock().unwrap();
+            Ok(objects
+                .get(&(bucket.to_string(), key.to_string()))
+                .map(|object| Self::info(key, object)))
+        }
+
+        async fn list_objects(
+            &self,
+            bucket: &str,
+            prefix: &str,
+        ) -> Result<Vec<ObjectInfo>, StorageError> {
+            let objects = self.objects.lock().unwrap();
+            Ok(objects
+                .iter()
+                .filter(|((b, key), _)| b == bucket && key.starts_with(prefix))
+                .map(|((_, key), object)| Self::info(key, object))
+                .collect())
+        }
+
+        async fn copy_object(
+            &self,
+            src_bucket: &str,
+            src_key: &str,
+            dst_bucket: &str,
+            dst_key: &str,
+        ) -> Result<(), StorageError> {
+            let mut objects = self.objects.lock().unwrap();
+            let object = objects
+                .get(&(src_bucket.to_string(), src_key.to_string()))
+                .cloned()
+                .ok_or_else(|| StorageError::NotFound {
+                    key: src_key.to_string(),
+                })?;
+            objects.insert((dst_bucket.to_string(), dst_key.to_string()), object);
+            Ok(())
+        }
+
+        async fn presigned_get_url(
+            &self,
+            bucket: &str,
+            key: &str,
+            _expires_in: Duration,
+        ) -> Result<String, StorageError> {
+            Ok(format!("memory://{}/{}", bucket, key))
+        }
+
+        async fn presigned_put_url(
+            &self,
+            bucket: &str,
+            key: &str,
+            _expires_in: Duration,
+        ) -> Result<String, StorageError> {
+            Ok(format!("memory://{}/{}", bucket, key))
+        }
+
+        async fn create_bucket(&self, _bucket: &str) -> Result<(), StorageError> {
+            Ok(())
+        }
+
+        async fn delete_bucket(&self, _bucket: &str) -> Result<(), StorageError> {
+            Ok(())
+        }
+    }
+
+    fn files() -> Vec<AttachmentFile> {
+        ["a.txt", "b.txt"]
+            .into_iter()
+            .map(|filename| AttachmentFile {
+                filename: filename.to_string(),
+                content_type: "text/plain".to_string(),
+                data: Bytes::from_static(b"hello"),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_refused_message_leaves_no_objects() {
+        let storage = MemoryStorage::default();
+        let channel_id = Uuid::now_v7();
+
+        let result: Result<(), ApiError> =
+            post_with_files(&storage, "bucket", channel_id, files(), |attachments| {
+                let stored = storage.keys();
+                async move {
+                    assert_eq!(attachments.len(), 2);
+                    assert_eq!(stored.len(), 2);
+                    Err(ApiError::BadRequest {
+                        message: "message is empty".to_string(),
+                    })
+                }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert!(storage.keys().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_posted_message_keeps_its_objects() {
+        let storage = MemoryStorage::default();
+        let channel_id = Uuid::now_v7();
+
+        let attachments = post_with_files(
+            &storage,
+            "bucket",
+            channel_id,
+            files(),
+            |attachments| async { Ok(attachments) },
+        )
+        .await
+        .unwrap();
+
+        let mut keys = storage.keys();
+        keys.sort();
+        let mut expected: Vec<String> = attachments
+            .into_iter()
+            .map(|input| input.storage_key)
+            .collect();
+        expected.sort();
+        assert_eq!(keys, expected);
+        assert!(
+            keys.iter()
+                .all(|key| key.starts_with(&format!("attachments/{}/", channel_id)))
+        );
+    }
+}
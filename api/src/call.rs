@@ -0,0 +1,134 @@
+//! DM calls. Participants are rung and kept up to date through their
+//! `user:{id}` rooms; the call message written when a call ends goes to the
+//! DM room like any other message.
+
+use std::time::Duration;
+
+use ferriscord_core::{
+    guild::domain::voice::ports::{JoinCallInput, VoiceJoin, VoiceService},
+    user::domain::{
+        call::ports::{CallService, CallUpdate},
+        common::CoreError,
+    },
+};
+use ferriscord_entities::dm_call::DmCall;
+use ferriscord_error::ApiError;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{state::AppState, ws::WsHub};
+
+/// How often rings are checked for timeouts.
+const RING_SWEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+pub(crate) fn map_call_error(error: CoreError) -> ApiError {
+    match error {
+        CoreError::DmChannelNotFound | CoreError::NoActiveCall => ApiError::NotFound {
+            message: error.to_string(),
+        },
+        CoreError::CallAlreadyActive => ApiError::BadRequest {
+            message: error.to_string(),
+        },
+        CoreError::NotInCall => ApiError::Forbidden {
+            message: error.to_string(),
+        },
+        _ => ApiError::Unknown {
+            message: error.to_string(),
+        },
+    }
+}
+
+/// Sends an event carrying the call to every participant.
+pub async fn publish_call(hub: &WsHub, kind: &str, call: &DmCall) {
+    for participant in &call.participants {
+        let room = format!("user:{}", participant.user_id);
+        if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+            "type": kind,
+            "room": room,
+            "data": call,
+        })) {
+            hub.publish(&room, payload).await;
+        }
+    }
+}
+
+/// Publishes a started call and rings everybody but the caller.
+pub async fn call_started(hub: &WsHub, call: &DmCall) {
+    publish_call(hub, "call.create", call).await;
+
+    for user_id in call.ringing() {
+        let room = format!("user:{}", user_id);
+        if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+            "type": "call.ring",
+            "room": room,
+            "data": {
+                "call_id": call.id,
+                "channel_id": call.channel_id,
+                "caller_id": call.started_by,
+            },
+        })) {
+            hub.publish(&room, payload).await;
+        }
+    }
+}
+
+/// Publishes a changed call and, when the change ended it, the call message.
+pub async fn call_updated(hub: &WsHub, update: &CallUpdate) {
+    publish_call(hub, "call.update", &update.call).await;
+
+    if let Some(message) = &update.ended {
+        let room = format!("dm:{}", update.call.channel_id);
+        if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+            "type": "message.new",
+            "room": room,
+            "data": message,
+        })) {
+            hub.publish(&room, payload).await;
+        }
+    }
+}
+
+/// Hangs the user up from the call of a DM channel, e.g. once their voice
+/// connection to it ended.
+pub async fn call_left(state: &AppState, user_id: Uuid, channel_id: Uuid) {
+    match state.call_service.leave_call(user_id, channel_id).await {
+        Ok(Some(update)) => call_updated(&state.hub, &update).await,
+        Ok(None) => {}
+        Err(e) => warn!("failed to leave DM call: {:?}", e),
+    }
+}
+
+/// Connects a gateway session to the voice channel of a DM call the user
+/// joined.
+pub async fn connect(state: &AppState, input: JoinCallInput) -> Result<VoiceJoin, String> {
+    state
+        .call_service
+        .authorize_voice(input.user_id, input.channel_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    state
+        .voice_service
+        .join_call(input)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Periodically marks unanswered rings as missed, ending calls nobody
+/// answered.
+pub async fn expire_call_rings(state: AppState) {
+    let mut interval = tokio::time::interval(RING_SWEEP_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        match state.call_service.expire_rings().await {
+            Ok(updates) => {
+                for update in updates {
+                    call_updated(&state.hub, &update).await;
+                }
+            }
+            Err(e) => warn!("failed to expire DM call rings: {:?}", e),
+        }
+    }
+}
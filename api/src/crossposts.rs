@@ -0,0 +1,115 @@
+//! Announcement crossposts. Copies of a published message show up in the
+//! following channels like new messages, and edits and deletes of the source
+//! follow them there.
+
+use std::time::Duration;
+
+use ferriscord_core::guild::domain::message::ports::Crosspost;
+use ferriscord_entities::{
+    event_subscription::GuildEventType,
+    message::{Message, MessageReference},
+};
+use ferriscord_storage::StoragePort;
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{events::dispatch_guild_event, read_state::record_guild_message, state::AppState};
+
+async fn publish(state: &AppState, room: String, kind: &str, data: impl Serialize) {
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": kind,
+        "room": room,
+        "data": data,
+    })) {
+        state.hub.publish(&room, payload).await;
+    }
+}
+
+/// Fills in download URLs. Copies share the stored files of their source.
+pub async fn presign_attachments(state: &AppState, message: &mut Message) {
+    let bucket = &state.args.storage.bucket;
+    for attachment in &mut message.attachments {
+        match state
+            .storage
+            .presigned_get_url(bucket, &attachment.storage_key, Duration::from_secs(3600))
+            .await
+        {
+            Ok(url) => attachment.url = url,
+            Err(e) => error!(
+                "failed to generate presigned URL for '{}': {}",
+                attachment.storage_key, e
+            ),
+        }
+    }
+}
+
+/// Publishes an edited guild message to its channel and guild.
+pub async fn publish_message_update(state: &AppState, guild_id: Uuid, message: &Message) {
+    for room in [
+        format!("channel:{}", message.channel_id),
+        format!("guild:{}", guild_id),
+    ] {
+        publish(state, room, "message.update", message).await;
+    }
+    dispatch_guild_event(state, guild_id, GuildEventType::MessageUpdate, message).await;
+}
+
+/// Publishes fresh copies as new messages of the following channels.
+pub async fn publish_crossposts(state: &AppState, crossposts: Vec<Crosspost>) {
+    for Crosspost {
+        guild_id,
+        mut message,
+    } in crossposts
+    {
+        presign_attachments(state, &mut message).await;
+        for room in [
+            format!("channel:{}", message.channel_id),
+            format!("guild:{}", guild_id),
+        ] {
+            publish(state, room, "message.new", &message).await;
+        }
+
+        record_guild_message(
+            state,
+            guild_id,
+            message.channel_id.get_uuid(),
+            message.id.get_uuid(),
+            None,
+            &message.content,
+        )
+        .await;
+
+        dispatch_guild_event(state, guild_id, GuildEventType::MessageCreate, &message).await;
+    }
+}
+
+/// Publishes copies that took over an edit of their source.
+pub async fn publish_crosspost_updates(state: &AppState, crossposts: Vec<Crosspost>) {
+    for Crosspost {
+        guild_id,
+        mut message,
+    } in crossposts
+    {
+        presign_attachments(state, &mut message).await;
+        publish_message_update(state, guild_id, &message).await;
+    }
+}
+
+/// Publishes the removal of copies whose source was deleted.
+pub async fn publish_crosspost_deletes(state: &AppState, copies: &[MessageReference]) {
+    for copy in copies {
+        let data = serde_json::json!({
+            "message_id": copy.message_id,
+            "channel_id": copy.channel_id,
+        });
+        publish(
+            state,
+            format!("channel:{}", copy.channel_id),
+            "message.delete",
+            &data,
+        )
+        .await;
+        dispatch_guild_event(state, copy.guild_id, GuildEventType::MessageDelete, data).await;
+    }
+}
@@ -0,0 +1,88 @@
+//! Outgoing guild events. Handlers queue events for the guild's event
+//! subscriptions; every replica runs a worker that claims due deliveries and
+//! POSTs them, so a delivery is attempted by one replica at a time.
+
+use std::time::Duration;
+
+use ferriscord_core::guild::domain::event_subscription::ports::EventSubscriptionService;
+use ferriscord_entities::event_subscription::GuildEventType;
+use serde::Serialize;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+use crate::state::AppState;
+
+/// How often due deliveries are looked for.
+const DELIVERY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Deliveries claimed per poll.
+const DELIVERY_BATCH: i64 = 50;
+/// How often the delivery log is pruned.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Queues a guild event for its subscriptions. Failing to do so is logged
+/// and does not fail the request that caused the event.
+pub async fn dispatch_guild_event(
+    state: &AppState,
+    guild_id: Uuid,
+    event_type: GuildEventType,
+    data: impl Serialize,
+) {
+    let data = match serde_json::to_value(data) {
+        Ok(data) => data,
+        Err(e) => {
+            warn!("failed to serialize {} event: {}", event_type.as_str(), e);
+            return;
+        }
+    };
+
+    if let Err(e) = state
+        .event_subscription_service
+        .publish_event(guild_id, event_type, data)
+        .await
+    {
+        warn!("failed to queue {} event: {:?}", event_type.as_str(), e);
+    }
+}
+
+/// Sends due deliveries, each in its own task so a slow endpoint does not hold
+/// the others up, and prunes the delivery log now and then.
+pub async fn deliver_events(state: AppState) {
+    let mut interval = tokio::time::interval(DELIVERY_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut prune = tokio::time::interval(PRUNE_INTERVAL);
+    prune.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let jobs = match state
+                    .event_subscription_service
+                    .claim_due_deliveries(DELIVERY_BATCH)
+                    .await
+                {
+                    Ok(jobs) => jobs,
+                    Err(e) => {
+                        warn!("failed to claim event deliveries: {:?}", e);
+                        continue;
+                    }
+                };
+
+                for job in jobs {
+                    let state = state.clone();
+                    tokio::spawn(async move {
+                        let delivery_id = job.delivery.id;
+                        match state.event_subscription_service.deliver(job).await {
+                            Ok(outcome) => debug!("event delivery {}: {:?}", delivery_id, outcome),
+                            Err(e) => warn!("failed to record event delivery {}: {:?}", delivery_id, e),
+                        }
+                    });
+                }
+            }
+            _ = prune.tick() => {
+                if let Err(e) = state.event_subscription_service.prune_deliveries().await {
+                    warn!("failed to prune event deliveries: {:?}", e);
+                }
+            }
+        }
+    }
+}
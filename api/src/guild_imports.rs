@@ -0,0 +1,196 @@
+//! Guild imports from Discord exports. Every replica runs a worker that
+//! claims started imports; a claimed import is leased and renewed with every
+//! batch of messages, so an import whose replica goes away is picked up by
+//! another one from its last batch.
+
+use std::time::Duration;
+
+use axum::body::Bytes;
+use ferriscord_core::guild::domain::{
+    errors::CoreError,
+    guild_import::ports::{
+        GuildImportService, ImportJob, ImportedAttachment, ImportedMessage, StagedMessage,
+    },
+};
+use ferriscord_entities::guild_import::GuildImport;
+use ferriscord_storage::StoragePort;
+use tracing::{info, warn};
+
+use crate::{state::AppState, ws::WsHub};
+
+/// How often started imports are looked for.
+const IMPORT_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Staged messages imported per batch.
+const IMPORT_BATCH: i64 = 100;
+/// How often old imports are pruned.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const DEFAULT_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Tells every session of the importer how their import is doing.
+pub async fn publish_import_update(hub: &WsHub, import: &GuildImport) {
+    let room = format!("user:{}", import.user_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "guild_import.update",
+        "room": room,
+        "data": import,
+    })) {
+        hub.publish(&room, payload).await;
+    }
+}
+
+/// Downloads the attachments of a batch and stores them. A dry run only
+/// checks which could be. Returns the messages, and how many attachments
+/// were skipped.
+async fn import_batch(
+    state: &AppState,
+    job: &ImportJob,
+    staged: &[StagedMessage],
+) -> (Vec<ImportedMessage>, u64) {
+    let bucket = &state.args.storage.bucket;
+    let mut skipped = 0;
+    let mut messages = Vec::with_capacity(staged.len());
+    for message in staged {
+        let channel_id = job.channel_id(&message.channel_source_id);
+        let mut attachments = Vec::with_capacity(message.attachments.len());
+        for attachment in &message.attachments {
+            let id = job.attachment_id(attachment);
+            let storage_key = format!("attachments/{}/{}", channel_id, id);
+            let content_type = if job.import.dry_run {
+                if !attachment.is_importable() {
+                    skipped += 1;
+                    continue;
+                }
+                DEFAULT_CONTENT_TYPE.to_string()
+            } else {
+                let fetched = match state
+                    .guild_import_service
+                    .fetch_attachment(attachment)
+                    .await
+                {
+                    Ok(fetched) => fetched,
+                    Err(e) => {
+                        warn!("skipping attachment {}: {}", attachment.url, e);
+                        skipped += 1;
+                        continue;
+                    }
+                };
+                let content_type = fetched
+                    .content_type
+                    .unwrap_or_else(|| DEFAULT_CONTENT_TYPE.to_string());
+                if let Err(e) = state
+                    .storage
+                    .put_object(
+                        bucket,
+                        &storage_key,
+                        Bytes::from(fetched.data),
+                        &content_type,
+                    )
+                    .await
+                {
+                    warn!("failed to store attachment {}: {}", attachment.url, e);
+                    skipped += 1;
+                    continue;
+                }
+                content_type
+            };
+            attachments.push(ImportedAttachment {
+                id,
+                filename: attachment.filename.clone(),
+                content_type,
+                size_bytes: attachment.size_bytes,
+                storage_key,
+            });
+        }
+
+        messages.push(ImportedMessage {
+            id: job.message_id(message),
+            channel_id,
+            author_id: job.author_id(&message.author_source_id),
+            content: message.content.clone(),
+            attachments,
+            created_at: message.created_at,
+            edited_at: message.edited_at,
+        });
+    }
+    (messages, skipped)
+}
+
+/// Imports a claimed import to the end, or until it is canceled.
+async fn run_import(state: &AppState, job: &mut ImportJob) -> Result<(), CoreError> {
+    let service = &state.guild_import_service;
+    if !service.create_structure(job).await? {
+        return Ok(());
+    }
+    publish_import_update(&state.hub, &job.import).await;
+
+    loop {
+        let staged = service.next_messages(job, IMPORT_BATCH).await?;
+        let Some(last_seq) = staged.last().map(|message| message.seq) else {
+            break;
+        };
+        let (messages, skipped) = import_batch(state, job, &staged).await;
+        if !service
+            .record_messages(job, messages, skipped, last_seq)
+            .await?
+        {
+            return Ok(());
+        }
+        publish_import_update(&state.hub, &job.import).await;
+    }
+
+    if let Some(import) = service.finish_import(job).await? {
+        info!("guild import {} completed", import.id);
+        publish_import_update(&state.hub, &import).await;
+    }
+    Ok(())
+}
+
+/// Runs started imports, each in its own task, and prunes old ones now and
+/// then. An import failing on the database keeps its lease and is resumed
+/// once it runs out; any other error fails it.
+pub async fn process_guild_imports(state: AppState) {
+    let mut interval = tokio::time::interval(IMPORT_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut prune = tokio::time::interval(PRUNE_INTERVAL);
+    prune.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let job = match state.guild_import_service.claim_next().await {
+                    Ok(Some(job)) => job,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        warn!("failed to claim guild imports: {:?}", e);
+                        continue;
+                    }
+                };
+
+                let state = state.clone();
+                tokio::spawn(async move {
+                    let mut job = job;
+                    match run_import(&state, &mut job).await {
+                        Ok(()) => {}
+                        Err(e @ CoreError::Unknown { .. }) => {
+                            warn!("guild import {} interrupted: {:?}", job.import.id, e);
+                        }
+                        Err(e) => match state
+                            .guild_import_service
+                            .fail_import(&job, &e.to_string())
+                            .await
+                        {
+                            Ok(Some(import)) => publish_import_update(&state.hub, &import).await,
+                            Ok(None) => {}
+                            Err(e) => warn!("failed to fail guild import {}: {:?}", job.import.id, e),
+                        },
+                    }
+                });
+            }
+            _ = prune.tick() => {
+                if let Err(e) = state.guild_import_service.prune_imports().await {
+                    warn!("failed to prune guild imports: {:?}", e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::application::ports::{ApplicationService, CreateApplicationInput},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::application::Application;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications")]
+pub struct CreateApplicationRoute;
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateApplicationRequest {
+    /// 2 to 32 letters, digits, '_', '.' or '-'. Also the bot's username.
+    pub name: String,
+    /// Up to 400 characters.
+    pub description: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/applications",
+    tag = "applications",
+    summary = "Create an application",
+    description = "Creates an application owned by the caller, together with its bot user. The response holds the bot token, which is not shown again; bots send it as `Authorization: Bot <token>`.",
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = CreateApplicationRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = Application),
+        (status = 400, description = "Invalid or taken name, or too many applications", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Bots cannot create applications", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_application_handler(
+    _: CreateApplicationRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<CreateApplicationRequest>,
+) -> Result<Response<Application>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let application = state
+        .application_service
+        .create_application(
+            identity,
+            user.id.0,
+            CreateApplicationInput {
+                name: req.name,
+                description: req.description,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::Created(application))
+}
@@ -0,0 +1,144 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::interaction::ports::{CommandInput, InteractionService},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    guild::GuildId,
+    interaction::{ApplicationCommand, CommandOption},
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications/{application_id}/commands")]
+pub struct CreateGlobalCommandRoute {
+    pub application_id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications/{application_id}/guilds/{guild_id}/commands")]
+pub struct CreateGuildCommandRoute {
+    pub application_id: Uuid,
+    pub guild_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateCommandRequest {
+    /// 1 to 32 lowercase letters, digits, '-' or '_'.
+    pub name: String,
+    /// 1 to 100 characters.
+    pub description: String,
+    /// Up to 25 options, required ones first.
+    #[serde(default)]
+    pub options: Vec<CommandOption>,
+}
+
+async fn create_command(
+    state: AppState,
+    identity: Identity,
+    application_id: Uuid,
+    guild_id: Option<Uuid>,
+    req: CreateCommandRequest,
+) -> Result<Response<ApplicationCommand>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let command = state
+        .interaction_service
+        .create_command(
+            user.id.0,
+            application_id,
+            guild_id.map(GuildId::from),
+            CommandInput {
+                name: req.name,
+                description: req.description,
+                options: req.options,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::Created(command))
+}
+
+#[utoipa::path(
+    post,
+    path = "/applications/{application_id}/commands",
+    tag = "commands",
+    summary = "Register a global command",
+    description = "Registers a slash command usable in every guild the bot is in, replacing the global command of the same name. Allowed for the application's owner and its bot.",
+    params(
+        ("application_id" = Uuid, Path, description = "Application ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = CreateCommandRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = ApplicationCommand),
+        (status = 400, description = "Invalid command or too many commands", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Application not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_global_command_handler(
+    CreateGlobalCommandRoute { application_id }: CreateGlobalCommandRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<CreateCommandRequest>,
+) -> Result<Response<ApplicationCommand>, ApiError> {
+    create_command(state, identity, application_id, None, req).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/applications/{application_id}/guilds/{guild_id}/commands",
+    tag = "commands",
+    summary = "Register a guild command",
+    description = "Registers a slash command usable in one guild the bot is in, replacing that guild's command of the same name. Allowed for the application's owner and its bot.",
+    params(
+        ("application_id" = Uuid, Path, description = "Application ID"),
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = CreateCommandRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = ApplicationCommand),
+        (status = 400, description = "Invalid command, bot not in the guild or too many commands", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Application not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_guild_command_handler(
+    CreateGuildCommandRoute {
+        application_id,
+        guild_id,
+    }: CreateGuildCommandRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<CreateCommandRequest>,
+) -> Result<Response<ApplicationCommand>, ApiError> {
+    create_command(state, identity, application_id, Some(guild_id), req).await
+}
@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::application::ports::ApplicationService, user::domain::user::ports::UserService,
+};
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications/{application_id}")]
+pub struct DeleteApplicationRoute {
+    pub application_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/applications/{application_id}",
+    tag = "applications",
+    summary = "Delete an application",
+    description = "Deletes one of the caller's applications and its bot user, removing the bot from every guild it was added to.",
+    params(
+        ("application_id" = Uuid, Path, description = "Application ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 204, description = "Application deleted"),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Application not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn delete_application_handler(
+    DeleteApplicationRoute { application_id }: DeleteApplicationRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    state
+        .application_service
+        .delete_application(user.id.0, application_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
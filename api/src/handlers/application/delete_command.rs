@@ -0,0 +1,67 @@
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::interaction::ports::InteractionService, user::domain::user::ports::UserService,
+};
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications/{application_id}/commands/{command_id}")]
+pub struct DeleteCommandRoute {
+    pub application_id: Uuid,
+    pub command_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/applications/{application_id}/commands/{command_id}",
+    tag = "commands",
+    summary = "Delete a command",
+    description = "Deletes a global or guild command of the application. Allowed for the application's owner and its bot.",
+    params(
+        ("application_id" = Uuid, Path, description = "Application ID"),
+        ("command_id" = Uuid, Path, description = "Command ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 204, description = "Command deleted"),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Application or command not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn delete_command_handler(
+    DeleteCommandRoute {
+        application_id,
+        command_id,
+    }: DeleteCommandRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    state
+        .interaction_service
+        .delete_command(user.id.0, application_id, command_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,61 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::application::ports::ApplicationService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::application::Application;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications/{application_id}")]
+pub struct GetApplicationRoute {
+    pub application_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/applications/{application_id}",
+    tag = "applications",
+    summary = "Get an application",
+    description = "Returns one of the caller's applications.",
+    params(
+        ("application_id" = Uuid, Path, description = "Application ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Application),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Application not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_application_handler(
+    GetApplicationRoute { application_id }: GetApplicationRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Application>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let application = state
+        .application_service
+        .get_application(user.id.0, application_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(application))
+}
@@ -0,0 +1,54 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::application::ports::ApplicationService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::application::Application;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications")]
+pub struct ListApplicationsRoute;
+
+#[utoipa::path(
+    get,
+    path = "/applications",
+    tag = "applications",
+    summary = "List your applications",
+    description = "Lists the applications owned by the caller. Bot tokens are never included.",
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<Application>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_applications_handler(
+    _: ListApplicationsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<Application>>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let applications = state
+        .application_service
+        .list_applications(user.id.0)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(applications))
+}
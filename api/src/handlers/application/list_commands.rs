@@ -0,0 +1,106 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::interaction::ports::InteractionService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{guild::GuildId, interaction::ApplicationCommand};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications/{application_id}/commands")]
+pub struct ListGlobalCommandsRoute {
+    pub application_id: Uuid,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications/{application_id}/guilds/{guild_id}/commands")]
+pub struct ListApplicationGuildCommandsRoute {
+    pub application_id: Uuid,
+    pub guild_id: Uuid,
+}
+
+async fn list_commands(
+    state: AppState,
+    identity: Identity,
+    application_id: Uuid,
+    guild_id: Option<Uuid>,
+) -> Result<Response<Vec<ApplicationCommand>>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let commands = state
+        .interaction_service
+        .list_commands(user.id.0, application_id, guild_id.map(GuildId::from))
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(commands))
+}
+
+#[utoipa::path(
+    get,
+    path = "/applications/{application_id}/commands",
+    tag = "commands",
+    summary = "List global commands",
+    description = "Lists the application's global commands. Allowed for the application's owner and its bot.",
+    params(
+        ("application_id" = Uuid, Path, description = "Application ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<ApplicationCommand>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Application not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_global_commands_handler(
+    ListGlobalCommandsRoute { application_id }: ListGlobalCommandsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<ApplicationCommand>>, ApiError> {
+    list_commands(state, identity, application_id, None).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/applications/{application_id}/guilds/{guild_id}/commands",
+    tag = "commands",
+    summary = "List guild commands",
+    description = "Lists the application's commands registered for one guild. Allowed for the application's owner and its bot.",
+    params(
+        ("application_id" = Uuid, Path, description = "Application ID"),
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<ApplicationCommand>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Application not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_application_guild_commands_handler(
+    ListApplicationGuildCommandsRoute {
+        application_id,
+        guild_id,
+    }: ListApplicationGuildCommandsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<ApplicationCommand>>, ApiError> {
+    list_commands(state, identity, application_id, Some(guild_id)).await
+}
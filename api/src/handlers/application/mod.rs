@@ -0,0 +1,45 @@
+use axum::Router;
+use axum_extra::routing::RouterExt;
+
+use crate::{
+    handlers::application::{
+        create_application::create_application_handler,
+        create_command::{create_global_command_handler, create_guild_command_handler},
+        delete_application::delete_application_handler,
+        delete_command::delete_command_handler,
+        get_application::get_application_handler,
+        list_applications::list_applications_handler,
+        list_commands::{list_application_guild_commands_handler, list_global_commands_handler},
+        reset_bot_token::reset_bot_token_handler,
+        set_interactions_endpoint::set_interactions_endpoint_handler,
+        update_application::update_application_handler,
+    },
+    state::AppState,
+};
+
+pub mod create_application;
+pub mod create_command;
+pub mod delete_application;
+pub mod delete_command;
+pub mod get_application;
+pub mod list_applications;
+pub mod list_commands;
+pub mod reset_bot_token;
+pub mod set_interactions_endpoint;
+pub mod update_application;
+
+pub fn application_routes(_state: AppState) -> Router<AppState> {
+    Router::new()
+        .typed_post(create_application_handler)
+        .typed_get(list_applications_handler)
+        .typed_get(get_application_handler)
+        .typed_patch(update_application_handler)
+        .typed_post(reset_bot_token_handler)
+        .typed_delete(delete_application_handler)
+        .typed_put(set_interactions_endpoint_handler)
+        .typed_post(create_global_command_handler)
+        .typed_post(create_guild_command_handler)
+        .typed_get(list_global_commands_handler)
+        .typed_get(list_application_guild_commands_handler)
+        .typed_delete(delete_command_handler)
+}
@@ -0,0 +1,74 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::interaction::ports::InteractionService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::application::Application;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications/{application_id}/interactions-endpoint")]
+pub struct SetInteractionsEndpointRoute {
+    pub application_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SetInteractionsEndpointRequest {
+    /// `null` or empty to receive interactions over the gateway again.
+    pub url: Option<String>,
+}
+
+#[utoipa::path(
+    put,
+    path = "/applications/{application_id}/interactions-endpoint",
+    tag = "applications",
+    summary = "Set the interactions endpoint",
+    description = "Has interactions POSTed to an HTTP endpoint instead of the bot's gateway connection. The endpoint must be a public address on the default port. Requests are signed with the application's `interactions_secret` like event deliveries, and the endpoint must answer a `ping` with a 2xx before it is saved. It may answer an interaction in its response body, within 3 seconds. Owner only.",
+    params(
+        ("application_id" = Uuid, Path, description = "Application ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = SetInteractionsEndpointRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = Application),
+        (status = 400, description = "Invalid URL or the endpoint did not answer the ping", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Application not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn set_interactions_endpoint_handler(
+    SetInteractionsEndpointRoute { application_id }: SetInteractionsEndpointRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<SetInteractionsEndpointRequest>,
+) -> Result<Response<Application>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let application = state
+        .interaction_service
+        .set_interactions_endpoint(user.id.0, application_id, req.url)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(application))
+}
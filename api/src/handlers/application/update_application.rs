@@ -0,0 +1,84 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::application::ports::{ApplicationService, UpdateApplicationInput},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::application::Application;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/applications/{application_id}")]
+pub struct UpdateApplicationRoute {
+    pub application_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateApplicationRequest {
+    /// Renames the bot user too.
+    pub name: Option<String>,
+    /// An empty string clears the description.
+    pub description: Option<String>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/applications/{application_id}",
+    tag = "applications",
+    summary = "Update an application",
+    description = "Changes the name or description of one of the caller's applications. Renaming it renames its bot user.",
+    params(
+        ("application_id" = Uuid, Path, description = "Application ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = UpdateApplicationRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = Application),
+        (status = 400, description = "Invalid or taken name", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Application not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn update_application_handler(
+    UpdateApplicationRoute { application_id }: UpdateApplicationRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<UpdateApplicationRequest>,
+) -> Result<Response<Application>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let application = state
+        .application_service
+        .update_application(
+            user.id.0,
+            application_id,
+            UpdateApplicationInput {
+                name: req.name,
+                description: req.description,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(application))
+}
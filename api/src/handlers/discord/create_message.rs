@@ -0,0 +1,65 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::message::ports::{EncryptionMeta, MessageService};
+use ferriscord_entities::{Id, embed::Embed, guild::GuildId};
+use serde::Deserialize;
+
+use super::{
+    DiscordError, inbound_content,
+    models::{DiscordMessage, discord_messages},
+    visible_channel,
+};
+use crate::{handlers::guild::channel::send_message::publish_new_message, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/channels/{channel_id}/messages")]
+pub struct CreateMessageRoute {
+    channel_id: String,
+}
+
+/// The JSON form of Discord's create message. Fields FerrisCord has no
+/// counterpart for, such as `tts` or `nonce`, are ignored.
+#[derive(Deserialize)]
+pub struct CreateMessageRequest {
+    #[serde(default)]
+    content: String,
+    #[serde(default)]
+    embeds: Vec<Embed>,
+    /// Single embed of API versions before v8, still sent by some bots.
+    embed: Option<Embed>,
+}
+
+pub async fn create_message_handler(
+    CreateMessageRoute { channel_id }: CreateMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<CreateMessageRequest>,
+) -> Result<Json<DiscordMessage>, DiscordError> {
+    let (guild_id, channel) = visible_channel(&state, &identity, &channel_id).await?;
+    let content = inbound_content(&state, &req.content).await?;
+    let mut embeds = req.embeds;
+    embeds.extend(req.embed);
+
+    let guild_id = GuildId(Id(guild_id));
+    let message = state
+        .message_service
+        .send_message(
+            identity.clone(),
+            guild_id.clone(),
+            channel.id,
+            content,
+            embeds,
+            Vec::new(),
+            EncryptionMeta::default(),
+            None,
+        )
+        .await?;
+    publish_new_message(&state, identity, &guild_id, &message).await;
+
+    discord_messages(&state, *guild_id.get_uuid(), &[message])
+        .await?
+        .pop()
+        .map(Json)
+        .ok_or_else(DiscordError::unknown_message)
+}
@@ -0,0 +1,55 @@
+use axum::{Extension, extract::State, http::StatusCode};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::message::ports::MessageService;
+use ferriscord_entities::{Id, event_subscription::GuildEventType, guild::GuildId};
+use serde::Deserialize;
+
+use super::{DiscordError, resolve, visible_channel};
+use crate::{crossposts::publish_crosspost_deletes, events::dispatch_guild_event, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/channels/{channel_id}/messages/{message_id}")]
+pub struct DeleteMessageRoute {
+    channel_id: String,
+    message_id: String,
+}
+
+pub async fn delete_message_handler(
+    DeleteMessageRoute {
+        channel_id,
+        message_id,
+    }: DeleteMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, DiscordError> {
+    let (guild_id, channel) = visible_channel(&state, &identity, &channel_id).await?;
+    let message_id = resolve(&state, &message_id, DiscordError::unknown_message).await?;
+    let channel_id = channel.id.get_uuid();
+
+    let copies = state
+        .message_service
+        .delete_message(identity, GuildId(Id(guild_id)), channel.id, message_id)
+        .await?;
+
+    let room = format!("channel:{}", channel_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "message.delete",
+        "room": room,
+        "data": { "message_id": message_id, "channel_id": channel_id },
+    })) {
+        state.hub.publish(&room, payload).await;
+    }
+
+    dispatch_guild_event(
+        &state,
+        guild_id,
+        GuildEventType::MessageDelete,
+        serde_json::json!({ "message_id": message_id, "channel_id": channel_id }),
+    )
+    .await;
+
+    publish_crosspost_deletes(&state, &copies).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
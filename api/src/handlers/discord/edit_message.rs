@@ -0,0 +1,69 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::message::ports::MessageService;
+use ferriscord_entities::{Id, guild::GuildId};
+use serde::Deserialize;
+
+use super::{
+    DiscordError, inbound_content,
+    models::{DiscordMessage, discord_messages},
+    resolve, visible_channel,
+};
+use crate::{
+    crossposts::{presign_attachments, publish_crosspost_updates, publish_message_update},
+    link_previews::spawn_previews,
+    state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/channels/{channel_id}/messages/{message_id}")]
+pub struct EditMessageRoute {
+    channel_id: String,
+    message_id: String,
+}
+
+/// Only the content of a message can be edited.
+#[derive(Deserialize)]
+pub struct EditMessageRequest {
+    content: Option<String>,
+}
+
+pub async fn edit_message_handler(
+    EditMessageRoute {
+        channel_id,
+        message_id,
+    }: EditMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<EditMessageRequest>,
+) -> Result<Json<DiscordMessage>, DiscordError> {
+    let (guild_id, channel) = visible_channel(&state, &identity, &channel_id).await?;
+    let message_id = resolve(&state, &message_id, DiscordError::unknown_message).await?;
+    let content = req
+        .content
+        .ok_or_else(|| DiscordError::invalid_form_body("only `content` can be edited"))?;
+    let content = inbound_content(&state, &content).await?;
+
+    let (mut message, crossposts) = state
+        .message_service
+        .edit_message(
+            identity.clone(),
+            GuildId(Id(guild_id)),
+            channel.id,
+            message_id,
+            content,
+        )
+        .await?;
+
+    presign_attachments(&state, &mut message).await;
+    publish_message_update(&state, guild_id, &message).await;
+    publish_crosspost_updates(&state, crossposts).await;
+    spawn_previews(&state, Some(identity), guild_id, &message);
+
+    discord_messages(&state, guild_id, &[message])
+        .await?
+        .pop()
+        .map(Json)
+        .ok_or_else(DiscordError::unknown_message)
+}
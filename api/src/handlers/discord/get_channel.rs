@@ -0,0 +1,30 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use serde::Deserialize;
+
+use super::{
+    DiscordError,
+    models::{DiscordChannel, discord_channels},
+    visible_channel,
+};
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/channels/{channel_id}")]
+pub struct GetChannelRoute {
+    channel_id: String,
+}
+
+pub async fn get_channel_handler(
+    GetChannelRoute { channel_id }: GetChannelRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<DiscordChannel>, DiscordError> {
+    let (guild_id, channel) = visible_channel(&state, &identity, &channel_id).await?;
+    let mut channels = discord_channels(&state, &identity, guild_id, &[channel]).await?;
+    channels
+        .pop()
+        .map(Json)
+        .ok_or_else(DiscordError::unknown_channel)
+}
@@ -0,0 +1,52 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::{DiscordError, Snowflakes, current_user, models::DiscordUser};
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/oauth2/applications/@me")]
+pub struct GetCurrentApplicationRoute;
+
+#[derive(Serialize)]
+pub struct DiscordApplication {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub bot_public: bool,
+    pub bot_require_code_grant: bool,
+    pub verify_key: String,
+    pub flags: u64,
+    pub bot: DiscordUser,
+}
+
+/// The application of the calling bot. Only bots have one.
+pub async fn get_current_application_handler(
+    _: GetCurrentApplicationRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<DiscordApplication>, DiscordError> {
+    let Identity::Bot(bot) = &identity else {
+        return Err(DiscordError::unknown_application());
+    };
+    let application_id: Uuid = bot
+        .application_id
+        .parse()
+        .map_err(|_| DiscordError::unknown_application())?;
+    let user = current_user(&state, &identity).await?;
+    let snowflakes = Snowflakes::load(&state, [application_id, user.id.0]).await?;
+
+    Ok(Json(DiscordApplication {
+        id: snowflakes.get(application_id),
+        name: bot.username.clone(),
+        description: String::new(),
+        bot_public: false,
+        bot_require_code_grant: false,
+        verify_key: String::new(),
+        flags: 0,
+        bot: DiscordUser::from_user(&snowflakes, &user),
+    }))
+}
@@ -0,0 +1,21 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use serde::Deserialize;
+
+use super::{DiscordError, Snowflakes, current_user, models::DiscordUser};
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/users/@me")]
+pub struct GetCurrentUserRoute;
+
+pub async fn get_current_user_handler(
+    _: GetCurrentUserRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<DiscordUser>, DiscordError> {
+    let user = current_user(&state, &identity).await?;
+    let snowflakes = Snowflakes::load(&state, [user.id.0]).await?;
+    Ok(Json(DiscordUser::from_user(&snowflakes, &user)))
+}
@@ -0,0 +1,38 @@
+use axum::{Json, http::HeaderMap};
+use axum_extra::routing::TypedPath;
+use serde::{Deserialize, Serialize};
+
+use super::GATEWAY_PATH;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/gateway")]
+pub struct GetGatewayRoute;
+
+#[derive(Serialize)]
+pub struct GatewayResponse {
+    pub url: String,
+}
+
+/// The gateway's URL, on the host the request was sent to. Discord
+/// libraries append their own query string.
+pub(crate) fn gateway_url(headers: &HeaderMap) -> String {
+    let host = headers
+        .get("x-forwarded-host")
+        .or_else(|| headers.get("host"))
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("localhost");
+    let scheme = match headers
+        .get("x-forwarded-proto")
+        .and_then(|proto| proto.to_str().ok())
+    {
+        Some("https") => "wss",
+        _ => "ws",
+    };
+    format!("{}://{}{}", scheme, host, GATEWAY_PATH)
+}
+
+pub async fn get_gateway_handler(_: GetGatewayRoute, headers: HeaderMap) -> Json<GatewayResponse> {
+    Json(GatewayResponse {
+        url: gateway_url(&headers),
+    })
+}
@@ -0,0 +1,43 @@
+use axum::{Json, http::HeaderMap};
+use axum_extra::routing::TypedPath;
+use serde::{Deserialize, Serialize};
+
+use super::get_gateway::gateway_url;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/gateway/bot")]
+pub struct GetGatewayBotRoute;
+
+#[derive(Serialize)]
+pub struct SessionStartLimit {
+    pub total: u32,
+    pub remaining: u32,
+    pub reset_after: u64,
+    pub max_concurrency: u32,
+}
+
+#[derive(Serialize)]
+pub struct GatewayBotResponse {
+    pub url: String,
+    pub shards: u32,
+    pub session_start_limit: SessionStartLimit,
+}
+
+/// The gateway isn't sharded and doesn't limit identifies beyond its
+/// connection rate limit, so the answer is always one shard and a full
+/// budget.
+pub async fn get_gateway_bot_handler(
+    _: GetGatewayBotRoute,
+    headers: HeaderMap,
+) -> Json<GatewayBotResponse> {
+    Json(GatewayBotResponse {
+        url: gateway_url(&headers),
+        shards: 1,
+        session_start_limit: SessionStartLimit {
+            total: 1000,
+            remaining: 1000,
+            reset_after: 0,
+            max_concurrency: 1,
+        },
+    })
+}
@@ -0,0 +1,33 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::channel::ports::ChannelService;
+use serde::Deserialize;
+
+use super::{
+    DiscordError, member_guild,
+    models::{DiscordChannel, discord_channels},
+};
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/guilds/{guild_id}/channels")]
+pub struct GetGuildChannelsRoute {
+    guild_id: String,
+}
+
+/// The channels of the guild the caller can view.
+pub async fn get_guild_channels_handler(
+    GetGuildChannelsRoute { guild_id }: GetGuildChannelsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<Vec<DiscordChannel>>, DiscordError> {
+    let guild = member_guild(&state, &identity, &guild_id).await?;
+    let channels = state
+        .channel_service
+        .get_guild_channels(identity.clone(), guild.id.clone())
+        .await?;
+    Ok(Json(
+        discord_channels(&state, &identity, *guild.id.get_uuid(), &channels).await?,
+    ))
+}
@@ -0,0 +1,34 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::member::ports::MemberRepository;
+use serde::Deserialize;
+
+use super::{DiscordError, Snowflakes, member_guild, models::DiscordMember, resolve};
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/guilds/{guild_id}/members/{user_id}")]
+pub struct GetMemberRoute {
+    guild_id: String,
+    user_id: String,
+}
+
+pub async fn get_member_handler(
+    GetMemberRoute { guild_id, user_id }: GetMemberRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<DiscordMember>, DiscordError> {
+    let guild = member_guild(&state, &identity, &guild_id).await?;
+    let user_id = resolve(&state, &user_id, DiscordError::unknown_user).await?;
+
+    let member = state
+        .member_repository
+        .list_members(&guild.id)
+        .await?
+        .into_iter()
+        .find(|member| member.user_id == user_id)
+        .ok_or_else(DiscordError::unknown_member)?;
+    let snowflakes = Snowflakes::load(&state, DiscordMember::ids(&member)).await?;
+    Ok(Json(DiscordMember::new(&snowflakes, &member)))
+}
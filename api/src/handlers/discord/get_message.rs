@@ -0,0 +1,44 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::message::ports::MessageService;
+use ferriscord_entities::{Id, guild::GuildId};
+use serde::Deserialize;
+
+use super::{
+    DiscordError,
+    models::{DiscordMessage, discord_messages},
+    resolve, visible_channel,
+};
+use crate::{crossposts::presign_attachments, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/channels/{channel_id}/messages/{message_id}")]
+pub struct GetMessageRoute {
+    channel_id: String,
+    message_id: String,
+}
+
+pub async fn get_message_handler(
+    GetMessageRoute {
+        channel_id,
+        message_id,
+    }: GetMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<DiscordMessage>, DiscordError> {
+    let (guild_id, channel) = visible_channel(&state, &identity, &channel_id).await?;
+    let message_id = resolve(&state, &message_id, DiscordError::unknown_message).await?;
+
+    let mut message = state
+        .message_service
+        .get_message(identity, GuildId(Id(guild_id)), channel.id, message_id)
+        .await?;
+    presign_attachments(&state, &mut message).await;
+
+    discord_messages(&state, guild_id, &[message])
+        .await?
+        .pop()
+        .map(Json)
+        .ok_or_else(DiscordError::unknown_message)
+}
@@ -0,0 +1,73 @@
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::message::ports::MessageService;
+use ferriscord_entities::{Id, guild::GuildId, message::MessageId};
+use serde::Deserialize;
+
+use super::{
+    DiscordError,
+    models::{DiscordMessage, discord_messages},
+    resolve, visible_channel,
+};
+use crate::{crossposts::presign_attachments, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/channels/{channel_id}/messages")]
+pub struct GetMessagesRoute {
+    channel_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct GetMessagesQuery {
+    before: Option<String>,
+    after: Option<String>,
+    around: Option<String>,
+    limit: Option<u32>,
+}
+
+/// Pages backwards from `before`, newest first like Discord. Paging with
+/// `after` or `around` isn't supported by the message store.
+pub async fn get_messages_handler(
+    GetMessagesRoute { channel_id }: GetMessagesRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Query(query): Query<GetMessagesQuery>,
+) -> Result<Json<Vec<DiscordMessage>>, DiscordError> {
+    if query.after.is_some() || query.around.is_some() {
+        return Err(DiscordError::invalid_form_body(
+            "only `before` is supported to page through messages",
+        ));
+    }
+    let limit = query.limit.unwrap_or(50);
+    if !(1..=100).contains(&limit) {
+        return Err(DiscordError::invalid_form_body(
+            "limit must be between 1 and 100",
+        ));
+    }
+
+    let (guild_id, channel) = visible_channel(&state, &identity, &channel_id).await?;
+    let before = match query.before {
+        Some(before) => Some(MessageId(Id(resolve(
+            &state,
+            &before,
+            DiscordError::unknown_message,
+        )
+        .await?))),
+        None => None,
+    };
+
+    let mut messages = state
+        .message_service
+        .get_channel_messages(identity, GuildId(Id(guild_id)), channel.id, before, limit)
+        .await?;
+    messages.reverse();
+    for message in &mut messages {
+        presign_attachments(&state, message).await;
+    }
+
+    Ok(Json(discord_messages(&state, guild_id, &messages).await?))
+}
@@ -0,0 +1,34 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use serde::Deserialize;
+
+use super::{
+    DiscordError, guild_roles, member_guild,
+    models::{DiscordGuild, DiscordRole, guild_snowflakes},
+};
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/guilds/{guild_id}/roles")]
+pub struct GetRolesRoute {
+    guild_id: String,
+}
+
+pub async fn get_roles_handler(
+    GetRolesRoute { guild_id }: GetRolesRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Json<Vec<DiscordRole>>, DiscordError> {
+    let guild = member_guild(&state, &identity, &guild_id).await?;
+    let guild_id = *guild.id.get_uuid();
+    let roles = guild_roles(&state, &identity, guild_id).await?;
+    let snowflakes =
+        guild_snowflakes(&state, guild_id, &roles, DiscordGuild::ids(&guild, &roles)).await?;
+    Ok(Json(
+        roles
+            .iter()
+            .map(|role| DiscordRole::new(&snowflakes, guild_id, role))
+            .collect(),
+    ))
+}
@@ -0,0 +1,55 @@
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::member::ports::MemberRepository;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::{DiscordError, Snowflakes, member_guild, models::DiscordMember};
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/guilds/{guild_id}/members")]
+pub struct ListMembersRoute {
+    guild_id: String,
+}
+
+#[derive(Deserialize)]
+pub struct ListMembersQuery {
+    limit: Option<usize>,
+    after: Option<u64>,
+}
+
+/// Members ordered by user id, paged with `after` like on Discord.
+pub async fn list_members_handler(
+    ListMembersRoute { guild_id }: ListMembersRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Query(query): Query<ListMembersQuery>,
+) -> Result<Json<Vec<DiscordMember>>, DiscordError> {
+    let limit = query.limit.unwrap_or(1);
+    if !(1..=1000).contains(&limit) {
+        return Err(DiscordError::invalid_form_body(
+            "limit must be between 1 and 1000",
+        ));
+    }
+
+    let guild = member_guild(&state, &identity, &guild_id).await?;
+    let mut members = state.member_repository.list_members(&guild.id).await?;
+    let ids: Vec<Uuid> = members.iter().flat_map(DiscordMember::ids).collect();
+    let snowflakes = Snowflakes::load(&state, ids).await?;
+
+    let after = query.after.unwrap_or(0);
+    members.retain(|member| snowflakes.value(member.user_id) > after);
+    members.sort_by_key(|member| snowflakes.value(member.user_id));
+    Ok(Json(
+        members
+            .iter()
+            .take(limit)
+            .map(|member| DiscordMember::new(&snowflakes, member))
+            .collect(),
+    ))
+}
@@ -0,0 +1,388 @@
+//! A Discord-compatible API under `/compat/discord/v10`, so that bots written
+//! for Discord run against FerrisCord with only their base URL changed. The
+//! routes cover channels, messages, members and roles, and translate onto
+//! the regular services: permissions, events and side effects are the same
+//! as through the native API.
+//!
+//! Ids are exposed as snowflakes handed out on first sight, and mentions in
+//! message content are rewritten both ways. These routes follow Discord's
+//! documentation rather than FerrisCord's, and are left out of the OpenAPI
+//! document.
+
+use std::collections::HashMap;
+
+use axum::{
+    Json, Router,
+    http::StatusCode,
+    middleware,
+    response::{IntoResponse, Response},
+};
+use axum_extra::routing::RouterExt;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::{
+        channel::ports::ChannelService,
+        discord_compat::{
+            mentions::{mentioned_ids, rewrite_mentions},
+            ports::DiscordCompatService,
+        },
+        errors::CoreError,
+        guild::ports::GuildService,
+        role::{entities::FindRolesInput, ports::RoleService},
+    },
+    user::domain::{
+        common::CoreError as UserCoreError,
+        user::{User, ports::UserService},
+    },
+};
+use ferriscord_entities::{
+    Id,
+    channel::{Channel, ChannelId},
+    guild::{Guild, GuildId},
+    role::{Role, RoleId},
+    user::UserId,
+};
+use ferriscord_error::ApiError;
+use uuid::Uuid;
+
+use crate::{
+    handlers::{
+        discord::{
+            add_member_role::add_member_role_handler, create_message::create_message_handler,
+            delete_message::delete_message_handler, edit_message::edit_message_handler,
+            get_channel::get_channel_handler,
+            get_current_application::get_current_application_handler,
+            get_current_user::get_current_user_handler, get_gateway::get_gateway_handler,
+            get_gateway_bot::get_gateway_bot_handler, get_guild::get_guild_handler,
+            get_guild_channels::get_guild_channels_handler, get_member::get_member_handler,
+            get_message::get_message_handler, get_messages::get_messages_handler,
+            get_roles::get_roles_handler, list_members::list_members_handler,
+            remove_member_role::remove_member_role_handler, trigger_typing::trigger_typing_handler,
+        },
+        map_core_error, service_auth_middleware,
+    },
+    state::AppState,
+};
+
+pub mod add_member_role;
+pub mod create_message;
+pub mod delete_message;
+pub mod edit_message;
+pub mod get_channel;
+pub mod get_current_application;
+pub mod get_current_user;
+pub mod get_gateway;
+pub mod get_gateway_bot;
+pub mod get_guild;
+pub mod get_guild_channels;
+pub mod get_member;
+pub mod get_message;
+pub mod get_messages;
+pub mod get_roles;
+pub mod list_members;
+pub mod models;
+pub mod remove_member_role;
+pub mod trigger_typing;
+
+/// Path of the gateway, which Discord libraries get from `GET /gateway/bot`.
+pub(crate) const GATEWAY_PATH: &str = "/compat/discord/gateway";
+
+pub fn discord_routes(state: AppState) -> Router<AppState> {
+    let authenticated = Router::new()
+        .typed_get(get_gateway_bot_handler)
+        .typed_get(get_current_user_handler)
+        .typed_get(get_current_application_handler)
+        .typed_get(get_channel_handler)
+        .typed_get(get_messages_handler)
+        .typed_post(create_message_handler)
+        .typed_get(get_message_handler)
+        .typed_patch(edit_message_handler)
+        .typed_delete(delete_message_handler)
+        .typed_post(trigger_typing_handler)
+        .typed_get(get_guild_handler)
+        .typed_get(get_guild_channels_handler)
+        .typed_get(list_members_handler)
+        .typed_get(get_member_handler)
+        .typed_get(get_roles_handler)
+        .typed_put(add_member_role_handler)
+        .typed_delete(remove_member_role_handler)
+        .layer(middleware::from_fn_with_state(
+            state,
+            service_auth_middleware,
+        ));
+
+    // Discord serves the gateway URL without a token.
+    Router::new()
+        .typed_get(get_gateway_handler)
+        .merge(authenticated)
+}
+
+// ─── Errors ──────────────────────────────────────────────────────────────────
+
+/// An error in Discord's format, with one of its JSON error codes.
+#[derive(Debug)]
+pub struct DiscordError {
+    status: StatusCode,
+    code: u32,
+    message: String,
+}
+
+impl DiscordError {
+    fn new(status: StatusCode, code: u32, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn unknown_application() -> Self {
+        Self::new(StatusCode::NOT_FOUND, 10002, "Unknown Application")
+    }
+
+    pub(crate) fn unknown_channel() -> Self {
+        Self::new(StatusCode::NOT_FOUND, 10003, "Unknown Channel")
+    }
+
+    pub(crate) fn unknown_guild() -> Self {
+        Self::new(StatusCode::NOT_FOUND, 10004, "Unknown Guild")
+    }
+
+    pub(crate) fn unknown_member() -> Self {
+        Self::new(StatusCode::NOT_FOUND, 10007, "Unknown Member")
+    }
+
+    pub(crate) fn unknown_message() -> Self {
+        Self::new(StatusCode::NOT_FOUND, 10008, "Unknown Message")
+    }
+
+    pub(crate) fn unknown_role() -> Self {
+        Self::new(StatusCode::NOT_FOUND, 10011, "Unknown Role")
+    }
+
+    pub(crate) fn unknown_user() -> Self {
+        Self::new(StatusCode::NOT_FOUND, 10013, "Unknown User")
+    }
+
+    pub(crate) fn invalid_form_body(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, 50035, message)
+    }
+}
+
+impl IntoResponse for DiscordError {
+    fn into_response(self) -> Response {
+        let body = serde_json::json!({ "message": self.message, "code": self.code });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+impl From<ApiError> for DiscordError {
+    fn from(error: ApiError) -> Self {
+        match error {
+            ApiError::Forbidden { message } => Self::new(StatusCode::FORBIDDEN, 50013, message),
+            ApiError::NotFound { message } => Self::new(StatusCode::NOT_FOUND, 0, message),
+            ApiError::BadRequest { message } => Self::invalid_form_body(message),
+            ApiError::TooManyRequests { message } => {
+                Self::new(StatusCode::TOO_MANY_REQUESTS, 0, message)
+            }
+            ApiError::TokenNotFound | ApiError::InvalidToken { .. } => {
+                Self::new(StatusCode::UNAUTHORIZED, 0, "401: Unauthorized")
+            }
+            ApiError::Unknown { message } => {
+                Self::new(StatusCode::INTERNAL_SERVER_ERROR, 0, message)
+            }
+        }
+    }
+}
+
+impl From<UserCoreError> for DiscordError {
+    fn from(error: UserCoreError) -> Self {
+        match error {
+            UserCoreError::NotFound => Self::unknown_user(),
+            error => Self::new(StatusCode::INTERNAL_SERVER_ERROR, 0, error.to_string()),
+        }
+    }
+}
+
+impl From<CoreError> for DiscordError {
+    fn from(error: CoreError) -> Self {
+        match error {
+            CoreError::ChannelNotFound { .. } => Self::unknown_channel(),
+            CoreError::GuildNotFound { .. } => Self::unknown_guild(),
+            CoreError::MessageNotFound => Self::unknown_message(),
+            CoreError::NotGuildMember => Self::new(StatusCode::FORBIDDEN, 50001, "Missing Access"),
+            CoreError::InsufficientPermissions => {
+                Self::new(StatusCode::FORBIDDEN, 50013, "Missing Permissions")
+            }
+            error => map_core_error(error).into(),
+        }
+    }
+}
+
+// ─── Ids ─────────────────────────────────────────────────────────────────────
+
+/// The snowflakes of the ids in a response. An @everyone role takes the
+/// snowflake of its guild, as on Discord.
+pub(crate) struct Snowflakes {
+    snowflakes: HashMap<Uuid, u64>,
+    aliases: HashMap<Uuid, Uuid>,
+}
+
+impl Snowflakes {
+    pub(crate) async fn load(
+        state: &AppState,
+        ids: impl IntoIterator<Item = Uuid>,
+    ) -> Result<Self, DiscordError> {
+        let ids: Vec<Uuid> = ids.into_iter().collect();
+        Ok(Self {
+            snowflakes: state.discord_compat_service.snowflakes(&ids).await?,
+            aliases: HashMap::new(),
+        })
+    }
+
+    /// Gives the snowflake of `to` to `id`, which must have been loaded.
+    pub(crate) fn alias(mut self, id: Uuid, to: Uuid) -> Self {
+        self.aliases.insert(id, to);
+        self
+    }
+
+    /// The snowflake of `id` as a string, the way Discord sends them.
+    pub(crate) fn get(&self, id: Uuid) -> String {
+        self.value(id).to_string()
+    }
+
+    pub(crate) fn value(&self, id: Uuid) -> u64 {
+        let id = self.aliases.get(&id).unwrap_or(&id);
+        // Ids are loaded before use; a missing one lost two draws in a row.
+        self.snowflakes.get(id).copied().unwrap_or_default()
+    }
+}
+
+/// The id behind a snowflake of the path, or `not_found`.
+pub(crate) async fn resolve(
+    state: &AppState,
+    snowflake: &str,
+    not_found: fn() -> DiscordError,
+) -> Result<Uuid, DiscordError> {
+    let snowflake: u64 = snowflake.parse().map_err(|_| not_found())?;
+    state
+        .discord_compat_service
+        .resolve(&[snowflake])
+        .await?
+        .remove(&snowflake)
+        .ok_or_else(not_found)
+}
+
+/// Rewrites the snowflakes of mentions in content sent by a bot to the ids
+/// they stand for. Unknown snowflakes are left as they are.
+pub(crate) async fn inbound_content(
+    state: &AppState,
+    content: &str,
+) -> Result<String, DiscordError> {
+    let snowflakes: Vec<u64> = mentioned_ids(content)
+        .into_iter()
+        .filter_map(|(_, id)| id.parse().ok())
+        .collect();
+    if snowflakes.is_empty() {
+        return Ok(content.to_string());
+    }
+    let ids = state.discord_compat_service.resolve(&snowflakes).await?;
+    Ok(rewrite_mentions(content, |_, id| {
+        let snowflake: u64 = id.parse().ok()?;
+        ids.get(&snowflake).map(Uuid::to_string)
+    }))
+}
+
+// ─── Lookups ─────────────────────────────────────────────────────────────────
+
+/// The user behind the caller.
+pub(crate) async fn current_user(
+    state: &AppState,
+    identity: &Identity,
+) -> Result<User, DiscordError> {
+    state
+        .user_service
+        .get_me(identity.id())
+        .await?
+        .ok_or_else(DiscordError::unknown_user)
+}
+
+/// A guild of the caller, by snowflake.
+pub(crate) async fn member_guild(
+    state: &AppState,
+    identity: &Identity,
+    guild_id: &str,
+) -> Result<Guild, DiscordError> {
+    let guild_id = resolve(state, guild_id, DiscordError::unknown_guild).await?;
+    let user = current_user(state, identity).await?;
+    state
+        .guild_service
+        .get_user_guilds(identity.clone(), UserId(Id(user.id.0)))
+        .await?
+        .into_iter()
+        .find(|guild| *guild.id.get_uuid() == guild_id)
+        .ok_or_else(DiscordError::unknown_guild)
+}
+
+/// A guild channel the caller can view, by snowflake, and its guild.
+pub(crate) async fn visible_channel(
+    state: &AppState,
+    identity: &Identity,
+    channel_id: &str,
+) -> Result<(Uuid, Channel), DiscordError> {
+    let channel_id = resolve(state, channel_id, DiscordError::unknown_channel).await?;
+    let guild_id = state
+        .discord_compat_service
+        .channel_guild(channel_id)
+        .await?;
+    let channel = state
+        .channel_service
+        .get_channel(
+            identity.clone(),
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+        )
+        .await?;
+    Ok((guild_id, channel))
+}
+
+/// Whether a role is the @everyone role of its guild, which FerrisCord
+/// stores like any other role.
+pub(crate) fn is_everyone(name: &str) -> bool {
+    name == "@everyone" || name == "everyone"
+}
+
+/// The roles of a guild, @everyone included even when the guild has not
+/// stored one.
+pub(crate) async fn guild_roles(
+    state: &AppState,
+    identity: &Identity,
+    guild_id: Uuid,
+) -> Result<Vec<Role>, DiscordError> {
+    let mut roles = state
+        .role_service
+        .find_roles(
+            identity.clone(),
+            FindRolesInput {
+                guild_id: GuildId(Id(guild_id)),
+                per_page: Some(100),
+                page: Some(1),
+            },
+        )
+        .await?
+        .data;
+    if !roles.iter().any(|role| is_everyone(&role.name)) {
+        let mut everyone = Role::everyone(GuildId(Id(guild_id)));
+        everyone.id = RoleId::from(guild_id);
+        roles.push(everyone);
+    }
+    Ok(roles)
+}
+
+/// The id of the @everyone role among `roles`.
+pub(crate) fn everyone_role(roles: &[Role]) -> Option<Uuid> {
+    roles
+        .iter()
+        .find(|role| is_everyone(&role.name))
+        .map(|role| role.id.0.0)
+}
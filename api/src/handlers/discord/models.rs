@@ -0,0 +1,569 @@
+//! Discord's objects, built from FerrisCord's. Fields FerrisCord has no
+//! counterpart for get Discord's defaults.
+
+use chrono::{DateTime, Utc};
+use ferriscord_auth::{Bot, Identity};
+use ferriscord_core::{
+    guild::domain::{
+        discord_compat::{
+            mentions::{MentionKind, mentioned_ids, rewrite_mentions},
+            permissions::{bits_to_discord, to_discord},
+        },
+        member::ports::MemberWithUser,
+    },
+    user::domain::user::{User, UserId, ports::UserService},
+};
+use ferriscord_entities::{
+    channel::{Channel, OverwriteKind},
+    embed::Embed,
+    guild::Guild,
+    message::Message,
+    role::Role,
+};
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::{DiscordError, Snowflakes, everyone_role, guild_roles, is_everyone};
+use crate::state::AppState;
+
+/// Users mentioned in a message that are looked up for its `mentions`.
+const MAX_MENTIONED_USERS: usize = 25;
+
+// Message flags.
+const CROSSPOSTED: u32 = 1 << 0;
+const IS_CROSSPOST: u32 = 1 << 1;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscordUser {
+    pub id: String,
+    pub username: String,
+    /// Always `"0"`: FerrisCord usernames are unique, like Discord's since
+    /// the discriminators were dropped.
+    pub discriminator: &'static str,
+    pub global_name: Option<String>,
+    /// Discord sends an image hash here; FerrisCord avatars are URLs.
+    pub avatar: Option<String>,
+    pub bot: bool,
+    pub system: bool,
+    pub public_flags: u64,
+    pub flags: u64,
+}
+
+impl DiscordUser {
+    pub fn new(id: String, username: String, global_name: Option<String>, bot: bool) -> Self {
+        Self {
+            id,
+            username,
+            discriminator: "0",
+            global_name,
+            avatar: None,
+            bot,
+            system: false,
+            public_flags: 0,
+            flags: 0,
+        }
+    }
+
+    pub fn from_user(snowflakes: &Snowflakes, user: &User) -> Self {
+        Self::new(
+            snowflakes.get(user.id.0),
+            user.username.clone(),
+            user.display_name.clone(),
+            user.oauth_sub.starts_with(Bot::SUBJECT_PREFIX),
+        )
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscordRole {
+    pub id: String,
+    pub name: String,
+    pub color: u32,
+    pub hoist: bool,
+    pub icon: Option<String>,
+    pub unicode_emoji: Option<String>,
+    pub position: i32,
+    pub permissions: String,
+    pub managed: bool,
+    pub mentionable: bool,
+    pub flags: u64,
+}
+
+impl DiscordRole {
+    /// `guild_id` is the id of the role's guild, which its @everyone role
+    /// takes on Discord.
+    pub fn new(snowflakes: &Snowflakes, guild_id: Uuid, role: &Role) -> Self {
+        let id = if is_everyone(&role.name) {
+            guild_id
+        } else {
+            role.id.0.0
+        };
+        Self {
+            id: snowflakes.get(id),
+            name: role.name.clone(),
+            color: role.color,
+            hoist: role.hoist,
+            icon: None,
+            unicode_emoji: None,
+            position: role.position,
+            permissions: to_discord(role.permissions).to_string(),
+            managed: false,
+            mentionable: role.mentionable,
+            flags: 0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscordOverwrite {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: u8,
+    pub allow: String,
+    pub deny: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscordChannel {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: i16,
+    pub guild_id: Option<String>,
+    pub position: i32,
+    pub permission_overwrites: Vec<DiscordOverwrite>,
+    pub name: String,
+    pub topic: Option<String>,
+    pub nsfw: bool,
+    pub last_message_id: Option<String>,
+    pub bitrate: Option<u32>,
+    pub user_limit: Option<u32>,
+    pub rate_limit_per_user: u32,
+    pub parent_id: Option<String>,
+    pub last_pin_timestamp: Option<DateTime<Utc>>,
+    pub rtc_region: Option<String>,
+    pub flags: u32,
+}
+
+impl DiscordChannel {
+    /// Overwrites of the @everyone role point at the guild when
+    /// `snowflakes` come from [`guild_snowflakes`].
+    pub fn new(snowflakes: &Snowflakes, channel: &Channel) -> Self {
+        Self {
+            id: snowflakes.get(channel.id.get_uuid()),
+            kind: channel.kind.into(),
+            guild_id: channel
+                .guild_id
+                .as_ref()
+                .map(|guild_id| snowflakes.get(*guild_id.get_uuid())),
+            position: channel.position,
+            permission_overwrites: channel
+                .permission_overwrites
+                .iter()
+                .map(|overwrite| DiscordOverwrite {
+                    id: snowflakes.get(overwrite.id),
+                    kind: match overwrite.kind {
+                        OverwriteKind::Role => 0,
+                        OverwriteKind::Member => 1,
+                    },
+                    allow: bits_to_discord(overwrite.allow).to_string(),
+                    deny: bits_to_discord(overwrite.deny).to_string(),
+                })
+                .collect(),
+            name: channel.name.clone(),
+            topic: channel.topic.clone(),
+            nsfw: channel.nsfw,
+            last_message_id: channel.last_message_id.map(|id| snowflakes.get(id)),
+            bitrate: channel.bitrate,
+            user_limit: channel.user_limit,
+            rate_limit_per_user: channel.rate_limit_per_user,
+            parent_id: channel
+                .parent_id
+                .as_ref()
+                .map(|parent_id| snowflakes.get(parent_id.get_uuid())),
+            last_pin_timestamp: channel.last_pin_timestamp,
+            rtc_region: channel.rtc_region.clone(),
+            flags: channel.flags.0,
+        }
+    }
+
+    /// The ids a channel refers to.
+    pub fn ids(channel: &Channel) -> Vec<Uuid> {
+        [channel.id.get_uuid()]
+            .into_iter()
+            .chain(channel.guild_id.as_ref().map(|id| *id.get_uuid()))
+            .chain(channel.last_message_id)
+            .chain(channel.parent_id.as_ref().map(|id| id.get_uuid()))
+            .chain(channel.permission_overwrites.iter().map(|o| o.id))
+            .collect()
+    }
+}
+
+/// Converts channels of a guild, loading its roles to point overwrites of
+/// @everyone at the guild.
+pub(crate) async fn discord_channels(
+    state: &AppState,
+    identity: &Identity,
+    guild_id: Uuid,
+    channels: &[Channel],
+) -> Result<Vec<DiscordChannel>, DiscordError> {
+    let roles = guild_roles(state, identity, guild_id).await?;
+    let ids: Vec<Uuid> = channels.iter().flat_map(DiscordChannel::ids).collect();
+    let snowflakes = guild_snowflakes(state, guild_id, &roles, ids).await?;
+    Ok(channels
+        .iter()
+        .map(|channel| DiscordChannel::new(&snowflakes, channel))
+        .collect())
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscordMember {
+    pub user: DiscordUser,
+    pub nick: Option<String>,
+    pub avatar: Option<String>,
+    pub roles: Vec<String>,
+    pub joined_at: DateTime<Utc>,
+    pub premium_since: Option<DateTime<Utc>>,
+    pub deaf: bool,
+    pub mute: bool,
+    pub flags: u64,
+    pub pending: bool,
+}
+
+impl DiscordMember {
+    /// Members' bot flag is left unset: the member list doesn't carry it.
+    pub fn new(snowflakes: &Snowflakes, member: &MemberWithUser) -> Self {
+        Self {
+            user: DiscordUser::new(
+                snowflakes.get(member.user_id),
+                member.username.clone(),
+                member.display_name.clone(),
+                false,
+            ),
+            nick: None,
+            avatar: None,
+            roles: member
+                .roles
+                .iter()
+                .filter(|role| !is_everyone(&role.name))
+                .map(|role| snowflakes.get(role.id))
+                .collect(),
+            joined_at: member.joined_at,
+            premium_since: None,
+            deaf: false,
+            mute: false,
+            flags: 0,
+            pending: false,
+        }
+    }
+
+    pub fn ids(member: &MemberWithUser) -> Vec<Uuid> {
+        std::iter::once(member.user_id)
+            .chain(member.roles.iter().map(|role| role.id))
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscordGuild {
+    pub id: String,
+    pub name: String,
+    pub icon: Option<String>,
+    pub splash: Option<String>,
+    pub discovery_splash: Option<String>,
+    pub owner_id: String,
+    pub afk_channel_id: Option<String>,
+    pub afk_timeout: u32,
+    pub verification_level: u8,
+    pub default_message_notifications: u8,
+    pub explicit_content_filter: u8,
+    pub roles: Vec<DiscordRole>,
+    pub emojis: Vec<serde_json::Value>,
+    pub features: Vec<String>,
+    pub mfa_level: u8,
+    pub application_id: Option<String>,
+    pub system_channel_id: Option<String>,
+    pub system_channel_flags: u64,
+    pub rules_channel_id: Option<String>,
+    pub vanity_url_code: Option<String>,
+    pub description: Option<String>,
+    pub banner: Option<String>,
+    pub premium_tier: u8,
+    pub preferred_locale: &'static str,
+    pub public_updates_channel_id: Option<String>,
+    pub nsfw_level: u8,
+    pub premium_progress_bar_enabled: bool,
+    pub stickers: Vec<serde_json::Value>,
+}
+
+impl DiscordGuild {
+    pub fn new(snowflakes: &Snowflakes, guild: &Guild, roles: &[Role]) -> Self {
+        let guild_id = *guild.id.get_uuid();
+        Self {
+            id: snowflakes.get(guild_id),
+            name: guild.name.clone(),
+            icon: None,
+            splash: None,
+            discovery_splash: None,
+            owner_id: snowflakes.get(guild.owner_id.0.0),
+            afk_channel_id: None,
+            afk_timeout: 300,
+            verification_level: 0,
+            default_message_notifications: 0,
+            explicit_content_filter: 0,
+            roles: roles
+                .iter()
+                .map(|role| DiscordRole::new(snowflakes, guild_id, role))
+                .collect(),
+            emojis: Vec::new(),
+            features: Vec::new(),
+            mfa_level: 0,
+            application_id: None,
+            system_channel_id: None,
+            system_channel_flags: 0,
+            rules_channel_id: None,
+            vanity_url_code: None,
+            description: None,
+            banner: None,
+            premium_tier: 0,
+            preferred_locale: "en-US",
+            public_updates_channel_id: None,
+            nsfw_level: 0,
+            premium_progress_bar_enabled: false,
+            stickers: Vec::new(),
+        }
+    }
+
+    /// The ids a guild and its roles refer to. The @everyone role is left
+    /// out, since it takes the guild's id.
+    pub fn ids(guild: &Guild, roles: &[Role]) -> Vec<Uuid> {
+        [*guild.id.get_uuid(), guild.owner_id.0.0]
+            .into_iter()
+            .chain(
+                roles
+                    .iter()
+                    .filter(|role| !is_everyone(&role.name))
+                    .map(|role| role.id.0.0),
+            )
+            .collect()
+    }
+}
+
+/// Snowflakes for a guild's objects, with its @everyone role aliased to the
+/// guild, so that channel overwrites of the role point at it.
+pub(crate) async fn guild_snowflakes(
+    state: &AppState,
+    guild_id: Uuid,
+    roles: &[Role],
+    ids: impl IntoIterator<Item = Uuid>,
+) -> Result<Snowflakes, DiscordError> {
+    let snowflakes = Snowflakes::load(state, std::iter::once(guild_id).chain(ids)).await?;
+    Ok(match everyone_role(roles) {
+        Some(role_id) => snowflakes.alias(role_id, guild_id),
+        None => snowflakes,
+    })
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscordAttachment {
+    pub id: String,
+    pub filename: String,
+    pub size: i64,
+    pub url: String,
+    pub proxy_url: String,
+    pub content_type: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscordMessageReference {
+    #[serde(rename = "type")]
+    pub kind: u8,
+    pub message_id: String,
+    pub channel_id: String,
+    pub guild_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DiscordMessage {
+    pub id: String,
+    pub channel_id: String,
+    pub guild_id: String,
+    pub author: DiscordUser,
+    pub content: String,
+    pub timestamp: DateTime<Utc>,
+    pub edited_timestamp: Option<DateTime<Utc>>,
+    pub tts: bool,
+    pub mention_everyone: bool,
+    pub mentions: Vec<DiscordUser>,
+    pub mention_roles: Vec<String>,
+    pub attachments: Vec<DiscordAttachment>,
+    pub embeds: Vec<Embed>,
+    pub pinned: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_id: Option<String>,
+    #[serde(rename = "type")]
+    pub kind: u8,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_reference: Option<DiscordMessageReference>,
+    pub flags: u32,
+}
+
+/// The ids mentioned in a message's content.
+#[derive(Default)]
+struct Mentions {
+    users: Vec<Uuid>,
+    roles: Vec<Uuid>,
+    channels: Vec<Uuid>,
+}
+
+impl Mentions {
+    fn of(content: &str) -> Self {
+        let mut mentions = Self::default();
+        for (kind, id) in mentioned_ids(content) {
+            let Ok(id) = id.parse::<Uuid>() else {
+                continue;
+            };
+            let ids = match kind {
+                MentionKind::User => &mut mentions.users,
+                MentionKind::Role => &mut mentions.roles,
+                MentionKind::Channel => &mut mentions.channels,
+            };
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+        mentions.users.truncate(MAX_MENTIONED_USERS);
+        mentions
+    }
+}
+
+/// Converts messages of a guild's channel, rewriting the mentions in their
+/// content to snowflakes. Encrypted messages are sent without content.
+pub(crate) async fn discord_messages(
+    state: &AppState,
+    guild_id: Uuid,
+    messages: &[Message],
+) -> Result<Vec<DiscordMessage>, DiscordError> {
+    let mut ids = vec![guild_id];
+    let mut mentioned_users = Vec::new();
+    for message in messages {
+        ids.extend([
+            message.id.get_uuid(),
+            message.channel_id.get_uuid(),
+            *message.author.id.get_uuid(),
+        ]);
+        ids.extend(message.webhook_id);
+        ids.extend(message.attachments.iter().map(|a| a.id.get_uuid()));
+        if let Some(reference) = &message.reference {
+            ids.extend([
+                reference.message_id,
+                reference.channel_id,
+                reference.guild_id,
+            ]);
+        }
+        if !message.encrypted {
+            let mentions = Mentions::of(&message.content);
+            ids.extend(mentions.roles);
+            ids.extend(mentions.channels);
+            for user in mentions.users {
+                if !mentioned_users.contains(&user) {
+                    mentioned_users.push(user);
+                }
+            }
+        }
+    }
+
+    let mut profiles = Vec::with_capacity(mentioned_users.len());
+    for user_id in mentioned_users {
+        if let Some(user) = state.user_service.get_profile(UserId(user_id)).await? {
+            profiles.push(user);
+        }
+    }
+    ids.extend(profiles.iter().map(|user| user.id.0));
+    let snowflakes = Snowflakes::load(state, ids).await?;
+
+    Ok(messages
+        .iter()
+        .map(|message| discord_message(&snowflakes, guild_id, &profiles, message))
+        .collect())
+}
+
+fn discord_message(
+    snowflakes: &Snowflakes,
+    guild_id: Uuid,
+    profiles: &[User],
+    message: &Message,
+) -> DiscordMessage {
+    let (content, mentions, mention_roles) = if message.encrypted {
+        (String::new(), Vec::new(), Vec::new())
+    } else {
+        let Mentions { users, roles, .. } = Mentions::of(&message.content);
+        let content = rewrite_mentions(&message.content, |kind, id| {
+            let id = id.parse::<Uuid>().ok()?;
+            // Users past the looked up ones have no snowflake loaded.
+            match kind {
+                MentionKind::User if !users.contains(&id) => None,
+                _ => Some(snowflakes.get(id)),
+            }
+        });
+        let mentions = profiles
+            .iter()
+            .filter(|user| users.contains(&user.id.0))
+            .map(|user| DiscordUser::from_user(snowflakes, user))
+            .collect();
+        let mention_roles = roles.into_iter().map(|id| snowflakes.get(id)).collect();
+        (content, mentions, mention_roles)
+    };
+
+    let mut flags = 0;
+    if message.crossposted {
+        flags |= CROSSPOSTED;
+    }
+    if message.reference.is_some() {
+        flags |= IS_CROSSPOST;
+    }
+
+    DiscordMessage {
+        id: snowflakes.get(message.id.get_uuid()),
+        channel_id: snowflakes.get(message.channel_id.get_uuid()),
+        guild_id: snowflakes.get(guild_id),
+        author: DiscordUser::new(
+            snowflakes.get(*message.author.id.get_uuid()),
+            message.author.username.clone(),
+            None,
+            message.author.bot,
+        ),
+        mention_everyone: content.contains("@everyone") || content.contains("@here"),
+        content,
+        timestamp: message.created_at,
+        edited_timestamp: message.edited_at,
+        tts: false,
+        mentions,
+        mention_roles,
+        attachments: message
+            .attachments
+            .iter()
+            .map(|attachment| DiscordAttachment {
+                id: snowflakes.get(attachment.id.get_uuid()),
+                filename: attachment.filename.clone(),
+                size: attachment.size_bytes,
+                url: attachment.url.clone(),
+                proxy_url: attachment.url.clone(),
+                content_type: attachment.content_type.clone(),
+            })
+            .collect(),
+        embeds: message.embeds.clone(),
+        pinned: false,
+        webhook_id: message.webhook_id.map(|id| snowflakes.get(id)),
+        kind: 0,
+        message_reference: message
+            .reference
+            .as_ref()
+            .map(|reference| DiscordMessageReference {
+                kind: 0,
+                message_id: snowflakes.get(reference.message_id),
+                channel_id: snowflakes.get(reference.channel_id),
+                guild_id: snowflakes.get(reference.guild_id),
+            }),
+        flags,
+    }
+}
@@ -0,0 +1,61 @@
+use axum::{Extension, extract::State, http::StatusCode};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::role::{entities::RemoveRoleInput, ports::RoleService};
+use ferriscord_entities::{Id, event_subscription::GuildEventType, role::RoleId, user::UserId};
+use serde::Deserialize;
+
+use super::{DiscordError, member_guild, resolve};
+use crate::{events::dispatch_guild_event, member_list::publish_guild_event, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/guilds/{guild_id}/members/{user_id}/roles/{role_id}")]
+pub struct RemoveMemberRoleRoute {
+    guild_id: String,
+    user_id: String,
+    role_id: String,
+}
+
+pub async fn remove_member_role_handler(
+    RemoveMemberRoleRoute {
+        guild_id,
+        user_id,
+        role_id,
+    }: RemoveMemberRoleRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, DiscordError> {
+    let guild = member_guild(&state, &identity, &guild_id).await?;
+    let user_id = resolve(&state, &user_id, DiscordError::unknown_user).await?;
+    let role_id = resolve(&state, &role_id, DiscordError::unknown_role).await?;
+    let guild_id = *guild.id.get_uuid();
+
+    state
+        .role_service
+        .remove_role(
+            identity,
+            RemoveRoleInput {
+                guild_id: guild.id,
+                user_id: UserId(Id(user_id)),
+                role_id: RoleId(Id(role_id)),
+            },
+        )
+        .await?;
+
+    publish_guild_event(
+        &state.hub,
+        guild_id,
+        "member.update",
+        serde_json::json!({ "user_id": user_id }),
+    )
+    .await;
+    dispatch_guild_event(
+        &state,
+        guild_id,
+        GuildEventType::MemberUpdate,
+        serde_json::json!({ "user_id": user_id, "removed_role_id": role_id }),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,38 @@
+use axum::{Extension, extract::State, http::StatusCode};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use serde::Deserialize;
+
+use super::{DiscordError, current_user, visible_channel};
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/compat/discord/v10/channels/{channel_id}/typing")]
+pub struct TriggerTypingRoute {
+    channel_id: String,
+}
+
+/// Shows the caller typing, like a `typing.update` sent over the gateway.
+pub async fn trigger_typing_handler(
+    TriggerTypingRoute { channel_id }: TriggerTypingRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, DiscordError> {
+    let (_, channel) = visible_channel(&state, &identity, &channel_id).await?;
+    let user = current_user(&state, &identity).await?;
+
+    let room = format!("channel:{}", channel.id.get_uuid());
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "typing.update",
+        "room": room,
+        "data": {
+            "user_id": user.id.0,
+            "username": identity.username(),
+            "is_typing": true,
+        },
+    })) {
+        state.hub.publish(&room, payload).await;
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
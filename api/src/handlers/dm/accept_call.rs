@@ -0,0 +1,57 @@
+use axum::{Extension, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::user::domain::{call::ports::CallService, user::ports::UserService};
+use ferriscord_entities::dm_call::DmCall;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    call::{map_call_error, publish_call},
+    state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/channels/@me/{channel_id}/call/accept")]
+pub struct AcceptDmCallRoute {
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/channels/@me/{channel_id}/call/accept",
+    tag = "dms",
+    summary = "Answer a DM call",
+    description = "Also rejoins a call the user left or declined while it is in progress.",
+    security(("Authorization" = ["Bearer"])),
+    params(("channel_id" = Uuid, Path, description = "DM channel ID")),
+    responses(
+        (status = 200, body = DmCall),
+        (status = 401, body = ApiError),
+        (status = 404, description = "No call in progress, or not a participant", body = ApiError),
+    )
+)]
+pub async fn accept_dm_call_handler(
+    AcceptDmCallRoute { channel_id }: AcceptDmCallRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<DmCall>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let call = state
+        .call_service
+        .accept_call(user.id.0, channel_id)
+        .await
+        .map_err(map_call_error)?;
+
+    publish_call(&state.hub, "call.update", &call).await;
+
+    Ok(Response::OK(call))
+}
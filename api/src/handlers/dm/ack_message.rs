@@ -0,0 +1,51 @@
+use axum::{Extension, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::user::domain::user::ports::UserService;
+use ferriscord_entities::read_state::ReadState;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{read_state::ack_message, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/channels/@me/{channel_id}/messages/{message_id}/ack")]
+pub struct AckDmMessageRoute {
+    pub channel_id: Uuid,
+    pub message_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/channels/@me/{channel_id}/messages/{message_id}/ack",
+    tag = "dms",
+    summary = "Mark a DM as read",
+    security(("Authorization" = ["Bearer"])),
+    params(
+        ("channel_id" = Uuid, Path, description = "DM channel ID"),
+        ("message_id" = Uuid, Path, description = "Last read message ID"),
+    ),
+    responses(
+        (status = 200, body = ReadState),
+        (status = 401, body = ApiError),
+        (status = 404, description = "Message not found or not a participant", body = ApiError),
+    )
+)]
+pub async fn ack_dm_message_handler(
+    AckDmMessageRoute { channel_id, message_id }: AckDmMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<ReadState>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let read_state = ack_message(&state, user.id.0, None, channel_id, message_id).await?;
+
+    Ok(Response::OK(read_state))
+}
@@ -0,0 +1,57 @@
+use axum::{Extension, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::user::domain::{call::ports::CallService, user::ports::UserService};
+use ferriscord_entities::dm_call::DmCall;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    call::{call_updated, map_call_error},
+    state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/channels/@me/{channel_id}/call/decline")]
+pub struct DeclineDmCallRoute {
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/channels/@me/{channel_id}/call/decline",
+    tag = "dms",
+    summary = "Decline a DM call",
+    description = "Ends the call when nobody else is rung and the caller is left alone.",
+    security(("Authorization" = ["Bearer"])),
+    params(("channel_id" = Uuid, Path, description = "DM channel ID")),
+    responses(
+        (status = 200, body = DmCall),
+        (status = 401, body = ApiError),
+        (status = 404, description = "No call in progress, or not a participant", body = ApiError),
+    )
+)]
+pub async fn decline_dm_call_handler(
+    DeclineDmCallRoute { channel_id }: DeclineDmCallRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<DmCall>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let update = state
+        .call_service
+        .decline_call(user.id.0, channel_id)
+        .await
+        .map_err(map_call_error)?;
+
+    call_updated(&state.hub, &update).await;
+
+    Ok(Response::OK(update.call))
+}
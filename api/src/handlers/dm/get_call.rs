@@ -0,0 +1,51 @@
+use axum::{Extension, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::user::domain::{call::ports::CallService, user::ports::UserService};
+use ferriscord_entities::dm_call::DmCall;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{call::map_call_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/channels/@me/{channel_id}/call")]
+pub struct GetDmCallRoute {
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/channels/@me/{channel_id}/call",
+    tag = "dms",
+    summary = "Get the call in progress in a DM",
+    security(("Authorization" = ["Bearer"])),
+    params(("channel_id" = Uuid, Path, description = "DM channel ID")),
+    responses(
+        (status = 200, description = "The live call, or null", body = Option<DmCall>),
+        (status = 401, body = ApiError),
+        (status = 404, description = "Channel not found or not a participant", body = ApiError),
+    )
+)]
+pub async fn get_dm_call_handler(
+    GetDmCallRoute { channel_id }: GetDmCallRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Option<DmCall>>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let call = state
+        .call_service
+        .get_call(user.id.0, channel_id)
+        .await
+        .map_err(map_call_error)?;
+
+    Ok(Response::OK(call))
+}
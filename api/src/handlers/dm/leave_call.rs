@@ -0,0 +1,56 @@
+use axum::{Extension, extract::State, http::StatusCode};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::voice::ports::VoiceService, user::domain::user::ports::UserService,
+};
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{call::call_left, handlers::map_core_error, state::AppState, voice::voice_left};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/channels/@me/{channel_id}/call")]
+pub struct LeaveDmCallRoute {
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/channels/@me/{channel_id}/call",
+    tag = "dms",
+    summary = "Hang up a DM call",
+    description = "Disconnects the user from the call's voice. The call ends once at most one participant is left and nobody is rung.",
+    security(("Authorization" = ["Bearer"])),
+    params(("channel_id" = Uuid, Path, description = "DM channel ID")),
+    responses(
+        (status = 204, description = "Hung up"),
+        (status = 401, body = ApiError),
+    )
+)]
+pub async fn leave_dm_call_handler(
+    LeaveDmCallRoute { channel_id }: LeaveDmCallRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    // Leaving the voice channel hangs up as well.
+    match state
+        .voice_service
+        .leave_channel(user.id.0, channel_id)
+        .await
+        .map_err(map_core_error)?
+    {
+        Some(left) => voice_left(&state, &left).await,
+        None => call_left(&state, user.id.0, channel_id).await,
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
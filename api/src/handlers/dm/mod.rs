@@ -3,23 +3,35 @@ use axum_extra::routing::RouterExt;
 
 use crate::state::AppState;
 
+pub mod accept_call;
+pub mod ack_message;
 pub mod create_or_get;
+pub mod decline_call;
 pub mod delete_message;
+pub mod get_call;
 pub mod get_messages;
 pub mod history_sync;
+pub mod leave_call;
 pub mod list_dms;
 pub mod send_message;
+pub mod start_call;
 
+use accept_call::accept_dm_call_handler;
+use ack_message::ack_dm_message_handler;
 use create_or_get::create_or_get_dm_handler;
+use decline_call::decline_dm_call_handler;
 use delete_message::delete_dm_message_handler;
+use get_call::get_dm_call_handler;
 use get_messages::get_dm_messages_handler;
 use history_sync::{
     complete_dm_history_sync_job_handler, create_dm_history_sync_job_handler,
     fail_dm_history_sync_job_handler, get_dm_history_sync_job_handler,
     list_dm_history_sync_messages_handler, upload_dm_history_sync_payloads_handler,
 };
+use leave_call::leave_dm_call_handler;
 use list_dms::list_dms_handler;
 use send_message::send_dm_message_handler;
+use start_call::start_dm_call_handler;
 
 pub fn dm_routes(_state: AppState) -> Router<AppState> {
     Router::new()
@@ -28,10 +40,16 @@ pub fn dm_routes(_state: AppState) -> Router<AppState> {
         .typed_get(get_dm_messages_handler)
         .typed_post(send_dm_message_handler)
         .typed_delete(delete_dm_message_handler)
+        .typed_post(ack_dm_message_handler)
         .typed_post(create_dm_history_sync_job_handler)
         .typed_get(get_dm_history_sync_job_handler)
         .typed_get(list_dm_history_sync_messages_handler)
         .typed_post(upload_dm_history_sync_payloads_handler)
         .typed_put(complete_dm_history_sync_job_handler)
         .typed_put(fail_dm_history_sync_job_handler)
+        .typed_post(start_dm_call_handler)
+        .typed_get(get_dm_call_handler)
+        .typed_post(accept_dm_call_handler)
+        .typed_post(decline_dm_call_handler)
+        .typed_delete(leave_dm_call_handler)
 }
@@ -3,8 +3,9 @@ use std::time::Duration;
 use axum::{Extension, extract::State};
 use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
-use ferriscord_core::user::domain::dm::ports::{
-    DmAttachmentInput, DmDevicePayload, DmEncryptionMeta, DmService,
+use ferriscord_core::user::domain::{
+    dm::ports::{DmAttachmentInput, DmDevicePayload, DmEncryptionMeta, DmService},
+    read_state::ports::ReadStateService,
 };
 use ferriscord_entities::{Id, attachment::AttachmentId, message::Message};
 use ferriscord_error::ApiError;
@@ -14,7 +15,7 @@ use serde::Deserialize;
 use tracing::error;
 use uuid::Uuid;
 
-use crate::state::AppState;
+use crate::{read_state::publish_read_states, state::AppState};
 
 #[derive(Debug, Deserialize)]
 struct DevicePayloadUpload {
@@ -26,6 +27,27 @@ fn dm_room(channel_id: Uuid) -> String {
     format!("dm:{}", channel_id)
 }
 
+/// Publishes a new DM message and moves the read positions.
+pub(crate) async fn publish_new_dm_message(state: &AppState, channel_id: Uuid, message: &Message) {
+    let room = dm_room(channel_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "message.new",
+        "room": room,
+        "data": message,
+    })) {
+        state.hub.publish(&room, payload).await;
+    }
+
+    match state
+        .read_state_service
+        .record_dm_message(channel_id, message.id.get_uuid(), *message.author.id.get_uuid())
+        .await
+    {
+        Ok(read_states) => publish_read_states(&state.hub, &read_states).await,
+        Err(e) => error!("failed to update read states: {}", e),
+    }
+}
+
 #[derive(TypedPath, Deserialize)]
 #[typed_path("/channels/@me/{channel_id}/messages")]
 pub struct SendDmMessageRoute {
@@ -157,14 +179,7 @@ pub async fn send_dm_message_handler(
         }
     }
 
-    let room = dm_room(channel_id);
-    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
-        "type": "message.new",
-        "room": room,
-        "data": &message,
-    })) {
-        state.hub.publish(&room, payload).await;
-    }
+    publish_new_dm_message(&state, channel_id, &message).await;
 
     Ok(Response::Created(message))
 }
@@ -0,0 +1,58 @@
+use axum::{Extension, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::user::domain::{call::ports::CallService, user::ports::UserService};
+use ferriscord_entities::dm_call::DmCall;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    call::{call_started, map_call_error},
+    state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/channels/@me/{channel_id}/call")]
+pub struct StartDmCallRoute {
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/channels/@me/{channel_id}/call",
+    tag = "dms",
+    summary = "Start a call in a DM",
+    description = "The caller joins the call and every other participant is rung through `call.ring` on their user room. Rings not answered within 30 seconds are missed. Connect to the call's voice with `voice_state.update` without a `guild_id`.",
+    security(("Authorization" = ["Bearer"])),
+    params(("channel_id" = Uuid, Path, description = "DM channel ID")),
+    responses(
+        (status = 201, body = DmCall),
+        (status = 400, description = "A call is already in progress", body = ApiError),
+        (status = 401, body = ApiError),
+        (status = 404, description = "Channel not found or not a participant", body = ApiError),
+    )
+)]
+pub async fn start_dm_call_handler(
+    StartDmCallRoute { channel_id }: StartDmCallRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<DmCall>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let call = state
+        .call_service
+        .start_call(user.id.0, channel_id)
+        .await
+        .map_err(map_call_error)?;
+
+    call_started(&state.hub, &call).await;
+
+    Ok(Response::Created(call))
+}
@@ -3,11 +3,14 @@ use axum::http::StatusCode;
 use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
 use ferriscord_entities::{Id, guild::GuildId, role::RoleId, user::UserId};
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_core::guild::domain::role::{entities::AssignRoleInput, ports::RoleService};
 use serde::Deserialize;
 use uuid::Uuid;
 
+use crate::member_list::publish_guild_event;
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath, Deserialize)]
@@ -51,5 +54,20 @@ pub async fn assign_member_role_handler(
         .await
         .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
 
+    publish_guild_event(
+        &state.hub,
+        guild_id,
+        "member.update",
+        serde_json::json!({ "user_id": user_id }),
+    )
+    .await;
+    dispatch_guild_event(
+        &state,
+        guild_id,
+        GuildEventType::MemberUpdate,
+        serde_json::json!({ "user_id": user_id, "added_role_id": role_id }),
+    )
+    .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
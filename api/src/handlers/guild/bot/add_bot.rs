@@ -0,0 +1,103 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::application::ports::ApplicationService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    application::GuildBot, event_subscription::GuildEventType, guild::GuildId,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    events::dispatch_guild_event, handlers::map_core_error, member_list::publish_guild_event,
+    state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/bots")]
+pub struct AddBotRoute {
+    pub guild_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct AddBotRequest {
+    pub application_id: Uuid,
+    /// Permission bits granted to the bot through a role named after it.
+    #[serde(default)]
+    pub permissions: u64,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/bots",
+    tag = "bots",
+    summary = "Add a bot to a guild",
+    description = "Adds an application's bot user to the guild as a member. Requested permissions are granted through a new role named after the bot and cannot exceed the caller's own. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = AddBotRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = GuildBot),
+        (status = 400, description = "Unknown permission bits or bot already in the guild", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD or requested permissions", body = ApiError),
+        (status = 404, description = "Application not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn add_bot_handler(
+    AddBotRoute { guild_id }: AddBotRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<AddBotRequest>,
+) -> Result<Response<GuildBot>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let bot = state
+        .application_service
+        .add_bot(
+            identity,
+            user.id.0,
+            GuildId::from(guild_id),
+            req.application_id,
+            req.permissions,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_guild_event(
+        &state.hub,
+        guild_id,
+        "member.add",
+        serde_json::json!({ "user_id": bot.bot_user_id }),
+    )
+    .await;
+    dispatch_guild_event(
+        &state,
+        guild_id,
+        GuildEventType::MemberJoin,
+        serde_json::json!({ "user_id": bot.bot_user_id, "username": bot.username, "bot": true }),
+    )
+    .await;
+
+    Ok(Response::Created(bot))
+}
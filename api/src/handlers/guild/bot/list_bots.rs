@@ -0,0 +1,48 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::application::ports::ApplicationService;
+use ferriscord_entities::{application::GuildBot, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/bots")]
+pub struct ListBotsRoute {
+    pub guild_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/bots",
+    tag = "bots",
+    summary = "List a guild's bots",
+    description = "Lists the bots added to the guild. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<GuildBot>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_bots_handler(
+    ListBotsRoute { guild_id }: ListBotsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<GuildBot>>, ApiError> {
+    let bots = state
+        .application_service
+        .list_guild_bots(identity, GuildId::from(guild_id))
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(bots))
+}
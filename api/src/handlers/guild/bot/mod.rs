@@ -0,0 +1,3 @@
+pub mod add_bot;
+pub mod list_bots;
+pub mod remove_bot;
@@ -0,0 +1,74 @@
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::application::ports::ApplicationService;
+use ferriscord_entities::{event_subscription::GuildEventType, guild::GuildId};
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    events::dispatch_guild_event, handlers::map_core_error, member_list::publish_guild_event,
+    state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/bots/{application_id}")]
+pub struct RemoveBotRoute {
+    pub guild_id: Uuid,
+    pub application_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/guilds/{guild_id}/bots/{application_id}",
+    tag = "bots",
+    summary = "Remove a bot from a guild",
+    description = "Removes the bot's membership and the role created for it when it was added. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("application_id" = Uuid, Path, description = "Application ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 204, description = "Bot removed"),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 404, description = "Bot not in the guild", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn remove_bot_handler(
+    RemoveBotRoute {
+        guild_id,
+        application_id,
+    }: RemoveBotRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    let bot = state
+        .application_service
+        .remove_bot(identity, GuildId::from(guild_id), application_id)
+        .await
+        .map_err(map_core_error)?;
+
+    publish_guild_event(
+        &state.hub,
+        guild_id,
+        "member.remove",
+        serde_json::json!({ "user_id": bot.bot_user_id }),
+    )
+    .await;
+    dispatch_guild_event(
+        &state,
+        guild_id,
+        GuildEventType::MemberLeave,
+        serde_json::json!({ "user_id": bot.bot_user_id }),
+    )
+    .await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,62 @@
+use axum::{Extension, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::user::domain::user::ports::UserService;
+use ferriscord_entities::read_state::ReadState;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{read_state::ack_message, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/ack")]
+pub struct AckMessageRoute {
+    guild_id: Uuid,
+    channel_id: Uuid,
+    message_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/ack",
+    tag = "messages",
+    summary = "Mark a channel as read",
+    description = "Marks `message_id` as the last message read in the channel and resets the mention count accordingly. Acking an older message marks the channel as unread from that point. Other sessions of the user receive a `read_state.update` event.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+        ("message_id" = Uuid, Path, description = "Last read message ID"),
+    ),
+    security(
+        ("Authorization" = ["Bearer"]),
+    ),
+    responses(
+        (status = 200, body = ReadState),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing VIEW_CHANNEL permission", body = ApiError),
+        (status = 404, description = "Message not found in channel", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn ack_message_handler(
+    AckMessageRoute {
+        guild_id,
+        channel_id,
+        message_id,
+    }: AckMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<ReadState>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let read_state = ack_message(&state, user.id.0, Some(guild_id), channel_id, message_id).await?;
+
+    Ok(Response::OK(read_state))
+}
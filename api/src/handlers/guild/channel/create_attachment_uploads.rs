@@ -0,0 +1,109 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::attachment_upload::ports::{
+    AttachmentUploadService, UPLOAD_URL_TTL, UploadRequest,
+};
+use ferriscord_entities::{Id, attachment::AttachmentUpload, channel::ChannelId, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use ferriscord_storage::StoragePort;
+use serde::Deserialize;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(Deserialize, ToSchema)]
+pub struct UploadFileRequest {
+    pub filename: String,
+    /// The `Content-Type` the file will be uploaded with.
+    pub content_type: String,
+    pub size_bytes: i64,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateAttachmentUploadsRequest {
+    pub files: Vec<UploadFileRequest>,
+}
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/attachments")]
+pub struct CreateAttachmentUploadsRoute {
+    guild_id: Uuid,
+    channel_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/channels/{channel_id}/attachments",
+    tag = "messages",
+    summary = "Request attachment uploads",
+    description = "Reserves an upload slot for each of up to 10 files of at most 25 MiB, to upload straight to storage instead of through the API. `PUT` each file to its `upload_url` with the declared `Content-Type`, then send a message with the slot ids in its `uploads` field. Slots not attached within an hour expire, with their files. Requires SEND_MESSAGES and ATTACH_FILES.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = CreateAttachmentUploadsRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = Vec<AttachmentUpload>),
+        (status = 400, description = "Invalid file, too many files, or too many pending uploads", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing SEND_MESSAGES or ATTACH_FILES permission", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_attachment_uploads_handler(
+    CreateAttachmentUploadsRoute {
+        guild_id,
+        channel_id,
+    }: CreateAttachmentUploadsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<CreateAttachmentUploadsRequest>,
+) -> Result<Response<Vec<AttachmentUpload>>, ApiError> {
+    let files = req
+        .files
+        .into_iter()
+        .map(|file| UploadRequest {
+            filename: file.filename,
+            content_type: file.content_type,
+            size_bytes: file.size_bytes,
+        })
+        .collect();
+
+    let mut uploads = state
+        .attachment_upload_service
+        .create_uploads(
+            identity,
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+            files,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    let bucket = &state.args.storage.bucket;
+    for upload in &mut uploads {
+        upload.upload_url = state
+            .storage
+            .presigned_put_url(bucket, &upload.storage_key, UPLOAD_URL_TTL)
+            .await
+            .map_err(|e| {
+                error!(
+                    "failed to generate upload URL for '{}': {}",
+                    upload.storage_key, e
+                );
+                ApiError::Unknown {
+                    message: format!("failed to generate upload URL: {}", e),
+                }
+            })?;
+    }
+
+    Ok(Response::Created(uploads))
+}
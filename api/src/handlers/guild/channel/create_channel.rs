@@ -6,6 +6,7 @@ use ferriscord_entities::channel::{
     ForumLayout, ForumTag, PermissionOverwrite, SortOrder,
 };
 use ferriscord_entities::{Id, guild::GuildId};
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_server::http::response::Response;
 use ferriscord_core::guild::domain::channel::{
@@ -15,6 +16,7 @@ use serde::Deserialize;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath, Deserialize)]
@@ -99,11 +101,19 @@ pub async fn create_channel_handler(
 
     let channel = state
         .channel_service
-        .create_channel(identity, guild_id, input)
+        .create_channel(identity, guild_id.clone(), input)
         .await
         .map_err(|e| ApiError::Unknown {
             message: e.to_string(),
         })?;
 
+    dispatch_guild_event(
+        &state,
+        *guild_id.get_uuid(),
+        GuildEventType::ChannelCreate,
+        &channel,
+    )
+    .await;
+
     Ok(Response::Created(channel))
 }
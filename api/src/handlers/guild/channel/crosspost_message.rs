@@ -0,0 +1,86 @@
+use axum::{Extension, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::channel_follow::ports::ChannelFollowService,
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, message::Message};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    crossposts::{presign_attachments, publish_crossposts, publish_message_update},
+    handlers::map_core_error,
+    state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/crosspost")]
+pub struct CrosspostMessageRoute {
+    guild_id: Uuid,
+    channel_id: Uuid,
+    message_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/crosspost",
+    tag = "messages",
+    summary = "Publish an announcement",
+    description = "Copies a message of an announcement channel into every channel following it. A message is published once; its copies carry a `reference` to it and take over its later edits and deletion. The author needs SEND_MESSAGES, anyone else MANAGE_MESSAGES.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+        ("message_id" = Uuid, Path, description = "Message ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Message),
+        (status = 400, description = "Not an announcement channel, or message already published, encrypted or a copy", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing SEND_MESSAGES or MANAGE_MESSAGES permission", body = ApiError),
+        (status = 404, description = "Message not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn crosspost_message_handler(
+    CrosspostMessageRoute {
+        guild_id,
+        channel_id,
+        message_id,
+    }: CrosspostMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Message>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let (mut message, crossposts) = state
+        .channel_follow_service
+        .crosspost_message(
+            identity,
+            user.id.0,
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+            message_id,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    presign_attachments(&state, &mut message).await;
+    publish_message_update(&state, guild_id, &message).await;
+    publish_crossposts(&state, crossposts).await;
+
+    Ok(Response::OK(message))
+}
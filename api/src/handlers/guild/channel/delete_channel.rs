@@ -3,12 +3,14 @@ use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
 use ferriscord_core::guild::domain::channel::ports::ChannelService;
 use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId};
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_server::http::response::Response;
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath, Deserialize)]
@@ -57,6 +59,14 @@ pub async fn delete_channel_handler(
             message: e.to_string(),
         })?;
 
+    dispatch_guild_event(
+        &state,
+        guild_id,
+        GuildEventType::ChannelDelete,
+        serde_json::json!({ "channel_id": channel_id }),
+    )
+    .await;
+
     Ok(Response::OK(DeleteChannelResponse {
         message: "channel deleted".to_string(),
     }))
@@ -2,6 +2,7 @@ use axum::{Extension, extract::State};
 use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
 use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId};
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_server::http::response::Response;
 use ferriscord_core::guild::domain::message::ports::MessageService;
@@ -9,6 +10,8 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::crossposts::publish_crosspost_deletes;
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath, Deserialize)]
@@ -29,6 +32,7 @@ pub struct DeleteMessageResponse {
     path = "/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}",
     tag = "messages",
     summary = "Delete a message",
+    description = "Deletes one of your messages. Deleting a published announcement also deletes its copies in the following channels.",
     params(
         ("guild_id" = Uuid, Path, description = "Guild ID"),
         ("channel_id" = Uuid, Path, description = "Channel ID"),
@@ -47,7 +51,7 @@ pub async fn delete_message_handler(
     State(state): State<AppState>,
     Extension(identity): Extension<Identity>,
 ) -> Result<Response<DeleteMessageResponse>, ApiError> {
-    state
+    let copies = state
         .message_service
         .delete_message(
             identity,
@@ -67,5 +71,15 @@ pub async fn delete_message_handler(
         state.hub.publish(&room, payload).await;
     }
 
+    dispatch_guild_event(
+        &state,
+        guild_id,
+        GuildEventType::MessageDelete,
+        serde_json::json!({ "message_id": message_id, "channel_id": channel_id }),
+    )
+    .await;
+
+    publish_crosspost_deletes(&state, &copies).await;
+
     Ok(Response::OK(DeleteMessageResponse { message: "message deleted".to_string() }))
 }
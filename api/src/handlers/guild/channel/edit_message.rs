@@ -0,0 +1,85 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::message::ports::MessageService;
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, message::Message};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    crossposts::{presign_attachments, publish_crosspost_updates, publish_message_update},
+    handlers::map_core_error,
+    link_previews::spawn_previews,
+    state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}")]
+pub struct EditMessageRoute {
+    guild_id: Uuid,
+    channel_id: Uuid,
+    message_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct EditMessageRequest {
+    pub content: String,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}",
+    tag = "messages",
+    summary = "Edit a message",
+    description = "Replaces the content of one of your messages and publishes a `message.update`. Edits of a published announcement are carried over to its copies in the following channels.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+        ("message_id" = Uuid, Path, description = "Message ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = EditMessageRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = Message),
+        (status = 400, description = "Empty message", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden (not your message)", body = ApiError),
+        (status = 404, description = "Message not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn edit_message_handler(
+    EditMessageRoute {
+        guild_id,
+        channel_id,
+        message_id,
+    }: EditMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<EditMessageRequest>,
+) -> Result<Response<Message>, ApiError> {
+    let (mut message, crossposts) = state
+        .message_service
+        .edit_message(
+            identity.clone(),
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+            message_id,
+            req.content,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    presign_attachments(&state, &mut message).await;
+    publish_message_update(&state, guild_id, &message).await;
+    publish_crosspost_updates(&state, crossposts).await;
+    spawn_previews(&state, Some(identity), guild_id, &message);
+
+    Ok(Response::OK(message))
+}
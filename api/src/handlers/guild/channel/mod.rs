@@ -1,6 +1,10 @@
+pub mod ack_message;
+pub mod create_attachment_uploads;
 pub mod create_channel;
+pub mod crosspost_message;
 pub mod delete_channel;
 pub mod delete_message;
+pub mod edit_message;
 pub mod get_channels;
 pub mod get_messages;
 pub mod send_message;
@@ -1,20 +1,35 @@
 use std::time::Duration;
 
 use axum::{Extension, extract::State};
+use chrono::{DateTime, Utc};
 use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::attachment_upload::{
+    attachment_key, check_uploaded_object, ports::AttachmentUploadService,
+};
 use ferriscord_core::guild::domain::message::ports::{AttachmentInput, EncryptionMeta, MessageService};
+use ferriscord_core::guild::domain::poll::ports::PollInput;
 use ferriscord_entities::{
-    Id, attachment::AttachmentId, channel::ChannelId, guild::GuildId, message::Message,
+    Id,
+    attachment::{AttachmentId, AttachmentUpload},
+    channel::ChannelId,
+    embed::Embed,
+    guild::GuildId,
+    message::Message,
 };
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_server::http::response::Response;
 use ferriscord_storage::StoragePort;
 use serde::Deserialize;
-use tracing::error;
+use tracing::{error, warn};
 use uuid::Uuid;
 
+use crate::attachments::{AttachmentFile, delete_objects, post_with_files};
 use crate::handlers::map_core_error;
+use crate::read_state::record_guild_message;
+use crate::events::dispatch_guild_event;
+use crate::link_previews::spawn_previews;
 use crate::state::AppState;
 
 fn channel_room(channel_id: &ChannelId) -> String {
@@ -25,6 +40,150 @@ fn guild_room(guild_id: &GuildId) -> String {
     format!("guild:{}", guild_id.get_uuid())
 }
 
+/// Publishes a new message of a guild channel, moves the read positions,
+/// and dispatches it to event subscriptions and link previews.
+pub(crate) async fn publish_new_message(
+    state: &AppState,
+    identity: Identity,
+    guild_id: &GuildId,
+    message: &Message,
+) {
+    let room = channel_room(&message.channel_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "message.new",
+        "room": room,
+        "data": message,
+    })) {
+        state.hub.publish(&room, payload).await;
+    }
+
+    let guild_room = guild_room(guild_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "message.new",
+        "room": guild_room,
+        "data": message,
+    })) {
+        state.hub.publish(&guild_room, payload).await;
+    }
+
+    // Move the author's read position and hand out mention badges.
+    record_guild_message(
+        state,
+        *guild_id.get_uuid(),
+        message.channel_id.get_uuid(),
+        message.id.get_uuid(),
+        Some(*message.author.id.get_uuid()),
+        &message.content,
+    )
+    .await;
+
+    dispatch_guild_event(
+        state,
+        *guild_id.get_uuid(),
+        GuildEventType::MessageCreate,
+        message,
+    )
+    .await;
+
+    spawn_previews(state, Some(identity), *guild_id.get_uuid(), message);
+}
+
+/// The `poll` field of a new message.
+#[derive(Deserialize)]
+struct PollRequest {
+    question: String,
+    answers: Vec<String>,
+    #[serde(default)]
+    allow_multiselect: bool,
+    #[serde(default)]
+    hide_results: bool,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<PollRequest> for PollInput {
+    fn from(request: PollRequest) -> Self {
+        PollInput {
+            question: request.question,
+            answers: request.answers,
+            allow_multiselect: request.allow_multiselect,
+            hide_results: request.hide_results,
+            expires_at: request.expires_at,
+        }
+    }
+}
+
+fn storage_error(context: &str, key: &str, e: impl std::fmt::Display) -> ApiError {
+    error!("{} '{}': {}", context, key, e);
+    ApiError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+/// Makes claimed slots available again after their message failed.
+async fn release_uploads(state: &AppState, ids: &[Uuid]) {
+    if let Err(e) = state.attachment_upload_service.release_uploads(ids).await {
+        warn!("failed to release attachment uploads: {:?}", e);
+    }
+}
+
+/// Copies the file of each slot to the key it is attached under, which its
+/// uploader cannot write to, and checks the copy against what was declared.
+/// Copies already made are removed if any file fails.
+async fn link_uploads(
+    state: &AppState,
+    uploads: Vec<AttachmentUpload>,
+) -> Result<Vec<AttachmentInput>, ApiError> {
+    let bucket = &state.args.storage.bucket;
+    let mut linked: Vec<AttachmentInput> = Vec::with_capacity(uploads.len());
+    for upload in uploads {
+        let storage_key = attachment_key(&upload);
+        let result = async {
+            let uploaded = state
+                .storage
+                .head_object(bucket, &upload.storage_key)
+                .await
+                .map_err(|e| storage_error("failed to check upload", &upload.storage_key, e))?;
+            if uploaded.is_none() {
+                return check_uploaded_object(&upload, None).map_err(map_core_error);
+            }
+            state
+                .storage
+                .copy_object(bucket, &upload.storage_key, bucket, &storage_key)
+                .await
+                .map_err(|e| storage_error("failed to copy upload", &upload.storage_key, e))?;
+            let object = state
+                .storage
+                .head_object(bucket, &storage_key)
+                .await
+                .map_err(|e| storage_error("failed to check upload", &storage_key, e))?;
+            check_uploaded_object(
+                &upload,
+                object
+                    .as_ref()
+                    .map(|object| (object.size, object.content_type.as_deref())),
+            )
+            .map_err(map_core_error)
+        }
+        .await;
+
+        if let Err(e) = result {
+            let mut copies: Vec<String> =
+                linked.into_iter().map(|input| input.storage_key).collect();
+            copies.push(storage_key);
+            delete_objects(&state.storage, bucket, &copies).await;
+            return Err(e);
+        }
+        linked.push(AttachmentInput {
+            id: AttachmentId(Id(upload.id)),
+            filename: upload.filename,
+            content_type: upload.content_type,
+            size_bytes: upload.size_bytes,
+            storage_key,
+        });
+    }
+    Ok(linked)
+}
+
 #[derive(TypedPath, Deserialize)]
 #[typed_path("/guilds/{guild_id}/channels/{channel_id}/messages")]
 pub struct SendMessageRoute {
@@ -37,7 +196,7 @@ pub struct SendMessageRoute {
     path = "/guilds/{guild_id}/channels/{channel_id}/messages",
     tag = "messages",
     summary = "Send a message",
-    description = "Sends a message (with optional file attachments) to a text channel. Requires SEND_MESSAGES permission. Use multipart/form-data: `content` field for text, `files` fields for attachments.",
+    description = "Sends a message (with optional file attachments) to a text channel. Requires SEND_MESSAGES permission. Use multipart/form-data: `content` field for text, `files` fields for attachments. Bots may add an `embeds` field with a JSON array of embeds, which requires EMBED_LINKS. A `poll` field with a JSON object `{question, answers, allow_multiselect, hide_results, expires_at}` attaches a poll of 2 to 10 answers, open for up to 32 days. An `uploads` field with a JSON array of upload slot ids attaches files uploaded straight to storage, once checked against their declared size and type (see `POST /guilds/{guild_id}/channels/{channel_id}/attachments`).",
     params(
         ("guild_id" = Uuid, Path, description = "Guild ID"),
         ("channel_id" = Uuid, Path, description = "Channel ID"),
@@ -66,7 +225,10 @@ pub async fn send_message_handler(
     let channel_id = ChannelId(Id(channel_id));
 
     let mut content = String::new();
-    let mut attachment_inputs: Vec<AttachmentInput> = Vec::new();
+    let mut embeds: Vec<Embed> = Vec::new();
+    let mut poll: Option<PollInput> = None;
+    let mut files: Vec<AttachmentFile> = Vec::new();
+    let mut upload_ids: Vec<Uuid> = Vec::new();
     let mut encryption = EncryptionMeta::default();
     let bucket = &state.args.storage.bucket;
 
@@ -83,6 +245,29 @@ pub async fn send_message_handler(
             content = field.text().await.map_err(|e| ApiError::Unknown {
                 message: format!("failed to read content field: {}", e),
             })?;
+        } else if field_name == "embeds" {
+            let val = field.text().await.map_err(|e| ApiError::Unknown {
+                message: format!("failed to read embeds field: {}", e),
+            })?;
+            embeds = serde_json::from_str(&val).map_err(|e| ApiError::BadRequest {
+                message: format!("invalid embeds: {}", e),
+            })?;
+        } else if field_name == "poll" {
+            let val = field.text().await.map_err(|e| ApiError::Unknown {
+                message: format!("failed to read poll field: {}", e),
+            })?;
+            let request: PollRequest =
+                serde_json::from_str(&val).map_err(|e| ApiError::BadRequest {
+                    message: format!("invalid poll: {}", e),
+                })?;
+            poll = Some(request.into());
+        } else if field_name == "uploads" {
+            let val = field.text().await.map_err(|e| ApiError::Unknown {
+                message: format!("failed to read uploads field: {}", e),
+            })?;
+            upload_ids = serde_json::from_str(&val).map_err(|e| ApiError::BadRequest {
+                message: format!("invalid uploads: {}", e),
+            })?;
         } else if field_name == "encrypted" {
             let val = field.text().await.unwrap_or_default();
             encryption.encrypted = val == "true";
@@ -105,44 +290,90 @@ pub async fn send_message_handler(
             let data = field.bytes().await.map_err(|e| ApiError::Unknown {
                 message: format!("failed to read file '{}': {}", filename, e),
             })?;
-            let size_bytes = data.len() as i64;
-
-            let id = AttachmentId::new();
-            let storage_key = format!("attachments/{}/{}", channel_id.get_uuid(), id.get_uuid());
-
-            state
-                .storage
-                .put_object(bucket, &storage_key, data, &content_type)
-                .await
-                .map_err(|e| {
-                    error!("failed to upload attachment '{}': {}", filename, e);
-                    ApiError::Unknown {
-                        message: format!("failed to upload attachment: {}", e),
-                    }
-                })?;
 
-            attachment_inputs.push(AttachmentInput {
-                id,
+            // Stored only once the message is otherwise ready.
+            files.push(AttachmentFile {
                 filename,
                 content_type,
-                size_bytes,
-                storage_key,
+                data,
             });
         }
     }
 
-    let mut message = state
-        .message_service
-        .send_message(
-            identity,
-            guild_id.clone(),
-            channel_id.clone(),
-            content,
-            attachment_inputs,
-            encryption,
-        )
+    // Files uploaded straight to storage are claimed for this message and
+    // copied out of reach of their uploader, then linked once the copies
+    // match what was declared.
+    let uploads = state
+        .attachment_upload_service
+        .claim_uploads(&identity, &channel_id, upload_ids)
         .await
         .map_err(map_core_error)?;
+    let upload_ids: Vec<Uuid> = uploads.iter().map(|upload| upload.id).collect();
+    let upload_keys: Vec<String> = uploads
+        .iter()
+        .map(|upload| upload.storage_key.clone())
+        .collect();
+    let linked = match link_uploads(&state, uploads).await {
+        Ok(linked) => linked,
+        Err(e) => {
+            release_uploads(&state, &upload_ids).await;
+            return Err(e);
+        }
+    };
+    let linked_keys: Vec<String> = linked
+        .iter()
+        .map(|input| input.storage_key.clone())
+        .collect();
+
+    // Files sent in the request are stored last, and removed again along
+    // with the linked copies if the message is refused.
+    let message = post_with_files(
+        &state.storage,
+        bucket,
+        channel_id.get_uuid(),
+        files,
+        |mut attachment_inputs| {
+            attachment_inputs.extend(linked);
+            let identity = identity.clone();
+            let guild_id = guild_id.clone();
+            let channel_id = channel_id.clone();
+            let state = &state;
+            async move {
+                state
+                    .message_service
+                    .send_message(
+                        identity,
+                        guild_id,
+                        channel_id,
+                        content,
+                        embeds,
+                        attachment_inputs,
+                        encryption,
+                        poll,
+                    )
+                    .await
+                    .map_err(map_core_error)
+            }
+        },
+    )
+    .await;
+    let mut message = match message {
+        Ok(message) => message,
+        Err(e) => {
+            delete_objects(&state.storage, bucket, &linked_keys).await;
+            release_uploads(&state, &upload_ids).await;
+            return Err(e);
+        }
+    };
+
+    if let Err(e) = state
+        .attachment_upload_service
+        .complete_uploads(&upload_ids)
+        .await
+    {
+        warn!("failed to complete attachment uploads: {:?}", e);
+    }
+    delete_objects(&state.storage, bucket, &upload_keys).await;
 
     // Populate presigned URLs for all attachments
     for attachment in &mut message.attachments {
@@ -161,23 +392,7 @@ pub async fn send_message_handler(
             })?;
     }
 
-    let room = channel_room(&channel_id);
-    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
-        "type": "message.new",
-        "room": room,
-        "data": &message,
-    })) {
-        state.hub.publish(&room, payload).await;
-    }
-
-    let guild_room = guild_room(&guild_id);
-    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
-        "type": "message.new",
-        "room": guild_room,
-        "data": &message,
-    })) {
-        state.hub.publish(&guild_room, payload).await;
-    }
+    publish_new_message(&state, identity, &guild_id, &message).await;
 
     Ok(Response::Created(message))
 }
@@ -9,12 +9,14 @@ use ferriscord_entities::{
     channel::{Channel, ChannelId, PermissionOverwrite},
     guild::GuildId,
 };
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_server::http::response::Response;
 use serde::Deserialize;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath, Deserialize)]
@@ -93,5 +95,13 @@ pub async fn update_channel_handler(
         state.hub.publish(&guild_room, payload).await;
     }
 
+    dispatch_guild_event(
+        &state,
+        *guild_id.get_uuid(),
+        GuildEventType::ChannelUpdate,
+        &channel,
+    )
+    .await;
+
     Ok(Response::OK(channel))
 }
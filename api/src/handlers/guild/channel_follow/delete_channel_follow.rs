@@ -0,0 +1,64 @@
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::channel_follow::ports::ChannelFollowService;
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId};
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/follows/{follow_id}")]
+pub struct DeleteChannelFollowRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    pub follow_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/guilds/{guild_id}/channels/{channel_id}/follows/{follow_id}",
+    tag = "channel follows",
+    summary = "Unfollow an announcement channel",
+    description = "Stops the channel from receiving new announcements. Copies already posted stay. Requires MANAGE_WEBHOOKS.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Following channel ID"),
+        ("follow_id" = Uuid, Path, description = "Channel follow ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 204, description = "Channel unfollowed"),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_WEBHOOKS permission", body = ApiError),
+        (status = 404, description = "Channel follow not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn delete_channel_follow_handler(
+    DeleteChannelFollowRoute {
+        guild_id,
+        channel_id,
+        follow_id,
+    }: DeleteChannelFollowRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .channel_follow_service
+        .delete_channel_follow(
+            identity,
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+            follow_id,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,86 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::channel_follow::ports::ChannelFollowService,
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, channel::ChannelId, channel_follow::ChannelFollow, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/followers")]
+pub struct FollowChannelRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct FollowChannelRequest {
+    /// Text channel of another guild that receives the announcements.
+    pub target_channel_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/channels/{channel_id}/followers",
+    tag = "channel follows",
+    summary = "Follow an announcement channel",
+    description = "Has a text channel of another guild receive a copy of every message published in this announcement channel. Requires VIEW_CHANNEL on the announcement channel and MANAGE_WEBHOOKS on the target channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID of the announcement channel"),
+        ("channel_id" = Uuid, Path, description = "Announcement channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = FollowChannelRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = ChannelFollow),
+        (status = 400, description = "Not an announcement channel, invalid target, already followed or too many follows", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing VIEW_CHANNEL or MANAGE_WEBHOOKS permission", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn follow_channel_handler(
+    FollowChannelRoute {
+        guild_id,
+        channel_id,
+    }: FollowChannelRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<FollowChannelRequest>,
+) -> Result<Response<ChannelFollow>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let follow = state
+        .channel_follow_service
+        .follow_channel(
+            identity,
+            user.id.0,
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+            ChannelId(Id(req.target_channel_id)),
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::Created(follow))
+}
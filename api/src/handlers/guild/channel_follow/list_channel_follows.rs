@@ -0,0 +1,53 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::channel_follow::ports::ChannelFollowService;
+use ferriscord_entities::{Id, channel::ChannelId, channel_follow::ChannelFollow, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/follows")]
+pub struct ListChannelFollowsRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/channels/{channel_id}/follows",
+    tag = "channel follows",
+    summary = "List followed channels",
+    description = "Lists the announcement channels this channel follows. Requires MANAGE_WEBHOOKS.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Following channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<ChannelFollow>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_WEBHOOKS permission", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_channel_follows_handler(
+    ListChannelFollowsRoute {
+        guild_id,
+        channel_id,
+    }: ListChannelFollowsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<ChannelFollow>>, ApiError> {
+    let follows = state
+        .channel_follow_service
+        .list_channel_follows(identity, GuildId(Id(guild_id)), ChannelId(Id(channel_id)))
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(follows))
+}
@@ -0,0 +1,3 @@
+pub mod delete_channel_follow;
+pub mod follow_channel;
+pub mod list_channel_follows;
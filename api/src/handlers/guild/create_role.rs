@@ -2,6 +2,7 @@ use axum::{Extension, Json, extract::State};
 use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
 use ferriscord_entities::role::Role;
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_server::http::response::Response;
 use ferriscord_core::guild::domain::role::{entities::CreateRoleInput, ports::RoleService};
@@ -9,6 +10,7 @@ use serde::Deserialize;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath, Deserialize)]
@@ -71,5 +73,7 @@ pub async fn create_role_handler(
             message: e.to_string(),
         })?;
 
+    dispatch_guild_event(&state, guild_id, GuildEventType::RoleCreate, &role).await;
+
     Ok(Response::Created(role))
 }
@@ -1,6 +1,7 @@
 use axum::{Extension, extract::State};
 use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_server::http::response::Response;
 use ferriscord_core::guild::domain::role::{entities::DeleteRoleInput, ports::RoleService};
@@ -8,6 +9,8 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::member_list::publish_guild_event;
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath, Deserialize)]
@@ -61,6 +64,22 @@ pub async fn delete_role_handler(
         .map_err(|e| ApiError::Unknown {
             message: e.to_string(),
         })?;
+
+    publish_guild_event(
+        &state.hub,
+        guild_id,
+        "role.delete",
+        serde_json::json!({ "role_id": role_id }),
+    )
+    .await;
+    dispatch_guild_event(
+        &state,
+        guild_id,
+        GuildEventType::RoleDelete,
+        serde_json::json!({ "role_id": role_id }),
+    )
+    .await;
+
     Ok(Response::OK(DeleteRoleResponse {
         message: "role deleted".to_string(),
     }))
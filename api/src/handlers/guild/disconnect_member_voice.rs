@@ -0,0 +1,52 @@
+use axum::extract::{Extension, State};
+use axum::http::StatusCode;
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::voice::ports::VoiceService;
+use ferriscord_entities::guild::GuildId;
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState, voice::voice_left};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/members/{user_id}/voice")]
+pub struct DisconnectMemberVoiceRoute {
+    pub guild_id: Uuid,
+    pub user_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/guilds/{guild_id}/members/{user_id}/voice",
+    tag = "voice",
+    summary = "Disconnect a member from voice",
+    description = "Requires MOVE_MEMBERS in the member's voice channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("user_id" = Uuid, Path, description = "Member user ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 204, description = "Member disconnected"),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden, or the member is not connected", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn disconnect_member_voice_handler(
+    DisconnectMemberVoiceRoute { guild_id, user_id }: DisconnectMemberVoiceRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    let left = state
+        .voice_service
+        .disconnect_member(identity, GuildId::from(guild_id), user_id)
+        .await
+        .map_err(map_core_error)?;
+
+    voice_left(&state, &left).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
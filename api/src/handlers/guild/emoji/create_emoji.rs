@@ -0,0 +1,156 @@
+use axum::{
+    body::Bytes,
+    extract::{Extension, Multipart, State},
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::emoji::{
+        image::inspect_image,
+        ports::{CreateEmojiInput, EmojiService},
+    },
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{emoji::GuildEmoji, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use ferriscord_storage::StoragePort;
+use serde::Deserialize;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use super::{populate_emoji_url, publish_emojis_update};
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/emojis")]
+pub struct CreateEmojiRoute {
+    pub guild_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/emojis",
+    tag = "emojis",
+    summary = "Create an emoji",
+    description = "Uploads a custom emoji. Send as multipart/form-data with a `name` field, an `image` field (PNG, JPEG, GIF or WebP, animated or not, at most 256 KiB and 256x256 pixels) and an optional `roles` field with a JSON array of the role IDs allowed to use it. Requires MANAGE_EMOJIS.",
+    params(("guild_id" = Uuid, Path, description = "Guild ID")),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 201, body = GuildEmoji),
+        (status = 400, description = "Invalid name, image or roles, or emoji limit reached", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_EMOJIS permission", body = ApiError),
+        (status = 404, description = "Guild not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_emoji_handler(
+    CreateEmojiRoute { guild_id }: CreateEmojiRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    mut multipart: Multipart,
+) -> Result<Response<GuildEmoji>, ApiError> {
+    let bucket = &state.args.storage.bucket;
+
+    let mut name = String::new();
+    let mut roles: Vec<Uuid> = Vec::new();
+    let mut data = Bytes::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| ApiError::BadRequest {
+            message: format!("invalid multipart request: {}", e),
+        })?
+    {
+        match field.name().unwrap_or("") {
+            "name" => {
+                name = field
+                    .text()
+                    .await
+                    .map_err(|e| ApiError::BadRequest {
+                        message: format!("failed to read name field: {}", e),
+                    })?
+                    .trim()
+                    .to_string();
+            }
+            "roles" => {
+                let value = field.text().await.map_err(|e| ApiError::BadRequest {
+                    message: format!("failed to read roles field: {}", e),
+                })?;
+                roles = serde_json::from_str(&value).map_err(|e| ApiError::BadRequest {
+                    message: format!("invalid roles: {}", e),
+                })?;
+            }
+            "image" => {
+                data = field.bytes().await.map_err(|e| ApiError::BadRequest {
+                    message: format!("failed to read image field: {}", e),
+                })?;
+            }
+            _ => {}
+        }
+    }
+
+    // The declared content type is ignored: the image is what its bytes say.
+    let image = inspect_image(&data).map_err(map_core_error)?;
+
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let id = Uuid::now_v7();
+    let storage_key = format!("emojis/{}/{}", guild_id, id);
+    let size_bytes = data.len() as i64;
+    state
+        .storage
+        .put_object(bucket, &storage_key, data, image.content_type)
+        .await
+        .map_err(|e| {
+            error!("failed to upload emoji image: {}", e);
+            ApiError::Unknown {
+                message: format!("failed to upload image: {}", e),
+            }
+        })?;
+
+    let created = state
+        .emoji_service
+        .create_emoji(
+            identity,
+            user.id.0,
+            GuildId::from(guild_id),
+            CreateEmojiInput {
+                id,
+                name,
+                roles,
+                image,
+                size_bytes,
+                storage_key: storage_key.clone(),
+            },
+        )
+        .await;
+    let mut emoji = match created {
+        Ok(emoji) => emoji,
+        Err(e) => {
+            if let Err(e) = state.storage.delete_object(bucket, &storage_key).await {
+                warn!(
+                    "failed to delete unused emoji image '{}': {}",
+                    storage_key, e
+                );
+            }
+            return Err(map_core_error(e));
+        }
+    };
+
+    populate_emoji_url(&state, &mut emoji).await?;
+    publish_emojis_update(&state.hub, guild_id).await;
+
+    Ok(Response::Created(emoji))
+}
@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::emoji::ports::EmojiService;
+use ferriscord_entities::guild::GuildId;
+use ferriscord_error::ApiError;
+use ferriscord_storage::StoragePort;
+use serde::Deserialize;
+use tracing::warn;
+use uuid::Uuid;
+
+use super::publish_emojis_update;
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/emojis/{emoji_id}")]
+pub struct DeleteEmojiRoute {
+    pub guild_id: Uuid,
+    pub emoji_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/guilds/{guild_id}/emojis/{emoji_id}",
+    tag = "emojis",
+    summary = "Delete an emoji",
+    description = "Deletes an emoji and its image. Messages using it show its text form. Requires MANAGE_EMOJIS.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("emoji_id" = Uuid, Path, description = "Emoji ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 204, description = "Emoji deleted"),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_EMOJIS permission", body = ApiError),
+        (status = 404, description = "Emoji not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn delete_emoji_handler(
+    DeleteEmojiRoute { guild_id, emoji_id }: DeleteEmojiRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    let emoji = state
+        .emoji_service
+        .delete_emoji(identity, GuildId::from(guild_id), emoji_id)
+        .await
+        .map_err(map_core_error)?;
+
+    // The emoji is gone either way; a leftover image is only wasted space.
+    if let Err(e) = state
+        .storage
+        .delete_object(&state.args.storage.bucket, &emoji.storage_key)
+        .await
+    {
+        warn!(
+            "failed to delete emoji image '{}': {}",
+            emoji.storage_key, e
+        );
+    }
+    publish_emojis_update(&state.hub, guild_id).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
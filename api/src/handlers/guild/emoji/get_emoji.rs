@@ -0,0 +1,47 @@
+use axum::extract::State;
+use axum_extra::routing::TypedPath;
+use ferriscord_core::guild::domain::emoji::ports::EmojiService;
+use ferriscord_entities::emoji::GuildEmoji;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::populate_emoji_url;
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/emojis/{emoji_id}")]
+pub struct GetEmojiRoute {
+    pub emoji_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/emojis/{emoji_id}",
+    tag = "emojis",
+    summary = "Get an emoji",
+    description = "Returns any custom emoji by ID, so messages using emojis of other guilds can be rendered.",
+    params(("emoji_id" = Uuid, Path, description = "Emoji ID")),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = GuildEmoji),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Emoji not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_emoji_handler(
+    GetEmojiRoute { emoji_id }: GetEmojiRoute,
+    State(state): State<AppState>,
+) -> Result<Response<GuildEmoji>, ApiError> {
+    let mut emoji = state
+        .emoji_service
+        .get_emoji(emoji_id)
+        .await
+        .map_err(map_core_error)?;
+
+    populate_emoji_url(&state, &mut emoji).await?;
+
+    Ok(Response::OK(emoji))
+}
@@ -0,0 +1,52 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::emoji::ports::EmojiService;
+use ferriscord_entities::{emoji::GuildEmoji, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::populate_emoji_url;
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/emojis")]
+pub struct ListEmojisRoute {
+    pub guild_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/emojis",
+    tag = "emojis",
+    summary = "List guild emojis",
+    description = "Returns the custom emojis of a guild, sorted by name. Requires VIEW_GUILD.",
+    params(("guild_id" = Uuid, Path, description = "Guild ID")),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<GuildEmoji>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden", body = ApiError),
+        (status = 404, description = "Guild not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_emojis_handler(
+    ListEmojisRoute { guild_id }: ListEmojisRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<GuildEmoji>>, ApiError> {
+    let mut emojis = state
+        .emoji_service
+        .list_emojis(identity, GuildId::from(guild_id))
+        .await
+        .map_err(map_core_error)?;
+
+    for emoji in &mut emojis {
+        populate_emoji_url(&state, emoji).await?;
+    }
+
+    Ok(Response::OK(emojis))
+}
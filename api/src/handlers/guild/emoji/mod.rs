@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use ferriscord_entities::emoji::GuildEmoji;
+use ferriscord_error::ApiError;
+use ferriscord_storage::StoragePort;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{member_list::publish_guild_event, state::AppState, ws::WsHub};
+
+pub mod create_emoji;
+pub mod delete_emoji;
+pub mod get_emoji;
+pub mod list_emojis;
+pub mod update_emoji;
+
+/// Emoji images are linked from messages, so their URLs live as long as
+/// guild icons do.
+const EMOJI_URL_TTL: Duration = Duration::from_secs(7 * 24 * 3600);
+
+pub(crate) async fn populate_emoji_url(
+    state: &AppState,
+    emoji: &mut GuildEmoji,
+) -> Result<(), ApiError> {
+    emoji.url = state
+        .storage
+        .presigned_get_url(
+            &state.args.storage.bucket,
+            &emoji.storage_key,
+            EMOJI_URL_TTL,
+        )
+        .await
+        .map_err(|e| {
+            error!(
+                "failed to generate presigned URL for '{}': {}",
+                emoji.storage_key, e
+            );
+            ApiError::Unknown {
+                message: format!("failed to generate emoji URL: {}", e),
+            }
+        })?;
+    Ok(())
+}
+
+/// Tells the guild its emojis changed, so clients refetch the list.
+pub(crate) async fn publish_emojis_update(hub: &WsHub, guild_id: Uuid) {
+    publish_guild_event(
+        hub,
+        guild_id,
+        "guild.emojis_update",
+        serde_json::json!({ "guild_id": guild_id }),
+    )
+    .await;
+}
@@ -0,0 +1,77 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::emoji::ports::{EmojiService, UpdateEmojiInput};
+use ferriscord_entities::{emoji::GuildEmoji, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{populate_emoji_url, publish_emojis_update};
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/emojis/{emoji_id}")]
+pub struct UpdateEmojiRoute {
+    pub guild_id: Uuid,
+    pub emoji_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateEmojiRequest {
+    pub name: Option<String>,
+    /// Roles allowed to use the emoji. An empty list lets everyone use it.
+    pub roles: Option<Vec<Uuid>>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/guilds/{guild_id}/emojis/{emoji_id}",
+    tag = "emojis",
+    summary = "Update an emoji",
+    description = "Renames an emoji or changes the roles allowed to use it. Requires MANAGE_EMOJIS.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("emoji_id" = Uuid, Path, description = "Emoji ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = UpdateEmojiRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = GuildEmoji),
+        (status = 400, description = "Invalid name or roles", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_EMOJIS permission", body = ApiError),
+        (status = 404, description = "Emoji not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn update_emoji_handler(
+    UpdateEmojiRoute { guild_id, emoji_id }: UpdateEmojiRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<UpdateEmojiRequest>,
+) -> Result<Response<GuildEmoji>, ApiError> {
+    let mut emoji = state
+        .emoji_service
+        .update_emoji(
+            identity,
+            GuildId::from(guild_id),
+            emoji_id,
+            UpdateEmojiInput {
+                name: req.name,
+                roles: req.roles,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    populate_emoji_url(&state, &mut emoji).await?;
+    publish_emojis_update(&state.hub, guild_id).await;
+
+    Ok(Response::OK(emoji))
+}
@@ -0,0 +1,89 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::event_subscription::ports::{
+        CreateEventSubscriptionInput, EventSubscriptionService,
+    },
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    event_subscription::{EventSubscription, GuildEventType},
+    guild::GuildId,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/event-subscriptions")]
+pub struct CreateEventSubscriptionRoute {
+    pub guild_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateEventSubscriptionRequest {
+    /// http or https endpoint the events are POSTed to.
+    pub url: String,
+    pub event_types: Vec<GuildEventType>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/event-subscriptions",
+    tag = "event-subscriptions",
+    summary = "Create an event subscription",
+    description = "Subscribes an HTTP endpoint to guild events. The endpoint must be a public address on the default port. Each event is POSTed as JSON with an `X-Ferriscord-Signature: sha256=<hex>` header, the HMAC-SHA256 of `{X-Ferriscord-Timestamp}.{body}` keyed by the subscription secret. The response holds the secret, which is not shown again. Failed deliveries are retried with exponential backoff; the subscription is disabled after 20 failures in a row. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = CreateEventSubscriptionRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = EventSubscription),
+        (status = 400, description = "Invalid URL or event types, or too many subscriptions", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_event_subscription_handler(
+    CreateEventSubscriptionRoute { guild_id }: CreateEventSubscriptionRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<CreateEventSubscriptionRequest>,
+) -> Result<Response<EventSubscription>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let subscription = state
+        .event_subscription_service
+        .create_subscription(
+            identity,
+            user.id.0,
+            GuildId::from(guild_id),
+            CreateEventSubscriptionInput {
+                url: req.url,
+                event_types: req.event_types,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::Created(subscription))
+}
@@ -0,0 +1,56 @@
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::event_subscription::ports::EventSubscriptionService;
+use ferriscord_entities::guild::GuildId;
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/event-subscriptions/{subscription_id}")]
+pub struct DeleteEventSubscriptionRoute {
+    pub guild_id: Uuid,
+    pub subscription_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/guilds/{guild_id}/event-subscriptions/{subscription_id}",
+    tag = "event-subscriptions",
+    summary = "Delete an event subscription",
+    description = "Deletes an event subscription together with its delivery log. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("subscription_id" = Uuid, Path, description = "Event subscription ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 204, description = "Event subscription deleted"),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 404, description = "Event subscription not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn delete_event_subscription_handler(
+    DeleteEventSubscriptionRoute {
+        guild_id,
+        subscription_id,
+    }: DeleteEventSubscriptionRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .event_subscription_service
+        .delete_subscription(identity, GuildId::from(guild_id), subscription_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
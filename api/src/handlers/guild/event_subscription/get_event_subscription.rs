@@ -0,0 +1,54 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::event_subscription::ports::EventSubscriptionService;
+use ferriscord_entities::{event_subscription::EventSubscription, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/event-subscriptions/{subscription_id}")]
+pub struct GetEventSubscriptionRoute {
+    pub guild_id: Uuid,
+    pub subscription_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/event-subscriptions/{subscription_id}",
+    tag = "event-subscriptions",
+    summary = "Get an event subscription",
+    description = "Returns an event subscription, including whether it was disabled after repeated failures. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("subscription_id" = Uuid, Path, description = "Event subscription ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = EventSubscription),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 404, description = "Event subscription not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_event_subscription_handler(
+    GetEventSubscriptionRoute {
+        guild_id,
+        subscription_id,
+    }: GetEventSubscriptionRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<EventSubscription>, ApiError> {
+    let subscription = state
+        .event_subscription_service
+        .get_subscription(identity, GuildId::from(guild_id), subscription_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(subscription))
+}
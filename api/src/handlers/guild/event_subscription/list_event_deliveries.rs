@@ -0,0 +1,68 @@
+use axum::extract::{Extension, Query, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::event_subscription::ports::EventSubscriptionService;
+use ferriscord_entities::{event_subscription::EventDelivery, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/event-subscriptions/{subscription_id}/deliveries")]
+pub struct ListEventDeliveriesRoute {
+    pub guild_id: Uuid,
+    pub subscription_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListEventDeliveriesQuery {
+    /// Maximum number of deliveries to return (1-100, default 50)
+    pub limit: Option<u32>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/event-subscriptions/{subscription_id}/deliveries",
+    tag = "event-subscriptions",
+    summary = "List event deliveries",
+    description = "Returns the delivery log of a subscription, most recent first: each event's body, attempts, last HTTP status and error. Finished deliveries are kept for 7 days. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("subscription_id" = Uuid, Path, description = "Event subscription ID"),
+        ListEventDeliveriesQuery,
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<EventDelivery>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 404, description = "Event subscription not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_event_deliveries_handler(
+    ListEventDeliveriesRoute {
+        guild_id,
+        subscription_id,
+    }: ListEventDeliveriesRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Query(query): Query<ListEventDeliveriesQuery>,
+) -> Result<Response<Vec<EventDelivery>>, ApiError> {
+    let deliveries = state
+        .event_subscription_service
+        .list_deliveries(
+            identity,
+            GuildId::from(guild_id),
+            subscription_id,
+            query.limit.unwrap_or(50),
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(deliveries))
+}
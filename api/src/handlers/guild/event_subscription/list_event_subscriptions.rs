@@ -0,0 +1,48 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::event_subscription::ports::EventSubscriptionService;
+use ferriscord_entities::{event_subscription::EventSubscription, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/event-subscriptions")]
+pub struct ListEventSubscriptionsRoute {
+    pub guild_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/event-subscriptions",
+    tag = "event-subscriptions",
+    summary = "List event subscriptions",
+    description = "Lists the event subscriptions of a guild. Secrets are not included. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<EventSubscription>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_event_subscriptions_handler(
+    ListEventSubscriptionsRoute { guild_id }: ListEventSubscriptionsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<EventSubscription>>, ApiError> {
+    let subscriptions = state
+        .event_subscription_service
+        .list_subscriptions(identity, GuildId::from(guild_id))
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(subscriptions))
+}
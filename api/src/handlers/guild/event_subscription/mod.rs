@@ -0,0 +1,7 @@
+pub mod create_event_subscription;
+pub mod delete_event_subscription;
+pub mod get_event_subscription;
+pub mod list_event_deliveries;
+pub mod list_event_subscriptions;
+pub mod ping_event_subscription;
+pub mod update_event_subscription;
@@ -0,0 +1,55 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::event_subscription::ports::EventSubscriptionService;
+use ferriscord_entities::{event_subscription::EventDelivery, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/event-subscriptions/{subscription_id}/ping")]
+pub struct PingEventSubscriptionRoute {
+    pub guild_id: Uuid,
+    pub subscription_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/event-subscriptions/{subscription_id}/ping",
+    tag = "event-subscriptions",
+    summary = "Ping an event subscription",
+    description = "Queues a signed `ping` event for the endpoint, to check it is reachable and verifies signatures. Its outcome shows up in the delivery log. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("subscription_id" = Uuid, Path, description = "Event subscription ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 202, body = EventDelivery),
+        (status = 400, description = "The subscription is disabled", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 404, description = "Event subscription not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn ping_event_subscription_handler(
+    PingEventSubscriptionRoute {
+        guild_id,
+        subscription_id,
+    }: PingEventSubscriptionRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<EventDelivery>, ApiError> {
+    let delivery = state
+        .event_subscription_service
+        .ping_subscription(identity, GuildId::from(guild_id), subscription_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::Accepted(delivery))
+}
@@ -0,0 +1,83 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::event_subscription::ports::{
+    EventSubscriptionService, UpdateEventSubscriptionInput,
+};
+use ferriscord_entities::{
+    event_subscription::{EventSubscription, GuildEventType},
+    guild::GuildId,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/event-subscriptions/{subscription_id}")]
+pub struct UpdateEventSubscriptionRoute {
+    pub guild_id: Uuid,
+    pub subscription_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateEventSubscriptionRequest {
+    pub url: Option<String>,
+    pub event_types: Option<Vec<GuildEventType>>,
+    /// Re-enabling a subscription resets its failure count.
+    pub enabled: Option<bool>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/guilds/{guild_id}/event-subscriptions/{subscription_id}",
+    tag = "event-subscriptions",
+    summary = "Update an event subscription",
+    description = "Changes the URL or event types of a subscription, or enables or disables it. Disabling it fails its pending deliveries. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("subscription_id" = Uuid, Path, description = "Event subscription ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = UpdateEventSubscriptionRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = EventSubscription),
+        (status = 400, description = "Invalid URL or event types", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 404, description = "Event subscription not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn update_event_subscription_handler(
+    UpdateEventSubscriptionRoute {
+        guild_id,
+        subscription_id,
+    }: UpdateEventSubscriptionRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<UpdateEventSubscriptionRequest>,
+) -> Result<Response<EventSubscription>, ApiError> {
+    let subscription = state
+        .event_subscription_service
+        .update_subscription(
+            identity,
+            GuildId::from(guild_id),
+            subscription_id,
+            UpdateEventSubscriptionInput {
+                url: req.url,
+                event_types: req.event_types,
+                enabled: req.enabled,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(subscription))
+}
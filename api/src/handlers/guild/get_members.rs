@@ -5,11 +5,15 @@ use ferriscord_entities::{Id, guild::GuildId};
 use ferriscord_error::ApiError;
 use ferriscord_server::http::response::Response;
 use ferriscord_core::guild::domain::member::ports::MemberRepository;
+use ferriscord_core::user::domain::presence::ports::PresenceService;
 use serde::Serialize;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::{presence::PresenceStatus, state::AppState};
+use crate::{
+    presence::{CustomStatus, PresenceStatus},
+    state::AppState,
+};
 
 #[derive(TypedPath, serde::Deserialize)]
 #[typed_path("/guilds/{guild_id}/members")]
@@ -33,6 +37,7 @@ pub struct GuildMemberResponse {
     pub avatar_url: Option<String>,
     pub joined_at: chrono::DateTime<chrono::Utc>,
     pub status: PresenceStatus,
+    pub custom_status: Option<CustomStatus>,
     pub roles: Vec<RoleSummaryResponse>,
 }
 
@@ -60,10 +65,14 @@ pub async fn get_members_handler(
         .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
 
     let user_ids: Vec<uuid::Uuid> = members.iter().map(|m| m.user_id).collect();
-    let presences = state.presence.get_many(&user_ids).await;
+    let mut presences = state
+        .presence_service
+        .get_many(&user_ids)
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
 
     let response = members.into_iter().map(|m| {
-        let status = presences.get(&m.user_id).cloned().unwrap_or_default();
+        let presence = presences.remove(&m.user_id);
         GuildMemberResponse {
             member_id: m.member_id,
             user_id: m.user_id,
@@ -71,7 +80,8 @@ pub async fn get_members_handler(
             display_name: m.display_name,
             avatar_url: m.avatar_url,
             joined_at: m.joined_at,
-            status,
+            status: presence.as_ref().map(|p| p.status).unwrap_or_default(),
+            custom_status: presence.and_then(|p| p.custom_status),
             roles: m.roles.into_iter().map(|r| RoleSummaryResponse {
                 id: r.id,
                 name: r.name,
@@ -0,0 +1,57 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::voice::ports::VoiceService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{guild::GuildId, voice_state::VoiceState};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/voice-states")]
+pub struct GetVoiceStatesRoute {
+    pub guild_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/voice-states",
+    tag = "voice",
+    summary = "List voice states of a guild",
+    description = "Returns who is connected to which voice channel of the guild, with their self-mute and self-deaf flags.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<VoiceState>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_voice_states_handler(
+    GetVoiceStatesRoute { guild_id }: GetVoiceStatesRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<VoiceState>>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let voice_states = state
+        .voice_service
+        .list_guild_voice_states(identity, user.id.0, GuildId::from(guild_id))
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(voice_states))
+}
@@ -0,0 +1,62 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::guild_import::ports::GuildImportService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::guild_import::GuildImport;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{guild_imports::publish_import_update, handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/@me/guild-imports/{import_id}/cancel")]
+pub struct CancelGuildImportRoute {
+    pub import_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/@me/guild-imports/{import_id}/cancel",
+    tag = "guild imports",
+    summary = "Cancel a guild import",
+    description = "Cancels an import that is not finished. What it already imported is kept.",
+    params(("import_id" = Uuid, Path, description = "Guild import ID")),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = GuildImport),
+        (status = 400, description = "The import is finished", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Guild import not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn cancel_guild_import_handler(
+    CancelGuildImportRoute { import_id }: CancelGuildImportRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<GuildImport>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let import = state
+        .guild_import_service
+        .cancel_import(user.id.0, import_id)
+        .await
+        .map_err(map_core_error)?;
+
+    publish_import_update(&state.hub, &import).await;
+
+    Ok(Response::OK(import))
+}
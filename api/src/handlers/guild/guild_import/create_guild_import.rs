@@ -0,0 +1,69 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::guild_import::ports::{CreateImportInput, GuildImportService},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::guild_import::GuildImport;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+
+use super::CreateGuildImportRequest;
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath)]
+#[typed_path("/users/@me/guild-imports")]
+pub struct CreateGuildImportRoute;
+
+#[utoipa::path(
+    post,
+    path = "/users/@me/guild-imports",
+    tag = "guild imports",
+    summary = "Import a guild from Discord",
+    description = "Creates an import of a Discord server, exported with DiscordChatExporter in JSON. Upload every exported channel to it, then start it. The caller owns the imported guild. Bots cannot import guilds.",
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = CreateGuildImportRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = GuildImport),
+        (status = 400, description = "Invalid name or user mappings, or too many imports in progress", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_guild_import_handler(
+    _: CreateGuildImportRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<CreateGuildImportRequest>,
+) -> Result<Response<GuildImport>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let import = state
+        .guild_import_service
+        .create_import(
+            identity,
+            user.id.0,
+            CreateImportInput {
+                name: req.name,
+                dry_run: req.dry_run,
+                user_mappings: req.user_mappings,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::Created(import))
+}
@@ -0,0 +1,59 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::guild_import::ports::GuildImportService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::guild_import::GuildImport;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/@me/guild-imports/{import_id}")]
+pub struct GetGuildImportRoute {
+    pub import_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/@me/guild-imports/{import_id}",
+    tag = "guild imports",
+    summary = "Get a guild import",
+    description = "Returns an import of the caller with its progress. While it runs, its progress is also sent as `guild_import.update` events to the caller's sessions.",
+    params(("import_id" = Uuid, Path, description = "Guild import ID")),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = GuildImport),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Guild import not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_guild_import_handler(
+    GetGuildImportRoute { import_id }: GetGuildImportRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<GuildImport>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let import = state
+        .guild_import_service
+        .get_import(user.id.0, import_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(import))
+}
@@ -0,0 +1,53 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::guild_import::ports::GuildImportService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::guild_import::GuildImport;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath)]
+#[typed_path("/users/@me/guild-imports")]
+pub struct ListGuildImportsRoute;
+
+#[utoipa::path(
+    get,
+    path = "/users/@me/guild-imports",
+    tag = "guild imports",
+    summary = "List my guild imports",
+    description = "Returns the caller's imports in progress, and those finished in the last 7 days, newest first.",
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<GuildImport>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_guild_imports_handler(
+    _: ListGuildImportsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<GuildImport>>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let imports = state
+        .guild_import_service
+        .list_imports(user.id.0)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(imports))
+}
@@ -0,0 +1,27 @@
+use std::collections::HashMap;
+
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub mod cancel_guild_import;
+pub mod create_guild_import;
+pub mod get_guild_import;
+pub mod list_guild_imports;
+pub mod start_guild_import;
+pub mod upload_guild_import_channel;
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateGuildImportRequest {
+    /// Name of the guild to create, by default that of the exported one.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Only count what would be imported, without creating anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Your Discord user ids, each mapped to your own FerrisCord user id so
+    /// their messages are imported as yours. Mapping to anyone else is
+    /// refused. Other authors are recreated as placeholder users.
+    #[serde(default)]
+    pub user_mappings: HashMap<String, Uuid>,
+}
@@ -0,0 +1,62 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::guild_import::ports::GuildImportService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::guild_import::GuildImport;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{guild_imports::publish_import_update, handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/@me/guild-imports/{import_id}/start")]
+pub struct StartGuildImportRoute {
+    pub import_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/@me/guild-imports/{import_id}/start",
+    tag = "guild imports",
+    summary = "Start a guild import",
+    description = "Queues an import once its channels are uploaded. It then runs in the background, and resumes where it stopped after a restart. A dry run creates nothing and only fills in the progress. The caller must be able to own one more guild, and its name must not be taken.",
+    params(("import_id" = Uuid, Path, description = "Guild import ID")),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = GuildImport),
+        (status = 400, description = "Not uploading, no channel uploaded, too many guilds or name taken", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Guild import not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn start_guild_import_handler(
+    StartGuildImportRoute { import_id }: StartGuildImportRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<GuildImport>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let import = state
+        .guild_import_service
+        .start_import(user.id.0, import_id)
+        .await
+        .map_err(map_core_error)?;
+
+    publish_import_update(&state.hub, &import).await;
+
+    Ok(Response::OK(import))
+}
@@ -0,0 +1,67 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::guild_import::{export::ExportDocument, ports::GuildImportService},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::guild_import::GuildImport;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/@me/guild-imports/{import_id}/channels")]
+pub struct UploadGuildImportChannelRoute {
+    pub import_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/users/@me/guild-imports/{import_id}/channels",
+    tag = "guild imports",
+    summary = "Upload an exported channel",
+    description = "Adds one channel, exported with DiscordChatExporter in JSON, to an import that is not started yet: its category, the roles and authors found in it, and its messages. Text, announcement, voice, stage and forum channels can be imported; system messages are skipped. Up to 100 MiB per channel.",
+    params(("import_id" = Uuid, Path, description = "Guild import ID")),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = ExportDocument,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = GuildImport),
+        (status = 400, description = "Invalid export, channel from another guild or already uploaded, too large an import, or not uploading", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Guild import not found", body = ApiError),
+        (status = 413, description = "Export too large"),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn upload_guild_import_channel_handler(
+    UploadGuildImportChannelRoute { import_id }: UploadGuildImportChannelRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(export): Json<ExportDocument>,
+) -> Result<Response<GuildImport>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let import = state
+        .guild_import_service
+        .upload_channel(user.id.0, import_id, export)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(import))
+}
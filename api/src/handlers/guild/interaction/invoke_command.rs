@@ -0,0 +1,102 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::interaction::ports::{InteractionService, InvokeCommandInput},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    Id,
+    channel::ChannelId,
+    guild::GuildId,
+    interaction::{Interaction, InteractionOption},
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, interactions::deliver_interaction, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/interactions")]
+pub struct InvokeCommandRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InvokeCommandRequest {
+    pub command_id: Uuid,
+    /// Option values by name; users, channels and roles are given by ID.
+    #[serde(default)]
+    pub options: Vec<InteractionOption>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/channels/{channel_id}/interactions",
+    tag = "commands",
+    summary = "Invoke a command",
+    description = "Invokes a slash command in a text channel. The bot receives an `interaction.create` event, or a signed POST to its interactions endpoint, and has 3 seconds to answer. The answer arrives as a `message.new`, or as an `interaction.reply` only the invoker sees; an `interaction.failed` event follows if the bot doesn't answer in time. Requires USE_SLASH_COMMANDS.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = InvokeCommandRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 202, body = Interaction),
+        (status = 400, description = "Invalid options or not a text channel", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing USE_SLASH_COMMANDS permission", body = ApiError),
+        (status = 404, description = "Command not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn invoke_command_handler(
+    InvokeCommandRoute {
+        guild_id,
+        channel_id,
+    }: InvokeCommandRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<InvokeCommandRequest>,
+) -> Result<Response<Interaction>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let invocation = state
+        .interaction_service
+        .invoke_command(
+            identity,
+            user.id.0,
+            GuildId::from(guild_id),
+            ChannelId(Id(channel_id)),
+            InvokeCommandInput {
+                command_id: req.command_id,
+                options: req.options,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    // The token is the bot's to answer with, not the invoker's.
+    let mut interaction = invocation.interaction.clone();
+    interaction.token = None;
+    deliver_interaction(&state, invocation).await;
+
+    Ok(Response::Accepted(interaction))
+}
@@ -0,0 +1,48 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::interaction::ports::InteractionService;
+use ferriscord_entities::{guild::GuildId, interaction::ApplicationCommand};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/commands")]
+pub struct ListGuildCommandsRoute {
+    pub guild_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/commands",
+    tag = "commands",
+    summary = "List a guild's commands",
+    description = "Lists the global and guild commands of the bots in the guild, for command pickers. Members only.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<ApplicationCommand>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Not a member of the guild", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_guild_commands_handler(
+    ListGuildCommandsRoute { guild_id }: ListGuildCommandsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<ApplicationCommand>>, ApiError> {
+    let commands = state
+        .interaction_service
+        .list_guild_commands(identity, GuildId::from(guild_id))
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(commands))
+}
@@ -0,0 +1,2 @@
+pub mod invoke_command;
+pub mod list_guild_commands;
@@ -2,12 +2,15 @@ use axum::{Extension, Json, extract::State};
 use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
 use ferriscord_entities::{guild::Guild, user::UserId as EntityUserId};
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_core::guild::domain::invite::ports::InviteService;
 use ferriscord_core::user::domain::user::ports::UserService;
 use serde::Deserialize;
 use utoipa::ToSchema;
 
+use crate::member_list::publish_guild_event;
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath)]
@@ -56,5 +59,20 @@ pub async fn join_guild_handler(
         .await
         .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
 
+    publish_guild_event(
+        &state.hub,
+        *guild.id.get_uuid(),
+        "member.add",
+        serde_json::json!({ "user_id": user.id.0 }),
+    )
+    .await;
+    dispatch_guild_event(
+        &state,
+        *guild.id.get_uuid(),
+        GuildEventType::MemberJoin,
+        serde_json::json!({ "user_id": user.id.0, "username": user.username }),
+    )
+    .await;
+
     Ok(Json(guild))
 }
@@ -4,11 +4,14 @@ use axum::Extension;
 use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
 use ferriscord_entities::{Id, guild::GuildId, user::UserId};
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::{ApiError, ApiErrorResponse};
 use ferriscord_core::guild::domain::guild::ports::GuildService;
 use ferriscord_core::user::domain::user::ports::UserService;
 use uuid::Uuid;
 
+use crate::member_list::publish_guild_event;
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath, serde::Deserialize)]
@@ -57,5 +60,20 @@ pub async fn leave_guild_handler(
         .await
         .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
 
+    publish_guild_event(
+        &state.hub,
+        *guild_id.get_uuid(),
+        "member.remove",
+        serde_json::json!({ "user_id": user.id.0 }),
+    )
+    .await;
+    dispatch_guild_event(
+        &state,
+        *guild_id.get_uuid(),
+        GuildEventType::MemberLeave,
+        serde_json::json!({ "user_id": user.id.0 }),
+    )
+    .await;
+
     Ok(StatusCode::NO_CONTENT)
 }
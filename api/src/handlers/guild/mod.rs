@@ -1,50 +1,137 @@
 use axum::{Router, extract::DefaultBodyLimit};
 use axum_extra::routing::RouterExt;
+use ferriscord_core::guild::domain::guild_import::ports::MAX_EXPORT_BYTES;
 
 use crate::{
     handlers::guild::{
         assign_member_role::assign_member_role_handler,
+        bot::{add_bot::add_bot_handler, list_bots::list_bots_handler, remove_bot::remove_bot_handler},
         channel::{
-            create_channel::create_channel_handler, delete_channel::delete_channel_handler,
-            delete_message::delete_message_handler, get_channels::get_channels_handler,
-            get_messages::get_messages_handler, send_message::send_message_handler,
-            update_channel::update_channel_handler,
+            ack_message::ack_message_handler,
+            create_attachment_uploads::create_attachment_uploads_handler,
+            create_channel::create_channel_handler,
+            crosspost_message::crosspost_message_handler, delete_channel::delete_channel_handler,
+            delete_message::delete_message_handler, edit_message::edit_message_handler,
+            get_channels::get_channels_handler, get_messages::get_messages_handler,
+            send_message::send_message_handler, update_channel::update_channel_handler,
+        },
+        channel_follow::{
+            delete_channel_follow::delete_channel_follow_handler,
+            follow_channel::follow_channel_handler,
+            list_channel_follows::list_channel_follows_handler,
         },
         create_guild::create_guild_handler,
         create_role::create_role_handler,
         delete_guild::delete_guild_handler,
         delete_role::delete_role_handler,
+        disconnect_member_voice::disconnect_member_voice_handler,
+        emoji::{
+            create_emoji::create_emoji_handler, delete_emoji::delete_emoji_handler,
+            get_emoji::get_emoji_handler, list_emojis::list_emojis_handler,
+            update_emoji::update_emoji_handler,
+        },
+        event_subscription::{
+            create_event_subscription::create_event_subscription_handler,
+            delete_event_subscription::delete_event_subscription_handler,
+            get_event_subscription::get_event_subscription_handler,
+            list_event_deliveries::list_event_deliveries_handler,
+            list_event_subscriptions::list_event_subscriptions_handler,
+            ping_event_subscription::ping_event_subscription_handler,
+            update_event_subscription::update_event_subscription_handler,
+        },
         get_members::get_members_handler,
+        guild_import::{
+            cancel_guild_import::cancel_guild_import_handler,
+            create_guild_import::create_guild_import_handler,
+            get_guild_import::get_guild_import_handler,
+            list_guild_imports::list_guild_imports_handler,
+            start_guild_import::start_guild_import_handler,
+            upload_guild_import_channel::upload_guild_import_channel_handler,
+        },
         get_role::get_role_handler,
         get_roles::get_roles_handler,
+        get_voice_states::get_voice_states_handler,
+        interaction::{
+            invoke_command::invoke_command_handler,
+            list_guild_commands::list_guild_commands_handler,
+        },
         invite::{
             create_invite::create_invite_handler, delete_invite::delete_invite_handler,
             join_guild::join_guild_handler, list_invites::list_invites_handler,
             preview_invite::preview_invite_handler,
         },
         leave_guild::leave_guild_handler,
+        moderate_member_voice::moderate_member_voice_handler,
+        move_member_voice::move_member_voice_handler,
+        poll::{
+            add_poll_vote::add_poll_vote_handler, get_poll::get_poll_handler,
+            remove_poll_vote::remove_poll_vote_handler,
+        },
         remove_member_role::remove_member_role_handler,
+        scheduled_event::{
+            cancel_event::cancel_event_handler,
+            create_calendar_feed::create_calendar_feed_handler,
+            create_event::create_event_handler, get_event::get_event_handler,
+            list_event_rsvps::list_event_rsvps_handler, list_events::list_events_handler,
+            remove_event_rsvp::remove_event_rsvp_handler,
+            set_event_rsvp::set_event_rsvp_handler,
+        },
+        scheduled_message::{
+            cancel_scheduled_message::cancel_scheduled_message_handler,
+            list_scheduled_messages::list_scheduled_messages_handler,
+            schedule_message::schedule_message_handler,
+            schedule_reminder::schedule_reminder_handler,
+            update_scheduled_message::update_scheduled_message_handler,
+        },
+        stage::{
+            add_speaker::add_speaker_handler, end_stage::end_stage_handler,
+            get_stage::get_stage_handler, get_stage_history::get_stage_history_handler,
+            lower_hand::lower_hand_handler, raise_hand::raise_hand_handler,
+            remove_speaker::remove_speaker_handler, start_stage::start_stage_handler,
+            update_stage::update_stage_handler,
+        },
         update_guild::update_guild_handler,
         update_role::update_role_handler,
+        webhook::{
+            create_webhook::create_webhook_handler, delete_webhook::delete_webhook_handler,
+            get_webhook::get_webhook_handler, list_webhooks::list_webhooks_handler,
+            reset_webhook_token::reset_webhook_token_handler,
+            update_webhook::update_webhook_handler,
+        },
     },
     state::AppState,
 };
 
 pub mod assign_member_role;
+pub mod bot;
 pub mod channel;
+pub mod channel_follow;
 pub mod create_guild;
 pub mod create_role;
 pub mod delete_guild;
 pub mod delete_role;
+pub mod disconnect_member_voice;
+pub mod emoji;
+pub mod event_subscription;
 pub mod get_members;
 pub mod get_role;
 pub mod get_roles;
+pub mod get_voice_states;
+pub mod guild_import;
+pub mod interaction;
 pub mod internal;
 pub mod invite;
 pub mod leave_guild;
+pub mod moderate_member_voice;
+pub mod move_member_voice;
+pub mod poll;
 pub mod remove_member_role;
+pub mod scheduled_event;
+pub mod scheduled_message;
+pub mod stage;
 pub mod update_guild;
 pub mod update_role;
+pub mod webhook;
 
 pub fn guild_routes(_state: AppState) -> Router<AppState> {
     Router::new()
@@ -60,20 +147,93 @@ pub fn guild_routes(_state: AppState) -> Router<AppState> {
         .typed_delete(delete_channel_handler)
         .typed_get(get_messages_handler)
         .typed_post(send_message_handler)
+        .typed_post(create_attachment_uploads_handler)
         .typed_delete(delete_message_handler)
+        .typed_patch(edit_message_handler)
+        .typed_post(crosspost_message_handler)
+        .typed_post(follow_channel_handler)
+        .typed_get(list_channel_follows_handler)
+        .typed_delete(delete_channel_follow_handler)
+        .typed_post(ack_message_handler)
         .typed_post(join_guild_handler)
         .typed_post(create_invite_handler)
         .typed_get(list_invites_handler)
         .typed_delete(delete_invite_handler)
         .typed_get(preview_invite_handler)
         .typed_get(get_members_handler)
+        .typed_get(get_voice_states_handler)
+        .typed_patch(moderate_member_voice_handler)
+        .typed_post(move_member_voice_handler)
+        .typed_delete(disconnect_member_voice_handler)
+        .typed_post(start_stage_handler)
+        .typed_get(get_stage_handler)
+        .typed_patch(update_stage_handler)
+        .typed_delete(end_stage_handler)
+        .typed_get(get_stage_history_handler)
+        .typed_put(raise_hand_handler)
+        .typed_delete(lower_hand_handler)
+        .typed_put(add_speaker_handler)
+        .typed_delete(remove_speaker_handler)
         .typed_delete(leave_guild_handler)
         .typed_patch(update_channel_handler)
         .typed_put(assign_member_role_handler)
         .typed_delete(remove_member_role_handler)
+        .typed_post(create_webhook_handler)
+        .typed_get(list_webhooks_handler)
+        .typed_get(get_webhook_handler)
+        .typed_patch(update_webhook_handler)
+        .typed_post(reset_webhook_token_handler)
+        .typed_delete(delete_webhook_handler)
+        .typed_post(add_bot_handler)
+        .typed_get(list_bots_handler)
+        .typed_delete(remove_bot_handler)
+        .typed_post(create_event_subscription_handler)
+        .typed_get(list_event_subscriptions_handler)
+        .typed_get(get_event_subscription_handler)
+        .typed_patch(update_event_subscription_handler)
+        .typed_delete(delete_event_subscription_handler)
+        .typed_get(list_event_deliveries_handler)
+        .typed_post(ping_event_subscription_handler)
+        .typed_get(list_guild_commands_handler)
+        .typed_post(invoke_command_handler)
+        .typed_get(list_emojis_handler)
+        .typed_get(get_emoji_handler)
+        .typed_patch(update_emoji_handler)
+        .typed_delete(delete_emoji_handler)
+        .typed_post(schedule_message_handler)
+        .typed_post(schedule_reminder_handler)
+        .typed_get(list_scheduled_messages_handler)
+        .typed_patch(update_scheduled_message_handler)
+        .typed_delete(cancel_scheduled_message_handler)
+        .typed_get(get_poll_handler)
+        .typed_put(add_poll_vote_handler)
+        .typed_delete(remove_poll_vote_handler)
+        .typed_post(create_event_handler)
+        .typed_get(list_events_handler)
+        .typed_get(get_event_handler)
+        .typed_post(cancel_event_handler)
+        .typed_put(set_event_rsvp_handler)
+        .typed_delete(remove_event_rsvp_handler)
+        .typed_get(list_event_rsvps_handler)
+        .typed_post(create_calendar_feed_handler)
+        .typed_post(create_guild_import_handler)
+        .typed_get(list_guild_imports_handler)
+        .typed_get(get_guild_import_handler)
+        .typed_post(start_guild_import_handler)
+        .typed_post(cancel_guild_import_handler)
         .merge(
             Router::new()
                 .typed_patch(update_guild_handler)
                 .layer(DefaultBodyLimit::max(8 * 1024 * 1024)),
         )
+        .merge(
+            Router::new()
+                .typed_post(create_emoji_handler)
+                .layer(DefaultBodyLimit::max(1024 * 1024)),
+        )
+        .merge(
+            Router::new()
+                .typed_post(upload_guild_import_channel_handler)
+                .layer(DefaultBodyLimit::max(MAX_EXPORT_BYTES)),
+        )
 }
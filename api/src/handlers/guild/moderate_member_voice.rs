@@ -0,0 +1,89 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::voice::ports::VoiceService;
+use ferriscord_entities::{guild::GuildId, voice_state::VoiceState};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState, voice::voice_state_changed};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/members/{user_id}/voice")]
+pub struct ModerateMemberVoiceRoute {
+    pub guild_id: Uuid,
+    pub user_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct ModerateMemberVoiceRequest {
+    /// Server-mute the member. Left unchanged when omitted.
+    #[serde(default)]
+    pub mute: Option<bool>,
+    /// Server-deafen the member. Left unchanged when omitted.
+    #[serde(default)]
+    pub deaf: Option<bool>,
+}
+
+#[derive(Serialize, PartialEq, ToSchema)]
+pub struct ModerateMemberVoiceResponse {
+    pub mute: bool,
+    pub deaf: bool,
+    /// The member's voice state, when connected.
+    pub voice_state: Option<VoiceState>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/guilds/{guild_id}/members/{user_id}/voice",
+    tag = "voice",
+    summary = "Server-mute or server-deafen a member",
+    description = "The flags stay set across reconnects until cleared. Changing `mute` requires MUTE_MEMBERS and changing `deaf` requires DEAFEN_MEMBERS, in the member's voice channel when connected.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("user_id" = Uuid, Path, description = "Member user ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = ModerateMemberVoiceRequest,
+        description = "Flags to change",
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = ModerateMemberVoiceResponse),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden, or not a member", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn moderate_member_voice_handler(
+    ModerateMemberVoiceRoute { guild_id, user_id }: ModerateMemberVoiceRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<ModerateMemberVoiceRequest>,
+) -> Result<Response<ModerateMemberVoiceResponse>, ApiError> {
+    let moderation = state
+        .voice_service
+        .moderate(
+            identity,
+            GuildId::from(guild_id),
+            user_id,
+            req.mute,
+            req.deaf,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    if let Some(voice_state) = &moderation.state {
+        voice_state_changed(&state, voice_state).await;
+    }
+
+    Ok(Response::OK(ModerateMemberVoiceResponse {
+        mute: moderation.mute,
+        deaf: moderation.deaf,
+        voice_state: moderation.state,
+    }))
+}
@@ -0,0 +1,73 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::voice::ports::VoiceService;
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, voice_state::VoiceState};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState, voice::voice_member_moved};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/members/{user_id}/voice/move")]
+pub struct MoveMemberVoiceRoute {
+    pub guild_id: Uuid,
+    pub user_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct MoveMemberVoiceRequest {
+    /// Voice or stage channel of the same guild.
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/members/{user_id}/voice/move",
+    tag = "voice",
+    summary = "Move a member to another voice channel",
+    description = "Requires MOVE_MEMBERS in both channels and CONNECT in the destination. The destination's user limit does not apply.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("user_id" = Uuid, Path, description = "Member user ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = MoveMemberVoiceRequest,
+        description = "Destination channel",
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = VoiceState),
+        (status = 400, description = "Not a voice channel", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden, or the member is not connected", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn move_member_voice_handler(
+    MoveMemberVoiceRoute { guild_id, user_id }: MoveMemberVoiceRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<MoveMemberVoiceRequest>,
+) -> Result<Response<VoiceState>, ApiError> {
+    let join = state
+        .voice_service
+        .move_member(
+            identity,
+            GuildId::from(guild_id),
+            user_id,
+            ChannelId(Id(req.channel_id)),
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    if join.previous.is_some() {
+        voice_member_moved(&state, &join).await;
+    }
+
+    Ok(Response::OK(join.state))
+}
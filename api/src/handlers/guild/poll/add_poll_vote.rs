@@ -0,0 +1,85 @@
+use axum::{Extension, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::poll::ports::PollService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, poll::Poll};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, polls::publish_vote_update, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path(
+    "/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/poll/answers/{answer_id}/votes/@me"
+)]
+pub struct AddPollVoteRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    pub message_id: Uuid,
+    pub answer_id: i32,
+}
+
+#[utoipa::path(
+    put,
+    path = "/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/poll/answers/{answer_id}/votes/@me",
+    tag = "polls",
+    summary = "Vote for a poll answer",
+    description = "Votes for an answer of an open poll. On a single-choice poll this replaces the caller's previous vote. The new tallies are sent to the channel as a `poll.vote_update` event. Requires VIEW_CHANNEL.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+        ("message_id" = Uuid, Path, description = "Message ID"),
+        ("answer_id" = i32, Path, description = "Answer ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, description = "The poll with the caller's votes", body = Poll),
+        (status = 400, description = "Unknown answer, or the poll is closed", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing VIEW_CHANNEL permission", body = ApiError),
+        (status = 404, description = "Message or poll not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn add_poll_vote_handler(
+    AddPollVoteRoute {
+        guild_id,
+        channel_id,
+        message_id,
+        answer_id,
+    }: AddPollVoteRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Poll>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let poll = state
+        .poll_service
+        .vote(
+            identity,
+            user.id.0,
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+            message_id,
+            answer_id,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_vote_update(&state.hub, channel_id, message_id, &poll).await;
+
+    Ok(Response::OK(poll))
+}
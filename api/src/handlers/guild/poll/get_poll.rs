@@ -0,0 +1,76 @@
+use axum::{Extension, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::poll::ports::PollService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, poll::Poll};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/poll")]
+pub struct GetPollRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    pub message_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/poll",
+    tag = "polls",
+    summary = "Get a poll",
+    description = "Returns the poll of a message with the caller's votes in `my_votes`. Counts are left out while an open poll hides its results. Requires VIEW_CHANNEL.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+        ("message_id" = Uuid, Path, description = "Message ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Poll),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing VIEW_CHANNEL permission", body = ApiError),
+        (status = 404, description = "Message or poll not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_poll_handler(
+    GetPollRoute {
+        guild_id,
+        channel_id,
+        message_id,
+    }: GetPollRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Poll>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let poll = state
+        .poll_service
+        .get_poll(
+            identity,
+            user.id.0,
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+            message_id,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(poll))
+}
@@ -0,0 +1,3 @@
+pub mod add_poll_vote;
+pub mod get_poll;
+pub mod remove_poll_vote;
@@ -0,0 +1,54 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::scheduled_event::ports::ScheduledEventService;
+use ferriscord_entities::{Id, guild::GuildId, scheduled_event::GuildScheduledEvent};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, scheduled_events::publish_event_update, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/scheduled-events/{event_id}/cancel")]
+pub struct CancelEventRoute {
+    pub guild_id: Uuid,
+    pub event_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/scheduled-events/{event_id}/cancel",
+    tag = "scheduled events",
+    summary = "Cancel a scheduled event",
+    description = "Cancels an upcoming or running event. It stays in calendar feeds, marked as cancelled. Requires MANAGE_GUILD.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("event_id" = Uuid, Path, description = "Event ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = GuildScheduledEvent),
+        (status = 400, description = "The event is already over or cancelled", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 404, description = "Event not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn cancel_event_handler(
+    CancelEventRoute { guild_id, event_id }: CancelEventRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<GuildScheduledEvent>, ApiError> {
+    let event = state
+        .scheduled_event_service
+        .cancel_event(identity, GuildId(Id(guild_id)), event_id)
+        .await
+        .map_err(map_core_error)?;
+
+    publish_event_update(&state.hub, "guild_scheduled_event.update", &event).await;
+
+    Ok(Response::OK(event))
+}
@@ -0,0 +1,64 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_event::ports::ScheduledEventService,
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, guild::GuildId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::CalendarFeedResponse;
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/scheduled-events/calendar-feed")]
+pub struct CreateCalendarFeedRoute {
+    pub guild_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/scheduled-events/calendar-feed",
+    tag = "scheduled events",
+    summary = "Create a calendar feed",
+    description = "Creates a secret iCalendar feed of the guild's events for the caller, to subscribe to from a calendar app. The feed only lists the events the caller can see, and stops working if they leave the guild. An earlier feed of the caller for this guild stops working.",
+    params(("guild_id" = Uuid, Path, description = "Guild ID")),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 201, body = CalendarFeedResponse),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Not a member of the guild", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_calendar_feed_handler(
+    CreateCalendarFeedRoute { guild_id }: CreateCalendarFeedRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<CalendarFeedResponse>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let token = state
+        .scheduled_event_service
+        .create_calendar_feed(identity, user.id.0, GuildId(Id(guild_id)))
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::Created(CalendarFeedResponse {
+        path: format!("/calendars/{}.ics", token),
+        token,
+    }))
+}
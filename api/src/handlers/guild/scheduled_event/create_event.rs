@@ -0,0 +1,98 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_event::ports::{CreateEventInput, ScheduledEventService},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    Id,
+    guild::GuildId,
+    scheduled_event::{GuildScheduledEvent, ScheduledEventLocation},
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, scheduled_events::publish_event_update, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/scheduled-events")]
+pub struct CreateEventRoute {
+    pub guild_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateEventRequest {
+    /// 1 to 100 characters.
+    pub title: String,
+    /// Up to 1000 characters.
+    #[serde(default)]
+    pub description: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub location: ScheduledEventLocation,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/scheduled-events",
+    tag = "scheduled events",
+    summary = "Create a scheduled event",
+    description = "Schedules an event held in a voice or stage channel of the guild, or at a free text location. Events in a stage channel open the stage when they start. Requires MANAGE_GUILD.",
+    params(("guild_id" = Uuid, Path, description = "Guild ID")),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = CreateEventRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = GuildScheduledEvent),
+        (status = 400, description = "Invalid event, or too many upcoming events", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_GUILD permission", body = ApiError),
+        (status = 404, description = "Channel not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_event_handler(
+    CreateEventRoute { guild_id }: CreateEventRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<CreateEventRequest>,
+) -> Result<Response<GuildScheduledEvent>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let event = state
+        .scheduled_event_service
+        .create_event(
+            identity,
+            user.id.0,
+            GuildId(Id(guild_id)),
+            CreateEventInput {
+                title: req.title,
+                description: req.description,
+                starts_at: req.starts_at,
+                ends_at: req.ends_at,
+                location: req.location,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_event_update(&state.hub, "guild_scheduled_event.create", &event).await;
+
+    Ok(Response::Created(event))
+}
@@ -0,0 +1,49 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::{IntoResponse, Response},
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_core::guild::domain::scheduled_event::ports::ScheduledEventService;
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/calendars/{token}")]
+pub struct GetCalendarFeedRoute {
+    /// The feed token, optionally followed by `.ics`.
+    pub token: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/calendars/{token}",
+    tag = "scheduled events",
+    summary = "Get a calendar feed",
+    description = "Returns the events of a guild as an iCalendar document, for calendar apps. Upcoming and running events are listed, along with those that ended or were cancelled in the last 30 days.",
+    params(("token" = String, Path, description = "Feed token, optionally followed by `.ics`")),
+    responses(
+        (status = 200, description = "iCalendar document", content_type = "text/calendar", body = String),
+        (status = 404, description = "Unknown feed", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_calendar_feed_handler(
+    GetCalendarFeedRoute { token }: GetCalendarFeedRoute,
+    State(state): State<AppState>,
+) -> Result<Response, ApiError> {
+    let token = token.strip_suffix(".ics").unwrap_or(&token);
+    let calendar = state
+        .scheduled_event_service
+        .render_calendar_feed(token)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        calendar,
+    )
+        .into_response())
+}
@@ -0,0 +1,65 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_event::ports::ScheduledEventService,
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, guild::GuildId, scheduled_event::GuildScheduledEvent};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/scheduled-events/{event_id}")]
+pub struct GetEventRoute {
+    pub guild_id: Uuid,
+    pub event_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/scheduled-events/{event_id}",
+    tag = "scheduled events",
+    summary = "Get a scheduled event",
+    description = "Returns an event of the guild, whatever its status, with the caller's answer in `my_rsvp`.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("event_id" = Uuid, Path, description = "Event ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = GuildScheduledEvent),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Not a member of the guild", body = ApiError),
+        (status = 404, description = "Event not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_event_handler(
+    GetEventRoute { guild_id, event_id }: GetEventRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<GuildScheduledEvent>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let event = state
+        .scheduled_event_service
+        .get_event(identity, user.id.0, GuildId(Id(guild_id)), event_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(event))
+}
@@ -0,0 +1,65 @@
+use axum::extract::{Extension, Query, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::scheduled_event::ports::ScheduledEventService;
+use ferriscord_entities::{Id, guild::GuildId, scheduled_event::EventRsvp};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/scheduled-events/{event_id}/rsvps")]
+pub struct ListEventRsvpsRoute {
+    pub guild_id: Uuid,
+    pub event_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListEventRsvpsQuery {
+    /// 1 to 100, defaults to 100
+    pub limit: Option<i64>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/scheduled-events/{event_id}/rsvps",
+    tag = "scheduled events",
+    summary = "List answers to a scheduled event",
+    description = "Returns the members interested in or going to an event, oldest answers first.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("event_id" = Uuid, Path, description = "Event ID"),
+        ListEventRsvpsQuery,
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<EventRsvp>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Not a member of the guild", body = ApiError),
+        (status = 404, description = "Event not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_event_rsvps_handler(
+    ListEventRsvpsRoute { guild_id, event_id }: ListEventRsvpsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Query(query): Query<ListEventRsvpsQuery>,
+) -> Result<Response<Vec<EventRsvp>>, ApiError> {
+    let rsvps = state
+        .scheduled_event_service
+        .list_rsvps(
+            identity,
+            GuildId(Id(guild_id)),
+            event_id,
+            query.limit.unwrap_or(100),
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(rsvps))
+}
@@ -0,0 +1,60 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_event::ports::ScheduledEventService,
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, guild::GuildId, scheduled_event::GuildScheduledEvent};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/scheduled-events")]
+pub struct ListEventsRoute {
+    pub guild_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/scheduled-events",
+    tag = "scheduled events",
+    summary = "List scheduled events",
+    description = "Returns the upcoming and running events of the guild, soonest first, with the caller's answer in `my_rsvp`. Events in channels the caller cannot view are left out.",
+    params(("guild_id" = Uuid, Path, description = "Guild ID")),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<GuildScheduledEvent>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Not a member of the guild", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_events_handler(
+    ListEventsRoute { guild_id }: ListEventsRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<GuildScheduledEvent>>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let events = state
+        .scheduled_event_service
+        .list_events(identity, user.id.0, GuildId(Id(guild_id)))
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(events))
+}
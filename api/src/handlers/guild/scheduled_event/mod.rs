@@ -0,0 +1,38 @@
+use axum::Router;
+use axum_extra::routing::RouterExt;
+use ferriscord_entities::scheduled_event::RsvpStatus;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::{
+    handlers::guild::scheduled_event::get_calendar_feed::get_calendar_feed_handler, state::AppState,
+};
+
+pub mod cancel_event;
+pub mod create_calendar_feed;
+pub mod create_event;
+pub mod get_calendar_feed;
+pub mod get_event;
+pub mod list_event_rsvps;
+pub mod list_events;
+pub mod remove_event_rsvp;
+pub mod set_event_rsvp;
+
+/// Routes authenticated by the feed token in the path rather than a bearer
+/// token, so that calendar apps can subscribe to them.
+pub fn calendar_routes() -> Router<AppState> {
+    Router::new().typed_get(get_calendar_feed_handler)
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SetRsvpRequest {
+    pub status: RsvpStatus,
+}
+
+#[derive(Serialize, PartialEq, ToSchema)]
+pub struct CalendarFeedResponse {
+    /// Secret token of the feed. It is only shown once.
+    pub token: String,
+    /// Path of the feed, for calendar apps to subscribe to.
+    pub path: String,
+}
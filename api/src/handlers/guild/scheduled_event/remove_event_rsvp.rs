@@ -0,0 +1,67 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_event::ports::ScheduledEventService,
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, guild::GuildId, scheduled_event::GuildScheduledEvent};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, scheduled_events::publish_event_update, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/scheduled-events/{event_id}/rsvp")]
+pub struct RemoveEventRsvpRoute {
+    pub guild_id: Uuid,
+    pub event_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/guilds/{guild_id}/scheduled-events/{event_id}/rsvp",
+    tag = "scheduled events",
+    summary = "Withdraw an answer to a scheduled event",
+    description = "Removes the caller's answer to an event, if any.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("event_id" = Uuid, Path, description = "Event ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = GuildScheduledEvent),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Not a member of the guild", body = ApiError),
+        (status = 404, description = "Event not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn remove_event_rsvp_handler(
+    RemoveEventRsvpRoute { guild_id, event_id }: RemoveEventRsvpRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<GuildScheduledEvent>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let event = state
+        .scheduled_event_service
+        .remove_rsvp(identity, user.id.0, GuildId(Id(guild_id)), event_id)
+        .await
+        .map_err(map_core_error)?;
+
+    publish_event_update(&state.hub, "guild_scheduled_event.update", &event).await;
+
+    Ok(Response::OK(event))
+}
@@ -0,0 +1,80 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_event::ports::ScheduledEventService,
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, guild::GuildId, scheduled_event::GuildScheduledEvent};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::SetRsvpRequest;
+use crate::{handlers::map_core_error, scheduled_events::publish_event_update, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/scheduled-events/{event_id}/rsvp")]
+pub struct SetEventRsvpRoute {
+    pub guild_id: Uuid,
+    pub event_id: Uuid,
+}
+
+#[utoipa::path(
+    put,
+    path = "/guilds/{guild_id}/scheduled-events/{event_id}/rsvp",
+    tag = "scheduled events",
+    summary = "Answer a scheduled event",
+    description = "Marks the caller as interested in or going to an upcoming or running event, replacing a previous answer. Members who answered are reminded before the event starts.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("event_id" = Uuid, Path, description = "Event ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = SetRsvpRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = GuildScheduledEvent),
+        (status = 400, description = "The event is over or cancelled", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Not a member of the guild", body = ApiError),
+        (status = 404, description = "Event not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn set_event_rsvp_handler(
+    SetEventRsvpRoute { guild_id, event_id }: SetEventRsvpRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<SetRsvpRequest>,
+) -> Result<Response<GuildScheduledEvent>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let event = state
+        .scheduled_event_service
+        .set_rsvp(
+            identity,
+            user.id.0,
+            GuildId(Id(guild_id)),
+            event_id,
+            req.status,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_event_update(&state.hub, "guild_scheduled_event.update", &event).await;
+
+    Ok(Response::OK(event))
+}
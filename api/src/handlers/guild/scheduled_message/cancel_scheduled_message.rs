@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_message::ports::ScheduledMessageService,
+    user::domain::user::ports::UserService,
+};
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/@me/scheduled-messages/{scheduled_id}")]
+pub struct CancelScheduledMessageRoute {
+    pub scheduled_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/@me/scheduled-messages/{scheduled_id}",
+    tag = "scheduled messages",
+    summary = "Cancel a scheduled message or reminder",
+    description = "Cancels a pending scheduled message or reminder of the caller, or dismisses a delivered or failed one.",
+    params(("scheduled_id" = Uuid, Path, description = "Scheduled message ID")),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 204, description = "Cancelled"),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 404, description = "Scheduled message not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn cancel_scheduled_message_handler(
+    CancelScheduledMessageRoute { scheduled_id }: CancelScheduledMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    state
+        .scheduled_message_service
+        .cancel_scheduled(user.id.0, scheduled_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
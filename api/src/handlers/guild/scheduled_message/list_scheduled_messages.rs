@@ -0,0 +1,54 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_message::ports::ScheduledMessageService,
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::scheduled_message::ScheduledMessage;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath)]
+#[typed_path("/users/@me/scheduled-messages")]
+pub struct ListScheduledMessagesRoute;
+
+#[utoipa::path(
+    get,
+    path = "/users/@me/scheduled-messages",
+    tag = "scheduled messages",
+    summary = "List my scheduled messages and reminders",
+    description = "Returns the caller's pending scheduled messages and reminders, and those delivered or failed in the last 7 days, by time.",
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<ScheduledMessage>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_scheduled_messages_handler(
+    _: ListScheduledMessagesRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<ScheduledMessage>>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let scheduled = state
+        .scheduled_message_service
+        .list_scheduled(user.id.0)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(scheduled))
+}
@@ -0,0 +1,17 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+pub mod cancel_scheduled_message;
+pub mod list_scheduled_messages;
+pub mod schedule_message;
+pub mod schedule_reminder;
+pub mod update_scheduled_message;
+
+#[derive(Deserialize, ToSchema)]
+pub struct ScheduleRequest {
+    /// The text to post, or an optional note of a reminder.
+    #[serde(default)]
+    pub content: String,
+    pub send_at: DateTime<Utc>,
+}
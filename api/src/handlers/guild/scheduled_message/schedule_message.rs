@@ -0,0 +1,90 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_message::ports::{CreateScheduledInput, ScheduledMessageService},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    Id, channel::ChannelId, guild::GuildId, scheduled_message::ScheduledMessage,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::ScheduleRequest;
+use crate::{
+    handlers::map_core_error, scheduled_messages::publish_scheduled_update, state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/scheduled-messages")]
+pub struct ScheduleMessageRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/channels/{channel_id}/scheduled-messages",
+    tag = "scheduled messages",
+    summary = "Schedule a message",
+    description = "Queues a message to be posted in the channel at `send_at`. Requires SEND_MESSAGES, now and again when it is posted; if the permission is gone by then the scheduled message fails instead.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = ScheduleRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = ScheduledMessage),
+        (status = 400, description = "Invalid content or time, or too many pending", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing SEND_MESSAGES permission", body = ApiError),
+        (status = 404, description = "Channel not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn schedule_message_handler(
+    ScheduleMessageRoute {
+        guild_id,
+        channel_id,
+    }: ScheduleMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<ScheduleRequest>,
+) -> Result<Response<ScheduledMessage>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let scheduled = state
+        .scheduled_message_service
+        .schedule_message(
+            identity,
+            user.id.0,
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+            CreateScheduledInput {
+                content: req.content,
+                send_at: req.send_at,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_scheduled_update(&state.hub, &scheduled).await;
+
+    Ok(Response::Created(scheduled))
+}
@@ -0,0 +1,94 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_message::ports::{CreateScheduledInput, ScheduledMessageService},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    Id, channel::ChannelId, guild::GuildId, scheduled_message::ScheduledMessage,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::ScheduleRequest;
+use crate::{
+    handlers::map_core_error, scheduled_messages::publish_scheduled_update, state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/reminders")]
+pub struct ScheduleReminderRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    pub message_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/channels/{channel_id}/messages/{message_id}/reminders",
+    tag = "scheduled messages",
+    summary = "Remind me about a message",
+    description = "Schedules a private reminder about the message at `send_at`, with an optional note in `content`. It is sent as a `reminder` event to the caller's sessions, with the message if they can still see it. Requires VIEW_CHANNEL.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Channel ID"),
+        ("message_id" = Uuid, Path, description = "Message ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = ScheduleRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = ScheduledMessage),
+        (status = 400, description = "Invalid note or time, or too many pending", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing VIEW_CHANNEL permission", body = ApiError),
+        (status = 404, description = "Message not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn schedule_reminder_handler(
+    ScheduleReminderRoute {
+        guild_id,
+        channel_id,
+        message_id,
+    }: ScheduleReminderRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<ScheduleRequest>,
+) -> Result<Response<ScheduledMessage>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let scheduled = state
+        .scheduled_message_service
+        .schedule_reminder(
+            identity,
+            user.id.0,
+            GuildId(Id(guild_id)),
+            ChannelId(Id(channel_id)),
+            message_id,
+            CreateScheduledInput {
+                content: req.content,
+                send_at: req.send_at,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_scheduled_update(&state.hub, &scheduled).await;
+
+    Ok(Response::Created(scheduled))
+}
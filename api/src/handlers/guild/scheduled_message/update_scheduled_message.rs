@@ -0,0 +1,87 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::scheduled_message::ports::{ScheduledMessageService, UpdateScheduledInput},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::scheduled_message::ScheduledMessage;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    handlers::map_core_error, scheduled_messages::publish_scheduled_update, state::AppState,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/@me/scheduled-messages/{scheduled_id}")]
+pub struct UpdateScheduledMessageRoute {
+    pub scheduled_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateScheduledRequest {
+    pub content: Option<String>,
+    pub send_at: Option<DateTime<Utc>>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/users/@me/scheduled-messages/{scheduled_id}",
+    tag = "scheduled messages",
+    summary = "Edit a scheduled message or reminder",
+    description = "Changes the content or time of a pending scheduled message or reminder of the caller. Scheduled messages need SEND_MESSAGES again.",
+    params(("scheduled_id" = Uuid, Path, description = "Scheduled message ID")),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = UpdateScheduledRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = ScheduledMessage),
+        (status = 400, description = "Invalid content or time, or no longer pending", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing SEND_MESSAGES permission", body = ApiError),
+        (status = 404, description = "Scheduled message not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn update_scheduled_message_handler(
+    UpdateScheduledMessageRoute { scheduled_id }: UpdateScheduledMessageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<UpdateScheduledRequest>,
+) -> Result<Response<ScheduledMessage>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let scheduled = state
+        .scheduled_message_service
+        .update_scheduled(
+            identity,
+            user.id.0,
+            scheduled_id,
+            UpdateScheduledInput {
+                content: req.content,
+                send_at: req.send_at,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_scheduled_update(&state.hub, &scheduled).await;
+
+    Ok(Response::OK(scheduled))
+}
@@ -0,0 +1,71 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::stage::ports::StageService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, voice_state::VoiceState};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState, voice::voice_state_changed};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/stage/speakers/{user_id}")]
+pub struct AddSpeakerRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    pub user_id: Uuid,
+}
+
+#[utoipa::path(
+    put,
+    path = "/guilds/{guild_id}/channels/{channel_id}/stage/speakers/{user_id}",
+    tag = "stage",
+    summary = "Invite a user to speak",
+    description = "Moves a user from the audience to the speakers of a live stage. Requires MUTE_MEMBERS or MOVE_MEMBERS in the channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Stage channel ID"),
+        ("user_id" = Uuid, Path, description = "User connected to the stage"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = VoiceState),
+        (status = 400, description = "Not a stage channel or not live", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden, or the user is not connected to the stage", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn add_speaker_handler(
+    AddSpeakerRoute { guild_id, channel_id, user_id }: AddSpeakerRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<VoiceState>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let voice_state = state
+        .stage_service
+        .set_speaker(
+            identity,
+            user.id.0,
+            GuildId::from(guild_id),
+            ChannelId(Id(channel_id)),
+            user_id,
+            true,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    voice_state_changed(&state, &voice_state).await;
+
+    Ok(Response::OK(voice_state))
+}
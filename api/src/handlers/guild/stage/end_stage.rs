@@ -0,0 +1,67 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::stage::ports::StageService;
+use ferriscord_entities::{
+    Id, channel::ChannelId, guild::GuildId, stage_instance::StageInstance,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    handlers::map_core_error, member_list::publish_guild_event, state::AppState,
+    voice::voice_state_changed,
+};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/stage")]
+pub struct EndStageRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/guilds/{guild_id}/channels/{channel_id}/stage",
+    tag = "stage",
+    summary = "End the live stage",
+    description = "Ends the stage and moves its speakers back to the audience. The ended stage stays in the channel's history. Requires MUTE_MEMBERS or MOVE_MEMBERS in the channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Stage channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = StageInstance),
+        (status = 400, description = "Not a stage channel or not live", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn end_stage_handler(
+    EndStageRoute { guild_id, channel_id }: EndStageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<StageInstance>, ApiError> {
+    let end = state
+        .stage_service
+        .end_stage(identity, GuildId::from(guild_id), ChannelId(Id(channel_id)))
+        .await
+        .map_err(map_core_error)?;
+
+    publish_guild_event(
+        &state.hub,
+        guild_id,
+        "stage_instance.delete",
+        serde_json::json!(end.instance),
+    )
+    .await;
+    for demoted in &end.demoted {
+        voice_state_changed(&state, demoted).await;
+    }
+
+    Ok(Response::OK(end.instance))
+}
@@ -0,0 +1,63 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::stage::ports::StageService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    Id, channel::ChannelId, guild::GuildId, stage_instance::StageInstance,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/stage")]
+pub struct GetStageRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/channels/{channel_id}/stage",
+    tag = "stage",
+    summary = "Get the live stage of a channel",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Stage channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = StageInstance),
+        (status = 400, description = "Not a stage channel", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden", body = ApiError),
+        (status = 404, description = "No stage is live", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_stage_handler(
+    GetStageRoute { guild_id, channel_id }: GetStageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<StageInstance>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let instance = state
+        .stage_service
+        .get_stage(identity, user.id.0, GuildId::from(guild_id), ChannelId(Id(channel_id)))
+        .await
+        .map_err(map_core_error)?
+        .ok_or_else(|| ApiError::NotFound { message: "no stage is live".into() })?;
+
+    Ok(Response::OK(instance))
+}
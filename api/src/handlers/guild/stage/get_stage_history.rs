@@ -0,0 +1,62 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::stage::ports::StageService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    Id, channel::ChannelId, guild::GuildId, stage_instance::StageInstance,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/stage/history")]
+pub struct GetStageHistoryRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/channels/{channel_id}/stage/history",
+    tag = "stage",
+    summary = "List the stages of a channel",
+    description = "Returns the latest 50 stages of the channel with their start and end times, most recent first.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Stage channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<StageInstance>),
+        (status = 400, description = "Not a stage channel", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_stage_history_handler(
+    GetStageHistoryRoute { guild_id, channel_id }: GetStageHistoryRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<StageInstance>>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let history = state
+        .stage_service
+        .list_stage_history(identity, user.id.0, GuildId::from(guild_id), ChannelId(Id(channel_id)))
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(history))
+}
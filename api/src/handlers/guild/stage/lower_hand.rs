@@ -0,0 +1,62 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::stage::ports::StageService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, voice_state::VoiceState};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState, voice::publish_voice_state};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/stage/hand")]
+pub struct LowerHandRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/guilds/{guild_id}/channels/{channel_id}/stage/hand",
+    tag = "stage",
+    summary = "Lower your hand",
+    description = "Withdraws a request to speak.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Stage channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = VoiceState),
+        (status = 400, description = "Not a stage channel", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Not connected to the stage", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn lower_hand_handler(
+    LowerHandRoute { guild_id, channel_id }: LowerHandRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<VoiceState>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let voice_state = state
+        .stage_service
+        .set_hand(user.id.0, GuildId::from(guild_id), ChannelId(Id(channel_id)), false)
+        .await
+        .map_err(map_core_error)?;
+
+    publish_voice_state(&state.hub, &voice_state, true).await;
+
+    Ok(Response::OK(voice_state))
+}
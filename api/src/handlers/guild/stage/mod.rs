@@ -0,0 +1,9 @@
+pub mod add_speaker;
+pub mod end_stage;
+pub mod get_stage;
+pub mod get_stage_history;
+pub mod lower_hand;
+pub mod raise_hand;
+pub mod remove_speaker;
+pub mod start_stage;
+pub mod update_stage;
@@ -0,0 +1,89 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::stage::ports::StageService, user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{
+    Id, channel::ChannelId, guild::GuildId, stage_instance::StageInstance,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, member_list::publish_guild_event, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/stage")]
+pub struct StartStageRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct StartStageRequest {
+    /// 1 to 120 characters.
+    pub topic: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/channels/{channel_id}/stage",
+    tag = "stage",
+    summary = "Start a stage",
+    description = "Starts a stage in a stage channel. Requires MUTE_MEMBERS or MOVE_MEMBERS in the channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Stage channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = StartStageRequest,
+        description = "Stage topic",
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = StageInstance),
+        (status = 400, description = "Not a stage channel, invalid topic or already live", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn start_stage_handler(
+    StartStageRoute { guild_id, channel_id }: StartStageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<StartStageRequest>,
+) -> Result<Response<StageInstance>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let instance = state
+        .stage_service
+        .start_stage(
+            identity,
+            user.id.0,
+            GuildId::from(guild_id),
+            ChannelId(Id(channel_id)),
+            req.topic,
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_guild_event(
+        &state.hub,
+        guild_id,
+        "stage_instance.create",
+        serde_json::json!(instance),
+    )
+    .await;
+
+    Ok(Response::Created(instance))
+}
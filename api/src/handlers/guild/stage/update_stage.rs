@@ -0,0 +1,74 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::stage::ports::StageService;
+use ferriscord_entities::{
+    Id, channel::ChannelId, guild::GuildId, stage_instance::StageInstance,
+};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, member_list::publish_guild_event, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/stage")]
+pub struct UpdateStageRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateStageRequest {
+    /// 1 to 120 characters.
+    pub topic: String,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/guilds/{guild_id}/channels/{channel_id}/stage",
+    tag = "stage",
+    summary = "Change the topic of the live stage",
+    description = "Requires MUTE_MEMBERS or MOVE_MEMBERS in the channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Stage channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = UpdateStageRequest,
+        description = "New stage topic",
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = StageInstance),
+        (status = 400, description = "Not a stage channel, invalid topic or not live", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Forbidden", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn update_stage_handler(
+    UpdateStageRoute { guild_id, channel_id }: UpdateStageRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<UpdateStageRequest>,
+) -> Result<Response<StageInstance>, ApiError> {
+    let instance = state
+        .stage_service
+        .update_stage(identity, GuildId::from(guild_id), ChannelId(Id(channel_id)), req.topic)
+        .await
+        .map_err(map_core_error)?;
+
+    publish_guild_event(
+        &state.hub,
+        guild_id,
+        "stage_instance.update",
+        serde_json::json!(instance),
+    )
+    .await;
+
+    Ok(Response::OK(instance))
+}
@@ -134,7 +134,7 @@ pub async fn update_guild_handler(
         }
     }
 
-    let banner_color_db = banner_color.map(|c| if c.is_empty() { None } else { Some(c) }).flatten();
+    let banner_color_db = banner_color.and_then(|c| if c.is_empty() { None } else { Some(c) });
 
     let guild = state
         .guild_service
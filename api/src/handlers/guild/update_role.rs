@@ -3,12 +3,15 @@ use axum_extra::routing::TypedPath;
 use ferriscord_auth::Identity;
 use ferriscord_core::guild::domain::role::{entities::UpdateRoleInput, ports::RoleService};
 use ferriscord_entities::{Id, guild::GuildId, role::Role, role::RoleId};
+use ferriscord_entities::event_subscription::GuildEventType;
 use ferriscord_error::ApiError;
 use ferriscord_server::http::response::Response;
 use serde::Deserialize;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::member_list::publish_guild_event;
+use crate::events::dispatch_guild_event;
 use crate::state::AppState;
 
 #[derive(TypedPath, Deserialize)]
@@ -23,6 +26,10 @@ pub struct UpdateRoleRequest {
     pub color: u32,
     pub permissions: u64,
     pub name: String,
+    /// Display members with this role in their own member list group.
+    /// Left unchanged when omitted.
+    #[serde(default)]
+    pub hoist: Option<bool>,
 }
 
 #[utoipa::path(
@@ -64,6 +71,7 @@ pub async fn update_role_handler(
                 name: req.name,
                 permissions: req.permissions,
                 color: Some(req.color),
+                hoist: req.hoist,
             },
         )
         .await
@@ -71,5 +79,8 @@ pub async fn update_role_handler(
             message: e.to_string(),
         })?;
 
+    publish_guild_event(&state.hub, guild_id, "role.update", serde_json::json!(role)).await;
+    dispatch_guild_event(&state, guild_id, GuildEventType::RoleUpdate, &role).await;
+
     Ok(Response::OK(role))
 }
@@ -0,0 +1,93 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::webhook::ports::{CreateWebhookInput, WebhookService},
+    user::domain::user::ports::UserService,
+};
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, webhook::Webhook};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::publish_webhooks_update;
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/channels/{channel_id}/webhooks")]
+pub struct CreateWebhookRoute {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    /// 1 to 80 characters. Default username of the messages it posts.
+    pub name: String,
+    pub avatar_url: Option<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/channels/{channel_id}/webhooks",
+    tag = "webhooks",
+    summary = "Create a webhook",
+    description = "Creates an incoming webhook for a text channel. The response holds the secret token, which is not shown again; messages are posted to `/webhooks/{id}/{token}`. Requires MANAGE_WEBHOOKS in the channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("channel_id" = Uuid, Path, description = "Text channel ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = CreateWebhookRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 201, body = Webhook),
+        (status = 400, description = "Invalid name, not a text channel or too many webhooks", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_WEBHOOKS permission", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_webhook_handler(
+    CreateWebhookRoute {
+        guild_id,
+        channel_id,
+    }: CreateWebhookRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<CreateWebhookRequest>,
+) -> Result<Response<Webhook>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown {
+            message: e.to_string(),
+        })?
+        .ok_or_else(|| ApiError::NotFound {
+            message: "user not found".into(),
+        })?;
+
+    let webhook = state
+        .webhook_service
+        .create_webhook(
+            identity,
+            user.id.0,
+            GuildId::from(guild_id),
+            ChannelId(Id(channel_id)),
+            CreateWebhookInput {
+                name: req.name,
+                avatar_url: req.avatar_url,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_webhooks_update(&state.hub, guild_id, channel_id).await;
+
+    Ok(Response::Created(webhook))
+}
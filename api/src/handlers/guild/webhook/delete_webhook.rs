@@ -0,0 +1,59 @@
+use axum::{
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::webhook::ports::WebhookService;
+use ferriscord_entities::guild::GuildId;
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::publish_webhooks_update;
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/webhooks/{webhook_id}")]
+pub struct DeleteWebhookRoute {
+    pub guild_id: Uuid,
+    pub webhook_id: Uuid,
+}
+
+#[utoipa::path(
+    delete,
+    path = "/guilds/{guild_id}/webhooks/{webhook_id}",
+    tag = "webhooks",
+    summary = "Delete a webhook",
+    description = "Deletes a webhook. Messages it posted stay in the channel. Requires MANAGE_WEBHOOKS in its channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 204, description = "Webhook deleted"),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_WEBHOOKS permission", body = ApiError),
+        (status = 404, description = "Webhook not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn delete_webhook_handler(
+    DeleteWebhookRoute {
+        guild_id,
+        webhook_id,
+    }: DeleteWebhookRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<StatusCode, ApiError> {
+    let webhook = state
+        .webhook_service
+        .delete_webhook(identity, GuildId::from(guild_id), webhook_id)
+        .await
+        .map_err(map_core_error)?;
+
+    publish_webhooks_update(&state.hub, guild_id, webhook.channel_id).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,54 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::webhook::ports::WebhookService;
+use ferriscord_entities::{guild::GuildId, webhook::Webhook};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/webhooks/{webhook_id}")]
+pub struct GetWebhookRoute {
+    pub guild_id: Uuid,
+    pub webhook_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/webhooks/{webhook_id}",
+    tag = "webhooks",
+    summary = "Get a webhook",
+    description = "Returns a webhook without its token. Requires MANAGE_WEBHOOKS in its channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Webhook),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_WEBHOOKS permission", body = ApiError),
+        (status = 404, description = "Webhook not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_webhook_handler(
+    GetWebhookRoute {
+        guild_id,
+        webhook_id,
+    }: GetWebhookRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Webhook>, ApiError> {
+    let webhook = state
+        .webhook_service
+        .get_webhook(identity, GuildId::from(guild_id), webhook_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(webhook))
+}
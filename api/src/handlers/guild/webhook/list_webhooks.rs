@@ -0,0 +1,61 @@
+use axum::extract::{Extension, Query, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::webhook::ports::WebhookService;
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, webhook::Webhook};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::IntoParams;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/webhooks")]
+pub struct ListWebhooksRoute {
+    pub guild_id: Uuid,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ListWebhooksQuery {
+    /// Only list the webhooks of this channel
+    pub channel_id: Option<Uuid>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/guilds/{guild_id}/webhooks",
+    tag = "webhooks",
+    summary = "List webhooks",
+    description = "Lists the webhooks of a guild, or of one of its channels. Tokens are not included. Requires MANAGE_WEBHOOKS in the guild, or in the channel when filtering by channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ListWebhooksQuery,
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<Webhook>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_WEBHOOKS permission", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn list_webhooks_handler(
+    ListWebhooksRoute { guild_id }: ListWebhooksRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Query(query): Query<ListWebhooksQuery>,
+) -> Result<Response<Vec<Webhook>>, ApiError> {
+    let webhooks = state
+        .webhook_service
+        .list_webhooks(
+            identity,
+            GuildId::from(guild_id),
+            query.channel_id.map(|id| ChannelId(Id(id))),
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(webhooks))
+}
@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+use crate::{member_list::publish_guild_event, ws::WsHub};
+
+pub mod create_webhook;
+pub mod delete_webhook;
+pub mod get_webhook;
+pub mod list_webhooks;
+pub mod reset_webhook_token;
+pub mod update_webhook;
+
+/// Tells the guild a channel's webhooks changed. Tokens are never broadcast.
+pub(crate) async fn publish_webhooks_update(hub: &WsHub, guild_id: Uuid, channel_id: Uuid) {
+    publish_guild_event(
+        hub,
+        guild_id,
+        "webhooks.update",
+        serde_json::json!({ "guild_id": guild_id, "channel_id": channel_id }),
+    )
+    .await;
+}
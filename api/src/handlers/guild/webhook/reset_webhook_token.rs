@@ -0,0 +1,54 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::webhook::ports::WebhookService;
+use ferriscord_entities::{guild::GuildId, webhook::Webhook};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/webhooks/{webhook_id}/token")]
+pub struct ResetWebhookTokenRoute {
+    pub guild_id: Uuid,
+    pub webhook_id: Uuid,
+}
+
+#[utoipa::path(
+    post,
+    path = "/guilds/{guild_id}/webhooks/{webhook_id}/token",
+    tag = "webhooks",
+    summary = "Reset a webhook token",
+    description = "Replaces the webhook's secret token, e.g. after it leaked. The old URL stops working at once. Requires MANAGE_WEBHOOKS in its channel.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, description = "Webhook with its new token", body = Webhook),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_WEBHOOKS permission", body = ApiError),
+        (status = 404, description = "Webhook not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn reset_webhook_token_handler(
+    ResetWebhookTokenRoute {
+        guild_id,
+        webhook_id,
+    }: ResetWebhookTokenRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Webhook>, ApiError> {
+    let webhook = state
+        .webhook_service
+        .reset_webhook_token(identity, GuildId::from(guild_id), webhook_id)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(webhook))
+}
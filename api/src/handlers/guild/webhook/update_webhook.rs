@@ -0,0 +1,101 @@
+use axum::{Extension, Json, extract::State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::webhook::ports::{UpdateWebhookInput, WebhookService};
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, webhook::Webhook};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::publish_webhooks_update;
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/guilds/{guild_id}/webhooks/{webhook_id}")]
+pub struct UpdateWebhookRoute {
+    pub guild_id: Uuid,
+    pub webhook_id: Uuid,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateWebhookRequest {
+    pub name: Option<String>,
+    /// An empty string removes the avatar.
+    pub avatar_url: Option<String>,
+    /// Moves the webhook to another text channel of the guild.
+    pub channel_id: Option<Uuid>,
+}
+
+#[utoipa::path(
+    patch,
+    path = "/guilds/{guild_id}/webhooks/{webhook_id}",
+    tag = "webhooks",
+    summary = "Update a webhook",
+    description = "Renames a webhook, changes its avatar or moves it to another channel. Requires MANAGE_WEBHOOKS in its channel, and in the new channel when moving it.",
+    params(
+        ("guild_id" = Uuid, Path, description = "Guild ID"),
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+    ),
+    security(("Authorization" = ["Bearer"])),
+    request_body(
+        content = UpdateWebhookRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, body = Webhook),
+        (status = 400, description = "Invalid name or channel", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 403, description = "Missing MANAGE_WEBHOOKS permission", body = ApiError),
+        (status = 404, description = "Webhook not found", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn update_webhook_handler(
+    UpdateWebhookRoute {
+        guild_id,
+        webhook_id,
+    }: UpdateWebhookRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(req): Json<UpdateWebhookRequest>,
+) -> Result<Response<Webhook>, ApiError> {
+    let guild = GuildId::from(guild_id);
+    // Moving a webhook also changes the webhooks of the channel it leaves.
+    let previous_channel = match req.channel_id {
+        Some(_) => Some(
+            state
+                .webhook_service
+                .get_webhook(identity.clone(), guild.clone(), webhook_id)
+                .await
+                .map_err(map_core_error)?
+                .channel_id,
+        ),
+        None => None,
+    };
+
+    let webhook = state
+        .webhook_service
+        .update_webhook(
+            identity,
+            guild,
+            webhook_id,
+            UpdateWebhookInput {
+                name: req.name,
+                avatar_url: req.avatar_url,
+                channel_id: req.channel_id.map(|id| ChannelId(Id(id))),
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_webhooks_update(&state.hub, guild_id, webhook.channel_id).await;
+    if let Some(previous) = previous_channel
+        && previous != webhook.channel_id
+    {
+        publish_webhooks_update(&state.hub, guild_id, previous).await;
+    }
+
+    Ok(Response::OK(webhook))
+}
@@ -0,0 +1,76 @@
+use axum::{Json, extract::State, http::StatusCode};
+use axum_extra::routing::TypedPath;
+use ferriscord_core::guild::domain::interaction::ports::{FollowUpInput, InteractionService};
+use ferriscord_entities::embed::Embed;
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, interactions::publish_reply, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/interactions/{interaction_id}/{token}/followups")]
+pub struct CreateFollowUpRoute {
+    pub interaction_id: Uuid,
+    pub token: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateFollowUpRequest {
+    #[serde(default)]
+    pub content: String,
+    /// Rich embeds, sent along or instead of the content.
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+    /// Defaults to whether the initial response was ephemeral.
+    pub ephemeral: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/interactions/{interaction_id}/{token}/followups",
+    tag = "commands",
+    summary = "Send a follow-up",
+    description = "Sends a further message for an answered or deferred interaction, within 15 minutes of the invocation.",
+    params(
+        ("interaction_id" = Uuid, Path, description = "Interaction ID"),
+        ("token" = String, Path, description = "Interaction token"),
+    ),
+    request_body(
+        content = CreateFollowUpRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 204, description = "Follow-up sent"),
+        (status = 400, description = "Invalid content, not answered yet or too late", body = ApiError),
+        (status = 404, description = "Unknown interaction or wrong token", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn create_follow_up_handler(
+    CreateFollowUpRoute {
+        interaction_id,
+        token,
+    }: CreateFollowUpRoute,
+    State(state): State<AppState>,
+    Json(req): Json<CreateFollowUpRequest>,
+) -> Result<StatusCode, ApiError> {
+    let reply = state
+        .interaction_service
+        .follow_up(
+            interaction_id,
+            &token,
+            FollowUpInput {
+                content: req.content,
+                embeds: req.embeds,
+                ephemeral: req.ephemeral,
+            },
+        )
+        .await
+        .map_err(map_core_error)?;
+
+    publish_reply(&state, reply).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,21 @@
+use axum::Router;
+use axum_extra::routing::RouterExt;
+
+use crate::{
+    handlers::interaction::{
+        create_follow_up::create_follow_up_handler,
+        respond_interaction::respond_interaction_handler,
+    },
+    state::AppState,
+};
+
+pub mod create_follow_up;
+pub mod respond_interaction;
+
+/// Routes authenticated by the interaction token in the path rather than a
+/// bearer token.
+pub fn interaction_routes() -> Router<AppState> {
+    Router::new()
+        .typed_post(respond_interaction_handler)
+        .typed_post(create_follow_up_handler)
+}
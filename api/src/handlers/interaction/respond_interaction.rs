@@ -0,0 +1,56 @@
+use axum::{Json, extract::State, http::StatusCode};
+use axum_extra::routing::TypedPath;
+use ferriscord_core::guild::domain::interaction::ports::InteractionService;
+use ferriscord_entities::interaction::InteractionCallback;
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, interactions::publish_reply, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/interactions/{interaction_id}/{token}/callback")]
+pub struct RespondInteractionRoute {
+    pub interaction_id: Uuid,
+    pub token: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/interactions/{interaction_id}/{token}/callback",
+    tag = "commands",
+    summary = "Respond to an interaction",
+    description = "The bot's initial response, within 3 seconds of the invocation: a message, possibly ephemeral, or a deferral that shows the invoker a loading state until a follow-up arrives. Only one response is accepted.",
+    params(
+        ("interaction_id" = Uuid, Path, description = "Interaction ID"),
+        ("token" = String, Path, description = "Interaction token"),
+    ),
+    request_body(
+        content = InteractionCallback,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 204, description = "Response applied"),
+        (status = 400, description = "Invalid content, already answered or too late", body = ApiError),
+        (status = 404, description = "Unknown interaction or wrong token", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn respond_interaction_handler(
+    RespondInteractionRoute {
+        interaction_id,
+        token,
+    }: RespondInteractionRoute,
+    State(state): State<AppState>,
+    Json(callback): Json<InteractionCallback>,
+) -> Result<StatusCode, ApiError> {
+    let reply = state
+        .interaction_service
+        .respond(interaction_id, &token, callback)
+        .await
+        .map_err(map_core_error)?;
+
+    publish_reply(&state, reply).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -5,24 +5,85 @@ use axum::{
     middleware::{self, Next},
     response::Response,
 };
-use ferriscord_auth::AuthRepository;
+use ferriscord_auth::{AuthRepository, Identity};
 use ferriscord_core::{
     crypto::domain::ports::CryptoError,
-    guild::domain::errors::CoreError,
+    guild::domain::{application::ports::ApplicationService, errors::CoreError},
 };
 use ferriscord_core::user::domain::user::ports::UserService;
 use ferriscord_error::ApiError;
 use ferriscord_server::http::extract_token_from_bearer;
 use tracing::error;
 
+pub mod application;
 pub mod crypto;
+pub mod discord;
 pub mod dm;
 pub mod guild;
+pub mod interaction;
 pub mod user;
+pub mod webhook;
 
 pub(crate) fn map_core_error(error: CoreError) -> ApiError {
     match error {
-        CoreError::InsufficientPermissions => ApiError::Forbidden {
+        CoreError::InsufficientPermissions
+        | CoreError::NotGuildMember
+        | CoreError::NotInVoiceChannel
+        | CoreError::EmojiNotAllowed { .. } => ApiError::Forbidden {
+            message: error.to_string(),
+        },
+        CoreError::NotVoiceChannel { .. }
+        | CoreError::VoiceChannelFull { .. }
+        | CoreError::InvalidVoiceSignal { .. }
+        | CoreError::NotStageChannel { .. }
+        | CoreError::StageAlreadyLive
+        | CoreError::StageNotLive
+        | CoreError::InvalidStageTopic { .. }
+        | CoreError::InvalidWebhook { .. }
+        | CoreError::MaxWebhooksReached { .. }
+        | CoreError::InvalidEventSubscription { .. }
+        | CoreError::MaxEventSubscriptionsReached { .. }
+        | CoreError::InvalidApplication { .. }
+        | CoreError::MaxApplicationsReached { .. }
+        | CoreError::InvalidCommand { .. }
+        | CoreError::MaxCommandsReached { .. }
+        | CoreError::InvalidInteraction { .. }
+        | CoreError::InteractionExpired
+        | CoreError::InvalidMessage { .. }
+        | CoreError::InvalidChannelFollow { .. }
+        | CoreError::MaxChannelFollowsReached { .. }
+        | CoreError::InvalidEmoji { .. }
+        | CoreError::MaxEmojisReached { .. }
+        | CoreError::InvalidScheduledMessage { .. }
+        | CoreError::MaxScheduledMessagesReached { .. }
+        | CoreError::InvalidPoll { .. }
+        | CoreError::PollClosed
+        | CoreError::InvalidScheduledEvent { .. }
+        | CoreError::ScheduledEventEnded
+        | CoreError::MaxScheduledEventsReached { .. }
+        | CoreError::InvalidGuildImport { .. }
+        | CoreError::MaxGuildImportsReached { .. }
+        | CoreError::InvalidAttachmentUpload { .. }
+        | CoreError::MaxAttachmentUploadsReached { .. }
+        | CoreError::GuildSlugAlreadyExists { .. }
+        | CoreError::MaxGuildsReached { .. } => {
+            ApiError::BadRequest {
+                message: error.to_string(),
+            }
+        }
+        CoreError::WebhookNotFound
+        | CoreError::EventSubscriptionNotFound
+        | CoreError::ApplicationNotFound
+        | CoreError::CommandNotFound
+        | CoreError::InteractionNotFound
+        | CoreError::MessageNotFound
+        | CoreError::ChannelFollowNotFound
+        | CoreError::EmojiNotFound
+        | CoreError::ScheduledMessageNotFound
+        | CoreError::PollNotFound
+        | CoreError::ScheduledEventNotFound
+        | CoreError::CalendarFeedNotFound
+        | CoreError::GuildImportNotFound => ApiError::NotFound {
             message: error.to_string(),
         },
         _ => ApiError::Unknown {
@@ -56,6 +117,26 @@ async fn service_auth_middleware(
         .get(AUTHORIZATION)
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
+    // Bots authenticate with their application's token instead of the IdP
+    if let Some(token) = auth_header
+        .to_str()
+        .ok()
+        .and_then(|h| h.strip_prefix("Bot "))
+    {
+        let bot = state
+            .application_service
+            .authenticate_bot(token.trim())
+            .await
+            .map_err(|e| {
+                error!("Auth middleware: failed to authenticate bot: {:?}", e);
+                StatusCode::INTERNAL_SERVER_ERROR
+            })?
+            .ok_or(StatusCode::UNAUTHORIZED)?;
+
+        req.extensions_mut().insert(Identity::Bot(bot));
+        return Ok(next.run(req).await);
+    }
+
     let token = extract_token_from_bearer(auth_header)
         .await
         .map_err(|_| StatusCode::UNAUTHORIZED)?;
@@ -66,15 +147,14 @@ async fn service_auth_middleware(
     })?;
 
     // Upsert the user in the ferriscord DB on every authenticated request
-    if identity.is_user() {
-        if let Err(e) = state
+    if identity.is_user()
+        && let Err(e) = state
             .user_service
             .upsert_by_sub(identity.id(), identity.username())
             .await
-        {
-            error!("Auth middleware: failed to upsert user: {:?}", e);
-            return Err(StatusCode::INTERNAL_SERVER_ERROR);
-        }
+    {
+        error!("Auth middleware: failed to upsert user: {:?}", e);
+        return Err(StatusCode::INTERNAL_SERVER_ERROR);
     }
 
     req.extensions_mut().insert(identity);
@@ -88,6 +168,7 @@ pub fn handlers_routes(state: crate::state::AppState) -> Router<crate::state::Ap
         .merge(user::user_routes(state.clone()))
         .merge(dm::dm_routes(state.clone()))
         .merge(crypto::crypto_routes(state.clone()))
+        .merge(application::application_routes(state.clone()))
         .layer(middleware::from_fn_with_state(
             state.clone(),
             service_auth_middleware,
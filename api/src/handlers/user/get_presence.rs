@@ -0,0 +1,47 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::user::domain::presence::ports::PresenceService;
+use ferriscord_entities::presence::Presence;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/{user_id}/presence")]
+pub struct GetPresenceRoute {
+    pub user_id: Uuid,
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/presence",
+    tag = "users",
+    summary = "Get a user's presence",
+    description = "Returns the user's status aggregated over all of their sessions, their custom status and when they were last seen.",
+    security(("Authorization" = ["Bearer"])),
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+    ),
+    responses(
+        (status = 200, body = Presence),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_presence_handler(
+    GetPresenceRoute { user_id }: GetPresenceRoute,
+    State(state): State<AppState>,
+    Extension(_identity): Extension<Identity>,
+) -> Result<Response<Presence>, ApiError> {
+    let presence = state
+        .presence_service
+        .get(user_id)
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
+
+    Ok(Response::OK(presence))
+}
@@ -0,0 +1,79 @@
+use axum::extract::{Extension, State};
+use axum_extra::routing::TypedPath;
+use ferriscord_auth::Identity;
+use ferriscord_core::{
+    guild::domain::{channel::ports::ChannelService, guild::ports::GuildService},
+    user::domain::{dm::ports::DmService, read_state::ports::ReadStateService, user::ports::UserService},
+};
+use ferriscord_entities::{channel::ChannelKind, read_state::ReadState, user::UserId};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath)]
+#[typed_path("/users/@me/read-states")]
+pub struct GetReadStatesRoute;
+
+#[utoipa::path(
+    get,
+    path = "/users/@me/read-states",
+    tag = "users",
+    summary = "Get unread state",
+    description = "Returns the read state (last read message, latest message, mention count) of every text channel the user can see across their guilds, and of every DM.",
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Vec<ReadState>),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_read_states_handler(
+    _: GetReadStatesRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Vec<ReadState>>, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let guilds = state
+        .guild_service
+        .get_user_guilds(identity.clone(), UserId::from(user.id.0))
+        .await
+        .map_err(map_core_error)?;
+
+    let mut channel_ids: Vec<Uuid> = Vec::new();
+    for guild in guilds {
+        let channels = state
+            .channel_service
+            .get_guild_channels(identity.clone(), guild.id)
+            .await
+            .map_err(map_core_error)?;
+        channel_ids.extend(
+            channels
+                .into_iter()
+                .filter(|c| matches!(c.kind, ChannelKind::Text | ChannelKind::Announcement))
+                .map(|c| c.id.get_uuid()),
+        );
+    }
+
+    let dms = state
+        .dm_service
+        .list(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
+    channel_ids.extend(dms.iter().map(|dm| dm.id.get_uuid()));
+
+    let read_states = state
+        .read_state_service
+        .list(user.id.0, &channel_ids)
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
+
+    Ok(Response::OK(read_states))
+}
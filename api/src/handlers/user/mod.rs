@@ -4,8 +4,11 @@ use axum_extra::routing::RouterExt;
 use crate::{
     handlers::user::{
         get_me::get_me_handler,
+        get_presence::get_presence_handler,
+        get_read_states::get_read_states_handler,
         get_user::get_user_handler,
         get_user_guilds::get_user_guilds,
+        set_custom_status::{clear_custom_status_handler, set_custom_status_handler},
         update_profile::update_profile_handler,
     },
     state::AppState,
@@ -13,8 +16,11 @@ use crate::{
 
 pub mod friends;
 pub mod get_me;
+pub mod get_presence;
+pub mod get_read_states;
 pub mod get_user;
 pub mod get_user_guilds;
+pub mod set_custom_status;
 pub mod update_profile;
 
 pub fn user_routes(state: AppState) -> Router<AppState> {
@@ -23,5 +29,9 @@ pub fn user_routes(state: AppState) -> Router<AppState> {
         .typed_get(get_me_handler)
         .typed_get(get_user_handler)
         .typed_patch(update_profile_handler)
+        .typed_get(get_presence_handler)
+        .typed_get(get_read_states_handler)
+        .typed_put(set_custom_status_handler)
+        .typed_delete(clear_custom_status_handler)
         .merge(friends::friend_routes(state.clone()))
 }
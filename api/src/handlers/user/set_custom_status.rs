@@ -0,0 +1,109 @@
+use axum::{
+    Json,
+    extract::{Extension, State},
+};
+use axum_extra::routing::TypedPath;
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_core::user::domain::{
+    common::CoreError, presence::ports::PresenceService, user::ports::UserService,
+};
+use ferriscord_entities::presence::{CustomStatus, Presence};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::{presence::broadcast_presence_to_user_guilds, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/users/@me/custom-status")]
+pub struct CustomStatusRoute;
+
+#[derive(Deserialize, ToSchema)]
+pub struct SetCustomStatusRequest {
+    pub text: Option<String>,
+    pub emoji: Option<String>,
+    /// When set, the custom status is cleared automatically at this time.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[utoipa::path(
+    put,
+    path = "/users/@me/custom-status",
+    tag = "users",
+    summary = "Set custom status",
+    description = "Sets the current user's custom status text and/or emoji, optionally expiring at `expires_at`. Sending neither text nor emoji clears it.",
+    security(("Authorization" = ["Bearer"])),
+    request_body = SetCustomStatusRequest,
+    responses(
+        (status = 200, body = Presence),
+        (status = 400, description = "Bad request", body = ApiError),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn set_custom_status_handler(
+    _: CustomStatusRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+    Json(body): Json<SetCustomStatusRequest>,
+) -> Result<Response<Presence>, ApiError> {
+    let custom_status = CustomStatus {
+        text: body.text,
+        emoji: body.emoji,
+        expires_at: body.expires_at,
+    };
+
+    let presence = update_custom_status(&state, identity, Some(custom_status)).await?;
+
+    Ok(Response::OK(presence))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/@me/custom-status",
+    tag = "users",
+    summary = "Clear custom status",
+    security(("Authorization" = ["Bearer"])),
+    responses(
+        (status = 200, body = Presence),
+        (status = 401, description = "Unauthorized", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn clear_custom_status_handler(
+    _: CustomStatusRoute,
+    State(state): State<AppState>,
+    Extension(identity): Extension<Identity>,
+) -> Result<Response<Presence>, ApiError> {
+    let presence = update_custom_status(&state, identity, None).await?;
+
+    Ok(Response::OK(presence))
+}
+
+async fn update_custom_status(
+    state: &AppState,
+    identity: Identity,
+    custom_status: Option<CustomStatus>,
+) -> Result<Presence, ApiError> {
+    let user = state
+        .user_service
+        .get_me(identity.id())
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .ok_or_else(|| ApiError::NotFound { message: "user not found".into() })?;
+
+    let presence = state
+        .presence_service
+        .set_custom_status(user.id.0, custom_status)
+        .await
+        .map_err(|e| match e {
+            CoreError::InvalidCustomStatus { .. } => ApiError::BadRequest { message: e.to_string() },
+            _ => ApiError::Unknown { message: e.to_string() },
+        })?;
+
+    broadcast_presence_to_user_guilds(state, identity, user.id.0, &presence).await;
+
+    Ok(presence)
+}
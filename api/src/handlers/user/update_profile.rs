@@ -152,9 +152,9 @@ pub async fn update_profile_handler(
     }
 
     // Resolve empty display_name string → None (NULL in DB)
-    let display_name_db = display_name.map(|d| if d.is_empty() { None } else { Some(d) }).flatten();
+    let display_name_db = display_name.and_then(|d| if d.is_empty() { None } else { Some(d) });
     // Resolve empty bio string → None (NULL in DB)
-    let bio_db = bio.map(|b| if b.is_empty() { None } else { Some(b) }).flatten();
+    let bio_db = bio.and_then(|b| if b.is_empty() { None } else { Some(b) });
 
     let user = state
         .user_service
@@ -0,0 +1,60 @@
+use axum::{Json, extract::State, http::StatusCode};
+use axum_extra::routing::TypedPath;
+use ferriscord_error::ApiError;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use super::{WebhookPost, post_webhook_message, slack::SlackPayload};
+use crate::state::AppState;
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/webhooks/{webhook_id}/{token}/slack")]
+pub struct ExecuteSlackWebhookRoute {
+    pub webhook_id: Uuid,
+    pub token: String,
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks/{webhook_id}/{token}/slack",
+    tag = "webhooks",
+    summary = "Execute a webhook with a Slack payload",
+    description = "Accepts a Slack incoming-webhook payload. `text` and Block Kit header, section and context blocks become the message content; legacy attachments become embeds. Answers `ok` like Slack does.",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+        ("token" = String, Path, description = "Webhook token"),
+    ),
+    request_body(
+        content = SlackPayload,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, description = "Message posted", body = String),
+        (status = 400, description = "Invalid or empty message", body = ApiError),
+        (status = 404, description = "Unknown webhook or wrong token", body = ApiError),
+        (status = 429, description = "Webhook rate limit exceeded", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn execute_slack_webhook_handler(
+    ExecuteSlackWebhookRoute { webhook_id, token }: ExecuteSlackWebhookRoute,
+    State(state): State<AppState>,
+    Json(payload): Json<SlackPayload>,
+) -> Result<(StatusCode, &'static str), ApiError> {
+    let message = payload.into_message();
+    post_webhook_message(
+        &state,
+        webhook_id,
+        &token,
+        WebhookPost {
+            username: message.username,
+            avatar_url: message.avatar_url,
+            content: message.content,
+            embeds: message.embeds,
+            files: Vec::new(),
+        },
+    )
+    .await?;
+
+    Ok((StatusCode::OK, "ok"))
+}
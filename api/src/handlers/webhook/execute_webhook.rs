@@ -0,0 +1,168 @@
+use axum::{
+    Json,
+    extract::{FromRequest, Multipart, Query, Request, State},
+    http::{StatusCode, header::CONTENT_TYPE},
+    response::{IntoResponse, Response as AxumResponse},
+};
+use axum_extra::routing::TypedPath;
+use ferriscord_entities::{embed::Embed, message::Message};
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use super::{WebhookPost, post_webhook_message};
+use crate::{attachments::AttachmentFile, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/webhooks/{webhook_id}/{token}")]
+pub struct ExecuteWebhookRoute {
+    pub webhook_id: Uuid,
+    pub token: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct ExecuteWebhookQuery {
+    /// Wait for the message to be stored and return it
+    pub wait: Option<bool>,
+}
+
+/// Same shape as a Discord webhook payload; fields this server does not know
+/// (`tts`, `allowed_mentions`, ...) are ignored.
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct ExecuteWebhookRequest {
+    #[serde(default)]
+    pub content: Option<String>,
+    /// Overrides the webhook's name for this message.
+    #[serde(default)]
+    pub username: Option<String>,
+    /// Overrides the webhook's avatar for this message.
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+    /// Up to 10 embeds.
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+}
+
+fn bad_request(message: String) -> ApiError {
+    ApiError::BadRequest { message }
+}
+
+/// Reads a multipart body: the payload as `payload_json` (or plain
+/// `content`, `username` and `avatar_url` fields) and any file field.
+async fn read_multipart(mut multipart: Multipart) -> Result<WebhookPost, ApiError> {
+    let mut payload = ExecuteWebhookRequest::default();
+    let mut files = Vec::new();
+
+    while let Some(field) = multipart
+        .next_field()
+        .await
+        .map_err(|e| bad_request(format!("multipart error: {}", e)))?
+    {
+        let name = field.name().unwrap_or("").to_string();
+
+        if let Some(filename) = field.file_name().map(str::to_string) {
+            let content_type = field
+                .content_type()
+                .unwrap_or("application/octet-stream")
+                .to_string();
+            let data = field
+                .bytes()
+                .await
+                .map_err(|e| bad_request(format!("failed to read file '{}': {}", filename, e)))?;
+            files.push(AttachmentFile {
+                filename,
+                content_type,
+                data,
+            });
+            continue;
+        }
+
+        let value = field
+            .text()
+            .await
+            .map_err(|e| bad_request(format!("failed to read field '{}': {}", name, e)))?;
+        match name.as_str() {
+            "payload_json" => {
+                payload = serde_json::from_str(&value)
+                    .map_err(|e| bad_request(format!("invalid payload_json: {}", e)))?
+            }
+            "content" => payload.content = Some(value),
+            "username" => payload.username = Some(value),
+            "avatar_url" => payload.avatar_url = Some(value),
+            _ => {}
+        }
+    }
+
+    Ok(WebhookPost {
+        username: payload.username,
+        avatar_url: payload.avatar_url,
+        content: payload.content.unwrap_or_default(),
+        embeds: payload.embeds,
+        files,
+    })
+}
+
+#[utoipa::path(
+    post,
+    path = "/webhooks/{webhook_id}/{token}",
+    tag = "webhooks",
+    summary = "Execute a webhook",
+    description = "Posts a message into the webhook's channel. No bearer token is needed: the webhook token in the path authenticates the request. Send JSON, or multipart/form-data with the JSON in a `payload_json` field and files in any file field. Discord webhook payloads are accepted as is. Each webhook is rate limited.",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+        ("token" = String, Path, description = "Webhook token"),
+        ExecuteWebhookQuery,
+    ),
+    request_body(
+        content = ExecuteWebhookRequest,
+        content_type = "application/json",
+    ),
+    responses(
+        (status = 200, description = "Message posted (with `wait=true`)", body = Message),
+        (status = 204, description = "Message posted"),
+        (status = 400, description = "Invalid or empty message", body = ApiError),
+        (status = 404, description = "Unknown webhook or wrong token", body = ApiError),
+        (status = 429, description = "Webhook rate limit exceeded", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn execute_webhook_handler(
+    ExecuteWebhookRoute { webhook_id, token }: ExecuteWebhookRoute,
+    State(state): State<AppState>,
+    Query(query): Query<ExecuteWebhookQuery>,
+    request: Request,
+) -> Result<AxumResponse, ApiError> {
+    let is_multipart = request
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("multipart/form-data"));
+
+    let post = if is_multipart {
+        let multipart = Multipart::from_request(request, &state)
+            .await
+            .map_err(|e| bad_request(e.body_text()))?;
+        read_multipart(multipart).await?
+    } else {
+        let Json(payload) = Json::<ExecuteWebhookRequest>::from_request(request, &state)
+            .await
+            .map_err(|e| bad_request(e.body_text()))?;
+        WebhookPost {
+            username: payload.username,
+            avatar_url: payload.avatar_url,
+            content: payload.content.unwrap_or_default(),
+            embeds: payload.embeds,
+            files: Vec::new(),
+        }
+    };
+
+    let message = post_webhook_message(&state, webhook_id, &token, post).await?;
+
+    if query.wait.unwrap_or(false) {
+        Ok(Response::OK(message).into_response())
+    } else {
+        Ok(StatusCode::NO_CONTENT.into_response())
+    }
+}
@@ -0,0 +1,46 @@
+use axum::extract::State;
+use axum_extra::routing::TypedPath;
+use ferriscord_core::guild::domain::webhook::ports::WebhookService;
+use ferriscord_entities::webhook::Webhook;
+use ferriscord_error::ApiError;
+use ferriscord_server::http::response::Response;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState};
+
+#[derive(TypedPath, Deserialize)]
+#[typed_path("/webhooks/{webhook_id}/{token}")]
+pub struct GetWebhookWithTokenRoute {
+    pub webhook_id: Uuid,
+    pub token: String,
+}
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/{webhook_id}/{token}",
+    tag = "webhooks",
+    summary = "Get a webhook with its token",
+    description = "Returns the webhook the token belongs to, without the token. Lets integrations check their URL.",
+    params(
+        ("webhook_id" = Uuid, Path, description = "Webhook ID"),
+        ("token" = String, Path, description = "Webhook token"),
+    ),
+    responses(
+        (status = 200, body = Webhook),
+        (status = 404, description = "Unknown webhook or wrong token", body = ApiError),
+        (status = 500, description = "Internal server error", body = ApiError),
+    )
+)]
+pub async fn get_webhook_with_token_handler(
+    GetWebhookWithTokenRoute { webhook_id, token }: GetWebhookWithTokenRoute,
+    State(state): State<AppState>,
+) -> Result<Response<Webhook>, ApiError> {
+    let webhook = state
+        .webhook_service
+        .get_webhook_with_token(webhook_id, &token)
+        .await
+        .map_err(map_core_error)?;
+
+    Ok(Response::OK(webhook))
+}
@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use axum::{Router, extract::DefaultBodyLimit};
+use axum_extra::routing::RouterExt;
+use ferriscord_core::guild::domain::webhook::ports::{ExecuteWebhookInput, WebhookService};
+use ferriscord_entities::{embed::Embed, event_subscription::GuildEventType, message::Message};
+use ferriscord_error::ApiError;
+use ferriscord_storage::StoragePort;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    attachments::{AttachmentFile, post_with_files},
+    events::dispatch_guild_event,
+    handlers::{
+        map_core_error,
+        webhook::{
+            execute_slack_webhook::execute_slack_webhook_handler,
+            execute_webhook::execute_webhook_handler,
+            get_webhook_with_token::get_webhook_with_token_handler,
+        },
+    },
+    link_previews::spawn_previews,
+    read_state::record_guild_message,
+    state::AppState,
+};
+
+pub mod execute_slack_webhook;
+pub mod execute_webhook;
+pub mod get_webhook_with_token;
+pub mod slack;
+
+/// Routes authenticated by the webhook token in the path rather than a
+/// bearer token.
+pub fn webhook_routes() -> Router<AppState> {
+    Router::new()
+        .typed_get(get_webhook_with_token_handler)
+        .typed_post(execute_webhook_handler)
+        .typed_post(execute_slack_webhook_handler)
+        .layer(DefaultBodyLimit::max(8 * 1024 * 1024))
+}
+
+/// What a webhook payload asks to post, whatever format it came in.
+pub(crate) struct WebhookPost {
+    pub username: Option<String>,
+    pub avatar_url: Option<String>,
+    pub content: String,
+    pub embeds: Vec<Embed>,
+    pub files: Vec<AttachmentFile>,
+}
+
+/// Posts a message through a webhook: checks the token and the webhook's
+/// rate limit, stores files, then publishes the message like a user's.
+pub(crate) async fn post_webhook_message(
+    state: &AppState,
+    webhook_id: Uuid,
+    token: &str,
+    post: WebhookPost,
+) -> Result<Message, ApiError> {
+    let webhook = state
+        .webhook_service
+        .get_webhook_with_token(webhook_id, token)
+        .await
+        .map_err(map_core_error)?;
+
+    // Only requests holding the token count against the webhook's budget.
+    state
+        .webhook_limiter
+        .check(webhook.id)
+        .map_err(|retry_after| ApiError::TooManyRequests {
+            message: format!(
+                "webhook rate limit exceeded, retry in {}s",
+                retry_after.as_secs().max(1)
+            ),
+        })?;
+
+    // Files are stored only once the token checks out, and removed again
+    // if the payload is refused.
+    let bucket = &state.args.storage.bucket;
+    let (webhook, mut message) = post_with_files(
+        &state.storage,
+        bucket,
+        webhook.channel_id,
+        post.files,
+        |attachments| async move {
+            state
+                .webhook_service
+                .execute_webhook(
+                    webhook_id,
+                    token,
+                    ExecuteWebhookInput {
+                        username: post.username,
+                        avatar_url: post.avatar_url,
+                        content: post.content,
+                        embeds: post.embeds,
+                        attachments,
+                    },
+                )
+                .await
+                .map_err(map_core_error)
+        },
+    )
+    .await?;
+
+    for attachment in &mut message.attachments {
+        match state
+            .storage
+            .presigned_get_url(bucket, &attachment.storage_key, Duration::from_secs(3600))
+            .await
+        {
+            Ok(url) => attachment.url = url,
+            Err(e) => error!(
+                "failed to generate presigned URL for '{}': {}",
+                attachment.storage_key, e
+            ),
+        }
+    }
+
+    for room in [
+        format!("channel:{}", webhook.channel_id),
+        format!("guild:{}", webhook.guild_id),
+    ] {
+        if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+            "type": "message.new",
+            "room": room,
+            "data": &message,
+        })) {
+            state.hub.publish(&room, payload).await;
+        }
+    }
+
+    record_guild_message(
+        state,
+        webhook.guild_id,
+        webhook.channel_id,
+        message.id.get_uuid(),
+        None,
+        &message.content,
+    )
+    .await;
+
+    dispatch_guild_event(
+        state,
+        webhook.guild_id,
+        GuildEventType::MessageCreate,
+        &message,
+    )
+    .await;
+
+    spawn_previews(state, None, webhook.guild_id, &message);
+
+    Ok(message)
+}
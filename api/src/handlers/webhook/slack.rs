@@ -0,0 +1,257 @@
+//! Translation of Slack incoming-webhook payloads, so integrations that only
+//! speak Slack can post without changes.
+
+use chrono::DateTime;
+use ferriscord_entities::embed::{
+    Embed, EmbedAuthor, EmbedField, EmbedFooter, EmbedKind, EmbedMedia,
+};
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct SlackPayload {
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub username: Option<String>,
+    #[serde(default)]
+    pub icon_url: Option<String>,
+    #[serde(default)]
+    pub attachments: Vec<SlackAttachment>,
+    /// Block Kit layout. Header, section and context blocks are rendered as
+    /// text; other blocks are ignored.
+    #[serde(default)]
+    #[schema(value_type = Vec<Object>)]
+    pub blocks: Vec<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct SlackAttachment {
+    pub fallback: Option<String>,
+    /// `#RRGGBB`, or one of `good`, `warning` and `danger`.
+    pub color: Option<String>,
+    pub pretext: Option<String>,
+    pub author_name: Option<String>,
+    pub author_link: Option<String>,
+    pub author_icon: Option<String>,
+    pub title: Option<String>,
+    pub title_link: Option<String>,
+    pub text: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<SlackField>,
+    pub image_url: Option<String>,
+    pub thumb_url: Option<String>,
+    pub footer: Option<String>,
+    pub footer_icon: Option<String>,
+    /// Unix timestamp, as a number or a string.
+    #[schema(value_type = Option<Object>)]
+    pub ts: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct SlackField {
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub value: String,
+    #[serde(default)]
+    pub short: bool,
+}
+
+/// A Slack payload translated into message content and embeds.
+pub struct SlackMessage {
+    pub username: Option<String>,
+    pub avatar_url: Option<String>,
+    pub content: String,
+    pub embeds: Vec<Embed>,
+}
+
+/// Rewrites Slack links (`<url|label>` and `<url>`) to Markdown.
+fn convert_links(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('<') {
+        let Some(len) = rest[start..].find('>') else {
+            break;
+        };
+        out.push_str(&rest[..start]);
+        let inner = &rest[start + 1..start + len];
+        match inner.split_once('|') {
+            Some((url, label)) if url.contains("://") || url.starts_with("mailto:") => {
+                out.push_str(&format!("[{label}]({url})"))
+            }
+            None if inner.contains("://") => out.push_str(inner),
+            _ => out.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+fn parse_color(color: &str) -> Option<u32> {
+    match color {
+        "good" => Some(0x2EB67D),
+        "warning" => Some(0xECB22E),
+        "danger" => Some(0xE01E5A),
+        hex => u32::from_str_radix(hex.trim_start_matches('#'), 16)
+            .ok()
+            .filter(|c| *c <= 0xFF_FFFF),
+    }
+}
+
+fn parse_ts(ts: &serde_json::Value) -> Option<f64> {
+    match ts {
+        serde_json::Value::Number(n) => n.as_f64(),
+        serde_json::Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn block_text(value: &serde_json::Value) -> Option<String> {
+    value
+        .get("text")
+        .and_then(|t| t.as_str())
+        .map(convert_links)
+}
+
+fn render_blocks(blocks: &[serde_json::Value]) -> Vec<String> {
+    blocks
+        .iter()
+        .filter_map(|block| match block.get("type")?.as_str()? {
+            "header" => block
+                .get("text")
+                .and_then(block_text)
+                .map(|t| format!("**{t}**")),
+            "section" => {
+                let mut lines: Vec<String> =
+                    block.get("text").and_then(block_text).into_iter().collect();
+                if let Some(fields) = block.get("fields").and_then(|f| f.as_array()) {
+                    lines.extend(fields.iter().filter_map(block_text));
+                }
+                Some(lines.join("\n")).filter(|t| !t.is_empty())
+            }
+            "context" => {
+                let parts: Vec<String> = block
+                    .get("elements")?
+                    .as_array()?
+                    .iter()
+                    .filter_map(block_text)
+                    .collect();
+                Some(parts.join(" ")).filter(|t| !t.is_empty())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+fn into_embed(attachment: SlackAttachment) -> Embed {
+    let mut embed = Embed {
+        kind: EmbedKind::Rich,
+        title: non_empty(attachment.title),
+        url: non_empty(attachment.title_link),
+        description: non_empty(attachment.text).map(|t| convert_links(&t)),
+        color: attachment.color.as_deref().and_then(parse_color),
+        timestamp: attachment
+            .ts
+            .as_ref()
+            .and_then(parse_ts)
+            .and_then(|ts| DateTime::from_timestamp(ts as i64, 0)),
+        author: non_empty(attachment.author_name).map(|name| EmbedAuthor {
+            name,
+            url: non_empty(attachment.author_link),
+            icon_url: non_empty(attachment.author_icon),
+        }),
+        provider: None,
+        footer: non_empty(attachment.footer).map(|text| EmbedFooter {
+            text,
+            icon_url: non_empty(attachment.footer_icon),
+        }),
+        image: non_empty(attachment.image_url).map(|url| EmbedMedia { url }),
+        thumbnail: non_empty(attachment.thumb_url).map(|url| EmbedMedia { url }),
+        fields: attachment
+            .fields
+            .into_iter()
+            .filter(|f| !f.value.trim().is_empty())
+            .map(|f| EmbedField {
+                // Embed fields need a name; Slack's are optional.
+                name: if f.title.trim().is_empty() {
+                    "\u{200b}".to_string()
+                } else {
+                    f.title
+                },
+                value: convert_links(&f.value),
+                inline: f.short,
+            })
+            .collect(),
+    };
+    if embed.text_len() == 0 {
+        embed.description = non_empty(attachment.fallback);
+    }
+    embed
+}
+
+impl SlackPayload {
+    pub fn into_message(self) -> SlackMessage {
+        // With blocks, `text` is only the notification fallback.
+        let mut lines = render_blocks(&self.blocks);
+        if lines.is_empty() {
+            lines.extend(non_empty(self.text).map(|t| convert_links(&t)));
+        }
+        lines.extend(
+            self.attachments
+                .iter()
+                .filter_map(|a| non_empty(a.pretext.clone()))
+                .map(|t| convert_links(&t)),
+        );
+
+        SlackMessage {
+            username: self.username,
+            avatar_url: self.icon_url,
+            content: lines.join("\n"),
+            embeds: self.attachments.into_iter().map(into_embed).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slack_payload_into_message() {
+        let payload: SlackPayload = serde_json::from_value(serde_json::json!({
+            "text": "Deploy of <https://ci.example/42|#42> finished",
+            "username": "ci",
+            "attachments": [{
+                "color": "good",
+                "title": "api",
+                "title_link": "https://ci.example/42",
+                "fields": [{ "title": "Duration", "value": "3m", "short": true }],
+                "ts": "1760000000"
+            }]
+        }))
+        .unwrap();
+
+        let message = payload.into_message();
+        assert_eq!(
+            message.content,
+            "Deploy of [#42](https://ci.example/42) finished"
+        );
+        assert_eq!(message.username.as_deref(), Some("ci"));
+        let embed = &message.embeds[0];
+        assert_eq!(embed.color, Some(0x2EB67D));
+        assert_eq!(embed.fields[0].name, "Duration");
+        assert!(embed.fields[0].inline);
+        assert_eq!(embed.timestamp.map(|t| t.timestamp()), Some(1_760_000_000));
+
+        assert_eq!(
+            convert_links("a <b> <!here> <mailto:x@y.z|mail>"),
+            "a <b> <!here> [mail](mailto:x@y.z)"
+        );
+    }
+}
@@ -0,0 +1,172 @@
+//! Slash command interactions. An invocation is handed to its bot over the
+//! gateway or the application's HTTP endpoint; whatever the bot answers is
+//! published to the channel, or to the invoker alone when ephemeral.
+
+use std::time::Duration;
+
+use ferriscord_core::guild::domain::interaction::ports::{
+    INTERACTION_RESPONSE_WINDOW, InteractionDelivery, InteractionReply, InteractionService,
+    Invocation,
+};
+use ferriscord_entities::{event_subscription::GuildEventType, interaction::Interaction};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::{events::dispatch_guild_event, read_state::record_guild_message, state::AppState};
+
+/// How often interactions past their follow-up window are deleted.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+async fn publish(state: &AppState, room: String, kind: &str, data: impl Serialize) {
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": kind,
+        "room": room,
+        "data": data,
+    })) {
+        state.hub.publish(&room, payload).await;
+    }
+}
+
+/// Hands an invocation to its bot and fails it if the bot doesn't answer
+/// within the response window.
+pub async fn deliver_interaction(state: &AppState, invocation: Invocation) {
+    let Invocation {
+        interaction,
+        delivery,
+    } = invocation;
+
+    match delivery {
+        InteractionDelivery::Gateway { bot_user_id } => {
+            publish(
+                state,
+                format!("user:{}", bot_user_id),
+                "interaction.create",
+                &interaction,
+            )
+            .await;
+        }
+        InteractionDelivery::Http(request) => {
+            let state = state.clone();
+            let interaction = interaction.clone();
+            tokio::spawn(async move {
+                match state
+                    .interaction_service
+                    .deliver_http(&interaction, request)
+                    .await
+                {
+                    Ok(Some(reply)) => publish_reply(&state, reply).await,
+                    Ok(None) => {}
+                    Err(e) => warn!("interaction {} delivery failed: {}", interaction.id, e),
+                }
+            });
+        }
+    }
+
+    let state = state.clone();
+    tokio::spawn(async move {
+        let window = INTERACTION_RESPONSE_WINDOW.to_std().unwrap_or_default();
+        tokio::time::sleep(window).await;
+        match state
+            .interaction_service
+            .fail_unanswered(interaction.id)
+            .await
+        {
+            Ok(Some(failed)) => publish_failure(&state, &failed).await,
+            Ok(None) => {}
+            Err(e) => warn!("failed to expire interaction {}: {:?}", interaction.id, e),
+        }
+    });
+}
+
+/// Tells the invoker the bot didn't answer.
+async fn publish_failure(state: &AppState, interaction: &Interaction) {
+    publish(
+        state,
+        format!("user:{}", interaction.user_id),
+        "interaction.failed",
+        serde_json::json!({
+            "interaction_id": interaction.id,
+            "guild_id": interaction.guild_id,
+            "channel_id": interaction.channel_id,
+            "command_name": interaction.command_name,
+        }),
+    )
+    .await;
+}
+
+/// Publishes a bot's answer: messages like any other, ephemeral replies and
+/// ephemeral deferrals to the invoker only.
+pub async fn publish_reply(state: &AppState, reply: InteractionReply) {
+    match reply {
+        InteractionReply::Message { guild_id, message } => {
+            let channel_id = message.channel_id.get_uuid();
+            publish(
+                state,
+                format!("channel:{}", channel_id),
+                "message.new",
+                &message,
+            )
+            .await;
+            publish(
+                state,
+                format!("guild:{}", guild_id),
+                "message.new",
+                &message,
+            )
+            .await;
+
+            record_guild_message(
+                state,
+                guild_id,
+                channel_id,
+                message.id.get_uuid(),
+                Some(*message.author.id.get_uuid()),
+                &message.content,
+            )
+            .await;
+
+            dispatch_guild_event(state, guild_id, GuildEventType::MessageCreate, &message).await;
+        }
+        InteractionReply::Ephemeral { user_id, message } => {
+            publish(
+                state,
+                format!("user:{}", user_id),
+                "interaction.reply",
+                &message,
+            )
+            .await;
+        }
+        InteractionReply::Deferred(interaction) => {
+            let room = if interaction.ephemeral {
+                format!("user:{}", interaction.user_id)
+            } else {
+                format!("channel:{}", interaction.channel_id)
+            };
+            publish(
+                state,
+                room,
+                "interaction.deferred",
+                serde_json::json!({
+                    "interaction_id": interaction.id,
+                    "channel_id": interaction.channel_id,
+                    "application_id": interaction.application_id,
+                    "ephemeral": interaction.ephemeral,
+                }),
+            )
+            .await;
+        }
+    }
+}
+
+/// Deletes interactions that can no longer be followed up.
+pub async fn prune_interactions(state: AppState) {
+    let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = state.interaction_service.prune_interactions().await {
+            warn!("failed to prune interactions: {:?}", e);
+        }
+    }
+}
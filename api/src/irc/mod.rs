@@ -0,0 +1,663 @@
+//! IRC gateway. IRC clients log in with SASL PLAIN, giving a FerrisCord
+//! access token, or `Bot <token>`, as the password. Guild text channels are
+//! joined as `#<guild slug>/<channel>` and DMs are private messages. Messages
+//! go through the same services and hub rooms as those of the web client,
+//! so permissions apply the same way.
+
+use std::time::Duration;
+
+use ferriscord_irc::{IrcError, Message, SaslPlain, numeric};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{
+        TcpListener, TcpStream,
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+    },
+    sync::mpsc,
+    task::JoinHandle,
+};
+use tracing::{info, warn};
+
+use crate::{
+    state::AppState,
+    ws::{WsAuth, authenticate},
+};
+
+mod session;
+
+/// Clients must be logged in this long after connecting.
+const REGISTRATION_TIMEOUT: Duration = Duration::from_secs(60);
+/// Longest line read from a client, message tags included.
+const MAX_INBOUND_LINE: usize = 8 * 1024;
+/// How long to wait for the last lines to be flushed before closing.
+const CLOSE_FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A line read from the client.
+enum Inbound {
+    Line(String),
+    /// The line exceeded `MAX_INBOUND_LINE`; the connection is closed.
+    TooLong,
+}
+
+/// A client that logged in and registered.
+struct Registered {
+    auth: WsAuth,
+    /// The nickname the client asked for. Nicknames follow usernames, so it
+    /// is replaced if it differs.
+    nick: String,
+}
+
+/// Starts the gateway when `IRC_ENABLED` is set.
+pub fn start(state: AppState) {
+    let irc = &state.args.server.irc;
+    if !irc.enabled {
+        return;
+    }
+    let addr = format!("{}:{}", irc.host, irc.port);
+    tokio::spawn(listen(state, addr));
+}
+
+async fn listen(state: AppState, addr: String) {
+    let listener = match TcpListener::bind(&addr).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            warn!("IRC: failed to listen on {}: {}", addr, e);
+            return;
+        }
+    };
+    info!("IRC gateway listening on {}", addr);
+    serve(state, listener).await;
+}
+
+async fn serve(state: AppState, listener: TcpListener) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                tokio::spawn(handle_connection(state.clone(), stream));
+            }
+            Err(e) => warn!("IRC: failed to accept a connection: {}", e),
+        }
+    }
+}
+
+/// Reads lines from the client into a channel, so that reading is not lost
+/// when the session waits on something else.
+fn spawn_reader(read: OwnedReadHalf) -> mpsc::Receiver<Inbound> {
+    let (tx, rx) = mpsc::channel(64);
+    tokio::spawn(async move {
+        let mut reader = BufReader::new(read);
+        let mut buf = Vec::new();
+        loop {
+            buf.clear();
+            match (&mut reader)
+                .take(MAX_INBOUND_LINE as u64)
+                .read_until(b'\n', &mut buf)
+                .await
+            {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if buf.last() != Some(&b'\n') && buf.len() >= MAX_INBOUND_LINE {
+                        let _ = tx.send(Inbound::TooLong).await;
+                        break;
+                    }
+                    let line = String::from_utf8_lossy(&buf).into_owned();
+                    if tx.send(Inbound::Line(line)).await.is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+    rx
+}
+
+/// Writes the lines sent on the returned channel, until every sender is
+/// dropped.
+fn spawn_writer(mut write: OwnedWriteHalf) -> (mpsc::Sender<String>, JoinHandle<()>) {
+    let (tx, mut rx) = mpsc::channel::<String>(256);
+    let task = tokio::spawn(async move {
+        while let Some(line) = rx.recv().await {
+            if write
+                .write_all(format!("{line}\r\n").as_bytes())
+                .await
+                .is_err()
+            {
+                return;
+            }
+        }
+        let _ = write.shutdown().await;
+    });
+    (tx, task)
+}
+
+async fn send(out: &mpsc::Sender<String>, message: Message) {
+    let _ = out.send(message.to_string()).await;
+}
+
+/// A reply from the server, addressed to `target`.
+fn reply<'a>(server: &str, command: &str, params: impl IntoIterator<Item = &'a str>) -> Message {
+    Message::new(Some(server), command, params)
+}
+
+/// Answers capability negotiation. Only `sasl` is offered.
+fn cap_reply(server: &str, target: &str, message: &Message) -> Option<Message> {
+    let subcommand = message.param(0)?.to_ascii_uppercase();
+    Some(match subcommand.as_str() {
+        "LS" => {
+            let version = message.param(1).and_then(|v| v.parse::<u32>().ok());
+            let caps = if version.is_some_and(|v| v >= 302) {
+                "sasl=PLAIN"
+            } else {
+                "sasl"
+            };
+            reply(server, "CAP", [target, "LS", caps])
+        }
+        "LIST" => reply(server, "CAP", [target, "LIST", ""]),
+        "REQ" => {
+            let requested = message.param(1).unwrap_or("");
+            let known = requested
+                .split_whitespace()
+                .all(|cap| cap.eq_ignore_ascii_case("sasl"));
+            let verb = if known { "ACK" } else { "NAK" };
+            reply(server, "CAP", [target, verb, requested])
+        }
+        "END" => return None,
+        _ => reply(
+            server,
+            numeric::ERR_INVALIDCAPCMD,
+            [target, &subcommand, "Invalid CAP command"],
+        ),
+    })
+}
+
+/// Runs capability negotiation, SASL and NICK/USER. Returns the reason to
+/// give the client on failure, or `None` if it went away.
+async fn register(
+    state: &AppState,
+    server: &str,
+    inbound: &mut mpsc::Receiver<Inbound>,
+    out: &mpsc::Sender<String>,
+) -> Result<Registered, Option<&'static str>> {
+    let mut nick: Option<String> = None;
+    let mut has_user = false;
+    let mut negotiating = false;
+    let mut sasl: Option<SaslPlain> = None;
+    let mut auth: Option<WsAuth> = None;
+
+    while let Some(line) = inbound.recv().await {
+        let line = match line {
+            Inbound::Line(line) => line,
+            Inbound::TooLong => return Err(Some("Line too long")),
+        };
+        let Some(message) = Message::parse(&line) else {
+            continue;
+        };
+        let target = nick.clone().unwrap_or_else(|| "*".into());
+
+        match message.command.as_str() {
+            "CAP" => {
+                // Registration waits for `CAP END` once negotiation started.
+                negotiating = !message
+                    .param(0)
+                    .is_some_and(|c| c.eq_ignore_ascii_case("END"));
+                if let Some(reply) = cap_reply(server, &target, &message) {
+                    send(out, reply).await;
+                }
+            }
+            "AUTHENTICATE" => {
+                let Some(param) = message.param(0) else {
+                    send(
+                        out,
+                        reply(
+                            server,
+                            numeric::ERR_NEEDMOREPARAMS,
+                            [&target, "AUTHENTICATE", "Not enough parameters"],
+                        ),
+                    )
+                    .await;
+                    continue;
+                };
+                if auth.is_some() {
+                    send(
+                        out,
+                        reply(
+                            server,
+                            numeric::ERR_SASLALREADY,
+                            [&target, "You have already authenticated"],
+                        ),
+                    )
+                    .await;
+                    continue;
+                }
+                if param == "*" {
+                    sasl = None;
+                    send(
+                        out,
+                        reply(
+                            server,
+                            numeric::ERR_SASLABORTED,
+                            [&target, "SASL authentication aborted"],
+                        ),
+                    )
+                    .await;
+                    continue;
+                }
+
+                let Some(plain) = sasl.as_mut() else {
+                    if param.eq_ignore_ascii_case("PLAIN") {
+                        sasl = Some(SaslPlain::default());
+                        send(out, Message::new(None, "AUTHENTICATE", ["+"])).await;
+                    } else {
+                        send(
+                            out,
+                            reply(
+                                server,
+                                numeric::RPL_SASLMECHS,
+                                [&target, "PLAIN", "are available SASL mechanisms"],
+                            ),
+                        )
+                        .await;
+                        send(
+                            out,
+                            reply(
+                                server,
+                                numeric::ERR_SASLFAIL,
+                                [&target, "SASL authentication failed"],
+                            ),
+                        )
+                        .await;
+                    }
+                    continue;
+                };
+                let Some(credentials) = plain.push(param) else {
+                    continue;
+                };
+                sasl = None;
+
+                let authenticated = match credentials {
+                    Ok(credentials) => authenticate(state, &credentials.password).await.ok(),
+                    Err(IrcError::SaslPayloadTooLong) => {
+                        send(
+                            out,
+                            reply(
+                                server,
+                                numeric::ERR_SASLTOOLONG,
+                                [&target, "SASL message too long"],
+                            ),
+                        )
+                        .await;
+                        continue;
+                    }
+                    Err(_) => None,
+                };
+                match authenticated {
+                    Some(authenticated) => {
+                        let account = authenticated.identity.username().to_string();
+                        let mask = format!("{}!*@*", target);
+                        let logged_in = format!("You are now logged in as {}", account);
+                        send(
+                            out,
+                            reply(
+                                server,
+                                numeric::RPL_LOGGEDIN,
+                                [target.as_str(), &mask, &account, &logged_in],
+                            ),
+                        )
+                        .await;
+                        send(
+                            out,
+                            reply(
+                                server,
+                                numeric::RPL_SASLSUCCESS,
+                                [&target, "SASL authentication successful"],
+                            ),
+                        )
+                        .await;
+                        auth = Some(authenticated);
+                    }
+                    None => {
+                        send(
+                            out,
+                            reply(
+                                server,
+                                numeric::ERR_SASLFAIL,
+                                [&target, "SASL authentication failed"],
+                            ),
+                        )
+                        .await;
+                    }
+                }
+            }
+            "NICK" => match message.param(0).filter(|n| !n.is_empty()) {
+                Some(requested) => nick = Some(requested.to_string()),
+                None => {
+                    send(
+                        out,
+                        reply(
+                            server,
+                            numeric::ERR_NONICKNAMEGIVEN,
+                            [&target, "No nickname given"],
+                        ),
+                    )
+                    .await;
+                }
+            },
+            "USER" => {
+                if message.params.len() < 4 {
+                    send(
+                        out,
+                        reply(
+                            server,
+                            numeric::ERR_NEEDMOREPARAMS,
+                            [&target, "USER", "Not enough parameters"],
+                        ),
+                    )
+                    .await;
+                } else {
+                    has_user = true;
+                }
+            }
+            // Clients may send a server password; the token goes through
+            // SASL instead.
+            "PASS" => {}
+            "PING" => {
+                let token = message.param(0).unwrap_or(server);
+                send(out, reply(server, "PONG", [server, token])).await;
+            }
+            "QUIT" => return Err(Some("Client quit")),
+            _ => {
+                send(
+                    out,
+                    reply(
+                        server,
+                        numeric::ERR_NOTREGISTERED,
+                        [&target, "You have not registered"],
+                    ),
+                )
+                .await;
+            }
+        }
+
+        if let Some(nick) = &nick
+            && has_user
+            && !negotiating
+        {
+            return match auth.take() {
+                Some(auth) => Ok(Registered {
+                    auth,
+                    nick: nick.clone(),
+                }),
+                None => Err(Some("SASL authentication is required")),
+            };
+        }
+    }
+    Err(None)
+}
+
+async fn handle_connection(state: AppState, stream: TcpStream) {
+    let (read, write) = stream.into_split();
+    let mut inbound = spawn_reader(read);
+    let (out, mut writer) = spawn_writer(write);
+    let server = state.args.server.irc.server_name.clone();
+
+    let reason = match tokio::time::timeout(
+        REGISTRATION_TIMEOUT,
+        register(&state, &server, &mut inbound, &out),
+    )
+    .await
+    {
+        Ok(Ok(registered)) => session::run(state, server, registered, inbound, out.clone()).await,
+        Ok(Err(reason)) => reason,
+        Err(_) => Some("Registration timed out"),
+    };
+
+    if let Some(reason) = reason {
+        send(
+            &out,
+            Message::new(None, "ERROR", [format!("Closing link: {}", reason)]),
+        )
+        .await;
+    }
+    drop(out);
+    if tokio::time::timeout(CLOSE_FLUSH_TIMEOUT, &mut writer)
+        .await
+        .is_err()
+    {
+        writer.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+
+    use base64::{Engine, engine::general_purpose::STANDARD};
+    use clap::Parser;
+    use ferriscord_auth::{Identity, User};
+    use ferriscord_core::guild::domain::{
+        application::ports::{ApplicationService, CreateApplicationInput},
+        channel::{entities::CreateChannelInput, ports::ChannelService},
+        guild::{entities::CreateGuildInput, ports::GuildService},
+        message::ports::{EncryptionMeta, MessageService},
+    };
+    use ferriscord_core::user::domain::user::ports::UserService;
+    use ferriscord_entities::{Id, channel::ChannelKind, guild::OwnerId, user::UserId};
+    use ferriscord_irc::names;
+    use sqlx::PgPool;
+    use tokio::io::Lines;
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::args::Args;
+    use crate::handlers::guild::channel::send_message::publish_new_message;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    struct Client {
+        lines: Lines<BufReader<OwnedReadHalf>>,
+        write: OwnedWriteHalf,
+    }
+
+    impl Client {
+        async fn connect(addr: SocketAddr) -> Self {
+            let (read, write) = TcpStream::connect(addr).await.unwrap().into_split();
+            Self {
+                lines: BufReader::new(read).lines(),
+                write,
+            }
+        }
+
+        async fn send(&mut self, line: &str) {
+            self.write
+                .write_all(format!("{line}\r\n").as_bytes())
+                .await
+                .unwrap();
+        }
+
+        /// Skips lines until one is a `command`.
+        async fn expect(&mut self, command: &str) -> Message {
+            tokio::time::timeout(TIMEOUT, async {
+                loop {
+                    let line = self
+                        .lines
+                        .next_line()
+                        .await
+                        .unwrap()
+                        .expect("connection closed");
+                    let message = Message::parse(&line).unwrap();
+                    if message.command == command {
+                        return message;
+                    }
+                }
+            })
+            .await
+            .unwrap_or_else(|_| panic!("no {command} received"))
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL database, configured with DATABASE_* like the api"]
+    async fn test_relays_channel_messages_both_ways() {
+        let args = Arc::new(Args::parse_from(["api"]));
+        let db = &args.db;
+        let pool = PgPool::connect(&format!(
+            "postgres://{}:{}@{}:{}/{}",
+            db.user, db.password, db.host, db.port, db.name
+        ))
+        .await
+        .unwrap();
+        sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+        let state = crate::state::state(args).await.unwrap();
+
+        let suffix = Uuid::now_v7().simple().to_string()[20..].to_string();
+        let sub = Uuid::now_v7();
+        let username = format!("owner{suffix}");
+        let owner = state
+            .user_service
+            .upsert_by_sub(&sub.to_string(), &username)
+            .await
+            .unwrap();
+        let identity = Identity::User(User {
+            id: sub.to_string(),
+            username: username.clone(),
+            email: None,
+            name: None,
+            roles: Vec::new(),
+        });
+        let guild = state
+            .guild_service
+            .create_guild(CreateGuildInput {
+                name: format!("irc {suffix}"),
+                owner_id: OwnerId(Id(sub)),
+                owner_user_id: UserId::from(owner.id.0),
+            })
+            .await
+            .unwrap();
+        let channel = state
+            .channel_service
+            .create_channel(
+                identity.clone(),
+                guild.id.clone(),
+                CreateChannelInput {
+                    name: "general".to_string(),
+                    kind: ChannelKind::Text,
+                    guild_id: guild.id.clone(),
+                    topic: None,
+                    position: None,
+                    nsfw: None,
+                    rate_limit_per_user: None,
+                    parent_id: None,
+                    bitrate: None,
+                    user_limit: None,
+                    rtc_region: None,
+                    permission_overwrites: None,
+                    default_auto_archive_duration: None,
+                    flags: None,
+                    available_tags: None,
+                    default_reaction_emoji: None,
+                    default_thread_rate_limit_per_user: None,
+                    default_sort_order: None,
+                    default_forum_layout: None,
+                },
+            )
+            .await
+            .unwrap();
+        let application = state
+            .application_service
+            .create_application(
+                identity.clone(),
+                owner.id.0,
+                CreateApplicationInput {
+                    name: format!("relay{suffix}"),
+                    description: None,
+                },
+            )
+            .await
+            .unwrap();
+        // @everyone may view channels and send messages.
+        state
+            .application_service
+            .add_bot(
+                identity.clone(),
+                owner.id.0,
+                guild.id.clone(),
+                application.id,
+                0,
+            )
+            .await
+            .unwrap();
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(state.clone(), listener));
+
+        // Registration, as clients with SASL support do it.
+        let mut client = Client::connect(addr).await;
+        client.send("CAP LS 302").await;
+        client.expect("CAP").await;
+        client.send("NICK relay").await;
+        client.send("USER relay 0 * :Relay").await;
+        client.send("CAP REQ :sasl").await;
+        assert_eq!(client.expect("CAP").await.param(1), Some("ACK"));
+        client.send("AUTHENTICATE PLAIN").await;
+        client.expect("AUTHENTICATE").await;
+        let token = application.bot_token.unwrap();
+        let payload = STANDARD.encode(format!("\0{}\0Bot {token}", application.name));
+        client.send(&format!("AUTHENTICATE {payload}")).await;
+        client.expect(numeric::RPL_SASLSUCCESS).await;
+        client.send("CAP END").await;
+        client.expect(numeric::RPL_WELCOME).await;
+
+        let name = names::channel_name(&guild.slug, &channel.name);
+        client.send(&format!("JOIN {name}")).await;
+        assert_eq!(client.expect("JOIN").await.param(0), Some(name.as_str()));
+
+        // From IRC to FerrisCord.
+        client
+            .send(&format!("PRIVMSG {name} :hello from irc"))
+            .await;
+        let stored = tokio::time::timeout(TIMEOUT, async {
+            loop {
+                let content: Option<String> = sqlx::query_scalar(
+                    "SELECT content FROM messages WHERE channel_id = $1 AND author_id = $2",
+                )
+                .bind(channel.id.get_uuid())
+                .bind(application.bot_user_id)
+                .fetch_optional(&pool)
+                .await
+                .unwrap();
+                if let Some(content) = content {
+                    return content;
+                }
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        })
+        .await
+        .expect("the message was not stored");
+        assert_eq!(stored, "hello from irc");
+
+        // From FerrisCord to IRC.
+        let message = state
+            .message_service
+            .send_message(
+                identity.clone(),
+                guild.id.clone(),
+                channel.id.clone(),
+                "hello from ferriscord".to_string(),
+                Vec::new(),
+                Vec::new(),
+                EncryptionMeta::default(),
+                None,
+            )
+            .await
+            .unwrap();
+        publish_new_message(&state, identity, &guild.id, &message).await;
+
+        let relayed = client.expect("PRIVMSG").await;
+        assert_eq!(relayed.param(0), Some(name.as_str()));
+        assert_eq!(relayed.param(1), Some("hello from ferriscord"));
+        let prefix = relayed.prefix.unwrap();
+        assert!(prefix.starts_with(&format!("{}!", names::nick(&username))));
+    }
+}
@@ -0,0 +1,978 @@
+//! A registered IRC client: commands, relayed hub events, presence and
+//! keepalives.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::{
+    channel::ports::ChannelService,
+    errors::CoreError,
+    guild::ports::GuildService,
+    message::ports::{EncryptionMeta, MessageService},
+};
+use ferriscord_core::user::domain::{
+    dm::ports::{DmEncryptionMeta, DmService},
+    friend::ports::FriendService,
+    presence::ports::PresenceService,
+};
+use ferriscord_entities::{
+    Id,
+    channel::{Channel, ChannelId, ChannelKind},
+    guild::GuildId,
+    presence::Presence,
+    user::UserId,
+};
+use ferriscord_irc::{MAX_LINE_LEN, Message, names, numeric, split_text};
+use serde::Deserialize;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use super::{Inbound, Registered, cap_reply, reply, send};
+use crate::handlers::{
+    dm::send_message::publish_new_dm_message, guild::channel::send_message::publish_new_message,
+};
+use crate::presence::broadcast_presence_to_user_guilds;
+use crate::rate_limit::RateLimiter;
+use crate::state::AppState;
+use crate::ws::deadline_at;
+
+/// How often the client is pinged. It is dropped after two intervals
+/// without a line.
+const PING_INTERVAL: Duration = Duration::from_secs(60);
+/// How often the presence session is heartbeated.
+const PRESENCE_TICK: Duration = Duration::from_secs(30);
+/// How often joined channels are checked for lost access and topic changes.
+const REVALIDATE_INTERVAL: Duration = Duration::from_secs(300);
+/// Messages sent by this client, so that they are not echoed back.
+const SENT_HISTORY: usize = 64;
+/// Host part of the hostmask of every user.
+const USER_HOST: &str = "ferriscord";
+const BOT_HOST: &str = "bot.ferriscord";
+const ENCRYPTED_PLACEHOLDER: &str = "[encrypted message]";
+
+/// A joined guild channel.
+struct Joined {
+    name: String,
+    guild_id: Uuid,
+    topic: Option<String>,
+    task: JoinHandle<()>,
+}
+
+/// A DM channel, shown as private messages with `nick`.
+struct DmPeer {
+    nick: String,
+    task: JoinHandle<()>,
+}
+
+#[derive(Deserialize)]
+struct HubEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    room: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+/// The fields of a message that are relayed to IRC.
+#[derive(Deserialize)]
+struct RelayedMessage {
+    id: Uuid,
+    author: RelayedAuthor,
+    content: String,
+    #[serde(default)]
+    attachments: Vec<RelayedAttachment>,
+    poll: Option<RelayedPoll>,
+    #[serde(default)]
+    encrypted: bool,
+}
+
+#[derive(Deserialize)]
+struct RelayedAuthor {
+    id: Uuid,
+    username: String,
+    #[serde(default)]
+    bot: bool,
+}
+
+#[derive(Deserialize)]
+struct RelayedAttachment {
+    filename: String,
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct RelayedPoll {
+    question: String,
+}
+
+impl RelayedMessage {
+    /// The text shown on IRC, before it is split into lines.
+    fn text(&self) -> String {
+        if self.encrypted {
+            return ENCRYPTED_PLACEHOLDER.to_string();
+        }
+        let mut text = self.content.clone();
+        for attachment in &self.attachments {
+            text.push_str(&format!("\n{} {}", attachment.filename, attachment.url));
+        }
+        if let Some(poll) = &self.poll {
+            text.push_str(&format!("\n[poll] {}", poll.question));
+        }
+        text
+    }
+}
+
+fn hostmask(nick: &str, bot: bool) -> String {
+    format!(
+        "{}!{}@{}",
+        nick,
+        nick,
+        if bot { BOT_HOST } else { USER_HOST }
+    )
+}
+
+/// Turns a CTCP ACTION (`/me`) into the markdown it stands for. Other CTCP
+/// requests are dropped.
+fn message_text(text: &str) -> Option<String> {
+    let Some(ctcp) = text.strip_prefix('\u{1}') else {
+        return Some(text.to_string());
+    };
+    let ctcp = ctcp.strip_suffix('\u{1}').unwrap_or(ctcp);
+    ctcp.strip_prefix("ACTION ")
+        .map(|action| format!("_{}_", action))
+}
+
+/// Forwards every message of a room to the session until either side goes
+/// away.
+fn forward_room(mut rx: broadcast::Receiver<String>, tx: mpsc::Sender<String>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(msg) => {
+                    if tx.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            }
+        }
+    })
+}
+
+struct Session {
+    state: AppState,
+    server: String,
+    identity: Identity,
+    user_id: Uuid,
+    nick: String,
+    out: mpsc::Sender<String>,
+    /// Hub events of the rooms the session listens to.
+    events: mpsc::Sender<String>,
+    joined: HashMap<Uuid, Joined>,
+    dms: HashMap<Uuid, DmPeer>,
+    sent: VecDeque<Uuid>,
+}
+
+/// Serves a registered client until it goes away. Returns the reason to
+/// give it, if it is still there.
+pub(super) async fn run(
+    state: AppState,
+    server: String,
+    registered: Registered,
+    mut inbound: mpsc::Receiver<Inbound>,
+    out: mpsc::Sender<String>,
+) -> Option<&'static str> {
+    let Registered {
+        auth,
+        nick: requested,
+    } = registered;
+    let user_id = auth.user_id;
+    let nick = names::nick(auth.identity.username());
+    let (events, mut events_rx) = mpsc::channel::<String>(256);
+    let user_task = forward_room(
+        state.hub.subscribe(&format!("user:{}", user_id)).await,
+        events.clone(),
+    );
+
+    let mut session = Session {
+        state: state.clone(),
+        server,
+        identity: auth.identity,
+        user_id,
+        nick,
+        out,
+        events,
+        joined: HashMap::new(),
+        dms: HashMap::new(),
+        sent: VecDeque::new(),
+    };
+    session.welcome(&requested).await;
+    session.refresh_dms(false).await;
+
+    // Like a gateway connection, the client is its own presence session.
+    let presence = state.presence_service.clone();
+    let session_id = Uuid::now_v7();
+    let mut last_presence = match presence.connect(user_id, session_id).await {
+        Ok(p) => {
+            broadcast_presence_to_user_guilds(&state, session.identity.clone(), user_id, &p).await;
+            p
+        }
+        Err(e) => {
+            error!("IRC: failed to register presence session: {:?}", e);
+            Presence::offline(user_id)
+        }
+    };
+
+    let token_expiry = tokio::time::sleep_until(deadline_at(auth.expires_at));
+    tokio::pin!(token_expiry);
+
+    let start = tokio::time::Instant::now();
+    let mut ping = tokio::time::interval_at(start + PING_INTERVAL, PING_INTERVAL);
+    ping.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut presence_tick = tokio::time::interval_at(start + PRESENCE_TICK, PRESENCE_TICK);
+    presence_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut revalidate = tokio::time::interval_at(start + REVALIDATE_INTERVAL, REVALIDATE_INTERVAL);
+    revalidate.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last_line = Instant::now();
+
+    let gateway = &state.args.gateway;
+    let mut rate_limiter = RateLimiter::new(
+        gateway.rate_limit,
+        Duration::from_secs(gateway.rate_limit_window_secs),
+    );
+
+    let reason = loop {
+        tokio::select! {
+            line = inbound.recv() => {
+                let line = match line {
+                    Some(Inbound::Line(line)) => line,
+                    Some(Inbound::TooLong) => break Some("Line too long"),
+                    None => break None,
+                };
+                last_line = Instant::now();
+                let Some(message) = Message::parse(&line) else {
+                    continue;
+                };
+                if !rate_limiter.check() {
+                    break Some("Excess flood");
+                }
+                if matches!(message.command.as_str(), "PRIVMSG" | "NOTICE")
+                    && let Err(e) = presence.record_activity(session_id).await
+                {
+                    warn!("IRC: failed to record activity: {:?}", e);
+                }
+                if let Err(reason) = session.handle(message).await {
+                    break Some(reason);
+                }
+            }
+            Some(payload) = events_rx.recv() => session.handle_event(&payload).await,
+            _ = &mut token_expiry => break Some("Token expired"),
+            _ = ping.tick() => {
+                if last_line.elapsed() > PING_INTERVAL * 2 {
+                    break Some("Ping timeout");
+                }
+                let server = session.server.clone();
+                send(&session.out, Message::new(None, "PING", [server])).await;
+            }
+            _ = presence_tick.tick() => {
+                if let Err(e) = presence.heartbeat(session_id).await {
+                    warn!("IRC: presence heartbeat failed: {:?}", e);
+                }
+                match presence.get(user_id).await {
+                    Ok(p) => {
+                        if p.status != last_presence.status
+                            || p.custom_status != last_presence.custom_status
+                        {
+                            broadcast_presence_to_user_guilds(
+                                &state,
+                                session.identity.clone(),
+                                user_id,
+                                &p,
+                            )
+                            .await;
+                        }
+                        last_presence = p;
+                    }
+                    Err(e) => warn!("IRC: failed to refresh presence: {:?}", e),
+                }
+            }
+            _ = revalidate.tick() => session.revalidate().await,
+        }
+    };
+
+    // Drop this session; the user only goes offline if it was their last one.
+    match presence.disconnect(user_id, session_id).await {
+        Ok(p) => {
+            if p != last_presence {
+                broadcast_presence_to_user_guilds(&state, session.identity.clone(), user_id, &p)
+                    .await;
+            }
+        }
+        Err(e) => warn!("IRC: failed to remove presence session: {:?}", e),
+    }
+
+    user_task.abort();
+    for (_, joined) in session.joined.drain() {
+        joined.task.abort();
+    }
+    for (_, peer) in session.dms.drain() {
+        peer.task.abort();
+    }
+    reason
+}
+
+impl Session {
+    async fn reply(&self, command: &str, params: &[&str]) {
+        let mut all = vec![self.nick.as_str()];
+        all.extend_from_slice(params);
+        send(&self.out, reply(&self.server, command, all)).await;
+    }
+
+    async fn notice(&self, text: &str) {
+        self.reply("NOTICE", &[text]).await;
+    }
+
+    async fn welcome(&self, requested: &str) {
+        let welcome = format!("Welcome to FerrisCord, {}", self.nick);
+        let host = format!("Your host is {}", self.server);
+        self.reply(numeric::RPL_WELCOME, &[&welcome]).await;
+        self.reply(numeric::RPL_YOURHOST, &[&host]).await;
+        self.reply(
+            numeric::RPL_CREATED,
+            &["This server is a FerrisCord gateway"],
+        )
+        .await;
+        self.reply(
+            numeric::RPL_MYINFO,
+            &[&self.server, "ferriscord", "i", "nt"],
+        )
+        .await;
+        self.reply(
+            numeric::RPL_ISUPPORT,
+            &[
+                "CHANTYPES=#",
+                "CASEMAPPING=ascii",
+                "NETWORK=FerrisCord",
+                "are supported by this server",
+            ],
+        )
+        .await;
+        self.reply(numeric::ERR_NOMOTD, &["MOTD File is missing"])
+            .await;
+        if names::fold(requested) != names::fold(&self.nick) {
+            self.notice("Nicknames follow FerrisCord usernames").await;
+        }
+    }
+
+    /// Handles a command. Returns the reason to close the link with when
+    /// the client quits.
+    async fn handle(&mut self, message: Message) -> Result<(), &'static str> {
+        match message.command.as_str() {
+            "PING" => {
+                let token = message.param(0).unwrap_or(&self.server).to_string();
+                send(
+                    &self.out,
+                    reply(&self.server, "PONG", [self.server.as_str(), &token]),
+                )
+                .await;
+            }
+            "PONG" => {}
+            "QUIT" => return Err("Client quit"),
+            "CAP" => {
+                if let Some(reply) = cap_reply(&self.server, &self.nick, &message) {
+                    send(&self.out, reply).await;
+                }
+            }
+            "AUTHENTICATE" => {
+                self.reply(
+                    numeric::ERR_SASLALREADY,
+                    &["You have already authenticated"],
+                )
+                .await;
+            }
+            "USER" | "PASS" => {
+                self.reply(numeric::ERR_ALREADYREGISTERED, &["You may not reregister"])
+                    .await;
+            }
+            "NICK" => {
+                if message
+                    .param(0)
+                    .is_some_and(|n| names::fold(n) != names::fold(&self.nick))
+                {
+                    self.notice("Nicknames follow FerrisCord usernames").await;
+                }
+            }
+            "JOIN" => {
+                let Some(targets) = message.param(0) else {
+                    self.reply(
+                        numeric::ERR_NEEDMOREPARAMS,
+                        &["JOIN", "Not enough parameters"],
+                    )
+                    .await;
+                    return Ok(());
+                };
+                if targets == "0" {
+                    let ids: Vec<Uuid> = self.joined.keys().copied().collect();
+                    for id in ids {
+                        self.part(id, None).await;
+                    }
+                    return Ok(());
+                }
+                for name in targets.split(',') {
+                    self.join(name).await;
+                }
+            }
+            "PART" => {
+                let Some(targets) = message.param(0) else {
+                    self.reply(
+                        numeric::ERR_NEEDMOREPARAMS,
+                        &["PART", "Not enough parameters"],
+                    )
+                    .await;
+                    return Ok(());
+                };
+                for name in targets.split(',') {
+                    match self.joined_id(name) {
+                        Some(id) => self.part(id, message.param(1)).await,
+                        None => {
+                            self.reply(
+                                numeric::ERR_NOTONCHANNEL,
+                                &[name, "You're not on that channel"],
+                            )
+                            .await;
+                        }
+                    }
+                }
+            }
+            "PRIVMSG" | "NOTICE" => {
+                let notice = message.command == "NOTICE";
+                let (Some(target), Some(text)) = (message.param(0), message.param(1)) else {
+                    if !notice {
+                        match message.param(0) {
+                            None => {
+                                self.reply(
+                                    numeric::ERR_NORECIPIENT,
+                                    &["No recipient given (PRIVMSG)"],
+                                )
+                                .await;
+                            }
+                            Some(_) => {
+                                self.reply(numeric::ERR_NOTEXTTOSEND, &["No text to send"])
+                                    .await;
+                            }
+                        }
+                    }
+                    return Ok(());
+                };
+                let Some(text) = message_text(text) else {
+                    return Ok(());
+                };
+                for target in target.split(',') {
+                    if names::is_channel(target) {
+                        self.send_to_channel(target, &text, notice).await;
+                    } else {
+                        self.send_to_user(target, &text, notice).await;
+                    }
+                }
+            }
+            "TOPIC" => {
+                let Some(name) = message.param(0) else {
+                    self.reply(
+                        numeric::ERR_NEEDMOREPARAMS,
+                        &["TOPIC", "Not enough parameters"],
+                    )
+                    .await;
+                    return Ok(());
+                };
+                let Some(joined) = self.joined_id(name).and_then(|id| self.joined.get(&id)) else {
+                    self.reply(
+                        numeric::ERR_NOTONCHANNEL,
+                        &[name, "You're not on that channel"],
+                    )
+                    .await;
+                    return Ok(());
+                };
+                if message.param(1).is_some() {
+                    self.notice("Topics are set from FerrisCord").await;
+                }
+                self.send_topic(joined).await;
+            }
+            "NAMES" => {
+                for name in message.param(0).unwrap_or("").split(',') {
+                    if let Some(joined) = self.joined_id(name).and_then(|id| self.joined.get(&id)) {
+                        self.send_names(&joined.name).await;
+                    } else if !name.is_empty() {
+                        self.reply(numeric::RPL_ENDOFNAMES, &[name, "End of /NAMES list"])
+                            .await;
+                    }
+                }
+            }
+            "LIST" => self.list().await,
+            "WHO" => {
+                let mask = message.param(0).unwrap_or("*");
+                self.reply(numeric::RPL_ENDOFWHO, &[mask, "End of /WHO list"])
+                    .await;
+            }
+            "MODE" => match message.param(0) {
+                Some(target) if names::is_channel(target) => {
+                    if let Some(joined) = self.joined_id(target).and_then(|id| self.joined.get(&id))
+                    {
+                        self.reply(numeric::RPL_CHANNELMODEIS, &[&joined.name, "+nt"])
+                            .await;
+                    } else {
+                        self.reply(numeric::ERR_NOSUCHCHANNEL, &[target, "No such channel"])
+                            .await;
+                    }
+                }
+                Some(target) if names::fold(target) == names::fold(&self.nick) => {
+                    self.reply(numeric::RPL_UMODEIS, &["+i"]).await;
+                }
+                _ => {}
+            },
+            "MOTD" => {
+                self.reply(numeric::ERR_NOMOTD, &["MOTD File is missing"])
+                    .await;
+            }
+            command => {
+                self.reply(numeric::ERR_UNKNOWNCOMMAND, &[command, "Unknown command"])
+                    .await;
+            }
+        }
+        Ok(())
+    }
+
+    fn joined_id(&self, name: &str) -> Option<Uuid> {
+        let name = names::fold(name);
+        self.joined
+            .iter()
+            .find(|(_, joined)| joined.name == name)
+            .map(|(id, _)| *id)
+    }
+
+    /// The text channels the user can see, with their IRC names.
+    async fn visible_channels(&self) -> Vec<(String, Channel)> {
+        let guilds = match self
+            .state
+            .guild_service
+            .get_user_guilds(self.identity.clone(), UserId::from(self.user_id))
+            .await
+        {
+            Ok(guilds) => guilds,
+            Err(e) => {
+                warn!("IRC: failed to list guilds: {}", e);
+                return Vec::new();
+            }
+        };
+
+        let mut visible = Vec::new();
+        for guild in guilds {
+            match self
+                .state
+                .channel_service
+                .get_guild_channels(self.identity.clone(), guild.id.clone())
+                .await
+            {
+                Ok(channels) => visible.extend(
+                    channels
+                        .into_iter()
+                        .filter(|c| matches!(c.kind, ChannelKind::Text | ChannelKind::Announcement))
+                        .map(|c| (names::channel_name(&guild.slug, &c.name), c)),
+                ),
+                Err(e) => warn!("IRC: failed to list channels of {}: {}", guild.id, e),
+            }
+        }
+        visible
+    }
+
+    async fn join(&mut self, name: &str) {
+        let folded = names::fold(name);
+        if self.joined_id(&folded).is_some() {
+            return;
+        }
+        let found = if names::split_channel_name(&folded).is_some() {
+            self.visible_channels()
+                .await
+                .into_iter()
+                .find(|(channel_name, _)| *channel_name == folded)
+        } else {
+            None
+        };
+        let Some((name, channel)) = found else {
+            self.reply(numeric::ERR_NOSUCHCHANNEL, &[name, "No such channel"])
+                .await;
+            return;
+        };
+        let Some(guild_id) = channel.guild_id.as_ref().map(|id| *id.get_uuid()) else {
+            return;
+        };
+
+        let channel_id = channel.id.get_uuid();
+        let task = forward_room(
+            self.state
+                .hub
+                .subscribe(&format!("channel:{}", channel_id))
+                .await,
+            self.events.clone(),
+        );
+        let joined = Joined {
+            name: name.clone(),
+            guild_id,
+            topic: channel.topic.filter(|t| !t.is_empty()),
+            task,
+        };
+
+        let prefix = hostmask(&self.nick, self.identity.is_bot());
+        send(
+            &self.out,
+            Message::new(Some(&prefix), "JOIN", [name.as_str()]),
+        )
+        .await;
+        self.send_topic(&joined).await;
+        self.send_names(&name).await;
+        self.joined.insert(channel_id, joined);
+    }
+
+    async fn part(&mut self, channel_id: Uuid, reason: Option<&str>) {
+        let Some(joined) = self.joined.remove(&channel_id) else {
+            return;
+        };
+        joined.task.abort();
+        let prefix = hostmask(&self.nick, self.identity.is_bot());
+        let mut params = vec![joined.name.as_str()];
+        params.extend(reason);
+        send(&self.out, Message::new(Some(&prefix), "PART", params)).await;
+    }
+
+    async fn send_topic(&self, joined: &Joined) {
+        match &joined.topic {
+            Some(topic) => {
+                self.reply(numeric::RPL_TOPIC, &[&joined.name, topic]).await;
+            }
+            None => {
+                self.reply(numeric::RPL_NOTOPIC, &[&joined.name, "No topic is set"])
+                    .await;
+            }
+        }
+    }
+
+    /// Member lists are not relayed, so only the client itself is listed.
+    async fn send_names(&self, name: &str) {
+        self.reply(numeric::RPL_NAMREPLY, &["=", name, &self.nick])
+            .await;
+        self.reply(numeric::RPL_ENDOFNAMES, &[name, "End of /NAMES list"])
+            .await;
+    }
+
+    async fn list(&self) {
+        self.reply(numeric::RPL_LISTSTART, &["Channel", "Users  Name"])
+            .await;
+        for (name, channel) in self.visible_channels().await {
+            let topic = channel.topic.unwrap_or_default();
+            self.reply(numeric::RPL_LIST, &[&name, "0", &topic]).await;
+        }
+        self.reply(numeric::RPL_LISTEND, &["End of /LIST"]).await;
+    }
+
+    fn remember_sent(&mut self, message_id: Uuid) {
+        if self.sent.len() == SENT_HISTORY {
+            self.sent.pop_front();
+        }
+        self.sent.push_back(message_id);
+    }
+
+    async fn send_to_channel(&mut self, name: &str, text: &str, notice: bool) {
+        let Some(channel_id) = self.joined_id(name) else {
+            if !notice {
+                self.reply(
+                    numeric::ERR_CANNOTSENDTOCHAN,
+                    &[name, "Cannot send to channel"],
+                )
+                .await;
+            }
+            return;
+        };
+        let guild_id = GuildId(Id(self.joined[&channel_id].guild_id));
+
+        let result = self
+            .state
+            .message_service
+            .send_message(
+                self.identity.clone(),
+                guild_id.clone(),
+                ChannelId(Id(channel_id)),
+                text.to_string(),
+                Vec::new(),
+                Vec::new(),
+                EncryptionMeta::default(),
+                None,
+            )
+            .await;
+        match result {
+            Ok(message) => {
+                self.remember_sent(message.id.get_uuid());
+                publish_new_message(&self.state, self.identity.clone(), &guild_id, &message).await;
+            }
+            Err(e) => {
+                if !notice {
+                    let reason = format!("Cannot send to channel: {}", e);
+                    self.reply(numeric::ERR_CANNOTSENDTOCHAN, &[name, &reason])
+                        .await;
+                }
+            }
+        }
+    }
+
+    async fn send_to_user(&mut self, nick: &str, text: &str, notice: bool) {
+        let channel_id = match self.dm_with(nick) {
+            Some(channel_id) => Some(channel_id),
+            None => self.open_dm(nick).await,
+        };
+        let Some(channel_id) = channel_id else {
+            if !notice {
+                self.reply(numeric::ERR_NOSUCHNICK, &[nick, "No such nick"])
+                    .await;
+            }
+            return;
+        };
+
+        let result = self
+            .state
+            .dm_service
+            .send_message(
+                self.identity.id(),
+                channel_id,
+                text.to_string(),
+                Vec::new(),
+                DmEncryptionMeta::default(),
+            )
+            .await;
+        match result {
+            Ok(message) => {
+                self.remember_sent(message.id.get_uuid());
+                publish_new_dm_message(&self.state, channel_id, &message).await;
+            }
+            Err(e) => {
+                if !notice {
+                    let reason = format!("Cannot send to nick: {}", e);
+                    self.reply(numeric::ERR_CANNOTSENDTOCHAN, &[nick, &reason])
+                        .await;
+                }
+            }
+        }
+    }
+
+    fn dm_with(&self, nick: &str) -> Option<Uuid> {
+        let nick = names::fold(nick);
+        self.dms
+            .iter()
+            .find(|(_, peer)| names::fold(&peer.nick) == nick)
+            .map(|(id, _)| *id)
+    }
+
+    /// Opens a DM with a friend who has none yet.
+    async fn open_dm(&mut self, nick: &str) -> Option<Uuid> {
+        let friends = match self
+            .state
+            .friend_service
+            .list_friends(self.identity.id())
+            .await
+        {
+            Ok(friends) => friends,
+            Err(e) => {
+                warn!("IRC: failed to list friends: {}", e);
+                return None;
+            }
+        };
+        let nick = names::fold(nick);
+        let friend = friends
+            .into_iter()
+            .find(|f| names::fold(&names::nick(&f.user.username)) == nick)?;
+
+        match self
+            .state
+            .dm_service
+            .create_or_get(self.identity.id(), *friend.user.id.get_uuid())
+            .await
+        {
+            Ok(dm) => {
+                let channel_id = dm.id.get_uuid();
+                self.listen_dm(channel_id, names::nick(&dm.recipient.username))
+                    .await;
+                Some(channel_id)
+            }
+            Err(e) => {
+                warn!("IRC: failed to open a DM: {}", e);
+                None
+            }
+        }
+    }
+
+    async fn listen_dm(&mut self, channel_id: Uuid, nick: String) {
+        let task = forward_room(
+            self.state
+                .hub
+                .subscribe(&format!("dm:{}", channel_id))
+                .await,
+            self.events.clone(),
+        );
+        self.dms.insert(channel_id, DmPeer { nick, task });
+    }
+
+    /// Listens to DM channels opened since the last refresh. With
+    /// `relay_latest`, their latest message is relayed too: it was sent
+    /// before the session listened to the channel.
+    async fn refresh_dms(&mut self, relay_latest: bool) {
+        let dms = match self.state.dm_service.list(self.identity.id()).await {
+            Ok(dms) => dms,
+            Err(e) => {
+                warn!("IRC: failed to list DMs: {}", e);
+                return;
+            }
+        };
+        for dm in dms {
+            let channel_id = dm.id.get_uuid();
+            if self.dms.contains_key(&channel_id) {
+                continue;
+            }
+            self.listen_dm(channel_id, names::nick(&dm.recipient.username))
+                .await;
+            if !relay_latest {
+                continue;
+            }
+            let latest = self
+                .state
+                .dm_service
+                .get_messages(self.identity.id(), channel_id, None, None, 1)
+                .await
+                .ok()
+                .and_then(|mut messages| messages.pop())
+                .and_then(|m| serde_json::to_value(m).ok())
+                .and_then(|m| serde_json::from_value::<RelayedMessage>(m).ok());
+            if let Some(message) = latest {
+                self.relay_dm(channel_id, &message).await;
+            }
+        }
+    }
+
+    async fn handle_event(&mut self, payload: &str) {
+        let Ok(event) = serde_json::from_str::<HubEvent>(payload) else {
+            return;
+        };
+        match event.kind.as_str() {
+            "message.new" => {
+                let Ok(message) = serde_json::from_value::<RelayedMessage>(event.data) else {
+                    return;
+                };
+                if self.sent.contains(&message.id) {
+                    return;
+                }
+                if let Some(id) = event.room.strip_prefix("channel:") {
+                    let Some(name) = Uuid::parse_str(id)
+                        .ok()
+                        .and_then(|id| self.joined.get(&id))
+                        .map(|joined| joined.name.clone())
+                    else {
+                        return;
+                    };
+                    let author = names::nick(&message.author.username);
+                    self.relay(&author, message.author.bot, &name, &message.text())
+                        .await;
+                } else if let Some(id) = event.room.strip_prefix("dm:")
+                    && let Ok(channel_id) = Uuid::parse_str(id)
+                {
+                    self.relay_dm(channel_id, &message).await;
+                }
+            }
+            // DM messages move the read position of the recipient; one of an
+            // unknown DM channel means a new DM was opened.
+            "read_state.update" => {
+                let guild_id = event.data.get("guild_id").and_then(|id| id.as_str());
+                let channel_id = event
+                    .data
+                    .get("channel_id")
+                    .and_then(|id| id.as_str())
+                    .and_then(|id| Uuid::parse_str(id).ok());
+                if guild_id.is_none()
+                    && let Some(channel_id) = channel_id
+                    && !self.dms.contains_key(&channel_id)
+                {
+                    self.refresh_dms(true).await;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn relay_dm(&self, channel_id: Uuid, message: &RelayedMessage) {
+        let Some(peer) = self.dms.get(&channel_id) else {
+            return;
+        };
+        // Messages sent from other sessions of the user show as sent by
+        // them to the peer.
+        let (author, target) = if message.author.id == self.user_id {
+            (self.nick.as_str(), peer.nick.as_str())
+        } else {
+            (peer.nick.as_str(), self.nick.as_str())
+        };
+        self.relay(author, message.author.bot, target, &message.text())
+            .await;
+    }
+
+    async fn relay(&self, author: &str, bot: bool, target: &str, text: &str) {
+        let prefix = hostmask(author, bot);
+        // `:<prefix> PRIVMSG <target> :<text>\r\n`
+        let overhead = prefix.len() + target.len() + ":  PRIVMSG  :\r\n".len();
+        for line in split_text(text, MAX_LINE_LEN.saturating_sub(overhead)) {
+            send(
+                &self.out,
+                Message::new(Some(&prefix), "PRIVMSG", [target, &line]),
+            )
+            .await;
+        }
+    }
+
+    /// Parts channels the user can no longer see and relays topic changes.
+    async fn revalidate(&mut self) {
+        let ids: Vec<Uuid> = self.joined.keys().copied().collect();
+        for channel_id in ids {
+            let guild_id = GuildId(Id(self.joined[&channel_id].guild_id));
+            match self
+                .state
+                .channel_service
+                .get_channel(self.identity.clone(), guild_id, ChannelId(Id(channel_id)))
+                .await
+            {
+                Ok(channel) => {
+                    let topic = channel.topic.filter(|t| !t.is_empty());
+                    let Some(joined) = self.joined.get_mut(&channel_id) else {
+                        continue;
+                    };
+                    if topic != joined.topic {
+                        joined.topic = topic;
+                        let topic = joined.topic.clone().unwrap_or_default();
+                        send(
+                            &self.out,
+                            reply(&self.server, "TOPIC", [joined.name.as_str(), &topic]),
+                        )
+                        .await;
+                    }
+                }
+                Err(
+                    CoreError::ChannelNotFound { .. }
+                    | CoreError::GuildNotFound { .. }
+                    | CoreError::NotGuildMember
+                    | CoreError::InsufficientPermissions,
+                ) => {
+                    self.part(channel_id, Some("You can no longer see this channel"))
+                        .await;
+                }
+                Err(e) => warn!("IRC: failed to revalidate channel {}: {}", channel_id, e),
+            }
+        }
+    }
+}
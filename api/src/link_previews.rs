@@ -0,0 +1,75 @@
+//! Link previews. The links of a new or edited guild message are fetched in
+//! the background; once their previews are attached the message is
+//! published again as a `message.update`.
+
+use std::time::Duration;
+
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::link_preview::ports::LinkPreviewService;
+use ferriscord_entities::{Id, guild::GuildId, message::Message};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    crossposts::{presign_attachments, publish_message_update},
+    state::AppState,
+};
+
+/// How often expired previews are dropped from the cache.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Previews the links of a message sent or edited by `identity`, or by a
+/// webhook when `None`, without holding up the request.
+pub fn spawn_previews(
+    state: &AppState,
+    identity: Option<Identity>,
+    guild_id: Uuid,
+    message: &Message,
+) {
+    let state = state.clone();
+    let message = message.clone();
+    tokio::spawn(async move {
+        let links = match state
+            .link_preview_service
+            .links_to_preview(
+                identity,
+                GuildId(Id(guild_id)),
+                message.channel_id.clone(),
+                &message,
+            )
+            .await
+        {
+            Ok(links) => links,
+            Err(e) => {
+                warn!("failed to find links to preview: {:?}", e);
+                return;
+            }
+        };
+
+        match state
+            .link_preview_service
+            .preview_message(&message, links)
+            .await
+        {
+            Ok(Some(mut message)) => {
+                presign_attachments(&state, &mut message).await;
+                publish_message_update(&state, guild_id, &message).await;
+            }
+            Ok(None) => {}
+            Err(e) => warn!("failed to preview links of message {}: {:?}", message.id, e),
+        }
+    });
+}
+
+/// Drops cached previews once they expire.
+pub async fn prune_link_previews(state: AppState) {
+    let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        if let Err(e) = state.link_preview_service.prune_previews().await {
+            warn!("failed to prune link previews: {:?}", e);
+        }
+    }
+}
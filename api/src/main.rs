@@ -8,11 +8,30 @@ use tracing_subscriber::EnvFilter;
 use crate::{args::Args, router::router, state::state};
 
 mod args;
+mod attachment_uploads;
+mod attachments;
+mod call;
+mod crossposts;
+mod events;
+mod guild_imports;
 mod handlers;
+mod interactions;
+#[cfg(feature = "irc")]
+mod irc;
+mod link_previews;
+mod member_list;
 mod openapi;
+mod polls;
 mod presence;
+mod rate_limit;
+mod read_state;
 mod router;
+mod scheduled_events;
+mod scheduled_messages;
+#[cfg(feature = "sfu")]
+mod sfu;
 mod state;
+mod voice;
 mod ws;
 
 fn init_logger(args: &LogArgs) {
@@ -40,6 +59,28 @@ async fn main() -> Result<(), ApiError> {
 
     let app_state = state(args.clone()).await?;
 
+    #[cfg(not(feature = "sfu"))]
+    if args.server.sfu.enabled {
+        tracing::warn!("SFU_ENABLED is set but the API was built without the sfu feature");
+    }
+    #[cfg(not(feature = "irc"))]
+    if args.server.irc.enabled {
+        tracing::warn!("IRC_ENABLED is set but the API was built without the irc feature");
+    }
+
+    tokio::spawn(voice::reap_orphaned_voice_states(app_state.clone()));
+    tokio::spawn(call::expire_call_rings(app_state.clone()));
+    tokio::spawn(events::deliver_events(app_state.clone()));
+    tokio::spawn(interactions::prune_interactions(app_state.clone()));
+    tokio::spawn(link_previews::prune_link_previews(app_state.clone()));
+    tokio::spawn(scheduled_messages::deliver_scheduled_messages(app_state.clone()));
+    tokio::spawn(scheduled_events::process_scheduled_events(app_state.clone()));
+    tokio::spawn(guild_imports::process_guild_imports(app_state.clone()));
+    tokio::spawn(attachment_uploads::prune_attachment_uploads(app_state.clone()));
+    tokio::spawn(polls::close_polls(app_state.clone()));
+    #[cfg(feature = "irc")]
+    irc::start(app_state.clone());
+
     let router = router(app_state)?;
 
     let addr = get_addr(&args.server.host, args.server.port)
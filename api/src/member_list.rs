@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use ferriscord_core::guild::domain::member::{
+    list::{MemberList, build_member_list},
+    ports::MemberRepository,
+};
+use ferriscord_core::user::domain::presence::ports::PresenceService;
+use ferriscord_entities::guild::GuildId;
+use ferriscord_error::ApiError;
+use tokio::sync::{broadcast, watch};
+use tokio::time::Instant;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{state::AppState, ws::WsHub};
+
+/// Guild room events after which member lists are recomputed.
+pub const MEMBER_LIST_EVENTS: &[&str] = &[
+    "presence.update",
+    "member.add",
+    "member.remove",
+    "member.update",
+    "role.update",
+    "role.delete",
+];
+
+/// Changes are batched for this long before a list is recomputed, so a burst
+/// of presence updates costs a single reload.
+const RECOMPUTE_DELAY: Duration = Duration::from_millis(500);
+
+/// The member lists of the guilds somebody is subscribed to. Each is reloaded
+/// once per batch of changes and shared by all of its subscribers, who diff
+/// their own ranges against it.
+#[derive(Clone, Default)]
+pub struct MemberLists {
+    guilds: Arc<Mutex<HashMap<Uuid, ListSender>>>,
+}
+
+type ListSender = Arc<watch::Sender<Arc<MemberList>>>;
+
+impl MemberLists {
+    /// Watches the member list of a guild, loading it and tracking its
+    /// changes until the last receiver is dropped.
+    pub async fn watch(
+        &self,
+        state: &AppState,
+        guild_id: Uuid,
+    ) -> Result<watch::Receiver<Arc<MemberList>>, ApiError> {
+        if let Some(list_tx) = self.lock().get(&guild_id) {
+            return Ok(list_tx.subscribe());
+        }
+
+        // Listen before loading so no change between the two is missed.
+        let events = state.hub.subscribe(&format!("guild:{}", guild_id)).await;
+        let list = load_member_list(state, guild_id).await?;
+
+        let list_tx = {
+            let mut guilds = self.lock();
+            // Another connection may have started tracking it meanwhile.
+            if let Some(list_tx) = guilds.get(&guild_id) {
+                return Ok(list_tx.subscribe());
+            }
+            let list_tx = Arc::new(watch::Sender::new(Arc::new(list)));
+            guilds.insert(guild_id, list_tx.clone());
+            list_tx
+        };
+        let list_rx = list_tx.subscribe();
+        tokio::spawn(self.clone().track(state.clone(), guild_id, events, list_tx));
+        Ok(list_rx)
+    }
+
+    async fn track(
+        self,
+        state: AppState,
+        guild_id: Uuid,
+        mut events: broadcast::Receiver<String>,
+        list_tx: ListSender,
+    ) {
+        let recompute = tokio::time::sleep(Duration::ZERO);
+        tokio::pin!(recompute);
+        let mut pending = false;
+
+        loop {
+            tokio::select! {
+                event = events.recv() => {
+                    let relevant = match event {
+                        Ok(payload) => is_member_list_event(&payload),
+                        // Dropped events may have been relevant.
+                        Err(broadcast::error::RecvError::Lagged(_)) => true,
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    };
+                    if relevant && !pending {
+                        pending = true;
+                        recompute.as_mut().reset(Instant::now() + RECOMPUTE_DELAY);
+                    }
+                }
+                _ = &mut recompute, if pending => {
+                    pending = false;
+                    match load_member_list(&state, guild_id).await {
+                        Ok(new) => {
+                            list_tx.send_if_modified(|list| {
+                                let modified = **list != new;
+                                if modified {
+                                    *list = Arc::new(new);
+                                }
+                                modified
+                            });
+                        }
+                        Err(e) => warn!("WS: failed to reload member list: {:?}", e),
+                    }
+                }
+                _ = list_tx.closed() => {
+                    // Checked under the lock: a connection may be subscribing.
+                    let mut guilds = self.lock();
+                    if list_tx.receiver_count() == 0 {
+                        guilds.remove(&guild_id);
+                        return;
+                    }
+                }
+            }
+        }
+
+        let mut guilds = self.lock();
+        if guilds.get(&guild_id).is_some_and(|tx| Arc::ptr_eq(tx, &list_tx)) {
+            guilds.remove(&guild_id);
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<Uuid, ListSender>> {
+        self.guilds.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn is_member_list_event(payload: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(payload)
+        .ok()
+        .and_then(|event| event.get("type")?.as_str().map(|kind| MEMBER_LIST_EVENTS.contains(&kind)))
+        .unwrap_or(false)
+}
+
+/// Publishes a member or role change to the guild room so subscribed member
+/// lists pick it up.
+pub async fn publish_guild_event(hub: &WsHub, guild_id: Uuid, kind: &str, data: serde_json::Value) {
+    let room = format!("guild:{}", guild_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": kind,
+        "room": room,
+        "data": data,
+    })) {
+        hub.publish(&room, payload).await;
+    }
+}
+
+/// Loads the members of a guild and groups them by hoisted role and presence.
+pub async fn load_member_list(state: &AppState, guild_id: Uuid) -> Result<MemberList, ApiError> {
+    let members = state
+        .member_repository
+        .list_members(&GuildId::from(guild_id))
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
+
+    let user_ids: Vec<Uuid> = members.iter().map(|m| m.user_id).collect();
+    let statuses: HashMap<Uuid, _> = state
+        .presence_service
+        .get_many(&user_ids)
+        .await
+        .map_err(|e| ApiError::Unknown { message: e.to_string() })?
+        .into_iter()
+        .map(|(user_id, presence)| (user_id, presence.status))
+        .collect();
+
+    Ok(build_member_list(&members, &statuses))
+}
@@ -1,6 +1,20 @@
 use utoipa::OpenApi;
 
 use super::handlers::{
+    application::{
+        create_application::__path_create_application_handler,
+        create_command::{__path_create_global_command_handler, __path_create_guild_command_handler},
+        delete_application::__path_delete_application_handler,
+        delete_command::__path_delete_command_handler,
+        get_application::__path_get_application_handler,
+        list_applications::__path_list_applications_handler,
+        list_commands::{
+            __path_list_application_guild_commands_handler, __path_list_global_commands_handler,
+        },
+        reset_bot_token::__path_reset_bot_token_handler,
+        set_interactions_endpoint::__path_set_interactions_endpoint_handler,
+        update_application::__path_update_application_handler,
+    },
     crypto::{
         backup::__path_get_key_backup_handler,
         backup::__path_upsert_key_backup_handler,
@@ -19,8 +33,12 @@ use super::handlers::{
         sender_keys::__path_get_sender_keys_handler,
     },
     dm::{
+        accept_call::__path_accept_dm_call_handler,
+        ack_message::__path_ack_dm_message_handler,
         create_or_get::__path_create_or_get_dm_handler,
+        decline_call::__path_decline_dm_call_handler,
         delete_message::__path_delete_dm_message_handler,
+        get_call::__path_get_dm_call_handler,
         get_messages::__path_get_dm_messages_handler, list_dms::__path_list_dms_handler,
         history_sync::{
             __path_complete_dm_history_sync_job_handler,
@@ -30,25 +48,68 @@ use super::handlers::{
             __path_list_dm_history_sync_messages_handler,
             __path_upload_dm_history_sync_payloads_handler,
         },
+        leave_call::__path_leave_dm_call_handler,
         send_message::__path_send_dm_message_handler,
+        start_call::__path_start_dm_call_handler,
     },
     guild::{
         assign_member_role::__path_assign_member_role_handler,
+        bot::{
+            add_bot::__path_add_bot_handler, list_bots::__path_list_bots_handler,
+            remove_bot::__path_remove_bot_handler,
+        },
+        interaction::{
+            invoke_command::__path_invoke_command_handler,
+            list_guild_commands::__path_list_guild_commands_handler,
+        },
         channel::{
+            ack_message::__path_ack_message_handler,
+            create_attachment_uploads::__path_create_attachment_uploads_handler,
             create_channel::__path_create_channel_handler,
+            crosspost_message::__path_crosspost_message_handler,
             delete_channel::__path_delete_channel_handler,
             delete_message::__path_delete_message_handler,
+            edit_message::__path_edit_message_handler,
             get_channels::__path_get_channels_handler, get_messages::__path_get_messages_handler,
             send_message::__path_send_message_handler,
             update_channel::__path_update_channel_handler,
         },
+        channel_follow::{
+            delete_channel_follow::__path_delete_channel_follow_handler,
+            follow_channel::__path_follow_channel_handler,
+            list_channel_follows::__path_list_channel_follows_handler,
+        },
         create_guild::__path_create_guild_handler,
         create_role::__path_create_role_handler,
         delete_guild::__path_delete_guild_handler,
         delete_role::__path_delete_role_handler,
+        disconnect_member_voice::__path_disconnect_member_voice_handler,
+        emoji::{
+            create_emoji::__path_create_emoji_handler,
+            delete_emoji::__path_delete_emoji_handler, get_emoji::__path_get_emoji_handler,
+            list_emojis::__path_list_emojis_handler, update_emoji::__path_update_emoji_handler,
+        },
+        event_subscription::{
+            create_event_subscription::__path_create_event_subscription_handler,
+            delete_event_subscription::__path_delete_event_subscription_handler,
+            get_event_subscription::__path_get_event_subscription_handler,
+            list_event_deliveries::__path_list_event_deliveries_handler,
+            list_event_subscriptions::__path_list_event_subscriptions_handler,
+            ping_event_subscription::__path_ping_event_subscription_handler,
+            update_event_subscription::__path_update_event_subscription_handler,
+        },
         get_members::__path_get_members_handler,
+        guild_import::{
+            cancel_guild_import::__path_cancel_guild_import_handler,
+            create_guild_import::__path_create_guild_import_handler,
+            get_guild_import::__path_get_guild_import_handler,
+            list_guild_imports::__path_list_guild_imports_handler,
+            start_guild_import::__path_start_guild_import_handler,
+            upload_guild_import_channel::__path_upload_guild_import_channel_handler,
+        },
         get_role::__path_get_role_handler,
         get_roles::__path_get_roles_handler,
+        get_voice_states::__path_get_voice_states_handler,
         invite::{
             create_invite::__path_create_invite_handler,
             delete_invite::__path_delete_invite_handler, join_guild::__path_join_guild_handler,
@@ -56,9 +117,53 @@ use super::handlers::{
             preview_invite::__path_preview_invite_handler,
         },
         leave_guild::__path_leave_guild_handler,
+        moderate_member_voice::__path_moderate_member_voice_handler,
+        move_member_voice::__path_move_member_voice_handler,
+        poll::{
+            add_poll_vote::__path_add_poll_vote_handler, get_poll::__path_get_poll_handler,
+            remove_poll_vote::__path_remove_poll_vote_handler,
+        },
         remove_member_role::__path_remove_member_role_handler,
+        scheduled_event::{
+            cancel_event::__path_cancel_event_handler,
+            create_calendar_feed::__path_create_calendar_feed_handler,
+            create_event::__path_create_event_handler,
+            get_calendar_feed::__path_get_calendar_feed_handler,
+            get_event::__path_get_event_handler,
+            list_event_rsvps::__path_list_event_rsvps_handler,
+            list_events::__path_list_events_handler,
+            remove_event_rsvp::__path_remove_event_rsvp_handler,
+            set_event_rsvp::__path_set_event_rsvp_handler,
+        },
+        scheduled_message::{
+            cancel_scheduled_message::__path_cancel_scheduled_message_handler,
+            list_scheduled_messages::__path_list_scheduled_messages_handler,
+            schedule_message::__path_schedule_message_handler,
+            schedule_reminder::__path_schedule_reminder_handler,
+            update_scheduled_message::__path_update_scheduled_message_handler,
+        },
+        stage::{
+            add_speaker::__path_add_speaker_handler, end_stage::__path_end_stage_handler,
+            get_stage::__path_get_stage_handler,
+            get_stage_history::__path_get_stage_history_handler,
+            lower_hand::__path_lower_hand_handler, raise_hand::__path_raise_hand_handler,
+            remove_speaker::__path_remove_speaker_handler,
+            start_stage::__path_start_stage_handler, update_stage::__path_update_stage_handler,
+        },
         update_guild::__path_update_guild_handler,
         update_role::__path_update_role_handler,
+        webhook::{
+            create_webhook::__path_create_webhook_handler,
+            delete_webhook::__path_delete_webhook_handler,
+            get_webhook::__path_get_webhook_handler,
+            list_webhooks::__path_list_webhooks_handler,
+            reset_webhook_token::__path_reset_webhook_token_handler,
+            update_webhook::__path_update_webhook_handler,
+        },
+    },
+    interaction::{
+        create_follow_up::__path_create_follow_up_handler,
+        respond_interaction::__path_respond_interaction_handler,
     },
     user::{
         friends::{
@@ -69,10 +174,20 @@ use super::handlers::{
             send_request::__path_send_friend_request_handler,
         },
         get_me::__path_get_me_handler,
+        get_presence::__path_get_presence_handler,
+        get_read_states::__path_get_read_states_handler,
         get_user::__path_get_user_handler,
         get_user_guilds::__path_get_user_guilds,
+        set_custom_status::{
+            __path_clear_custom_status_handler, __path_set_custom_status_handler,
+        },
         update_profile::__path_update_profile_handler,
     },
+    webhook::{
+        execute_slack_webhook::__path_execute_slack_webhook_handler,
+        execute_webhook::__path_execute_webhook_handler,
+        get_webhook_with_token::__path_get_webhook_with_token_handler,
+    },
 };
 
 #[derive(OpenApi)]
@@ -91,6 +206,7 @@ use super::handlers::{
         delete_channel_handler,
         get_messages_handler,
         send_message_handler,
+        create_attachment_uploads_handler,
         create_invite_handler,
         list_invites_handler,
         delete_invite_handler,
@@ -100,25 +216,127 @@ use super::handlers::{
         get_me_handler,
         get_user_handler,
         update_profile_handler,
+        get_presence_handler,
+        set_custom_status_handler,
+        clear_custom_status_handler,
+        get_read_states_handler,
         update_channel_handler,
         assign_member_role_handler,
         remove_member_role_handler,
         update_guild_handler,
         get_members_handler,
+        get_voice_states_handler,
+        moderate_member_voice_handler,
+        move_member_voice_handler,
+        disconnect_member_voice_handler,
+        start_stage_handler,
+        get_stage_handler,
+        update_stage_handler,
+        end_stage_handler,
+        get_stage_history_handler,
+        raise_hand_handler,
+        lower_hand_handler,
+        add_speaker_handler,
+        remove_speaker_handler,
         delete_message_handler,
+        edit_message_handler,
+        ack_message_handler,
         leave_guild_handler,
+        // Webhook handlers
+        create_webhook_handler,
+        list_webhooks_handler,
+        get_webhook_handler,
+        update_webhook_handler,
+        reset_webhook_token_handler,
+        delete_webhook_handler,
+        get_webhook_with_token_handler,
+        execute_webhook_handler,
+        execute_slack_webhook_handler,
+        // Event subscription handlers
+        create_event_subscription_handler,
+        list_event_subscriptions_handler,
+        get_event_subscription_handler,
+        update_event_subscription_handler,
+        delete_event_subscription_handler,
+        list_event_deliveries_handler,
+        ping_event_subscription_handler,
+        // Announcement handlers
+        crosspost_message_handler,
+        follow_channel_handler,
+        list_channel_follows_handler,
+        delete_channel_follow_handler,
+        // Emoji handlers
+        list_emojis_handler,
+        create_emoji_handler,
+        get_emoji_handler,
+        update_emoji_handler,
+        delete_emoji_handler,
+        // Scheduled message handlers
+        schedule_message_handler,
+        schedule_reminder_handler,
+        list_scheduled_messages_handler,
+        update_scheduled_message_handler,
+        cancel_scheduled_message_handler,
+        // Poll handlers
+        get_poll_handler,
+        add_poll_vote_handler,
+        remove_poll_vote_handler,
+        // Scheduled event handlers
+        create_event_handler,
+        list_events_handler,
+        get_event_handler,
+        cancel_event_handler,
+        set_event_rsvp_handler,
+        remove_event_rsvp_handler,
+        list_event_rsvps_handler,
+        create_calendar_feed_handler,
+        get_calendar_feed_handler,
+        // Guild import handlers
+        create_guild_import_handler,
+        list_guild_imports_handler,
+        get_guild_import_handler,
+        upload_guild_import_channel_handler,
+        start_guild_import_handler,
+        cancel_guild_import_handler,
+        // Application and bot handlers
+        create_application_handler,
+        list_applications_handler,
+        get_application_handler,
+        update_application_handler,
+        reset_bot_token_handler,
+        delete_application_handler,
+        add_bot_handler,
+        list_bots_handler,
+        remove_bot_handler,
+        // Interaction handlers
+        set_interactions_endpoint_handler,
+        create_global_command_handler,
+        create_guild_command_handler,
+        list_global_commands_handler,
+        list_application_guild_commands_handler,
+        delete_command_handler,
+        list_guild_commands_handler,
+        invoke_command_handler,
+        respond_interaction_handler,
+        create_follow_up_handler,
         // DM handlers
         list_dms_handler,
         create_or_get_dm_handler,
         get_dm_messages_handler,
         send_dm_message_handler,
         delete_dm_message_handler,
+        ack_dm_message_handler,
         create_dm_history_sync_job_handler,
         get_dm_history_sync_job_handler,
         list_dm_history_sync_messages_handler,
         upload_dm_history_sync_payloads_handler,
         complete_dm_history_sync_job_handler,
         fail_dm_history_sync_job_handler,
+        start_dm_call_handler,
+        get_dm_call_handler,
+        accept_dm_call_handler,
+        decline_dm_call_handler,
+        leave_dm_call_handler,
         // Friends handlers
         list_friends_handler,
         list_incoming_handler,
@@ -0,0 +1,64 @@
+//! Poll tallies and closing. Votes are pushed to the channel as they come in;
+//! every replica runs a worker that closes expired polls, freezing their
+//! results, and sends the final message to its viewers.
+
+use std::time::Duration;
+
+use ferriscord_core::guild::domain::poll::ports::PollService;
+use ferriscord_entities::poll::Poll;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{
+    crossposts::{presign_attachments, publish_message_update},
+    state::AppState,
+    ws::WsHub,
+};
+
+/// How often expired polls are looked for.
+const CLOSE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+/// Polls closed per tick.
+const CLOSE_BATCH: i64 = 100;
+
+/// Sends the new tallies of a poll to the channel. The caller's own votes
+/// are left out, and hidden results stay hidden.
+pub async fn publish_vote_update(hub: &WsHub, channel_id: Uuid, message_id: Uuid, poll: &Poll) {
+    let poll = Poll {
+        my_votes: Vec::new(),
+        ..poll.clone()
+    };
+    let room = format!("channel:{}", channel_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "poll.vote_update",
+        "room": room,
+        "data": {
+            "channel_id": channel_id,
+            "message_id": message_id,
+            "poll": poll,
+        },
+    })) {
+        hub.publish(&room, payload).await;
+    }
+}
+
+/// Closes expired polls and publishes their messages with the final results.
+pub async fn close_polls(state: AppState) {
+    let mut interval = tokio::time::interval(CLOSE_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        let closed = match state.poll_service.close_expired_polls(CLOSE_BATCH).await {
+            Ok(closed) => closed,
+            Err(e) => {
+                warn!("failed to close polls: {:?}", e);
+                continue;
+            }
+        };
+
+        for (guild_id, mut message) in closed {
+            presign_attachments(&state, &mut message).await;
+            publish_message_update(&state, guild_id, &message).await;
+        }
+    }
+}
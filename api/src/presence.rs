@@ -1,46 +1,55 @@
-use std::collections::HashMap;
-use std::sync::Arc;
-use serde::{Deserialize, Serialize};
-use tokio::sync::RwLock;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::guild::ports::GuildService;
+use ferriscord_entities::{presence::Presence, user::UserId};
+use tracing::warn;
 use uuid::Uuid;
-use utoipa::ToSchema;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, ToSchema, Default)]
-#[serde(rename_all = "snake_case")]
-pub enum PresenceStatus {
-    Online,
-    Idle,
-    DoNotDisturb,
-    #[default]
-    Offline,
-}
+use crate::{state::AppState, ws::WsHub};
 
-#[derive(Clone)]
-pub struct PresenceStore {
-    users: Arc<RwLock<HashMap<Uuid, PresenceStatus>>>,
-}
+pub use ferriscord_entities::presence::{CustomStatus, PresenceStatus};
 
-impl PresenceStore {
-    pub fn new() -> Self {
-        Self { users: Arc::new(RwLock::new(HashMap::new())) }
-    }
+/// Builds a `presence.update` event for `room`.
+pub fn presence_payload(room: &str, presence: &Presence) -> Option<String> {
+    serde_json::to_string(&serde_json::json!({
+        "type": "presence.update",
+        "room": room,
+        "data": {
+            "user_id": presence.user_id,
+            "status": presence.status,
+            "custom_status": presence.custom_status,
+            "last_seen_at": presence.last_seen_at,
+        },
+    }))
+    .ok()
+}
 
-    pub async fn set(&self, user_id: Uuid, status: PresenceStatus) {
-        let mut map = self.users.write().await;
-        if status == PresenceStatus::Offline {
-            map.remove(&user_id);
-        } else {
-            map.insert(user_id, status);
+/// Publishes `presence` to every given room.
+pub async fn broadcast_presence<'a>(
+    hub: &WsHub,
+    rooms: impl IntoIterator<Item = &'a String>,
+    presence: &Presence,
+) {
+    for room in rooms {
+        if let Some(payload) = presence_payload(room, presence) {
+            hub.publish(room, payload).await;
         }
     }
+}
 
-    pub async fn get(&self, user_id: Uuid) -> PresenceStatus {
-        let map = self.users.read().await;
-        map.get(&user_id).cloned().unwrap_or_default()
-    }
+/// Publishes `presence` to the user's own room and to every guild they are a
+/// member of. Used when the update does not originate from a gateway session.
+pub async fn broadcast_presence_to_user_guilds(
+    state: &AppState,
+    identity: Identity,
+    user_id: Uuid,
+    presence: &Presence,
+) {
+    let mut rooms = vec![format!("user:{}", user_id)];
 
-    pub async fn get_many(&self, user_ids: &[Uuid]) -> HashMap<Uuid, PresenceStatus> {
-        let map = self.users.read().await;
-        user_ids.iter().map(|id| (*id, map.get(id).cloned().unwrap_or_default())).collect()
+    match state.guild_service.get_user_guilds(identity, UserId::from(user_id)).await {
+        Ok(guilds) => rooms.extend(guilds.iter().map(|g| format!("guild:{}", g.id))),
+        Err(e) => warn!("failed to list guilds for presence broadcast: {}", e),
     }
+
+    broadcast_presence(&state.hub, &rooms, presence).await;
 }
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Fixed-window limiter for one client.
+pub(crate) struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    window_start: Instant,
+    count: u32,
+}
+
+impl RateLimiter {
+    pub(crate) fn new(limit: u32, window: Duration) -> Self {
+        Self { limit, window, window_start: Instant::now(), count: 0 }
+    }
+
+    /// Records one message and returns false once the limit is exceeded.
+    pub(crate) fn check(&mut self) -> bool {
+        if self.window_start.elapsed() >= self.window {
+            self.window_start = Instant::now();
+            self.count = 0;
+        }
+        self.count += 1;
+        self.count <= self.limit
+    }
+
+    /// Time left until the current window resets.
+    pub(crate) fn retry_after(&self) -> Duration {
+        self.window.saturating_sub(self.window_start.elapsed())
+    }
+
+    fn expired(&self) -> bool {
+        self.window_start.elapsed() >= self.window
+    }
+}
+
+/// Fixed-window limiters keyed by client, e.g. one per webhook. Limits are
+/// per process: each replica counts on its own.
+#[derive(Clone)]
+pub(crate) struct KeyedRateLimiter<K> {
+    limit: u32,
+    window: Duration,
+    limiters: Arc<Mutex<HashMap<K, RateLimiter>>>,
+}
+
+/// Idle limiters are dropped once this many keys are tracked.
+const PRUNE_THRESHOLD: usize = 1024;
+
+impl<K: Eq + Hash> KeyedRateLimiter<K> {
+    pub(crate) fn new(limit: u32, window: Duration) -> Self {
+        Self { limit, window, limiters: Arc::new(Mutex::new(HashMap::new())) }
+    }
+
+    /// Records one message for `key`. Returns how long to wait once the limit
+    /// is exceeded.
+    pub(crate) fn check(&self, key: K) -> Result<(), Duration> {
+        let mut limiters = self.limiters.lock().unwrap_or_else(|e| e.into_inner());
+        if limiters.len() >= PRUNE_THRESHOLD {
+            limiters.retain(|_, limiter| !limiter.expired());
+        }
+        let limiter = limiters
+            .entry(key)
+            .or_insert_with(|| RateLimiter::new(self.limit, self.window));
+        if limiter.check() { Ok(()) } else { Err(limiter.retry_after()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyed_rate_limiter() {
+        let limiter = KeyedRateLimiter::new(2, Duration::from_secs(60));
+        assert!(limiter.check(1).is_ok());
+        assert!(limiter.check(1).is_ok());
+        assert!(limiter.check(1).is_err());
+        // Other keys have their own budget.
+        assert!(limiter.check(2).is_ok());
+    }
+}
@@ -0,0 +1,114 @@
+use ferriscord_core::{
+    guild::domain::{channel::ports::ChannelService, errors::CoreError as GuildCoreError},
+    user::domain::{
+        common::CoreError,
+        read_state::{parse_user_mentions, ports::ReadStateService},
+    },
+};
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId, read_state::ReadState};
+use ferriscord_error::ApiError;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{handlers::map_core_error, state::AppState, ws::WsHub};
+
+/// Sends a `read_state.update` event to every session of `user_id`.
+pub async fn publish_read_state(hub: &WsHub, user_id: Uuid, read_state: &ReadState) {
+    let room = format!("user:{}", user_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "read_state.update",
+        "room": room,
+        "data": read_state,
+    })) {
+        hub.publish(&room, payload).await;
+    }
+}
+
+pub async fn publish_read_states(hub: &WsHub, read_states: &[(Uuid, ReadState)]) {
+    for (user_id, read_state) in read_states {
+        publish_read_state(hub, *user_id, read_state).await;
+    }
+}
+
+/// Records a new guild message in read states and notifies the users whose
+/// read state changed. Only the mentioned members who can view the channel
+/// get a mention. Webhook messages have no `author_id`.
+pub async fn record_guild_message(
+    state: &AppState,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    message_id: Uuid,
+    author_id: Option<Uuid>,
+    content: &str,
+) {
+    let mentions = state
+        .channel_service
+        .viewers(
+            &GuildId(Id(guild_id)),
+            &ChannelId(Id(channel_id)),
+            &parse_user_mentions(content),
+        )
+        .await
+        .unwrap_or_else(|e| {
+            error!("failed to check mentioned members: {}", e);
+            Vec::new()
+        });
+
+    let read_states = match author_id {
+        Some(author_id) => {
+            state
+                .read_state_service
+                .record_guild_message(guild_id, channel_id, message_id, author_id, &mentions)
+                .await
+        }
+        None => {
+            state
+                .read_state_service
+                .record_webhook_message(guild_id, channel_id, message_id, &mentions)
+                .await
+        }
+    };
+    match read_states {
+        Ok(read_states) => publish_read_states(&state.hub, &read_states).await,
+        Err(e) => error!("failed to update read states: {}", e),
+    }
+}
+
+/// Acknowledges `message_id` for `user_id` and notifies their other sessions.
+/// `guild_id` is `None` for DM channels; in guild channels the user must be
+/// able to view the channel, as for mentions.
+pub async fn ack_message(
+    state: &AppState,
+    user_id: Uuid,
+    guild_id: Option<Uuid>,
+    channel_id: Uuid,
+    message_id: Uuid,
+) -> Result<ReadState, ApiError> {
+    if let Some(guild_id) = guild_id {
+        let viewers = state
+            .channel_service
+            .viewers(
+                &GuildId(Id(guild_id)),
+                &ChannelId(Id(channel_id)),
+                &[user_id],
+            )
+            .await
+            .map_err(map_core_error)?;
+        if viewers.is_empty() {
+            return Err(map_core_error(GuildCoreError::InsufficientPermissions));
+        }
+    }
+
+    let read_state = state
+        .read_state_service
+        .ack(user_id, guild_id, channel_id, message_id)
+        .await
+        .map_err(|e| match e {
+            CoreError::MessageNotFound => ApiError::NotFound { message: e.to_string() },
+            _ => ApiError::Unknown { message: e.to_string() },
+        })?;
+
+    publish_read_state(&state.hub, user_id, &read_state).await;
+
+    Ok(read_state)
+}
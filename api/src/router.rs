@@ -13,7 +13,7 @@ use tracing::info_span;
 use utoipa::OpenApi;
 use utoipa_scalar::{Scalar, Servable};
 
-use crate::{handlers::handlers_routes, openapi::ApiDoc, state::AppState, ws::ws_handler};
+use crate::{handlers::{discord::{GATEWAY_PATH, discord_routes}, guild::scheduled_event::calendar_routes, handlers_routes, interaction::interaction_routes, webhook::webhook_routes}, openapi::ApiDoc, state::AppState, ws::{discord::discord_gateway_handler, ws_handler}};
 
 async fn openapi_json() -> impl IntoResponse {
     let json = ApiDoc::openapi().to_json().unwrap_or_default();
@@ -79,7 +79,12 @@ pub fn router(state: AppState) -> Result<Router, ApiError> {
         .route("/openapi.json", get(openapi_json))
         .route("/openapi.yaml", get(openapi_yaml))
         .route("/ws", get(ws_handler))
+        .route(GATEWAY_PATH, get(discord_gateway_handler))
         .merge(handlers_routes(state.clone()))
+        .merge(webhook_routes())
+        .merge(interaction_routes())
+        .merge(calendar_routes())
+        .merge(discord_routes(state.clone()))
         .layer(cors_layer)
         .layer(trace_layer)
         .with_state(state);
@@ -0,0 +1,87 @@
+//! Guild scheduled events. Every replica runs a worker that sends reminders
+//! before events start, starts and completes them on time, and opens the
+//! stage of events held in a stage channel.
+
+use std::time::Duration;
+
+use ferriscord_core::guild::domain::scheduled_event::ports::ScheduledEventService;
+use ferriscord_entities::scheduled_event::GuildScheduledEvent;
+use tracing::warn;
+
+use crate::{member_list::publish_guild_event, state::AppState, ws::WsHub};
+
+/// How often due events are looked for.
+const EVENT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+/// Events handled per step and poll.
+const EVENT_BATCH: i64 = 100;
+
+/// Sends an event to the members who can see it: the channel it is held in,
+/// or the whole guild for events held elsewhere.
+pub async fn publish_event_update(hub: &WsHub, kind: &str, event: &GuildScheduledEvent) {
+    let event = GuildScheduledEvent {
+        my_rsvp: None,
+        ..event.clone()
+    };
+    let room = match event.channel_id() {
+        Some(channel_id) => format!("channel:{}", channel_id),
+        None => format!("guild:{}", event.guild_id),
+    };
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": kind,
+        "room": room,
+        "data": event,
+    })) {
+        hub.publish(&room, payload).await;
+    }
+}
+
+/// Sends reminders, and starts and completes due events.
+pub async fn process_scheduled_events(state: AppState) {
+    let mut interval = tokio::time::interval(EVENT_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        let due = match state
+            .scheduled_event_service
+            .process_due_events(EVENT_BATCH)
+            .await
+        {
+            Ok(due) => due,
+            Err(e) => {
+                warn!("failed to process scheduled events: {:?}", e);
+                continue;
+            }
+        };
+
+        for reminder in due.reminders {
+            for user_id in reminder.user_ids {
+                let room = format!("user:{}", user_id);
+                if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+                    "type": "guild_scheduled_event.reminder",
+                    "room": room,
+                    "data": &reminder.event,
+                })) {
+                    state.hub.publish(&room, payload).await;
+                }
+            }
+        }
+
+        for started in due.started {
+            publish_event_update(&state.hub, "guild_scheduled_event.update", &started.event).await;
+            if let Some(instance) = started.stage {
+                publish_guild_event(
+                    &state.hub,
+                    instance.guild_id,
+                    "stage_instance.create",
+                    serde_json::json!(instance),
+                )
+                .await;
+            }
+        }
+
+        for event in due.completed {
+            publish_event_update(&state.hub, "guild_scheduled_event.update", &event).await;
+        }
+    }
+}
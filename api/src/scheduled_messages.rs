@@ -0,0 +1,152 @@
+//! Scheduled messages and reminders. Every replica runs a worker that claims
+//! due rows; a scheduled message is posted in the same transaction that marks
+//! it delivered, so it goes out once even when replicas race or restart.
+
+use std::time::Duration;
+
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::scheduled_message::ports::{
+    DeliveryOutcome, ScheduledMessageService,
+};
+use ferriscord_entities::{
+    event_subscription::GuildEventType, scheduled_message::ScheduledMessage,
+};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::{
+    crossposts::presign_attachments, events::dispatch_guild_event, link_previews::spawn_previews,
+    read_state::record_guild_message, state::AppState, ws::WsHub,
+};
+
+/// How often due rows are looked for.
+const DELIVERY_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Rows claimed per poll.
+const DELIVERY_BATCH: i64 = 50;
+/// How often old delivered and failed rows are pruned.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+async fn publish(hub: &WsHub, room: String, kind: &str, data: impl Serialize) {
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": kind,
+        "room": room,
+        "data": data,
+    })) {
+        hub.publish(&room, payload).await;
+    }
+}
+
+/// Tells every session of the author that one of their scheduled messages
+/// or reminders changed.
+pub async fn publish_scheduled_update(hub: &WsHub, scheduled: &ScheduledMessage) {
+    publish(
+        hub,
+        format!("user:{}", scheduled.user_id),
+        "scheduled_message.update",
+        scheduled,
+    )
+    .await;
+}
+
+async fn handle_outcome(state: &AppState, outcome: DeliveryOutcome, identity: Identity) {
+    match outcome {
+        DeliveryOutcome::Posted {
+            scheduled,
+            mut message,
+        } => {
+            presign_attachments(state, &mut message).await;
+            for room in [
+                format!("channel:{}", message.channel_id),
+                format!("guild:{}", scheduled.guild_id),
+            ] {
+                publish(&state.hub, room, "message.new", &message).await;
+            }
+
+            record_guild_message(
+                state,
+                scheduled.guild_id,
+                message.channel_id.get_uuid(),
+                message.id.get_uuid(),
+                Some(*message.author.id.get_uuid()),
+                &message.content,
+            )
+            .await;
+
+            dispatch_guild_event(
+                state,
+                scheduled.guild_id,
+                GuildEventType::MessageCreate,
+                &message,
+            )
+            .await;
+            spawn_previews(state, Some(identity), scheduled.guild_id, &message);
+            publish_scheduled_update(&state.hub, &scheduled).await;
+        }
+        DeliveryOutcome::Reminded { scheduled, message } => {
+            let message = match message {
+                Some(mut message) => {
+                    presign_attachments(state, &mut message).await;
+                    Some(message)
+                }
+                None => None,
+            };
+            publish(
+                &state.hub,
+                format!("user:{}", scheduled.user_id),
+                "reminder",
+                serde_json::json!({ "reminder": &scheduled, "message": message }),
+            )
+            .await;
+            publish_scheduled_update(&state.hub, &scheduled).await;
+        }
+        DeliveryOutcome::Failed { scheduled } => {
+            publish_scheduled_update(&state.hub, &scheduled).await;
+        }
+        DeliveryOutcome::Skipped => {}
+    }
+}
+
+/// Delivers due scheduled messages and reminders, each in its own task, and
+/// prunes old ones now and then. A row whose delivery errors keeps its lease
+/// and is retried once the lease runs out.
+pub async fn deliver_scheduled_messages(state: AppState) {
+    let mut interval = tokio::time::interval(DELIVERY_POLL_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut prune = tokio::time::interval(PRUNE_INTERVAL);
+    prune.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {
+                let jobs = match state
+                    .scheduled_message_service
+                    .claim_due(DELIVERY_BATCH)
+                    .await
+                {
+                    Ok(jobs) => jobs,
+                    Err(e) => {
+                        warn!("failed to claim scheduled messages: {:?}", e);
+                        continue;
+                    }
+                };
+
+                for job in jobs {
+                    let state = state.clone();
+                    tokio::spawn(async move {
+                        let id = job.scheduled.id;
+                        let identity = job.identity.clone();
+                        match state.scheduled_message_service.deliver(job).await {
+                            Ok(outcome) => handle_outcome(&state, outcome, identity).await,
+                            Err(e) => warn!("failed to deliver scheduled message {}: {:?}", id, e),
+                        }
+                    });
+                }
+            }
+            _ = prune.tick() => {
+                if let Err(e) = state.scheduled_message_service.prune_scheduled().await {
+                    warn!("failed to prune scheduled messages: {:?}", e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Embedded media server. When enabled, voice clients send their media to the
+//! server through `voice.sfu.offer` instead of connecting to each other, and
+//! renegotiations started by the server reach them on their session room.
+
+use std::sync::Arc;
+
+use axum::extract::ws::Message;
+use ferriscord_auth::{Identity, User};
+use ferriscord_core::{
+    guild::domain::voice::{
+        ports::{VoiceMediaPolicy, VoiceService},
+        signal::validate_sdp,
+    },
+    user::domain::user::{UserId, ports::UserService},
+};
+use ferriscord_entities::voice_state::VoiceState;
+use ferriscord_sfu::{IceServer, MediaPolicy, Sfu, SfuConfig, SfuEvent};
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::{args::Args, state::AppState, ws::WsHub};
+
+/// Starts the media server when `SFU_ENABLED` is set. Rooms are voice
+/// channels and participants are gateway sessions.
+pub fn start(args: &Args, hub: WsHub) -> Option<Arc<Sfu>> {
+    if !args.server.sfu.enabled {
+        return None;
+    }
+
+    let ice = &args.server.ice;
+    let mut ice_servers = Vec::new();
+    if !ice.stun_urls.is_empty() {
+        ice_servers.push(IceServer {
+            urls: ice.stun_urls.clone(),
+            ..Default::default()
+        });
+    }
+    if !ice.turn_urls.is_empty() {
+        ice_servers.push(IceServer {
+            urls: ice.turn_urls.clone(),
+            username: ice.turn_username.clone(),
+            credential: ice.turn_credential.clone(),
+        });
+    }
+
+    let (sfu, events) = Sfu::new(SfuConfig {
+        ice_servers,
+        public_ips: args.server.sfu.public_ips.clone(),
+        include_loopback: false,
+    });
+    tokio::spawn(dispatch_events(events, hub));
+    info!("SFU enabled");
+
+    Some(Arc::new(sfu))
+}
+
+async fn dispatch_events(mut events: mpsc::UnboundedReceiver<SfuEvent>, hub: WsHub) {
+    while let Some(event) = events.recv().await {
+        let (participant_id, payload) = match event {
+            SfuEvent::Offer {
+                room_id,
+                participant_id,
+                sdp,
+            } => (
+                participant_id,
+                serde_json::json!({
+                    "type": "voice.sfu.offer",
+                    "data": { "channel_id": room_id, "sdp": sdp },
+                }),
+            ),
+            SfuEvent::Disconnected {
+                room_id,
+                participant_id,
+            } => (
+                participant_id,
+                serde_json::json!({
+                    "type": "voice.sfu.disconnected",
+                    "data": { "channel_id": room_id },
+                }),
+            ),
+        };
+
+        let room = format!("session:{}", participant_id);
+        if let Ok(payload) = serde_json::to_string(&payload) {
+            hub.publish(&room, payload).await;
+        }
+    }
+}
+
+fn media_policy(policy: VoiceMediaPolicy) -> MediaPolicy {
+    MediaPolicy {
+        can_speak: policy.can_speak,
+        can_stream: policy.can_stream,
+        deaf: policy.deaf,
+    }
+}
+
+async fn send(conn_tx: &mpsc::Sender<Message>, payload: serde_json::Value) {
+    if let Ok(payload) = serde_json::to_string(&payload) {
+        let _ = conn_tx.send(Message::Text(payload.into())).await;
+    }
+}
+
+async fn send_failed(conn_tx: &mpsc::Sender<Message>, channel_id: Uuid, message: String) {
+    send(
+        conn_tx,
+        serde_json::json!({
+            "type": "voice.sfu_failed",
+            "data": { "channel_id": channel_id, "message": message },
+        }),
+    )
+    .await;
+}
+
+/// Handles `voice.sfu.offer`: connects the session's voice connection to the
+/// media server and replies with `voice.sfu.answer`.
+#[allow(clippy::too_many_arguments)]
+pub async fn offer(
+    state: &AppState,
+    sfu: &Sfu,
+    identity: Identity,
+    user_id: Uuid,
+    session_id: Uuid,
+    channel_id: Uuid,
+    sdp: String,
+    conn_tx: &mpsc::Sender<Message>,
+) {
+    if let Err(e) = validate_sdp(&sdp) {
+        return send_failed(conn_tx, channel_id, e.to_string()).await;
+    }
+
+    let policy = match state
+        .voice_service
+        .media_policy(identity, user_id, session_id, channel_id)
+        .await
+    {
+        Ok(policy) => policy,
+        Err(e) => return send_failed(conn_tx, channel_id, e.to_string()).await,
+    };
+
+    let answer = match sfu
+        .join(channel_id, session_id, policy.bitrate, media_policy(policy), sdp)
+        .await
+    {
+        Ok(answer) => answer,
+        Err(e) => return send_failed(conn_tx, channel_id, e.to_string()).await,
+    };
+
+    send(
+        conn_tx,
+        serde_json::json!({
+            "type": "voice.sfu.answer",
+            "data": { "channel_id": channel_id, "sdp": answer },
+        }),
+    )
+    .await;
+
+    // Offers for the other participants' tracks may follow now.
+    if let Err(e) = sfu.ready(channel_id, session_id).await {
+        warn!("SFU: participant left before ready: {}", e);
+    }
+}
+
+/// Handles `voice.sfu.answer`, the reply to a server-initiated offer.
+pub async fn answer(
+    sfu: &Sfu,
+    session_id: Uuid,
+    channel_id: Uuid,
+    sdp: String,
+    conn_tx: &mpsc::Sender<Message>,
+) {
+    if let Err(e) = validate_sdp(&sdp) {
+        return send_failed(conn_tx, channel_id, e.to_string()).await;
+    }
+    if let Err(e) = sfu.answer(channel_id, session_id, sdp).await {
+        send_failed(conn_tx, channel_id, e.to_string()).await;
+    }
+}
+
+/// Re-applies the media policy of a voice connection after its state was
+/// changed by someone else, e.g. a stage moderator.
+pub async fn refresh_policy(state: &AppState, voice_state: &VoiceState) {
+    let Some(sfu) = &state.sfu else {
+        return;
+    };
+
+    // The policy follows the permissions of the connected user, not of the
+    // user who changed their state.
+    let identity = match state.user_service.get_profile(UserId::from(voice_state.user_id)).await {
+        Ok(Some(user)) => Identity::User(User {
+            id: user.oauth_sub,
+            username: user.username,
+            email: None,
+            name: None,
+            roles: Vec::new(),
+        }),
+        Ok(None) => return,
+        Err(e) => return warn!("SFU: failed to load voice user: {:?}", e),
+    };
+
+    match state
+        .voice_service
+        .media_policy(identity, voice_state.user_id, voice_state.session_id, voice_state.channel_id)
+        .await
+    {
+        Ok(policy) => {
+            // Not connected to the media server (or on another replica).
+            let _ = sfu
+                .set_policy(voice_state.channel_id, voice_state.session_id, media_policy(policy))
+                .await;
+        }
+        Err(e) => warn!("SFU: failed to refresh media policy: {:?}", e),
+    }
+}
+
+/// Drops the media connection of a voice state that ended.
+pub async fn voice_left(state: &AppState, left: &VoiceState) {
+    if let Some(sfu) = &state.sfu {
+        sfu.leave(left.channel_id, left.session_id).await;
+    }
+}
+
+/// Drops every media connection of a gateway session that closed.
+pub async fn session_closed(state: &AppState, session_id: Uuid) {
+    if let Some(sfu) = &state.sfu {
+        sfu.disconnect(session_id).await;
+    }
+}
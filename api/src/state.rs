@@ -1,24 +1,34 @@
 use std::sync::Arc;
+use std::time::Duration;
 
 use ferriscord_auth::{FerriskeyAuthRepository, HasAuthRepository};
 use ferriscord_config::{AuthConfig, DatabaseConfig, StorageConfig};
 use ferriscord_core::{
     crypto::infrastructure::postgres::PostgresCryptoKeyRepository,
     guild::application::{
-        ChannelFerrisCordService, GuildFerrisCordService, InviteFerrisCordService,
-        MemberFerrisCordRepository, MessageFerrisCordService, RoleFerrisCordService,
-        create_auth_repository, create_guild_services,
+        ApplicationFerrisCordService, AttachmentUploadFerrisCordService, ChannelFerrisCordService, ChannelFollowFerrisCordService, DiscordCompatFerrisCordService, EmojiFerrisCordService, EventSubscriptionFerrisCordService, GuildFerrisCordService, GuildImportFerrisCordService,
+        InteractionFerrisCordService, InviteFerrisCordService, LinkPreviewFerrisCordService, MemberFerrisCordRepository, MessageFerrisCordService, RoleFerrisCordService,
+        PollFerrisCordService, ScheduledEventFerrisCordService, ScheduledMessageFerrisCordService, StageFerrisCordService, VoiceFerrisCordService, WebhookFerrisCordService,
+        create_application_service, create_attachment_upload_service, create_auth_repository, create_channel_follow_service, create_discord_compat_service, create_emoji_service, create_event_subscription_service, create_guild_import_service, create_guild_services,
+        create_interaction_service, create_link_preview_service, create_poll_service, create_scheduled_event_service, create_scheduled_message_service, create_stage_service,
+        create_voice_service, create_webhook_service,
     },
     user::application::{
-        DmFerrisCordService, FriendFerrisCordService, UserFerrisCordService, create_user_services,
+        CallFerrisCordService, DmFerrisCordService, FriendFerrisCordService, PresenceFerrisCordService,
+        ReadStateFerrisCordService, UserFerrisCordService, create_call_service,
+        create_presence_service, create_read_state_service, create_user_services,
     },
 };
 use ferriscord_error::ApiError;
+#[cfg(feature = "sfu")]
+use ferriscord_sfu::Sfu;
 use ferriscord_storage::S3Client;
 use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::args::Args;
-use crate::presence::PresenceStore;
+use crate::member_list::MemberLists;
+use crate::rate_limit::KeyedRateLimiter;
 use crate::ws::WsHub;
 
 #[derive(Clone)]
@@ -29,17 +39,40 @@ pub struct AppState {
     pub user_service: UserFerrisCordService,
     pub friend_service: FriendFerrisCordService,
     pub dm_service: DmFerrisCordService,
+    pub presence_service: PresenceFerrisCordService,
+    pub read_state_service: ReadStateFerrisCordService,
+    pub call_service: CallFerrisCordService,
     // Guild domain
     pub guild_service: GuildFerrisCordService,
     pub role_service: RoleFerrisCordService,
     pub channel_service: ChannelFerrisCordService,
     pub message_service: MessageFerrisCordService,
     pub invite_service: InviteFerrisCordService,
+    pub voice_service: VoiceFerrisCordService,
+    pub stage_service: StageFerrisCordService,
+    pub webhook_service: WebhookFerrisCordService,
+    pub channel_follow_service: ChannelFollowFerrisCordService,
+    pub emoji_service: EmojiFerrisCordService,
+    pub event_subscription_service: EventSubscriptionFerrisCordService,
+    pub application_service: ApplicationFerrisCordService,
+    pub interaction_service: InteractionFerrisCordService,
+    pub link_preview_service: LinkPreviewFerrisCordService,
+    pub scheduled_message_service: ScheduledMessageFerrisCordService,
+    pub poll_service: PollFerrisCordService,
+    pub scheduled_event_service: ScheduledEventFerrisCordService,
+    pub discord_compat_service: DiscordCompatFerrisCordService,
+    pub guild_import_service: GuildImportFerrisCordService,
+    pub attachment_upload_service: AttachmentUploadFerrisCordService,
     pub member_repository: MemberFerrisCordRepository,
     pub crypto_repository: PostgresCryptoKeyRepository,
     pub storage: S3Client,
     pub hub: WsHub,
-    pub presence: PresenceStore,
+    pub member_lists: MemberLists,
+    /// Messages posted per webhook.
+    pub webhook_limiter: KeyedRateLimiter<Uuid>,
+    /// Set when the embedded media server is enabled.
+    #[cfg(feature = "sfu")]
+    pub sfu: Option<Arc<Sfu>>,
 }
 
 impl HasAuthRepository for AppState {
@@ -74,6 +107,27 @@ pub async fn state(args: Arc<Args>) -> Result<AppState, ApiError> {
         create_guild_services(pool.clone(), auth_config.issuer.clone())
             .map_err(|e| ApiError::Unknown { message: e.to_string() })?;
 
+    let presence_service = create_presence_service(pool.clone());
+    let read_state_service = create_read_state_service(pool.clone());
+    let call_service = create_call_service(pool.clone());
+
+    let voice_service = create_voice_service(pool.clone());
+    let stage_service = create_stage_service(pool.clone());
+    let webhook_service = create_webhook_service(pool.clone());
+    let channel_follow_service = create_channel_follow_service(pool.clone());
+    let emoji_service = create_emoji_service(pool.clone());
+    let event_subscription_service =
+        create_event_subscription_service(pool.clone(), args.webhook.allowed_hosts.clone());
+    let application_service = create_application_service(pool.clone());
+    let interaction_service =
+        create_interaction_service(pool.clone(), args.webhook.allowed_hosts.clone());
+    let link_preview_service = create_link_preview_service(pool.clone());
+    let scheduled_message_service = create_scheduled_message_service(pool.clone());
+    let poll_service = create_poll_service(pool.clone());
+    let scheduled_event_service = create_scheduled_event_service(pool.clone());
+    let discord_compat_service = create_discord_compat_service(pool.clone());
+    let guild_import_service = create_guild_import_service(pool.clone());
+    let attachment_upload_service = create_attachment_upload_service(pool.clone());
     let member_repository = MemberFerrisCordRepository::new(pool.clone());
     let crypto_repository = PostgresCryptoKeyRepository::new(pool.clone());
 
@@ -81,21 +135,50 @@ pub async fn state(args: Arc<Args>) -> Result<AppState, ApiError> {
         message: e.to_string(),
     })?;
 
+    let hub = WsHub::new();
+    let webhook_limiter = KeyedRateLimiter::new(
+        args.webhook.rate_limit,
+        Duration::from_secs(args.webhook.rate_limit_window_secs),
+    );
+    #[cfg(feature = "sfu")]
+    let sfu = crate::sfu::start(&args, hub.clone());
+
     Ok(AppState {
         args,
         auth,
         user_service,
         friend_service,
         dm_service,
+        presence_service,
+        read_state_service,
+        call_service,
         guild_service,
         role_service,
         channel_service,
         message_service,
         invite_service,
+        voice_service,
+        stage_service,
+        webhook_service,
+        channel_follow_service,
+        emoji_service,
+        event_subscription_service,
+        application_service,
+        interaction_service,
+        link_preview_service,
+        scheduled_message_service,
+        poll_service,
+        scheduled_event_service,
+        discord_compat_service,
+        guild_import_service,
+        attachment_upload_service,
         member_repository,
         crypto_repository,
         storage,
-        hub: WsHub::new(),
-        presence: PresenceStore::new(),
+        hub,
+        member_lists: MemberLists::default(),
+        webhook_limiter,
+        #[cfg(feature = "sfu")]
+        sfu,
     })
 }
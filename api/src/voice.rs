@@ -0,0 +1,308 @@
+use std::time::Duration;
+
+use ferriscord_core::guild::domain::{
+    errors::CoreError,
+    voice::{
+        ports::{VoiceJoin, VoiceService},
+        signal::VoiceSignal,
+    },
+};
+use ferriscord_entities::voice_state::VoiceState;
+use ferriscord_server::args::IceServerArgs;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::{state::AppState, ws::WsHub};
+
+/// How often voice states of users without a live gateway session are swept.
+const REAP_INTERVAL: Duration = Duration::from_secs(60);
+
+fn voice_room(state: &VoiceState) -> String {
+    match state.guild_id {
+        Some(guild_id) => format!("guild:{}", guild_id),
+        None => format!("dm:{}", state.channel_id),
+    }
+}
+
+/// Publishes a `voice_state.update`. When `connected` is false the user left
+/// and the event carries `channel_id: null`.
+pub async fn publish_voice_state(hub: &WsHub, state: &VoiceState, connected: bool) {
+    let room = voice_room(state);
+    let mut data = serde_json::json!(state);
+    if !connected {
+        data["channel_id"] = serde_json::Value::Null;
+    }
+
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "voice_state.update",
+        "room": room,
+        "data": data,
+    })) {
+        hub.publish(&room, payload).await;
+    }
+}
+
+/// Publishes a voice state changed by someone other than its user and applies
+/// it to their media connection, if any.
+pub async fn voice_state_changed(state: &AppState, changed: &VoiceState) {
+    publish_voice_state(&state.hub, changed, true).await;
+    #[cfg(feature = "sfu")]
+    crate::sfu::refresh_policy(state, changed).await;
+}
+
+/// Publishes that the user left voice and drops their media connection, if
+/// any.
+pub async fn voice_left(state: &AppState, left: &VoiceState) {
+    publish_voice_state(&state.hub, left, false).await;
+    #[cfg(feature = "sfu")]
+    crate::sfu::voice_left(state, left).await;
+    // Leaving the voice channel of a DM hangs up its call.
+    if left.guild_id.is_none() {
+        crate::call::call_left(state, left.user_id, left.channel_id).await;
+    }
+}
+
+/// Publishes a move of a user by a moderator, and hands their gateway session
+/// the channel to reconnect to.
+pub async fn voice_member_moved(state: &AppState, join: &VoiceJoin) {
+    if let Some(previous) = &join.previous {
+        voice_left(state, previous).await;
+    }
+    publish_voice_state(&state.hub, &join.state, true).await;
+
+    let room = format!("session:{}", join.state.session_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "voice_server.update",
+        "room": room,
+        "data": {
+            "guild_id": join.state.guild_id,
+            "channel_id": join.state.channel_id,
+            "session_id": join.state.session_id,
+            "ice_servers": ice_servers(&state.args.server.ice),
+        },
+    })) {
+        state.hub.publish(&room, payload).await;
+    }
+}
+
+/// ICE servers in the `RTCIceServer` shape expected by browsers.
+pub fn ice_servers(args: &IceServerArgs) -> serde_json::Value {
+    let mut servers = Vec::new();
+    if !args.stun_urls.is_empty() {
+        servers.push(serde_json::json!({ "urls": args.stun_urls }));
+    }
+    if !args.turn_urls.is_empty() {
+        servers.push(serde_json::json!({
+            "urls": args.turn_urls,
+            "username": args.turn_username,
+            "credential": args.turn_credential,
+        }));
+    }
+    serde_json::Value::Array(servers)
+}
+
+/// Validates a `voice.signal` from `session_id` and delivers it to the session
+/// holding the target's voice connection, provided both are in `channel_id`.
+pub async fn relay_voice_signal(
+    state: &AppState,
+    user_id: Uuid,
+    session_id: Uuid,
+    channel_id: Uuid,
+    target_user_id: Uuid,
+    signal: serde_json::Value,
+) -> Result<(), CoreError> {
+    let signal: VoiceSignal = serde_json::from_value(signal)
+        .map_err(|e| CoreError::InvalidVoiceSignal { message: e.to_string() })?;
+    signal.validate()?;
+
+    let target = state
+        .voice_service
+        .authorize_signal(user_id, session_id, channel_id, target_user_id)
+        .await?;
+
+    let room = format!("session:{}", target.session_id);
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "voice.signal",
+        "room": room,
+        "data": {
+            "channel_id": channel_id,
+            "from_user_id": user_id,
+            "from_session_id": session_id,
+            "signal": signal,
+        },
+    })) {
+        state.hub.publish(&room, payload).await;
+    }
+    Ok(())
+}
+
+/// Removes the user from voice once their last gateway session is gone.
+pub async fn voice_session_ended(state: &AppState, user_id: Uuid) {
+    match state.voice_service.session_ended(user_id).await {
+        Ok(Some(left)) => voice_left(state, &left).await,
+        Ok(None) => {}
+        Err(e) => warn!("failed to end voice session: {:?}", e),
+    }
+}
+
+/// Periodically removes voice states whose sessions died without
+/// disconnecting, e.g. on a replica that crashed.
+pub async fn reap_orphaned_voice_states(state: AppState) {
+    let mut interval = tokio::time::interval(REAP_INTERVAL);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        interval.tick().await;
+        reap_once(&state).await;
+    }
+}
+
+/// Removes the voice states orphaned right now and publishes their leaves.
+async fn reap_once(state: &AppState) {
+    match state.voice_service.reap_orphaned().await {
+        Ok(left) => {
+            for voice_state in left {
+                voice_left(state, &voice_state).await;
+            }
+        }
+        Err(e) => warn!("failed to reap orphaned voice states: {:?}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use clap::Parser;
+    use ferriscord_auth::{Identity, User};
+    use ferriscord_core::guild::domain::{
+        channel::{entities::CreateChannelInput, ports::ChannelService},
+        guild::{entities::CreateGuildInput, ports::GuildService},
+    };
+    use ferriscord_core::user::domain::user::ports::UserService;
+    use ferriscord_entities::{Id, channel::ChannelKind, guild::OwnerId, user::UserId};
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::args::Args;
+
+    #[tokio::test]
+    #[ignore = "needs a PostgreSQL database, configured with DATABASE_* like the api"]
+    async fn test_reaps_voice_states_without_a_live_session() {
+        let args = Arc::new(Args::parse_from(["api"]));
+        let db = &args.db;
+        let pool = PgPool::connect(&format!(
+            "postgres://{}:{}@{}:{}/{}",
+            db.user, db.password, db.host, db.port, db.name
+        ))
+        .await
+        .unwrap();
+        sqlx::migrate!("../migrations").run(&pool).await.unwrap();
+        let state = crate::state::state(args).await.unwrap();
+
+        let suffix = Uuid::now_v7().simple().to_string()[20..].to_string();
+        let mut users = Vec::new();
+        for name in ["stale", "fresh"] {
+            let sub = Uuid::now_v7();
+            let username = format!("{name}{suffix}");
+            let user = state
+                .user_service
+                .upsert_by_sub(&sub.to_string(), &username)
+                .await
+                .unwrap();
+            users.push((sub, username, user.id.0));
+        }
+        let (owner_sub, owner_name, owner_id) = users[0].clone();
+        let identity = Identity::User(User {
+            id: owner_sub.to_string(),
+            username: owner_name,
+            email: None,
+            name: None,
+            roles: Vec::new(),
+        });
+        let guild = state
+            .guild_service
+            .create_guild(CreateGuildInput {
+                name: format!("voice {suffix}"),
+                owner_id: OwnerId(Id(owner_sub)),
+                owner_user_id: UserId::from(owner_id),
+            })
+            .await
+            .unwrap();
+        let channel = state
+            .channel_service
+            .create_channel(
+                identity,
+                guild.id.clone(),
+                CreateChannelInput {
+                    name: "lounge".to_string(),
+                    kind: ChannelKind::Voice,
+                    guild_id: guild.id.clone(),
+                    topic: None,
+                    position: None,
+                    nsfw: None,
+                    rate_limit_per_user: None,
+                    parent_id: None,
+                    bitrate: None,
+                    user_limit: None,
+                    rtc_region: None,
+                    permission_overwrites: None,
+                    default_auto_archive_duration: None,
+                    flags: None,
+                    available_tags: None,
+                    default_reaction_emoji: None,
+                    default_thread_rate_limit_per_user: None,
+                    default_sort_order: None,
+                    default_forum_layout: None,
+                },
+            )
+            .await
+            .unwrap();
+
+        // Both users are in voice; only the second one's session still
+        // heartbeats.
+        let guild_id = guild.id.get_uuid();
+        let channel_id = channel.id.get_uuid();
+        for ((_, _, user_id), heartbeat_age) in users.iter().zip([91, 5]) {
+            sqlx::query(
+                "INSERT INTO voice_states (user_id, guild_id, channel_id, session_id)
+                 VALUES ($1, $2, $3, $4)",
+            )
+            .bind(user_id)
+            .bind(guild_id)
+            .bind(channel_id)
+            .bind(Uuid::now_v7())
+            .execute(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO presence_sessions (id, user_id, heartbeat_at)
+                 VALUES ($1, $2, now() - make_interval(secs => $3))",
+            )
+            .bind(Uuid::now_v7())
+            .bind(user_id)
+            .bind(heartbeat_age as f64)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let mut events = state.hub.subscribe(&format!("guild:{}", guild_id)).await;
+        reap_once(&state).await;
+
+        let (stale_id, fresh_id) = (users[0].2, users[1].2);
+        let event: serde_json::Value = serde_json::from_str(&events.try_recv().unwrap()).unwrap();
+        assert_eq!(event["type"], "voice_state.update");
+        assert_eq!(event["data"]["user_id"], stale_id.to_string());
+        assert!(event["data"]["channel_id"].is_null());
+        assert!(events.try_recv().is_err());
+
+        let remaining: Vec<Uuid> =
+            sqlx::query_scalar("SELECT user_id FROM voice_states WHERE channel_id = $1")
+                .bind(channel_id)
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(remaining, vec![fresh_id]);
+    }
+}
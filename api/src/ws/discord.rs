@@ -0,0 +1,1198 @@
+//! Discord's gateway protocol, for bots using the Discord-compatible API:
+//! hello, identify and heartbeats, then guild, channel, member, role and
+//! message events translated from the hub's. Sessions can't be resumed;
+//! bots identify again instead.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use axum::extract::ws::{CloseFrame, Message, WebSocket};
+use axum::extract::{State, WebSocketUpgrade};
+use axum::http::HeaderMap;
+use axum::response::IntoResponse;
+use chrono::Utc;
+use ferriscord_auth::Identity;
+use ferriscord_core::guild::domain::{
+    channel::ports::ChannelService,
+    guild::ports::GuildService,
+    member::ports::{MemberRepository, MemberWithUser},
+    message::ports::MessageService,
+};
+use ferriscord_core::user::domain::{
+    presence::ports::PresenceService,
+    user::{UserId, ports::UserService},
+};
+use ferriscord_entities::{
+    Id,
+    channel::{Channel, ChannelId},
+    guild::{Guild, GuildId},
+    presence::Presence,
+    role::Role,
+    user::UserId as GuildUserId,
+};
+use futures_util::stream::SplitStream;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::sync::{broadcast, mpsc};
+use tokio::task::JoinHandle;
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use super::{WsAuth, authenticate, close_code, close_frame, deadline_at};
+use crate::crossposts::presign_attachments;
+use crate::handlers::discord::{
+    DiscordError, Snowflakes, current_user,
+    get_gateway::gateway_url,
+    guild_roles,
+    models::{
+        DiscordChannel, DiscordGuild, DiscordMember, DiscordRole, DiscordUser, discord_channels,
+        discord_messages, guild_snowflakes,
+    },
+    resolve,
+};
+use crate::presence::broadcast_presence_to_user_guilds;
+use crate::rate_limit::RateLimiter;
+use crate::state::AppState;
+
+/// How often guilds and their channels are listed again, to pick up the
+/// changes the hub has no event for: joined and left guilds, created and
+/// deleted channels.
+const GUILD_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+/// How often the presence session is heartbeated.
+const PRESENCE_TICK: Duration = Duration::from_secs(30);
+/// Members sent in `GUILD_CREATE` and per `GUILD_MEMBERS_CHUNK`.
+const MAX_MEMBERS: usize = 1000;
+/// How long to wait for a close frame to be flushed before dropping the socket.
+const CLOSE_FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+mod op {
+    pub const DISPATCH: u8 = 0;
+    pub const HEARTBEAT: u8 = 1;
+    pub const IDENTIFY: u8 = 2;
+    pub const PRESENCE_UPDATE: u8 = 3;
+    pub const VOICE_STATE_UPDATE: u8 = 4;
+    pub const RESUME: u8 = 6;
+    pub const REQUEST_GUILD_MEMBERS: u8 = 8;
+    pub const INVALID_SESSION: u8 = 9;
+    pub const HELLO: u8 = 10;
+    pub const HEARTBEAT_ACK: u8 = 11;
+}
+
+/// The intents events are filtered by. Message content is always sent, so
+/// MESSAGE_CONTENT makes no difference.
+mod intent {
+    pub const GUILDS: u64 = 1 << 0;
+    pub const GUILD_MEMBERS: u64 = 1 << 1;
+    pub const GUILD_MESSAGES: u64 = 1 << 9;
+    pub const GUILD_MESSAGE_TYPING: u64 = 1 << 11;
+}
+
+/// Discord's close codes, besides the ones shared with the native gateway.
+mod discord_close_code {
+    /// Discord libraries reconnect and identify again on this code.
+    pub const UNKNOWN_ERROR: u16 = 4000;
+    pub const UNKNOWN_OPCODE: u16 = 4001;
+    pub const DECODE_ERROR: u16 = 4002;
+    pub const ALREADY_AUTHENTICATED: u16 = 4005;
+}
+
+#[derive(Deserialize)]
+struct Payload {
+    op: u8,
+    #[serde(default)]
+    d: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct Identify {
+    token: String,
+    #[serde(default)]
+    intents: u64,
+}
+
+#[derive(Deserialize)]
+struct RequestGuildMembers {
+    guild_id: String,
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    limit: usize,
+    /// One snowflake or a list of them.
+    #[serde(default)]
+    user_ids: Option<serde_json::Value>,
+    #[serde(default)]
+    nonce: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct HubEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    room: String,
+    #[serde(default)]
+    data: serde_json::Value,
+}
+
+/// The id of the user, message or role an event is about.
+#[derive(Deserialize)]
+struct EventIds {
+    id: Option<Uuid>,
+    user_id: Option<Uuid>,
+    message_id: Option<Uuid>,
+    role_id: Option<Uuid>,
+    channel_id: Option<Uuid>,
+    is_typing: Option<bool>,
+}
+
+fn frame(op: u8, d: serde_json::Value) -> Message {
+    let payload = serde_json::json!({ "op": op, "d": d, "s": null, "t": null });
+    Message::Text(payload.to_string().into())
+}
+
+/// Forwards every message of a room to the session until either side goes
+/// away.
+fn forward_room(mut rx: broadcast::Receiver<String>, tx: mpsc::Sender<String>) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(msg) => {
+                    if tx.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            }
+        }
+    })
+}
+
+pub async fn discord_gateway_handler(
+    ws: WebSocketUpgrade,
+    State(state): State<AppState>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let resume_gateway_url = gateway_url(&headers);
+    let ws = ws.max_message_size(state.args.gateway.max_message_size.saturating_mul(4));
+    ws.on_upgrade(move |socket| handle_socket(socket, state, resume_gateway_url))
+}
+
+/// Waits for the identify of a connection, answering heartbeats meanwhile.
+/// On failure, returns the close frame to send (if the socket is still open).
+async fn wait_for_identify(
+    state: &AppState,
+    ws_rx: &mut SplitStream<WebSocket>,
+    conn_tx: &mpsc::Sender<Message>,
+    timeout: Duration,
+) -> Result<(WsAuth, u64), Option<CloseFrame>> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        let msg = match tokio::time::timeout_at(deadline, ws_rx.next()).await {
+            Ok(Some(Ok(msg))) => msg,
+            Ok(_) => return Err(None),
+            Err(_) => {
+                return Err(Some(close_frame(
+                    close_code::NOT_AUTHENTICATED,
+                    "not authenticated",
+                )));
+            }
+        };
+        let Message::Text(text) = msg else {
+            continue;
+        };
+        if text.len() > state.args.gateway.max_message_size {
+            return Err(Some(close_frame(
+                close_code::MESSAGE_TOO_BIG,
+                "message too big",
+            )));
+        }
+        let Ok(payload) = serde_json::from_str::<Payload>(&text) else {
+            return Err(Some(close_frame(
+                discord_close_code::DECODE_ERROR,
+                "decode error",
+            )));
+        };
+
+        match payload.op {
+            op::HEARTBEAT => {
+                let _ = conn_tx
+                    .send(frame(op::HEARTBEAT_ACK, serde_json::Value::Null))
+                    .await;
+            }
+            // There is nothing to resume: the bot has to identify.
+            op::RESUME => {
+                let _ = conn_tx
+                    .send(frame(op::INVALID_SESSION, serde_json::json!(false)))
+                    .await;
+            }
+            op::IDENTIFY => {
+                let Ok(identify) = serde_json::from_value::<Identify>(payload.d) else {
+                    return Err(Some(close_frame(
+                        discord_close_code::DECODE_ERROR,
+                        "decode error",
+                    )));
+                };
+                // Discord libraries send the bare bot token.
+                let token = identify.token.trim();
+                let token = format!("Bot {}", token.strip_prefix("Bot ").unwrap_or(token));
+                return authenticate(state, &token)
+                    .await
+                    .map(|auth| (auth, identify.intents))
+                    .map_err(|_| {
+                        Some(close_frame(
+                            close_code::AUTHENTICATION_FAILED,
+                            "authentication failed",
+                        ))
+                    });
+            }
+            _ => {
+                return Err(Some(close_frame(
+                    close_code::NOT_AUTHENTICATED,
+                    "not authenticated",
+                )));
+            }
+        }
+    }
+}
+
+async fn handle_socket(socket: WebSocket, state: AppState, resume_gateway_url: String) {
+    let gateway = state.args.gateway.clone();
+    let heartbeat_interval = Duration::from_millis(gateway.heartbeat_interval_ms);
+
+    let (mut ws_tx, mut ws_rx) = socket.split();
+    let (conn_tx, mut conn_rx) = mpsc::channel::<Message>(256);
+    let mut send_task = tokio::spawn(async move {
+        while let Some(msg) = conn_rx.recv().await {
+            let closing = matches!(msg, Message::Close(_));
+            if ws_tx.send(msg).await.is_err() || closing {
+                break;
+            }
+        }
+    });
+
+    let _ = conn_tx
+        .send(frame(
+            op::HELLO,
+            serde_json::json!({ "heartbeat_interval": gateway.heartbeat_interval_ms }),
+        ))
+        .await;
+
+    let close = match wait_for_identify(&state, &mut ws_rx, &conn_tx, heartbeat_interval).await {
+        Ok((auth, intents)) => {
+            run(
+                &state,
+                auth,
+                intents,
+                resume_gateway_url,
+                &mut ws_rx,
+                conn_tx.clone(),
+            )
+            .await
+        }
+        Err(close) => close,
+    };
+
+    if let Some(frame) = close {
+        let _ = conn_tx.send(Message::Close(Some(frame))).await;
+        let _ = tokio::time::timeout(CLOSE_FLUSH_TIMEOUT, &mut send_task).await;
+    }
+    send_task.abort();
+}
+
+/// A guild the bot is in, with the room tasks of the guild and of the
+/// channels it can view.
+struct JoinedGuild {
+    task: JoinHandle<()>,
+    channels: HashMap<Uuid, JoinHandle<()>>,
+}
+
+impl Drop for JoinedGuild {
+    fn drop(&mut self) {
+        self.task.abort();
+        for task in self.channels.values() {
+            task.abort();
+        }
+    }
+}
+
+struct Session {
+    state: AppState,
+    identity: Identity,
+    user_id: Uuid,
+    intents: u64,
+    out: mpsc::Sender<Message>,
+    /// Hub events of the rooms the session listens to.
+    events: mpsc::Sender<String>,
+    seq: u64,
+    guilds: HashMap<Uuid, JoinedGuild>,
+}
+
+/// Serves an identified bot until it goes away. Returns the close frame to
+/// send, if the socket is still open.
+async fn run(
+    state: &AppState,
+    auth: WsAuth,
+    intents: u64,
+    resume_gateway_url: String,
+    ws_rx: &mut SplitStream<WebSocket>,
+    out: mpsc::Sender<Message>,
+) -> Option<CloseFrame> {
+    let gateway = &state.args.gateway;
+    let heartbeat_interval = Duration::from_millis(gateway.heartbeat_interval_ms);
+    let (events, mut events_rx) = mpsc::channel::<String>(256);
+    let mut session = Session {
+        state: state.clone(),
+        identity: auth.identity,
+        user_id: auth.user_id,
+        intents,
+        out,
+        events,
+        seq: 0,
+        guilds: HashMap::new(),
+    };
+    if let Err(e) = session.ready(resume_gateway_url).await {
+        error!("Discord gateway: failed to send READY: {:?}", e);
+        return Some(close_frame(
+            discord_close_code::UNKNOWN_ERROR,
+            "unknown error",
+        ));
+    }
+
+    // Like a native gateway connection, the bot is its own presence session.
+    let presence = state.presence_service.clone();
+    let session_id = Uuid::now_v7();
+    let mut last_presence = match presence.connect(session.user_id, session_id).await {
+        Ok(p) => {
+            broadcast_presence_to_user_guilds(state, session.identity.clone(), session.user_id, &p)
+                .await;
+            p
+        }
+        Err(e) => {
+            error!(
+                "Discord gateway: failed to register presence session: {:?}",
+                e
+            );
+            Presence::offline(session.user_id)
+        }
+    };
+
+    // Bot tokens are checked again when the bot reconnects.
+    let token_expiry = tokio::time::sleep_until(deadline_at(auth.expires_at));
+    tokio::pin!(token_expiry);
+
+    let start = tokio::time::Instant::now();
+    let mut heartbeat_check =
+        tokio::time::interval_at(start + heartbeat_interval, heartbeat_interval);
+    heartbeat_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let heartbeat_deadline = heartbeat_interval + heartbeat_interval / 2;
+    let mut last_heartbeat = Instant::now();
+    let mut presence_tick = tokio::time::interval_at(start + PRESENCE_TICK, PRESENCE_TICK);
+    presence_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut guild_refresh =
+        tokio::time::interval_at(start + GUILD_REFRESH_INTERVAL, GUILD_REFRESH_INTERVAL);
+    guild_refresh.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut rate_limiter = RateLimiter::new(
+        gateway.rate_limit,
+        Duration::from_secs(gateway.rate_limit_window_secs),
+    );
+
+    let close = loop {
+        tokio::select! {
+            msg = ws_rx.next() => {
+                let text = match msg {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break None,
+                    Some(Ok(_)) => continue,
+                };
+                if !rate_limiter.check() {
+                    break Some(close_frame(close_code::RATE_LIMITED, "rate limited"));
+                }
+                if text.len() > gateway.max_message_size {
+                    break Some(close_frame(close_code::MESSAGE_TOO_BIG, "message too big"));
+                }
+                let Ok(payload) = serde_json::from_str::<Payload>(&text) else {
+                    break Some(close_frame(discord_close_code::DECODE_ERROR, "decode error"));
+                };
+                match payload.op {
+                    op::HEARTBEAT => {
+                        last_heartbeat = Instant::now();
+                        let _ = session.out.send(frame(op::HEARTBEAT_ACK, serde_json::Value::Null)).await;
+                    }
+                    op::IDENTIFY => {
+                        break Some(close_frame(
+                            discord_close_code::ALREADY_AUTHENTICATED,
+                            "already authenticated",
+                        ));
+                    }
+                    op::REQUEST_GUILD_MEMBERS => {
+                        let Ok(request) = serde_json::from_value(payload.d) else {
+                            break Some(close_frame(discord_close_code::DECODE_ERROR, "decode error"));
+                        };
+                        session.request_guild_members(request).await;
+                    }
+                    // Presences are tracked from the connection itself, and
+                    // voice isn't bridged.
+                    op::PRESENCE_UPDATE | op::VOICE_STATE_UPDATE => {}
+                    op::RESUME => {
+                        let _ = session
+                            .out
+                            .send(frame(op::INVALID_SESSION, serde_json::json!(false)))
+                            .await;
+                    }
+                    _ => {
+                        break Some(close_frame(discord_close_code::UNKNOWN_OPCODE, "unknown opcode"));
+                    }
+                }
+            }
+            Some(event) = events_rx.recv() => session.handle_event(&event).await,
+            _ = &mut token_expiry => {
+                break Some(close_frame(discord_close_code::UNKNOWN_ERROR, "session expired"));
+            }
+            _ = heartbeat_check.tick() => {
+                if last_heartbeat.elapsed() > heartbeat_deadline {
+                    break Some(close_frame(close_code::HEARTBEAT_TIMEOUT, "session timed out"));
+                }
+            }
+            _ = presence_tick.tick() => {
+                if let Err(e) = presence.heartbeat(session_id).await {
+                    warn!("Discord gateway: presence heartbeat failed: {:?}", e);
+                }
+                match presence.get(session.user_id).await {
+                    Ok(p) => {
+                        if p.status != last_presence.status
+                            || p.custom_status != last_presence.custom_status
+                        {
+                            broadcast_presence_to_user_guilds(
+                                state,
+                                session.identity.clone(),
+                                session.user_id,
+                                &p,
+                            )
+                            .await;
+                        }
+                        last_presence = p;
+                    }
+                    Err(e) => warn!("Discord gateway: failed to refresh presence: {:?}", e),
+                }
+            }
+            _ = guild_refresh.tick() => session.refresh_guilds().await,
+        }
+    };
+
+    // Drop this session; the bot only goes offline if it was its last one.
+    match presence.disconnect(session.user_id, session_id).await {
+        Ok(p) => {
+            if p != last_presence {
+                broadcast_presence_to_user_guilds(
+                    state,
+                    session.identity.clone(),
+                    session.user_id,
+                    &p,
+                )
+                .await;
+            }
+        }
+        Err(e) => warn!(
+            "Discord gateway: failed to remove presence session: {:?}",
+            e
+        ),
+    }
+    session.guilds.clear();
+    close
+}
+
+impl Session {
+    fn wants(&self, intents: u64) -> bool {
+        self.intents & intents == intents
+    }
+
+    async fn dispatch(&mut self, event: &str, data: serde_json::Value) {
+        self.seq += 1;
+        let payload =
+            serde_json::json!({ "op": op::DISPATCH, "d": data, "s": self.seq, "t": event });
+        let _ = self
+            .out
+            .send(Message::Text(payload.to_string().into()))
+            .await;
+    }
+
+    async fn user_guilds(&self) -> Result<Vec<Guild>, DiscordError> {
+        Ok(self
+            .state
+            .guild_service
+            .get_user_guilds(self.identity.clone(), GuildUserId(Id(self.user_id)))
+            .await?)
+    }
+
+    async fn ready(&mut self, resume_gateway_url: String) -> Result<(), DiscordError> {
+        let user = current_user(&self.state, &self.identity).await?;
+        let guilds = self.user_guilds().await?;
+        let application_id = match &self.identity {
+            Identity::Bot(bot) => bot.application_id.parse::<Uuid>().ok(),
+            _ => None,
+        };
+
+        let ids = [user.id.0]
+            .into_iter()
+            .chain(application_id)
+            .chain(guilds.iter().map(|guild| *guild.id.get_uuid()));
+        let snowflakes = Snowflakes::load(&self.state, ids.collect::<Vec<_>>()).await?;
+        let unavailable: Vec<_> = guilds
+            .iter()
+            .map(|guild| {
+                serde_json::json!({
+                    "id": snowflakes.get(*guild.id.get_uuid()),
+                    "unavailable": true,
+                })
+            })
+            .collect();
+
+        self.dispatch(
+            "READY",
+            serde_json::json!({
+                "v": 10,
+                "user": DiscordUser::from_user(&snowflakes, &user),
+                "guilds": unavailable,
+                "session_id": Uuid::now_v7().simple().to_string(),
+                "resume_gateway_url": resume_gateway_url,
+                "shard": [0, 1],
+                "application": {
+                    "id": application_id.map(|id| snowflakes.get(id)),
+                    "flags": 0,
+                },
+                "private_channels": [],
+                "presences": [],
+                "relationships": [],
+                "guild_join_requests": [],
+            }),
+        )
+        .await;
+
+        for guild in guilds {
+            self.join_guild(guild).await;
+        }
+        Ok(())
+    }
+
+    /// Listens to a guild and its channels, and sends it whole.
+    async fn join_guild(&mut self, guild: Guild) {
+        let guild_id = *guild.id.get_uuid();
+        let channels = match self
+            .state
+            .channel_service
+            .get_guild_channels(self.identity.clone(), guild.id.clone())
+            .await
+        {
+            Ok(channels) => channels,
+            Err(e) => {
+                warn!(
+                    "Discord gateway: failed to list channels of {}: {}",
+                    guild_id, e
+                );
+                return;
+            }
+        };
+
+        let task = forward_room(
+            self.state
+                .hub
+                .subscribe(&format!("guild:{}", guild_id))
+                .await,
+            self.events.clone(),
+        );
+        let mut joined = JoinedGuild {
+            task,
+            channels: HashMap::new(),
+        };
+        for channel in &channels {
+            let channel_id = channel.id.get_uuid();
+            joined
+                .channels
+                .insert(channel_id, self.listen_channel(channel_id).await);
+        }
+        self.guilds.insert(guild_id, joined);
+
+        match self.guild_create(&guild, &channels).await {
+            Ok(data) => {
+                if self.wants(intent::GUILDS) {
+                    self.dispatch("GUILD_CREATE", data).await;
+                }
+            }
+            Err(e) => warn!("Discord gateway: failed to build GUILD_CREATE: {:?}", e),
+        }
+    }
+
+    async fn listen_channel(&self, channel_id: Uuid) -> JoinHandle<()> {
+        forward_room(
+            self.state
+                .hub
+                .subscribe(&format!("channel:{}", channel_id))
+                .await,
+            self.events.clone(),
+        )
+    }
+
+    async fn guild_create(
+        &self,
+        guild: &Guild,
+        channels: &[Channel],
+    ) -> Result<serde_json::Value, DiscordError> {
+        let guild_id = *guild.id.get_uuid();
+        let roles = guild_roles(&self.state, &self.identity, guild_id).await?;
+        let members = self.state.member_repository.list_members(&guild.id).await?;
+        let own = members.iter().find(|member| member.user_id == self.user_id);
+        // Without the members intent, only the bot's own member is sent.
+        let sent: Vec<&MemberWithUser> = if self.wants(intent::GUILD_MEMBERS) {
+            members.iter().take(MAX_MEMBERS).collect()
+        } else {
+            own.into_iter().collect()
+        };
+
+        let mut ids = DiscordGuild::ids(guild, &roles);
+        ids.extend(channels.iter().flat_map(DiscordChannel::ids));
+        ids.extend(sent.iter().flat_map(|member| DiscordMember::ids(member)));
+        let snowflakes = guild_snowflakes(&self.state, guild_id, &roles, ids).await?;
+
+        let mut data =
+            serde_json::to_value(DiscordGuild::new(&snowflakes, guild, &roles)).unwrap_or_default();
+        if let Some(object) = data.as_object_mut() {
+            let extra = serde_json::json!({
+                "joined_at": own.map(|member| member.joined_at).unwrap_or(guild.created_at),
+                "large": sent.len() < members.len(),
+                "unavailable": false,
+                "member_count": members.len(),
+                "members": sent
+                    .iter()
+                    .map(|member| DiscordMember::new(&snowflakes, member))
+                    .collect::<Vec<_>>(),
+                "channels": channels
+                    .iter()
+                    .map(|channel| DiscordChannel::new(&snowflakes, channel))
+                    .collect::<Vec<_>>(),
+                "threads": [],
+                "presences": [],
+                "voice_states": [],
+                "stage_instances": [],
+                "guild_scheduled_events": [],
+                "soundboard_sounds": [],
+            });
+            if let serde_json::Value::Object(extra) = extra {
+                object.extend(extra);
+            }
+        }
+        Ok(data)
+    }
+
+    /// Leaves a guild the bot is no longer in.
+    async fn leave_guild(&mut self, guild_id: Uuid) {
+        if self.guilds.remove(&guild_id).is_none() {
+            return;
+        }
+        if self.wants(intent::GUILDS)
+            && let Ok(snowflakes) = Snowflakes::load(&self.state, [guild_id]).await
+        {
+            self.dispatch(
+                "GUILD_DELETE",
+                serde_json::json!({ "id": snowflakes.get(guild_id) }),
+            )
+            .await;
+        }
+    }
+
+    /// Picks up joined and left guilds, then channels created, deleted or
+    /// hidden in the others.
+    async fn refresh_guilds(&mut self) {
+        let guilds = match self.user_guilds().await {
+            Ok(guilds) => guilds,
+            Err(e) => {
+                warn!("Discord gateway: failed to refresh guilds: {:?}", e);
+                return;
+            }
+        };
+        let left: Vec<Uuid> = self
+            .guilds
+            .keys()
+            .filter(|id| !guilds.iter().any(|guild| guild.id.get_uuid() == *id))
+            .copied()
+            .collect();
+        for guild_id in left {
+            self.leave_guild(guild_id).await;
+        }
+        for guild in guilds {
+            let guild_id = *guild.id.get_uuid();
+            if self.guilds.contains_key(&guild_id) {
+                self.refresh_channels(guild_id).await;
+            } else {
+                self.join_guild(guild).await;
+            }
+        }
+    }
+
+    /// Sends the channels of a guild that appeared or disappeared for the
+    /// bot since they were last listed.
+    async fn refresh_channels(&mut self, guild_id: Uuid) {
+        let channels = match self
+            .state
+            .channel_service
+            .get_guild_channels(self.identity.clone(), GuildId(Id(guild_id)))
+            .await
+        {
+            Ok(channels) => channels,
+            Err(e) => {
+                warn!(
+                    "Discord gateway: failed to refresh channels of {}: {}",
+                    guild_id, e
+                );
+                return;
+            }
+        };
+        let Some(joined) = self.guilds.get(&guild_id) else {
+            return;
+        };
+        let gone: Vec<Uuid> = joined
+            .channels
+            .keys()
+            .filter(|id| !channels.iter().any(|channel| channel.id.get_uuid() == **id))
+            .copied()
+            .collect();
+        let new: Vec<Channel> = channels
+            .into_iter()
+            .filter(|channel| !joined.channels.contains_key(&channel.id.get_uuid()))
+            .collect();
+
+        for channel_id in gone {
+            if let Some(task) = self
+                .guilds
+                .get_mut(&guild_id)
+                .and_then(|joined| joined.channels.remove(&channel_id))
+            {
+                task.abort();
+            }
+            if self.wants(intent::GUILDS)
+                && let Ok(snowflakes) = Snowflakes::load(&self.state, [channel_id, guild_id]).await
+            {
+                self.dispatch(
+                    "CHANNEL_DELETE",
+                    serde_json::json!({
+                        "id": snowflakes.get(channel_id),
+                        "guild_id": snowflakes.get(guild_id),
+                        "type": 0,
+                    }),
+                )
+                .await;
+            }
+        }
+        for channel in new {
+            let channel_id = channel.id.get_uuid();
+            let task = self.listen_channel(channel_id).await;
+            if let Some(joined) = self.guilds.get_mut(&guild_id) {
+                joined.channels.insert(channel_id, task);
+            }
+            self.send_channel("CHANNEL_CREATE", guild_id, channel).await;
+        }
+    }
+
+    async fn send_channel(&mut self, event: &str, guild_id: Uuid, channel: Channel) {
+        if !self.wants(intent::GUILDS) {
+            return;
+        }
+        match discord_channels(&self.state, &self.identity, guild_id, &[channel]).await {
+            Ok(mut channels) => {
+                if let Some(channel) = channels.pop() {
+                    self.dispatch(event, serde_json::json!(channel)).await;
+                }
+            }
+            Err(e) => warn!("Discord gateway: failed to convert channel: {:?}", e),
+        }
+    }
+
+    /// The guild a listened channel belongs to.
+    fn channel_guild(&self, channel_id: Uuid) -> Option<Uuid> {
+        self.guilds
+            .iter()
+            .find(|(_, joined)| joined.channels.contains_key(&channel_id))
+            .map(|(guild_id, _)| *guild_id)
+    }
+
+    async fn handle_event(&mut self, payload: &str) {
+        let Ok(event) = serde_json::from_str::<HubEvent>(payload) else {
+            return;
+        };
+        let Ok(ids) = serde_json::from_value::<EventIds>(event.data.clone()) else {
+            return;
+        };
+
+        if let Some(channel_id) = event
+            .room
+            .strip_prefix("channel:")
+            .and_then(|id| id.parse::<Uuid>().ok())
+        {
+            let Some(guild_id) = self.channel_guild(channel_id) else {
+                return;
+            };
+            match event.kind.as_str() {
+                "message.new" => {
+                    self.send_message("MESSAGE_CREATE", guild_id, channel_id, ids.id)
+                        .await
+                }
+                "message.update" => {
+                    self.send_message("MESSAGE_UPDATE", guild_id, channel_id, ids.id)
+                        .await
+                }
+                "message.delete" => {
+                    if let Some(message_id) = ids.message_id {
+                        self.send_message_delete(guild_id, channel_id, message_id)
+                            .await;
+                    }
+                }
+                "typing.update" => {
+                    if let Some(user_id) = ids.user_id
+                        && ids.is_typing == Some(true)
+                        && user_id != self.user_id
+                    {
+                        self.send_typing(guild_id, channel_id, user_id).await;
+                    }
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        let Some(guild_id) = event
+            .room
+            .strip_prefix("guild:")
+            .and_then(|id| id.parse::<Uuid>().ok())
+        else {
+            return;
+        };
+        match event.kind.as_str() {
+            "member.add" | "member.update" => {
+                let Some(user_id) = ids.user_id else {
+                    return;
+                };
+                // The bot's own roles decide which channels it sees.
+                if user_id == self.user_id {
+                    self.refresh_channels(guild_id).await;
+                }
+                let name = if event.kind == "member.add" {
+                    "GUILD_MEMBER_ADD"
+                } else {
+                    "GUILD_MEMBER_UPDATE"
+                };
+                self.send_member(name, guild_id, user_id).await;
+            }
+            "member.remove" => {
+                let Some(user_id) = ids.user_id else {
+                    return;
+                };
+                if user_id == self.user_id {
+                    self.leave_guild(guild_id).await;
+                } else {
+                    self.send_member_remove(guild_id, user_id).await;
+                }
+            }
+            "role.update" => {
+                if let Ok(role) = serde_json::from_value::<Role>(event.data) {
+                    self.send_role_update(guild_id, role).await;
+                }
+                self.refresh_channels(guild_id).await;
+            }
+            "role.delete" => {
+                if let Some(role_id) = ids.role_id
+                    && self.wants(intent::GUILDS)
+                    && let Ok(snowflakes) = Snowflakes::load(&self.state, [guild_id, role_id]).await
+                {
+                    self.dispatch(
+                        "GUILD_ROLE_DELETE",
+                        serde_json::json!({
+                            "guild_id": snowflakes.get(guild_id),
+                            "role_id": snowflakes.get(role_id),
+                        }),
+                    )
+                    .await;
+                }
+                self.refresh_channels(guild_id).await;
+            }
+            "channel.updated" => {
+                if let Some(channel_id) = ids.channel_id {
+                    self.channel_updated(guild_id, channel_id).await;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    async fn send_message(
+        &mut self,
+        event: &str,
+        guild_id: Uuid,
+        channel_id: Uuid,
+        message_id: Option<Uuid>,
+    ) {
+        let Some(message_id) = message_id else {
+            return;
+        };
+        if !self.wants(intent::GUILD_MESSAGES) {
+            return;
+        }
+        // The message is read again rather than taken from the event, which
+        // checks the bot may still view it.
+        let mut message = match self
+            .state
+            .message_service
+            .get_message(
+                self.identity.clone(),
+                GuildId(Id(guild_id)),
+                ChannelId(Id(channel_id)),
+                message_id,
+            )
+            .await
+        {
+            Ok(message) => message,
+            Err(e) => {
+                warn!(
+                    "Discord gateway: failed to load message {}: {}",
+                    message_id, e
+                );
+                return;
+            }
+        };
+        presign_attachments(&self.state, &mut message).await;
+        match discord_messages(&self.state, guild_id, &[message]).await {
+            Ok(mut messages) => {
+                if let Some(message) = messages.pop() {
+                    self.dispatch(event, serde_json::json!(message)).await;
+                }
+            }
+            Err(e) => warn!("Discord gateway: failed to convert message: {:?}", e),
+        }
+    }
+
+    async fn send_message_delete(&mut self, guild_id: Uuid, channel_id: Uuid, message_id: Uuid) {
+        if !self.wants(intent::GUILD_MESSAGES) {
+            return;
+        }
+        if let Ok(snowflakes) =
+            Snowflakes::load(&self.state, [guild_id, channel_id, message_id]).await
+        {
+            self.dispatch(
+                "MESSAGE_DELETE",
+                serde_json::json!({
+                    "id": snowflakes.get(message_id),
+                    "channel_id": snowflakes.get(channel_id),
+                    "guild_id": snowflakes.get(guild_id),
+                }),
+            )
+            .await;
+        }
+    }
+
+    async fn send_typing(&mut self, guild_id: Uuid, channel_id: Uuid, user_id: Uuid) {
+        if !self.wants(intent::GUILD_MESSAGE_TYPING) {
+            return;
+        }
+        if let Ok(snowflakes) = Snowflakes::load(&self.state, [guild_id, channel_id, user_id]).await
+        {
+            self.dispatch(
+                "TYPING_START",
+                serde_json::json!({
+                    "channel_id": snowflakes.get(channel_id),
+                    "guild_id": snowflakes.get(guild_id),
+                    "user_id": snowflakes.get(user_id),
+                    "timestamp": Utc::now().timestamp(),
+                }),
+            )
+            .await;
+        }
+    }
+
+    async fn send_member(&mut self, event: &str, guild_id: Uuid, user_id: Uuid) {
+        if !self.wants(intent::GUILD_MEMBERS) {
+            return;
+        }
+        let member = match self
+            .state
+            .member_repository
+            .list_members(&GuildId(Id(guild_id)))
+            .await
+        {
+            Ok(members) => members.into_iter().find(|member| member.user_id == user_id),
+            Err(e) => {
+                warn!(
+                    "Discord gateway: failed to list members of {}: {}",
+                    guild_id, e
+                );
+                return;
+            }
+        };
+        let Some(member) = member else {
+            return;
+        };
+        let mut ids = DiscordMember::ids(&member);
+        ids.push(guild_id);
+        if let Ok(snowflakes) = Snowflakes::load(&self.state, ids).await {
+            let mut data = serde_json::json!(DiscordMember::new(&snowflakes, &member));
+            data["guild_id"] = serde_json::json!(snowflakes.get(guild_id));
+            self.dispatch(event, data).await;
+        }
+    }
+
+    async fn send_member_remove(&mut self, guild_id: Uuid, user_id: Uuid) {
+        if !self.wants(intent::GUILD_MEMBERS) {
+            return;
+        }
+        let user = match self.state.user_service.get_profile(UserId(user_id)).await {
+            Ok(Some(user)) => user,
+            Ok(None) => return,
+            Err(e) => {
+                warn!("Discord gateway: failed to load user {}: {}", user_id, e);
+                return;
+            }
+        };
+        if let Ok(snowflakes) = Snowflakes::load(&self.state, [guild_id, user_id]).await {
+            self.dispatch(
+                "GUILD_MEMBER_REMOVE",
+                serde_json::json!({
+                    "guild_id": snowflakes.get(guild_id),
+                    "user": DiscordUser::from_user(&snowflakes, &user),
+                }),
+            )
+            .await;
+        }
+    }
+
+    async fn send_role_update(&mut self, guild_id: Uuid, role: Role) {
+        if !self.wants(intent::GUILDS) {
+            return;
+        }
+        if let Ok(snowflakes) = Snowflakes::load(&self.state, [guild_id, role.id.0.0]).await {
+            self.dispatch(
+                "GUILD_ROLE_UPDATE",
+                serde_json::json!({
+                    "guild_id": snowflakes.get(guild_id),
+                    "role": DiscordRole::new(&snowflakes, guild_id, &role),
+                }),
+            )
+            .await;
+        }
+    }
+
+    /// Sends an updated channel, or its creation or deletion when the update
+    /// changed whether the bot can view it.
+    async fn channel_updated(&mut self, guild_id: Uuid, channel_id: Uuid) {
+        let listened = self
+            .guilds
+            .get(&guild_id)
+            .is_some_and(|joined| joined.channels.contains_key(&channel_id));
+        let channel = self
+            .state
+            .channel_service
+            .get_channel(
+                self.identity.clone(),
+                GuildId(Id(guild_id)),
+                ChannelId(Id(channel_id)),
+            )
+            .await;
+        match channel {
+            Ok(channel) if listened => self.send_channel("CHANNEL_UPDATE", guild_id, channel).await,
+            // Hidden channels come and go with the channel list.
+            _ => self.refresh_channels(guild_id).await,
+        }
+    }
+
+    /// Answers a request for members of a guild, by user ids or by
+    /// username prefix, in a single chunk.
+    async fn request_guild_members(&mut self, request: RequestGuildMembers) {
+        let Ok(guild_id) =
+            resolve(&self.state, &request.guild_id, DiscordError::unknown_guild).await
+        else {
+            return;
+        };
+        if !self.guilds.contains_key(&guild_id) {
+            return;
+        }
+        let members = match self
+            .state
+            .member_repository
+            .list_members(&GuildId(Id(guild_id)))
+            .await
+        {
+            Ok(members) => members,
+            Err(e) => {
+                warn!(
+                    "Discord gateway: failed to list members of {}: {}",
+                    guild_id, e
+                );
+                return;
+            }
+        };
+
+        let requested: Option<Vec<u64>> = request.user_ids.map(|ids| match ids {
+            serde_json::Value::Array(ids) => ids.iter().filter_map(snowflake_value).collect(),
+            id => snowflake_value(&id).into_iter().collect(),
+        });
+        let limit = match request.limit {
+            0 => MAX_MEMBERS,
+            limit => limit.min(MAX_MEMBERS),
+        };
+
+        let mut ids: Vec<Uuid> = members.iter().map(|member| member.user_id).collect();
+        ids.push(guild_id);
+        let Ok(snowflakes) = Snowflakes::load(&self.state, ids).await else {
+            return;
+        };
+        let query = request.query.unwrap_or_default().to_lowercase();
+        let found: Vec<&MemberWithUser> = members
+            .iter()
+            .filter(|member| match &requested {
+                Some(requested) => requested.contains(&snowflakes.value(member.user_id)),
+                None => member.username.to_lowercase().starts_with(&query),
+            })
+            .take(limit)
+            .collect();
+
+        let mut ids: Vec<Uuid> = found
+            .iter()
+            .flat_map(|member| DiscordMember::ids(member))
+            .collect();
+        ids.push(guild_id);
+        let Ok(snowflakes) = Snowflakes::load(&self.state, ids).await else {
+            return;
+        };
+        let not_found: Vec<String> = requested
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|id| {
+                !found
+                    .iter()
+                    .any(|member| snowflakes.value(member.user_id) == *id)
+            })
+            .map(|id| id.to_string())
+            .collect();
+
+        self.dispatch(
+            "GUILD_MEMBERS_CHUNK",
+            serde_json::json!({
+                "guild_id": snowflakes.get(guild_id),
+                "members": found
+                    .iter()
+                    .map(|member| DiscordMember::new(&snowflakes, member))
+                    .collect::<Vec<_>>(),
+                "chunk_index": 0,
+                "chunk_count": 1,
+                "not_found": not_found,
+                "nonce": request.nonce,
+            }),
+        )
+        .await;
+    }
+}
+
+/// A snowflake sent as a string, or as a number by some libraries.
+fn snowflake_value(value: &serde_json::Value) -> Option<u64> {
+    match value {
+        serde_json::Value::String(id) => id.parse().ok(),
+        value => value.as_u64(),
+    }
+}
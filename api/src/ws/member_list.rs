@@ -0,0 +1,148 @@
+//! `member_list.subscribe`: a lazily synced, range-based member list for one
+//! guild. The client holds only the rows of its ranges; after the initial
+//! `sync` it receives insert/update/delete ops whenever a member joins or
+//! leaves, changes roles, or changes presence.
+
+use axum::extract::ws::Message;
+use ferriscord_core::guild::domain::member::list::{
+    MemberList, MemberListItem, MemberListOp, MemberListRange, diff_range, sync_range,
+};
+use ferriscord_error::ApiError;
+use tokio::sync::{mpsc, watch};
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+
+use crate::state::AppState;
+
+/// Most ranges a client may subscribe to per guild.
+pub const MAX_RANGES: usize = 5;
+/// Most rows in a single range.
+pub const MAX_RANGE_ROWS: usize = 100;
+
+/// Validates `[start, end]` pairs: inclusive, ordered, non-overlapping and
+/// within the row and range limits.
+pub fn parse_ranges(ranges: &[[usize; 2]]) -> Option<Vec<MemberListRange>> {
+    if ranges.is_empty() || ranges.len() > MAX_RANGES {
+        return None;
+    }
+
+    let mut parsed: Vec<MemberListRange> = Vec::with_capacity(ranges.len());
+    for &[start, end] in ranges {
+        if end < start || end - start >= MAX_RANGE_ROWS {
+            return None;
+        }
+        parsed.push(MemberListRange(start, end));
+    }
+
+    parsed.sort_by_key(|range| range.0);
+    if parsed.windows(2).any(|pair| pair[1].0 <= pair[0].1) {
+        return None;
+    }
+    Some(parsed)
+}
+
+pub struct MemberListSubscription {
+    ranges: watch::Sender<Vec<MemberListRange>>,
+    task: JoinHandle<()>,
+}
+
+impl MemberListSubscription {
+    /// Switches to new ranges; the client gets a `sync` op for each.
+    pub fn set_ranges(&self, ranges: Vec<MemberListRange>) {
+        let _ = self.ranges.send(ranges);
+    }
+}
+
+impl Drop for MemberListSubscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Sends the initial rows of `ranges` and keeps them up to date until the
+/// returned subscription is dropped. Only members of the guild may subscribe.
+pub async fn subscribe(
+    state: &AppState,
+    user_id: Uuid,
+    guild_id: Uuid,
+    ranges: Vec<MemberListRange>,
+    conn_tx: mpsc::Sender<Message>,
+) -> Result<MemberListSubscription, ApiError> {
+    let mut lists = state.member_lists.watch(state, guild_id).await?;
+    let mut list = lists.borrow_and_update().clone();
+
+    let is_member = list
+        .items
+        .iter()
+        .any(|item| matches!(item, MemberListItem::Member(m) if m.user_id == user_id));
+    if !is_member {
+        return Err(ApiError::Forbidden { message: "not a member of this guild".into() });
+    }
+
+    let ops = ranges.iter().map(|range| sync_range(&list.items, *range)).collect();
+    send_update(&conn_tx, guild_id, &list, ops).await;
+
+    let (ranges_tx, mut ranges_rx) = watch::channel(ranges);
+
+    let task = tokio::spawn(async move {
+        let mut ranges = ranges_rx.borrow_and_update().clone();
+
+        loop {
+            tokio::select! {
+                changed = lists.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    let new = lists.borrow_and_update().clone();
+
+                    let ops: Vec<MemberListOp> = ranges
+                        .iter()
+                        .flat_map(|range| diff_range(&list.items, &new.items, *range))
+                        .collect();
+                    let changed = !ops.is_empty()
+                        || new.groups != list.groups
+                        || new.online_count != list.online_count;
+                    list = new;
+
+                    if changed && !send_update(&conn_tx, guild_id, &list, ops).await {
+                        break;
+                    }
+                }
+                changed = ranges_rx.changed() => {
+                    if changed.is_err() {
+                        break;
+                    }
+                    ranges = ranges_rx.borrow_and_update().clone();
+                    let ops = ranges.iter().map(|range| sync_range(&list.items, *range)).collect();
+                    if !send_update(&conn_tx, guild_id, &list, ops).await {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+
+    Ok(MemberListSubscription { ranges: ranges_tx, task })
+}
+
+/// Sends a `member_list.update`; returns false once the connection is gone.
+async fn send_update(
+    conn_tx: &mpsc::Sender<Message>,
+    guild_id: Uuid,
+    list: &MemberList,
+    ops: Vec<MemberListOp>,
+) -> bool {
+    let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "member_list.update",
+        "data": {
+            "guild_id": guild_id,
+            "member_count": list.member_count,
+            "online_count": list.online_count,
+            "groups": list.groups,
+            "ops": ops,
+        },
+    })) else {
+        return true;
+    };
+    conn_tx.send(Message::Text(payload.into())).await.is_ok()
+}
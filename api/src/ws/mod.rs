@@ -1,12 +1,22 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use axum::extract::ws::{Message, WebSocket};
+use axum::extract::ws::{CloseFrame, Message, WebSocket};
+use chrono::{DateTime, Utc};
 use axum::extract::{Query, State, WebSocketUpgrade};
 use axum::http::StatusCode;
 use axum::response::IntoResponse;
-use ferriscord_auth::AuthRepository;
-use ferriscord_core::user::domain::user::ports::UserService;
+use ferriscord_auth::{AuthRepository, Claims, Identity};
+use ferriscord_core::guild::domain::application::ports::ApplicationService;
+use ferriscord_core::guild::domain::voice::ports::{JoinCallInput, JoinVoiceInput, VoiceService};
+use ferriscord_core::user::{
+    application::PresenceFerrisCordService,
+    domain::{presence::ports::PresenceService, user::ports::UserService},
+};
+use ferriscord_entities::{Id, channel::ChannelId, guild::GuildId};
+use ferriscord_entities::presence::Presence;
+use futures_util::stream::SplitStream;
 use futures_util::{SinkExt, StreamExt};
 use serde::Deserialize;
 use tokio::sync::{RwLock, broadcast, mpsc};
@@ -14,10 +24,44 @@ use tokio::task::JoinHandle;
 use tracing::{error, warn};
 use uuid::Uuid;
 
-use crate::presence::{PresenceStatus, PresenceStore};
+use self::member_list::MemberListSubscription;
+use crate::rate_limit::RateLimiter;
+use crate::presence::{PresenceStatus, broadcast_presence, presence_payload};
+use crate::read_state::ack_message;
 use crate::state::AppState;
+use crate::voice::{
+    ice_servers, publish_voice_state, relay_voice_signal, voice_left, voice_session_ended,
+};
+
+pub mod discord;
+mod member_list;
 
 const BROADCAST_CAPACITY: usize = 256;
+/// How often each connection heartbeats its presence session and checks for
+/// presence changes that happen without client input.
+const PRESENCE_TICK: Duration = Duration::from_secs(30);
+/// Bot tokens don't expire, so bot sessions end after this long unless the
+/// bot sends its token again through `auth.refresh`; a reset token thus
+/// ends them within a day.
+const BOT_SESSION_TTL: chrono::TimeDelta = chrono::TimeDelta::hours(24);
+/// How long to wait for a close frame to be flushed before dropping the socket.
+const CLOSE_FLUSH_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Close codes sent by the gateway. 4000-4999 are reserved for applications.
+pub mod close_code {
+    /// An inbound message exceeded the configured maximum size.
+    pub const MESSAGE_TOO_BIG: u16 = 1009;
+    /// The connection did not identify in time, or its first frame was not `identify`.
+    pub const NOT_AUTHENTICATED: u16 = 4003;
+    /// The identify or refresh token was rejected.
+    pub const AUTHENTICATION_FAILED: u16 = 4004;
+    /// The client sent more messages than the rate limit allows.
+    pub const RATE_LIMITED: u16 = 4008;
+    /// The client stopped sending heartbeats.
+    pub const HEARTBEAT_TIMEOUT: u16 = 4009;
+    /// The token expired without being refreshed through `auth.refresh`.
+    pub const TOKEN_EXPIRED: u16 = 4010;
+}
 
 // ─── Hub ─────────────────────────────────────────────────────────────────────
 
@@ -49,11 +93,19 @@ impl WsHub {
     }
 }
 
+// ─── Limits ──────────────────────────────────────────────────────────────────
+
+pub(crate) fn close_frame(code: u16, reason: &'static str) -> CloseFrame {
+    CloseFrame { code, reason: reason.into() }
+}
+
 // ─── Handler ─────────────────────────────────────────────────────────────────
 
 #[derive(Deserialize)]
 pub struct WsQuery {
-    pub token: String,
+    /// Optional: clients can authenticate with an `identify` frame instead,
+    /// which keeps the token out of URLs and proxy logs.
+    pub token: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -64,6 +116,74 @@ struct WsClientMsg {
     room: Option<String>,
     is_typing: Option<bool>,
     status: Option<PresenceStatus>,
+    guild_id: Option<Uuid>,
+    channel_id: Option<Uuid>,
+    message_id: Option<Uuid>,
+    token: Option<String>,
+    ranges: Option<Vec<[usize; 2]>>,
+    self_mute: Option<bool>,
+    self_deaf: Option<bool>,
+    target_user_id: Option<Uuid>,
+    signal: Option<serde_json::Value>,
+    #[cfg_attr(not(feature = "sfu"), allow(dead_code))]
+    sdp: Option<String>,
+}
+
+/// The authenticated user behind a gateway connection.
+pub(crate) struct WsAuth {
+    pub(crate) identity: Identity,
+    pub(crate) user_id: Uuid,
+    pub(crate) expires_at: DateTime<Utc>,
+}
+
+/// Resolves a token to its user, creating the user on first sight. Also
+/// used by the IRC gateway.
+pub(crate) async fn authenticate(state: &AppState, token: &str) -> Result<WsAuth, StatusCode> {
+    let (identity, expires_at) = validate(state, token).await.map_err(|e| {
+        warn!("WS: token rejected: {}", e);
+        StatusCode::UNAUTHORIZED
+    })?;
+
+    // Bot users are created with their application and never upserted.
+    let user = if identity.is_bot() {
+        state.user_service.get_me(identity.id()).await
+    } else {
+        state.user_service.upsert_by_sub(identity.id(), identity.username()).await.map(Some)
+    }
+    .map_err(|e| {
+        error!("WS: failed to load user: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?
+    .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    Ok(WsAuth { identity, user_id: user.id.0, expires_at })
+}
+
+/// Resolves a user access token or a `Bot <token>` to an identity and the
+/// time its session ends.
+async fn validate(state: &AppState, token: &str) -> Result<(Identity, DateTime<Utc>), String> {
+    if let Some(token) = token.strip_prefix("Bot ") {
+        let bot = state
+            .application_service
+            .authenticate_bot(token.trim())
+            .await
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "invalid bot token".to_string())?;
+        return Ok((Identity::Bot(bot), Utc::now() + BOT_SESSION_TTL));
+    }
+
+    let claims = state.auth.validate_token(token).await.map_err(|e| e.to_string())?;
+    // validate_token rejects tokens without an expiry, so `exp` is set here.
+    let expires_at = token_expires_at(&claims);
+    Ok((Identity::from(claims), expires_at))
+}
+
+fn token_expires_at(claims: &Claims) -> DateTime<Utc> {
+    claims.exp.and_then(|exp| DateTime::from_timestamp(exp, 0)).unwrap_or_else(Utc::now)
+}
+
+pub(crate) fn deadline_at(at: DateTime<Utc>) -> tokio::time::Instant {
+    tokio::time::Instant::now() + (at - Utc::now()).to_std().unwrap_or_default()
 }
 
 pub async fn ws_handler(
@@ -71,103 +191,273 @@ pub async fn ws_handler(
     Query(params): Query<WsQuery>,
     State(state): State<AppState>,
 ) -> Result<impl IntoResponse, StatusCode> {
-    let identity = state
-        .auth
-        .identify(&params.token)
-        .await
-        .map_err(|_| StatusCode::UNAUTHORIZED)?;
-
-    let user = state
-        .user_service
-        .upsert_by_sub(identity.id(), identity.username())
-        .await
-        .map_err(|e| {
-            error!("WS: failed to upsert user: {:?}", e);
-            StatusCode::INTERNAL_SERVER_ERROR
-        })?;
-
-    let user_id = user.id.0;
-    let user_room = format!("user:{}", user_id);
-    let hub = state.hub.clone();
-    let presence = state.presence.clone();
+    // A query-string token is checked before upgrading so the client gets a
+    // plain 401; without one the first frame must be `identify`.
+    let auth = match params.token {
+        Some(token) => Some(authenticate(&state, &token).await?),
+        None => None,
+    };
 
-    let username = identity.username().to_string();
+    // Hard cap enforced by the protocol layer; anything between the configured
+    // limit and this cap is rejected with a proper close frame instead.
+    let ws = ws.max_message_size(state.args.gateway.max_message_size.saturating_mul(4));
 
-    Ok(ws.on_upgrade(move |socket| {
-        handle_socket(socket, hub, user_room, presence, user_id, username)
-    }))
+    Ok(ws.on_upgrade(move |socket| handle_socket(socket, state, auth)))
 }
 
-async fn broadcast_presence_to_guilds(
-    hub: &WsHub,
-    user_id: Uuid,
-    status: &PresenceStatus,
-    room_tasks: &HashMap<String, JoinHandle<()>>,
-) {
-    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
-        "type": "presence.update",
-        "room": "",
-        "data": { "user_id": user_id, "status": status },
-    })) {
-        for room in room_tasks.keys() {
-            if room.starts_with("guild:") {
-                hub.publish(room, payload.clone()).await;
-            }
-        }
+/// Waits for the `identify` frame of a connection opened without a token.
+/// On failure, returns the close frame to send (if the socket is still open).
+async fn wait_for_identify(
+    state: &AppState,
+    ws_rx: &mut SplitStream<WebSocket>,
+    timeout: Duration,
+) -> Result<WsAuth, Option<CloseFrame>> {
+    let not_authenticated = || Some(close_frame(close_code::NOT_AUTHENTICATED, "not authenticated"));
+
+    let msg = match tokio::time::timeout(timeout, ws_rx.next()).await {
+        Ok(Some(Ok(msg))) => msg,
+        Ok(_) => return Err(None),
+        Err(_) => return Err(not_authenticated()),
+    };
+
+    let Message::Text(text) = msg else {
+        return Err(not_authenticated());
+    };
+    if text.len() > state.args.gateway.max_message_size {
+        return Err(Some(close_frame(close_code::MESSAGE_TOO_BIG, "message too big")));
     }
+
+    let token = serde_json::from_str::<WsClientMsg>(&text)
+        .ok()
+        .filter(|cmd| cmd.kind == "identify")
+        .and_then(|cmd| cmd.token)
+        .ok_or_else(not_authenticated)?;
+
+    authenticate(state, &token).await.map_err(|_| {
+        Some(close_frame(close_code::AUTHENTICATION_FAILED, "authentication failed"))
+    })
 }
 
-async fn handle_socket(
-    socket: WebSocket,
-    hub: WsHub,
-    user_room: String,
-    presence: PresenceStore,
-    user_id: Uuid,
-    username: String,
+/// Sends `frame` (if any) and gives it a chance to reach the client before the
+/// writer task is dropped.
+async fn close_connection(
+    conn_tx: &mpsc::Sender<Message>,
+    send_task: &mut JoinHandle<()>,
+    frame: Option<CloseFrame>,
 ) {
-    // Mark user as online
-    presence.set(user_id, PresenceStatus::Online).await;
-
-    let (mut ws_tx, mut ws_rx) = socket.split();
-    let (conn_tx, mut conn_rx) = mpsc::channel::<String>(256);
+    if let Some(frame) = frame {
+        let _ = conn_tx.send(Message::Close(Some(frame))).await;
+        let _ = tokio::time::timeout(CLOSE_FLUSH_TIMEOUT, &mut *send_task).await;
+    }
+    send_task.abort();
+}
 
-    // Auto-subscribe to the user's personal room
-    let mut user_rx = hub.subscribe(&user_room).await;
-    let conn_tx_user = conn_tx.clone();
+/// Forwards every message of a room to the connection until either side
+/// goes away.
+async fn forward_room(hub: &WsHub, room: String, conn_tx: mpsc::Sender<Message>) -> JoinHandle<()> {
+    let mut rx = hub.subscribe(&room).await;
     tokio::spawn(async move {
         loop {
-            match user_rx.recv().await {
+            match rx.recv().await {
                 Ok(msg) => {
-                    if conn_tx_user.send(msg).await.is_err() {
+                    if conn_tx.send(Message::Text(msg.into())).await.is_err() {
                         break;
                     }
                 }
                 Err(broadcast::error::RecvError::Closed) => break,
-                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    // We missed some messages — log it so it's visible, then
+                    // continue (the client will eventually refetch on
+                    // reconnect).
+                    warn!(room = %room, skipped, "broadcast lagged: {} messages dropped", skipped);
+                    continue;
+                }
             }
         }
-    });
+    })
+}
+
+/// Rooms that should hear about this connection's presence changes: the
+/// user's own room (other sessions) and every subscribed guild room.
+fn presence_rooms<'a>(
+    user_room: &'a String,
+    room_tasks: &'a HashMap<String, JoinHandle<()>>,
+) -> impl Iterator<Item = &'a String> {
+    std::iter::once(user_room).chain(room_tasks.keys().filter(|room| room.starts_with("guild:")))
+}
+
+async fn handle_socket(socket: WebSocket, state: AppState, auth: Option<WsAuth>) {
+    let hub = state.hub.clone();
+    let presence = state.presence_service.clone();
+    let gateway = state.args.gateway.clone();
+    let heartbeat_interval = Duration::from_millis(gateway.heartbeat_interval_ms);
+
+    let (mut ws_tx, mut ws_rx) = socket.split();
+    let (conn_tx, mut conn_rx) = mpsc::channel::<Message>(256);
 
     // Forward messages from conn_rx to the WebSocket
-    let send_task = tokio::spawn(async move {
+    let mut send_task = tokio::spawn(async move {
         while let Some(msg) = conn_rx.recv().await {
-            if ws_tx.send(Message::Text(msg.into())).await.is_err() {
+            let closing = matches!(msg, Message::Close(_));
+            if ws_tx.send(msg).await.is_err() || closing {
                 break;
             }
         }
     });
 
+    // Tell the client how often to heartbeat and which limits apply.
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "hello",
+        "data": {
+            "heartbeat_interval": gateway.heartbeat_interval_ms,
+            "max_message_size": gateway.max_message_size,
+            "rate_limit": {
+                "limit": gateway.rate_limit,
+                "window_secs": gateway.rate_limit_window_secs,
+            },
+        },
+    })) {
+        let _ = conn_tx.send(Message::Text(payload.into())).await;
+    }
+
+    // Connections opened without a token get one heartbeat interval to identify.
+    let auth = match auth {
+        Some(auth) => auth,
+        None => match wait_for_identify(&state, &mut ws_rx, heartbeat_interval).await {
+            Ok(auth) => auth,
+            Err(frame) => {
+                close_connection(&conn_tx, &mut send_task, frame).await;
+                return;
+            }
+        },
+    };
+    let WsAuth { mut identity, user_id, mut expires_at } = auth;
+    let user_room = format!("user:{}", user_id);
+    let username = identity.username().to_string();
+
+    // Each connection is its own presence session; the user stays online as
+    // long as any of their sessions (on any replica) is alive.
+    let session_id = Uuid::now_v7();
+    let mut last_presence = match presence.connect(user_id, session_id).await {
+        Ok(p) => p,
+        Err(e) => {
+            error!("WS: failed to register presence session: {:?}", e);
+            Presence::offline(user_id)
+        }
+    };
+
+    if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+        "type": "ready",
+        "data": {
+            "user_id": user_id,
+            "session_id": session_id,
+            "token_expires_at": expires_at,
+        },
+    })) {
+        let _ = conn_tx.send(Message::Text(payload.into())).await;
+    }
+
+    // Auto-subscribe to the user's personal room, and to the session room
+    // that carries messages meant for this connection only (voice signaling).
+    let user_task = forward_room(&hub, user_room.clone(), conn_tx.clone()).await;
+    let session_task = forward_room(&hub, format!("session:{}", session_id), conn_tx.clone()).await;
+
+    // The session ends when the token expires unless the client sends a
+    // fresh one with `auth.refresh` beforehand.
+    let token_expiry = tokio::time::sleep_until(deadline_at(expires_at));
+    tokio::pin!(token_expiry);
+
     // Track one forwarding task per room so we can cancel on unsubscribe
     // and avoid duplicate tasks if the client re-subscribes to the same room.
     let mut room_tasks: HashMap<String, JoinHandle<()>> = HashMap::new();
+    // Member list subscriptions by guild; dropping one stops its task.
+    let mut member_lists: HashMap<Uuid, MemberListSubscription> = HashMap::new();
+
+    let mut presence_tick = tokio::time::interval(PRESENCE_TICK);
+    presence_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    // A client is considered dead once it misses a heartbeat by more than half
+    // an interval; this also reaps half-open TCP connections.
+    let mut heartbeat_check = tokio::time::interval(heartbeat_interval);
+    heartbeat_check.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let heartbeat_deadline = heartbeat_interval + heartbeat_interval / 2;
+    let mut last_heartbeat = Instant::now();
+
+    let mut rate_limiter =
+        RateLimiter::new(gateway.rate_limit, Duration::from_secs(gateway.rate_limit_window_secs));
+    let mut close: Option<CloseFrame> = None;
 
     // Handle incoming messages from the client
-    while let Some(Ok(msg)) = ws_rx.next().await {
+    loop {
+        let msg = tokio::select! {
+            msg = ws_rx.next() => match msg {
+                Some(Ok(msg)) => msg,
+                _ => break,
+            },
+            _ = &mut token_expiry => {
+                close = Some(close_frame(close_code::TOKEN_EXPIRED, "token expired"));
+                break;
+            }
+            _ = heartbeat_check.tick() => {
+                if last_heartbeat.elapsed() > heartbeat_deadline {
+                    close = Some(close_frame(close_code::HEARTBEAT_TIMEOUT, "heartbeat timeout"));
+                    break;
+                }
+                continue;
+            }
+            _ = presence_tick.tick() => {
+                // Keep the session alive and pick up changes that happen
+                // without client input (auto-idle, custom status expiry,
+                // other sessions).
+                if let Err(e) = presence.heartbeat(session_id).await {
+                    warn!("WS: presence heartbeat failed: {:?}", e);
+                }
+                refresh_presence(&hub, &presence, &user_room, &room_tasks, &mut last_presence)
+                    .await;
+                continue;
+            }
+        };
+
+        if matches!(msg, Message::Text(_) | Message::Binary(_)) {
+            if !rate_limiter.check() {
+                close = Some(close_frame(close_code::RATE_LIMITED, "rate limited"));
+                break;
+            }
+            let size = match &msg {
+                Message::Text(text) => text.len(),
+                Message::Binary(data) => data.len(),
+                _ => 0,
+            };
+            if size > gateway.max_message_size {
+                close = Some(close_frame(close_code::MESSAGE_TOO_BIG, "message too big"));
+                break;
+            }
+        }
+
         match msg {
             Message::Text(text) => {
                 let Ok(cmd) = serde_json::from_str::<WsClientMsg>(&text) else {
                     continue;
                 };
+
+                // Anything but a keepalive or re-authentication counts as
+                // user activity.
+                if !matches!(cmd.kind.as_str(), "heartbeat" | "ping" | "auth.refresh") {
+                    if let Err(e) = presence.record_activity(session_id).await {
+                        warn!("WS: failed to record activity: {:?}", e);
+                    }
+                    if last_presence.status == PresenceStatus::Idle {
+                        refresh_presence(
+                            &hub,
+                            &presence,
+                            &user_room,
+                            &room_tasks,
+                            &mut last_presence,
+                        )
+                        .await;
+                    }
+                }
+
                 match cmd.kind.as_str() {
                     "subscribe" => {
                         for room in cmd.rooms.unwrap_or_default() {
@@ -175,44 +465,18 @@ async fn handle_socket(
                             if room_tasks.contains_key(&room) {
                                 continue;
                             }
+                            // Personal and session rooms are joined automatically
+                            // and never on request.
+                            if room.starts_with("user:") || room.starts_with("session:") {
+                                continue;
+                            }
                             // If subscribing to a guild room, announce presence
-                            if room.starts_with("guild:") {
-                                let current_status = presence.get(user_id).await;
-                                if let Ok(payload) = serde_json::to_string(&serde_json::json!({
-                                    "type": "presence.update",
-                                    "room": room,
-                                    "data": { "user_id": user_id, "status": current_status },
-                                })) {
-                                    hub.publish(&room, payload).await;
-                                }
+                            if room.starts_with("guild:")
+                                && let Some(payload) = presence_payload(&room, &last_presence)
+                            {
+                                hub.publish(&room, payload).await;
                             }
-                            let mut rx = hub.subscribe(&room).await;
-                            let tx = conn_tx.clone();
-                            let room_name = room.clone();
-                            let handle = tokio::spawn(async move {
-                                loop {
-                                    match rx.recv().await {
-                                        Ok(msg) => {
-                                            if tx.send(msg).await.is_err() {
-                                                break;
-                                            }
-                                        }
-                                        Err(broadcast::error::RecvError::Closed) => break,
-                                        Err(broadcast::error::RecvError::Lagged(skipped)) => {
-                                            // We missed some messages — log it so it's
-                                            // visible, then continue (the client will
-                                            // eventually refetch on reconnect).
-                                            warn!(
-                                                room = %room_name,
-                                                skipped,
-                                                "broadcast lagged: {} messages dropped",
-                                                skipped
-                                            );
-                                            continue;
-                                        }
-                                    }
-                                }
-                            });
+                            let handle = forward_room(&hub, room.clone(), conn_tx.clone()).await;
                             room_tasks.insert(room, handle);
                         }
                     }
@@ -223,21 +487,252 @@ async fn handle_socket(
                             }
                         }
                     }
-                    "ping" => {
-                        let _ = conn_tx.send(r#"{"type":"pong"}"#.to_string()).await;
+                    // `ping` predates the hello/heartbeat protocol and is kept
+                    // as an alias for older clients.
+                    "heartbeat" | "ping" => {
+                        last_heartbeat = Instant::now();
+                        if let Err(e) = presence.heartbeat(session_id).await {
+                            warn!("WS: presence heartbeat failed: {:?}", e);
+                        }
+                        let ack = if cmd.kind == "ping" {
+                            r#"{"type":"pong"}"#
+                        } else {
+                            r#"{"type":"heartbeat.ack"}"#
+                        };
+                        let _ = conn_tx.send(Message::Text(ack.into())).await;
+                    }
+                    "activity" => {}
+                    "auth.refresh" => {
+                        let Some(token) = cmd.token else {
+                            continue;
+                        };
+                        match validate(&state, &token).await {
+                            Ok((refreshed, refreshed_expiry)) if refreshed.id() == identity.id() => {
+                                expires_at = refreshed_expiry;
+                                token_expiry.as_mut().reset(deadline_at(expires_at));
+                                identity = refreshed;
+                                if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+                                    "type": "auth.refreshed",
+                                    "data": { "token_expires_at": expires_at },
+                                })) {
+                                    let _ = conn_tx.send(Message::Text(payload.into())).await;
+                                }
+                            }
+                            Ok(_) => {
+                                close = Some(close_frame(
+                                    close_code::AUTHENTICATION_FAILED,
+                                    "token belongs to another user",
+                                ));
+                                break;
+                            }
+                            Err(e) => {
+                                // Keep the current session; it still ends at the
+                                // old expiry if no valid token arrives.
+                                if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+                                    "type": "auth.refresh_failed",
+                                    "data": { "message": e },
+                                })) {
+                                    let _ = conn_tx.send(Message::Text(payload.into())).await;
+                                }
+                            }
+                        }
                     }
                     "presence.set" => {
-                        if let Some(new_status) = cmd.status {
-                            // Don't allow setting offline via this message
-                            let status = if new_status == PresenceStatus::Offline {
-                                PresenceStatus::Online
-                            } else {
-                                new_status
-                            };
-                            presence.set(user_id, status.clone()).await;
-                            broadcast_presence_to_guilds(&hub, user_id, &status, &room_tasks).await;
+                        let Some(status) = cmd.status else {
+                            continue;
+                        };
+                        match presence.set_status(user_id, session_id, status).await {
+                            Ok(p) => {
+                                if p != last_presence {
+                                    broadcast_presence(
+                                        &hub,
+                                        presence_rooms(&user_room, &room_tasks),
+                                        &p,
+                                    )
+                                    .await;
+                                }
+                                last_presence = p;
+                            }
+                            Err(e) => warn!("WS: failed to set presence: {:?}", e),
+                        }
+                    }
+                    "read_state.ack" => {
+                        let (Some(channel_id), Some(message_id)) = (cmd.channel_id, cmd.message_id)
+                        else {
+                            continue;
+                        };
+                        // The resulting read_state.update reaches every session
+                        // of the user through their personal room.
+                        if let Err(e) = ack_message(
+                            &state,
+                            user_id,
+                            cmd.guild_id,
+                            channel_id,
+                            message_id,
+                        )
+                        .await
+                        {
+                            warn!("WS: failed to ack message: {:?}", e);
                         }
                     }
+                    "member_list.subscribe" => {
+                        let Some(guild_id) = cmd.guild_id else {
+                            continue;
+                        };
+                        let Some(ranges) = member_list::parse_ranges(&cmd.ranges.unwrap_or_default())
+                        else {
+                            continue;
+                        };
+                        // Re-subscribing to a guild only moves the ranges.
+                        if let Some(subscription) = member_lists.get(&guild_id) {
+                            subscription.set_ranges(ranges);
+                            continue;
+                        }
+                        match member_list::subscribe(&state, user_id, guild_id, ranges, conn_tx.clone())
+                            .await
+                        {
+                            Ok(subscription) => {
+                                member_lists.insert(guild_id, subscription);
+                            }
+                            Err(e) => warn!("WS: member list subscription failed: {:?}", e),
+                        }
+                    }
+                    "member_list.unsubscribe" => {
+                        if let Some(guild_id) = cmd.guild_id {
+                            member_lists.remove(&guild_id);
+                        }
+                    }
+                    // A channel joins or moves the user; no channel leaves voice.
+                    "voice_state.update" => {
+                        let Some(channel_id) = cmd.channel_id else {
+                            match state.voice_service.leave(user_id).await {
+                                Ok(Some(left)) => voice_left(&state, &left).await,
+                                Ok(None) => {}
+                                Err(e) => warn!("WS: failed to leave voice: {:?}", e),
+                            }
+                            continue;
+                        };
+                        let self_mute = cmd.self_mute.unwrap_or(false);
+                        let self_deaf = cmd.self_deaf.unwrap_or(false);
+                        // Without a guild, the channel is a DM whose call the
+                        // user joined.
+                        let joined = match cmd.guild_id {
+                            Some(guild_id) => {
+                                let input = JoinVoiceInput {
+                                    guild_id: GuildId::from(guild_id),
+                                    channel_id: ChannelId(Id(channel_id)),
+                                    user_id,
+                                    session_id,
+                                    self_mute,
+                                    self_deaf,
+                                };
+                                state
+                                    .voice_service
+                                    .join(identity.clone(), input)
+                                    .await
+                                    .map_err(|e| e.to_string())
+                            }
+                            None => {
+                                let input = JoinCallInput {
+                                    channel_id,
+                                    user_id,
+                                    session_id,
+                                    self_mute,
+                                    self_deaf,
+                                };
+                                crate::call::connect(&state, input).await
+                            }
+                        };
+                        let guild_id = cmd.guild_id;
+                        match joined {
+                            Ok(join) => {
+                                if let Some(previous) = join.previous {
+                                    voice_left(&state, &previous).await;
+                                }
+                                publish_voice_state(&hub, &join.state, true).await;
+                                // Everything the client needs to set up its peer
+                                // connections for this channel.
+                                if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+                                    "type": "voice_server.update",
+                                    "data": {
+                                        "guild_id": guild_id,
+                                        "channel_id": channel_id,
+                                        "session_id": session_id,
+                                        "ice_servers": ice_servers(&state.args.server.ice),
+                                    },
+                                })) {
+                                    let _ = conn_tx.send(Message::Text(payload.into())).await;
+                                }
+                            }
+                            Err(message) => {
+                                if let Ok(payload) = serde_json::to_string(&serde_json::json!({
+                                    "type": "voice_state.update_failed",
+                                    "data": {
+                                        "guild_id": guild_id,
+                                        "channel_id": channel_id,
+                                        "message": message,
+                                    },
+                                })) {
+                                    let _ = conn_tx.send(Message::Text(payload.into())).await;
+                                }
+                            }
+                        }
+                    }
+                    "voice.signal" => {
+                        let (Some(channel_id), Some(target_user_id), Some(signal)) =
+                            (cmd.channel_id, cmd.target_user_id, cmd.signal)
+                        else {
+                            continue;
+                        };
+                        if let Err(e) = relay_voice_signal(
+                            &state,
+                            user_id,
+                            session_id,
+                            channel_id,
+                            target_user_id,
+                            signal,
+                        )
+                        .await
+                            && let Ok(payload) = serde_json::to_string(&serde_json::json!({
+                                "type": "voice.signal_failed",
+                                "data": {
+                                    "channel_id": channel_id,
+                                    "target_user_id": target_user_id,
+                                    "message": e.to_string(),
+                                },
+                            }))
+                        {
+                            let _ = conn_tx.send(Message::Text(payload.into())).await;
+                        }
+                    }
+                    #[cfg(feature = "sfu")]
+                    "voice.sfu.offer" => {
+                        let (Some(sfu), Some(channel_id), Some(sdp)) =
+                            (&state.sfu, cmd.channel_id, cmd.sdp)
+                        else {
+                            continue;
+                        };
+                        crate::sfu::offer(
+                            &state,
+                            sfu,
+                            identity.clone(),
+                            user_id,
+                            session_id,
+                            channel_id,
+                            sdp,
+                            &conn_tx,
+                        )
+                        .await;
+                    }
+                    #[cfg(feature = "sfu")]
+                    "voice.sfu.answer" => {
+                        let (Some(sfu), Some(channel_id), Some(sdp)) =
+                            (&state.sfu, cmd.channel_id, cmd.sdp)
+                        else {
+                            continue;
+                        };
+                        crate::sfu::answer(sfu, session_id, channel_id, sdp, &conn_tx).await;
+                    }
                     "typing.update" => {
                         let Some(room) = cmd.room else {
                             continue;
@@ -272,13 +767,46 @@ async fn handle_socket(
         }
     }
 
-    // Mark user as offline and notify guild rooms
-    presence.set(user_id, PresenceStatus::Offline).await;
-    broadcast_presence_to_guilds(&hub, user_id, &PresenceStatus::Offline, &room_tasks).await;
+    // Drop this session; the user only goes offline if it was their last one.
+    match presence.disconnect(user_id, session_id).await {
+        Ok(p) => {
+            if p != last_presence {
+                broadcast_presence(&hub, presence_rooms(&user_room, &room_tasks), &p).await;
+            }
+        }
+        Err(e) => warn!("WS: failed to remove presence session: {:?}", e),
+    }
+    voice_session_ended(&state, user_id).await;
+    #[cfg(feature = "sfu")]
+    crate::sfu::session_closed(&state, session_id).await;
 
     // Clean up all room tasks when the connection closes
     for (_, handle) in room_tasks {
         handle.abort();
     }
-    send_task.abort();
+    member_lists.clear();
+    user_task.abort();
+    session_task.abort();
+
+    close_connection(&conn_tx, &mut send_task, close).await;
+}
+
+/// Re-reads the aggregated presence and broadcasts it when it changed since
+/// the last broadcast from this connection.
+async fn refresh_presence(
+    hub: &WsHub,
+    presence: &PresenceFerrisCordService,
+    user_room: &String,
+    room_tasks: &HashMap<String, JoinHandle<()>>,
+    last_presence: &mut Presence,
+) {
+    match presence.get(last_presence.user_id).await {
+        Ok(p) => {
+            if p.status != last_presence.status || p.custom_status != last_presence.custom_status {
+                broadcast_presence(hub, presence_rooms(user_room, room_tasks), &p).await;
+            }
+            *last_presence = p;
+        }
+        Err(e) => warn!("WS: failed to refresh presence: {:?}", e),
+    }
 }
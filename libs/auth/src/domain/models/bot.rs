@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+/// A bot user, authenticated with its application's bot token rather than
+/// through the identity provider.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Bot {
+    /// Subject of the bot user, `bot:{application_id}`.
+    pub id: String,
+    pub application_id: String,
+    pub username: String,
+    pub roles: Vec<String>,
+}
+
+impl Bot {
+    pub const SUBJECT_PREFIX: &'static str = "bot:";
+
+    pub fn subject(application_id: impl std::fmt::Display) -> String {
+        format!("{}{}", Self::SUBJECT_PREFIX, application_id)
+    }
+}
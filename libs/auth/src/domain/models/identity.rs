@@ -1,11 +1,12 @@
 use serde::{Deserialize, Serialize};
 
-use crate::domain::models::{claims::Claims, client::Client, user::User};
+use crate::domain::models::{bot::Bot, claims::Claims, client::Client, user::User};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum Identity {
     User(User),
     Client(Client),
+    Bot(Bot),
 }
 
 impl Identity {
@@ -13,6 +14,7 @@ impl Identity {
         match self {
             Identity::User(u) => &u.id,
             Identity::Client(c) => &c.id,
+            Identity::Bot(b) => &b.id,
         }
     }
 
@@ -24,10 +26,15 @@ impl Identity {
         matches!(self, Identity::Client(_))
     }
 
+    pub fn is_bot(&self) -> bool {
+        matches!(self, Identity::Bot(_))
+    }
+
     pub fn username(&self) -> &str {
         match self {
             Identity::User(u) => &u.username,
             Identity::Client(c) => &c.client_id,
+            Identity::Bot(b) => &b.username,
         }
     }
 
@@ -35,6 +42,7 @@ impl Identity {
         match self {
             Identity::User(u) => &u.roles,
             Identity::Client(c) => &c.roles,
+            Identity::Bot(b) => &b.roles,
         }
     }
 
@@ -45,10 +53,10 @@ impl Identity {
 
 impl From<Claims> for Identity {
     fn from(claims: Claims) -> Self {
-        if claims.client_id.is_some() {
+        if let Some(client_id) = claims.client_id {
             Identity::Client(Client {
                 id: claims.sub.0,
-                client_id: claims.client_id.unwrap(),
+                client_id,
                 roles: Vec::new(),
                 scopes: Vec::new(),
             })
@@ -74,7 +82,6 @@ mod tests {
         Claims {
             sub: crate::domain::models::claims::Subject("user-123".to_string()),
             iss: "https://auth.ferriscord.com".to_string(),
-            aud: Some("ferriscord-api".to_string()),
             email: Some("john.doe@example.com".to_string()),
             email_verified: true,
             exp: None,
@@ -101,7 +108,6 @@ mod tests {
         Claims {
             sub: crate::domain::models::claims::Subject("service-123".to_string()),
             iss: "https://auth.ferriscord.com".to_string(),
-            aud: Some("ferriscord-api".to_string()),
             email: None,
             email_verified: false,
             name: None,
@@ -137,6 +143,7 @@ mod tests {
                 assert_eq!(user.name, Some("John Doe".to_string()));
             }
             Identity::Client(_) => panic!("Expected User, got Client"),
+            Identity::Bot(_) => panic!("Expected User, got Bot"),
         }
     }
 
@@ -151,6 +158,7 @@ mod tests {
                 assert_eq!(client.client_id, "ferriscord-bot");
             }
             Identity::User(_) => panic!("Expected Client, got User"),
+            Identity::Bot(_) => panic!("Expected Client, got Bot"),
         }
     }
 }
@@ -1,3 +1,4 @@
+pub(crate) mod bot;
 pub(crate) mod claims;
 pub(crate) mod client;
 pub(crate) mod errors;
@@ -5,6 +6,7 @@ pub(crate) mod identity;
 pub(crate) mod token;
 pub(crate) mod user;
 
+pub use bot::*;
 pub use claims::*;
 pub use client::*;
 pub use errors::*;
@@ -3,13 +3,37 @@ use sqlx::PgPool;
 
 use crate::guild::{
     domain::{
-        channel::ChannelServiceImpl, errors::CoreError, guild::GuildServiceImpl,
-        invite::InviteServiceImpl, message::MessageServiceImpl, role::RoleServiceImpl,
+        application::ApplicationServiceImpl, attachment_upload::AttachmentUploadServiceImpl, channel::ChannelServiceImpl,
+        channel_follow::ChannelFollowServiceImpl, discord_compat::DiscordCompatServiceImpl, emoji::EmojiServiceImpl, errors::CoreError,
+        event_subscription::EventSubscriptionServiceImpl, guild::GuildServiceImpl, guild_import::GuildImportServiceImpl,
+        interaction::InteractionServiceImpl, invite::InviteServiceImpl,
+        link_preview::LinkPreviewServiceImpl, message::MessageServiceImpl, poll::PollServiceImpl, role::RoleServiceImpl,
+        scheduled_event::ScheduledEventServiceImpl, scheduled_message::ScheduledMessageServiceImpl, stage::StageServiceImpl, voice::VoiceServiceImpl, webhook::WebhookServiceImpl,
     },
     infrastructure::{
-        channel::postgres::PostgresChannelRepository, guild::postgres::PostgresGuildRepository,
-        invite::postgres::PostgresInviteRepository, member::postgres::PostgresMemberRepository,
-        message::postgres::PostgresMessageRepository, role::postgres::PostgresRoleRepository,
+        application::postgres::PostgresApplicationRepository,
+        attachment_upload::postgres::PostgresAttachmentUploadRepository,
+        channel::postgres::PostgresChannelRepository,
+        channel_follow::postgres::PostgresChannelFollowRepository,
+        discord_compat::postgres::PostgresSnowflakeRepository,
+        emoji::postgres::PostgresEmojiRepository,
+        guild_import::{http::HttpAttachmentFetcher, postgres::PostgresGuildImportRepository},
+        event_subscription::{
+            http::HttpEventSender, postgres::PostgresEventSubscriptionRepository,
+        },
+        guild::postgres::PostgresGuildRepository,
+        interaction::{http::HttpInteractionSender, postgres::PostgresInteractionRepository},
+        invite::postgres::PostgresInviteRepository,
+        link_preview::{http::HttpLinkPreviewFetcher, postgres::PostgresLinkPreviewRepository},
+        member::postgres::PostgresMemberRepository,
+        message::postgres::PostgresMessageRepository,
+        poll::postgres::PostgresPollRepository,
+        role::postgres::PostgresRoleRepository,
+        scheduled_event::postgres::PostgresScheduledEventRepository,
+        scheduled_message::postgres::PostgresScheduledMessageRepository,
+        stage::postgres::PostgresStageInstanceRepository,
+        voice::postgres::PostgresVoiceStateRepository,
+        webhook::postgres::PostgresWebhookRepository,
     },
 };
 
@@ -32,11 +56,136 @@ pub type MessageFerrisCordService = MessageServiceImpl<
     PostgresRoleRepository,
     PostgresMemberRepository,
     PostgresChannelRepository,
+    PostgresEmojiRepository,
+    PostgresPollRepository,
 >;
 
 pub type InviteFerrisCordService =
     InviteServiceImpl<PostgresInviteRepository, PostgresGuildRepository, PostgresMemberRepository>;
 
+pub type VoiceFerrisCordService = VoiceServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresVoiceStateRepository,
+>;
+
+pub type StageFerrisCordService = StageServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresVoiceStateRepository,
+    PostgresStageInstanceRepository,
+>;
+
+pub type WebhookFerrisCordService = WebhookServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresMessageRepository,
+    PostgresWebhookRepository,
+>;
+
+pub type ChannelFollowFerrisCordService = ChannelFollowServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresMessageRepository,
+    PostgresChannelFollowRepository,
+>;
+
+pub type EmojiFerrisCordService = EmojiServiceImpl<
+    PostgresGuildRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresEmojiRepository,
+>;
+
+pub type EventSubscriptionFerrisCordService = EventSubscriptionServiceImpl<
+    PostgresGuildRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresEventSubscriptionRepository,
+    HttpEventSender,
+>;
+
+pub type ApplicationFerrisCordService = ApplicationServiceImpl<
+    PostgresGuildRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresApplicationRepository,
+>;
+
+pub type InteractionFerrisCordService = InteractionServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresMessageRepository,
+    PostgresApplicationRepository,
+    PostgresInteractionRepository,
+    HttpInteractionSender,
+>;
+
+pub type LinkPreviewFerrisCordService = LinkPreviewServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresMessageRepository,
+    PostgresLinkPreviewRepository,
+    HttpLinkPreviewFetcher,
+>;
+
+pub type DiscordCompatFerrisCordService =
+    DiscordCompatServiceImpl<PostgresChannelRepository, PostgresSnowflakeRepository>;
+
+pub type ScheduledMessageFerrisCordService = ScheduledMessageServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresMessageRepository,
+    PostgresEmojiRepository,
+    PostgresScheduledMessageRepository,
+>;
+
+pub type AttachmentUploadFerrisCordService = AttachmentUploadServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresAttachmentUploadRepository,
+>;
+
+pub type GuildImportFerrisCordService = GuildImportServiceImpl<
+    PostgresGuildRepository,
+    PostgresGuildImportRepository,
+    HttpAttachmentFetcher,
+>;
+
+pub type ScheduledEventFerrisCordService = ScheduledEventServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresStageInstanceRepository,
+    PostgresScheduledEventRepository,
+>;
+
+pub type PollFerrisCordService = PollServiceImpl<
+    PostgresGuildRepository,
+    PostgresChannelRepository,
+    PostgresRoleRepository,
+    PostgresMemberRepository,
+    PostgresMessageRepository,
+    PostgresPollRepository,
+>;
+
 pub type MemberFerrisCordRepository = PostgresMemberRepository;
 
 pub fn create_guild_services(
@@ -82,6 +231,8 @@ pub fn create_guild_services(
             role_repository: role_repo.clone(),
             member_repository: member_repo.clone(),
             channel_repository: channel_repo.clone(),
+            emoji_repository: PostgresEmojiRepository::new(pool.clone()),
+            poll_repository: PostgresPollRepository::new(pool.clone()),
         },
         InviteServiceImpl {
             invite_repository: invite_repo,
@@ -91,6 +242,170 @@ pub fn create_guild_services(
     ))
 }
 
+pub fn create_voice_service(pool: PgPool) -> VoiceFerrisCordService {
+    VoiceServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        voice_state_repository: PostgresVoiceStateRepository::new(pool),
+    }
+}
+
+pub fn create_stage_service(pool: PgPool) -> StageFerrisCordService {
+    StageServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        voice_state_repository: PostgresVoiceStateRepository::new(pool.clone()),
+        stage_instance_repository: PostgresStageInstanceRepository::new(pool),
+    }
+}
+
+pub fn create_webhook_service(pool: PgPool) -> WebhookFerrisCordService {
+    WebhookServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        message_repository: PostgresMessageRepository::new(pool.clone()),
+        webhook_repository: PostgresWebhookRepository::new(pool),
+    }
+}
+
+pub fn create_channel_follow_service(pool: PgPool) -> ChannelFollowFerrisCordService {
+    ChannelFollowServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        message_repository: PostgresMessageRepository::new(pool.clone()),
+        follow_repository: PostgresChannelFollowRepository::new(pool),
+    }
+}
+
+pub fn create_emoji_service(pool: PgPool) -> EmojiFerrisCordService {
+    EmojiServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        emoji_repository: PostgresEmojiRepository::new(pool),
+    }
+}
+
+/// `allowed_hosts` may be reached by deliveries even when they are not public.
+pub fn create_event_subscription_service(
+    pool: PgPool,
+    allowed_hosts: Vec<String>,
+) -> EventSubscriptionFerrisCordService {
+    EventSubscriptionServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        event_subscription_repository: PostgresEventSubscriptionRepository::new(pool),
+        event_sender: HttpEventSender::with_allowed_hosts(allowed_hosts),
+    }
+}
+
+pub fn create_application_service(pool: PgPool) -> ApplicationFerrisCordService {
+    ApplicationServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        application_repository: PostgresApplicationRepository::new(pool),
+    }
+}
+
+/// `allowed_hosts` may be reached by interactions even when they are not
+/// public.
+pub fn create_interaction_service(
+    pool: PgPool,
+    allowed_hosts: Vec<String>,
+) -> InteractionFerrisCordService {
+    InteractionServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        message_repository: PostgresMessageRepository::new(pool.clone()),
+        application_repository: PostgresApplicationRepository::new(pool.clone()),
+        interaction_repository: PostgresInteractionRepository::new(pool),
+        interaction_sender: HttpInteractionSender::with_allowed_hosts(allowed_hosts),
+    }
+}
+
+pub fn create_link_preview_service(pool: PgPool) -> LinkPreviewFerrisCordService {
+    LinkPreviewServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        message_repository: PostgresMessageRepository::new(pool.clone()),
+        preview_repository: PostgresLinkPreviewRepository::new(pool),
+        fetcher: HttpLinkPreviewFetcher::new(),
+    }
+}
+
+pub fn create_discord_compat_service(pool: PgPool) -> DiscordCompatFerrisCordService {
+    DiscordCompatServiceImpl {
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        snowflake_repository: PostgresSnowflakeRepository::new(pool),
+    }
+}
+
+pub fn create_scheduled_message_service(pool: PgPool) -> ScheduledMessageFerrisCordService {
+    ScheduledMessageServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        message_repository: PostgresMessageRepository::new(pool.clone()),
+        emoji_repository: PostgresEmojiRepository::new(pool.clone()),
+        scheduled_repository: PostgresScheduledMessageRepository::new(pool),
+    }
+}
+
+pub fn create_poll_service(pool: PgPool) -> PollFerrisCordService {
+    PollServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        message_repository: PostgresMessageRepository::new(pool.clone()),
+        poll_repository: PostgresPollRepository::new(pool),
+    }
+}
+
+pub fn create_scheduled_event_service(pool: PgPool) -> ScheduledEventFerrisCordService {
+    ScheduledEventServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        stage_instance_repository: PostgresStageInstanceRepository::new(pool.clone()),
+        event_repository: PostgresScheduledEventRepository::new(pool),
+    }
+}
+
+pub fn create_guild_import_service(pool: PgPool) -> GuildImportFerrisCordService {
+    GuildImportServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        import_repository: PostgresGuildImportRepository::new(pool),
+        fetcher: HttpAttachmentFetcher::new(),
+    }
+}
+
+pub fn create_attachment_upload_service(pool: PgPool) -> AttachmentUploadFerrisCordService {
+    AttachmentUploadServiceImpl {
+        guild_repository: PostgresGuildRepository::new(pool.clone()),
+        channel_repository: PostgresChannelRepository::new(pool.clone()),
+        role_repository: PostgresRoleRepository::new(pool.clone()),
+        member_repository: PostgresMemberRepository::new(pool.clone()),
+        upload_repository: PostgresAttachmentUploadRepository::new(pool),
+    }
+}
+
 pub fn create_auth_repository(issuer: impl Into<String>) -> FerriskeyAuthRepository {
     FerriskeyAuthRepository::new(issuer.into(), None)
 }
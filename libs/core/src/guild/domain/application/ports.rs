@@ -0,0 +1,179 @@
+use ferriscord_auth::{Bot, Identity};
+use ferriscord_entities::{
+    application::{Application, GuildBot},
+    guild::GuildId,
+};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+pub const MAX_APPLICATIONS_PER_USER: i64 = 25;
+pub const MIN_APPLICATION_NAME_LEN: usize = 2;
+pub const MAX_APPLICATION_NAME_LEN: usize = 32;
+pub const MAX_APPLICATION_DESCRIPTION_LEN: usize = 400;
+
+pub struct CreateApplicationInput {
+    pub name: String,
+    pub description: Option<String>,
+}
+
+/// `None` leaves a field unchanged; an empty description clears it.
+pub struct UpdateApplicationInput {
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+pub trait ApplicationRepository: Send + Sync {
+    /// Creates the application and its bot user.
+    fn insert(
+        &self,
+        application: &Application,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Application, CoreError>> + Send;
+
+    fn find_by_id(
+        &self,
+        application_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Application>, CoreError>> + Send;
+
+    fn find_by_token(
+        &self,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<Application>, CoreError>> + Send;
+
+    fn list_by_owner(
+        &self,
+        owner_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Application>, CoreError>> + Send;
+
+    fn count_by_owner(&self, owner_id: Uuid)
+    -> impl Future<Output = Result<i64, CoreError>> + Send;
+
+    /// Renaming an application renames its bot user.
+    fn update(
+        &self,
+        application_id: Uuid,
+        name: Option<String>,
+        description: Option<Option<String>>,
+    ) -> impl Future<Output = Result<Option<Application>, CoreError>> + Send;
+
+    fn set_token(
+        &self,
+        application_id: Uuid,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<Application>, CoreError>> + Send;
+
+    /// Sets or clears the interactions endpoint. `secret` is only stored if
+    /// the application has none yet.
+    fn set_interactions_endpoint(
+        &self,
+        application_id: Uuid,
+        url: Option<String>,
+        secret: Option<String>,
+    ) -> impl Future<Output = Result<Option<Application>, CoreError>> + Send;
+
+    /// Deletes the application together with its bot user.
+    fn delete(&self, application_id: Uuid) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Makes the bot a member of the guild, holding `role_id` if given.
+    fn add_to_guild(
+        &self,
+        guild_id: Uuid,
+        application: &Application,
+        role_id: Option<Uuid>,
+        added_by: Uuid,
+    ) -> impl Future<Output = Result<GuildBot, CoreError>> + Send;
+
+    fn find_guild_bot(
+        &self,
+        guild_id: Uuid,
+        application_id: Uuid,
+    ) -> impl Future<Output = Result<Option<GuildBot>, CoreError>> + Send;
+
+    fn list_guild_bots(
+        &self,
+        guild_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<GuildBot>, CoreError>> + Send;
+
+    /// Removes the bot's membership and the role created for it.
+    fn remove_from_guild(
+        &self,
+        guild_id: Uuid,
+        application_id: Uuid,
+    ) -> impl Future<Output = Result<Option<GuildBot>, CoreError>> + Send;
+}
+
+/// Applications are managed by their owner only. Bots cannot own
+/// applications.
+pub trait ApplicationService: Send + Sync {
+    /// Returns the application with its bot token, which is not shown again.
+    fn create_application(
+        &self,
+        identity: Identity,
+        owner_id: Uuid,
+        input: CreateApplicationInput,
+    ) -> impl Future<Output = Result<Application, CoreError>> + Send;
+
+    fn list_applications(
+        &self,
+        owner_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Application>, CoreError>> + Send;
+
+    fn get_application(
+        &self,
+        owner_id: Uuid,
+        application_id: Uuid,
+    ) -> impl Future<Output = Result<Application, CoreError>> + Send;
+
+    fn update_application(
+        &self,
+        owner_id: Uuid,
+        application_id: Uuid,
+        input: UpdateApplicationInput,
+    ) -> impl Future<Output = Result<Application, CoreError>> + Send;
+
+    /// Issues a new bot token; the previous one stops working.
+    fn reset_bot_token(
+        &self,
+        owner_id: Uuid,
+        application_id: Uuid,
+    ) -> impl Future<Output = Result<Application, CoreError>> + Send;
+
+    fn delete_application(
+        &self,
+        owner_id: Uuid,
+        application_id: Uuid,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Resolves a bot token to the identity of its bot user.
+    fn authenticate_bot(
+        &self,
+        token: &str,
+    ) -> impl Future<Output = Result<Option<Bot>, CoreError>> + Send;
+
+    /// Adds a bot to a guild. The requested permissions are granted through a
+    /// role named after the bot and must be a subset of the caller's own.
+    /// Requires MANAGE_GUILD.
+    fn add_bot(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        application_id: Uuid,
+        permissions: u64,
+    ) -> impl Future<Output = Result<GuildBot, CoreError>> + Send;
+
+    fn list_guild_bots(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+    ) -> impl Future<Output = Result<Vec<GuildBot>, CoreError>> + Send;
+
+    /// Removes a bot from a guild. Requires MANAGE_GUILD.
+    fn remove_bot(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        application_id: Uuid,
+    ) -> impl Future<Output = Result<GuildBot, CoreError>> + Send;
+}
@@ -0,0 +1,343 @@
+use chrono::Utc;
+use ferriscord_auth::{Bot, Identity};
+use ferriscord_entities::{
+    application::{Application, GuildBot},
+    guild::GuildId,
+};
+use ferriscord_permission::{Permissions, require_permission};
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    common::build_permission_context, errors::CoreError, guild::ports::GuildPort,
+    member::ports::MemberRepository, role::ports::RoleRepository,
+};
+
+use super::ports::{
+    ApplicationRepository, ApplicationService, CreateApplicationInput,
+    MAX_APPLICATION_DESCRIPTION_LEN, MAX_APPLICATION_NAME_LEN, MAX_APPLICATIONS_PER_USER,
+    MIN_APPLICATION_NAME_LEN, UpdateApplicationInput,
+};
+
+const TOKEN_LEN: usize = 72;
+
+#[derive(Clone)]
+pub struct ApplicationServiceImpl<G, R, M, A>
+where
+    G: GuildPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    A: ApplicationRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) application_repository: A,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidApplication {
+        message: message.into(),
+    }
+}
+
+fn new_token() -> (String, String) {
+    let token = Alphanumeric.sample_string(&mut rand::thread_rng(), TOKEN_LEN);
+    let hash = hash_token(&token);
+    (token, hash)
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// The name doubles as the bot's username, so it follows the same rules.
+fn validate_name(name: String) -> Result<String, CoreError> {
+    let name = name.trim().to_string();
+    let len = name.chars().count();
+    if !(MIN_APPLICATION_NAME_LEN..=MAX_APPLICATION_NAME_LEN).contains(&len) {
+        return Err(invalid(format!(
+            "name must be {MIN_APPLICATION_NAME_LEN} to {MAX_APPLICATION_NAME_LEN} characters"
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'))
+    {
+        return Err(invalid(
+            "name may only contain letters, digits, '_', '.' and '-'",
+        ));
+    }
+    Ok(name)
+}
+
+fn validate_description(description: String) -> Result<Option<String>, CoreError> {
+    let description = description.trim().to_string();
+    if description.chars().count() > MAX_APPLICATION_DESCRIPTION_LEN {
+        return Err(invalid(format!(
+            "description must be at most {MAX_APPLICATION_DESCRIPTION_LEN} characters"
+        )));
+    }
+    Ok(Some(description).filter(|d| !d.is_empty()))
+}
+
+impl<G, R, M, A> ApplicationServiceImpl<G, R, M, A>
+where
+    G: GuildPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    A: ApplicationRepository,
+{
+    async fn owned_application(
+        &self,
+        owner_id: Uuid,
+        application_id: Uuid,
+    ) -> Result<Application, CoreError> {
+        self.application_repository
+            .find_by_id(application_id)
+            .await?
+            .filter(|a| a.owner_id == owner_id)
+            .ok_or(CoreError::ApplicationNotFound)
+    }
+
+    /// Checks MANAGE_GUILD and returns the caller's permissions in the guild.
+    async fn require_manage_guild(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+    ) -> Result<Permissions, CoreError> {
+        let mut permission_context = build_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            identity,
+            guild_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, Permissions::MANAGE_GUILD);
+        Ok(permission_context.get_permissions())
+    }
+}
+
+impl<G, R, M, A> ApplicationService for ApplicationServiceImpl<G, R, M, A>
+where
+    G: GuildPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    A: ApplicationRepository,
+{
+    async fn create_application(
+        &self,
+        identity: Identity,
+        owner_id: Uuid,
+        input: CreateApplicationInput,
+    ) -> Result<Application, CoreError> {
+        if identity.is_bot() {
+            return Err(CoreError::InsufficientPermissions);
+        }
+        if self.application_repository.count_by_owner(owner_id).await? >= MAX_APPLICATIONS_PER_USER
+        {
+            return Err(CoreError::MaxApplicationsReached {
+                max_applications: MAX_APPLICATIONS_PER_USER,
+            });
+        }
+
+        let (token, token_hash) = new_token();
+        let application = Application {
+            id: Uuid::now_v7(),
+            name: validate_name(input.name)?,
+            description: match input.description {
+                Some(description) => validate_description(description)?,
+                None => None,
+            },
+            owner_id,
+            bot_user_id: Uuid::now_v7(),
+            created_at: Utc::now(),
+            interactions_endpoint_url: None,
+            interactions_secret: None,
+            bot_token: None,
+        };
+
+        let application = self
+            .application_repository
+            .insert(&application, &token_hash)
+            .await?;
+        Ok(Application {
+            bot_token: Some(token),
+            ..application
+        })
+    }
+
+    async fn list_applications(&self, owner_id: Uuid) -> Result<Vec<Application>, CoreError> {
+        self.application_repository.list_by_owner(owner_id).await
+    }
+
+    async fn get_application(
+        &self,
+        owner_id: Uuid,
+        application_id: Uuid,
+    ) -> Result<Application, CoreError> {
+        self.owned_application(owner_id, application_id).await
+    }
+
+    async fn update_application(
+        &self,
+        owner_id: Uuid,
+        application_id: Uuid,
+        input: UpdateApplicationInput,
+    ) -> Result<Application, CoreError> {
+        self.owned_application(owner_id, application_id).await?;
+
+        let name = input.name.map(validate_name).transpose()?;
+        let description = input.description.map(validate_description).transpose()?;
+
+        self.application_repository
+            .update(application_id, name, description)
+            .await?
+            .ok_or(CoreError::ApplicationNotFound)
+    }
+
+    async fn reset_bot_token(
+        &self,
+        owner_id: Uuid,
+        application_id: Uuid,
+    ) -> Result<Application, CoreError> {
+        self.owned_application(owner_id, application_id).await?;
+
+        let (token, token_hash) = new_token();
+        let application = self
+            .application_repository
+            .set_token(application_id, &token_hash)
+            .await?
+            .ok_or(CoreError::ApplicationNotFound)?;
+        Ok(Application {
+            bot_token: Some(token),
+            ..application
+        })
+    }
+
+    async fn delete_application(
+        &self,
+        owner_id: Uuid,
+        application_id: Uuid,
+    ) -> Result<(), CoreError> {
+        self.owned_application(owner_id, application_id).await?;
+
+        if !self.application_repository.delete(application_id).await? {
+            return Err(CoreError::ApplicationNotFound);
+        }
+        Ok(())
+    }
+
+    async fn authenticate_bot(&self, token: &str) -> Result<Option<Bot>, CoreError> {
+        let application = self
+            .application_repository
+            .find_by_token(&hash_token(token))
+            .await?;
+
+        Ok(application.map(|application| Bot {
+            id: Bot::subject(application.id),
+            application_id: application.id.to_string(),
+            username: application.name,
+            roles: Vec::new(),
+        }))
+    }
+
+    async fn add_bot(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        application_id: Uuid,
+        permissions: u64,
+    ) -> Result<GuildBot, CoreError> {
+        let permissions = Permissions::from_bits(permissions)
+            .ok_or_else(|| invalid("unknown permission bits"))?;
+        let granted = self.require_manage_guild(&identity, &guild_id).await?;
+        if !granted.contains(Permissions::ADMINISTRATOR) && !granted.contains(permissions) {
+            return Err(CoreError::InsufficientPermissions);
+        }
+
+        let application = self
+            .application_repository
+            .find_by_id(application_id)
+            .await?
+            .ok_or(CoreError::ApplicationNotFound)?;
+
+        let guild = *guild_id.get_uuid();
+        if self
+            .application_repository
+            .find_guild_bot(guild, application_id)
+            .await?
+            .is_some()
+        {
+            return Err(invalid("the bot is already in this guild"));
+        }
+
+        let role_id = if permissions.is_empty() {
+            None
+        } else {
+            let role = self
+                .role_repository
+                .insert(&application.name, 0, permissions.bits(), &guild_id)
+                .await?;
+            Some(role.id.0.get_uuid())
+        };
+
+        self.application_repository
+            .add_to_guild(guild, &application, role_id, user_id)
+            .await
+    }
+
+    async fn list_guild_bots(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+    ) -> Result<Vec<GuildBot>, CoreError> {
+        self.require_manage_guild(&identity, &guild_id).await?;
+        self.application_repository
+            .list_guild_bots(*guild_id.get_uuid())
+            .await
+    }
+
+    async fn remove_bot(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        application_id: Uuid,
+    ) -> Result<GuildBot, CoreError> {
+        self.require_manage_guild(&identity, &guild_id).await?;
+        self.application_repository
+            .remove_from_guild(*guild_id.get_uuid(), application_id)
+            .await?
+            .ok_or(CoreError::ApplicationNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_application_fields() {
+        assert_eq!(
+            validate_name("  ferris-bot ".to_string()).unwrap(),
+            "ferris-bot"
+        );
+        assert!(validate_name("x".to_string()).is_err());
+        assert!(validate_name("ferris bot".to_string()).is_err());
+        assert!(validate_name("a".repeat(MAX_APPLICATION_NAME_LEN + 1)).is_err());
+
+        assert_eq!(validate_description("  ".to_string()).unwrap(), None);
+        assert_eq!(
+            validate_description(" Moderation ".to_string()).unwrap(),
+            Some("Moderation".to_string())
+        );
+
+        let (token, hash) = new_token();
+        assert_eq!(token.len(), TOKEN_LEN);
+        assert_eq!(hash, hash_token(&token));
+    }
+}
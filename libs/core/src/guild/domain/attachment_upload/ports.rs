@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{attachment::AttachmentUpload, channel::ChannelId, guild::GuildId};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+/// Files per request, and uploads per message.
+pub const MAX_UPLOADS_PER_REQUEST: usize = 10;
+pub const MAX_UPLOAD_BYTES: i64 = 25 * 1024 * 1024;
+/// Slots a user may hold that are neither attached nor expired.
+pub const MAX_PENDING_UPLOADS_PER_USER: i64 = 50;
+pub const MAX_UPLOAD_FILENAME_LEN: usize = 255;
+/// How long a slot can be uploaded to and attached.
+pub const UPLOAD_SLOT_TTL: TimeDelta = TimeDelta::hours(1);
+/// How long the pre-signed URL of a slot is valid.
+pub const UPLOAD_URL_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// A file the caller is about to upload.
+pub struct UploadRequest {
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+}
+
+pub trait AttachmentUploadRepository: Send + Sync {
+    /// `user_sub` is the JWT `sub` claim of the uploader.
+    fn insert(
+        &self,
+        user_sub: &str,
+        uploads: &[AttachmentUpload],
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn count_pending_by_user(
+        &self,
+        user_sub: &str,
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<i64, CoreError>> + Send;
+
+    /// Claims the slots among `ids` of the uploader in the channel that are
+    /// neither claimed nor expired, and returns them.
+    fn claim(
+        &self,
+        user_sub: &str,
+        channel_id: Uuid,
+        ids: &[Uuid],
+        now: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<AttachmentUpload>, CoreError>> + Send;
+
+    /// Makes claimed slots available again.
+    fn release(&self, ids: &[Uuid]) -> impl Future<Output = Result<u64, CoreError>> + Send;
+
+    fn delete(&self, ids: &[Uuid]) -> impl Future<Output = Result<u64, CoreError>> + Send;
+
+    /// Deletes the slots expired before `before`, and returns them, whose
+    /// objects are left to delete.
+    fn prune(
+        &self,
+        before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<AttachmentUpload>, CoreError>> + Send;
+}
+
+pub trait AttachmentUploadService: Send + Sync {
+    /// Reserves a slot per file to upload to the channel. Requires
+    /// SEND_MESSAGES and ATTACH_FILES.
+    fn create_uploads(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        files: Vec<UploadRequest>,
+    ) -> impl Future<Output = Result<Vec<AttachmentUpload>, CoreError>> + Send;
+
+    /// Claims the caller's slots to attach to a message in the channel, in
+    /// the order given. Fails, claiming none, if any is unknown, expired or
+    /// already claimed by another message.
+    fn claim_uploads(
+        &self,
+        identity: &Identity,
+        channel_id: &ChannelId,
+        ids: Vec<Uuid>,
+    ) -> impl Future<Output = Result<Vec<AttachmentUpload>, CoreError>> + Send;
+
+    /// Releases claimed slots whose message was not sent, so they can be
+    /// attached again.
+    fn release_uploads(&self, ids: &[Uuid]) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Removes the slots of uploads now attached to a message.
+    fn complete_uploads(&self, ids: &[Uuid]) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Removes expired slots, and returns them.
+    fn prune_uploads(
+        &self,
+    ) -> impl Future<Output = Result<Vec<AttachmentUpload>, CoreError>> + Send;
+}
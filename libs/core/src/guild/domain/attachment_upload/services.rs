@@ -0,0 +1,322 @@
+use std::collections::HashSet;
+
+use chrono::Utc;
+use ferriscord_auth::Identity;
+use ferriscord_entities::{attachment::AttachmentUpload, channel::ChannelId, guild::GuildId};
+use ferriscord_permission::{Permissions, require_permission};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    channel::ports::ChannelPort, common::build_channel_permission_context, errors::CoreError,
+    guild::ports::GuildPort, member::ports::MemberRepository, role::ports::RoleRepository,
+};
+
+use super::ports::{
+    AttachmentUploadRepository, AttachmentUploadService, MAX_PENDING_UPLOADS_PER_USER,
+    MAX_UPLOAD_BYTES, MAX_UPLOAD_FILENAME_LEN, MAX_UPLOADS_PER_REQUEST, UPLOAD_SLOT_TTL,
+    UploadRequest,
+};
+
+#[derive(Clone)]
+pub struct AttachmentUploadServiceImpl<G, C, R, M, U>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    U: AttachmentUploadRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) upload_repository: U,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidAttachmentUpload {
+        message: message.into(),
+    }
+}
+
+/// The part of a MIME type that is compared: `image/png` of
+/// `Image/PNG; charset=binary`.
+fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+fn is_token(s: &str) -> bool {
+    !s.is_empty()
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$&-^_.+".contains(&b))
+}
+
+/// Checks a file to upload, keeping only the last component of its name
+/// and the essence of its type.
+fn validate_request(request: UploadRequest) -> Result<UploadRequest, CoreError> {
+    let filename = request
+        .filename
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_string();
+    if filename.is_empty() || filename == "." || filename == ".." {
+        return Err(invalid("filename must not be empty"));
+    }
+    if filename.chars().count() > MAX_UPLOAD_FILENAME_LEN {
+        return Err(invalid(format!(
+            "filename must be at most {MAX_UPLOAD_FILENAME_LEN} characters"
+        )));
+    }
+    if filename.chars().any(char::is_control) {
+        return Err(invalid("filename must not contain control characters"));
+    }
+
+    let content_type = essence(&request.content_type);
+    match content_type.split_once('/') {
+        Some((kind, subtype)) if is_token(kind) && is_token(subtype) => {}
+        _ => {
+            return Err(invalid(format!(
+                "{} is not a valid content type",
+                request.content_type
+            )));
+        }
+    }
+
+    if request.size_bytes <= 0 || request.size_bytes > MAX_UPLOAD_BYTES {
+        return Err(invalid(format!(
+            "{filename} must be between 1 byte and {} MiB",
+            MAX_UPLOAD_BYTES / (1024 * 1024)
+        )));
+    }
+
+    Ok(UploadRequest {
+        filename,
+        content_type,
+        size_bytes: request.size_bytes,
+    })
+}
+
+/// Where the file of a slot is copied once attached. Unlike the key it was
+/// uploaded to, the uploader cannot write to it.
+pub fn attachment_key(upload: &AttachmentUpload) -> String {
+    format!("attachments/{}/{}", upload.channel_id, upload.id)
+}
+
+/// Checks that the object of a slot is the file declared, from its size and
+/// type in storage, or `None` if nothing was uploaded.
+pub fn check_uploaded_object(
+    upload: &AttachmentUpload,
+    object: Option<(Option<i64>, Option<&str>)>,
+) -> Result<(), CoreError> {
+    let Some((size, content_type)) = object else {
+        return Err(invalid(format!("{} was not uploaded", upload.filename)));
+    };
+    if size != Some(upload.size_bytes) {
+        return Err(invalid(format!(
+            "{} does not have the declared size of {} bytes",
+            upload.filename, upload.size_bytes
+        )));
+    }
+    if content_type.map(essence).as_deref() != Some(upload.content_type.as_str()) {
+        return Err(invalid(format!(
+            "{} was not uploaded as {}",
+            upload.filename, upload.content_type
+        )));
+    }
+    Ok(())
+}
+
+impl<G, C, R, M, U> AttachmentUploadService for AttachmentUploadServiceImpl<G, C, R, M, U>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    U: AttachmentUploadRepository,
+{
+    async fn create_uploads(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        files: Vec<UploadRequest>,
+    ) -> Result<Vec<AttachmentUpload>, CoreError> {
+        let mut permission_context = build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            &identity,
+            &guild_id,
+            &channel_id,
+        )
+        .await?;
+        require_permission!(permission_context, Permissions::SEND_MESSAGES);
+        require_permission!(permission_context, Permissions::ATTACH_FILES);
+
+        if files.is_empty() || files.len() > MAX_UPLOADS_PER_REQUEST {
+            return Err(invalid(format!(
+                "between 1 and {MAX_UPLOADS_PER_REQUEST} files can be uploaded at once"
+            )));
+        }
+        let files = files
+            .into_iter()
+            .map(validate_request)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let now = Utc::now();
+        let pending = self
+            .upload_repository
+            .count_pending_by_user(identity.id(), now)
+            .await?;
+        if pending + files.len() as i64 > MAX_PENDING_UPLOADS_PER_USER {
+            return Err(CoreError::MaxAttachmentUploadsReached {
+                max_uploads: MAX_PENDING_UPLOADS_PER_USER,
+            });
+        }
+
+        let channel_id = channel_id.get_uuid();
+        let uploads: Vec<AttachmentUpload> = files
+            .into_iter()
+            .map(|file| {
+                let id = Uuid::now_v7();
+                AttachmentUpload {
+                    id,
+                    channel_id,
+                    filename: file.filename,
+                    content_type: file.content_type,
+                    size_bytes: file.size_bytes,
+                    storage_key: format!("uploads/{channel_id}/{id}"),
+                    upload_url: String::new(),
+                    created_at: now,
+                    expires_at: now + UPLOAD_SLOT_TTL,
+                }
+            })
+            .collect();
+        self.upload_repository
+            .insert(identity.id(), &uploads)
+            .await?;
+
+        Ok(uploads)
+    }
+
+    async fn claim_uploads(
+        &self,
+        identity: &Identity,
+        channel_id: &ChannelId,
+        ids: Vec<Uuid>,
+    ) -> Result<Vec<AttachmentUpload>, CoreError> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        if ids.len() > MAX_UPLOADS_PER_REQUEST {
+            return Err(invalid(format!(
+                "a message has at most {MAX_UPLOADS_PER_REQUEST} uploads"
+            )));
+        }
+        if ids.iter().collect::<HashSet<_>>().len() != ids.len() {
+            return Err(invalid("an upload is referenced twice"));
+        }
+
+        let mut claimed = self
+            .upload_repository
+            .claim(identity.id(), channel_id.get_uuid(), &ids, Utc::now())
+            .await?;
+        if let Some(missing) = ids
+            .iter()
+            .find(|id| !claimed.iter().any(|upload| upload.id == **id))
+        {
+            let claimed_ids: Vec<Uuid> = claimed.iter().map(|upload| upload.id).collect();
+            self.release_uploads(&claimed_ids).await?;
+            return Err(invalid(format!(
+                "upload {missing} is unknown, expired or already attached"
+            )));
+        }
+        Ok(ids
+            .iter()
+            .filter_map(|id| {
+                let index = claimed.iter().position(|upload| upload.id == *id)?;
+                Some(claimed.swap_remove(index))
+            })
+            .collect())
+    }
+
+    async fn release_uploads(&self, ids: &[Uuid]) -> Result<(), CoreError> {
+        if !ids.is_empty() {
+            self.upload_repository.release(ids).await?;
+        }
+        Ok(())
+    }
+
+    async fn complete_uploads(&self, ids: &[Uuid]) -> Result<(), CoreError> {
+        if !ids.is_empty() {
+            self.upload_repository.delete(ids).await?;
+        }
+        Ok(())
+    }
+
+    async fn prune_uploads(&self) -> Result<Vec<AttachmentUpload>, CoreError> {
+        self.upload_repository.prune(Utc::now()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(filename: &str, content_type: &str, size_bytes: i64) -> UploadRequest {
+        UploadRequest {
+            filename: filename.into(),
+            content_type: content_type.into(),
+            size_bytes,
+        }
+    }
+
+    fn upload() -> AttachmentUpload {
+        let now = Utc::now();
+        AttachmentUpload {
+            id: Uuid::now_v7(),
+            channel_id: Uuid::now_v7(),
+            filename: "ferris.png".into(),
+            content_type: "image/png".into(),
+            size_bytes: 1024,
+            storage_key: String::new(),
+            upload_url: String::new(),
+            created_at: now,
+            expires_at: now + UPLOAD_SLOT_TTL,
+        }
+    }
+
+    #[test]
+    fn test_validate_request() {
+        let file = validate_request(request("../photos/ferris.png", "Image/PNG; q=1", 10)).unwrap();
+        assert_eq!(file.filename, "ferris.png");
+        assert_eq!(file.content_type, "image/png");
+
+        assert!(validate_request(request("dir/", "image/png", 10)).is_err());
+        assert!(validate_request(request("a\nb.png", "image/png", 10)).is_err());
+        assert!(validate_request(request("a.png", "png", 10)).is_err());
+        assert!(validate_request(request("a.png", "image/png", 0)).is_err());
+        assert!(validate_request(request("a.png", "image/png", MAX_UPLOAD_BYTES + 1)).is_err());
+        assert!(validate_request(request("a.png", "image/png", MAX_UPLOAD_BYTES)).is_ok());
+    }
+
+    #[test]
+    fn test_check_uploaded_object() {
+        let upload = upload();
+        assert!(check_uploaded_object(&upload, Some((Some(1024), Some("image/png")))).is_ok());
+        assert!(check_uploaded_object(&upload, Some((Some(1024), Some("IMAGE/png; x=y")))).is_ok());
+
+        assert!(check_uploaded_object(&upload, None).is_err());
+        assert!(check_uploaded_object(&upload, Some((Some(2048), Some("image/png")))).is_err());
+        assert!(check_uploaded_object(&upload, Some((Some(1024), Some("text/html")))).is_err());
+        assert!(check_uploaded_object(&upload, Some((Some(1024), None))).is_err());
+    }
+}
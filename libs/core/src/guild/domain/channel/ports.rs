@@ -3,6 +3,7 @@ use ferriscord_entities::{
     channel::{Channel, ChannelId},
     guild::GuildId,
 };
+use uuid::Uuid;
 
 use crate::guild::domain::errors::CoreError;
 
@@ -51,6 +52,23 @@ pub trait ChannelService: Send + Sync {
         guild_id: GuildId,
     ) -> impl Future<Output = Result<Vec<Channel>, CoreError>> + Send;
 
+    /// Returns a single channel of the guild. Requires VIEW_CHANNEL.
+    fn get_channel(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> impl Future<Output = Result<Channel, CoreError>> + Send;
+
+    /// The members among `user_ids` who can view the channel, to tell them
+    /// about its activity.
+    fn viewers(
+        &self,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+        user_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<Uuid>, CoreError>> + Send;
+
     fn update_channel(
         &self,
         identity: Identity,
@@ -3,10 +3,14 @@ use ferriscord_entities::{
     channel::{Channel, ChannelId, ChannelKind},
     guild::GuildId,
 };
+use ferriscord_pagination::PaginationParams;
 use ferriscord_permission::{Permissions, require_permission};
+use uuid::Uuid;
 
 use crate::guild::domain::{
-    common::{build_channel_permission_context, build_permission_context},
+    common::{
+        build_channel_permission_context, build_permission_context, member_channel_permissions,
+    },
     errors::CoreError,
     guild::ports::GuildPort,
     member::ports::MemberRepository,
@@ -131,6 +135,92 @@ where
         Ok(visible)
     }
 
+    async fn get_channel(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> Result<Channel, CoreError> {
+        let channel = self
+            .channel_repository
+            .find_by_id(&channel_id)
+            .await?
+            .filter(|c| c.guild_id.as_ref() == Some(&guild_id))
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: channel_id.clone(),
+            })?;
+
+        let mut permission_context = build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            &identity,
+            &guild_id,
+            &channel_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, Permissions::VIEW_CHANNEL);
+
+        Ok(channel)
+    }
+
+    async fn viewers(
+        &self,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<Uuid>, CoreError> {
+        if user_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let guild = self
+            .guild_repository
+            .find_by_id(guild_id)
+            .await?
+            .ok_or_else(|| CoreError::GuildNotFound {
+                guild_id: guild_id.clone(),
+            })?;
+        let channel = self
+            .channel_repository
+            .find_by_id(channel_id)
+            .await?
+            .filter(|c| c.guild_id.as_ref() == Some(guild_id))
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: channel_id.clone(),
+            })?;
+        let parent = match &channel.parent_id {
+            Some(parent_id) => self.channel_repository.find_by_id(parent_id).await?,
+            None => None,
+        };
+        let roles = self
+            .role_repository
+            .find_by_guild_id(
+                guild_id.clone(),
+                PaginationParams {
+                    page: 1,
+                    per_page: 100,
+                },
+            )
+            .await?
+            .0;
+
+        Ok(self
+            .member_repository
+            .list_members(guild_id)
+            .await?
+            .iter()
+            .filter(|member| user_ids.contains(&member.user_id))
+            .filter(|member| {
+                member_channel_permissions(&guild, &roles, member, parent.as_ref(), &channel)
+                    .can(Permissions::VIEW_CHANNEL)
+            })
+            .map(|member| member.user_id)
+            .collect())
+    }
+
     async fn update_channel(
         &self,
         identity: Identity,
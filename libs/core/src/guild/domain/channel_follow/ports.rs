@@ -0,0 +1,92 @@
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    channel::ChannelId, channel_follow::ChannelFollow, guild::GuildId, message::Message,
+};
+use uuid::Uuid;
+
+use crate::guild::domain::{errors::CoreError, message::ports::Crosspost};
+
+/// Announcement channels one channel may follow.
+pub const MAX_FOLLOWS_PER_CHANNEL: i64 = 10;
+/// Length of the name crossposted copies display.
+pub const MAX_CROSSPOST_USERNAME_LEN: usize = 80;
+
+pub trait ChannelFollowRepository: Send + Sync {
+    fn insert(
+        &self,
+        id: Uuid,
+        source_channel_id: Uuid,
+        guild_id: Uuid,
+        channel_id: Uuid,
+        created_by: Uuid,
+    ) -> impl Future<Output = Result<ChannelFollow, CoreError>> + Send;
+
+    fn find_by_id(
+        &self,
+        follow_id: Uuid,
+    ) -> impl Future<Output = Result<Option<ChannelFollow>, CoreError>> + Send;
+
+    /// Announcement channels the channel follows.
+    fn list_by_channel(
+        &self,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<ChannelFollow>, CoreError>> + Send;
+
+    /// Channels following the announcement channel.
+    fn list_by_source(
+        &self,
+        source_channel_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<ChannelFollow>, CoreError>> + Send;
+
+    fn count_by_channel(
+        &self,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<i64, CoreError>> + Send;
+
+    fn delete(&self, follow_id: Uuid) -> impl Future<Output = Result<bool, CoreError>> + Send;
+}
+
+/// Following an announcement channel requires VIEW_CHANNEL on it and
+/// MANAGE_WEBHOOKS on the following channel, which is also what managing the
+/// follow requires.
+pub trait ChannelFollowService: Send + Sync {
+    /// Has `target_channel_id`, in another guild, follow the announcement
+    /// channel.
+    fn follow_channel(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        target_channel_id: ChannelId,
+    ) -> impl Future<Output = Result<ChannelFollow, CoreError>> + Send;
+
+    /// Announcement channels the channel follows.
+    fn list_channel_follows(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> impl Future<Output = Result<Vec<ChannelFollow>, CoreError>> + Send;
+
+    /// Returns the deleted follow.
+    fn delete_channel_follow(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        follow_id: Uuid,
+    ) -> impl Future<Output = Result<ChannelFollow, CoreError>> + Send;
+
+    /// Copies an announcement into every following channel, once. The
+    /// author needs SEND_MESSAGES, anyone else MANAGE_MESSAGES. Returns the
+    /// published message and its copies.
+    fn crosspost_message(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+    ) -> impl Future<Output = Result<(Message, Vec<Crosspost>), CoreError>> + Send;
+}
@@ -0,0 +1,408 @@
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    Id,
+    channel::{Channel, ChannelId, ChannelKind},
+    channel_follow::ChannelFollow,
+    guild::GuildId,
+    message::Message,
+};
+use ferriscord_permission::{Permissions, require_permission};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    channel::ports::ChannelPort,
+    common::build_channel_permission_context,
+    errors::CoreError,
+    guild::ports::GuildPort,
+    member::ports::MemberRepository,
+    message::ports::{Crosspost, CrosspostInput, MessagePort},
+    role::ports::RoleRepository,
+};
+
+use super::ports::{
+    ChannelFollowRepository, ChannelFollowService, MAX_CROSSPOST_USERNAME_LEN,
+    MAX_FOLLOWS_PER_CHANNEL,
+};
+
+#[derive(Clone)]
+pub struct ChannelFollowServiceImpl<G, C, R, M, Msg, F>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    F: ChannelFollowRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) message_repository: Msg,
+    pub(crate) follow_repository: F,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidChannelFollow {
+        message: message.into(),
+    }
+}
+
+/// Copies show where they were published from, like "Guild #news".
+fn crosspost_username(guild_name: &str, channel_name: &str) -> String {
+    format!("{guild_name} #{channel_name}")
+        .chars()
+        .take(MAX_CROSSPOST_USERNAME_LEN)
+        .collect()
+}
+
+/// Only plain messages of an announcement channel are published, once.
+fn check_publishable(message: &Message) -> Result<(), CoreError> {
+    let reason = if message.reference.is_some() {
+        "crossposted copies cannot be published again"
+    } else if message.encrypted {
+        "encrypted messages cannot be published"
+    } else if message.crossposted {
+        "message was already published"
+    } else {
+        return Ok(());
+    };
+    Err(CoreError::InvalidMessage {
+        message: reason.into(),
+    })
+}
+
+impl<G, C, R, M, Msg, F> ChannelFollowServiceImpl<G, C, R, M, Msg, F>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    F: ChannelFollowRepository,
+{
+    /// Loads a channel of the guild.
+    async fn guild_channel(
+        &self,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+    ) -> Result<Channel, CoreError> {
+        self.channel_repository
+            .find_by_id(channel_id)
+            .await?
+            .filter(|c| c.guild_id.as_ref() == Some(guild_id))
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: channel_id.clone(),
+            })
+    }
+
+    async fn require_channel_permission(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+        permission: Permissions,
+    ) -> Result<(), CoreError> {
+        let mut permission_context = build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            identity,
+            guild_id,
+            channel_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, permission);
+        Ok(())
+    }
+}
+
+impl<G, C, R, M, Msg, F> ChannelFollowService for ChannelFollowServiceImpl<G, C, R, M, Msg, F>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    F: ChannelFollowRepository,
+{
+    async fn follow_channel(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        target_channel_id: ChannelId,
+    ) -> Result<ChannelFollow, CoreError> {
+        let source = self.guild_channel(&guild_id, &channel_id).await?;
+        if source.kind != ChannelKind::Announcement {
+            return Err(invalid("only announcement channels can be followed"));
+        }
+        self.require_channel_permission(
+            &identity,
+            &guild_id,
+            &channel_id,
+            Permissions::VIEW_CHANNEL,
+        )
+        .await?;
+
+        let target = self
+            .channel_repository
+            .find_by_id(&target_channel_id)
+            .await?
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: target_channel_id.clone(),
+            })?;
+        let Some(target_guild_id) = target.guild_id.clone() else {
+            return Err(invalid("only guild channels can follow"));
+        };
+        if target_guild_id == guild_id {
+            return Err(invalid(
+                "a channel can only follow channels of other guilds",
+            ));
+        }
+        if !matches!(target.kind, ChannelKind::Text | ChannelKind::Announcement) {
+            return Err(invalid("only text channels can follow"));
+        }
+        self.require_channel_permission(
+            &identity,
+            &target_guild_id,
+            &target_channel_id,
+            Permissions::MANAGE_WEBHOOKS,
+        )
+        .await?;
+
+        if self
+            .follow_repository
+            .count_by_channel(target_channel_id.get_uuid())
+            .await?
+            >= MAX_FOLLOWS_PER_CHANNEL
+        {
+            return Err(CoreError::MaxChannelFollowsReached {
+                channel_id: target_channel_id,
+                max_follows: MAX_FOLLOWS_PER_CHANNEL,
+            });
+        }
+
+        self.follow_repository
+            .insert(
+                Uuid::now_v7(),
+                channel_id.get_uuid(),
+                *target_guild_id.get_uuid(),
+                target_channel_id.get_uuid(),
+                user_id,
+            )
+            .await
+    }
+
+    async fn list_channel_follows(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> Result<Vec<ChannelFollow>, CoreError> {
+        self.guild_channel(&guild_id, &channel_id).await?;
+        self.require_channel_permission(
+            &identity,
+            &guild_id,
+            &channel_id,
+            Permissions::MANAGE_WEBHOOKS,
+        )
+        .await?;
+
+        self.follow_repository
+            .list_by_channel(channel_id.get_uuid())
+            .await
+    }
+
+    async fn delete_channel_follow(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        follow_id: Uuid,
+    ) -> Result<ChannelFollow, CoreError> {
+        let follow = self
+            .follow_repository
+            .find_by_id(follow_id)
+            .await?
+            .filter(|f| f.guild_id == *guild_id.get_uuid() && f.channel_id == channel_id.get_uuid())
+            .ok_or(CoreError::ChannelFollowNotFound)?;
+        self.require_channel_permission(
+            &identity,
+            &guild_id,
+            &channel_id,
+            Permissions::MANAGE_WEBHOOKS,
+        )
+        .await?;
+
+        if !self.follow_repository.delete(follow_id).await? {
+            return Err(CoreError::ChannelFollowNotFound);
+        }
+        Ok(follow)
+    }
+
+    async fn crosspost_message(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+    ) -> Result<(Message, Vec<Crosspost>), CoreError> {
+        let channel = self.guild_channel(&guild_id, &channel_id).await?;
+        if channel.kind != ChannelKind::Announcement {
+            return Err(CoreError::InvalidMessage {
+                message: "only messages of announcement channels can be published".into(),
+            });
+        }
+
+        let message = self
+            .message_repository
+            .find_by_id(message_id)
+            .await?
+            .filter(|m| m.channel_id == channel_id)
+            .ok_or(CoreError::MessageNotFound)?;
+
+        let permission = if *message.author.id.get_uuid() == user_id {
+            Permissions::SEND_MESSAGES
+        } else {
+            Permissions::MANAGE_MESSAGES
+        };
+        self.require_channel_permission(&identity, &guild_id, &channel_id, permission)
+            .await?;
+
+        check_publishable(&message)?;
+        // Claims the message so concurrent requests publish it only once.
+        if !self.message_repository.mark_crossposted(message_id).await? {
+            return Err(CoreError::InvalidMessage {
+                message: "message was already published".into(),
+            });
+        }
+
+        let guild = self
+            .guild_repository
+            .find_by_id(&guild_id)
+            .await?
+            .ok_or_else(|| CoreError::GuildNotFound {
+                guild_id: guild_id.clone(),
+            })?;
+        let username = crosspost_username(&guild.name, &channel.name);
+
+        let follows = self
+            .follow_repository
+            .list_by_source(channel_id.get_uuid())
+            .await?;
+        let mut crossposts = Vec::with_capacity(follows.len());
+        for follow in follows {
+            // One unreachable follower must not keep the others from
+            // receiving the announcement.
+            match self
+                .message_repository
+                .insert_crosspost(
+                    &ChannelId(Id(follow.channel_id)),
+                    &message,
+                    CrosspostInput {
+                        source_guild_id: *guild_id.get_uuid(),
+                        username: username.clone(),
+                        avatar_url: guild.icon_url.clone(),
+                    },
+                )
+                .await
+            {
+                Ok(copy) => crossposts.push(Crosspost {
+                    guild_id: follow.guild_id,
+                    message: copy,
+                }),
+                Err(e) => error!(
+                    "failed to crosspost message {} to channel {}: {}",
+                    message_id, follow.channel_id, e
+                ),
+            }
+        }
+
+        Ok((
+            Message {
+                crossposted: true,
+                ..message
+            },
+            crossposts,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use ferriscord_entities::{
+        message::{MessageAuthor, MessageId, MessageKind, MessageReference},
+        user::UserId,
+    };
+
+    use super::*;
+
+    fn message() -> Message {
+        Message {
+            id: MessageId::new(),
+            channel_id: ChannelId::new(),
+            author: MessageAuthor {
+                id: UserId::from(Uuid::now_v7()),
+                username: "alice".to_string(),
+                avatar_url: None,
+                bot: false,
+            },
+            kind: MessageKind::Default,
+            content: "v2 is out".to_string(),
+            attachments: Vec::new(),
+            embeds: Vec::new(),
+            poll: None,
+            encrypted: false,
+            encryption_version: 0,
+            sender_key_generation: None,
+            sender_device_id: None,
+            payload_sync_kind: None,
+            call: None,
+            webhook_id: None,
+            reference: None,
+            crossposted: false,
+            edited_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_check_publishable() {
+        assert!(check_publishable(&message()).is_ok());
+
+        let published = Message {
+            crossposted: true,
+            ..message()
+        };
+        assert!(check_publishable(&published).is_err());
+
+        let encrypted = Message {
+            encrypted: true,
+            ..message()
+        };
+        assert!(check_publishable(&encrypted).is_err());
+
+        let copy = Message {
+            reference: Some(MessageReference {
+                guild_id: Uuid::now_v7(),
+                channel_id: Uuid::now_v7(),
+                message_id: Uuid::now_v7(),
+            }),
+            ..message()
+        };
+        assert!(check_publishable(&copy).is_err());
+
+        assert_eq!(crosspost_username("Rust", "news"), "Rust #news");
+        assert_eq!(
+            crosspost_username(&"a".repeat(100), "news").chars().count(),
+            MAX_CROSSPOST_USERNAME_LEN
+        );
+    }
+}
@@ -1,7 +1,7 @@
 use ferriscord_auth::Identity;
 use ferriscord_entities::{
-    channel::ChannelId,
-    guild::GuildId,
+    channel::{Channel, ChannelId},
+    guild::{Guild, GuildId},
     role::{PermissionContext, Role},
 };
 use ferriscord_pagination::PaginationParams;
@@ -10,11 +10,83 @@ use ferriscord_permission::Permissions;
 
 use crate::guild::domain::{
     channel::ports::ChannelPort, errors::CoreError, guild::ports::GuildPort,
-    member::ports::MemberRepository, role::ports::RoleRepository,
+    member::ports::{MemberRepository, MemberWithUser},
+    role::ports::RoleRepository,
 };
 
 fn default_everyone_permissions() -> Permissions {
-    Permissions::VIEW_GUILD | Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES
+    Permissions::VIEW_GUILD
+        | Permissions::VIEW_CHANNEL
+        | Permissions::SEND_MESSAGES
+        | Permissions::CONNECT
+        | Permissions::SPEAK
+}
+
+/// Whether a member of a guild is the caller.
+pub(crate) fn is_member_identity(member: &MemberWithUser, identity: &Identity) -> bool {
+    member.user_id.to_string() == identity.id() || member.username == identity.username()
+}
+
+fn everyone_role(roles: &[Role], guild_id: &GuildId) -> Role {
+    match roles
+        .iter()
+        .find(|role| role.name == "@everyone" || role.name == "everyone")
+    {
+        Some(everyone_role) => {
+            let mut everyone_role = everyone_role.clone();
+            if everyone_role.permissions.is_empty() {
+                everyone_role.permissions = default_everyone_permissions();
+            }
+            everyone_role
+        }
+        None => Role::everyone(guild_id.clone()),
+    }
+}
+
+/// Permissions of any member in a channel, rather than the caller's: from
+/// the guild's `roles`, the member's own and the overwrites of the
+/// channel's `parent` then of the channel.
+pub(crate) fn member_channel_permissions(
+    guild: &Guild,
+    roles: &[Role],
+    member: &MemberWithUser,
+    parent: Option<&Channel>,
+    channel: &Channel,
+) -> Permissions {
+    let mut context = PermissionContext::new(member.user_id.to_string(), guild.id.to_string())
+        .with_channel(channel.id.to_string())
+        .add_role(everyone_role(roles, &guild.id));
+
+    if guild.owner_id.0.get_uuid() == member.user_id {
+        context = context.add_role(Role::new(
+            guild.id.clone(),
+            "Owner".to_string(),
+            Permissions::ADMINISTRATOR,
+        ));
+    }
+    for role in roles.iter().filter(|role| {
+        member
+            .roles
+            .iter()
+            .any(|member_role| member_role.id == role.id.0.get_uuid())
+    }) {
+        context = context.add_role(role.clone());
+    }
+    let overwrites = parent
+        .into_iter()
+        .chain([channel])
+        .flat_map(|channel| &channel.permission_overwrites);
+    for overwrite in overwrites {
+        context = context.add_channel_override(
+            overwrite.id.to_string(),
+            PermissionOverrides {
+                allow: Permissions::from_bits_truncate(overwrite.allow),
+                deny: Permissions::from_bits_truncate(overwrite.deny),
+            },
+        );
+    }
+
+    context.compute_permissions()
 }
 
 pub(crate) async fn build_permission_context<
@@ -47,18 +119,7 @@ pub(crate) async fn build_permission_context<
         .await?
         .0;
 
-    if let Some(everyone_role) = roles
-        .iter()
-        .find(|role| role.name == "@everyone" || role.name == "everyone")
-    {
-        let mut everyone_role = everyone_role.clone();
-        if everyone_role.permissions.is_empty() {
-            everyone_role.permissions = default_everyone_permissions();
-        }
-        context = context.add_role(everyone_role);
-    } else {
-        context = context.add_role(Role::everyone(guild_id.clone()));
-    }
+    context = context.add_role(everyone_role(&roles, guild_id));
 
     if guild.owner_id.0.to_string() == identity.id() {
         let owner_role = Role::new(
@@ -70,9 +131,10 @@ pub(crate) async fn build_permission_context<
     }
 
     let members = member_repository.list_members(guild_id).await?;
-    if let Some(member) = members.into_iter().find(|member| {
-        member.user_id.to_string() == identity.id() || member.username == identity.username()
-    }) {
+    if let Some(member) = members
+        .into_iter()
+        .find(|member| is_member_identity(member, identity))
+    {
         for role in roles.into_iter().filter(|role| {
             member
                 .roles
@@ -117,17 +179,17 @@ pub(crate) async fn build_channel_permission_context<
             channel_id: channel_id.clone(),
         })?;
 
-    if let Some(parent_id) = &channel.parent_id {
-        if let Some(parent) = channel_repository.find_by_id(parent_id).await? {
-            for overwrite in parent.permission_overwrites {
-                context = context.add_channel_override(
-                    overwrite.id.to_string(),
-                    PermissionOverrides {
-                        allow: Permissions::from_bits_truncate(overwrite.allow),
-                        deny: Permissions::from_bits_truncate(overwrite.deny),
-                    },
-                );
-            }
+    if let Some(parent_id) = &channel.parent_id
+        && let Some(parent) = channel_repository.find_by_id(parent_id).await?
+    {
+        for overwrite in parent.permission_overwrites {
+            context = context.add_channel_override(
+                overwrite.id.to_string(),
+                PermissionOverrides {
+                    allow: Permissions::from_bits_truncate(overwrite.allow),
+                    deny: Permissions::from_bits_truncate(overwrite.deny),
+                },
+            );
         }
     }
 
@@ -143,3 +205,116 @@ pub(crate) async fn build_channel_permission_context<
 
     Ok(context)
 }
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+    use ferriscord_entities::{
+        Id,
+        channel::{ChannelFlags, ChannelKind, OverwriteKind, PermissionOverwrite},
+        guild::OwnerId,
+    };
+    use uuid::Uuid;
+
+    use super::*;
+    use crate::guild::domain::member::ports::RoleSummary;
+
+    fn channel(guild: &Guild, overwrites: Vec<PermissionOverwrite>) -> Channel {
+        Channel {
+            id: ChannelId(Id::new()),
+            kind: ChannelKind::Text,
+            guild_id: Some(guild.id.clone()),
+            position: 0,
+            permission_overwrites: overwrites,
+            name: "general".to_string(),
+            topic: None,
+            nsfw: false,
+            last_message_id: None,
+            rate_limit_per_user: 0,
+            parent_id: None,
+            last_pin_timestamp: None,
+            bitrate: None,
+            user_limit: None,
+            rtc_region: None,
+            default_auto_archive_duration: None,
+            flags: ChannelFlags::NONE,
+            available_tags: Vec::new(),
+            default_reaction_emoji: None,
+            default_thread_rate_limit_per_user: 0,
+            default_sort_order: None,
+            default_forum_layout: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn overwrite(role: &Role, allow: Permissions, deny: Permissions) -> PermissionOverwrite {
+        PermissionOverwrite {
+            id: role.id.0.get_uuid(),
+            kind: OverwriteKind::Role,
+            allow: allow.bits(),
+            deny: deny.bits(),
+        }
+    }
+
+    fn member(user_id: Uuid, roles: &[&Role]) -> MemberWithUser {
+        MemberWithUser {
+            member_id: Uuid::now_v7(),
+            user_id,
+            username: "member".to_string(),
+            display_name: None,
+            avatar_url: None,
+            joined_at: Utc::now(),
+            roles: roles
+                .iter()
+                .map(|role| RoleSummary {
+                    id: role.id.0.get_uuid(),
+                    name: role.name.clone(),
+                    color: 0,
+                    position: role.position,
+                    hoist: false,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_member_channel_permissions_follow_overwrites() {
+        let guild = Guild::new("guild".to_string(), OwnerId(Id::new()));
+        let everyone = Role::everyone(guild.id.clone());
+        let staff = Role::new(guild.id.clone(), "staff".to_string(), Permissions::empty());
+        let roles = vec![everyone.clone(), staff.clone()];
+
+        let mut category = channel(
+            &guild,
+            vec![overwrite(
+                &everyone,
+                Permissions::empty(),
+                Permissions::VIEW_CHANNEL,
+            )],
+        );
+        category.kind = ChannelKind::Category;
+        let mut hidden = channel(
+            &guild,
+            vec![overwrite(
+                &staff,
+                Permissions::VIEW_CHANNEL,
+                Permissions::empty(),
+            )],
+        );
+        hidden.parent_id = Some(category.id.clone());
+        let open = channel(&guild, Vec::new());
+
+        let plain = member(Uuid::now_v7(), &[]);
+        let staffer = member(Uuid::now_v7(), &[&staff]);
+        let owner = member(guild.owner_id.0.get_uuid(), &[]);
+
+        let can_view = |member: &MemberWithUser, parent: Option<&Channel>, channel: &Channel| {
+            member_channel_permissions(&guild, &roles, member, parent, channel)
+                .can(Permissions::VIEW_CHANNEL)
+        };
+        assert!(can_view(&plain, None, &open));
+        assert!(!can_view(&plain, Some(&category), &hidden));
+        assert!(can_view(&staffer, Some(&category), &hidden));
+        assert!(can_view(&owner, Some(&category), &hidden));
+    }
+}
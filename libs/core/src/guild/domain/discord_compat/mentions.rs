@@ -0,0 +1,121 @@
+/// What a mention in message content points at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MentionKind {
+    /// `<@id>`, or the legacy `<@!id>`.
+    User,
+    /// `<@&id>`
+    Role,
+    /// `<#id>`
+    Channel,
+}
+
+impl MentionKind {
+    fn prefix(self) -> &'static str {
+        match self {
+            MentionKind::User => "<@",
+            MentionKind::Role => "<@&",
+            MentionKind::Channel => "<#",
+        }
+    }
+}
+
+/// Finds the mention starting at `rest`, returning its kind, its id and the
+/// length of the whole mention.
+fn parse_mention(rest: &str) -> Option<(MentionKind, &str, usize)> {
+    let (kind, start) = if rest.starts_with("<@&") {
+        (MentionKind::Role, 3)
+    } else if rest.starts_with("<@!") {
+        (MentionKind::User, 3)
+    } else if rest.starts_with("<@") {
+        (MentionKind::User, 2)
+    } else if rest.starts_with("<#") {
+        (MentionKind::Channel, 2)
+    } else {
+        return None;
+    };
+    let end = start + rest[start..].find('>')?;
+    let id = &rest[start..end];
+    if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+        return None;
+    }
+    Some((kind, id, end + 1))
+}
+
+/// The ids mentioned in `content`, in order and with duplicates.
+pub fn mentioned_ids(content: &str) -> Vec<(MentionKind, &str)> {
+    let mut ids = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find('<') {
+        rest = &rest[start..];
+        match parse_mention(rest) {
+            Some((kind, id, len)) => {
+                ids.push((kind, id));
+                rest = &rest[len..];
+            }
+            None => rest = &rest[1..],
+        }
+    }
+    ids
+}
+
+/// Replaces the ids of the mentions in `content` by what `map` returns for
+/// them. Mentions it returns `None` for are left as they are. Legacy
+/// `<@!id>` user mentions are written as `<@id>`.
+pub fn rewrite_mentions(
+    content: &str,
+    mut map: impl FnMut(MentionKind, &str) -> Option<String>,
+) -> String {
+    let mut rewritten = String::with_capacity(content.len());
+    let mut rest = content;
+    while let Some(start) = rest.find('<') {
+        rewritten.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match parse_mention(rest).and_then(|(kind, id, len)| Some((kind, map(kind, id)?, len))) {
+            Some((kind, id, len)) => {
+                rewritten.push_str(kind.prefix());
+                rewritten.push_str(&id);
+                rewritten.push('>');
+                rest = &rest[len..];
+            }
+            None => {
+                rewritten.push('<');
+                rest = &rest[1..];
+            }
+        }
+    }
+    rewritten.push_str(rest);
+    rewritten
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mentioned_ids() {
+        let ids = mentioned_ids("hi <@1> <@!2> <@&3>, see <#4> <@> <5> 1 < 2 <@a b>");
+        assert_eq!(
+            ids,
+            vec![
+                (MentionKind::User, "1"),
+                (MentionKind::User, "2"),
+                (MentionKind::Role, "3"),
+                (MentionKind::Channel, "4"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_mentions() {
+        let rewritten =
+            rewrite_mentions("<@!1> and <@&2> in <#3>, not <@9> <3", |kind, id| {
+                match (kind, id) {
+                    (MentionKind::User, "1") => Some("a".into()),
+                    (MentionKind::Role, "2") => Some("b".into()),
+                    (MentionKind::Channel, "3") => Some("c".into()),
+                    _ => None,
+                }
+            });
+        assert_eq!(rewritten, "<@a> and <@&b> in <#c>, not <@9> <3");
+    }
+}
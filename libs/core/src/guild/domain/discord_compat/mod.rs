@@ -0,0 +1,59 @@
+//! Support for the Discord-compatible API: snowflake ids for FerrisCord's
+//! UUIDs, Discord's permission layout, and the rewriting of mentions
+//! between the two.
+
+pub mod mentions;
+pub mod permissions;
+pub mod ports;
+mod services;
+
+pub use services::DiscordCompatServiceImpl;
+
+use chrono::Utc;
+use uuid::Uuid;
+
+/// Start of 2015, the epoch of Discord snowflakes, in ms since the Unix epoch.
+pub const DISCORD_EPOCH_MS: i64 = 1_420_070_400_000;
+/// Bits of a snowflake below its timestamp.
+pub const SNOWFLAKE_TIMESTAMP_SHIFT: u32 = 22;
+
+/// When an id was created, in ms since the Unix epoch: the timestamp of
+/// version 7 UUIDs, and now for other ids. Snowflakes start with it, since
+/// Discord libraries read creation times from ids.
+pub fn created_at_ms(id: Uuid) -> i64 {
+    id.get_timestamp()
+        .filter(|_| id.get_version_num() == 7)
+        .map(|ts| {
+            let (secs, nanos) = ts.to_unix();
+            secs as i64 * 1000 + nanos as i64 / 1_000_000
+        })
+        .unwrap_or_else(|| Utc::now().timestamp_millis())
+        .max(DISCORD_EPOCH_MS)
+}
+
+/// When the object a snowflake stands for was created, in ms since the Unix
+/// epoch.
+pub fn snowflake_created_at_ms(snowflake: u64) -> i64 {
+    (snowflake >> SNOWFLAKE_TIMESTAMP_SHIFT) as i64 + DISCORD_EPOCH_MS
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::{NoContext, Timestamp};
+
+    use super::*;
+
+    #[test]
+    fn test_created_at_ms() {
+        let id = Uuid::new_v7(Timestamp::from_unix(NoContext, 1_700_000_000, 123_000_000));
+        assert_eq!(created_at_ms(id), 1_700_000_000_123);
+
+        let snowflake =
+            ((created_at_ms(id) - DISCORD_EPOCH_MS) as u64) << SNOWFLAKE_TIMESTAMP_SHIFT | 42;
+        assert_eq!(snowflake_created_at_ms(snowflake), 1_700_000_000_123);
+
+        // Ids without a timestamp get the current time.
+        let now = Utc::now().timestamp_millis();
+        assert!(created_at_ms(Uuid::from_u128(42)) >= now);
+    }
+}
@@ -0,0 +1,86 @@
+use ferriscord_permission::Permissions;
+
+/// FerrisCord permissions and the bit of their Discord counterpart.
+/// VIEW_GUILD has none: on Discord, being a member is enough.
+const DISCORD_BITS: [(Permissions, u32); 33] = [
+    (Permissions::CREATE_INSTANT_INVITE, 0),
+    (Permissions::KICK_MEMBERS, 1),
+    (Permissions::BAN_MEMBERS, 2),
+    (Permissions::ADMINISTRATOR, 3),
+    (Permissions::MANAGE_CHANNELS, 4),
+    (Permissions::MANAGE_GUILD, 5),
+    (Permissions::ADD_REACTIONS, 6),
+    (Permissions::VIEW_AUDIT_LOG, 7),
+    (Permissions::PRIORITY_SPEAKER, 8),
+    (Permissions::STREAM, 9),
+    (Permissions::VIEW_CHANNEL, 10),
+    (Permissions::SEND_MESSAGES, 11),
+    (Permissions::SEND_TTS_MESSAGES, 12),
+    (Permissions::MANAGE_MESSAGES, 13),
+    (Permissions::EMBED_LINKS, 14),
+    (Permissions::ATTACH_FILES, 15),
+    (Permissions::READ_MESSAGE_HISTORY, 16),
+    (Permissions::MENTION_EVERYONE, 17),
+    (Permissions::USE_EXTERNAL_EMOJIS, 18),
+    (Permissions::CONNECT, 20),
+    (Permissions::SPEAK, 21),
+    (Permissions::MUTE_MEMBERS, 22),
+    (Permissions::DEAFEN_MEMBERS, 23),
+    (Permissions::MOVE_MEMBERS, 24),
+    (Permissions::USE_VAD, 25),
+    (Permissions::CHANGE_NICKNAME, 26),
+    (Permissions::MANAGE_NICKNAMES, 27),
+    (Permissions::MANAGE_ROLES, 28),
+    (Permissions::MANAGE_WEBHOOKS, 29),
+    (Permissions::MANAGE_EMOJIS, 30),
+    (Permissions::USE_SLASH_COMMANDS, 31),
+    (Permissions::MANAGE_THREADS, 34),
+    (Permissions::SEND_MESSAGES_IN_THREADS, 38),
+];
+
+// FerrisCord has a single CREATE_THREADS for public and private threads.
+const CREATE_PUBLIC_THREADS: u32 = 35;
+const CREATE_PRIVATE_THREADS: u32 = 36;
+
+/// The Discord permission bitfield of FerrisCord `permissions`.
+pub fn to_discord(permissions: Permissions) -> u64 {
+    let mut bits = DISCORD_BITS
+        .iter()
+        .filter(|(permission, _)| permissions.contains(*permission))
+        .fold(0, |bits, (_, bit)| bits | (1 << bit));
+    if permissions.contains(Permissions::CREATE_THREADS) {
+        bits |= (1 << CREATE_PUBLIC_THREADS) | (1 << CREATE_PRIVATE_THREADS);
+    }
+    bits
+}
+
+/// The FerrisCord permissions of a raw bitfield, such as a channel
+/// overwrite, in Discord's layout.
+pub fn bits_to_discord(bits: u64) -> u64 {
+    to_discord(Permissions::from_bits_truncate(bits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_discord() {
+        assert_eq!(to_discord(Permissions::empty()), 0);
+        assert_eq!(
+            to_discord(Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES),
+            (1 << 10) | (1 << 11)
+        );
+        assert_eq!(to_discord(Permissions::ADMINISTRATOR), 1 << 3);
+        assert_eq!(
+            to_discord(Permissions::CREATE_THREADS),
+            (1 << 35) | (1 << 36)
+        );
+        // No Discord counterpart.
+        assert_eq!(to_discord(Permissions::VIEW_GUILD), 0);
+        assert_eq!(
+            bits_to_discord((Permissions::MANAGE_ROLES | Permissions::VIEW_GUILD).bits()),
+            1 << 28
+        );
+    }
+}
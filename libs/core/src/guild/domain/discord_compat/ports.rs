@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+pub trait SnowflakeRepository: Send + Sync {
+    /// Returns the snowflakes of `ids`, giving one to the ids that have
+    /// none yet. Each id comes with its creation time, in ms since the Unix
+    /// epoch.
+    fn assign(
+        &self,
+        ids: &[(Uuid, i64)],
+    ) -> impl Future<Output = Result<HashMap<Uuid, i64>, CoreError>> + Send;
+
+    /// Returns the ids of the known `snowflakes`.
+    fn find_ids(
+        &self,
+        snowflakes: &[i64],
+    ) -> impl Future<Output = Result<HashMap<i64, Uuid>, CoreError>> + Send;
+}
+
+pub trait DiscordCompatService: Send + Sync {
+    /// Returns the snowflakes of `ids`, giving one to the ids seen for the
+    /// first time.
+    fn snowflakes(
+        &self,
+        ids: &[Uuid],
+    ) -> impl Future<Output = Result<HashMap<Uuid, u64>, CoreError>> + Send;
+
+    /// Returns the ids of the known `snowflakes`. Unknown snowflakes were
+    /// never handed out, and are left out.
+    fn resolve(
+        &self,
+        snowflakes: &[u64],
+    ) -> impl Future<Output = Result<HashMap<u64, Uuid>, CoreError>> + Send;
+
+    /// The guild of a channel, for routes that address channels by id
+    /// only. Access to the channel is left to the caller to check.
+    fn channel_guild(
+        &self,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<Uuid, CoreError>> + Send;
+}
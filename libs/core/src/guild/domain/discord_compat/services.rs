@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use ferriscord_entities::{Id, channel::ChannelId};
+use uuid::Uuid;
+
+use crate::guild::domain::{channel::ports::ChannelPort, errors::CoreError};
+
+use super::{
+    created_at_ms,
+    ports::{DiscordCompatService, SnowflakeRepository},
+};
+
+#[derive(Clone)]
+pub struct DiscordCompatServiceImpl<C, S>
+where
+    C: ChannelPort,
+    S: SnowflakeRepository,
+{
+    pub(crate) channel_repository: C,
+    pub(crate) snowflake_repository: S,
+}
+
+impl<C, S> DiscordCompatService for DiscordCompatServiceImpl<C, S>
+where
+    C: ChannelPort,
+    S: SnowflakeRepository,
+{
+    async fn snowflakes(&self, ids: &[Uuid]) -> Result<HashMap<Uuid, u64>, CoreError> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let mut unique: Vec<Uuid> = ids.to_vec();
+        unique.sort_unstable();
+        unique.dedup();
+        let ids: Vec<(Uuid, i64)> = unique
+            .into_iter()
+            .map(|id| (id, created_at_ms(id)))
+            .collect();
+
+        let mut snowflakes = self.snowflake_repository.assign(&ids).await?;
+        // A new snowflake that collided with an existing one is drawn again.
+        let missing: Vec<(Uuid, i64)> = ids
+            .into_iter()
+            .filter(|(id, _)| !snowflakes.contains_key(id))
+            .collect();
+        if !missing.is_empty() {
+            snowflakes.extend(self.snowflake_repository.assign(&missing).await?);
+        }
+        Ok(snowflakes
+            .into_iter()
+            .map(|(id, snowflake)| (id, snowflake as u64))
+            .collect())
+    }
+
+    async fn resolve(&self, snowflakes: &[u64]) -> Result<HashMap<u64, Uuid>, CoreError> {
+        // Snowflakes above i64::MAX were never handed out.
+        let known: Vec<i64> = snowflakes
+            .iter()
+            .filter_map(|&snowflake| i64::try_from(snowflake).ok())
+            .collect();
+        if known.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let ids = self.snowflake_repository.find_ids(&known).await?;
+        Ok(ids
+            .into_iter()
+            .map(|(snowflake, id)| (snowflake as u64, id))
+            .collect())
+    }
+
+    async fn channel_guild(&self, channel_id: Uuid) -> Result<Uuid, CoreError> {
+        let channel_id = ChannelId(Id(channel_id));
+        self.channel_repository
+            .find_by_id(&channel_id)
+            .await?
+            .and_then(|channel| channel.guild_id)
+            .map(|guild_id| *guild_id.get_uuid())
+            .ok_or(CoreError::ChannelNotFound { channel_id })
+    }
+}
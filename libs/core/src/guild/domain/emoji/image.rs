@@ -0,0 +1,256 @@
+//! Checks of uploaded emoji images. Only the headers are read: enough to
+//! know the format, the dimensions and whether the image is animated.
+
+use crate::guild::domain::errors::CoreError;
+
+use super::ports::{EmojiImage, MAX_EMOJI_BYTES, MAX_EMOJI_DIMENSION};
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidEmoji {
+        message: message.into(),
+    }
+}
+
+fn u16_be(data: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_be_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn u16_le(data: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?) as u32)
+}
+
+fn u24_le(data: &[u8], at: usize) -> Option<u32> {
+    let b = data.get(at..at + 3)?;
+    Some(b[0] as u32 | (b[1] as u32) << 8 | (b[2] as u32) << 16)
+}
+
+fn u32_be(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+/// An APNG has an animation control chunk before its image data.
+fn inspect_png(data: &[u8]) -> Option<EmojiImage> {
+    if data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let mut animated = false;
+    let mut at = 8;
+    while let Some(len) = u32_be(data, at) {
+        match data.get(at + 4..at + 8)? {
+            b"acTL" => {
+                animated = true;
+                break;
+            }
+            b"IDAT" | b"IEND" => break,
+            _ => at += 12 + len as usize,
+        }
+    }
+    Some(EmojiImage {
+        content_type: "image/png",
+        width: u32_be(data, 16)?,
+        height: u32_be(data, 20)?,
+        animated,
+    })
+}
+
+/// Skips a chain of GIF data sub-blocks, returning the offset after it.
+fn skip_gif_blocks(data: &[u8], mut at: usize) -> Option<usize> {
+    loop {
+        let len = *data.get(at)? as usize;
+        at += 1 + len;
+        if len == 0 {
+            return Some(at);
+        }
+    }
+}
+
+/// A GIF is animated when it has more than one frame.
+fn inspect_gif(data: &[u8]) -> Option<EmojiImage> {
+    let packed = *data.get(10)?;
+    let mut at = 13;
+    if packed & 0x80 != 0 {
+        at += 3 << ((packed & 0x07) + 1);
+    }
+    let mut frames = 0;
+    while frames < 2 {
+        match *data.get(at)? {
+            // Extension: label, then sub-blocks.
+            0x21 => at = skip_gif_blocks(data, at + 2)?,
+            // Image descriptor, local color table, LZW code size, sub-blocks.
+            0x2C => {
+                frames += 1;
+                let local = *data.get(at + 9)?;
+                at += 10;
+                if local & 0x80 != 0 {
+                    at += 3 << ((local & 0x07) + 1);
+                }
+                at = skip_gif_blocks(data, at + 1)?;
+            }
+            _ => break,
+        }
+    }
+    Some(EmojiImage {
+        content_type: "image/gif",
+        width: u16_le(data, 6)?,
+        height: u16_le(data, 8)?,
+        animated: frames > 1,
+    })
+}
+
+/// The dimensions are in the first start-of-frame segment.
+fn inspect_jpeg(data: &[u8]) -> Option<EmojiImage> {
+    let mut at = 2;
+    loop {
+        if *data.get(at)? != 0xFF {
+            return None;
+        }
+        let marker = *data.get(at + 1)?;
+        match marker {
+            0xFF => at += 1,
+            0xD8 | 0x01 | 0xD0..=0xD7 => at += 2,
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                return Some(EmojiImage {
+                    content_type: "image/jpeg",
+                    width: u16_be(data, at + 7)?,
+                    height: u16_be(data, at + 5)?,
+                    animated: false,
+                });
+            }
+            _ => at += 2 + u16_be(data, at + 2)? as usize,
+        }
+    }
+}
+
+fn inspect_webp(data: &[u8]) -> Option<EmojiImage> {
+    let (width, height, animated) = match data.get(12..16)? {
+        b"VP8 " => {
+            if data.get(23..26)? != [0x9d, 0x01, 0x2a] {
+                return None;
+            }
+            (
+                u16_le(data, 26)? & 0x3fff,
+                u16_le(data, 28)? & 0x3fff,
+                false,
+            )
+        }
+        b"VP8L" => {
+            let b = data.get(21..25)?;
+            let width = 1 + (b[0] as u32 | (b[1] as u32 & 0x3f) << 8);
+            let height = 1 + (b[1] as u32 >> 6 | (b[2] as u32) << 2 | (b[3] as u32 & 0x0f) << 10);
+            (width, height, false)
+        }
+        b"VP8X" => {
+            let flags = *data.get(20)?;
+            (
+                1 + u24_le(data, 24)?,
+                1 + u24_le(data, 27)?,
+                flags & 0x02 != 0,
+            )
+        }
+        _ => return None,
+    };
+    Some(EmojiImage {
+        content_type: "image/webp",
+        width,
+        height,
+        animated,
+    })
+}
+
+/// Validates an uploaded emoji image: PNG, JPEG, GIF or WebP, animated or
+/// not, within the size and dimension limits.
+pub fn inspect_image(data: &[u8]) -> Result<EmojiImage, CoreError> {
+    if data.is_empty() {
+        return Err(invalid("image is required"));
+    }
+    if data.len() > MAX_EMOJI_BYTES {
+        return Err(invalid(format!(
+            "image must be at most {} KiB",
+            MAX_EMOJI_BYTES / 1024
+        )));
+    }
+
+    let image = if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        inspect_png(data)
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        inspect_gif(data)
+    } else if data.starts_with(&[0xFF, 0xD8]) {
+        inspect_jpeg(data)
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        inspect_webp(data)
+    } else {
+        return Err(invalid("image must be a PNG, JPEG, GIF or WebP"));
+    }
+    .ok_or_else(|| invalid("image is corrupt"))?;
+
+    if image.width == 0
+        || image.height == 0
+        || image.width > MAX_EMOJI_DIMENSION
+        || image.height > MAX_EMOJI_DIMENSION
+    {
+        return Err(invalid(format!(
+            "image must be at most {MAX_EMOJI_DIMENSION}x{MAX_EMOJI_DIMENSION} pixels"
+        )));
+    }
+    Ok(image)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn png(width: u32, height: u32, animated: bool) -> Vec<u8> {
+        let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+        let mut chunk = |kind: &[u8], body: &[u8]| {
+            data.extend_from_slice(&(body.len() as u32).to_be_bytes());
+            data.extend_from_slice(kind);
+            data.extend_from_slice(body);
+            data.extend_from_slice(&[0; 4]);
+        };
+        let mut ihdr = width.to_be_bytes().to_vec();
+        ihdr.extend_from_slice(&height.to_be_bytes());
+        ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+        chunk(b"IHDR", &ihdr);
+        if animated {
+            chunk(b"acTL", &[0, 0, 0, 2, 0, 0, 0, 0]);
+        }
+        chunk(b"IDAT", &[0; 8]);
+        chunk(b"IEND", &[]);
+        data
+    }
+
+    fn gif(frames: usize) -> Vec<u8> {
+        let mut data = b"GIF89a".to_vec();
+        data.extend_from_slice(&[32, 0, 32, 0, 0x80, 0, 0]);
+        data.extend_from_slice(&[0; 6]);
+        for _ in 0..frames {
+            data.extend_from_slice(&[0x21, 0xF9, 4, 0, 10, 0, 0, 0]);
+            data.extend_from_slice(&[0x2C, 0, 0, 0, 0, 32, 0, 32, 0, 0]);
+            data.extend_from_slice(&[2, 2, 0x4C, 0x01, 0]);
+        }
+        data.push(0x3B);
+        data
+    }
+
+    #[test]
+    fn test_inspect_image() {
+        let image = inspect_image(&png(128, 64, false)).unwrap();
+        assert_eq!(
+            image,
+            EmojiImage {
+                content_type: "image/png",
+                width: 128,
+                height: 64,
+                animated: false,
+            }
+        );
+        assert!(inspect_image(&png(64, 64, true)).unwrap().animated);
+        assert!(!inspect_image(&gif(1)).unwrap().animated);
+        assert!(inspect_image(&gif(3)).unwrap().animated);
+
+        assert!(inspect_image(&png(1024, 64, false)).is_err());
+        assert!(inspect_image(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>").is_err());
+        assert!(inspect_image(&png(64, 64, false)[..12]).is_err());
+        assert!(inspect_image(&vec![0; MAX_EMOJI_BYTES + 1]).is_err());
+    }
+}
@@ -0,0 +1,6 @@
+pub mod image;
+pub mod ports;
+mod services;
+
+pub use services::EmojiServiceImpl;
+pub(crate) use services::check_emoji_usage;
@@ -0,0 +1,126 @@
+use ferriscord_auth::Identity;
+use ferriscord_entities::{emoji::GuildEmoji, guild::GuildId};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+/// Emojis of a guild, counted apart for static and animated ones.
+pub const MAX_STATIC_EMOJIS_PER_GUILD: i64 = 50;
+pub const MAX_ANIMATED_EMOJIS_PER_GUILD: i64 = 50;
+pub const MAX_EMOJI_BYTES: usize = 256 * 1024;
+/// Largest width and height of an emoji image, in pixels.
+pub const MAX_EMOJI_DIMENSION: u32 = 256;
+pub const MIN_EMOJI_NAME_LEN: usize = 2;
+pub const MAX_EMOJI_NAME_LEN: usize = 32;
+
+/// What an uploaded image turned out to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EmojiImage {
+    pub content_type: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub animated: bool,
+}
+
+/// An emoji whose image was already checked and uploaded by the handler.
+pub struct CreateEmojiInput {
+    pub id: Uuid,
+    pub name: String,
+    /// Roles allowed to use it; empty for everyone.
+    pub roles: Vec<Uuid>,
+    pub image: EmojiImage,
+    pub size_bytes: i64,
+    pub storage_key: String,
+}
+
+/// Fields left `None` are kept.
+pub struct UpdateEmojiInput {
+    pub name: Option<String>,
+    pub roles: Option<Vec<Uuid>>,
+}
+
+pub trait EmojiRepository: Send + Sync {
+    fn insert(
+        &self,
+        guild_id: Uuid,
+        created_by: Uuid,
+        input: &CreateEmojiInput,
+    ) -> impl Future<Output = Result<GuildEmoji, CoreError>> + Send;
+
+    fn find_by_id(
+        &self,
+        emoji_id: Uuid,
+    ) -> impl Future<Output = Result<Option<GuildEmoji>, CoreError>> + Send;
+
+    /// The emojis among `emoji_ids` that exist, of any guild.
+    fn find_many(
+        &self,
+        emoji_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<GuildEmoji>, CoreError>> + Send;
+
+    fn list_by_guild(
+        &self,
+        guild_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<GuildEmoji>, CoreError>> + Send;
+
+    fn count_by_guild(
+        &self,
+        guild_id: Uuid,
+        animated: bool,
+    ) -> impl Future<Output = Result<i64, CoreError>> + Send;
+
+    fn update(
+        &self,
+        emoji_id: Uuid,
+        name: Option<&str>,
+        roles: Option<&[Uuid]>,
+    ) -> impl Future<Output = Result<Option<GuildEmoji>, CoreError>> + Send;
+
+    /// Returns the deleted emoji, so its image can be removed.
+    fn delete(
+        &self,
+        emoji_id: Uuid,
+    ) -> impl Future<Output = Result<Option<GuildEmoji>, CoreError>> + Send;
+}
+
+pub trait EmojiService: Send + Sync {
+    fn list_emojis(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+    ) -> impl Future<Output = Result<Vec<GuildEmoji>, CoreError>> + Send;
+
+    /// Any emoji by id, so messages using emojis of other guilds can be
+    /// rendered.
+    fn get_emoji(
+        &self,
+        emoji_id: Uuid,
+    ) -> impl Future<Output = Result<GuildEmoji, CoreError>> + Send;
+
+    /// Requires MANAGE_EMOJIS.
+    fn create_emoji(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        input: CreateEmojiInput,
+    ) -> impl Future<Output = Result<GuildEmoji, CoreError>> + Send;
+
+    /// Renames an emoji or changes the roles it is restricted to. Requires
+    /// MANAGE_EMOJIS.
+    fn update_emoji(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        emoji_id: Uuid,
+        input: UpdateEmojiInput,
+    ) -> impl Future<Output = Result<GuildEmoji, CoreError>> + Send;
+
+    /// Requires MANAGE_EMOJIS. Returns the deleted emoji.
+    fn delete_emoji(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        emoji_id: Uuid,
+    ) -> impl Future<Output = Result<GuildEmoji, CoreError>> + Send;
+}
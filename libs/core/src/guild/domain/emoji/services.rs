@@ -0,0 +1,328 @@
+use std::collections::{HashMap, hash_map::Entry};
+
+use ferriscord_auth::Identity;
+use ferriscord_entities::{emoji::GuildEmoji, guild::GuildId, role::PermissionContext};
+use ferriscord_pagination::PaginationParams;
+use ferriscord_permission::{Permissions, require_permission};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    common::{build_permission_context, is_member_identity},
+    errors::CoreError,
+    guild::ports::GuildPort,
+    member::ports::MemberRepository,
+    role::ports::RoleRepository,
+};
+
+use super::ports::{
+    CreateEmojiInput, EmojiRepository, EmojiService, MAX_ANIMATED_EMOJIS_PER_GUILD,
+    MAX_EMOJI_NAME_LEN, MAX_STATIC_EMOJIS_PER_GUILD, MIN_EMOJI_NAME_LEN, UpdateEmojiInput,
+};
+
+#[derive(Clone)]
+pub struct EmojiServiceImpl<G, R, M, E>
+where
+    G: GuildPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    E: EmojiRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) emoji_repository: E,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidEmoji {
+        message: message.into(),
+    }
+}
+
+fn validate_name(name: String) -> Result<String, CoreError> {
+    let len = name.chars().count();
+    if !(MIN_EMOJI_NAME_LEN..=MAX_EMOJI_NAME_LEN).contains(&len) {
+        return Err(invalid(format!(
+            "name must be {MIN_EMOJI_NAME_LEN} to {MAX_EMOJI_NAME_LEN} characters"
+        )));
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(invalid(
+            "name may only contain letters, digits and underscores",
+        ));
+    }
+    Ok(name)
+}
+
+/// Ids of the custom emojis a message uses, as `<:name:id>` or
+/// `<a:name:id>`, without duplicates.
+fn emoji_references(content: &str) -> Vec<Uuid> {
+    let mut ids = Vec::new();
+    for (start, _) in content.match_indices('<') {
+        let rest = &content[start + 1..];
+        let Some(rest) = rest.strip_prefix("a:").or_else(|| rest.strip_prefix(':')) else {
+            continue;
+        };
+        let Some((name, rest)) = rest.split_once(':') else {
+            continue;
+        };
+        let Some((id, _)) = rest.split_once('>') else {
+            continue;
+        };
+        if validate_name(name.to_string()).is_err() {
+            continue;
+        }
+        if let Ok(id) = Uuid::parse_str(id)
+            && !ids.contains(&id)
+        {
+            ids.push(id);
+        }
+    }
+    ids
+}
+
+/// Checks the custom emojis of a message sent in `guild_id`. Emojis of
+/// other guilds need USE_EXTERNAL_EMOJIS and membership of their guild;
+/// emojis restricted to roles need one of them. Unknown emojis show as
+/// plain text and are let through.
+pub(crate) async fn check_emoji_usage<E: EmojiRepository, M: MemberRepository>(
+    emoji_repository: &E,
+    member_repository: &M,
+    identity: &Identity,
+    guild_id: &GuildId,
+    permission_context: &mut PermissionContext,
+    content: &str,
+) -> Result<(), CoreError> {
+    let ids = emoji_references(content);
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    // Role ids of the author per guild, `None` where not a member.
+    let mut member_roles: HashMap<Uuid, Option<Vec<Uuid>>> = HashMap::new();
+    for emoji in emoji_repository.find_many(&ids).await? {
+        let external = emoji.guild_id != *guild_id.get_uuid();
+        if external && !permission_context.can(Permissions::USE_EXTERNAL_EMOJIS) {
+            return Err(CoreError::EmojiNotAllowed { emoji_id: emoji.id });
+        }
+        if !external && emoji.roles.is_empty() {
+            continue;
+        }
+
+        if let Entry::Vacant(slot) = member_roles.entry(emoji.guild_id) {
+            let roles = member_repository
+                .list_members(&GuildId::from(emoji.guild_id))
+                .await?
+                .into_iter()
+                .find(|member| is_member_identity(member, identity))
+                .map(|member| member.roles.iter().map(|role| role.id).collect());
+            slot.insert(roles);
+        }
+        let allowed = member_roles[&emoji.guild_id].as_ref().is_some_and(|roles| {
+            emoji.roles.is_empty() || emoji.roles.iter().any(|r| roles.contains(r))
+        });
+        if !allowed {
+            return Err(CoreError::EmojiNotAllowed { emoji_id: emoji.id });
+        }
+    }
+    Ok(())
+}
+
+impl<G, R, M, E> EmojiServiceImpl<G, R, M, E>
+where
+    G: GuildPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    E: EmojiRepository,
+{
+    async fn require_manage_emojis(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+    ) -> Result<(), CoreError> {
+        let mut permission_context = build_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            identity,
+            guild_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, Permissions::MANAGE_EMOJIS);
+        Ok(())
+    }
+
+    /// Loads an emoji of the guild the caller may manage.
+    async fn managed_emoji(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        emoji_id: Uuid,
+    ) -> Result<GuildEmoji, CoreError> {
+        self.require_manage_emojis(identity, guild_id).await?;
+        self.emoji_repository
+            .find_by_id(emoji_id)
+            .await?
+            .filter(|e| e.guild_id == *guild_id.get_uuid())
+            .ok_or(CoreError::EmojiNotFound)
+    }
+
+    /// Restricting roles must be roles of the guild.
+    async fn validate_roles(
+        &self,
+        guild_id: &GuildId,
+        mut roles: Vec<Uuid>,
+    ) -> Result<Vec<Uuid>, CoreError> {
+        roles.sort();
+        roles.dedup();
+        if roles.is_empty() {
+            return Ok(roles);
+        }
+        let (guild_roles, _) = self
+            .role_repository
+            .find_by_guild_id(
+                guild_id.clone(),
+                PaginationParams {
+                    page: 1,
+                    per_page: 100,
+                },
+            )
+            .await?;
+        if let Some(unknown) = roles
+            .iter()
+            .find(|id| !guild_roles.iter().any(|r| r.id.0.get_uuid() == **id))
+        {
+            return Err(invalid(format!(
+                "role {unknown} is not a role of this guild"
+            )));
+        }
+        Ok(roles)
+    }
+}
+
+impl<G, R, M, E> EmojiService for EmojiServiceImpl<G, R, M, E>
+where
+    G: GuildPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    E: EmojiRepository,
+{
+    async fn list_emojis(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+    ) -> Result<Vec<GuildEmoji>, CoreError> {
+        let mut permission_context = build_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &identity,
+            &guild_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, Permissions::VIEW_GUILD);
+
+        self.emoji_repository
+            .list_by_guild(*guild_id.get_uuid())
+            .await
+    }
+
+    async fn get_emoji(&self, emoji_id: Uuid) -> Result<GuildEmoji, CoreError> {
+        self.emoji_repository
+            .find_by_id(emoji_id)
+            .await?
+            .ok_or(CoreError::EmojiNotFound)
+    }
+
+    async fn create_emoji(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        mut input: CreateEmojiInput,
+    ) -> Result<GuildEmoji, CoreError> {
+        self.require_manage_emojis(&identity, &guild_id).await?;
+
+        input.name = validate_name(input.name)?;
+        input.roles = self.validate_roles(&guild_id, input.roles).await?;
+
+        let animated = input.image.animated;
+        let (kind, max_emojis) = if animated {
+            ("animated", MAX_ANIMATED_EMOJIS_PER_GUILD)
+        } else {
+            ("static", MAX_STATIC_EMOJIS_PER_GUILD)
+        };
+        if self
+            .emoji_repository
+            .count_by_guild(*guild_id.get_uuid(), animated)
+            .await?
+            >= max_emojis
+        {
+            return Err(CoreError::MaxEmojisReached {
+                guild_id,
+                kind,
+                max_emojis,
+            });
+        }
+
+        self.emoji_repository
+            .insert(*guild_id.get_uuid(), user_id, &input)
+            .await
+    }
+
+    async fn update_emoji(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        emoji_id: Uuid,
+        input: UpdateEmojiInput,
+    ) -> Result<GuildEmoji, CoreError> {
+        self.managed_emoji(&identity, &guild_id, emoji_id).await?;
+
+        let name = input.name.map(validate_name).transpose()?;
+        let roles = match input.roles {
+            Some(roles) => Some(self.validate_roles(&guild_id, roles).await?),
+            None => None,
+        };
+
+        self.emoji_repository
+            .update(emoji_id, name.as_deref(), roles.as_deref())
+            .await?
+            .ok_or(CoreError::EmojiNotFound)
+    }
+
+    async fn delete_emoji(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        emoji_id: Uuid,
+    ) -> Result<GuildEmoji, CoreError> {
+        self.managed_emoji(&identity, &guild_id, emoji_id).await?;
+        self.emoji_repository
+            .delete(emoji_id)
+            .await?
+            .ok_or(CoreError::EmojiNotFound)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emoji_references() {
+        let party = Uuid::now_v7();
+        let dance = Uuid::now_v7();
+        let short = Uuid::now_v7();
+        let content =
+            format!("<:party:{party}> <a:dance:{dance}> again <:party:{party}> <:x:{short}>");
+        assert_eq!(emoji_references(&content), vec![party, dance]);
+
+        assert!(emoji_references("<:party:not-an-id> <@123> a < b").is_empty());
+        assert!(validate_name("ferris_2".into()).is_ok());
+        assert!(validate_name("with space".into()).is_err());
+        assert!(validate_name("x".into()).is_err());
+    }
+}
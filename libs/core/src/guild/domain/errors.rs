@@ -1,5 +1,6 @@
 use ferriscord_entities::{channel::ChannelId, guild::GuildId};
 use thiserror::Error;
+use uuid::Uuid;
 
 #[derive(Debug, Error)]
 pub enum CoreError {
@@ -38,6 +39,160 @@ pub enum CoreError {
 
     #[error("invite has reached its maximum number of uses")]
     InviteMaxUsesReached,
+
+    #[error("user is not a member of this guild")]
+    NotGuildMember,
+
+    #[error("channel with id {channel_id} is not a voice channel")]
+    NotVoiceChannel { channel_id: ChannelId },
+
+    #[error("voice channel with id {channel_id} is full")]
+    VoiceChannelFull { channel_id: ChannelId },
+
+    #[error("user is not connected to this voice channel")]
+    NotInVoiceChannel,
+
+    #[error("invalid voice signal: {message}")]
+    InvalidVoiceSignal { message: String },
+
+    #[error("channel with id {channel_id} is not a stage channel")]
+    NotStageChannel { channel_id: ChannelId },
+
+    #[error("a stage is already live in this channel")]
+    StageAlreadyLive,
+
+    #[error("no stage is live in this channel")]
+    StageNotLive,
+
+    #[error("invalid stage topic: {message}")]
+    InvalidStageTopic { message: String },
+
+    #[error("webhook not found")]
+    WebhookNotFound,
+
+    #[error("invalid webhook: {message}")]
+    InvalidWebhook { message: String },
+
+    #[error("channel with id {channel_id} has reached its limit of {max_webhooks} webhooks")]
+    MaxWebhooksReached { channel_id: ChannelId, max_webhooks: i64 },
+
+    #[error("event subscription not found")]
+    EventSubscriptionNotFound,
+
+    #[error("invalid event subscription: {message}")]
+    InvalidEventSubscription { message: String },
+
+    #[error("guild with id {guild_id} has reached its limit of {max_subscriptions} event subscriptions")]
+    MaxEventSubscriptionsReached {
+        guild_id: GuildId,
+        max_subscriptions: i64,
+    },
+
+    #[error("application not found")]
+    ApplicationNotFound,
+
+    #[error("invalid application: {message}")]
+    InvalidApplication { message: String },
+
+    #[error("user has reached the limit of {max_applications} applications")]
+    MaxApplicationsReached { max_applications: i64 },
+
+    #[error("command not found")]
+    CommandNotFound,
+
+    #[error("invalid command: {message}")]
+    InvalidCommand { message: String },
+
+    #[error("application has reached its limit of {max_commands} commands in this scope")]
+    MaxCommandsReached { max_commands: i64 },
+
+    #[error("interaction not found")]
+    InteractionNotFound,
+
+    #[error("invalid interaction: {message}")]
+    InvalidInteraction { message: String },
+
+    #[error("interaction can no longer be answered")]
+    InteractionExpired,
+
+    #[error("message not found")]
+    MessageNotFound,
+
+    #[error("invalid message: {message}")]
+    InvalidMessage { message: String },
+
+    #[error("channel follow not found")]
+    ChannelFollowNotFound,
+
+    #[error("invalid channel follow: {message}")]
+    InvalidChannelFollow { message: String },
+
+    #[error("channel with id {channel_id} has reached its limit of {max_follows} followed channels")]
+    MaxChannelFollowsReached { channel_id: ChannelId, max_follows: i64 },
+
+    #[error("emoji not found")]
+    EmojiNotFound,
+
+    #[error("invalid emoji: {message}")]
+    InvalidEmoji { message: String },
+
+    #[error("guild with id {guild_id} has reached its limit of {max_emojis} {kind} emojis")]
+    MaxEmojisReached {
+        guild_id: GuildId,
+        kind: &'static str,
+        max_emojis: i64,
+    },
+
+    #[error("emoji {emoji_id} cannot be used here")]
+    EmojiNotAllowed { emoji_id: Uuid },
+
+    #[error("scheduled message not found")]
+    ScheduledMessageNotFound,
+
+    #[error("invalid scheduled message: {message}")]
+    InvalidScheduledMessage { message: String },
+
+    #[error("you have reached the limit of {max_scheduled} pending scheduled messages")]
+    MaxScheduledMessagesReached { max_scheduled: i64 },
+
+    #[error("poll not found")]
+    PollNotFound,
+
+    #[error("invalid poll: {message}")]
+    InvalidPoll { message: String },
+
+    #[error("the poll is closed")]
+    PollClosed,
+
+    #[error("scheduled event not found")]
+    ScheduledEventNotFound,
+
+    #[error("invalid scheduled event: {message}")]
+    InvalidScheduledEvent { message: String },
+
+    #[error("the event is over or canceled")]
+    ScheduledEventEnded,
+
+    #[error("the guild has reached the limit of {max_events} upcoming events")]
+    MaxScheduledEventsReached { max_events: i64 },
+
+    #[error("calendar feed not found")]
+    CalendarFeedNotFound,
+
+    #[error("guild import not found")]
+    GuildImportNotFound,
+
+    #[error("invalid guild import: {message}")]
+    InvalidGuildImport { message: String },
+
+    #[error("you have reached the limit of {max_imports} guild imports in progress")]
+    MaxGuildImportsReached { max_imports: i64 },
+
+    #[error("invalid upload: {message}")]
+    InvalidAttachmentUpload { message: String },
+
+    #[error("you have reached the limit of {max_uploads} pending uploads")]
+    MaxAttachmentUploadsReached { max_uploads: i64 },
 }
 
 impl From<&str> for CoreError {
@@ -0,0 +1,4 @@
+pub mod ports;
+mod services;
+
+pub use services::{EventSubscriptionServiceImpl, sign_payload, verify_signature};
@@ -0,0 +1,250 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    event_subscription::{EventDelivery, EventSubscription, GuildEventType},
+    guild::GuildId,
+};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+pub const MAX_SUBSCRIPTIONS_PER_GUILD: i64 = 10;
+pub const MAX_SUBSCRIPTION_URL_LEN: usize = 2048;
+/// Attempts per delivery before it is marked failed.
+pub const MAX_DELIVERY_ATTEMPTS: i32 = 8;
+/// Delay before the first retry; it doubles with every attempt.
+pub const BASE_RETRY_DELAY: TimeDelta = TimeDelta::seconds(10);
+pub const MAX_RETRY_DELAY: TimeDelta = TimeDelta::hours(1);
+/// Failed attempts in a row after which a subscription is disabled.
+pub const DISABLE_AFTER_FAILURES: i32 = 20;
+/// How long a claimed delivery stays reserved for the worker sending it. Past
+/// that (e.g. the replica died) it is picked up again.
+pub const DELIVERY_LEASE: TimeDelta = TimeDelta::seconds(60);
+/// Timeout of one delivery attempt. Must stay well under `DELIVERY_LEASE`.
+pub const DELIVERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+/// How long the delivery log is kept.
+pub const DELIVERY_RETENTION: TimeDelta = TimeDelta::days(7);
+
+/// `sha256=<hex HMAC-SHA256 of "{timestamp}.{body}">`, keyed by the secret.
+pub const SIGNATURE_HEADER: &str = "X-Ferriscord-Signature";
+/// Unix time of the attempt, in seconds. Part of the signed data so
+/// receivers can reject replays.
+pub const TIMESTAMP_HEADER: &str = "X-Ferriscord-Timestamp";
+pub const EVENT_HEADER: &str = "X-Ferriscord-Event";
+pub const DELIVERY_HEADER: &str = "X-Ferriscord-Delivery";
+
+pub struct CreateEventSubscriptionInput {
+    pub url: String,
+    pub event_types: Vec<GuildEventType>,
+}
+
+/// `None` leaves a field unchanged. Enabling a subscription resets its
+/// failure count.
+pub struct UpdateEventSubscriptionInput {
+    pub url: Option<String>,
+    pub event_types: Option<Vec<GuildEventType>>,
+    pub enabled: Option<bool>,
+}
+
+/// A claimed delivery with what is needed to send it.
+pub struct DeliveryJob {
+    pub delivery: EventDelivery,
+    pub url: String,
+    pub secret: String,
+}
+
+/// A delivery attempt ready to go out.
+pub struct SignedRequest {
+    pub url: String,
+    pub headers: Vec<(&'static str, String)>,
+    pub body: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    /// The endpoint answered with a 2xx status.
+    Delivered { status_code: i32 },
+    Failed {
+        status_code: Option<i32>,
+        error: String,
+    },
+}
+
+pub trait EventSender: Send + Sync {
+    /// Whether deliveries may be sent to `url`, or why not. Checked again
+    /// before every delivery.
+    fn check_url(&self, url: &str) -> Result<(), String>;
+
+    fn send(&self, request: SignedRequest) -> impl Future<Output = DeliveryOutcome> + Send;
+}
+
+pub trait EventSubscriptionRepository: Send + Sync {
+    fn insert(
+        &self,
+        subscription: &EventSubscription,
+        secret: &str,
+    ) -> impl Future<Output = Result<EventSubscription, CoreError>> + Send;
+
+    fn find_by_id(
+        &self,
+        subscription_id: Uuid,
+    ) -> impl Future<Output = Result<Option<EventSubscription>, CoreError>> + Send;
+
+    fn list_by_guild(
+        &self,
+        guild_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<EventSubscription>, CoreError>> + Send;
+
+    fn count_by_guild(&self, guild_id: Uuid)
+    -> impl Future<Output = Result<i64, CoreError>> + Send;
+
+    /// Disabling a subscription fails its pending deliveries.
+    fn update(
+        &self,
+        subscription_id: Uuid,
+        input: UpdateEventSubscriptionInput,
+    ) -> impl Future<Output = Result<Option<EventSubscription>, CoreError>> + Send;
+
+    fn delete(&self, subscription_id: Uuid)
+    -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Queues the event for every enabled subscription of the guild that
+    /// wants it. Returns how many deliveries were queued.
+    fn enqueue_event(
+        &self,
+        guild_id: Uuid,
+        event_type: GuildEventType,
+        body: &str,
+    ) -> impl Future<Output = Result<u64, CoreError>> + Send;
+
+    /// Queues a delivery for one subscription, whatever its event types.
+    fn enqueue_delivery(
+        &self,
+        subscription_id: Uuid,
+        event_type: &str,
+        body: &str,
+    ) -> impl Future<Output = Result<EventDelivery, CoreError>> + Send;
+
+    /// Claims up to `limit` due deliveries: counts the attempt and holds them
+    /// until `lease_until` so no other replica sends them meanwhile.
+    fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<DeliveryJob>, CoreError>> + Send;
+
+    /// Marks the delivery done and resets the subscription's failure count.
+    fn record_success(
+        &self,
+        delivery_id: Uuid,
+        status_code: i32,
+        at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Records a failed attempt. The delivery is retried at `retry_at`, or
+    /// marked failed when `None`. The subscription is disabled once it
+    /// reaches `disable_after` failures in a row; returns whether it was.
+    fn record_failure(
+        &self,
+        delivery_id: Uuid,
+        status_code: Option<i32>,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+        disable_after: i32,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Deliveries of the subscription, most recent first.
+    fn list_deliveries(
+        &self,
+        subscription_id: Uuid,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<EventDelivery>, CoreError>> + Send;
+
+    /// Deletes finished deliveries created before `before`.
+    fn prune_deliveries(
+        &self,
+        before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
+
+/// Managing subscriptions requires MANAGE_GUILD: they receive activity from
+/// every channel.
+pub trait EventSubscriptionService: Send + Sync {
+    /// Returns the subscription with its signing secret, which is not shown
+    /// again.
+    fn create_subscription(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        input: CreateEventSubscriptionInput,
+    ) -> impl Future<Output = Result<EventSubscription, CoreError>> + Send;
+
+    fn list_subscriptions(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+    ) -> impl Future<Output = Result<Vec<EventSubscription>, CoreError>> + Send;
+
+    fn get_subscription(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+    ) -> impl Future<Output = Result<EventSubscription, CoreError>> + Send;
+
+    fn update_subscription(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+        input: UpdateEventSubscriptionInput,
+    ) -> impl Future<Output = Result<EventSubscription, CoreError>> + Send;
+
+    fn delete_subscription(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn list_deliveries(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+        limit: u32,
+    ) -> impl Future<Output = Result<Vec<EventDelivery>, CoreError>> + Send;
+
+    /// Queues a `ping` delivery to check the endpoint and its signature
+    /// verification.
+    fn ping_subscription(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+    ) -> impl Future<Output = Result<EventDelivery, CoreError>> + Send;
+
+    /// Queues a guild event for the subscriptions that want it.
+    fn publish_event(
+        &self,
+        guild_id: Uuid,
+        event_type: GuildEventType,
+        data: serde_json::Value,
+    ) -> impl Future<Output = Result<u64, CoreError>> + Send;
+
+    fn claim_due_deliveries(
+        &self,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<DeliveryJob>, CoreError>> + Send;
+
+    /// Sends one claimed delivery and records the outcome, scheduling a
+    /// retry or disabling the subscription as needed.
+    fn deliver(
+        &self,
+        job: DeliveryJob,
+    ) -> impl Future<Output = Result<DeliveryOutcome, CoreError>> + Send;
+
+    fn prune_deliveries(&self) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
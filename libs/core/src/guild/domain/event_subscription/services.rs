@@ -0,0 +1,477 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    event_subscription::{EventDelivery, EventSubscription, GuildEventType},
+    guild::GuildId,
+};
+use ferriscord_permission::{Permissions, require_permission};
+use hmac::{Hmac, Mac};
+use rand::distributions::{Alphanumeric, DistString};
+use serde_json::json;
+use sha2::Sha256;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    common::build_permission_context, errors::CoreError, guild::ports::GuildPort,
+    member::ports::MemberRepository, role::ports::RoleRepository,
+};
+
+use super::ports::{
+    BASE_RETRY_DELAY, CreateEventSubscriptionInput, DELIVERY_HEADER, DELIVERY_LEASE,
+    DELIVERY_RETENTION, DISABLE_AFTER_FAILURES, DeliveryJob, DeliveryOutcome, EVENT_HEADER,
+    EventSender, EventSubscriptionRepository, EventSubscriptionService, MAX_DELIVERY_ATTEMPTS,
+    MAX_RETRY_DELAY, MAX_SUBSCRIPTION_URL_LEN, MAX_SUBSCRIPTIONS_PER_GUILD, SIGNATURE_HEADER,
+    SignedRequest, TIMESTAMP_HEADER, UpdateEventSubscriptionInput,
+};
+
+const SECRET_LEN: usize = 48;
+const MAX_LISTED_DELIVERIES: u32 = 100;
+
+#[derive(Clone)]
+pub struct EventSubscriptionServiceImpl<G, R, M, E, S>
+where
+    G: GuildPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    E: EventSubscriptionRepository,
+    S: EventSender,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) event_subscription_repository: E,
+    pub(crate) event_sender: S,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidEventSubscription {
+        message: message.into(),
+    }
+}
+
+fn mac(secret: &str, timestamp: i64, body: &str) -> Hmac<Sha256> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body.as_bytes());
+    mac
+}
+
+/// The `X-Ferriscord-Signature` value for a body sent at `timestamp`.
+pub fn sign_payload(secret: &str, timestamp: i64, body: &str) -> String {
+    format!(
+        "sha256={:x}",
+        mac(secret, timestamp, body).finalize().into_bytes()
+    )
+}
+
+/// Checks a `X-Ferriscord-Signature` value in constant time, as a receiver
+/// would.
+pub fn verify_signature(secret: &str, timestamp: i64, body: &str, signature: &str) -> bool {
+    let Some(hex) = signature.strip_prefix("sha256=") else {
+        return false;
+    };
+    if hex.len() % 2 != 0 || !hex.is_ascii() {
+        return false;
+    }
+    let bytes: Option<Vec<u8>> = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect();
+    bytes.is_some_and(|bytes| mac(secret, timestamp, body).verify_slice(&bytes).is_ok())
+}
+
+/// When to retry after the given number of attempts, or `None` when the
+/// delivery is out of attempts.
+fn retry_delay(attempts: i32) -> Option<TimeDelta> {
+    if attempts >= MAX_DELIVERY_ATTEMPTS {
+        return None;
+    }
+    let exponent = attempts.saturating_sub(1).clamp(0, 16) as u32;
+    Some((BASE_RETRY_DELAY * 2_i32.pow(exponent)).min(MAX_RETRY_DELAY))
+}
+
+fn validate_url(url: String) -> Result<String, CoreError> {
+    let url = url.trim().to_string();
+    if url.len() > MAX_SUBSCRIPTION_URL_LEN {
+        return Err(invalid(format!(
+            "url must be at most {MAX_SUBSCRIPTION_URL_LEN} characters"
+        )));
+    }
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| invalid("url must be an http or https URL"))?;
+    if rest.is_empty() || rest.starts_with('/') || url.chars().any(char::is_whitespace) {
+        return Err(invalid("url must have a host"));
+    }
+    Ok(url)
+}
+
+fn validate_event_types(
+    mut event_types: Vec<GuildEventType>,
+) -> Result<Vec<GuildEventType>, CoreError> {
+    if event_types.is_empty() {
+        return Err(invalid("at least one event type is required"));
+    }
+    let mut seen = Vec::with_capacity(event_types.len());
+    event_types.retain(|t| {
+        let new = !seen.contains(t);
+        seen.push(*t);
+        new
+    });
+    Ok(event_types)
+}
+
+/// The JSON body posted for an event; built once and shared by every
+/// subscription receiving it.
+fn envelope(guild_id: Uuid, event_type: &str, data: serde_json::Value) -> String {
+    json!({
+        "id": Uuid::now_v7(),
+        "type": event_type,
+        "guild_id": guild_id,
+        "created_at": Utc::now(),
+        "data": data,
+    })
+    .to_string()
+}
+
+fn signed_request(job: &DeliveryJob, now: DateTime<Utc>) -> SignedRequest {
+    let timestamp = now.timestamp();
+    let body = job.delivery.body.clone();
+    SignedRequest {
+        url: job.url.clone(),
+        headers: vec![
+            (
+                SIGNATURE_HEADER,
+                sign_payload(&job.secret, timestamp, &body),
+            ),
+            (TIMESTAMP_HEADER, timestamp.to_string()),
+            (EVENT_HEADER, job.delivery.event_type.clone()),
+            (DELIVERY_HEADER, job.delivery.id.to_string()),
+        ],
+        body,
+    }
+}
+
+impl<G, R, M, E, S> EventSubscriptionServiceImpl<G, R, M, E, S>
+where
+    G: GuildPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    E: EventSubscriptionRepository,
+    S: EventSender,
+{
+    async fn require_manage_guild(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+    ) -> Result<(), CoreError> {
+        let mut permission_context = build_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            identity,
+            guild_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, Permissions::MANAGE_GUILD);
+        Ok(())
+    }
+
+    /// Validates a subscription URL, which deliveries must be allowed to
+    /// reach.
+    fn checked_url(&self, url: String) -> Result<String, CoreError> {
+        let url = validate_url(url)?;
+        self.event_sender
+            .check_url(&url)
+            .map_err(|reason| invalid(format!("url is not allowed: {reason}")))?;
+        Ok(url)
+    }
+
+    /// Loads a subscription of the guild the caller may manage.
+    async fn managed_subscription(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        subscription_id: Uuid,
+    ) -> Result<EventSubscription, CoreError> {
+        self.require_manage_guild(identity, guild_id).await?;
+        self.event_subscription_repository
+            .find_by_id(subscription_id)
+            .await?
+            .filter(|s| s.guild_id == *guild_id.get_uuid())
+            .ok_or(CoreError::EventSubscriptionNotFound)
+    }
+}
+
+impl<G, R, M, E, S> EventSubscriptionService for EventSubscriptionServiceImpl<G, R, M, E, S>
+where
+    G: GuildPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    E: EventSubscriptionRepository,
+    S: EventSender,
+{
+    async fn create_subscription(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        input: CreateEventSubscriptionInput,
+    ) -> Result<EventSubscription, CoreError> {
+        self.require_manage_guild(&identity, &guild_id).await?;
+
+        let subscription = EventSubscription {
+            id: Uuid::now_v7(),
+            guild_id: *guild_id.get_uuid(),
+            url: self.checked_url(input.url)?,
+            event_types: validate_event_types(input.event_types)?,
+            enabled: true,
+            consecutive_failures: 0,
+            disabled_reason: None,
+            created_by: Some(user_id),
+            created_at: Utc::now(),
+            secret: None,
+        };
+
+        if self
+            .event_subscription_repository
+            .count_by_guild(subscription.guild_id)
+            .await?
+            >= MAX_SUBSCRIPTIONS_PER_GUILD
+        {
+            return Err(CoreError::MaxEventSubscriptionsReached {
+                guild_id,
+                max_subscriptions: MAX_SUBSCRIPTIONS_PER_GUILD,
+            });
+        }
+
+        let secret = Alphanumeric.sample_string(&mut rand::thread_rng(), SECRET_LEN);
+        let subscription = self
+            .event_subscription_repository
+            .insert(&subscription, &secret)
+            .await?;
+        Ok(EventSubscription {
+            secret: Some(secret),
+            ..subscription
+        })
+    }
+
+    async fn list_subscriptions(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+    ) -> Result<Vec<EventSubscription>, CoreError> {
+        self.require_manage_guild(&identity, &guild_id).await?;
+        self.event_subscription_repository
+            .list_by_guild(*guild_id.get_uuid())
+            .await
+    }
+
+    async fn get_subscription(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+    ) -> Result<EventSubscription, CoreError> {
+        self.managed_subscription(&identity, &guild_id, subscription_id)
+            .await
+    }
+
+    async fn update_subscription(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+        input: UpdateEventSubscriptionInput,
+    ) -> Result<EventSubscription, CoreError> {
+        self.managed_subscription(&identity, &guild_id, subscription_id)
+            .await?;
+
+        let input = UpdateEventSubscriptionInput {
+            url: input.url.map(|url| self.checked_url(url)).transpose()?,
+            event_types: input.event_types.map(validate_event_types).transpose()?,
+            enabled: input.enabled,
+        };
+
+        self.event_subscription_repository
+            .update(subscription_id, input)
+            .await?
+            .ok_or(CoreError::EventSubscriptionNotFound)
+    }
+
+    async fn delete_subscription(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+    ) -> Result<(), CoreError> {
+        self.managed_subscription(&identity, &guild_id, subscription_id)
+            .await?;
+
+        if !self
+            .event_subscription_repository
+            .delete(subscription_id)
+            .await?
+        {
+            return Err(CoreError::EventSubscriptionNotFound);
+        }
+        Ok(())
+    }
+
+    async fn list_deliveries(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+        limit: u32,
+    ) -> Result<Vec<EventDelivery>, CoreError> {
+        self.managed_subscription(&identity, &guild_id, subscription_id)
+            .await?;
+
+        self.event_subscription_repository
+            .list_deliveries(
+                subscription_id,
+                limit.clamp(1, MAX_LISTED_DELIVERIES) as i64,
+            )
+            .await
+    }
+
+    async fn ping_subscription(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        subscription_id: Uuid,
+    ) -> Result<EventDelivery, CoreError> {
+        let subscription = self
+            .managed_subscription(&identity, &guild_id, subscription_id)
+            .await?;
+        if !subscription.enabled {
+            return Err(invalid("the subscription is disabled"));
+        }
+
+        let body = envelope(
+            subscription.guild_id,
+            "ping",
+            json!({ "subscription_id": subscription.id }),
+        );
+        self.event_subscription_repository
+            .enqueue_delivery(subscription.id, "ping", &body)
+            .await
+    }
+
+    async fn publish_event(
+        &self,
+        guild_id: Uuid,
+        event_type: GuildEventType,
+        data: serde_json::Value,
+    ) -> Result<u64, CoreError> {
+        let body = envelope(guild_id, event_type.as_str(), data);
+        self.event_subscription_repository
+            .enqueue_event(guild_id, event_type, &body)
+            .await
+    }
+
+    async fn claim_due_deliveries(&self, limit: i64) -> Result<Vec<DeliveryJob>, CoreError> {
+        let now = Utc::now();
+        self.event_subscription_repository
+            .claim_due(now, now + DELIVERY_LEASE, limit)
+            .await
+    }
+
+    async fn deliver(&self, job: DeliveryJob) -> Result<DeliveryOutcome, CoreError> {
+        let now = Utc::now();
+        let outcome = self.event_sender.send(signed_request(&job, now)).await;
+
+        match &outcome {
+            DeliveryOutcome::Delivered { status_code } => {
+                self.event_subscription_repository
+                    .record_success(job.delivery.id, *status_code, Utc::now())
+                    .await?;
+            }
+            DeliveryOutcome::Failed { status_code, error } => {
+                let retry_at = retry_delay(job.delivery.attempts).map(|delay| now + delay);
+                let disabled = self
+                    .event_subscription_repository
+                    .record_failure(
+                        job.delivery.id,
+                        *status_code,
+                        error,
+                        retry_at,
+                        DISABLE_AFTER_FAILURES,
+                    )
+                    .await?;
+                if disabled {
+                    warn!(
+                        "event subscription {} disabled after {} failed deliveries",
+                        job.delivery.subscription_id, DISABLE_AFTER_FAILURES
+                    );
+                }
+            }
+        }
+        Ok(outcome)
+    }
+
+    async fn prune_deliveries(&self) -> Result<u64, CoreError> {
+        self.event_subscription_repository
+            .prune_deliveries(Utc::now() - DELIVERY_RETENTION)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_and_backoff() {
+        let signature = sign_payload("secret", 1_700_000_000, r#"{"type":"ping"}"#);
+        assert!(signature.starts_with("sha256="));
+        assert!(verify_signature(
+            "secret",
+            1_700_000_000,
+            r#"{"type":"ping"}"#,
+            &signature
+        ));
+        assert!(!verify_signature(
+            "other",
+            1_700_000_000,
+            r#"{"type":"ping"}"#,
+            &signature
+        ));
+        assert!(!verify_signature(
+            "secret",
+            1_700_000_001,
+            r#"{"type":"ping"}"#,
+            &signature
+        ));
+        assert!(!verify_signature("secret", 1_700_000_000, "{}", &signature));
+        assert!(!verify_signature(
+            "secret",
+            1_700_000_000,
+            "{}",
+            "sha256=zz"
+        ));
+
+        assert_eq!(retry_delay(1), Some(TimeDelta::seconds(10)));
+        assert_eq!(retry_delay(3), Some(TimeDelta::seconds(40)));
+        assert_eq!(
+            retry_delay(MAX_DELIVERY_ATTEMPTS - 1),
+            Some(TimeDelta::seconds(640))
+        );
+        assert_eq!(retry_delay(MAX_DELIVERY_ATTEMPTS), None);
+
+        assert!(validate_url("https://hooks.example/ferriscord".to_string()).is_ok());
+        assert!(validate_url("ftp://hooks.example".to_string()).is_err());
+        assert!(validate_url("https:///path".to_string()).is_err());
+        assert_eq!(
+            validate_event_types(vec![GuildEventType::MessageCreate; 2]).unwrap(),
+            vec![GuildEventType::MessageCreate]
+        );
+        assert!(validate_event_types(Vec::new()).is_err());
+    }
+}
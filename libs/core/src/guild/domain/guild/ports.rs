@@ -6,6 +6,9 @@ use ferriscord_entities::{
 
 use crate::guild::domain::{errors::CoreError, guild::entities::{CreateGuildInput, UpdateGuildInput}};
 
+/// Guilds a user may own.
+pub const MAX_GUILDS_PER_OWNER: usize = 10;
+
 pub trait GuildPort: Send + Sync {
     fn insert(
         &self,
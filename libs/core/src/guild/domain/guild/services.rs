@@ -8,7 +8,7 @@ use crate::guild::domain::{
     errors::CoreError,
     guild::{
         entities::{CreateGuildInput, UpdateGuildInput},
-        ports::{GuildPort, GuildService},
+        ports::{GuildPort, GuildService, MAX_GUILDS_PER_OWNER},
     },
     member::ports::MemberRepository,
     role::ports::RoleRepository,
@@ -35,8 +35,10 @@ where
     async fn create_guild(&self, input: CreateGuildInput) -> Result<Guild, CoreError> {
         let guilds = self.guild_repository.list_by_owner(&input.owner_id).await?;
 
-        if guilds.len() >= 10 {
-            return Err(CoreError::MaxGuildsReached { max_guilds: 10 });
+        if guilds.len() >= MAX_GUILDS_PER_OWNER {
+            return Err(CoreError::MaxGuildsReached {
+                max_guilds: MAX_GUILDS_PER_OWNER,
+            });
         }
 
         let owner_user_id = input.owner_user_id.clone();
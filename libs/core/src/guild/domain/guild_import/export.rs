@@ -0,0 +1,500 @@
+//! Discord exports in the JSON format of DiscordChatExporter: one document
+//! per exported channel. Only what an import recreates is read.
+
+use chrono::{DateTime, Utc};
+use ferriscord_entities::{channel::ChannelKind, guild_import::GuildImportProgress};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
+use uuid::{Builder, Uuid};
+
+use crate::guild::domain::errors::CoreError;
+
+use super::ports::{
+    MAX_IMPORT_CHANNELS, MAX_IMPORT_MESSAGES, MAX_IMPORT_ROLES, StagedAttachment, StagedMessage,
+};
+
+/// Longest name kept for imported channels, roles and users.
+const MAX_NAME_LEN: usize = 100;
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ExportGuild {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportChannel {
+    pub id: String,
+    /// e.g. `GuildTextChat`, `GuildVoiceChat` or `GuildNews`.
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub category_id: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub topic: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ExportRole {
+    pub id: String,
+    pub name: String,
+    /// `#RRGGBB`, or none for the default color.
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAuthor {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub nickname: Option<String>,
+    #[serde(default)]
+    pub roles: Vec<ExportRole>,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportAttachment {
+    pub id: String,
+    pub url: String,
+    pub file_name: String,
+    #[serde(default)]
+    pub file_size_bytes: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportMessage {
+    pub id: String,
+    /// e.g. `Default`, `Reply` or `GuildMemberJoin`.
+    #[serde(rename = "type", default = "default_message_kind")]
+    pub kind: String,
+    pub timestamp: DateTime<Utc>,
+    #[serde(default)]
+    pub timestamp_edited: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub content: String,
+    pub author: ExportAuthor,
+    #[serde(default)]
+    pub attachments: Vec<ExportAttachment>,
+}
+
+fn default_message_kind() -> String {
+    "Default".to_string()
+}
+
+/// One exported channel with its messages.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+pub struct ExportDocument {
+    pub guild: ExportGuild,
+    pub channel: ExportChannel,
+    #[serde(default)]
+    pub messages: Vec<ExportMessage>,
+}
+
+/// The FerrisCord kind of an exported channel. Threads, forum posts and
+/// direct messages have none.
+pub fn channel_kind(kind: &str) -> Option<ChannelKind> {
+    match kind {
+        "GuildTextChat" => Some(ChannelKind::Text),
+        "GuildNews" | "GuildAnnouncement" => Some(ChannelKind::Announcement),
+        "GuildVoiceChat" => Some(ChannelKind::Voice),
+        "GuildStageVoice" => Some(ChannelKind::Stage),
+        "GuildForum" => Some(ChannelKind::Forum),
+        _ => None,
+    }
+}
+
+/// Whether a message is written by its author, as opposed to a system
+/// message such as a join or a pin.
+fn is_imported_message(kind: &str) -> bool {
+    matches!(kind, "Default" | "Reply")
+}
+
+/// `#RRGGBB` as a color, 0 being the default one.
+pub fn parse_color(color: Option<&str>) -> u32 {
+    color
+        .and_then(|color| color.strip_prefix('#'))
+        .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+        .filter(|color| *color <= 0xFF_FF_FF)
+        .unwrap_or(0)
+}
+
+fn truncate(name: &str) -> String {
+    name.trim().chars().take(MAX_NAME_LEN).collect()
+}
+
+fn digest(import_id: Uuid, kind: &str, source_id: &str) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(import_id.as_bytes())
+        .chain_update(kind.as_bytes())
+        .chain_update(b":")
+        .chain_update(source_id.as_bytes())
+        .finalize()
+        .into()
+}
+
+/// The id of what an import creates for a Discord object, the same every
+/// time the import is resumed.
+pub fn import_uuid(import_id: Uuid, kind: &str, source_id: &str) -> Uuid {
+    let digest = digest(import_id, kind, source_id);
+    let mut bytes = [0u8; 16];
+    bytes.copy_from_slice(&digest[..16]);
+    Builder::from_random_bytes(bytes).into_uuid()
+}
+
+/// Like [`import_uuid`], as a UUIDv7 of the time the message was sent, so
+/// imported messages sort like the others.
+pub fn message_uuid(import_id: Uuid, source_id: &str, created_at: DateTime<Utc>) -> Uuid {
+    let digest = digest(import_id, "message", source_id);
+    let mut bytes = [0u8; 10];
+    bytes.copy_from_slice(&digest[..10]);
+    let millis = created_at.timestamp_millis().max(0) as u64;
+    Builder::from_unix_timestamp_millis(millis, &bytes).into_uuid()
+}
+
+/// The username of a placeholder user. Usernames are unique, so it ends
+/// with the start of the user's id.
+pub fn placeholder_username(name: &str, user_id: Uuid) -> String {
+    let id = user_id.simple().to_string();
+    format!("{}.{}", truncate(name), &id[..8])
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestCategory {
+    pub source_id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestChannel {
+    pub source_id: String,
+    pub kind: ChannelKind,
+    pub name: String,
+    pub topic: Option<String>,
+    pub category_source_id: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestRole {
+    pub source_id: String,
+    pub name: String,
+    pub color: u32,
+    pub position: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestAuthor {
+    pub source_id: String,
+    pub name: String,
+    pub display_name: Option<String>,
+}
+
+/// The structure of the exported guild, gathered from its channels as they
+/// are uploaded, in upload order. Roles are those of message authors:
+/// exports have no other.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportManifest {
+    pub source_guild_id: Option<String>,
+    pub source_guild_name: Option<String>,
+    pub categories: Vec<ManifestCategory>,
+    pub channels: Vec<ManifestChannel>,
+    pub roles: Vec<ManifestRole>,
+    pub authors: Vec<ManifestAuthor>,
+    pub messages: u64,
+    pub skipped_messages: u64,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidGuildImport {
+        message: message.into(),
+    }
+}
+
+impl ImportManifest {
+    /// Adds an exported channel, and returns its messages to stage.
+    pub fn add_channel(&mut self, export: ExportDocument) -> Result<Vec<StagedMessage>, CoreError> {
+        let ExportDocument {
+            guild,
+            channel,
+            messages,
+        } = export;
+
+        match &self.source_guild_id {
+            Some(id) if *id != guild.id => {
+                return Err(invalid("all channels must come from the same guild"));
+            }
+            Some(_) => {}
+            None => {
+                self.source_guild_id = Some(guild.id);
+                self.source_guild_name = Some(truncate(&guild.name));
+            }
+        }
+        let kind = channel_kind(&channel.kind).ok_or_else(|| {
+            invalid(format!(
+                "channel {} is a {}, which cannot be imported",
+                channel.name, channel.kind
+            ))
+        })?;
+        if self.channels.iter().any(|c| c.source_id == channel.id) {
+            return Err(invalid(format!(
+                "channel {} was already uploaded",
+                channel.name
+            )));
+        }
+        if self.channels.len() >= MAX_IMPORT_CHANNELS {
+            return Err(invalid(format!(
+                "an import has at most {MAX_IMPORT_CHANNELS} channels"
+            )));
+        }
+        if self.messages + messages.len() as u64 > MAX_IMPORT_MESSAGES {
+            return Err(invalid(format!(
+                "an import has at most {MAX_IMPORT_MESSAGES} messages"
+            )));
+        }
+
+        if let Some(category_id) = &channel.category_id
+            && !self.categories.iter().any(|c| c.source_id == *category_id)
+        {
+            self.categories.push(ManifestCategory {
+                source_id: category_id.clone(),
+                name: truncate(channel.category.as_deref().unwrap_or("Imported")),
+            });
+        }
+        self.channels.push(ManifestChannel {
+            source_id: channel.id.clone(),
+            kind,
+            name: truncate(&channel.name),
+            topic: channel.topic.filter(|topic| !topic.trim().is_empty()),
+            category_source_id: channel.category_id,
+        });
+
+        let mut staged = Vec::with_capacity(messages.len());
+        for message in messages {
+            if !is_imported_message(&message.kind) {
+                self.skipped_messages += 1;
+                continue;
+            }
+            self.add_author(&message.author)?;
+            staged.push(StagedMessage {
+                seq: 0,
+                source_id: message.id,
+                channel_source_id: channel.id.clone(),
+                author_source_id: message.author.id,
+                content: message.content,
+                attachments: message
+                    .attachments
+                    .into_iter()
+                    .map(|attachment| StagedAttachment {
+                        source_id: attachment.id,
+                        url: attachment.url,
+                        filename: attachment.file_name,
+                        size_bytes: attachment.file_size_bytes,
+                    })
+                    .collect(),
+                created_at: message.timestamp,
+                edited_at: message.timestamp_edited,
+            });
+        }
+        self.messages += staged.len() as u64;
+        Ok(staged)
+    }
+
+    fn add_author(&mut self, author: &ExportAuthor) -> Result<(), CoreError> {
+        for role in &author.roles {
+            if self.roles.iter().any(|r| r.source_id == role.id) {
+                continue;
+            }
+            if self.roles.len() >= MAX_IMPORT_ROLES {
+                return Err(invalid(format!(
+                    "an import has at most {MAX_IMPORT_ROLES} roles"
+                )));
+            }
+            self.roles.push(ManifestRole {
+                source_id: role.id.clone(),
+                name: truncate(&role.name),
+                color: parse_color(role.color.as_deref()),
+                position: role.position,
+            });
+        }
+        if !self.authors.iter().any(|a| a.source_id == author.id) {
+            self.authors.push(ManifestAuthor {
+                source_id: author.id.clone(),
+                name: truncate(&author.name),
+                display_name: author
+                    .nickname
+                    .as_deref()
+                    .map(truncate)
+                    .filter(|name| !name.is_empty()),
+            });
+        }
+        Ok(())
+    }
+
+    /// What the import will create, before any of it is.
+    pub fn progress(&self, is_mapped: impl Fn(&str) -> bool) -> GuildImportProgress {
+        let mapped_users = self
+            .authors
+            .iter()
+            .filter(|author| is_mapped(&author.source_id))
+            .count();
+        GuildImportProgress {
+            categories: self.categories.len() as u32,
+            channels: self.channels.len() as u32,
+            roles: self.roles.len() as u32,
+            mapped_users: mapped_users as u32,
+            placeholder_users: (self.authors.len() - mapped_users) as u32,
+            messages_total: self.messages,
+            messages_skipped: self.skipped_messages,
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn export(channel_id: &str, kind: &str) -> ExportDocument {
+        serde_json::from_value(serde_json::json!({
+            "guild": { "id": "1", "name": "Rustaceans", "iconUrl": "https://cdn.example/icon.png" },
+            "channel": {
+                "id": channel_id,
+                "type": kind,
+                "categoryId": "10",
+                "category": "Text Channels",
+                "name": "general",
+                "topic": "",
+            },
+            "messages": [
+                {
+                    "id": "100",
+                    "type": "Default",
+                    "timestamp": "2021-03-04T05:06:07.123+01:00",
+                    "timestampEdited": null,
+                    "content": "hello",
+                    "author": {
+                        "id": "7",
+                        "name": "ferris",
+                        "discriminator": "0000",
+                        "nickname": "Ferris",
+                        "isBot": false,
+                        "roles": [{ "id": "20", "name": "Crab", "color": "#FF8000", "position": 2 }],
+                    },
+                    "attachments": [
+                        { "id": "30", "url": "https://cdn.example/a.png", "fileName": "a.png", "fileSizeBytes": 42 },
+                    ],
+                    "reactions": [],
+                },
+                {
+                    "id": "101",
+                    "type": "GuildMemberJoin",
+                    "timestamp": "2021-03-04T05:07:00+00:00",
+                    "content": "",
+                    "author": { "id": "8", "name": "newcomer" },
+                },
+            ],
+            "messageCount": 2,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_add_channel() {
+        let mut manifest = ImportManifest::default();
+        let staged = manifest.add_channel(export("2", "GuildTextChat")).unwrap();
+
+        assert_eq!(manifest.source_guild_name.as_deref(), Some("Rustaceans"));
+        assert_eq!(manifest.categories.len(), 1);
+        assert_eq!(manifest.channels[0].kind, ChannelKind::Text);
+        assert_eq!(manifest.channels[0].topic, None);
+        assert_eq!(
+            manifest.channels[0].category_source_id.as_deref(),
+            Some("10")
+        );
+        assert_eq!(manifest.roles[0].color, 0xFF8000);
+        // The join message is skipped, and so is its author.
+        assert_eq!(manifest.authors.len(), 1);
+        assert_eq!(manifest.authors[0].display_name.as_deref(), Some("Ferris"));
+        assert_eq!((manifest.messages, manifest.skipped_messages), (1, 1));
+
+        assert_eq!(staged.len(), 1);
+        assert_eq!(
+            staged[0].created_at,
+            Utc.with_ymd_and_hms(2021, 3, 4, 4, 6, 7).unwrap()
+                + chrono::TimeDelta::milliseconds(123)
+        );
+        assert_eq!(staged[0].attachments[0].size_bytes, 42);
+
+        let progress = manifest.progress(|id| id == "7");
+        assert_eq!((progress.mapped_users, progress.placeholder_users), (1, 0));
+    }
+
+    #[test]
+    fn test_add_channel_rejected() {
+        let mut manifest = ImportManifest::default();
+        manifest.add_channel(export("2", "GuildTextChat")).unwrap();
+
+        assert!(manifest.add_channel(export("2", "GuildTextChat")).is_err());
+        assert!(
+            manifest
+                .add_channel(export("3", "GuildPublicThread"))
+                .is_err()
+        );
+        let mut other_guild = export("4", "GuildTextChat");
+        other_guild.guild.id = "9".to_string();
+        assert!(manifest.add_channel(other_guild).is_err());
+        // Nothing was added by the rejected channels.
+        assert_eq!(manifest.channels.len(), 1);
+        assert_eq!(manifest.messages, 1);
+    }
+
+    #[test]
+    fn test_parse_color() {
+        assert_eq!(parse_color(Some("#FF8000")), 0xFF8000);
+        assert_eq!(parse_color(Some("FF8000")), 0);
+        assert_eq!(parse_color(Some("#nope")), 0);
+        assert_eq!(parse_color(None), 0);
+    }
+
+    #[test]
+    fn test_import_ids_are_stable() {
+        let import_id = Uuid::now_v7();
+        assert_eq!(
+            import_uuid(import_id, "role", "20"),
+            import_uuid(import_id, "role", "20")
+        );
+        assert_ne!(
+            import_uuid(import_id, "role", "20"),
+            import_uuid(import_id, "channel", "20")
+        );
+        assert_ne!(
+            import_uuid(import_id, "role", "20"),
+            import_uuid(Uuid::now_v7(), "role", "20")
+        );
+
+        let sent = Utc.with_ymd_and_hms(2021, 3, 4, 5, 6, 7).unwrap();
+        let id = message_uuid(import_id, "100", sent);
+        assert_eq!(id, message_uuid(import_id, "100", sent));
+        assert_eq!(id.get_version_num(), 7);
+        let (seconds, _) = id.get_timestamp().unwrap().to_unix();
+        assert_eq!(seconds as i64, sent.timestamp());
+    }
+
+    #[test]
+    fn test_placeholder_username() {
+        let id = Uuid::parse_str("0123456789abcdef0123456789abcdef").unwrap();
+        assert_eq!(placeholder_username(" ferris ", id), "ferris.01234567");
+    }
+}
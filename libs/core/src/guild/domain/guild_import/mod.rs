@@ -0,0 +1,5 @@
+pub mod export;
+pub mod ports;
+mod services;
+
+pub use services::GuildImportServiceImpl;
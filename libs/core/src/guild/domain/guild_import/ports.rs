@@ -0,0 +1,380 @@
+use std::{collections::HashMap, time::Duration};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    channel::ChannelKind,
+    guild::Guild,
+    guild_import::{GuildImport, GuildImportProgress},
+};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+use super::export::{ExportDocument, ImportManifest, import_uuid, message_uuid};
+
+/// Imports per user that are not finished yet.
+pub const MAX_ACTIVE_IMPORTS_PER_USER: i64 = 3;
+pub const MAX_IMPORT_CHANNELS: usize = 500;
+pub const MAX_IMPORT_ROLES: usize = 250;
+pub const MAX_IMPORT_MESSAGES: u64 = 1_000_000;
+pub const MAX_USER_MAPPINGS: usize = 10_000;
+/// Largest exported channel that can be uploaded.
+pub const MAX_EXPORT_BYTES: usize = 100 * 1024 * 1024;
+/// Larger attachments are skipped.
+pub const MAX_IMPORTED_ATTACHMENT_BYTES: i64 = 25 * 1024 * 1024;
+pub const ATTACHMENT_FETCH_TIMEOUT: Duration = Duration::from_secs(60);
+/// How long a claimed import stays reserved for the replica running it. It
+/// is renewed with every batch of messages; past that (e.g. the replica
+/// died) the import is resumed by another one.
+pub const IMPORT_LEASE: TimeDelta = TimeDelta::minutes(2);
+/// How long finished imports are kept for their owner to see.
+pub const IMPORT_RETENTION: TimeDelta = TimeDelta::days(7);
+/// Imports never started are dropped after this long.
+pub const UPLOAD_TIMEOUT: TimeDelta = TimeDelta::days(1);
+/// The subject of placeholder users, followed by the import and the Discord
+/// id of the author. Like bot subjects, it never matches a login.
+pub const PLACEHOLDER_SUBJECT_PREFIX: &str = "import:";
+
+pub struct CreateImportInput {
+    /// Defaults to the name of the exported guild.
+    pub name: Option<String>,
+    pub dry_run: bool,
+    /// Discord user ids of the importer, whose messages are imported as
+    /// theirs.
+    pub user_mappings: HashMap<String, Uuid>,
+}
+
+/// An attachment of a staged message, as found in the export.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StagedAttachment {
+    pub source_id: String,
+    pub url: String,
+    pub filename: String,
+    pub size_bytes: i64,
+}
+
+impl StagedAttachment {
+    /// Whether it can be downloaded: exports made with their media have
+    /// local paths instead of URLs.
+    pub fn is_importable(&self) -> bool {
+        (self.url.starts_with("https://") || self.url.starts_with("http://"))
+            && self.size_bytes <= MAX_IMPORTED_ATTACHMENT_BYTES
+    }
+}
+
+/// An exported message waiting to be imported.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StagedMessage {
+    /// Assigned when staged; messages are imported in this order.
+    pub seq: i64,
+    pub source_id: String,
+    pub channel_source_id: String,
+    pub author_source_id: String,
+    pub content: String,
+    pub attachments: Vec<StagedAttachment>,
+    pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+pub struct ImportedAttachment {
+    pub id: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub storage_key: String,
+}
+
+pub struct ImportedMessage {
+    pub id: Uuid,
+    pub channel_id: Uuid,
+    pub author_id: Uuid,
+    pub content: String,
+    pub attachments: Vec<ImportedAttachment>,
+    pub created_at: DateTime<Utc>,
+    pub edited_at: Option<DateTime<Utc>>,
+}
+
+pub struct ImportedRole {
+    pub id: Uuid,
+    pub name: String,
+    pub color: u32,
+    pub position: i32,
+}
+
+pub struct ImportedChannel {
+    pub id: Uuid,
+    pub kind: ChannelKind,
+    pub name: String,
+    pub topic: Option<String>,
+    pub parent_id: Option<Uuid>,
+    pub position: i32,
+}
+
+pub struct PlaceholderUser {
+    pub id: Uuid,
+    pub oauth_sub: String,
+    pub username: String,
+    pub display_name: Option<String>,
+}
+
+/// Everything an import creates before its messages, in one go.
+pub struct ImportStructure {
+    pub guild: Guild,
+    pub owner_user_id: Uuid,
+    pub everyone_role_id: Uuid,
+    pub roles: Vec<ImportedRole>,
+    /// Categories first.
+    pub channels: Vec<ImportedChannel>,
+    pub users: Vec<PlaceholderUser>,
+}
+
+/// A claimed import, with what it is imported from.
+pub struct ImportJob {
+    pub import: GuildImport,
+    /// The owner's subject, as guilds store it.
+    pub owner_id: Uuid,
+    pub user_mappings: HashMap<String, Uuid>,
+    pub manifest: ImportManifest,
+    /// The last staged message imported.
+    pub cursor: i64,
+}
+
+impl ImportJob {
+    pub fn channel_id(&self, channel_source_id: &str) -> Uuid {
+        import_uuid(self.import.id, "channel", channel_source_id)
+    }
+
+    /// The mapped user of an author, or their placeholder.
+    pub fn author_id(&self, author_source_id: &str) -> Uuid {
+        self.user_mappings
+            .get(author_source_id)
+            .copied()
+            .unwrap_or_else(|| import_uuid(self.import.id, "user", author_source_id))
+    }
+
+    pub fn message_id(&self, message: &StagedMessage) -> Uuid {
+        message_uuid(self.import.id, &message.source_id, message.created_at)
+    }
+
+    pub fn attachment_id(&self, attachment: &StagedAttachment) -> Uuid {
+        import_uuid(self.import.id, "attachment", &attachment.source_id)
+    }
+}
+
+pub struct FetchedAttachment {
+    pub data: Vec<u8>,
+    pub content_type: Option<String>,
+}
+
+pub trait ImportAttachmentFetcher: Send + Sync {
+    /// Downloads an attachment of an export, up to `max_bytes`.
+    fn fetch(
+        &self,
+        url: &str,
+        max_bytes: usize,
+    ) -> impl Future<Output = Result<FetchedAttachment, String>> + Send;
+}
+
+pub trait GuildImportRepository: Send + Sync {
+    fn insert(
+        &self,
+        import: &GuildImport,
+        owner_id: Uuid,
+        user_mappings: &HashMap<String, Uuid>,
+    ) -> impl Future<Output = Result<GuildImport, CoreError>> + Send;
+
+    fn find_by_id(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<ImportJob>, CoreError>> + Send;
+
+    fn list_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<GuildImport>, CoreError>> + Send;
+
+    fn count_active_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<i64, CoreError>> + Send;
+
+    fn slug_exists(&self, slug: &str) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Adds an exported channel to an uploading import of `user_id`: its
+    /// structure to the manifest, its messages to the staged ones. Returns
+    /// `None` if there is no such import.
+    fn stage_channel(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        export: ExportDocument,
+    ) -> impl Future<Output = Result<Option<GuildImport>, CoreError>> + Send;
+
+    /// Queues an uploading import. Returns `None` if it is gone or no longer
+    /// uploading.
+    fn start(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<GuildImport>, CoreError>> + Send;
+
+    /// Cancels an unfinished import and drops its staged messages. What was
+    /// already imported is kept.
+    fn cancel(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<GuildImport>, CoreError>> + Send;
+
+    /// Reserves the oldest queued import, or one whose lease ran out, until
+    /// `lease_until`.
+    fn claim(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Option<ImportJob>, CoreError>> + Send;
+
+    /// Creates the guild, its roles and channels and the placeholder users
+    /// in one transaction. Returns false if the import is no longer running.
+    fn create_structure(
+        &self,
+        id: Uuid,
+        structure: &ImportStructure,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    fn list_staged(
+        &self,
+        id: Uuid,
+        after_seq: i64,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<StagedMessage>, CoreError>> + Send;
+
+    /// Writes imported messages, moves the cursor past them and renews the
+    /// lease, in one transaction. Messages already written are left alone.
+    /// Returns `None` if the import is no longer running.
+    fn record_messages(
+        &self,
+        id: Uuid,
+        messages: &[ImportedMessage],
+        cursor: i64,
+        progress: &GuildImportProgress,
+        lease_until: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Option<GuildImport>, CoreError>> + Send;
+
+    /// Marks a running import completed and drops its staged messages.
+    fn finish(
+        &self,
+        id: Uuid,
+        progress: &GuildImportProgress,
+    ) -> impl Future<Output = Result<Option<GuildImport>, CoreError>> + Send;
+
+    /// Marks a running import failed and drops its staged messages.
+    fn fail(
+        &self,
+        id: Uuid,
+        reason: &str,
+    ) -> impl Future<Output = Result<Option<GuildImport>, CoreError>> + Send;
+
+    /// Deletes imports finished before `finished_before`, and those still
+    /// uploading since before `created_before`.
+    fn prune(
+        &self,
+        finished_before: DateTime<Utc>,
+        created_before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
+
+pub trait GuildImportService: Send + Sync {
+    /// Creates an import, waiting for its channels. Bots cannot import
+    /// guilds.
+    fn create_import(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        input: CreateImportInput,
+    ) -> impl Future<Output = Result<GuildImport, CoreError>> + Send;
+
+    /// Adds one exported channel to an import of the caller that is still
+    /// uploading.
+    fn upload_channel(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        export: ExportDocument,
+    ) -> impl Future<Output = Result<GuildImport, CoreError>> + Send;
+
+    /// Queues an import of the caller once its channels are uploaded. The
+    /// caller must be able to own one more guild, under a name not taken.
+    fn start_import(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> impl Future<Output = Result<GuildImport, CoreError>> + Send;
+
+    fn cancel_import(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> impl Future<Output = Result<GuildImport, CoreError>> + Send;
+
+    fn get_import(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> impl Future<Output = Result<GuildImport, CoreError>> + Send;
+
+    /// The caller's imports, newest first.
+    fn list_imports(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<GuildImport>, CoreError>> + Send;
+
+    fn claim_next(&self) -> impl Future<Output = Result<Option<ImportJob>, CoreError>> + Send;
+
+    /// Creates the guild of a claimed import and everything but its
+    /// messages, unless it exists already or the import is a dry run.
+    /// Returns false if the import is no longer running.
+    fn create_structure(
+        &self,
+        job: &mut ImportJob,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// The next staged messages of a claimed import, oldest first.
+    fn next_messages(
+        &self,
+        job: &ImportJob,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<StagedMessage>, CoreError>> + Send;
+
+    /// Downloads an attachment. Fails for attachments too large to import.
+    fn fetch_attachment(
+        &self,
+        attachment: &StagedAttachment,
+    ) -> impl Future<Output = Result<FetchedAttachment, String>> + Send;
+
+    /// Records a batch of messages, up to staged message `last_seq`. A dry
+    /// run only counts them. Returns false if the import is no longer
+    /// running.
+    fn record_messages(
+        &self,
+        job: &mut ImportJob,
+        messages: Vec<ImportedMessage>,
+        skipped_attachments: u64,
+        last_seq: i64,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    fn finish_import(
+        &self,
+        job: &ImportJob,
+    ) -> impl Future<Output = Result<Option<GuildImport>, CoreError>> + Send;
+
+    fn fail_import(
+        &self,
+        job: &ImportJob,
+        reason: &str,
+    ) -> impl Future<Output = Result<Option<GuildImport>, CoreError>> + Send;
+
+    fn prune_imports(&self) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
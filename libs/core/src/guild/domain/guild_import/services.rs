@@ -0,0 +1,542 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    Id,
+    channel::ChannelKind,
+    guild::{Guild, GuildId, OwnerId},
+    guild_import::{GuildImport, GuildImportProgress, GuildImportStatus},
+};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    errors::CoreError,
+    guild::ports::{GuildPort, MAX_GUILDS_PER_OWNER},
+};
+
+use super::{
+    export::{ExportDocument, import_uuid, placeholder_username},
+    ports::{
+        CreateImportInput, FetchedAttachment, GuildImportRepository, GuildImportService,
+        IMPORT_LEASE, IMPORT_RETENTION, ImportAttachmentFetcher, ImportJob, ImportStructure,
+        ImportedChannel, ImportedMessage, ImportedRole, MAX_ACTIVE_IMPORTS_PER_USER,
+        MAX_IMPORTED_ATTACHMENT_BYTES, MAX_USER_MAPPINGS, PLACEHOLDER_SUBJECT_PREFIX,
+        PlaceholderUser, StagedAttachment, StagedMessage, UPLOAD_TIMEOUT,
+    },
+};
+
+const MAX_GUILD_NAME_LEN: usize = 100;
+
+#[derive(Clone)]
+pub struct GuildImportServiceImpl<G, I, F>
+where
+    G: GuildPort,
+    I: GuildImportRepository,
+    F: ImportAttachmentFetcher,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) import_repository: I,
+    pub(crate) fetcher: F,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidGuildImport {
+        message: message.into(),
+    }
+}
+
+fn validate_name(name: Option<String>) -> Result<Option<String>, CoreError> {
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(invalid("name must not be empty"));
+    }
+    if name.chars().count() > MAX_GUILD_NAME_LEN {
+        return Err(invalid(format!(
+            "name must be at most {MAX_GUILD_NAME_LEN} characters"
+        )));
+    }
+    Ok(Some(name.to_string()))
+}
+
+/// Authors can only be mapped to the importer: messages are never imported
+/// as somebody else.
+fn validate_mappings(mappings: &HashMap<String, Uuid>, user_id: Uuid) -> Result<(), CoreError> {
+    if mappings.len() > MAX_USER_MAPPINGS {
+        return Err(invalid(format!(
+            "at most {MAX_USER_MAPPINGS} users can be mapped"
+        )));
+    }
+    if let Some(id) = mappings.keys().find(|id| id.parse::<u64>().is_err()) {
+        return Err(invalid(format!("{id} is not a Discord user id")));
+    }
+    if mappings.values().any(|mapped| *mapped != user_id) {
+        return Err(invalid("authors can only be mapped to yourself"));
+    }
+    Ok(())
+}
+
+/// The guild an import creates, named as asked or like the exported one.
+fn import_guild(job: &ImportJob, now: DateTime<Utc>) -> Guild {
+    let name = job
+        .import
+        .name
+        .clone()
+        .or_else(|| job.manifest.source_guild_name.clone())
+        .unwrap_or_else(|| "Imported guild".to_string());
+    let mut guild = Guild::new(name, OwnerId(Id(job.owner_id)));
+    guild.id = GuildId(Id(import_uuid(job.import.id, "guild", "")));
+    guild.created_at = now;
+    guild
+}
+
+/// Everything a claimed import creates before its messages. Channels keep
+/// the order they were uploaded in, under their categories.
+fn build_structure(job: &ImportJob, now: DateTime<Utc>) -> ImportStructure {
+    let import_id = job.import.id;
+    let manifest = &job.manifest;
+
+    let roles = manifest
+        .roles
+        .iter()
+        .map(|role| ImportedRole {
+            id: import_uuid(import_id, "role", &role.source_id),
+            name: role.name.clone(),
+            color: role.color,
+            position: role.position,
+        })
+        .collect();
+
+    let categories = manifest
+        .categories
+        .iter()
+        .enumerate()
+        .map(|(position, category)| ImportedChannel {
+            id: import_uuid(import_id, "category", &category.source_id),
+            kind: ChannelKind::Category,
+            name: category.name.clone(),
+            topic: None,
+            parent_id: None,
+            position: position as i32,
+        });
+    let channels = manifest
+        .channels
+        .iter()
+        .enumerate()
+        .map(|(position, channel)| ImportedChannel {
+            id: job.channel_id(&channel.source_id),
+            kind: channel.kind,
+            name: channel.name.clone(),
+            topic: channel.topic.clone(),
+            parent_id: channel
+                .category_source_id
+                .as_deref()
+                .map(|category| import_uuid(import_id, "category", category)),
+            position: position as i32,
+        });
+
+    let users = manifest
+        .authors
+        .iter()
+        .filter(|author| !job.user_mappings.contains_key(&author.source_id))
+        .map(|author| {
+            let id = job.author_id(&author.source_id);
+            PlaceholderUser {
+                id,
+                oauth_sub: format!(
+                    "{PLACEHOLDER_SUBJECT_PREFIX}{import_id}:{}",
+                    author.source_id
+                ),
+                username: placeholder_username(&author.name, id),
+                display_name: author
+                    .display_name
+                    .clone()
+                    .or_else(|| Some(author.name.clone())),
+            }
+        })
+        .collect();
+
+    ImportStructure {
+        guild: import_guild(job, now),
+        owner_user_id: job.import.user_id,
+        everyone_role_id: import_uuid(import_id, "role", "everyone"),
+        roles,
+        channels: categories.chain(channels).collect(),
+        users,
+    }
+}
+
+impl<G, I, F> GuildImportServiceImpl<G, I, F>
+where
+    G: GuildPort,
+    I: GuildImportRepository,
+    F: ImportAttachmentFetcher,
+{
+    async fn find_own(&self, user_id: Uuid, id: Uuid) -> Result<ImportJob, CoreError> {
+        self.import_repository
+            .find_by_id(id)
+            .await?
+            .filter(|job| job.import.user_id == user_id)
+            .ok_or(CoreError::GuildImportNotFound)
+    }
+
+    /// Why an import of the caller could not be changed, given it exists.
+    async fn refusal(&self, user_id: Uuid, id: Uuid) -> CoreError {
+        match self.find_own(user_id, id).await {
+            Ok(job) => invalid(format!("the import is {}", job.import.status.as_str())),
+            Err(e) => e,
+        }
+    }
+}
+
+impl<G, I, F> GuildImportService for GuildImportServiceImpl<G, I, F>
+where
+    G: GuildPort,
+    I: GuildImportRepository,
+    F: ImportAttachmentFetcher,
+{
+    async fn create_import(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        input: CreateImportInput,
+    ) -> Result<GuildImport, CoreError> {
+        if identity.is_bot() {
+            return Err(invalid("bots cannot import guilds"));
+        }
+        let owner_id =
+            Uuid::parse_str(identity.id()).map_err(|_| invalid("the caller cannot own guilds"))?;
+        let name = validate_name(input.name)?;
+        validate_mappings(&input.user_mappings, user_id)?;
+        if self.import_repository.count_active_by_user(user_id).await?
+            >= MAX_ACTIVE_IMPORTS_PER_USER
+        {
+            return Err(CoreError::MaxGuildImportsReached {
+                max_imports: MAX_ACTIVE_IMPORTS_PER_USER,
+            });
+        }
+
+        let import = GuildImport {
+            id: Uuid::now_v7(),
+            user_id,
+            name,
+            dry_run: input.dry_run,
+            status: GuildImportStatus::Uploading,
+            guild_id: None,
+            progress: GuildImportProgress::default(),
+            failure_reason: None,
+            created_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+        };
+        self.import_repository
+            .insert(&import, owner_id, &input.user_mappings)
+            .await
+    }
+
+    async fn upload_channel(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        export: ExportDocument,
+    ) -> Result<GuildImport, CoreError> {
+        match self
+            .import_repository
+            .stage_channel(id, user_id, export)
+            .await?
+        {
+            Some(import) => Ok(import),
+            None => Err(self.refusal(user_id, id).await),
+        }
+    }
+
+    async fn start_import(&self, user_id: Uuid, id: Uuid) -> Result<GuildImport, CoreError> {
+        let job = self.find_own(user_id, id).await?;
+        if job.import.status != GuildImportStatus::Uploading {
+            return Err(self.refusal(user_id, id).await);
+        }
+        if job.manifest.channels.is_empty() {
+            return Err(invalid("upload at least one channel first"));
+        }
+
+        // Checked again when the guild is created; this only fails early.
+        let guild = import_guild(&job, Utc::now());
+        let owned = self.guild_repository.list_by_owner(&guild.owner_id).await?;
+        if owned.len() >= MAX_GUILDS_PER_OWNER {
+            return Err(CoreError::MaxGuildsReached {
+                max_guilds: MAX_GUILDS_PER_OWNER,
+            });
+        }
+        if self.import_repository.slug_exists(&guild.slug).await? {
+            return Err(CoreError::GuildSlugAlreadyExists { slug: guild.name });
+        }
+
+        match self.import_repository.start(id, user_id).await? {
+            Some(import) => Ok(import),
+            None => Err(self.refusal(user_id, id).await),
+        }
+    }
+
+    async fn cancel_import(&self, user_id: Uuid, id: Uuid) -> Result<GuildImport, CoreError> {
+        match self.import_repository.cancel(id, user_id).await? {
+            Some(import) => Ok(import),
+            None => Err(self.refusal(user_id, id).await),
+        }
+    }
+
+    async fn get_import(&self, user_id: Uuid, id: Uuid) -> Result<GuildImport, CoreError> {
+        Ok(self.find_own(user_id, id).await?.import)
+    }
+
+    async fn list_imports(&self, user_id: Uuid) -> Result<Vec<GuildImport>, CoreError> {
+        self.import_repository.list_by_user(user_id).await
+    }
+
+    async fn claim_next(&self) -> Result<Option<ImportJob>, CoreError> {
+        let now = Utc::now();
+        self.import_repository.claim(now, now + IMPORT_LEASE).await
+    }
+
+    async fn create_structure(&self, job: &mut ImportJob) -> Result<bool, CoreError> {
+        if job.import.dry_run || job.import.guild_id.is_some() {
+            return Ok(true);
+        }
+        let structure = build_structure(job, Utc::now());
+        if !self
+            .import_repository
+            .create_structure(job.import.id, &structure)
+            .await?
+        {
+            return Ok(false);
+        }
+        job.import.guild_id = Some(*structure.guild.id.get_uuid());
+        Ok(true)
+    }
+
+    async fn next_messages(
+        &self,
+        job: &ImportJob,
+        limit: i64,
+    ) -> Result<Vec<StagedMessage>, CoreError> {
+        self.import_repository
+            .list_staged(job.import.id, job.cursor, limit)
+            .await
+    }
+
+    async fn fetch_attachment(
+        &self,
+        attachment: &StagedAttachment,
+    ) -> Result<FetchedAttachment, String> {
+        if !attachment.is_importable() {
+            return Err(format!("{} cannot be downloaded", attachment.url));
+        }
+        self.fetcher
+            .fetch(&attachment.url, MAX_IMPORTED_ATTACHMENT_BYTES as usize)
+            .await
+    }
+
+    async fn record_messages(
+        &self,
+        job: &mut ImportJob,
+        messages: Vec<ImportedMessage>,
+        skipped_attachments: u64,
+        last_seq: i64,
+    ) -> Result<bool, CoreError> {
+        let mut progress = job.import.progress.clone();
+        progress.messages_imported += messages.len() as u64;
+        progress.attachments_imported += messages
+            .iter()
+            .map(|message| message.attachments.len() as u64)
+            .sum::<u64>();
+        progress.attachments_skipped += skipped_attachments;
+
+        let written = if job.import.dry_run {
+            &[][..]
+        } else {
+            &messages[..]
+        };
+        match self
+            .import_repository
+            .record_messages(
+                job.import.id,
+                written,
+                last_seq,
+                &progress,
+                Utc::now() + IMPORT_LEASE,
+            )
+            .await?
+        {
+            Some(import) => {
+                job.import = import;
+                job.cursor = last_seq;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    async fn finish_import(&self, job: &ImportJob) -> Result<Option<GuildImport>, CoreError> {
+        self.import_repository
+            .finish(job.import.id, &job.import.progress)
+            .await
+    }
+
+    async fn fail_import(
+        &self,
+        job: &ImportJob,
+        reason: &str,
+    ) -> Result<Option<GuildImport>, CoreError> {
+        self.import_repository.fail(job.import.id, reason).await
+    }
+
+    async fn prune_imports(&self) -> Result<u64, CoreError> {
+        let now = Utc::now();
+        self.import_repository
+            .prune(now - IMPORT_RETENTION, now - UPLOAD_TIMEOUT)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::guild::domain::guild_import::export::{
+        ImportManifest, ManifestAuthor, ManifestCategory, ManifestChannel, ManifestRole,
+    };
+
+    use super::*;
+
+    fn job() -> ImportJob {
+        let mapped = Uuid::now_v7();
+        ImportJob {
+            import: GuildImport {
+                id: Uuid::now_v7(),
+                user_id: Uuid::now_v7(),
+                name: None,
+                dry_run: false,
+                status: GuildImportStatus::Running,
+                guild_id: None,
+                progress: GuildImportProgress::default(),
+                failure_reason: None,
+                created_at: Utc::now(),
+                started_at: None,
+                finished_at: None,
+            },
+            owner_id: Uuid::now_v7(),
+            user_mappings: HashMap::from([("7".to_string(), mapped)]),
+            manifest: ImportManifest {
+                source_guild_id: Some("1".into()),
+                source_guild_name: Some("Rustaceans".into()),
+                categories: vec![ManifestCategory {
+                    source_id: "10".into(),
+                    name: "Text Channels".into(),
+                }],
+                channels: vec![
+                    ManifestChannel {
+                        source_id: "2".into(),
+                        kind: ChannelKind::Text,
+                        name: "general".into(),
+                        topic: None,
+                        category_source_id: Some("10".into()),
+                    },
+                    ManifestChannel {
+                        source_id: "3".into(),
+                        kind: ChannelKind::Voice,
+                        name: "Lounge".into(),
+                        topic: None,
+                        category_source_id: None,
+                    },
+                ],
+                roles: vec![ManifestRole {
+                    source_id: "20".into(),
+                    name: "Crab".into(),
+                    color: 0xFF8000,
+                    position: 2,
+                }],
+                authors: vec![
+                    ManifestAuthor {
+                        source_id: "7".into(),
+                        name: "ferris".into(),
+                        display_name: None,
+                    },
+                    ManifestAuthor {
+                        source_id: "8".into(),
+                        name: "corro".into(),
+                        display_name: None,
+                    },
+                ],
+                messages: 0,
+                skipped_messages: 0,
+            },
+            cursor: 0,
+        }
+    }
+
+    #[test]
+    fn test_build_structure() {
+        let job = job();
+        let structure = build_structure(&job, Utc::now());
+
+        assert_eq!(structure.guild.name, "Rustaceans");
+        assert_eq!(structure.guild.slug, "rustaceans");
+        assert_eq!(structure.guild.owner_id, OwnerId(Id(job.owner_id)));
+        assert_eq!(structure.roles.len(), 1);
+
+        let kinds: Vec<_> = structure.channels.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            [ChannelKind::Category, ChannelKind::Text, ChannelKind::Voice]
+        );
+        assert_eq!(
+            structure.channels[1].parent_id,
+            Some(structure.channels[0].id)
+        );
+        assert_eq!(structure.channels[1].id, job.channel_id("2"));
+        assert_eq!(structure.channels[2].parent_id, None);
+
+        // The mapped author keeps their account; the other gets a placeholder.
+        assert_eq!(structure.users.len(), 1);
+        assert_eq!(structure.users[0].id, job.author_id("8"));
+        assert_ne!(job.author_id("7"), job.author_id("8"));
+        assert_eq!(job.author_id("7"), job.user_mappings["7"]);
+        assert!(
+            structure.users[0]
+                .oauth_sub
+                .starts_with(PLACEHOLDER_SUBJECT_PREFIX)
+        );
+
+        // Resuming builds the same structure.
+        let again = build_structure(&job, Utc::now());
+        assert_eq!(again.guild.id, structure.guild.id);
+        assert_eq!(again.users[0].username, structure.users[0].username);
+    }
+
+    #[test]
+    fn test_validate_import() {
+        assert_eq!(validate_name(None).unwrap(), None);
+        assert_eq!(
+            validate_name(Some(" Crabs ".into())).unwrap().as_deref(),
+            Some("Crabs")
+        );
+        assert!(validate_name(Some("  ".into())).is_err());
+        assert!(validate_name(Some("x".repeat(MAX_GUILD_NAME_LEN + 1))).is_err());
+
+        let importer = Uuid::now_v7();
+        assert!(validate_mappings(&HashMap::from([("123".into(), importer)]), importer).is_ok());
+        assert!(
+            validate_mappings(&HashMap::from([("ferris".into(), importer)]), importer).is_err()
+        );
+    }
+
+    #[test]
+    fn test_mappings_to_other_users_are_refused() {
+        let importer = Uuid::now_v7();
+        let mappings = HashMap::from([("123".into(), importer), ("456".into(), Uuid::now_v7())]);
+        assert!(matches!(
+            validate_mappings(&mappings, importer),
+            Err(CoreError::InvalidGuildImport { .. })
+        ));
+    }
+}
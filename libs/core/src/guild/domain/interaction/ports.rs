@@ -0,0 +1,252 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    application::Application,
+    channel::ChannelId,
+    embed::Embed,
+    guild::GuildId,
+    interaction::{
+        ApplicationCommand, CommandOption, EphemeralMessage, Interaction, InteractionCallback,
+        InteractionOption, InteractionStatus,
+    },
+    message::Message,
+};
+use uuid::Uuid;
+
+use crate::guild::domain::{errors::CoreError, event_subscription::ports::SignedRequest};
+
+pub const MAX_COMMANDS_PER_SCOPE: i64 = 100;
+pub const MAX_COMMAND_NAME_LEN: usize = 32;
+pub const MAX_COMMAND_DESCRIPTION_LEN: usize = 100;
+pub const MAX_COMMAND_OPTIONS: usize = 25;
+pub const MAX_OPTION_CHOICES: usize = 25;
+pub const MAX_OPTION_STRING_LEN: usize = 2000;
+pub const MAX_INTERACTION_CONTENT_LEN: usize = 2000;
+pub const MAX_ENDPOINT_URL_LEN: usize = 2048;
+
+/// How long a bot has to answer or defer an interaction.
+pub const INTERACTION_RESPONSE_WINDOW: TimeDelta = TimeDelta::seconds(3);
+/// How long after the invocation follow-ups may be sent.
+pub const INTERACTION_FOLLOW_UP_WINDOW: TimeDelta = TimeDelta::minutes(15);
+/// Interactions endpoints must answer within the response window.
+pub const INTERACTION_DELIVERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(3);
+/// Largest response body read from an interactions endpoint.
+pub const MAX_INTERACTION_RESPONSE_BYTES: usize = 64 * 1024;
+
+/// Header carrying the interaction ID on HTTP deliveries.
+pub const INTERACTION_HEADER: &str = "X-Ferriscord-Interaction";
+
+pub struct CommandInput {
+    pub name: String,
+    pub description: String,
+    pub options: Vec<CommandOption>,
+}
+
+pub struct InvokeCommandInput {
+    pub command_id: Uuid,
+    pub options: Vec<InteractionOption>,
+}
+
+/// `ephemeral` defaults to the ephemerality of the initial response.
+pub struct FollowUpInput {
+    pub content: String,
+    pub embeds: Vec<Embed>,
+    pub ephemeral: Option<bool>,
+}
+
+/// How an interaction reaches its bot.
+pub enum InteractionDelivery {
+    /// Over the bot user's gateway connection.
+    Gateway { bot_user_id: Uuid },
+    /// POSTed to the application's interactions endpoint.
+    Http(SignedRequest),
+}
+
+/// A stored interaction, carrying its token, and how to deliver it.
+pub struct Invocation {
+    pub interaction: Interaction,
+    pub delivery: InteractionDelivery,
+}
+
+/// What a bot's answer produced.
+pub enum InteractionReply {
+    /// A message posted in the channel.
+    Message {
+        guild_id: Uuid,
+        message: Box<Message>,
+    },
+    /// A reply for the invoker's eyes only.
+    Ephemeral {
+        user_id: Uuid,
+        message: EphemeralMessage,
+    },
+    /// The interaction was deferred; the answer follows.
+    Deferred(Interaction),
+}
+
+pub trait InteractionSender: Send + Sync {
+    /// Whether interactions may be sent to `url`, or why not. Checked again
+    /// before every request.
+    fn check_url(&self, url: &str) -> Result<(), String>;
+
+    /// POSTs a signed request. Returns the response body of a 2xx answer,
+    /// `None` if empty, or why the delivery failed.
+    fn send(
+        &self,
+        request: SignedRequest,
+    ) -> impl Future<Output = Result<Option<String>, String>> + Send;
+}
+
+pub trait InteractionRepository: Send + Sync {
+    /// Registers a command, replacing the one with the same name in the same
+    /// scope.
+    fn upsert_command(
+        &self,
+        command: &ApplicationCommand,
+    ) -> impl Future<Output = Result<ApplicationCommand, CoreError>> + Send;
+
+    fn find_command(
+        &self,
+        command_id: Uuid,
+    ) -> impl Future<Output = Result<Option<ApplicationCommand>, CoreError>> + Send;
+
+    /// Commands of exactly one scope: global if `guild_id` is `None`.
+    fn list_commands(
+        &self,
+        application_id: Uuid,
+        guild_id: Option<Uuid>,
+    ) -> impl Future<Output = Result<Vec<ApplicationCommand>, CoreError>> + Send;
+
+    /// Commands usable in a guild: global and guild commands of the bots in it.
+    fn list_guild_commands(
+        &self,
+        guild_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<ApplicationCommand>, CoreError>> + Send;
+
+    fn delete_command(
+        &self,
+        command_id: Uuid,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    fn insert_interaction(
+        &self,
+        interaction: &Interaction,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn find_interaction(
+        &self,
+        interaction_id: Uuid,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<Interaction>, CoreError>> + Send;
+
+    /// Moves a pending interaction created after `created_after` to `status`.
+    /// Returns `None` if it was no longer pending or too old, so only one
+    /// answer wins.
+    fn acknowledge(
+        &self,
+        interaction_id: Uuid,
+        status: InteractionStatus,
+        ephemeral: bool,
+        created_after: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Option<Interaction>, CoreError>> + Send;
+
+    /// Marks an interaction failed if it is still pending.
+    fn fail_pending(
+        &self,
+        interaction_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Interaction>, CoreError>> + Send;
+
+    fn prune_interactions(
+        &self,
+        before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
+
+/// Commands are managed by the application's owner or its bot. Members
+/// invoke them with USE_SLASH_COMMANDS; the bot answers with the
+/// interaction's token.
+pub trait InteractionService: Send + Sync {
+    fn create_command(
+        &self,
+        user_id: Uuid,
+        application_id: Uuid,
+        guild_id: Option<GuildId>,
+        input: CommandInput,
+    ) -> impl Future<Output = Result<ApplicationCommand, CoreError>> + Send;
+
+    fn list_commands(
+        &self,
+        user_id: Uuid,
+        application_id: Uuid,
+        guild_id: Option<GuildId>,
+    ) -> impl Future<Output = Result<Vec<ApplicationCommand>, CoreError>> + Send;
+
+    fn delete_command(
+        &self,
+        user_id: Uuid,
+        application_id: Uuid,
+        command_id: Uuid,
+    ) -> impl Future<Output = Result<ApplicationCommand, CoreError>> + Send;
+
+    /// Sets or clears the interactions endpoint. A new endpoint must answer
+    /// a signed ping first.
+    fn set_interactions_endpoint(
+        &self,
+        user_id: Uuid,
+        application_id: Uuid,
+        url: Option<String>,
+    ) -> impl Future<Output = Result<Application, CoreError>> + Send;
+
+    /// Commands members can invoke in the guild.
+    fn list_guild_commands(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+    ) -> impl Future<Output = Result<Vec<ApplicationCommand>, CoreError>> + Send;
+
+    /// Validates and stores an invocation. Requires USE_SLASH_COMMANDS in
+    /// the channel.
+    fn invoke_command(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        input: InvokeCommandInput,
+    ) -> impl Future<Output = Result<Invocation, CoreError>> + Send;
+
+    /// POSTs an interaction to its endpoint. An answer in the response body
+    /// is applied as the initial response.
+    fn deliver_http(
+        &self,
+        interaction: &Interaction,
+        request: SignedRequest,
+    ) -> impl Future<Output = Result<Option<InteractionReply>, CoreError>> + Send;
+
+    /// The initial response, within the response window.
+    fn respond(
+        &self,
+        interaction_id: Uuid,
+        token: &str,
+        callback: InteractionCallback,
+    ) -> impl Future<Output = Result<InteractionReply, CoreError>> + Send;
+
+    /// A further message after the initial response, within the follow-up
+    /// window.
+    fn follow_up(
+        &self,
+        interaction_id: Uuid,
+        token: &str,
+        input: FollowUpInput,
+    ) -> impl Future<Output = Result<InteractionReply, CoreError>> + Send;
+
+    /// Fails the interaction if the bot hasn't answered it.
+    fn fail_unanswered(
+        &self,
+        interaction_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Interaction>, CoreError>> + Send;
+
+    /// Deletes interactions past their follow-up window.
+    fn prune_interactions(&self) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
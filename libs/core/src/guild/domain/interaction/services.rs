@@ -0,0 +1,892 @@
+use chrono::Utc;
+use ferriscord_auth::{Bot, Identity};
+use ferriscord_entities::{
+    Id,
+    application::Application,
+    channel::{ChannelId, ChannelKind},
+    embed::Embed,
+    guild::GuildId,
+    interaction::{
+        ApplicationCommand, CommandOption, CommandOptionType, CommandOptionValue, EphemeralMessage,
+        Interaction, InteractionCallback, InteractionOption, InteractionStatus,
+    },
+    message::MessageAuthor,
+    user::UserId,
+};
+use ferriscord_permission::{Permissions, require_permission};
+use rand::distributions::{Alphanumeric, DistString};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    application::ports::ApplicationRepository,
+    channel::ports::ChannelPort,
+    common::{build_channel_permission_context, build_permission_context},
+    errors::CoreError,
+    event_subscription::{
+        ports::{SIGNATURE_HEADER, SignedRequest, TIMESTAMP_HEADER},
+        sign_payload,
+    },
+    guild::ports::GuildPort,
+    member::ports::MemberRepository,
+    message::{
+        embeds::validate_embeds,
+        ports::{EncryptionMeta, MessagePort},
+    },
+    role::ports::RoleRepository,
+};
+
+use super::ports::{
+    CommandInput, FollowUpInput, INTERACTION_FOLLOW_UP_WINDOW, INTERACTION_HEADER,
+    INTERACTION_RESPONSE_WINDOW, InteractionDelivery, InteractionReply, InteractionRepository,
+    InteractionSender, InteractionService, Invocation, InvokeCommandInput,
+    MAX_COMMAND_DESCRIPTION_LEN, MAX_COMMAND_NAME_LEN, MAX_COMMAND_OPTIONS, MAX_COMMANDS_PER_SCOPE,
+    MAX_ENDPOINT_URL_LEN, MAX_INTERACTION_CONTENT_LEN, MAX_OPTION_CHOICES, MAX_OPTION_STRING_LEN,
+};
+
+const TOKEN_LEN: usize = 68;
+const SECRET_LEN: usize = 48;
+
+#[derive(Clone)]
+pub struct InteractionServiceImpl<G, C, R, M, Msg, A, I, S>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    A: ApplicationRepository,
+    I: InteractionRepository,
+    S: InteractionSender,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) message_repository: Msg,
+    pub(crate) application_repository: A,
+    pub(crate) interaction_repository: I,
+    pub(crate) interaction_sender: S,
+}
+
+fn invalid_command(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidCommand {
+        message: message.into(),
+    }
+}
+
+fn invalid_interaction(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidInteraction {
+        message: message.into(),
+    }
+}
+
+fn new_token() -> (String, String) {
+    let token = Alphanumeric.sample_string(&mut rand::thread_rng(), TOKEN_LEN);
+    let hash = hash_token(&token);
+    (token, hash)
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn signed_request(
+    url: String,
+    secret: &str,
+    body: String,
+    interaction_id: Option<Uuid>,
+) -> SignedRequest {
+    let timestamp = Utc::now().timestamp();
+    let mut headers = vec![
+        (SIGNATURE_HEADER, sign_payload(secret, timestamp, &body)),
+        (TIMESTAMP_HEADER, timestamp.to_string()),
+    ];
+    if let Some(id) = interaction_id {
+        headers.push((INTERACTION_HEADER, id.to_string()));
+    }
+    SignedRequest { url, headers, body }
+}
+
+fn validate_url(url: String) -> Result<String, CoreError> {
+    let url = url.trim().to_string();
+    let invalid = |message: &str| CoreError::InvalidApplication {
+        message: message.to_string(),
+    };
+    if url.len() > MAX_ENDPOINT_URL_LEN {
+        return Err(invalid("interactions endpoint URL is too long"));
+    }
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .ok_or_else(|| invalid("interactions endpoint must be an http or https URL"))?;
+    if rest.is_empty() || rest.starts_with('/') || url.chars().any(char::is_whitespace) {
+        return Err(invalid("interactions endpoint must have a host"));
+    }
+    Ok(url)
+}
+
+/// Command and option names: lowercase letters, digits, '-' and '_'.
+fn validate_name(what: &str, name: String) -> Result<String, CoreError> {
+    let name = name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_COMMAND_NAME_LEN {
+        return Err(invalid_command(format!(
+            "{what} name must be 1 to {MAX_COMMAND_NAME_LEN} characters"
+        )));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '_'))
+    {
+        return Err(invalid_command(format!(
+            "{what} name may only contain lowercase letters, digits, '-' and '_'"
+        )));
+    }
+    Ok(name)
+}
+
+fn validate_description(what: &str, description: String) -> Result<String, CoreError> {
+    let description = description.trim().to_string();
+    if description.is_empty() || description.chars().count() > MAX_COMMAND_DESCRIPTION_LEN {
+        return Err(invalid_command(format!(
+            "{what} description must be 1 to {MAX_COMMAND_DESCRIPTION_LEN} characters"
+        )));
+    }
+    Ok(description)
+}
+
+/// Whether `value` is of the option's type. Numbers accept integers; IDs
+/// are UUID strings.
+fn value_matches(kind: CommandOptionType, value: &CommandOptionValue) -> bool {
+    match (kind, value) {
+        (CommandOptionType::String, CommandOptionValue::String(_))
+        | (CommandOptionType::Integer, CommandOptionValue::Integer(_))
+        | (CommandOptionType::Number, CommandOptionValue::Integer(_))
+        | (CommandOptionType::Number, CommandOptionValue::Number(_))
+        | (CommandOptionType::Boolean, CommandOptionValue::Boolean(_)) => true,
+        (
+            CommandOptionType::User | CommandOptionType::Channel | CommandOptionType::Role,
+            CommandOptionValue::String(id),
+        ) => Uuid::parse_str(id).is_ok(),
+        _ => false,
+    }
+}
+
+fn same_value(a: &CommandOptionValue, b: &CommandOptionValue) -> bool {
+    match (a, b) {
+        (CommandOptionValue::Integer(i), CommandOptionValue::Number(n))
+        | (CommandOptionValue::Number(n), CommandOptionValue::Integer(i)) => *i as f64 == *n,
+        _ => a == b,
+    }
+}
+
+fn validate_options(options: Vec<CommandOption>) -> Result<Vec<CommandOption>, CoreError> {
+    if options.len() > MAX_COMMAND_OPTIONS {
+        return Err(invalid_command(format!(
+            "a command has at most {MAX_COMMAND_OPTIONS} options"
+        )));
+    }
+
+    let mut validated: Vec<CommandOption> = Vec::with_capacity(options.len());
+    for option in options {
+        let name = validate_name("option", option.name)?;
+        if validated.iter().any(|o| o.name == name) {
+            return Err(invalid_command(format!("duplicate option '{name}'")));
+        }
+        if option.required && validated.iter().any(|o| !o.required) {
+            return Err(invalid_command("required options must come first"));
+        }
+        if !option.choices.is_empty() {
+            if !matches!(
+                option.kind,
+                CommandOptionType::String | CommandOptionType::Integer | CommandOptionType::Number
+            ) {
+                return Err(invalid_command(format!(
+                    "option '{name}' cannot have choices"
+                )));
+            }
+            if option.choices.len() > MAX_OPTION_CHOICES {
+                return Err(invalid_command(format!(
+                    "an option has at most {MAX_OPTION_CHOICES} choices"
+                )));
+            }
+            for choice in &option.choices {
+                if choice.name.trim().is_empty()
+                    || choice.name.chars().count() > MAX_COMMAND_DESCRIPTION_LEN
+                    || !value_matches(option.kind, &choice.value)
+                {
+                    return Err(invalid_command(format!(
+                        "invalid choice '{}' for option '{name}'",
+                        choice.name
+                    )));
+                }
+            }
+        }
+        validated.push(CommandOption {
+            description: validate_description("option", option.description)?,
+            name,
+            ..option
+        });
+    }
+    Ok(validated)
+}
+
+/// Checks invocation options against the command's declaration and returns
+/// them in declaration order.
+fn validate_invocation(
+    command: &ApplicationCommand,
+    mut given: Vec<InteractionOption>,
+) -> Result<Vec<InteractionOption>, CoreError> {
+    if let Some(unknown) = given
+        .iter()
+        .find(|g| !command.options.iter().any(|o| o.name == g.name))
+    {
+        return Err(invalid_interaction(format!(
+            "unknown option '{}'",
+            unknown.name
+        )));
+    }
+
+    let mut options = Vec::with_capacity(given.len());
+    for declared in &command.options {
+        let mut matching = given.iter().filter(|g| g.name == declared.name);
+        if matching.nth(1).is_some() {
+            return Err(invalid_interaction(format!(
+                "option '{}' was given more than once",
+                declared.name
+            )));
+        }
+        let Some(position) = given.iter().position(|g| g.name == declared.name) else {
+            if declared.required {
+                return Err(invalid_interaction(format!(
+                    "option '{}' is required",
+                    declared.name
+                )));
+            }
+            continue;
+        };
+        let option = given.swap_remove(position);
+
+        if !value_matches(declared.kind, &option.value) {
+            return Err(invalid_interaction(format!(
+                "option '{}' must be of type {:?}",
+                declared.name, declared.kind
+            )));
+        }
+        if let CommandOptionValue::String(s) = &option.value
+            && s.chars().count() > MAX_OPTION_STRING_LEN
+        {
+            return Err(invalid_interaction(format!(
+                "option '{}' must be at most {MAX_OPTION_STRING_LEN} characters",
+                declared.name
+            )));
+        }
+        if !declared.choices.is_empty()
+            && !declared
+                .choices
+                .iter()
+                .any(|c| same_value(&c.value, &option.value))
+        {
+            return Err(invalid_interaction(format!(
+                "option '{}' must be one of its choices",
+                declared.name
+            )));
+        }
+        options.push(option);
+    }
+    Ok(options)
+}
+
+struct Answer {
+    content: String,
+    embeds: Vec<Embed>,
+}
+
+/// An answer needs content, embeds or both.
+fn validate_answer(content: String, embeds: Vec<Embed>) -> Result<Answer, CoreError> {
+    if content.trim().is_empty() && embeds.is_empty() {
+        return Err(invalid_interaction("content or embeds are required"));
+    }
+    if content.chars().count() > MAX_INTERACTION_CONTENT_LEN {
+        return Err(invalid_interaction(format!(
+            "content must be at most {MAX_INTERACTION_CONTENT_LEN} characters"
+        )));
+    }
+    let embeds = validate_embeds(embeds).map_err(invalid_interaction)?;
+    Ok(Answer { content, embeds })
+}
+
+impl<G, C, R, M, Msg, A, I, S> InteractionServiceImpl<G, C, R, M, Msg, A, I, S>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    A: ApplicationRepository,
+    I: InteractionRepository,
+    S: InteractionSender,
+{
+    /// Loads an application the caller may register commands for: its owner
+    /// or its own bot.
+    async fn managed_application(
+        &self,
+        user_id: Uuid,
+        application_id: Uuid,
+    ) -> Result<Application, CoreError> {
+        self.application_repository
+            .find_by_id(application_id)
+            .await?
+            .filter(|a| a.owner_id == user_id || a.bot_user_id == user_id)
+            .ok_or(CoreError::ApplicationNotFound)
+    }
+
+    /// Guild commands can only be registered where the bot is.
+    async fn command_scope(
+        &self,
+        application: &Application,
+        guild_id: Option<GuildId>,
+    ) -> Result<Option<Uuid>, CoreError> {
+        let Some(guild_id) = guild_id else {
+            return Ok(None);
+        };
+        let guild = *guild_id.get_uuid();
+        self.application_repository
+            .find_guild_bot(guild, application.id)
+            .await?
+            .ok_or_else(|| invalid_command("the bot is not in this guild"))?;
+        Ok(Some(guild))
+    }
+
+    async fn find_with_token(
+        &self,
+        interaction_id: Uuid,
+        token: &str,
+    ) -> Result<Interaction, CoreError> {
+        self.interaction_repository
+            .find_interaction(interaction_id, &hash_token(token))
+            .await?
+            .ok_or(CoreError::InteractionNotFound)
+    }
+
+    /// Applies the initial response. Only the first answer within the
+    /// response window is accepted.
+    async fn acknowledge_with(
+        &self,
+        interaction: &Interaction,
+        callback: InteractionCallback,
+    ) -> Result<InteractionReply, CoreError> {
+        match interaction.status {
+            InteractionStatus::Pending => {}
+            InteractionStatus::Failed => return Err(CoreError::InteractionExpired),
+            InteractionStatus::Responded | InteractionStatus::Deferred => {
+                return Err(invalid_interaction("interaction was already acknowledged"));
+            }
+        }
+        let created_after = Utc::now() - INTERACTION_RESPONSE_WINDOW;
+        if interaction.created_at < created_after {
+            return Err(CoreError::InteractionExpired);
+        }
+
+        let (status, ephemeral, answer) = match callback {
+            InteractionCallback::Message {
+                content,
+                embeds,
+                ephemeral,
+            } => (
+                InteractionStatus::Responded,
+                ephemeral,
+                Some(validate_answer(content, embeds)?),
+            ),
+            InteractionCallback::Deferred { ephemeral } => {
+                (InteractionStatus::Deferred, ephemeral, None)
+            }
+        };
+
+        let interaction = self
+            .interaction_repository
+            .acknowledge(interaction.id, status, ephemeral, created_after)
+            .await?
+            .ok_or(CoreError::InteractionExpired)?;
+
+        match answer {
+            Some(answer) => self.post(&interaction, answer, ephemeral).await,
+            None => Ok(InteractionReply::Deferred(interaction)),
+        }
+    }
+
+    /// Posts an answer as the bot, in the channel or to the invoker only.
+    async fn post(
+        &self,
+        interaction: &Interaction,
+        answer: Answer,
+        ephemeral: bool,
+    ) -> Result<InteractionReply, CoreError> {
+        let application = self
+            .application_repository
+            .find_by_id(interaction.application_id)
+            .await?
+            .ok_or(CoreError::ApplicationNotFound)?;
+
+        if ephemeral {
+            return Ok(InteractionReply::Ephemeral {
+                user_id: interaction.user_id,
+                message: EphemeralMessage {
+                    interaction_id: interaction.id,
+                    guild_id: interaction.guild_id,
+                    channel_id: interaction.channel_id,
+                    author: MessageAuthor {
+                        id: UserId::from(application.bot_user_id),
+                        username: application.name,
+                        avatar_url: None,
+                        bot: true,
+                    },
+                    content: answer.content,
+                    embeds: answer.embeds,
+                    created_at: Utc::now(),
+                },
+            });
+        }
+
+        let message = self
+            .message_repository
+            .insert(
+                &ChannelId(Id(interaction.channel_id)),
+                &Bot::subject(application.id),
+                answer.content,
+                answer.embeds,
+                Vec::new(),
+                EncryptionMeta::default(),
+            )
+            .await?;
+        Ok(InteractionReply::Message {
+            guild_id: interaction.guild_id,
+            message: Box::new(message),
+        })
+    }
+}
+
+impl<G, C, R, M, Msg, A, I, S> InteractionService
+    for InteractionServiceImpl<G, C, R, M, Msg, A, I, S>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    A: ApplicationRepository,
+    I: InteractionRepository,
+    S: InteractionSender,
+{
+    async fn create_command(
+        &self,
+        user_id: Uuid,
+        application_id: Uuid,
+        guild_id: Option<GuildId>,
+        input: CommandInput,
+    ) -> Result<ApplicationCommand, CoreError> {
+        let application = self.managed_application(user_id, application_id).await?;
+        let guild = self.command_scope(&application, guild_id).await?;
+
+        let name = validate_name("command", input.name)?;
+        let description = validate_description("command", input.description)?;
+        let options = validate_options(input.options)?;
+
+        let existing = self
+            .interaction_repository
+            .list_commands(application.id, guild)
+            .await?;
+        if !existing.iter().any(|c| c.name == name)
+            && existing.len() as i64 >= MAX_COMMANDS_PER_SCOPE
+        {
+            return Err(CoreError::MaxCommandsReached {
+                max_commands: MAX_COMMANDS_PER_SCOPE,
+            });
+        }
+
+        let now = Utc::now();
+        self.interaction_repository
+            .upsert_command(&ApplicationCommand {
+                id: Uuid::now_v7(),
+                application_id: application.id,
+                guild_id: guild,
+                name,
+                description,
+                options,
+                created_at: now,
+                updated_at: now,
+            })
+            .await
+    }
+
+    async fn list_commands(
+        &self,
+        user_id: Uuid,
+        application_id: Uuid,
+        guild_id: Option<GuildId>,
+    ) -> Result<Vec<ApplicationCommand>, CoreError> {
+        let application = self.managed_application(user_id, application_id).await?;
+        self.interaction_repository
+            .list_commands(application.id, guild_id.map(|g| *g.get_uuid()))
+            .await
+    }
+
+    async fn delete_command(
+        &self,
+        user_id: Uuid,
+        application_id: Uuid,
+        command_id: Uuid,
+    ) -> Result<ApplicationCommand, CoreError> {
+        let application = self.managed_application(user_id, application_id).await?;
+        let command = self
+            .interaction_repository
+            .find_command(command_id)
+            .await?
+            .filter(|c| c.application_id == application.id)
+            .ok_or(CoreError::CommandNotFound)?;
+
+        if !self
+            .interaction_repository
+            .delete_command(command.id)
+            .await?
+        {
+            return Err(CoreError::CommandNotFound);
+        }
+        Ok(command)
+    }
+
+    async fn set_interactions_endpoint(
+        &self,
+        user_id: Uuid,
+        application_id: Uuid,
+        url: Option<String>,
+    ) -> Result<Application, CoreError> {
+        let application = self
+            .application_repository
+            .find_by_id(application_id)
+            .await?
+            .filter(|a| a.owner_id == user_id)
+            .ok_or(CoreError::ApplicationNotFound)?;
+
+        let url = url
+            .filter(|u| !u.trim().is_empty())
+            .map(validate_url)
+            .transpose()?;
+        if let Some(url) = &url {
+            self.interaction_sender.check_url(url).map_err(|reason| {
+                CoreError::InvalidApplication {
+                    message: format!("interactions endpoint is not allowed: {reason}"),
+                }
+            })?;
+        }
+        let secret = application
+            .interactions_secret
+            .clone()
+            .unwrap_or_else(|| Alphanumeric.sample_string(&mut rand::thread_rng(), SECRET_LEN));
+
+        if let Some(url) = &url {
+            let ping = json!({ "type": "ping" }).to_string();
+            self.interaction_sender
+                .send(signed_request(url.clone(), &secret, ping, None))
+                .await
+                .map_err(|reason| CoreError::InvalidApplication {
+                    message: format!("interactions endpoint did not answer the ping: {reason}"),
+                })?;
+        }
+
+        self.application_repository
+            .set_interactions_endpoint(application.id, url, Some(secret))
+            .await?
+            .ok_or(CoreError::ApplicationNotFound)
+    }
+
+    async fn list_guild_commands(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+    ) -> Result<Vec<ApplicationCommand>, CoreError> {
+        // Any member may see the commands; invoking them is checked per channel.
+        build_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &identity,
+            &guild_id,
+        )
+        .await?;
+
+        self.interaction_repository
+            .list_guild_commands(*guild_id.get_uuid())
+            .await
+    }
+
+    async fn invoke_command(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        input: InvokeCommandInput,
+    ) -> Result<Invocation, CoreError> {
+        if identity.is_bot() {
+            return Err(CoreError::InsufficientPermissions);
+        }
+
+        let channel = self
+            .channel_repository
+            .find_by_id(&channel_id)
+            .await?
+            .filter(|c| c.guild_id.as_ref() == Some(&guild_id))
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: channel_id.clone(),
+            })?;
+        if !matches!(channel.kind, ChannelKind::Text | ChannelKind::Announcement) {
+            return Err(invalid_interaction(
+                "commands can only be used in text channels",
+            ));
+        }
+
+        let mut permission_context = build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            &identity,
+            &guild_id,
+            &channel_id,
+        )
+        .await?;
+        require_permission!(permission_context, Permissions::VIEW_CHANNEL);
+        require_permission!(permission_context, Permissions::USE_SLASH_COMMANDS);
+
+        let guild = *guild_id.get_uuid();
+        let command = self
+            .interaction_repository
+            .find_command(input.command_id)
+            .await?
+            .filter(|c| c.guild_id.is_none_or(|g| g == guild))
+            .ok_or(CoreError::CommandNotFound)?;
+        let application = self
+            .application_repository
+            .find_by_id(command.application_id)
+            .await?
+            .ok_or(CoreError::CommandNotFound)?;
+        self.application_repository
+            .find_guild_bot(guild, application.id)
+            .await?
+            .ok_or(CoreError::CommandNotFound)?;
+
+        let options = validate_invocation(&command, input.options)?;
+
+        let (token, token_hash) = new_token();
+        let interaction = Interaction {
+            id: Uuid::now_v7(),
+            application_id: application.id,
+            command_id: Some(command.id),
+            command_name: command.name,
+            guild_id: guild,
+            channel_id: channel_id.get_uuid(),
+            user_id,
+            username: identity.username().to_string(),
+            options,
+            status: InteractionStatus::Pending,
+            ephemeral: false,
+            created_at: Utc::now(),
+            token: None,
+        };
+        self.interaction_repository
+            .insert_interaction(&interaction, &token_hash)
+            .await?;
+        let interaction = Interaction {
+            token: Some(token),
+            ..interaction
+        };
+
+        let delivery = match (
+            application.interactions_endpoint_url,
+            application.interactions_secret,
+        ) {
+            (Some(url), Some(secret)) => {
+                let body = json!({ "type": "application_command", "data": &interaction });
+                InteractionDelivery::Http(signed_request(
+                    url,
+                    &secret,
+                    body.to_string(),
+                    Some(interaction.id),
+                ))
+            }
+            _ => InteractionDelivery::Gateway {
+                bot_user_id: application.bot_user_id,
+            },
+        };
+
+        Ok(Invocation {
+            interaction,
+            delivery,
+        })
+    }
+
+    async fn deliver_http(
+        &self,
+        interaction: &Interaction,
+        request: SignedRequest,
+    ) -> Result<Option<InteractionReply>, CoreError> {
+        let body = self
+            .interaction_sender
+            .send(request)
+            .await
+            .map_err(|reason| {
+                invalid_interaction(format!("interactions endpoint failed: {reason}"))
+            })?;
+
+        let Some(body) = body else {
+            return Ok(None);
+        };
+        let callback: InteractionCallback = serde_json::from_str(&body).map_err(|e| {
+            invalid_interaction(format!(
+                "interactions endpoint answered with an invalid response: {e}"
+            ))
+        })?;
+        self.acknowledge_with(interaction, callback).await.map(Some)
+    }
+
+    async fn respond(
+        &self,
+        interaction_id: Uuid,
+        token: &str,
+        callback: InteractionCallback,
+    ) -> Result<InteractionReply, CoreError> {
+        let interaction = self.find_with_token(interaction_id, token).await?;
+        self.acknowledge_with(&interaction, callback).await
+    }
+
+    async fn follow_up(
+        &self,
+        interaction_id: Uuid,
+        token: &str,
+        input: FollowUpInput,
+    ) -> Result<InteractionReply, CoreError> {
+        let interaction = self.find_with_token(interaction_id, token).await?;
+        match interaction.status {
+            InteractionStatus::Responded | InteractionStatus::Deferred => {}
+            InteractionStatus::Pending => {
+                return Err(invalid_interaction(
+                    "answer or defer the interaction before following up",
+                ));
+            }
+            InteractionStatus::Failed => return Err(CoreError::InteractionExpired),
+        }
+        if Utc::now() > interaction.created_at + INTERACTION_FOLLOW_UP_WINDOW {
+            return Err(CoreError::InteractionExpired);
+        }
+
+        let answer = validate_answer(input.content, input.embeds)?;
+        let ephemeral = input.ephemeral.unwrap_or(interaction.ephemeral);
+        self.post(&interaction, answer, ephemeral).await
+    }
+
+    async fn fail_unanswered(
+        &self,
+        interaction_id: Uuid,
+    ) -> Result<Option<Interaction>, CoreError> {
+        self.interaction_repository
+            .fail_pending(interaction_id)
+            .await
+    }
+
+    async fn prune_interactions(&self) -> Result<u64, CoreError> {
+        self.interaction_repository
+            .prune_interactions(Utc::now() - INTERACTION_FOLLOW_UP_WINDOW)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ferriscord_entities::interaction::CommandOptionChoice;
+
+    use super::*;
+
+    fn option(name: &str, kind: CommandOptionType, required: bool) -> CommandOption {
+        CommandOption {
+            name: name.to_string(),
+            description: format!("The {name}"),
+            kind,
+            required,
+            choices: Vec::new(),
+        }
+    }
+
+    fn given(name: &str, value: CommandOptionValue) -> InteractionOption {
+        InteractionOption {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn test_validate_command_and_invocation() {
+        assert!(validate_name("command", "Ban".to_string()).is_err());
+        assert!(
+            validate_options(vec![
+                option("reason", CommandOptionType::String, false),
+                option("user", CommandOptionType::User, true),
+            ])
+            .is_err()
+        );
+
+        let mut unit = option("unit", CommandOptionType::String, false);
+        unit.choices = vec![CommandOptionChoice {
+            name: "Minutes".to_string(),
+            value: CommandOptionValue::String("m".to_string()),
+        }];
+        let options = validate_options(vec![
+            option("user", CommandOptionType::User, true),
+            option("duration", CommandOptionType::Number, false),
+            unit,
+        ])
+        .unwrap();
+
+        let now = Utc::now();
+        let command = ApplicationCommand {
+            id: Uuid::now_v7(),
+            application_id: Uuid::now_v7(),
+            guild_id: None,
+            name: "mute".to_string(),
+            description: "Mute a member".to_string(),
+            options,
+            created_at: now,
+            updated_at: now,
+        };
+        let user = CommandOptionValue::String(Uuid::now_v7().to_string());
+
+        let valid = validate_invocation(
+            &command,
+            vec![
+                given("unit", CommandOptionValue::String("m".to_string())),
+                given("duration", CommandOptionValue::Integer(10)),
+                given("user", user.clone()),
+            ],
+        )
+        .unwrap();
+        let names: Vec<_> = valid.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, ["user", "duration", "unit"]);
+
+        for options in [
+            vec![],
+            vec![given("user", CommandOptionValue::String("bob".to_string()))],
+            vec![given("user", user.clone()), given("user", user.clone())],
+            vec![
+                given("user", user.clone()),
+                given("force", CommandOptionValue::Boolean(true)),
+            ],
+            vec![
+                given("user", user.clone()),
+                given("unit", CommandOptionValue::String("h".to_string())),
+            ],
+        ] {
+            assert!(validate_invocation(&command, options).is_err());
+        }
+    }
+}
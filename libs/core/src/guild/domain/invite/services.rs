@@ -65,17 +65,17 @@ where
             .ok_or(CoreError::InviteNotFound)?;
 
         // Check expiry
-        if let Some(expires_at) = invite.expires_at {
-            if expires_at < chrono::Utc::now() {
-                return Err(CoreError::InviteExpired);
-            }
+        if let Some(expires_at) = invite.expires_at
+            && expires_at < chrono::Utc::now()
+        {
+            return Err(CoreError::InviteExpired);
         }
 
         // Check max uses
-        if let Some(max) = invite.max_uses {
-            if invite.uses >= max {
-                return Err(CoreError::InviteMaxUsesReached);
-            }
+        if let Some(max) = invite.max_uses
+            && invite.uses >= max
+        {
+            return Err(CoreError::InviteMaxUsesReached);
         }
 
         let guild = self
@@ -0,0 +1,4 @@
+pub mod ports;
+mod services;
+
+pub use services::LinkPreviewServiceImpl;
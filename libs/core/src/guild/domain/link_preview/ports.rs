@@ -0,0 +1,79 @@
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{channel::ChannelId, embed::Embed, guild::GuildId, message::Message};
+
+use crate::guild::domain::errors::CoreError;
+
+/// Links of one message that get a preview.
+pub const MAX_PREVIEWS_PER_MESSAGE: usize = 5;
+/// Links longer than this are not previewed.
+pub const MAX_PREVIEW_URL_LEN: usize = 2048;
+pub const PREVIEW_FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+/// Bytes of a page read while looking for its metadata.
+pub const MAX_PREVIEW_PAGE_BYTES: usize = 1024 * 1024;
+pub const MAX_PREVIEW_REDIRECTS: usize = 3;
+/// How long a fetched preview is reused.
+pub const PREVIEW_CACHE_TTL: TimeDelta = TimeDelta::days(1);
+/// How long a page that could not be fetched is not tried again.
+pub const FAILED_PREVIEW_CACHE_TTL: TimeDelta = TimeDelta::hours(1);
+
+/// A cached fetch. `embed` is `None` if the page had no preview or could not
+/// be fetched.
+#[derive(Debug, Clone)]
+pub struct CachedPreview {
+    pub embed: Option<Embed>,
+    pub fetched_at: DateTime<Utc>,
+}
+
+pub trait LinkPreviewFetcher: Send + Sync {
+    /// Fetches the OpenGraph metadata of a page as a link embed. `Ok(None)`
+    /// if the page has none; the error is the reason the fetch failed.
+    fn fetch(&self, url: &str) -> impl Future<Output = Result<Option<Embed>, String>> + Send;
+}
+
+pub trait LinkPreviewRepository: Send + Sync {
+    fn find(
+        &self,
+        url: &str,
+    ) -> impl Future<Output = Result<Option<CachedPreview>, CoreError>> + Send;
+
+    /// Stores the outcome of a fetch, replacing an earlier one.
+    fn store(
+        &self,
+        url: &str,
+        embed: Option<&Embed>,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Deletes fetches older than `fetched_before`. Returns how many.
+    fn prune(
+        &self,
+        fetched_before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
+
+pub trait LinkPreviewService: Send + Sync {
+    /// Links of a new or edited message to preview. None for encrypted
+    /// messages or authors without EMBED_LINKS; webhook messages, sent
+    /// without an identity, are always previewed.
+    fn links_to_preview(
+        &self,
+        identity: Option<Identity>,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message: &Message,
+    ) -> impl Future<Output = Result<Vec<String>, CoreError>> + Send;
+
+    /// Fetches or reuses the previews of `links` and attaches them to the
+    /// message, replacing earlier ones. Returns the updated message, or
+    /// `None` if nothing changed or the message was edited meanwhile.
+    fn preview_message(
+        &self,
+        message: &Message,
+        links: Vec<String>,
+    ) -> impl Future<Output = Result<Option<Message>, CoreError>> + Send;
+
+    /// Drops expired previews from the cache. Returns how many.
+    fn prune_previews(&self) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
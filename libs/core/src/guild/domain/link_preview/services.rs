@@ -0,0 +1,216 @@
+use chrono::Utc;
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    channel::ChannelId,
+    embed::{Embed, EmbedKind},
+    guild::GuildId,
+    message::Message,
+};
+use ferriscord_permission::Permissions;
+use tracing::warn;
+
+use crate::guild::domain::{
+    channel::ports::ChannelPort, common::build_channel_permission_context, errors::CoreError,
+    guild::ports::GuildPort, member::ports::MemberRepository, message::ports::MessagePort,
+    role::ports::RoleRepository,
+};
+
+use super::ports::{
+    FAILED_PREVIEW_CACHE_TTL, LinkPreviewFetcher, LinkPreviewRepository, LinkPreviewService,
+    MAX_PREVIEW_URL_LEN, MAX_PREVIEWS_PER_MESSAGE, PREVIEW_CACHE_TTL,
+};
+
+#[derive(Clone)]
+pub struct LinkPreviewServiceImpl<G, C, R, M, Msg, P, F>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    P: LinkPreviewRepository,
+    F: LinkPreviewFetcher,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) message_repository: Msg,
+    pub(crate) preview_repository: P,
+    pub(crate) fetcher: F,
+}
+
+/// Characters ending a link that belong to the sentence around it, as in
+/// "see https://x.y." A closing parenthesis is only dropped when unbalanced.
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ':', ';', '!', '?', ']', '}', '*', '_'];
+
+fn trim_link(mut link: &str) -> &str {
+    while let Some(last) = link.chars().last() {
+        let trailing = if last == ')' {
+            link.matches(')').count() > link.matches('(').count()
+        } else {
+            TRAILING_PUNCTUATION.contains(&last)
+        };
+        if !trailing {
+            break;
+        }
+        link = &link[..link.len() - last.len_utf8()];
+    }
+    link
+}
+
+/// The http(s) links of a message, in order and without duplicates. Links
+/// wrapped in `<...>` are not previewed, as on Discord.
+fn extract_links(content: &str) -> Vec<String> {
+    let mut links: Vec<String> = Vec::new();
+    for word in content.split_whitespace() {
+        let Some(start) = word.find("https://").or_else(|| word.find("http://")) else {
+            continue;
+        };
+        let rest = &word[start..];
+        let end = rest.find(['<', '>', '"', '\'', '`']).unwrap_or(rest.len());
+        if word[..start].ends_with('<') && rest[end..].starts_with('>') {
+            continue;
+        }
+        let link = trim_link(&rest[..end]);
+        let host = link.split_once("://").map_or("", |(_, r)| r);
+        if host.is_empty() || host.starts_with('/') || link.len() > MAX_PREVIEW_URL_LEN {
+            continue;
+        }
+        if !links.iter().any(|l| l == link) {
+            links.push(link.to_string());
+        }
+        if links.len() == MAX_PREVIEWS_PER_MESSAGE {
+            break;
+        }
+    }
+    links
+}
+
+impl<G, C, R, M, Msg, P, F> LinkPreviewServiceImpl<G, C, R, M, Msg, P, F>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    P: LinkPreviewRepository,
+    F: LinkPreviewFetcher,
+{
+    /// The preview of one link, from the cache while fresh.
+    async fn preview(&self, url: &str) -> Result<Option<Embed>, CoreError> {
+        if let Some(cached) = self.preview_repository.find(url).await? {
+            let ttl = if cached.embed.is_some() {
+                PREVIEW_CACHE_TTL
+            } else {
+                FAILED_PREVIEW_CACHE_TTL
+            };
+            if cached.fetched_at + ttl > Utc::now() {
+                return Ok(cached.embed);
+            }
+        }
+
+        let embed = match self.fetcher.fetch(url).await {
+            Ok(embed) => embed,
+            Err(e) => {
+                warn!("failed to fetch link preview of {}: {}", url, e);
+                None
+            }
+        };
+        self.preview_repository.store(url, embed.as_ref()).await?;
+        Ok(embed)
+    }
+}
+
+impl<G, C, R, M, Msg, P, F> LinkPreviewService for LinkPreviewServiceImpl<G, C, R, M, Msg, P, F>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    P: LinkPreviewRepository,
+    F: LinkPreviewFetcher,
+{
+    async fn links_to_preview(
+        &self,
+        identity: Option<Identity>,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message: &Message,
+    ) -> Result<Vec<String>, CoreError> {
+        if message.encrypted {
+            return Ok(Vec::new());
+        }
+        if let Some(identity) = identity {
+            let mut permission_context = build_channel_permission_context(
+                &self.guild_repository,
+                &self.member_repository,
+                &self.role_repository,
+                &self.channel_repository,
+                &identity,
+                &guild_id,
+                &channel_id,
+            )
+            .await?;
+            if !permission_context.can(Permissions::EMBED_LINKS) {
+                return Ok(Vec::new());
+            }
+        }
+        Ok(extract_links(&message.content))
+    }
+
+    async fn preview_message(
+        &self,
+        message: &Message,
+        links: Vec<String>,
+    ) -> Result<Option<Message>, CoreError> {
+        let mut previews = Vec::new();
+        for link in &links {
+            if let Some(embed) = self.preview(link).await? {
+                previews.push(embed);
+            }
+        }
+
+        let had_previews = message.embeds.iter().any(|e| e.kind == EmbedKind::Link);
+        if previews.is_empty() && !had_previews {
+            return Ok(None);
+        }
+        self.message_repository
+            .set_link_embeds(message.id.get_uuid(), &message.content, previews)
+            .await
+    }
+
+    async fn prune_previews(&self) -> Result<u64, CoreError> {
+        self.preview_repository
+            .prune(Utc::now() - PREVIEW_CACHE_TTL)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_links() {
+        assert_eq!(
+            extract_links(
+                "see https://example.com/a, (http://example.org/b) and https://example.com/a again"
+            ),
+            vec!["https://example.com/a", "http://example.org/b"]
+        );
+        assert_eq!(
+            extract_links("(https://en.wikipedia.org/wiki/Rust_(programming_language)).")[0],
+            "https://en.wikipedia.org/wiki/Rust_(programming_language)"
+        );
+        assert!(extract_links("no preview for <https://example.com/quiet>").is_empty());
+        assert!(extract_links("ftp://example.com https:// http:///path").is_empty());
+
+        let many: Vec<String> = (0..8).map(|i| format!("https://example.com/{i}")).collect();
+        assert_eq!(
+            extract_links(&many.join(" ")).len(),
+            MAX_PREVIEWS_PER_MESSAGE
+        );
+    }
+}
@@ -0,0 +1,392 @@
+//! Lazy member list.
+//!
+//! Members are flattened into rows: one group header per hoisted role (highest
+//! position first), then `online`, then `offline`, each followed by its
+//! members. Clients subscribe to row ranges and receive ops that transform
+//! the rows they hold into the current rows.
+
+use std::collections::{HashMap, HashSet};
+
+use ferriscord_entities::presence::PresenceStatus;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::ports::MemberWithUser;
+
+pub const ONLINE_GROUP: &str = "online";
+pub const OFFLINE_GROUP: &str = "offline";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MemberListGroup {
+    /// Hoisted role id, `online` or `offline`.
+    pub id: String,
+    pub count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MemberListMember {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub status: PresenceStatus,
+    pub roles: Vec<Uuid>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MemberListItem {
+    Group(MemberListGroup),
+    Member(MemberListMember),
+}
+
+#[derive(PartialEq, Eq, Hash)]
+enum ItemKey<'a> {
+    Group(&'a str),
+    Member(Uuid),
+}
+
+impl MemberListItem {
+    fn key(&self) -> ItemKey<'_> {
+        match self {
+            Self::Group(group) => ItemKey::Group(&group.id),
+            Self::Member(member) => ItemKey::Member(member.user_id),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MemberList {
+    pub items: Vec<MemberListItem>,
+    pub groups: Vec<MemberListGroup>,
+    pub member_count: usize,
+    pub online_count: usize,
+}
+
+/// Inclusive row range, `[0, 99]` being the first hundred rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct MemberListRange(pub usize, pub usize);
+
+impl MemberListRange {
+    fn window<'a>(&self, items: &'a [MemberListItem]) -> &'a [MemberListItem] {
+        let start = self.0.min(items.len());
+        let end = self.1.saturating_add(1).min(items.len());
+        &items[start..end]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum MemberListOp {
+    Sync {
+        range: MemberListRange,
+        items: Vec<MemberListItem>,
+    },
+    Insert {
+        index: usize,
+        item: MemberListItem,
+    },
+    Update {
+        index: usize,
+        item: MemberListItem,
+    },
+    Delete {
+        index: usize,
+    },
+}
+
+pub fn build_member_list(
+    members: &[MemberWithUser],
+    statuses: &HashMap<Uuid, PresenceStatus>,
+) -> MemberList {
+    let mut hoisted: Vec<(i32, Uuid)> = Vec::new();
+    let mut by_group: HashMap<String, Vec<MemberListMember>> = HashMap::new();
+    let mut online_count = 0;
+
+    for member in members {
+        let status = statuses
+            .get(&member.user_id)
+            .copied()
+            .unwrap_or(PresenceStatus::Offline);
+
+        let group = if status == PresenceStatus::Offline {
+            OFFLINE_GROUP.to_string()
+        } else {
+            online_count += 1;
+            match member
+                .roles
+                .iter()
+                .filter(|role| role.hoist)
+                .max_by_key(|role| (role.position, role.id))
+            {
+                Some(role) => {
+                    if !hoisted.iter().any(|(_, id)| *id == role.id) {
+                        hoisted.push((role.position, role.id));
+                    }
+                    role.id.to_string()
+                }
+                None => ONLINE_GROUP.to_string(),
+            }
+        };
+
+        by_group.entry(group).or_default().push(MemberListMember {
+            user_id: member.user_id,
+            username: member.username.clone(),
+            display_name: member.display_name.clone(),
+            avatar_url: member.avatar_url.clone(),
+            status,
+            roles: member.roles.iter().map(|role| role.id).collect(),
+        });
+    }
+
+    hoisted.sort_by(|a, b| b.cmp(a));
+    let order = hoisted
+        .into_iter()
+        .map(|(_, id)| id.to_string())
+        .chain([ONLINE_GROUP.to_string(), OFFLINE_GROUP.to_string()]);
+
+    let mut list = MemberList {
+        member_count: members.len(),
+        online_count,
+        ..Default::default()
+    };
+
+    for id in order {
+        let Some(mut group_members) = by_group.remove(&id) else {
+            continue;
+        };
+        group_members.sort_by_cached_key(|m| {
+            (
+                m.display_name
+                    .as_deref()
+                    .unwrap_or(&m.username)
+                    .to_lowercase(),
+                m.user_id,
+            )
+        });
+
+        let group = MemberListGroup {
+            id,
+            count: group_members.len(),
+        };
+        list.groups.push(group.clone());
+        list.items.push(MemberListItem::Group(group));
+        list.items
+            .extend(group_members.into_iter().map(MemberListItem::Member));
+    }
+
+    list
+}
+
+pub fn sync_range(items: &[MemberListItem], range: MemberListRange) -> MemberListOp {
+    MemberListOp::Sync {
+        range,
+        items: range.window(items).to_vec(),
+    }
+}
+
+/// Ops that turn the rows of `range` in `old` into the rows of `range` in
+/// `new`, applied in order. Indexes are absolute row positions.
+pub fn diff_range(
+    old: &[MemberListItem],
+    new: &[MemberListItem],
+    range: MemberListRange,
+) -> Vec<MemberListOp> {
+    let target = range.window(new);
+    let mut current: Vec<&MemberListItem> = range.window(old).iter().collect();
+    let mut ops = Vec::new();
+
+    let target_keys: HashSet<ItemKey<'_>> = target.iter().map(MemberListItem::key).collect();
+    for i in (0..current.len()).rev() {
+        if !target_keys.contains(&current[i].key()) {
+            current.remove(i);
+            ops.push(MemberListOp::Delete { index: range.0 + i });
+        }
+    }
+
+    for (i, item) in target.iter().enumerate() {
+        if let Some(existing) = current.get(i)
+            && existing.key() == item.key()
+        {
+            if *existing != item {
+                current[i] = item;
+                ops.push(MemberListOp::Update {
+                    index: range.0 + i,
+                    item: item.clone(),
+                });
+            }
+            continue;
+        }
+
+        if let Some(pos) = current[i..].iter().position(|c| c.key() == item.key()) {
+            current.remove(i + pos);
+            ops.push(MemberListOp::Delete {
+                index: range.0 + i + pos,
+            });
+        }
+        current.insert(i, item);
+        ops.push(MemberListOp::Insert {
+            index: range.0 + i,
+            item: item.clone(),
+        });
+    }
+
+    while current.len() > target.len() {
+        current.pop();
+        ops.push(MemberListOp::Delete {
+            index: range.0 + current.len(),
+        });
+    }
+
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::*;
+    use crate::guild::domain::member::ports::RoleSummary;
+
+    fn role(position: i32, hoist: bool) -> RoleSummary {
+        RoleSummary {
+            id: Uuid::now_v7(),
+            name: format!("role-{position}"),
+            color: 0,
+            position,
+            hoist,
+        }
+    }
+
+    fn member(username: &str, roles: Vec<RoleSummary>) -> MemberWithUser {
+        MemberWithUser {
+            member_id: Uuid::now_v7(),
+            user_id: Uuid::now_v7(),
+            username: username.to_string(),
+            display_name: None,
+            avatar_url: None,
+            joined_at: Utc::now(),
+            roles,
+        }
+    }
+
+    fn labels(items: &[MemberListItem]) -> Vec<String> {
+        items
+            .iter()
+            .map(|item| match item {
+                MemberListItem::Group(g) => format!("#{}", g.id),
+                MemberListItem::Member(m) => m.username.clone(),
+            })
+            .collect()
+    }
+
+    fn apply(rows: &mut Vec<MemberListItem>, ops: &[MemberListOp], range: MemberListRange) {
+        for op in ops {
+            match op {
+                MemberListOp::Insert { index, item } => rows.insert(index - range.0, item.clone()),
+                MemberListOp::Update { index, item } => rows[index - range.0] = item.clone(),
+                MemberListOp::Delete { index } => {
+                    rows.remove(index - range.0);
+                }
+                MemberListOp::Sync { items, .. } => *rows = items.clone(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_groups_hoisted_roles_then_online_then_offline() {
+        let admin = role(10, true);
+        let moderator = role(5, true);
+        let plain = role(20, false);
+
+        let members = vec![
+            member("zed", vec![moderator.clone()]),
+            member("amy", vec![admin.clone(), moderator.clone()]),
+            member("bob", vec![plain.clone()]),
+            member("cat", vec![admin.clone()]),
+            member("dan", vec![]),
+        ];
+        let statuses = members
+            .iter()
+            .filter(|m| m.username != "cat")
+            .map(|m| (m.user_id, PresenceStatus::Online))
+            .collect();
+
+        let list = build_member_list(&members, &statuses);
+
+        assert_eq!(
+            labels(&list.items),
+            vec![
+                format!("#{}", admin.id),
+                "amy".to_string(),
+                format!("#{}", moderator.id),
+                "zed".to_string(),
+                "#online".to_string(),
+                "bob".to_string(),
+                "dan".to_string(),
+                "#offline".to_string(),
+                "cat".to_string(),
+            ]
+        );
+        assert_eq!(list.member_count, 5);
+        assert_eq!(list.online_count, 4);
+        assert_eq!(list.groups.len(), 4);
+    }
+
+    #[test]
+    fn test_diff_moves_member_between_groups() {
+        let members = vec![member("amy", vec![]), member("bob", vec![]), member("cat", vec![])];
+        let mut statuses: HashMap<Uuid, PresenceStatus> = members
+            .iter()
+            .map(|m| (m.user_id, PresenceStatus::Online))
+            .collect();
+        let old = build_member_list(&members, &statuses);
+
+        statuses.insert(members[1].user_id, PresenceStatus::Offline);
+        let new = build_member_list(&members, &statuses);
+
+        let range = MemberListRange(0, 99);
+        let ops = diff_range(&old.items, &new.items, range);
+        let mut rows = range.window(&old.items).to_vec();
+        apply(&mut rows, &ops, range);
+
+        assert_eq!(rows, new.items);
+        assert_eq!(
+            labels(&rows),
+            vec!["#online", "amy", "cat", "#offline", "bob"]
+        );
+    }
+
+    #[test]
+    fn test_diff_only_touches_subscribed_range() {
+        let mut members: Vec<MemberWithUser> =
+            (0..10).map(|i| member(&format!("user-{i:02}"), vec![])).collect();
+        let statuses = HashMap::new();
+        let old = build_member_list(&members, &statuses);
+
+        members.remove(2);
+        members.push(member("user-99", vec![]));
+        let new = build_member_list(&members, &statuses);
+
+        let range = MemberListRange(5, 7);
+        let ops = diff_range(&old.items, &new.items, range);
+        let mut rows = range.window(&old.items).to_vec();
+        apply(&mut rows, &ops, range);
+
+        assert_eq!(rows, range.window(&new.items));
+        assert!(ops.iter().all(|op| match op {
+            MemberListOp::Insert { index, .. }
+            | MemberListOp::Update { index, .. }
+            | MemberListOp::Delete { index } => (5..=7).contains(index),
+            MemberListOp::Sync { .. } => false,
+        }));
+    }
+
+    #[test]
+    fn test_diff_is_empty_when_nothing_changed() {
+        let members = vec![member("amy", vec![]), member("bob", vec![])];
+        let list = build_member_list(&members, &HashMap::new());
+
+        assert!(diff_range(&list.items, &list.items, MemberListRange(0, 99)).is_empty());
+    }
+}
@@ -4,11 +4,13 @@ use uuid::Uuid;
 
 use crate::guild::domain::errors::CoreError;
 
+#[derive(Clone)]
 pub struct RoleSummary {
     pub id: Uuid,
     pub name: String,
     pub color: u32,
     pub position: i32,
+    pub hoist: bool,
 }
 
 pub struct MemberWithUser {
@@ -0,0 +1,57 @@
+//! Checks of the embeds bots, webhooks and interactions send along messages.
+
+use ferriscord_entities::embed::{Embed, EmbedKind};
+
+use super::ports::{MAX_EMBED_FIELDS, MAX_EMBED_TEXT_LEN, MAX_EMBEDS};
+
+fn check_len(what: &str, value: Option<&str>, max: usize) -> Result<(), String> {
+    if value.is_some_and(|v| v.chars().count() > max) {
+        return Err(format!("{what} must be at most {max} characters"));
+    }
+    Ok(())
+}
+
+/// Validates sent embeds. They are always rich embeds: link previews are
+/// only added by the server. The error is the reason, for the caller to wrap
+/// in its own error.
+pub(crate) fn validate_embeds(mut embeds: Vec<Embed>) -> Result<Vec<Embed>, String> {
+    if embeds.len() > MAX_EMBEDS {
+        return Err(format!("at most {MAX_EMBEDS} embeds are allowed"));
+    }
+    for embed in &mut embeds {
+        embed.kind = EmbedKind::Rich;
+        embed.provider = None;
+        check_len("embed title", embed.title.as_deref(), 256)?;
+        check_len("embed description", embed.description.as_deref(), 4096)?;
+        check_len(
+            "embed author name",
+            embed.author.as_ref().map(|a| a.name.as_str()),
+            256,
+        )?;
+        check_len(
+            "embed footer",
+            embed.footer.as_ref().map(|f| f.text.as_str()),
+            2048,
+        )?;
+        if embed.color.is_some_and(|c| c > 0xFF_FFFF) {
+            return Err("embed color must be an RGB value".to_string());
+        }
+        if embed.fields.len() > MAX_EMBED_FIELDS {
+            return Err(format!("an embed has at most {MAX_EMBED_FIELDS} fields"));
+        }
+        for field in &embed.fields {
+            if field.name.trim().is_empty() || field.value.trim().is_empty() {
+                return Err("embed fields need a name and a value".to_string());
+            }
+            check_len("embed field name", Some(&field.name), 256)?;
+            check_len("embed field value", Some(&field.value), 1024)?;
+        }
+    }
+    let total: usize = embeds.iter().map(Embed::text_len).sum();
+    if total > MAX_EMBED_TEXT_LEN {
+        return Err(format!(
+            "embeds must total at most {MAX_EMBED_TEXT_LEN} characters"
+        ));
+    }
+    Ok(embeds)
+}
@@ -1,3 +1,4 @@
+pub(crate) mod embeds;
 pub mod ports;
 pub mod services;
 
@@ -4,12 +4,18 @@ use ferriscord_auth::Identity;
 use ferriscord_entities::{
     attachment::AttachmentId,
     channel::ChannelId,
+    embed::Embed,
     guild::GuildId,
-    message::{Message, MessageId},
+    message::{Message, MessageId, MessageReference},
 };
 use uuid::Uuid;
 
-use crate::guild::domain::errors::CoreError;
+use crate::guild::domain::{errors::CoreError, poll::ports::PollInput};
+
+pub const MAX_EMBEDS: usize = 10;
+pub const MAX_EMBED_FIELDS: usize = 25;
+/// Combined text of all embeds of one message.
+pub const MAX_EMBED_TEXT_LEN: usize = 6000;
 
 /// Data needed to persist one attachment (files already uploaded to S3 by the handler).
 pub struct AttachmentInput {
@@ -29,6 +35,32 @@ pub struct EncryptionMeta {
     pub sender_device_id: Option<Uuid>,
 }
 
+/// A message posted through a webhook. `username` and `avatar_url` are what
+/// the message displays, already resolved against the webhook's defaults.
+pub struct WebhookMessageInput {
+    pub webhook_id: Uuid,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub content: String,
+    pub embeds: Vec<Embed>,
+    pub attachments: Vec<AttachmentInput>,
+}
+
+/// How a crossposted copy displays: the source guild and channel it was
+/// published from.
+pub struct CrosspostInput {
+    pub source_guild_id: Uuid,
+    pub username: String,
+    pub avatar_url: Option<String>,
+}
+
+/// A copy of a published announcement in a following channel.
+#[derive(Debug, Clone)]
+pub struct Crosspost {
+    pub guild_id: Uuid,
+    pub message: Message,
+}
+
 pub trait MessagePort: Send + Sync {
     /// `author_sub` is the JWT `sub` claim (oauth_sub in the users table).
     fn insert(
@@ -36,10 +68,17 @@ pub trait MessagePort: Send + Sync {
         channel_id: &ChannelId,
         author_sub: &str,
         content: String,
+        embeds: Vec<Embed>,
         attachments: Vec<AttachmentInput>,
         encryption: EncryptionMeta,
     ) -> impl Future<Output = Result<Message, CoreError>> + Send;
 
+    fn insert_webhook_message(
+        &self,
+        channel_id: &ChannelId,
+        input: WebhookMessageInput,
+    ) -> impl Future<Output = Result<Message, CoreError>> + Send;
+
     fn list_by_channel(
         &self,
         channel_id: &ChannelId,
@@ -47,12 +86,66 @@ pub trait MessagePort: Send + Sync {
         limit: u32,
     ) -> impl Future<Output = Result<Vec<Message>, CoreError>> + Send;
 
+    fn find_by_id(
+        &self,
+        message_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Message>, CoreError>> + Send;
+
+    /// Replaces the content of a message of the caller in the channel.
+    /// Returns `None` if not found or not owned by the caller.
+    fn update_content(
+        &self,
+        channel_id: &ChannelId,
+        message_id: Uuid,
+        caller_sub: &str,
+        content: &str,
+    ) -> impl Future<Output = Result<Option<Message>, CoreError>> + Send;
+
+    /// Replaces the link previews of a message, keeping its other embeds.
+    /// Returns `None` if the message is gone or its content is no longer
+    /// `expected_content`, so previews of an outdated version are dropped.
+    fn set_link_embeds(
+        &self,
+        message_id: Uuid,
+        expected_content: &str,
+        previews: Vec<Embed>,
+    ) -> impl Future<Output = Result<Option<Message>, CoreError>> + Send;
+
     /// Returns true if deleted, false if not found or not owned by caller.
     fn delete(
         &self,
         message_id: Uuid,
         caller_sub: &str,
     ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Marks a message as published. Returns false if it already was.
+    fn mark_crossposted(
+        &self,
+        message_id: Uuid,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Copies a published message, with its embeds and attachments, into a
+    /// following channel.
+    fn insert_crosspost(
+        &self,
+        channel_id: &ChannelId,
+        source: &Message,
+        input: CrosspostInput,
+    ) -> impl Future<Output = Result<Message, CoreError>> + Send;
+
+    /// Gives the copies of a message its new content.
+    fn update_crossposts(
+        &self,
+        source_message_id: Uuid,
+        content: &str,
+    ) -> impl Future<Output = Result<Vec<Crosspost>, CoreError>> + Send;
+
+    /// Deletes the copies of a message. Returns the guild, channel and id of
+    /// each deleted copy.
+    fn delete_crossposts(
+        &self,
+        source_message_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<MessageReference>, CoreError>> + Send;
 }
 
 pub trait MessageService: Send + Sync {
@@ -65,21 +158,47 @@ pub trait MessageService: Send + Sync {
         limit: u32,
     ) -> impl Future<Output = Result<Vec<Message>, CoreError>> + Send;
 
+    /// Returns a single message of the channel. Requires VIEW_CHANNEL.
+    fn get_message(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+    ) -> impl Future<Output = Result<Message, CoreError>> + Send;
+
+    /// Only bots may send embeds. Encrypted messages cannot carry a poll.
+    #[allow(clippy::too_many_arguments)]
     fn send_message(
         &self,
         identity: Identity,
         guild_id: GuildId,
         channel_id: ChannelId,
         content: String,
+        embeds: Vec<Embed>,
         attachments: Vec<AttachmentInput>,
         encryption: EncryptionMeta,
+        poll: Option<PollInput>,
     ) -> impl Future<Output = Result<Message, CoreError>> + Send;
 
+    /// Only the author may edit a message. Edits of a published
+    /// announcement are carried over to its copies, which are returned.
+    fn edit_message(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+        content: String,
+    ) -> impl Future<Output = Result<(Message, Vec<Crosspost>), CoreError>> + Send;
+
+    /// Deleting a published announcement deletes its copies too. Returns
+    /// where the deleted copies were.
     fn delete_message(
         &self,
         identity: Identity,
         guild_id: GuildId,
         channel_id: ChannelId,
         message_id: Uuid,
-    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+    ) -> impl Future<Output = Result<Vec<MessageReference>, CoreError>> + Send;
 }
@@ -1,42 +1,60 @@
+use chrono::Utc;
 use ferriscord_auth::Identity;
 use ferriscord_entities::{
     channel::ChannelId,
+    embed::Embed,
     guild::GuildId,
-    message::{Message, MessageId},
+    message::{Message, MessageId, MessageReference},
 };
 use ferriscord_permission::{Permissions, require_permission};
 use uuid::Uuid;
 
 use crate::guild::domain::{
-    channel::ports::ChannelPort, common::build_channel_permission_context, errors::CoreError,
-    guild::ports::GuildPort, member::ports::MemberRepository, role::ports::RoleRepository,
+    channel::ports::ChannelPort,
+    common::build_channel_permission_context,
+    emoji::{check_emoji_usage, ports::EmojiRepository},
+    errors::CoreError,
+    guild::ports::GuildPort,
+    member::ports::MemberRepository,
+    poll::{
+        ports::{PollInput, PollRepository},
+        validate_poll,
+    },
+    role::ports::RoleRepository,
 };
 
-use super::ports::{AttachmentInput, EncryptionMeta, MessagePort, MessageService};
+use super::embeds::validate_embeds;
+use super::ports::{AttachmentInput, Crosspost, EncryptionMeta, MessagePort, MessageService};
 
 #[derive(Clone)]
-pub struct MessageServiceImpl<G, Msg, R, M, C>
+pub struct MessageServiceImpl<G, Msg, R, M, C, E, P>
 where
     G: GuildPort,
     Msg: MessagePort,
     R: RoleRepository,
     M: MemberRepository,
     C: ChannelPort,
+    E: EmojiRepository,
+    P: PollRepository,
 {
     pub(crate) guild_repository: G,
     pub(crate) message_repository: Msg,
     pub(crate) role_repository: R,
     pub(crate) member_repository: M,
     pub(crate) channel_repository: C,
+    pub(crate) emoji_repository: E,
+    pub(crate) poll_repository: P,
 }
 
-impl<G, Msg, R, M, C> MessageService for MessageServiceImpl<G, Msg, R, M, C>
+impl<G, Msg, R, M, C, E, P> MessageService for MessageServiceImpl<G, Msg, R, M, C, E, P>
 where
     G: GuildPort,
     Msg: MessagePort,
     R: RoleRepository,
     M: MemberRepository,
     C: ChannelPort,
+    E: EmojiRepository,
+    P: PollRepository,
 {
     async fn get_channel_messages(
         &self,
@@ -64,14 +82,43 @@ where
             .await
     }
 
+    async fn get_message(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+    ) -> Result<Message, CoreError> {
+        let mut permission_context = build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            &identity,
+            &guild_id,
+            &channel_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, Permissions::VIEW_CHANNEL);
+
+        self.message_repository
+            .find_by_id(message_id)
+            .await?
+            .filter(|m| m.channel_id == channel_id)
+            .ok_or(CoreError::MessageNotFound)
+    }
+
     async fn send_message(
         &self,
         identity: Identity,
         guild_id: GuildId,
         channel_id: ChannelId,
         content: String,
+        embeds: Vec<Embed>,
         attachments: Vec<AttachmentInput>,
         encryption: EncryptionMeta,
+        poll: Option<PollInput>,
     ) -> Result<Message, CoreError> {
         let mut permission_context = build_channel_permission_context(
             &self.guild_repository,
@@ -86,9 +133,123 @@ where
 
         require_permission!(permission_context, Permissions::SEND_MESSAGES);
 
+        if !encryption.encrypted {
+            check_emoji_usage(
+                &self.emoji_repository,
+                &self.member_repository,
+                &identity,
+                &guild_id,
+                &mut permission_context,
+                &content,
+            )
+            .await?;
+        }
+
+        let embeds = if embeds.is_empty() {
+            embeds
+        } else {
+            if !identity.is_bot() {
+                return Err(CoreError::InvalidMessage {
+                    message: "only bots and webhooks can send embeds".into(),
+                });
+            }
+            require_permission!(permission_context, Permissions::EMBED_LINKS);
+            validate_embeds(embeds).map_err(|message| CoreError::InvalidMessage { message })?
+        };
+
+        let poll = match poll {
+            Some(_) if encryption.encrypted => {
+                return Err(CoreError::InvalidPoll {
+                    message: "encrypted messages cannot carry a poll".into(),
+                });
+            }
+            Some(poll) => Some(validate_poll(poll, Utc::now())?),
+            None => None,
+        };
+
+        let message = self
+            .message_repository
+            .insert(&channel_id, identity.id(), content, embeds, attachments, encryption)
+            .await?;
+        let Some(poll) = poll else {
+            return Ok(message);
+        };
+
+        let message_id = message.id.0.0;
+        if let Err(e) = self.poll_repository.insert(message_id, &poll).await {
+            // A poll message without its poll would be misleading.
+            let _ = self
+                .message_repository
+                .delete(message_id, identity.id())
+                .await;
+            return Err(e);
+        }
         self.message_repository
-            .insert(&channel_id, identity.id(), content, attachments, encryption)
-            .await
+            .find_by_id(message_id)
+            .await?
+            .ok_or(CoreError::MessageNotFound)
+    }
+
+    async fn edit_message(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+        content: String,
+    ) -> Result<(Message, Vec<Crosspost>), CoreError> {
+        let mut permission_context = build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            &identity,
+            &guild_id,
+            &channel_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, Permissions::VIEW_CHANNEL);
+
+        let existing = self
+            .message_repository
+            .find_by_id(message_id)
+            .await?
+            .filter(|m| m.channel_id == channel_id)
+            .ok_or(CoreError::MessageNotFound)?;
+        if content.trim().is_empty() && existing.attachments.is_empty() && existing.embeds.is_empty()
+        {
+            return Err(CoreError::InvalidMessage {
+                message: "message must not be empty".into(),
+            });
+        }
+
+        if !existing.encrypted {
+            check_emoji_usage(
+                &self.emoji_repository,
+                &self.member_repository,
+                &identity,
+                &guild_id,
+                &mut permission_context,
+                &content,
+            )
+            .await?;
+        }
+
+        let message = self
+            .message_repository
+            .update_content(&channel_id, message_id, identity.id(), &content)
+            .await?
+            .ok_or(CoreError::InsufficientPermissions)?;
+
+        let crossposts = if message.crossposted {
+            self.message_repository
+                .update_crossposts(message_id, &message.content)
+                .await?
+        } else {
+            Vec::new()
+        };
+        Ok((message, crossposts))
     }
 
     async fn delete_message(
@@ -97,7 +258,7 @@ where
         guild_id: GuildId,
         _channel_id: ChannelId,
         message_id: Uuid,
-    ) -> Result<(), CoreError> {
+    ) -> Result<Vec<MessageReference>, CoreError> {
         let mut permission_context = build_channel_permission_context(
             &self.guild_repository,
             &self.member_repository,
@@ -118,6 +279,6 @@ where
         if !deleted {
             return Err(CoreError::InsufficientPermissions);
         }
-        Ok(())
+        self.message_repository.delete_crossposts(message_id).await
     }
 }
@@ -0,0 +1,5 @@
+pub mod ports;
+mod services;
+
+pub use services::PollServiceImpl;
+pub(crate) use services::validate_poll;
@@ -0,0 +1,113 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{channel::ChannelId, guild::GuildId, message::Message, poll::Poll};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+pub const MAX_POLL_ANSWERS: usize = 10;
+pub const MAX_POLL_QUESTION_LEN: usize = 300;
+pub const MAX_POLL_ANSWER_LEN: usize = 55;
+/// Shortest and longest time a poll can stay open.
+pub const MIN_POLL_DURATION: TimeDelta = TimeDelta::minutes(1);
+pub const MAX_POLL_DURATION: TimeDelta = TimeDelta::days(32);
+
+/// A poll sent along with a new message.
+pub struct PollInput {
+    pub question: String,
+    pub answers: Vec<String>,
+    pub allow_multiselect: bool,
+    pub hide_results: bool,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A poll closed by `close_due`, with the guild its message is in.
+pub struct ClosedPoll {
+    pub guild_id: Uuid,
+    pub message_id: Uuid,
+}
+
+pub trait PollRepository: Send + Sync {
+    fn insert(
+        &self,
+        message_id: Uuid,
+        input: &PollInput,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Records a vote. With `exclusive`, the voter's other votes on the poll
+    /// are replaced. Returns false if the poll closed in the meantime.
+    fn vote(
+        &self,
+        message_id: Uuid,
+        answer_id: i32,
+        user_id: Uuid,
+        exclusive: bool,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Returns false if the vote did not exist or the poll is closed.
+    fn unvote(
+        &self,
+        message_id: Uuid,
+        answer_id: i32,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// The answers `user_id` voted for.
+    fn find_votes(
+        &self,
+        message_id: Uuid,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<i32>, CoreError>> + Send;
+
+    /// Closes up to `limit` polls that expired by `now`, freezing their
+    /// counts. Each poll is closed by one replica only.
+    fn close_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<ClosedPoll>, CoreError>> + Send;
+}
+
+pub trait PollService: Send + Sync {
+    /// The poll of a message, with the caller's votes. Requires VIEW_CHANNEL.
+    fn get_poll(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+    ) -> impl Future<Output = Result<Poll, CoreError>> + Send;
+
+    /// Votes for an answer. On single-choice polls this replaces the
+    /// caller's previous vote. Requires VIEW_CHANNEL.
+    #[allow(clippy::too_many_arguments)]
+    fn vote(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+        answer_id: i32,
+    ) -> impl Future<Output = Result<Poll, CoreError>> + Send;
+
+    /// Removes the caller's vote for an answer. Requires VIEW_CHANNEL.
+    #[allow(clippy::too_many_arguments)]
+    fn unvote(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+        answer_id: i32,
+    ) -> impl Future<Output = Result<Poll, CoreError>> + Send;
+
+    /// Closes expired polls. Returns their messages, with final results,
+    /// and the guild of each.
+    fn close_expired_polls(
+        &self,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<(Uuid, Message)>, CoreError>> + Send;
+}
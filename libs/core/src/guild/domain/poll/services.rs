@@ -0,0 +1,269 @@
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{channel::ChannelId, guild::GuildId, message::Message, poll::Poll};
+use ferriscord_permission::{Permissions, require_permission};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    channel::ports::ChannelPort, common::build_channel_permission_context, errors::CoreError,
+    guild::ports::GuildPort, member::ports::MemberRepository, message::ports::MessagePort,
+    role::ports::RoleRepository,
+};
+
+use super::ports::{
+    MAX_POLL_ANSWER_LEN, MAX_POLL_ANSWERS, MAX_POLL_DURATION, MAX_POLL_QUESTION_LEN,
+    MIN_POLL_DURATION, PollInput, PollRepository, PollService,
+};
+
+#[derive(Clone)]
+pub struct PollServiceImpl<G, C, R, M, Msg, P>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    P: PollRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) message_repository: Msg,
+    pub(crate) poll_repository: P,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidPoll {
+        message: message.into(),
+    }
+}
+
+/// Checks a poll sent with a new message, trimming its texts.
+pub(crate) fn validate_poll(
+    mut input: PollInput,
+    now: DateTime<Utc>,
+) -> Result<PollInput, CoreError> {
+    input.question = input.question.trim().to_string();
+    if input.question.is_empty() {
+        return Err(invalid("question must not be empty"));
+    }
+    if input.question.chars().count() > MAX_POLL_QUESTION_LEN {
+        return Err(invalid(format!(
+            "question must be at most {MAX_POLL_QUESTION_LEN} characters"
+        )));
+    }
+
+    if input.answers.len() < 2 || input.answers.len() > MAX_POLL_ANSWERS {
+        return Err(invalid(format!(
+            "a poll must have between 2 and {MAX_POLL_ANSWERS} answers"
+        )));
+    }
+    for answer in &mut input.answers {
+        *answer = answer.trim().to_string();
+        if answer.is_empty() {
+            return Err(invalid("answers must not be empty"));
+        }
+        if answer.chars().count() > MAX_POLL_ANSWER_LEN {
+            return Err(invalid(format!(
+                "answers must be at most {MAX_POLL_ANSWER_LEN} characters"
+            )));
+        }
+    }
+
+    if input.expires_at < now + MIN_POLL_DURATION || input.expires_at > now + MAX_POLL_DURATION {
+        return Err(invalid(format!(
+            "expires_at must be between {} minute and {} days from now",
+            MIN_POLL_DURATION.num_minutes(),
+            MAX_POLL_DURATION.num_days()
+        )));
+    }
+    Ok(input)
+}
+
+impl<G, C, R, M, Msg, P> PollServiceImpl<G, C, R, M, Msg, P>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    P: PollRepository,
+{
+    /// The poll of a message in a channel the caller can view.
+    async fn find_poll(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+        message_id: Uuid,
+    ) -> Result<Poll, CoreError> {
+        let mut permission_context = build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            identity,
+            guild_id,
+            channel_id,
+        )
+        .await?;
+        require_permission!(permission_context, Permissions::VIEW_CHANNEL);
+
+        let message = self
+            .message_repository
+            .find_by_id(message_id)
+            .await?
+            .filter(|m| m.channel_id == *channel_id)
+            .ok_or(CoreError::MessageNotFound)?;
+        message.poll.ok_or(CoreError::PollNotFound)
+    }
+
+    /// The poll as the caller sees it once their vote changed.
+    async fn reload_poll(&self, message_id: Uuid, user_id: Uuid) -> Result<Poll, CoreError> {
+        let mut poll = self
+            .message_repository
+            .find_by_id(message_id)
+            .await?
+            .and_then(|m| m.poll)
+            .ok_or(CoreError::PollNotFound)?;
+        poll.my_votes = self.poll_repository.find_votes(message_id, user_id).await?;
+        Ok(poll)
+    }
+}
+
+/// Votes can only change on an open poll, and only for its answers.
+fn check_can_vote(poll: &Poll, answer_id: i32) -> Result<(), CoreError> {
+    if poll.closed || poll.expires_at <= Utc::now() {
+        return Err(CoreError::PollClosed);
+    }
+    if !poll.answers.iter().any(|a| a.id == answer_id) {
+        return Err(invalid(format!("unknown answer {answer_id}")));
+    }
+    Ok(())
+}
+
+impl<G, C, R, M, Msg, P> PollService for PollServiceImpl<G, C, R, M, Msg, P>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    P: PollRepository,
+{
+    async fn get_poll(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+    ) -> Result<Poll, CoreError> {
+        let mut poll = self
+            .find_poll(&identity, &guild_id, &channel_id, message_id)
+            .await?;
+        poll.my_votes = self.poll_repository.find_votes(message_id, user_id).await?;
+        Ok(poll)
+    }
+
+    async fn vote(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+        answer_id: i32,
+    ) -> Result<Poll, CoreError> {
+        let poll = self
+            .find_poll(&identity, &guild_id, &channel_id, message_id)
+            .await?;
+        check_can_vote(&poll, answer_id)?;
+
+        if !self
+            .poll_repository
+            .vote(message_id, answer_id, user_id, !poll.allow_multiselect)
+            .await?
+        {
+            return Err(CoreError::PollClosed);
+        }
+        self.reload_poll(message_id, user_id).await
+    }
+
+    async fn unvote(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+        answer_id: i32,
+    ) -> Result<Poll, CoreError> {
+        let poll = self
+            .find_poll(&identity, &guild_id, &channel_id, message_id)
+            .await?;
+        check_can_vote(&poll, answer_id)?;
+
+        // Removing a vote that does not exist is not an error.
+        self.poll_repository
+            .unvote(message_id, answer_id, user_id)
+            .await?;
+        self.reload_poll(message_id, user_id).await
+    }
+
+    async fn close_expired_polls(&self, limit: i64) -> Result<Vec<(Uuid, Message)>, CoreError> {
+        let closed = self.poll_repository.close_due(Utc::now(), limit).await?;
+
+        let mut messages = Vec::with_capacity(closed.len());
+        for poll in closed {
+            if let Some(message) = self.message_repository.find_by_id(poll.message_id).await? {
+                messages.push((poll.guild_id, message));
+            }
+        }
+        Ok(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn input(answers: &[&str], expires_in: TimeDelta) -> PollInput {
+        PollInput {
+            question: " Lunch? ".into(),
+            answers: answers.iter().map(|a| a.to_string()).collect(),
+            allow_multiselect: false,
+            hide_results: false,
+            expires_at: Utc::now() + expires_in,
+        }
+    }
+
+    #[test]
+    fn test_validate_poll() {
+        let now = Utc::now();
+        let poll = validate_poll(input(&["Pizza ", "Sushi"], TimeDelta::hours(1)), now).unwrap();
+        assert_eq!(poll.question, "Lunch?");
+        assert_eq!(poll.answers, vec!["Pizza", "Sushi"]);
+
+        assert!(validate_poll(input(&["Pizza"], TimeDelta::hours(1)), now).is_err());
+        assert!(validate_poll(input(&["Pizza", "  "], TimeDelta::hours(1)), now).is_err());
+        assert!(
+            validate_poll(
+                input(&["a"; MAX_POLL_ANSWERS + 1], TimeDelta::hours(1)),
+                now
+            )
+            .is_err()
+        );
+        assert!(validate_poll(input(&["Pizza", "Sushi"], TimeDelta::seconds(5)), now).is_err());
+        assert!(
+            validate_poll(
+                input(&["Pizza", "Sushi"], MAX_POLL_DURATION + TimeDelta::hours(1)),
+                now
+            )
+            .is_err()
+        );
+    }
+}
@@ -17,6 +17,7 @@ pub struct UpdateRoleInput {
     pub name: String,
     pub permissions: u64,
     pub color: Option<u32>,
+    pub hoist: Option<bool>,
 }
 
 pub struct FindRoleInput {
@@ -76,5 +76,6 @@ pub trait RoleRepository: Send + Sync {
         name: &str,
         color: u32,
         permissions: u64,
+        hoist: Option<bool>,
     ) -> impl Future<Output = Result<Role, CoreError>> + Send;
 }
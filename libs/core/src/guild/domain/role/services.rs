@@ -102,6 +102,7 @@ where
                 &input.name,
                 input.color.unwrap_or_default(),
                 input.permissions,
+                input.hoist,
             )
             .await
     }
@@ -0,0 +1,134 @@
+//! Rendering of guild events as an iCalendar (RFC 5545) document.
+
+use chrono::{DateTime, Utc};
+use ferriscord_entities::scheduled_event::{GuildScheduledEvent, ScheduledEventStatus};
+
+/// An event as it appears in a feed, with its location spelled out.
+pub struct CalendarEntry {
+    pub event: GuildScheduledEvent,
+    pub location: String,
+}
+
+/// Content lines may be at most 75 octets long.
+const MAX_LINE_OCTETS: usize = 75;
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Appends a content line, folded so that no line exceeds 75 octets and no
+/// character is split.
+fn push_line(out: &mut String, line: &str) {
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(c);
+        width += c.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+pub fn render_calendar(name: &str, entries: &[CalendarEntry], now: DateTime<Utc>) -> String {
+    let mut out = String::new();
+    push_line(&mut out, "BEGIN:VCALENDAR");
+    push_line(&mut out, "VERSION:2.0");
+    push_line(&mut out, "PRODID:-//FerrisCord//Guild Events//EN");
+    push_line(&mut out, "CALSCALE:GREGORIAN");
+    push_line(&mut out, &format!("X-WR-CALNAME:{}", escape_text(name)));
+
+    for CalendarEntry { event, location } in entries {
+        push_line(&mut out, "BEGIN:VEVENT");
+        push_line(&mut out, &format!("UID:{}@ferriscord", event.id));
+        push_line(&mut out, &format!("DTSTAMP:{}", format_time(now)));
+        push_line(
+            &mut out,
+            &format!("DTSTART:{}", format_time(event.starts_at)),
+        );
+        push_line(&mut out, &format!("DTEND:{}", format_time(event.ends_at)));
+        push_line(&mut out, &format!("SUMMARY:{}", escape_text(&event.title)));
+        if !event.description.is_empty() {
+            push_line(
+                &mut out,
+                &format!("DESCRIPTION:{}", escape_text(&event.description)),
+            );
+        }
+        push_line(&mut out, &format!("LOCATION:{}", escape_text(location)));
+        let status = match event.status {
+            ScheduledEventStatus::Canceled => "CANCELLED",
+            _ => "CONFIRMED",
+        };
+        push_line(&mut out, &format!("STATUS:{status}"));
+        push_line(&mut out, "END:VEVENT");
+    }
+
+    push_line(&mut out, "END:VCALENDAR");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+    use ferriscord_entities::scheduled_event::ScheduledEventLocation;
+    use uuid::Uuid;
+
+    use super::*;
+
+    #[test]
+    fn test_render_calendar() {
+        let now = Utc::now();
+        let event = GuildScheduledEvent {
+            id: Uuid::now_v7(),
+            guild_id: Uuid::now_v7(),
+            creator_id: None,
+            title: "Game night; bring snacks, please".into(),
+            description: "é".repeat(60),
+            starts_at: now,
+            ends_at: now + TimeDelta::hours(2),
+            location: ScheduledEventLocation::External {
+                location: "Town hall".into(),
+            },
+            status: ScheduledEventStatus::Canceled,
+            interested_count: 0,
+            going_count: 0,
+            my_rsvp: None,
+            created_at: now,
+        };
+        let calendar = render_calendar(
+            "Rustaceans",
+            &[CalendarEntry {
+                event,
+                location: "Town hall".into(),
+            }],
+            now,
+        );
+
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+        assert!(calendar.contains("SUMMARY:Game night\\; bring snacks\\, please\r\n"));
+        assert!(calendar.contains("STATUS:CANCELLED\r\n"));
+        assert!(
+            calendar
+                .split("\r\n")
+                .all(|line| line.len() <= MAX_LINE_OCTETS)
+        );
+        assert!(calendar.contains("\r\n é"));
+    }
+}
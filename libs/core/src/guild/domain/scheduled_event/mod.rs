@@ -0,0 +1,5 @@
+pub mod ical;
+pub mod ports;
+mod services;
+
+pub use services::ScheduledEventServiceImpl;
@@ -0,0 +1,235 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    guild::GuildId,
+    scheduled_event::{EventRsvp, GuildScheduledEvent, RsvpStatus, ScheduledEventLocation},
+    stage_instance::StageInstance,
+};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+/// Scheduled and active events per guild.
+pub const MAX_OPEN_EVENTS_PER_GUILD: i64 = 100;
+pub const MAX_EVENT_TITLE_LEN: usize = 100;
+pub const MAX_EVENT_DESCRIPTION_LEN: usize = 1000;
+pub const MAX_EVENT_LOCATION_LEN: usize = 100;
+/// How far ahead an event can start, and how long it can last.
+pub const MAX_EVENT_AHEAD: TimeDelta = TimeDelta::days(365);
+pub const MAX_EVENT_DURATION: TimeDelta = TimeDelta::days(7);
+/// How long before the start members who answered are reminded.
+pub const EVENT_REMINDER_LEAD: TimeDelta = TimeDelta::minutes(15);
+/// How far back ended and canceled events stay in calendar feeds.
+pub const CALENDAR_HISTORY: TimeDelta = TimeDelta::days(30);
+
+pub struct CreateEventInput {
+    pub title: String,
+    pub description: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub location: ScheduledEventLocation,
+}
+
+/// A feed found by its token, with the identity of the member it is for.
+pub struct CalendarFeed {
+    pub guild_id: Uuid,
+    pub user_id: Uuid,
+    pub identity: Identity,
+}
+
+/// An event about to start, with the members to remind.
+pub struct EventReminder {
+    pub event: GuildScheduledEvent,
+    pub user_ids: Vec<Uuid>,
+}
+
+/// An event that started, with the stage it opened, if any.
+pub struct StartedEvent {
+    pub event: GuildScheduledEvent,
+    pub stage: Option<StageInstance>,
+}
+
+/// What `process_due_events` did, for the caller to publish.
+#[derive(Default)]
+pub struct DueEvents {
+    pub reminders: Vec<EventReminder>,
+    pub started: Vec<StartedEvent>,
+    pub completed: Vec<GuildScheduledEvent>,
+}
+
+/// Events are returned with their RSVP counts. `my_rsvp` is only filled
+/// where a `user_id` is given.
+pub trait ScheduledEventRepository: Send + Sync {
+    fn insert(
+        &self,
+        event: &GuildScheduledEvent,
+    ) -> impl Future<Output = Result<GuildScheduledEvent, CoreError>> + Send;
+
+    fn find_by_id(
+        &self,
+        event_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> impl Future<Output = Result<Option<GuildScheduledEvent>, CoreError>> + Send;
+
+    /// Scheduled and active events of the guild, soonest first.
+    fn list_open(
+        &self,
+        guild_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> impl Future<Output = Result<Vec<GuildScheduledEvent>, CoreError>> + Send;
+
+    /// Events of the guild that end after `since`, whatever their status.
+    fn list_since(
+        &self,
+        guild_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<GuildScheduledEvent>, CoreError>> + Send;
+
+    fn count_open(&self, guild_id: Uuid) -> impl Future<Output = Result<i64, CoreError>> + Send;
+
+    /// Returns `None` if the event is already over or canceled.
+    fn cancel(
+        &self,
+        event_id: Uuid,
+    ) -> impl Future<Output = Result<Option<GuildScheduledEvent>, CoreError>> + Send;
+
+    /// Returns false if the event is over or canceled.
+    fn set_rsvp(
+        &self,
+        event_id: Uuid,
+        user_id: Uuid,
+        status: RsvpStatus,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    fn remove_rsvp(
+        &self,
+        event_id: Uuid,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Oldest answers first.
+    fn list_rsvps(
+        &self,
+        event_id: Uuid,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<EventRsvp>, CoreError>> + Send;
+
+    /// Marks events starting by `until` as reminded, and returns them with
+    /// the members who answered. Each reminder is claimed by one replica.
+    fn claim_reminders(
+        &self,
+        until: DateTime<Utc>,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<EventReminder>, CoreError>> + Send;
+
+    /// Moves scheduled events that started by `now` to active.
+    fn start_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<GuildScheduledEvent>, CoreError>> + Send;
+
+    /// Moves open events that ended by `now` to completed.
+    fn complete_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<GuildScheduledEvent>, CoreError>> + Send;
+
+    /// Stores the feed of a member, replacing their previous one.
+    fn upsert_calendar_feed(
+        &self,
+        guild_id: Uuid,
+        user_id: Uuid,
+        token_hash: &str,
+        identity: &Identity,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn find_calendar_feed(
+        &self,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<CalendarFeed>, CoreError>> + Send;
+}
+
+/// Members see the events of the guild, except those in channels they
+/// cannot view. Creating and canceling requires MANAGE_GUILD.
+pub trait ScheduledEventService: Send + Sync {
+    fn create_event(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        input: CreateEventInput,
+    ) -> impl Future<Output = Result<GuildScheduledEvent, CoreError>> + Send;
+
+    fn list_events(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+    ) -> impl Future<Output = Result<Vec<GuildScheduledEvent>, CoreError>> + Send;
+
+    fn get_event(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        event_id: Uuid,
+    ) -> impl Future<Output = Result<GuildScheduledEvent, CoreError>> + Send;
+
+    fn cancel_event(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        event_id: Uuid,
+    ) -> impl Future<Output = Result<GuildScheduledEvent, CoreError>> + Send;
+
+    /// Answers interested or going, replacing a previous answer.
+    fn set_rsvp(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        event_id: Uuid,
+        status: RsvpStatus,
+    ) -> impl Future<Output = Result<GuildScheduledEvent, CoreError>> + Send;
+
+    fn remove_rsvp(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        event_id: Uuid,
+    ) -> impl Future<Output = Result<GuildScheduledEvent, CoreError>> + Send;
+
+    fn list_rsvps(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        event_id: Uuid,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<EventRsvp>, CoreError>> + Send;
+
+    /// Creates the secret calendar feed of the caller for the guild, and
+    /// returns its token. An earlier feed of theirs stops working.
+    fn create_calendar_feed(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+    ) -> impl Future<Output = Result<String, CoreError>> + Send;
+
+    /// The iCalendar document of a feed, with the events its member can
+    /// still see.
+    fn render_calendar_feed(
+        &self,
+        token: &str,
+    ) -> impl Future<Output = Result<String, CoreError>> + Send;
+
+    /// Sends due reminders, and starts and completes events. Events in a
+    /// stage channel open the stage when they start.
+    fn process_due_events(
+        &self,
+        limit: i64,
+    ) -> impl Future<Output = Result<DueEvents, CoreError>> + Send;
+}
@@ -0,0 +1,574 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    Id,
+    channel::{ChannelId, ChannelKind},
+    guild::GuildId,
+    scheduled_event::{
+        EventRsvp, GuildScheduledEvent, RsvpStatus, ScheduledEventLocation, ScheduledEventStatus,
+    },
+    stage_instance::StageInstance,
+};
+use ferriscord_permission::{Permissions, require_permission};
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    channel::ports::ChannelPort,
+    common::{build_channel_permission_context, build_permission_context, is_member_identity},
+    errors::CoreError,
+    guild::ports::GuildPort,
+    member::ports::MemberRepository,
+    role::ports::RoleRepository,
+    stage::ports::{MAX_STAGE_TOPIC_LEN, StageInstanceRepository},
+};
+
+use super::ical::{CalendarEntry, render_calendar};
+use super::ports::{
+    CALENDAR_HISTORY, CreateEventInput, DueEvents, EVENT_REMINDER_LEAD, MAX_EVENT_AHEAD,
+    MAX_EVENT_DESCRIPTION_LEN, MAX_EVENT_DURATION, MAX_EVENT_LOCATION_LEN, MAX_EVENT_TITLE_LEN,
+    MAX_OPEN_EVENTS_PER_GUILD, ScheduledEventRepository, ScheduledEventService, StartedEvent,
+};
+
+const FEED_TOKEN_LEN: usize = 48;
+
+#[derive(Clone)]
+pub struct ScheduledEventServiceImpl<G, C, R, M, St, E>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    St: StageInstanceRepository,
+    E: ScheduledEventRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) stage_instance_repository: St,
+    pub(crate) event_repository: E,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidScheduledEvent {
+        message: message.into(),
+    }
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+/// Checks the texts and times of a new event, trimming its texts.
+fn validate_event(
+    mut input: CreateEventInput,
+    now: DateTime<Utc>,
+) -> Result<CreateEventInput, CoreError> {
+    input.title = input.title.trim().to_string();
+    if input.title.is_empty() {
+        return Err(invalid("title must not be empty"));
+    }
+    if input.title.chars().count() > MAX_EVENT_TITLE_LEN {
+        return Err(invalid(format!(
+            "title must be at most {MAX_EVENT_TITLE_LEN} characters"
+        )));
+    }
+    input.description = input.description.trim().to_string();
+    if input.description.chars().count() > MAX_EVENT_DESCRIPTION_LEN {
+        return Err(invalid(format!(
+            "description must be at most {MAX_EVENT_DESCRIPTION_LEN} characters"
+        )));
+    }
+
+    if input.starts_at <= now {
+        return Err(invalid("starts_at must be in the future"));
+    }
+    if input.starts_at > now + MAX_EVENT_AHEAD {
+        return Err(invalid(format!(
+            "starts_at must be within {} days",
+            MAX_EVENT_AHEAD.num_days()
+        )));
+    }
+    if input.ends_at <= input.starts_at {
+        return Err(invalid("ends_at must be after starts_at"));
+    }
+    if input.ends_at > input.starts_at + MAX_EVENT_DURATION {
+        return Err(invalid(format!(
+            "an event can last at most {} days",
+            MAX_EVENT_DURATION.num_days()
+        )));
+    }
+
+    if let ScheduledEventLocation::External { location } = &mut input.location {
+        *location = location.trim().to_string();
+        if location.is_empty() {
+            return Err(invalid("location must not be empty"));
+        }
+        if location.chars().count() > MAX_EVENT_LOCATION_LEN {
+            return Err(invalid(format!(
+                "location must be at most {MAX_EVENT_LOCATION_LEN} characters"
+            )));
+        }
+    }
+    Ok(input)
+}
+
+impl<G, C, R, M, St, E> ScheduledEventServiceImpl<G, C, R, M, St, E>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    St: StageInstanceRepository,
+    E: ScheduledEventRepository,
+{
+    async fn is_member(&self, identity: &Identity, guild_id: &GuildId) -> Result<bool, CoreError> {
+        Ok(self
+            .member_repository
+            .list_members(guild_id)
+            .await?
+            .iter()
+            .any(|member| is_member_identity(member, identity)))
+    }
+
+    async fn require_member(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+    ) -> Result<(), CoreError> {
+        if !self.is_member(identity, guild_id).await? {
+            return Err(CoreError::NotGuildMember);
+        }
+        Ok(())
+    }
+
+    async fn require_manage_guild(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+    ) -> Result<(), CoreError> {
+        let mut permission_context = build_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            identity,
+            guild_id,
+        )
+        .await?;
+        require_permission!(permission_context, Permissions::MANAGE_GUILD);
+        Ok(())
+    }
+
+    /// Names of the event channels the member can view. Channels they
+    /// cannot view, or that are gone, are left out.
+    async fn visible_channels(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        events: &[GuildScheduledEvent],
+    ) -> Result<HashMap<Uuid, String>, CoreError> {
+        let mut visible = HashMap::new();
+        for channel_id in events.iter().filter_map(|e| e.channel_id()) {
+            if visible.contains_key(&channel_id) {
+                continue;
+            }
+            let channel_id = ChannelId(Id(channel_id));
+            let can_view = match build_channel_permission_context(
+                &self.guild_repository,
+                &self.member_repository,
+                &self.role_repository,
+                &self.channel_repository,
+                identity,
+                guild_id,
+                &channel_id,
+            )
+            .await
+            {
+                Ok(mut permission_context) => permission_context.can(Permissions::VIEW_CHANNEL),
+                Err(CoreError::ChannelNotFound { .. }) => false,
+                Err(e) => return Err(e),
+            };
+            if can_view
+                && let Some(channel) = self.channel_repository.find_by_id(&channel_id).await?
+            {
+                visible.insert(channel_id.get_uuid(), channel.name);
+            }
+        }
+        Ok(visible)
+    }
+
+    /// An event of the guild the member can see.
+    async fn find_visible(
+        &self,
+        identity: &Identity,
+        user_id: Option<Uuid>,
+        guild_id: &GuildId,
+        event_id: Uuid,
+    ) -> Result<GuildScheduledEvent, CoreError> {
+        self.require_member(identity, guild_id).await?;
+        let event = self
+            .event_repository
+            .find_by_id(event_id, user_id)
+            .await?
+            .filter(|e| e.guild_id == *guild_id.get_uuid())
+            .ok_or(CoreError::ScheduledEventNotFound)?;
+
+        if let Some(channel_id) = event.channel_id()
+            && !self
+                .visible_channels(identity, guild_id, std::slice::from_ref(&event))
+                .await?
+                .contains_key(&channel_id)
+        {
+            return Err(CoreError::ScheduledEventNotFound);
+        }
+        Ok(event)
+    }
+
+    /// Opens the stage of an event held in a stage channel. Failing to do
+    /// so does not keep the event from starting.
+    async fn open_stage(&self, event: &GuildScheduledEvent) -> Option<StageInstance> {
+        let channel_id = event.channel_id()?;
+        // The stage needs someone to have started it.
+        let started_by = event.creator_id?;
+        let channel = match self
+            .channel_repository
+            .find_by_id(&ChannelId(Id(channel_id)))
+            .await
+        {
+            Ok(channel) => channel?,
+            Err(e) => {
+                warn!("failed to look up channel of event {}: {:?}", event.id, e);
+                return None;
+            }
+        };
+        if channel.kind != ChannelKind::Stage {
+            return None;
+        }
+
+        let instance = StageInstance {
+            id: Uuid::now_v7(),
+            guild_id: event.guild_id,
+            channel_id,
+            topic: event.title.chars().take(MAX_STAGE_TOPIC_LEN).collect(),
+            started_by,
+            started_at: Utc::now(),
+            ended_at: None,
+        };
+        match self.stage_instance_repository.insert(&instance).await {
+            Ok(instance) => instance,
+            Err(e) => {
+                warn!("failed to start the stage of event {}: {:?}", event.id, e);
+                None
+            }
+        }
+    }
+}
+
+impl<G, C, R, M, St, E> ScheduledEventService for ScheduledEventServiceImpl<G, C, R, M, St, E>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    St: StageInstanceRepository,
+    E: ScheduledEventRepository,
+{
+    async fn create_event(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        input: CreateEventInput,
+    ) -> Result<GuildScheduledEvent, CoreError> {
+        self.require_member(&identity, &guild_id).await?;
+        self.require_manage_guild(&identity, &guild_id).await?;
+        let input = validate_event(input, Utc::now())?;
+
+        if let ScheduledEventLocation::Channel { channel_id } = &input.location {
+            let channel = self
+                .channel_repository
+                .find_by_id(&ChannelId(Id(*channel_id)))
+                .await?
+                .filter(|c| c.guild_id.as_ref() == Some(&guild_id))
+                .ok_or(CoreError::ChannelNotFound {
+                    channel_id: ChannelId(Id(*channel_id)),
+                })?;
+            if !matches!(channel.kind, ChannelKind::Voice | ChannelKind::Stage) {
+                return Err(invalid("the location must be a voice or stage channel"));
+            }
+        }
+
+        if self
+            .event_repository
+            .count_open(*guild_id.get_uuid())
+            .await?
+            >= MAX_OPEN_EVENTS_PER_GUILD
+        {
+            return Err(CoreError::MaxScheduledEventsReached {
+                max_events: MAX_OPEN_EVENTS_PER_GUILD,
+            });
+        }
+
+        let event = GuildScheduledEvent {
+            id: Uuid::now_v7(),
+            guild_id: *guild_id.get_uuid(),
+            creator_id: Some(user_id),
+            title: input.title,
+            description: input.description,
+            starts_at: input.starts_at,
+            ends_at: input.ends_at,
+            location: input.location,
+            status: ScheduledEventStatus::Scheduled,
+            interested_count: 0,
+            going_count: 0,
+            my_rsvp: None,
+            created_at: Utc::now(),
+        };
+        self.event_repository.insert(&event).await
+    }
+
+    async fn list_events(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+    ) -> Result<Vec<GuildScheduledEvent>, CoreError> {
+        self.require_member(&identity, &guild_id).await?;
+        let events = self
+            .event_repository
+            .list_open(*guild_id.get_uuid(), Some(user_id))
+            .await?;
+        let visible = self.visible_channels(&identity, &guild_id, &events).await?;
+
+        Ok(events
+            .into_iter()
+            .filter(|e| e.channel_id().is_none_or(|id| visible.contains_key(&id)))
+            .collect())
+    }
+
+    async fn get_event(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        event_id: Uuid,
+    ) -> Result<GuildScheduledEvent, CoreError> {
+        self.find_visible(&identity, Some(user_id), &guild_id, event_id)
+            .await
+    }
+
+    async fn cancel_event(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        event_id: Uuid,
+    ) -> Result<GuildScheduledEvent, CoreError> {
+        self.require_manage_guild(&identity, &guild_id).await?;
+        self.find_visible(&identity, None, &guild_id, event_id)
+            .await?;
+
+        self.event_repository
+            .cancel(event_id)
+            .await?
+            .ok_or(CoreError::ScheduledEventEnded)
+    }
+
+    async fn set_rsvp(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        event_id: Uuid,
+        status: RsvpStatus,
+    ) -> Result<GuildScheduledEvent, CoreError> {
+        let event = self
+            .find_visible(&identity, None, &guild_id, event_id)
+            .await?;
+        if !event.status.is_open()
+            || !self
+                .event_repository
+                .set_rsvp(event_id, user_id, status)
+                .await?
+        {
+            return Err(CoreError::ScheduledEventEnded);
+        }
+
+        self.event_repository
+            .find_by_id(event_id, Some(user_id))
+            .await?
+            .ok_or(CoreError::ScheduledEventNotFound)
+    }
+
+    async fn remove_rsvp(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        event_id: Uuid,
+    ) -> Result<GuildScheduledEvent, CoreError> {
+        self.find_visible(&identity, None, &guild_id, event_id)
+            .await?;
+        self.event_repository.remove_rsvp(event_id, user_id).await?;
+
+        self.event_repository
+            .find_by_id(event_id, Some(user_id))
+            .await?
+            .ok_or(CoreError::ScheduledEventNotFound)
+    }
+
+    async fn list_rsvps(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        event_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<EventRsvp>, CoreError> {
+        self.find_visible(&identity, None, &guild_id, event_id)
+            .await?;
+        self.event_repository
+            .list_rsvps(event_id, limit.clamp(1, 100))
+            .await
+    }
+
+    async fn create_calendar_feed(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+    ) -> Result<String, CoreError> {
+        self.require_member(&identity, &guild_id).await?;
+
+        let token = Alphanumeric.sample_string(&mut rand::thread_rng(), FEED_TOKEN_LEN);
+        self.event_repository
+            .upsert_calendar_feed(
+                *guild_id.get_uuid(),
+                user_id,
+                &hash_token(&token),
+                &identity,
+            )
+            .await?;
+        Ok(token)
+    }
+
+    async fn render_calendar_feed(&self, token: &str) -> Result<String, CoreError> {
+        let feed = self
+            .event_repository
+            .find_calendar_feed(&hash_token(token))
+            .await?
+            .ok_or(CoreError::CalendarFeedNotFound)?;
+        let guild_id = GuildId(Id(feed.guild_id));
+        // Feeds of members who left stop working, without telling why.
+        if !self.is_member(&feed.identity, &guild_id).await? {
+            return Err(CoreError::CalendarFeedNotFound);
+        }
+        let guild = self
+            .guild_repository
+            .find_by_id(&guild_id)
+            .await?
+            .ok_or(CoreError::CalendarFeedNotFound)?;
+
+        let now = Utc::now();
+        let events = self
+            .event_repository
+            .list_since(feed.guild_id, now - CALENDAR_HISTORY)
+            .await?;
+        let visible = self
+            .visible_channels(&feed.identity, &guild_id, &events)
+            .await?;
+
+        let entries: Vec<CalendarEntry> = events
+            .into_iter()
+            .filter_map(|event| {
+                let location = match &event.location {
+                    ScheduledEventLocation::Channel { channel_id } => {
+                        format!("#{}", visible.get(channel_id)?)
+                    }
+                    ScheduledEventLocation::External { location } => location.clone(),
+                };
+                Some(CalendarEntry { event, location })
+            })
+            .collect();
+        Ok(render_calendar(&guild.name, &entries, now))
+    }
+
+    async fn process_due_events(&self, limit: i64) -> Result<DueEvents, CoreError> {
+        let now = Utc::now();
+        let reminders = self
+            .event_repository
+            .claim_reminders(now + EVENT_REMINDER_LEAD, limit)
+            .await?;
+
+        let mut started = Vec::new();
+        for event in self.event_repository.start_due(now, limit).await? {
+            let stage = self.open_stage(&event).await;
+            started.push(StartedEvent { event, stage });
+        }
+
+        let completed = self.event_repository.complete_due(now, limit).await?;
+
+        Ok(DueEvents {
+            reminders,
+            started,
+            completed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn input(starts_in: TimeDelta, lasts: TimeDelta, location: &str) -> CreateEventInput {
+        let starts_at = Utc::now() + starts_in;
+        CreateEventInput {
+            title: " Game night ".into(),
+            description: String::new(),
+            starts_at,
+            ends_at: starts_at + lasts,
+            location: ScheduledEventLocation::External {
+                location: location.into(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_validate_event() {
+        let now = Utc::now();
+        let event = validate_event(
+            input(TimeDelta::hours(1), TimeDelta::hours(2), " Pub "),
+            now,
+        )
+        .unwrap();
+        assert_eq!(event.title, "Game night");
+        assert_eq!(
+            event.location,
+            ScheduledEventLocation::External {
+                location: "Pub".into()
+            }
+        );
+
+        assert!(
+            validate_event(input(-TimeDelta::hours(1), TimeDelta::hours(2), "Pub"), now).is_err()
+        );
+        assert!(validate_event(input(TimeDelta::hours(1), TimeDelta::zero(), "Pub"), now).is_err());
+        assert!(
+            validate_event(
+                input(
+                    TimeDelta::hours(1),
+                    MAX_EVENT_DURATION + TimeDelta::hours(1),
+                    "Pub"
+                ),
+                now
+            )
+            .is_err()
+        );
+        assert!(validate_event(input(TimeDelta::hours(1), TimeDelta::hours(2), " "), now).is_err());
+    }
+}
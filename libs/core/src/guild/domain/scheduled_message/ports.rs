@@ -0,0 +1,185 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    channel::ChannelId, guild::GuildId, message::Message, scheduled_message::ScheduledMessage,
+};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+/// Pending scheduled messages and reminders per member.
+pub const MAX_PENDING_SCHEDULED_PER_USER: i64 = 100;
+/// How far ahead something can be scheduled.
+pub const MAX_SCHEDULE_AHEAD: TimeDelta = TimeDelta::days(365);
+pub const MAX_SCHEDULED_CONTENT_LEN: usize = 4000;
+pub const MAX_REMINDER_NOTE_LEN: usize = 1000;
+/// How long a claimed row stays reserved for the replica delivering it. Past
+/// that (e.g. the replica died) it is picked up again.
+pub const DELIVERY_LEASE: TimeDelta = TimeDelta::seconds(60);
+/// How long delivered and failed rows are kept for the author to see.
+pub const SCHEDULED_RETENTION: TimeDelta = TimeDelta::days(7);
+
+pub struct CreateScheduledInput {
+    pub content: String,
+    pub send_at: DateTime<Utc>,
+}
+
+/// `None` leaves a field unchanged.
+pub struct UpdateScheduledInput {
+    pub content: Option<String>,
+    pub send_at: Option<DateTime<Utc>>,
+}
+
+/// A claimed row with the identity of its author.
+pub struct ScheduledJob {
+    pub scheduled: ScheduledMessage,
+    pub identity: Identity,
+}
+
+#[derive(Debug)]
+pub enum DeliveryOutcome {
+    /// A scheduled message was posted in its channel.
+    Posted {
+        scheduled: ScheduledMessage,
+        message: Message,
+    },
+    /// A reminder is due. `message` is what it is about, unless the author
+    /// can no longer see it or it was deleted.
+    Reminded {
+        scheduled: ScheduledMessage,
+        message: Option<Message>,
+    },
+    Failed {
+        scheduled: ScheduledMessage,
+    },
+    /// Another replica got to it first.
+    Skipped,
+}
+
+pub trait ScheduledMessageRepository: Send + Sync {
+    fn insert(
+        &self,
+        scheduled: &ScheduledMessage,
+        identity: &Identity,
+    ) -> impl Future<Output = Result<ScheduledMessage, CoreError>> + Send;
+
+    fn find_by_id(
+        &self,
+        id: Uuid,
+    ) -> impl Future<Output = Result<Option<ScheduledMessage>, CoreError>> + Send;
+
+    fn list_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<ScheduledMessage>, CoreError>> + Send;
+
+    fn count_pending_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<i64, CoreError>> + Send;
+
+    /// Updates a pending row. Returns `None` if it is gone or no longer
+    /// pending.
+    fn update_pending(
+        &self,
+        id: Uuid,
+        content: &str,
+        send_at: DateTime<Utc>,
+        identity: &Identity,
+    ) -> impl Future<Output = Result<Option<ScheduledMessage>, CoreError>> + Send;
+
+    /// Returns false if not found or not owned by `user_id`.
+    fn delete(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Reserves up to `limit` due pending rows until `lease_until`.
+    fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<ScheduledJob>, CoreError>> + Send;
+
+    /// Posts a pending scheduled message as a message of its author and marks
+    /// it delivered, in one transaction. Returns the id of the new message,
+    /// or `None` if the row was no longer pending, so it is posted once even
+    /// when two replicas race for it.
+    fn post(&self, id: Uuid) -> impl Future<Output = Result<Option<Uuid>, CoreError>> + Send;
+
+    /// Marks a pending row delivered. Returns false if it no longer was
+    /// pending.
+    fn mark_delivered(&self, id: Uuid) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Marks a pending row failed. Returns false if it no longer was pending.
+    fn mark_failed(
+        &self,
+        id: Uuid,
+        reason: &str,
+    ) -> impl Future<Output = Result<bool, CoreError>> + Send;
+
+    /// Deletes delivered and failed rows older than `before`.
+    fn prune(&self, before: DateTime<Utc>) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
+
+pub trait ScheduledMessageService: Send + Sync {
+    /// Schedules a message in a channel. Requires SEND_MESSAGES now and again
+    /// at delivery time.
+    fn schedule_message(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        input: CreateScheduledInput,
+    ) -> impl Future<Output = Result<ScheduledMessage, CoreError>> + Send;
+
+    /// Schedules a private reminder about a message the caller can see.
+    /// `content` is an optional note.
+    fn schedule_reminder(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+        input: CreateScheduledInput,
+    ) -> impl Future<Output = Result<ScheduledMessage, CoreError>> + Send;
+
+    /// The caller's scheduled messages and reminders, pending ones and those
+    /// delivered or failed recently.
+    fn list_scheduled(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<ScheduledMessage>, CoreError>> + Send;
+
+    /// Edits a pending scheduled message or reminder of the caller.
+    fn update_scheduled(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        id: Uuid,
+        input: UpdateScheduledInput,
+    ) -> impl Future<Output = Result<ScheduledMessage, CoreError>> + Send;
+
+    /// Cancels a pending one, or dismisses a delivered or failed one.
+    fn cancel_scheduled(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn claim_due(
+        &self,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<ScheduledJob>, CoreError>> + Send;
+
+    fn deliver(
+        &self,
+        job: ScheduledJob,
+    ) -> impl Future<Output = Result<DeliveryOutcome, CoreError>> + Send;
+
+    fn prune_scheduled(&self) -> impl Future<Output = Result<u64, CoreError>> + Send;
+}
@@ -0,0 +1,448 @@
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    Id,
+    channel::ChannelId,
+    guild::GuildId,
+    role::PermissionContext,
+    scheduled_message::{ScheduledMessage, ScheduledMessageKind, ScheduledMessageStatus},
+};
+use ferriscord_permission::{Permissions, require_permission};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    channel::ports::ChannelPort,
+    common::{build_channel_permission_context, is_member_identity},
+    emoji::{check_emoji_usage, ports::EmojiRepository},
+    errors::CoreError,
+    guild::ports::GuildPort,
+    member::ports::MemberRepository,
+    message::ports::MessagePort,
+    role::ports::RoleRepository,
+};
+
+use super::ports::{
+    CreateScheduledInput, DELIVERY_LEASE, DeliveryOutcome, MAX_PENDING_SCHEDULED_PER_USER,
+    MAX_REMINDER_NOTE_LEN, MAX_SCHEDULE_AHEAD, MAX_SCHEDULED_CONTENT_LEN, SCHEDULED_RETENTION,
+    ScheduledJob, ScheduledMessageRepository, ScheduledMessageService, UpdateScheduledInput,
+};
+
+#[derive(Clone)]
+pub struct ScheduledMessageServiceImpl<G, C, R, M, Msg, E, S>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    E: EmojiRepository,
+    S: ScheduledMessageRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) message_repository: Msg,
+    pub(crate) emoji_repository: E,
+    pub(crate) scheduled_repository: S,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidScheduledMessage {
+        message: message.into(),
+    }
+}
+
+fn validate_send_at(send_at: DateTime<Utc>, now: DateTime<Utc>) -> Result<(), CoreError> {
+    if send_at <= now {
+        return Err(invalid("send_at must be in the future"));
+    }
+    if send_at > now + MAX_SCHEDULE_AHEAD {
+        return Err(invalid(format!(
+            "send_at must be within {} days",
+            MAX_SCHEDULE_AHEAD.num_days()
+        )));
+    }
+    Ok(())
+}
+
+/// The text of a scheduled message, or the note of a reminder.
+fn validate_content(kind: ScheduledMessageKind, content: String) -> Result<String, CoreError> {
+    let max_len = match kind {
+        ScheduledMessageKind::Message => {
+            if content.trim().is_empty() {
+                return Err(invalid("content must not be empty"));
+            }
+            MAX_SCHEDULED_CONTENT_LEN
+        }
+        ScheduledMessageKind::Reminder => MAX_REMINDER_NOTE_LEN,
+    };
+    if content.chars().count() > max_len {
+        return Err(invalid(format!(
+            "content must be at most {max_len} characters"
+        )));
+    }
+    Ok(content)
+}
+
+impl<G, C, R, M, Msg, E, S> ScheduledMessageServiceImpl<G, C, R, M, Msg, E, S>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    E: EmojiRepository,
+    S: ScheduledMessageRepository,
+{
+    async fn channel_permissions(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+    ) -> Result<PermissionContext, CoreError> {
+        build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            identity,
+            guild_id,
+            channel_id,
+        )
+        .await
+    }
+
+    /// What a scheduled message may be posted with, checked when it is
+    /// scheduled, edited and delivered.
+    async fn check_can_post(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+        content: &str,
+    ) -> Result<(), CoreError> {
+        let mut permission_context = self
+            .channel_permissions(identity, guild_id, channel_id)
+            .await?;
+        require_permission!(permission_context, Permissions::SEND_MESSAGES);
+
+        check_emoji_usage(
+            &self.emoji_repository,
+            &self.member_repository,
+            identity,
+            guild_id,
+            &mut permission_context,
+            content,
+        )
+        .await
+    }
+
+    async fn is_member(&self, identity: &Identity, guild_id: &GuildId) -> Result<bool, CoreError> {
+        Ok(self
+            .member_repository
+            .list_members(guild_id)
+            .await?
+            .iter()
+            .any(|member| is_member_identity(member, identity)))
+    }
+
+    async fn check_pending_limit(&self, user_id: Uuid) -> Result<(), CoreError> {
+        if self
+            .scheduled_repository
+            .count_pending_by_user(user_id)
+            .await?
+            >= MAX_PENDING_SCHEDULED_PER_USER
+        {
+            return Err(CoreError::MaxScheduledMessagesReached {
+                max_scheduled: MAX_PENDING_SCHEDULED_PER_USER,
+            });
+        }
+        Ok(())
+    }
+
+    /// Why a due scheduled message can no longer be posted, if so. Errors
+    /// other than lost access are returned, so the row is retried.
+    async fn post_refusal(&self, job: &ScheduledJob) -> Result<Option<String>, CoreError> {
+        let guild_id = GuildId(Id(job.scheduled.guild_id));
+        let channel_id = ChannelId(Id(job.scheduled.channel_id));
+        if !self.is_member(&job.identity, &guild_id).await? {
+            return Ok(Some("you are no longer a member of this guild".into()));
+        }
+        match self
+            .check_can_post(
+                &job.identity,
+                &guild_id,
+                &channel_id,
+                &job.scheduled.content,
+            )
+            .await
+        {
+            Ok(()) => Ok(None),
+            Err(CoreError::InsufficientPermissions) => Ok(Some(
+                "missing SEND_MESSAGES permission in this channel".into(),
+            )),
+            Err(
+                e @ (CoreError::EmojiNotAllowed { .. }
+                | CoreError::GuildNotFound { .. }
+                | CoreError::ChannelNotFound { .. }),
+            ) => Ok(Some(e.to_string())),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn deliver_message(&self, job: ScheduledJob) -> Result<DeliveryOutcome, CoreError> {
+        let mut scheduled = job.scheduled.clone();
+        if let Some(reason) = self.post_refusal(&job).await? {
+            if !self
+                .scheduled_repository
+                .mark_failed(scheduled.id, &reason)
+                .await?
+            {
+                return Ok(DeliveryOutcome::Skipped);
+            }
+            scheduled.status = ScheduledMessageStatus::Failed;
+            scheduled.failure_reason = Some(reason);
+            return Ok(DeliveryOutcome::Failed { scheduled });
+        }
+
+        let Some(message_id) = self.scheduled_repository.post(scheduled.id).await? else {
+            return Ok(DeliveryOutcome::Skipped);
+        };
+        let message = self
+            .message_repository
+            .find_by_id(message_id)
+            .await?
+            .ok_or(CoreError::MessageNotFound)?;
+
+        scheduled.status = ScheduledMessageStatus::Delivered;
+        scheduled.message_id = Some(message_id);
+        scheduled.delivered_at = Some(message.created_at);
+        Ok(DeliveryOutcome::Posted { scheduled, message })
+    }
+
+    async fn deliver_reminder(&self, job: ScheduledJob) -> Result<DeliveryOutcome, CoreError> {
+        let mut scheduled = job.scheduled;
+        if !self
+            .scheduled_repository
+            .mark_delivered(scheduled.id)
+            .await?
+        {
+            return Ok(DeliveryOutcome::Skipped);
+        }
+        scheduled.status = ScheduledMessageStatus::Delivered;
+        scheduled.delivered_at = Some(Utc::now());
+
+        // The reminder goes out either way; the message only if the author
+        // can still read it.
+        let guild_id = GuildId(Id(scheduled.guild_id));
+        let channel_id = ChannelId(Id(scheduled.channel_id));
+        let can_view = self.is_member(&job.identity, &guild_id).await?
+            && match self
+                .channel_permissions(&job.identity, &guild_id, &channel_id)
+                .await
+            {
+                Ok(mut permission_context) => permission_context.can(Permissions::VIEW_CHANNEL),
+                Err(_) => false,
+            };
+        let message = match scheduled.message_id {
+            Some(message_id) if can_view => self
+                .message_repository
+                .find_by_id(message_id)
+                .await?
+                .filter(|m| m.channel_id == channel_id),
+            _ => None,
+        };
+
+        Ok(DeliveryOutcome::Reminded { scheduled, message })
+    }
+}
+
+impl<G, C, R, M, Msg, E, S> ScheduledMessageService
+    for ScheduledMessageServiceImpl<G, C, R, M, Msg, E, S>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    E: EmojiRepository,
+    S: ScheduledMessageRepository,
+{
+    async fn schedule_message(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        input: CreateScheduledInput,
+    ) -> Result<ScheduledMessage, CoreError> {
+        let now = Utc::now();
+        let content = validate_content(ScheduledMessageKind::Message, input.content)?;
+        validate_send_at(input.send_at, now)?;
+        self.check_can_post(&identity, &guild_id, &channel_id, &content)
+            .await?;
+        self.check_pending_limit(user_id).await?;
+
+        let scheduled = ScheduledMessage {
+            id: Uuid::now_v7(),
+            kind: ScheduledMessageKind::Message,
+            user_id,
+            guild_id: *guild_id.get_uuid(),
+            channel_id: channel_id.get_uuid(),
+            message_id: None,
+            content,
+            send_at: input.send_at,
+            status: ScheduledMessageStatus::Pending,
+            failure_reason: None,
+            created_at: now,
+            delivered_at: None,
+        };
+        self.scheduled_repository
+            .insert(&scheduled, &identity)
+            .await
+    }
+
+    async fn schedule_reminder(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        message_id: Uuid,
+        input: CreateScheduledInput,
+    ) -> Result<ScheduledMessage, CoreError> {
+        let now = Utc::now();
+        let content = validate_content(ScheduledMessageKind::Reminder, input.content)?;
+        validate_send_at(input.send_at, now)?;
+
+        let mut permission_context = self
+            .channel_permissions(&identity, &guild_id, &channel_id)
+            .await?;
+        require_permission!(permission_context, Permissions::VIEW_CHANNEL);
+        self.message_repository
+            .find_by_id(message_id)
+            .await?
+            .filter(|m| m.channel_id == channel_id)
+            .ok_or(CoreError::MessageNotFound)?;
+        self.check_pending_limit(user_id).await?;
+
+        let scheduled = ScheduledMessage {
+            id: Uuid::now_v7(),
+            kind: ScheduledMessageKind::Reminder,
+            user_id,
+            guild_id: *guild_id.get_uuid(),
+            channel_id: channel_id.get_uuid(),
+            message_id: Some(message_id),
+            content,
+            send_at: input.send_at,
+            status: ScheduledMessageStatus::Pending,
+            failure_reason: None,
+            created_at: now,
+            delivered_at: None,
+        };
+        self.scheduled_repository
+            .insert(&scheduled, &identity)
+            .await
+    }
+
+    async fn list_scheduled(&self, user_id: Uuid) -> Result<Vec<ScheduledMessage>, CoreError> {
+        self.scheduled_repository.list_by_user(user_id).await
+    }
+
+    async fn update_scheduled(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        id: Uuid,
+        input: UpdateScheduledInput,
+    ) -> Result<ScheduledMessage, CoreError> {
+        let existing = self
+            .scheduled_repository
+            .find_by_id(id)
+            .await?
+            .filter(|s| s.user_id == user_id)
+            .ok_or(CoreError::ScheduledMessageNotFound)?;
+        if existing.status != ScheduledMessageStatus::Pending {
+            return Err(invalid("only pending scheduled messages can be edited"));
+        }
+
+        let content = match input.content {
+            Some(content) => validate_content(existing.kind, content)?,
+            None => existing.content,
+        };
+        let send_at = match input.send_at {
+            Some(send_at) => {
+                validate_send_at(send_at, Utc::now())?;
+                send_at
+            }
+            None => existing.send_at,
+        };
+        if existing.kind == ScheduledMessageKind::Message {
+            self.check_can_post(
+                &identity,
+                &GuildId(Id(existing.guild_id)),
+                &ChannelId(Id(existing.channel_id)),
+                &content,
+            )
+            .await?;
+        }
+
+        self.scheduled_repository
+            .update_pending(id, &content, send_at, &identity)
+            .await?
+            .ok_or_else(|| invalid("the scheduled message is no longer pending"))
+    }
+
+    async fn cancel_scheduled(&self, user_id: Uuid, id: Uuid) -> Result<(), CoreError> {
+        if !self.scheduled_repository.delete(id, user_id).await? {
+            return Err(CoreError::ScheduledMessageNotFound);
+        }
+        Ok(())
+    }
+
+    async fn claim_due(&self, limit: i64) -> Result<Vec<ScheduledJob>, CoreError> {
+        let now = Utc::now();
+        self.scheduled_repository
+            .claim_due(now, now + DELIVERY_LEASE, limit)
+            .await
+    }
+
+    async fn deliver(&self, job: ScheduledJob) -> Result<DeliveryOutcome, CoreError> {
+        match job.scheduled.kind {
+            ScheduledMessageKind::Message => self.deliver_message(job).await,
+            ScheduledMessageKind::Reminder => self.deliver_reminder(job).await,
+        }
+    }
+
+    async fn prune_scheduled(&self) -> Result<u64, CoreError> {
+        self.scheduled_repository
+            .prune(Utc::now() - SCHEDULED_RETENTION)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    #[test]
+    fn test_validate_scheduled() {
+        let now = Utc::now();
+        assert!(validate_send_at(now + TimeDelta::minutes(5), now).is_ok());
+        assert!(validate_send_at(now, now).is_err());
+        assert!(validate_send_at(now + MAX_SCHEDULE_AHEAD + TimeDelta::seconds(1), now).is_err());
+
+        assert!(validate_content(ScheduledMessageKind::Message, "  ".into()).is_err());
+        assert!(validate_content(ScheduledMessageKind::Reminder, String::new()).is_ok());
+        assert!(
+            validate_content(
+                ScheduledMessageKind::Reminder,
+                "x".repeat(MAX_REMINDER_NOTE_LEN + 1)
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    channel::ChannelId, guild::GuildId, stage_instance::StageInstance, voice_state::VoiceState,
+};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+pub const MAX_STAGE_TOPIC_LEN: usize = 120;
+
+/// Result of ending a stage: the record, and the speakers that were moved
+/// back to the audience.
+pub struct StageEnd {
+    pub instance: StageInstance,
+    pub demoted: Vec<VoiceState>,
+}
+
+pub trait StageInstanceRepository: Send + Sync {
+    /// Inserts a live stage. Returns `None` without writing when the channel
+    /// already has one.
+    fn insert(
+        &self,
+        instance: &StageInstance,
+    ) -> impl Future<Output = Result<Option<StageInstance>, CoreError>> + Send;
+
+    fn find_live(
+        &self,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<Option<StageInstance>, CoreError>> + Send;
+
+    fn update_topic(
+        &self,
+        channel_id: Uuid,
+        topic: &str,
+    ) -> impl Future<Output = Result<Option<StageInstance>, CoreError>> + Send;
+
+    /// Marks the live stage of the channel as ended.
+    fn end(
+        &self,
+        channel_id: Uuid,
+        ended_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Option<StageInstance>, CoreError>> + Send;
+
+    /// Stages of the channel, most recent first.
+    fn list_by_channel(
+        &self,
+        channel_id: Uuid,
+        limit: i64,
+    ) -> impl Future<Output = Result<Vec<StageInstance>, CoreError>> + Send;
+}
+
+/// Stage moderators are members with MUTE_MEMBERS or MOVE_MEMBERS in the
+/// stage channel.
+pub trait StageService: Send + Sync {
+    fn start_stage(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        topic: String,
+    ) -> impl Future<Output = Result<StageInstance, CoreError>> + Send;
+
+    /// The live stage of the channel, if any. Requires VIEW_CHANNEL.
+    fn get_stage(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> impl Future<Output = Result<Option<StageInstance>, CoreError>> + Send;
+
+    fn update_stage(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        topic: String,
+    ) -> impl Future<Output = Result<StageInstance, CoreError>> + Send;
+
+    fn end_stage(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> impl Future<Output = Result<StageEnd, CoreError>> + Send;
+
+    /// Past and live stages of the channel. Requires VIEW_CHANNEL.
+    fn list_stage_history(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> impl Future<Output = Result<Vec<StageInstance>, CoreError>> + Send;
+
+    /// Raises or lowers the hand of an audience member connected to the
+    /// stage.
+    fn set_hand(
+        &self,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        raised: bool,
+    ) -> impl Future<Output = Result<VoiceState, CoreError>> + Send;
+
+    /// Invites a user connected to the stage to speak, or moves them back to
+    /// the audience. Moderators only, except that anyone may step down.
+    fn set_speaker(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        target_user_id: Uuid,
+        speaker: bool,
+    ) -> impl Future<Output = Result<VoiceState, CoreError>> + Send;
+}
@@ -0,0 +1,347 @@
+use chrono::Utc;
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    channel::{Channel, ChannelId, ChannelKind},
+    guild::GuildId,
+    stage_instance::StageInstance,
+    voice_state::VoiceState,
+};
+use ferriscord_permission::{Permissions, require_permission};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    channel::ports::ChannelPort,
+    common::build_channel_permission_context,
+    errors::CoreError,
+    guild::ports::GuildPort,
+    member::ports::MemberRepository,
+    role::ports::RoleRepository,
+    voice::ports::VoiceStateRepository,
+};
+
+use super::ports::{
+    MAX_STAGE_TOPIC_LEN, StageEnd, StageInstanceRepository, StageService,
+};
+
+/// How many past stages the history lists.
+const STAGE_HISTORY_LIMIT: i64 = 50;
+
+#[derive(Clone)]
+pub struct StageServiceImpl<G, C, R, M, V, S>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    V: VoiceStateRepository,
+    S: StageInstanceRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) voice_state_repository: V,
+    pub(crate) stage_instance_repository: S,
+}
+
+fn validate_topic(topic: String) -> Result<String, CoreError> {
+    let topic = topic.trim().to_string();
+    if topic.is_empty() {
+        return Err(CoreError::InvalidStageTopic {
+            message: "topic must not be empty".to_string(),
+        });
+    }
+    if topic.chars().count() > MAX_STAGE_TOPIC_LEN {
+        return Err(CoreError::InvalidStageTopic {
+            message: format!("topic must be at most {MAX_STAGE_TOPIC_LEN} characters"),
+        });
+    }
+    Ok(topic)
+}
+
+impl<G, C, R, M, V, S> StageServiceImpl<G, C, R, M, V, S>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    V: VoiceStateRepository,
+    S: StageInstanceRepository,
+{
+    async fn stage_channel(
+        &self,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+    ) -> Result<Channel, CoreError> {
+        let channel = self
+            .channel_repository
+            .find_by_id(channel_id)
+            .await?
+            .filter(|c| c.guild_id.as_ref() == Some(guild_id))
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: channel_id.clone(),
+            })?;
+
+        if channel.kind != ChannelKind::Stage {
+            return Err(CoreError::NotStageChannel {
+                channel_id: channel_id.clone(),
+            });
+        }
+        Ok(channel)
+    }
+
+    async fn require_member(&self, guild_id: &GuildId, user_id: Uuid) -> Result<(), CoreError> {
+        let members = self.member_repository.list_members(guild_id).await?;
+        if !members.iter().any(|m| m.user_id == user_id) {
+            return Err(CoreError::NotGuildMember);
+        }
+        Ok(())
+    }
+
+    /// Returns whether the caller may moderate the stage, after checking
+    /// they can see it.
+    async fn is_moderator(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+    ) -> Result<bool, CoreError> {
+        let mut permission_context = build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            identity,
+            guild_id,
+            channel_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, Permissions::VIEW_CHANNEL);
+
+        Ok(permission_context.can(Permissions::MUTE_MEMBERS)
+            || permission_context.can(Permissions::MOVE_MEMBERS))
+    }
+
+    async fn require_moderator(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+    ) -> Result<(), CoreError> {
+        if !self.is_moderator(identity, guild_id, channel_id).await? {
+            return Err(CoreError::InsufficientPermissions);
+        }
+        Ok(())
+    }
+
+    async fn require_live(&self, channel_id: &ChannelId) -> Result<StageInstance, CoreError> {
+        self.stage_instance_repository
+            .find_live(channel_id.get_uuid())
+            .await?
+            .ok_or(CoreError::StageNotLive)
+    }
+}
+
+impl<G, C, R, M, V, S> StageService for StageServiceImpl<G, C, R, M, V, S>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    V: VoiceStateRepository,
+    S: StageInstanceRepository,
+{
+    async fn start_stage(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        topic: String,
+    ) -> Result<StageInstance, CoreError> {
+        self.stage_channel(&guild_id, &channel_id).await?;
+        self.require_member(&guild_id, user_id).await?;
+        self.require_moderator(&identity, &guild_id, &channel_id)
+            .await?;
+        let topic = validate_topic(topic)?;
+
+        let instance = StageInstance {
+            id: Uuid::now_v7(),
+            guild_id: *guild_id.get_uuid(),
+            channel_id: channel_id.get_uuid(),
+            topic,
+            started_by: user_id,
+            started_at: Utc::now(),
+            ended_at: None,
+        };
+
+        self.stage_instance_repository
+            .insert(&instance)
+            .await?
+            .ok_or(CoreError::StageAlreadyLive)
+    }
+
+    async fn get_stage(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> Result<Option<StageInstance>, CoreError> {
+        self.stage_channel(&guild_id, &channel_id).await?;
+        self.require_member(&guild_id, user_id).await?;
+        self.is_moderator(&identity, &guild_id, &channel_id).await?;
+
+        self.stage_instance_repository
+            .find_live(channel_id.get_uuid())
+            .await
+    }
+
+    async fn update_stage(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        topic: String,
+    ) -> Result<StageInstance, CoreError> {
+        self.stage_channel(&guild_id, &channel_id).await?;
+        self.require_moderator(&identity, &guild_id, &channel_id)
+            .await?;
+        let topic = validate_topic(topic)?;
+
+        self.stage_instance_repository
+            .update_topic(channel_id.get_uuid(), &topic)
+            .await?
+            .ok_or(CoreError::StageNotLive)
+    }
+
+    async fn end_stage(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> Result<StageEnd, CoreError> {
+        self.stage_channel(&guild_id, &channel_id).await?;
+        self.require_moderator(&identity, &guild_id, &channel_id)
+            .await?;
+
+        let instance = self
+            .stage_instance_repository
+            .end(channel_id.get_uuid(), Utc::now())
+            .await?
+            .ok_or(CoreError::StageNotLive)?;
+
+        // Nobody keeps the floor once the stage is over.
+        let mut demoted = Vec::new();
+        for state in self
+            .voice_state_repository
+            .list_by_channel(channel_id.get_uuid())
+            .await?
+        {
+            if state.suppress && state.request_to_speak_at.is_none() {
+                continue;
+            }
+            if let Some(state) = self
+                .voice_state_repository
+                .update_stage(state.user_id, state.channel_id, true, None)
+                .await?
+            {
+                demoted.push(state);
+            }
+        }
+
+        Ok(StageEnd { instance, demoted })
+    }
+
+    async fn list_stage_history(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+    ) -> Result<Vec<StageInstance>, CoreError> {
+        self.stage_channel(&guild_id, &channel_id).await?;
+        self.require_member(&guild_id, user_id).await?;
+        self.is_moderator(&identity, &guild_id, &channel_id).await?;
+
+        self.stage_instance_repository
+            .list_by_channel(channel_id.get_uuid(), STAGE_HISTORY_LIMIT)
+            .await
+    }
+
+    async fn set_hand(
+        &self,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        raised: bool,
+    ) -> Result<VoiceState, CoreError> {
+        self.stage_channel(&guild_id, &channel_id).await?;
+
+        let state = self
+            .voice_state_repository
+            .find_by_user(user_id)
+            .await?
+            .filter(|s| s.channel_id == channel_id.get_uuid())
+            .ok_or(CoreError::NotInVoiceChannel)?;
+
+        // Speakers already have the floor; their hand stays down.
+        if !state.suppress {
+            return Ok(state);
+        }
+        if raised {
+            self.require_live(&channel_id).await?;
+        }
+
+        let request_to_speak_at = match (raised, state.request_to_speak_at) {
+            (true, Some(at)) => Some(at),
+            (true, None) => Some(Utc::now()),
+            (false, _) => None,
+        };
+
+        self.voice_state_repository
+            .update_stage(user_id, state.channel_id, true, request_to_speak_at)
+            .await?
+            .ok_or(CoreError::NotInVoiceChannel)
+    }
+
+    async fn set_speaker(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        target_user_id: Uuid,
+        speaker: bool,
+    ) -> Result<VoiceState, CoreError> {
+        self.stage_channel(&guild_id, &channel_id).await?;
+
+        let stepping_down = !speaker && target_user_id == user_id;
+        if !stepping_down {
+            self.require_moderator(&identity, &guild_id, &channel_id)
+                .await?;
+        }
+        if speaker {
+            self.require_live(&channel_id).await?;
+        }
+
+        self.voice_state_repository
+            .update_stage(target_user_id, channel_id.get_uuid(), !speaker, None)
+            .await?
+            .ok_or(CoreError::NotInVoiceChannel)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trims_and_bounds_topics() {
+        assert_eq!(validate_topic("  Town hall  ".into()).unwrap(), "Town hall");
+        assert!(validate_topic("   ".into()).is_err());
+        assert!(validate_topic("é".repeat(MAX_STAGE_TOPIC_LEN)).is_ok());
+        assert!(validate_topic("x".repeat(MAX_STAGE_TOPIC_LEN + 1)).is_err());
+    }
+}
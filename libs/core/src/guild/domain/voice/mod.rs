@@ -0,0 +1,5 @@
+pub mod ports;
+mod services;
+pub mod signal;
+
+pub use services::VoiceServiceImpl;
@@ -0,0 +1,226 @@
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{channel::ChannelId, guild::GuildId, voice_state::VoiceState};
+use uuid::Uuid;
+
+use crate::guild::domain::errors::CoreError;
+
+pub struct JoinVoiceInput {
+    pub guild_id: GuildId,
+    pub channel_id: ChannelId,
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+    pub self_mute: bool,
+    pub self_deaf: bool,
+}
+
+/// Connection to the voice channel of a DM call.
+pub struct JoinCallInput {
+    pub channel_id: Uuid,
+    pub user_id: Uuid,
+    pub session_id: Uuid,
+    pub self_mute: bool,
+    pub self_deaf: bool,
+}
+
+/// Result of joining a voice channel. `previous` is set when the user was
+/// moved out of another channel.
+pub struct VoiceJoin {
+    pub state: VoiceState,
+    pub previous: Option<VoiceState>,
+}
+
+/// A member's server mute/deafen flags, and their voice state if connected.
+pub struct VoiceModeration {
+    pub mute: bool,
+    pub deaf: bool,
+    pub state: Option<VoiceState>,
+}
+
+/// What a voice connection may send, as enforced by the media server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VoiceMediaPolicy {
+    pub can_speak: bool,
+    pub can_stream: bool,
+    /// Server-deafened: no audio is forwarded to the connection.
+    pub deaf: bool,
+    /// Audio bitrate of the channel in bits per second.
+    pub bitrate: Option<u32>,
+}
+
+pub trait VoiceStateRepository: Send + Sync {
+    /// Inserts or replaces the user's voice state. `joined_at` is kept when the
+    /// user stays in the same channel; `mute` and `deaf` are taken from the
+    /// guild member. Returns `None` without writing when the channel already
+    /// holds `user_limit` other users.
+    fn upsert(
+        &self,
+        state: &VoiceState,
+        user_limit: Option<u32>,
+    ) -> impl Future<Output = Result<Option<VoiceState>, CoreError>> + Send;
+
+    fn find_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<VoiceState>, CoreError>> + Send;
+
+    fn list_by_guild(
+        &self,
+        guild_id: &GuildId,
+    ) -> impl Future<Output = Result<Vec<VoiceState>, CoreError>> + Send;
+
+    fn update_self(
+        &self,
+        user_id: Uuid,
+        self_mute: bool,
+        self_deaf: bool,
+    ) -> impl Future<Output = Result<Option<VoiceState>, CoreError>> + Send;
+
+    /// Sets the stage flags of a user connected to `channel_id`. Returns
+    /// `None` if they are not connected to it.
+    fn update_stage(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+        suppress: bool,
+        request_to_speak_at: Option<DateTime<Utc>>,
+    ) -> impl Future<Output = Result<Option<VoiceState>, CoreError>> + Send;
+
+    /// Updates a member's server mute/deafen flags, and their voice state in
+    /// the guild if connected. Returns `None` if they are not a member.
+    fn set_server_flags(
+        &self,
+        guild_id: &GuildId,
+        user_id: Uuid,
+        mute: Option<bool>,
+        deaf: Option<bool>,
+    ) -> impl Future<Output = Result<Option<VoiceModeration>, CoreError>> + Send;
+
+    fn list_by_channel(
+        &self,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<VoiceState>, CoreError>> + Send;
+
+    fn delete_by_user(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<VoiceState>, CoreError>> + Send;
+
+    /// Deletes voice states whose user has no gateway session that
+    /// heartbeated since `stale_before`, for one user or for everyone.
+    fn delete_without_sessions(
+        &self,
+        user_id: Option<Uuid>,
+        stale_before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<VoiceState>, CoreError>> + Send;
+}
+
+pub trait VoiceService: Send + Sync {
+    /// Connects the user to a voice channel, or moves them there from another
+    /// one. Requires CONNECT; MOVE_MEMBERS bypasses the channel's user limit.
+    /// Users join stage channels as audience.
+    fn join(
+        &self,
+        identity: Identity,
+        input: JoinVoiceInput,
+    ) -> impl Future<Output = Result<VoiceJoin, CoreError>> + Send;
+
+    /// Connects the user to the voice channel of a DM, or moves them there.
+    /// Callers check that the user joined the DM's call.
+    fn join_call(
+        &self,
+        input: JoinCallInput,
+    ) -> impl Future<Output = Result<VoiceJoin, CoreError>> + Send;
+
+    fn leave(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<VoiceState>, CoreError>> + Send;
+
+    /// Disconnects the user if they are connected to `channel_id`.
+    fn leave_channel(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<Option<VoiceState>, CoreError>> + Send;
+
+    fn set_self_state(
+        &self,
+        user_id: Uuid,
+        self_mute: Option<bool>,
+        self_deaf: Option<bool>,
+    ) -> impl Future<Output = Result<Option<VoiceState>, CoreError>> + Send;
+
+    /// Checks that a signaling message may be relayed: the sender's session
+    /// and the target are both connected to `channel_id`. Returns the target's
+    /// voice state, whose session is the one to deliver to.
+    fn authorize_signal(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+        channel_id: Uuid,
+        target_user_id: Uuid,
+    ) -> impl Future<Output = Result<VoiceState, CoreError>> + Send;
+
+    /// Media policy for the voice connection held by `session_id` in
+    /// `channel_id`, from the user's SPEAK and STREAM permissions there.
+    /// Server-muted users and stage audience members may not speak.
+    fn media_policy(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        session_id: Uuid,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<VoiceMediaPolicy, CoreError>> + Send;
+
+    /// Server-mutes or server-deafens a member; `None` leaves a flag as is.
+    /// Requires MUTE_MEMBERS to change `mute` and DEAFEN_MEMBERS to change
+    /// `deaf`, in the member's voice channel when connected.
+    fn moderate(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        target_user_id: Uuid,
+        mute: Option<bool>,
+        deaf: Option<bool>,
+    ) -> impl Future<Output = Result<VoiceModeration, CoreError>> + Send;
+
+    /// Moves a connected member to another voice channel of the guild.
+    /// Requires MOVE_MEMBERS in both channels and CONNECT in the destination;
+    /// the destination's user limit does not apply.
+    fn move_member(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        target_user_id: Uuid,
+        channel_id: ChannelId,
+    ) -> impl Future<Output = Result<VoiceJoin, CoreError>> + Send;
+
+    /// Disconnects a member from voice. Requires MOVE_MEMBERS in their
+    /// channel.
+    fn disconnect_member(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        target_user_id: Uuid,
+    ) -> impl Future<Output = Result<VoiceState, CoreError>> + Send;
+
+    /// Voice states of a guild. Only members of the guild may list them.
+    fn list_guild_voice_states(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+    ) -> impl Future<Output = Result<Vec<VoiceState>, CoreError>> + Send;
+
+    /// Called when a gateway session of the user ends: removes the user from
+    /// voice once none of their sessions is left.
+    fn session_ended(
+        &self,
+        user_id: Uuid,
+    ) -> impl Future<Output = Result<Option<VoiceState>, CoreError>> + Send;
+
+    /// Removes voice states left behind by sessions that died without
+    /// disconnecting (e.g. a replica that crashed).
+    fn reap_orphaned(&self) -> impl Future<Output = Result<Vec<VoiceState>, CoreError>> + Send;
+}
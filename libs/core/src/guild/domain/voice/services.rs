@@ -0,0 +1,670 @@
+use chrono::{DateTime, Duration, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    Id,
+    channel::{Channel, ChannelId, ChannelKind},
+    guild::GuildId,
+    role::PermissionContext,
+    voice_state::VoiceState,
+};
+use ferriscord_permission::{Permissions, require_permission};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    channel::ports::ChannelPort,
+    common::{build_channel_permission_context, build_permission_context},
+    errors::CoreError,
+    guild::ports::GuildPort,
+    member::ports::MemberRepository,
+    role::ports::RoleRepository,
+};
+
+use super::ports::{
+    JoinCallInput, JoinVoiceInput, VoiceJoin, VoiceMediaPolicy, VoiceModeration, VoiceService,
+    VoiceStateRepository,
+};
+
+/// Matches the presence session TTL: a session that has not heartbeated for
+/// this long no longer keeps its user in voice.
+const SESSION_TTL_SECS: i64 = 90;
+
+/// Voice states of users whose sessions last heartbeated before this are
+/// orphaned.
+fn stale_before(now: DateTime<Utc>) -> DateTime<Utc> {
+    now - Duration::seconds(SESSION_TTL_SECS)
+}
+
+/// The user limit a join is held to, once the joiner may view and connect to
+/// the channel. Members who can move others are let into full channels.
+fn join_user_limit(
+    permission_context: &mut PermissionContext,
+    user_limit: Option<u32>,
+) -> Result<Option<u32>, CoreError> {
+    require_permission!(permission_context, Permissions::VIEW_CHANNEL);
+    require_permission!(permission_context, Permissions::CONNECT);
+
+    if permission_context.can(Permissions::MOVE_MEMBERS) {
+        return Ok(None);
+    }
+    Ok(user_limit.filter(|limit| *limit > 0))
+}
+
+/// Checks the permissions a server mute or deafen needs, for the flags it
+/// sets.
+fn require_moderation(
+    permission_context: &mut PermissionContext,
+    mute: Option<bool>,
+    deaf: Option<bool>,
+) -> Result<(), CoreError> {
+    if mute.is_some() {
+        require_permission!(permission_context, Permissions::MUTE_MEMBERS);
+    }
+    if deaf.is_some() {
+        require_permission!(permission_context, Permissions::DEAFEN_MEMBERS);
+    }
+    Ok(())
+}
+
+/// Checks moving a member out of the channel of `source` into the one of
+/// `destination`, the mover's permissions in each.
+fn require_move(
+    source: &mut PermissionContext,
+    destination: &mut PermissionContext,
+) -> Result<(), CoreError> {
+    require_permission!(source, Permissions::MOVE_MEMBERS);
+    require_permission!(destination, Permissions::MOVE_MEMBERS);
+    require_permission!(destination, Permissions::CONNECT);
+    Ok(())
+}
+
+/// What a member in a guild voice channel may send. Audience members of a
+/// stage, still suppressed, may neither speak nor stream.
+fn guild_media_policy(
+    permission_context: &mut PermissionContext,
+    kind: ChannelKind,
+    bitrate: Option<u32>,
+    state: &VoiceState,
+) -> VoiceMediaPolicy {
+    let on_stage = kind != ChannelKind::Stage || !state.suppress;
+    VoiceMediaPolicy {
+        can_speak: on_stage && !state.mute && permission_context.can(Permissions::SPEAK),
+        can_stream: on_stage && permission_context.can(Permissions::STREAM),
+        deaf: state.deaf,
+        bitrate,
+    }
+}
+
+#[derive(Clone)]
+pub struct VoiceServiceImpl<G, C, R, M, V>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    V: VoiceStateRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) voice_state_repository: V,
+}
+
+impl<G, C, R, M, V> VoiceServiceImpl<G, C, R, M, V>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    V: VoiceStateRepository,
+{
+    async fn voice_channel(
+        &self,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+    ) -> Result<Channel, CoreError> {
+        let channel = self
+            .channel_repository
+            .find_by_id(channel_id)
+            .await?
+            .filter(|c| c.guild_id.as_ref() == Some(guild_id))
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: channel_id.clone(),
+            })?;
+
+        if !matches!(channel.kind, ChannelKind::Voice | ChannelKind::Stage) {
+            return Err(CoreError::NotVoiceChannel {
+                channel_id: channel_id.clone(),
+            });
+        }
+        Ok(channel)
+    }
+
+    async fn channel_permissions(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+    ) -> Result<PermissionContext, CoreError> {
+        build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            identity,
+            guild_id,
+            channel_id,
+        )
+        .await
+    }
+
+    /// The target's voice state, if they are connected to a channel of the
+    /// guild.
+    async fn guild_voice_state(
+        &self,
+        guild_id: &GuildId,
+        user_id: Uuid,
+    ) -> Result<Option<VoiceState>, CoreError> {
+        Ok(self
+            .voice_state_repository
+            .find_by_user(user_id)
+            .await?
+            .filter(|s| s.guild_id.as_ref() == Some(guild_id.get_uuid())))
+    }
+
+    async fn require_member(&self, guild_id: &GuildId, user_id: Uuid) -> Result<(), CoreError> {
+        let members = self.member_repository.list_members(guild_id).await?;
+        if !members.iter().any(|m| m.user_id == user_id) {
+            return Err(CoreError::NotGuildMember);
+        }
+        Ok(())
+    }
+}
+
+impl<G, C, R, M, V> VoiceService for VoiceServiceImpl<G, C, R, M, V>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    V: VoiceStateRepository,
+{
+    async fn join(&self, identity: Identity, input: JoinVoiceInput) -> Result<VoiceJoin, CoreError> {
+        let channel = self
+            .voice_channel(&input.guild_id, &input.channel_id)
+            .await?;
+
+        self.require_member(&input.guild_id, input.user_id).await?;
+
+        let mut permission_context = self
+            .channel_permissions(&identity, &input.guild_id, &input.channel_id)
+            .await?;
+
+        let user_limit = join_user_limit(&mut permission_context, channel.user_limit)?;
+
+        let previous = self.voice_state_repository.find_by_user(input.user_id).await?;
+
+        let state = VoiceState {
+            user_id: input.user_id,
+            guild_id: Some(*input.guild_id.get_uuid()),
+            channel_id: input.channel_id.get_uuid(),
+            session_id: input.session_id,
+            self_mute: input.self_mute,
+            self_deaf: input.self_deaf,
+            // Set from the member by the repository.
+            mute: false,
+            deaf: false,
+            priority_speaker: permission_context.can(Permissions::PRIORITY_SPEAKER),
+            suppress: channel.kind == ChannelKind::Stage,
+            request_to_speak_at: None,
+            joined_at: Utc::now(),
+        };
+
+        let state = self
+            .voice_state_repository
+            .upsert(&state, user_limit)
+            .await?
+            .ok_or(CoreError::VoiceChannelFull {
+                channel_id: input.channel_id,
+            })?;
+
+        Ok(VoiceJoin {
+            previous: previous.filter(|p| p.channel_id != state.channel_id),
+            state,
+        })
+    }
+
+    async fn join_call(&self, input: JoinCallInput) -> Result<VoiceJoin, CoreError> {
+        let channel_id = ChannelId(Id(input.channel_id));
+        let channel = self
+            .channel_repository
+            .find_by_id(&channel_id)
+            .await?
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: channel_id.clone(),
+            })?;
+
+        if channel.kind != ChannelKind::Dm {
+            return Err(CoreError::NotVoiceChannel { channel_id });
+        }
+
+        let previous = self.voice_state_repository.find_by_user(input.user_id).await?;
+
+        let state = VoiceState {
+            user_id: input.user_id,
+            guild_id: None,
+            channel_id: input.channel_id,
+            session_id: input.session_id,
+            self_mute: input.self_mute,
+            self_deaf: input.self_deaf,
+            mute: false,
+            deaf: false,
+            priority_speaker: false,
+            suppress: false,
+            request_to_speak_at: None,
+            joined_at: Utc::now(),
+        };
+
+        let state = self
+            .voice_state_repository
+            .upsert(&state, None)
+            .await?
+            .ok_or(CoreError::VoiceChannelFull { channel_id })?;
+
+        Ok(VoiceJoin {
+            previous: previous.filter(|p| p.channel_id != state.channel_id),
+            state,
+        })
+    }
+
+    async fn leave(&self, user_id: Uuid) -> Result<Option<VoiceState>, CoreError> {
+        self.voice_state_repository.delete_by_user(user_id).await
+    }
+
+    async fn leave_channel(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> Result<Option<VoiceState>, CoreError> {
+        let connected = self
+            .voice_state_repository
+            .find_by_user(user_id)
+            .await?
+            .is_some_and(|s| s.channel_id == channel_id);
+        if !connected {
+            return Ok(None);
+        }
+        self.voice_state_repository.delete_by_user(user_id).await
+    }
+
+    async fn set_self_state(
+        &self,
+        user_id: Uuid,
+        self_mute: Option<bool>,
+        self_deaf: Option<bool>,
+    ) -> Result<Option<VoiceState>, CoreError> {
+        let Some(current) = self.voice_state_repository.find_by_user(user_id).await? else {
+            return Ok(None);
+        };
+
+        self.voice_state_repository
+            .update_self(
+                user_id,
+                self_mute.unwrap_or(current.self_mute),
+                self_deaf.unwrap_or(current.self_deaf),
+            )
+            .await
+    }
+
+    async fn authorize_signal(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+        channel_id: Uuid,
+        target_user_id: Uuid,
+    ) -> Result<VoiceState, CoreError> {
+        if user_id == target_user_id {
+            return Err(CoreError::InvalidVoiceSignal {
+                message: "cannot signal yourself".to_string(),
+            });
+        }
+
+        // Only the session holding the voice connection may signal for it.
+        self.voice_state_repository
+            .find_by_user(user_id)
+            .await?
+            .filter(|s| s.session_id == session_id && s.channel_id == channel_id)
+            .ok_or(CoreError::NotInVoiceChannel)?;
+
+        self.voice_state_repository
+            .find_by_user(target_user_id)
+            .await?
+            .filter(|s| s.channel_id == channel_id)
+            .ok_or(CoreError::NotInVoiceChannel)
+    }
+
+    async fn media_policy(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        session_id: Uuid,
+        channel_id: Uuid,
+    ) -> Result<VoiceMediaPolicy, CoreError> {
+        let state = self
+            .voice_state_repository
+            .find_by_user(user_id)
+            .await?
+            .filter(|s| s.session_id == session_id && s.channel_id == channel_id)
+            .ok_or(CoreError::NotInVoiceChannel)?;
+
+        let channel_id = ChannelId(Id(channel_id));
+        let channel = self
+            .channel_repository
+            .find_by_id(&channel_id)
+            .await?
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: channel_id.clone(),
+            })?;
+
+        let Some(guild_id) = state.guild_id.map(GuildId::from) else {
+            return Ok(VoiceMediaPolicy {
+                can_speak: !state.mute,
+                can_stream: true,
+                deaf: state.deaf,
+                bitrate: channel.bitrate,
+            });
+        };
+
+        let mut permission_context = self
+            .channel_permissions(&identity, &guild_id, &channel_id)
+            .await?;
+
+        Ok(guild_media_policy(
+            &mut permission_context,
+            channel.kind,
+            channel.bitrate,
+            &state,
+        ))
+    }
+
+    async fn moderate(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        target_user_id: Uuid,
+        mute: Option<bool>,
+        deaf: Option<bool>,
+    ) -> Result<VoiceModeration, CoreError> {
+        // Checked in the member's voice channel when connected, so channel
+        // overwrites apply.
+        let mut permission_context = match self.guild_voice_state(&guild_id, target_user_id).await?
+        {
+            Some(state) => {
+                self.channel_permissions(&identity, &guild_id, &ChannelId(Id(state.channel_id)))
+                    .await?
+            }
+            None => {
+                build_permission_context(
+                    &self.guild_repository,
+                    &self.member_repository,
+                    &self.role_repository,
+                    &identity,
+                    &guild_id,
+                )
+                .await?
+            }
+        };
+
+        require_moderation(&mut permission_context, mute, deaf)?;
+
+        self.voice_state_repository
+            .set_server_flags(&guild_id, target_user_id, mute, deaf)
+            .await?
+            .ok_or(CoreError::NotGuildMember)
+    }
+
+    async fn move_member(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        target_user_id: Uuid,
+        channel_id: ChannelId,
+    ) -> Result<VoiceJoin, CoreError> {
+        let channel = self.voice_channel(&guild_id, &channel_id).await?;
+
+        let current = self
+            .guild_voice_state(&guild_id, target_user_id)
+            .await?
+            .ok_or(CoreError::NotInVoiceChannel)?;
+
+        let mut source_context = self
+            .channel_permissions(&identity, &guild_id, &ChannelId(Id(current.channel_id)))
+            .await?;
+        let mut destination_context = self
+            .channel_permissions(&identity, &guild_id, &channel_id)
+            .await?;
+        require_move(&mut source_context, &mut destination_context)?;
+
+        if current.channel_id == channel.id.get_uuid() {
+            return Ok(VoiceJoin {
+                state: current,
+                previous: None,
+            });
+        }
+
+        let state = VoiceState {
+            channel_id: channel.id.get_uuid(),
+            suppress: channel.kind == ChannelKind::Stage,
+            request_to_speak_at: None,
+            joined_at: Utc::now(),
+            ..current.clone()
+        };
+
+        let state = self
+            .voice_state_repository
+            .upsert(&state, None)
+            .await?
+            .ok_or(CoreError::VoiceChannelFull { channel_id })?;
+
+        Ok(VoiceJoin {
+            state,
+            previous: Some(current),
+        })
+    }
+
+    async fn disconnect_member(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        target_user_id: Uuid,
+    ) -> Result<VoiceState, CoreError> {
+        let current = self
+            .guild_voice_state(&guild_id, target_user_id)
+            .await?
+            .ok_or(CoreError::NotInVoiceChannel)?;
+
+        let mut permission_context = self
+            .channel_permissions(&identity, &guild_id, &ChannelId(Id(current.channel_id)))
+            .await?;
+        require_permission!(permission_context, Permissions::MOVE_MEMBERS);
+
+        self.voice_state_repository
+            .delete_by_user(target_user_id)
+            .await?
+            .ok_or(CoreError::NotInVoiceChannel)
+    }
+
+    async fn list_guild_voice_states(
+        &self,
+        _identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+    ) -> Result<Vec<VoiceState>, CoreError> {
+        self.guild_repository
+            .find_by_id(&guild_id)
+            .await?
+            .ok_or_else(|| CoreError::GuildNotFound {
+                guild_id: guild_id.clone(),
+            })?;
+
+        self.require_member(&guild_id, user_id).await?;
+
+        self.voice_state_repository.list_by_guild(&guild_id).await
+    }
+
+    async fn session_ended(&self, user_id: Uuid) -> Result<Option<VoiceState>, CoreError> {
+        Ok(self
+            .voice_state_repository
+            .delete_without_sessions(Some(user_id), stale_before(Utc::now()))
+            .await?
+            .pop())
+    }
+
+    async fn reap_orphaned(&self) -> Result<Vec<VoiceState>, CoreError> {
+        self.voice_state_repository
+            .delete_without_sessions(None, stale_before(Utc::now()))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ferriscord_entities::role::Role;
+
+    use super::*;
+
+    fn context(permissions: Permissions) -> PermissionContext {
+        let guild_id = GuildId(Id::new());
+        PermissionContext::new(Uuid::now_v7().to_string(), guild_id.to_string())
+            .add_role(Role::new(guild_id, "voice".to_string(), permissions))
+    }
+
+    #[test]
+    fn test_join_requires_view_and_connect() {
+        let member = Permissions::VIEW_CHANNEL | Permissions::CONNECT;
+        assert_eq!(
+            join_user_limit(&mut context(member), Some(5)).unwrap(),
+            Some(5)
+        );
+
+        for missing in [Permissions::VIEW_CHANNEL, Permissions::CONNECT] {
+            assert!(matches!(
+                join_user_limit(&mut context(member - missing), None),
+                Err(CoreError::InsufficientPermissions)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_join_user_limit_is_bypassed_by_movers() {
+        let member = Permissions::VIEW_CHANNEL | Permissions::CONNECT;
+        assert_eq!(
+            join_user_limit(&mut context(member), Some(0)).unwrap(),
+            None
+        );
+        assert_eq!(join_user_limit(&mut context(member), None).unwrap(), None);
+
+        let mover = member | Permissions::MOVE_MEMBERS;
+        assert_eq!(join_user_limit(&mut context(mover), Some(5)).unwrap(), None);
+        assert_eq!(
+            join_user_limit(&mut context(Permissions::ADMINISTRATOR), Some(5)).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_moderation_requires_the_permission_of_each_flag() {
+        let muter = Permissions::MUTE_MEMBERS;
+        let deafener = Permissions::DEAFEN_MEMBERS;
+
+        assert!(require_moderation(&mut context(muter), Some(true), None).is_ok());
+        assert!(require_moderation(&mut context(deafener), None, Some(false)).is_ok());
+        assert!(require_moderation(&mut context(muter | deafener), Some(true), Some(true)).is_ok());
+        assert!(require_moderation(&mut context(Permissions::empty()), None, None).is_ok());
+
+        assert!(matches!(
+            require_moderation(&mut context(muter), Some(true), Some(true)),
+            Err(CoreError::InsufficientPermissions)
+        ));
+        assert!(matches!(
+            require_moderation(&mut context(deafener), Some(false), None),
+            Err(CoreError::InsufficientPermissions)
+        ));
+    }
+
+    #[test]
+    fn test_move_requires_moving_out_and_connecting_in() {
+        let mover = Permissions::MOVE_MEMBERS | Permissions::CONNECT;
+        assert!(require_move(&mut context(mover), &mut context(mover)).is_ok());
+
+        for (source, destination) in [
+            (Permissions::CONNECT, mover),
+            (mover, Permissions::MOVE_MEMBERS),
+            (mover, Permissions::CONNECT),
+        ] {
+            assert!(matches!(
+                require_move(&mut context(source), &mut context(destination)),
+                Err(CoreError::InsufficientPermissions)
+            ));
+        }
+    }
+
+    fn voice_state(suppress: bool) -> VoiceState {
+        VoiceState {
+            user_id: Uuid::now_v7(),
+            guild_id: Some(Uuid::now_v7()),
+            channel_id: Uuid::now_v7(),
+            session_id: Uuid::now_v7(),
+            self_mute: false,
+            self_deaf: false,
+            mute: false,
+            deaf: false,
+            priority_speaker: false,
+            suppress,
+            request_to_speak_at: None,
+            joined_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_suppressed_stage_audience_can_neither_speak_nor_stream() {
+        let speaker = Permissions::SPEAK | Permissions::STREAM;
+
+        let policy = guild_media_policy(
+            &mut context(speaker),
+            ChannelKind::Stage,
+            None,
+            &voice_state(true),
+        );
+        assert!(!policy.can_speak);
+        assert!(!policy.can_stream);
+
+        let policy = guild_media_policy(
+            &mut context(speaker),
+            ChannelKind::Stage,
+            None,
+            &voice_state(false),
+        );
+        assert!(policy.can_speak);
+        assert!(policy.can_stream);
+
+        // Suppression only applies on stages.
+        let policy = guild_media_policy(
+            &mut context(speaker),
+            ChannelKind::Voice,
+            Some(64_000),
+            &voice_state(true),
+        );
+        assert!(policy.can_speak);
+        assert!(policy.can_stream);
+        assert_eq!(policy.bitrate, Some(64_000));
+
+        let policy = guild_media_policy(
+            &mut context(Permissions::SPEAK),
+            ChannelKind::Voice,
+            None,
+            &voice_state(false),
+        );
+        assert!(!policy.can_stream);
+    }
+}
@@ -0,0 +1,166 @@
+//! WebRTC signaling messages relayed between participants of a voice channel
+//! or DM call. The server never interprets them beyond sanity checks; it only
+//! makes sure they reach a participant of the sender's own channel.
+
+use serde::{Deserialize, Serialize};
+
+use crate::guild::domain::errors::CoreError;
+
+pub const MAX_SDP_LEN: usize = 12 * 1024;
+pub const MAX_CANDIDATE_LEN: usize = 1024;
+pub const MAX_SDP_MID_LEN: usize = 64;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum VoiceSignal {
+    Offer {
+        sdp: String,
+    },
+    Answer {
+        sdp: String,
+    },
+    /// An empty `candidate` signals the end of candidates.
+    IceCandidate {
+        candidate: String,
+        sdp_mid: Option<String>,
+        sdp_m_line_index: Option<u16>,
+    },
+}
+
+fn invalid(message: &str) -> CoreError {
+    CoreError::InvalidVoiceSignal {
+        message: message.to_string(),
+    }
+}
+
+/// A session description must look like SDP: start with `v=0`, consist of
+/// `<letter>=<value>` lines and describe at least one media section.
+pub fn validate_sdp(sdp: &str) -> Result<(), CoreError> {
+    if sdp.len() > MAX_SDP_LEN {
+        return Err(invalid("session description is too large"));
+    }
+    if !sdp.starts_with("v=0") {
+        return Err(invalid("session description must start with v=0"));
+    }
+
+    let mut has_media = false;
+    for line in sdp.split('\n').map(|l| l.trim_end_matches('\r')) {
+        if line.is_empty() {
+            continue;
+        }
+        let bytes = line.as_bytes();
+        if bytes.len() < 2 || !bytes[0].is_ascii_lowercase() || bytes[1] != b'=' {
+            return Err(invalid("malformed session description line"));
+        }
+        if line.chars().any(|c| c.is_control() && c != '\t') {
+            return Err(invalid("session description contains control characters"));
+        }
+        has_media |= bytes[0] == b'm';
+    }
+
+    if !has_media {
+        return Err(invalid("session description has no media section"));
+    }
+    Ok(())
+}
+
+impl VoiceSignal {
+    pub fn validate(&self) -> Result<(), CoreError> {
+        match self {
+            Self::Offer { sdp } | Self::Answer { sdp } => validate_sdp(sdp),
+            Self::IceCandidate {
+                candidate,
+                sdp_mid,
+                ..
+            } => {
+                if candidate.len() > MAX_CANDIDATE_LEN {
+                    return Err(invalid("ICE candidate is too large"));
+                }
+                if !candidate.is_empty() && !candidate.starts_with("candidate:") {
+                    return Err(invalid("ICE candidate must start with candidate:"));
+                }
+                if candidate.chars().any(char::is_control) {
+                    return Err(invalid("ICE candidate contains control characters"));
+                }
+                if sdp_mid
+                    .as_ref()
+                    .is_some_and(|mid| mid.len() > MAX_SDP_MID_LEN)
+                {
+                    return Err(invalid("sdp_mid is too large"));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OFFER: &str = "v=0\r\no=- 4611731400430051336 2 IN IP4 127.0.0.1\r\ns=-\r\nt=0 0\r\nm=audio 9 UDP/TLS/RTP/SAVPF 111\r\na=rtpmap:111 opus/48000/2\r\n";
+
+    #[test]
+    fn test_accepts_offer_and_candidates() {
+        assert!(VoiceSignal::Offer { sdp: OFFER.into() }.validate().is_ok());
+        assert!(
+            VoiceSignal::IceCandidate {
+                candidate: "candidate:842163049 1 udp 1677729535 192.0.2.1 3478 typ srflx".into(),
+                sdp_mid: Some("0".into()),
+                sdp_m_line_index: Some(0),
+            }
+            .validate()
+            .is_ok()
+        );
+        assert!(
+            VoiceSignal::IceCandidate {
+                candidate: String::new(),
+                sdp_mid: None,
+                sdp_m_line_index: None,
+            }
+            .validate()
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_descriptions() {
+        for sdp in [
+            "",
+            "hello",
+            "v=0\r\ns=-\r\n",
+            "v=0\r\nm=audio 9 RTP 0\r\nnot a line\r\n",
+            "v=0\r\nm=audio 9 RTP 0\r\na=x\u{7}\r\n",
+        ] {
+            assert!(
+                VoiceSignal::Answer { sdp: sdp.into() }.validate().is_err(),
+                "{sdp:?}"
+            );
+        }
+
+        let huge = format!("{OFFER}{}", "a=x\r\n".repeat(MAX_SDP_LEN));
+        assert!(VoiceSignal::Offer { sdp: huge }.validate().is_err());
+    }
+
+    #[test]
+    fn test_rejects_malformed_candidates() {
+        assert!(
+            VoiceSignal::IceCandidate {
+                candidate: "typ host".into(),
+                sdp_mid: None,
+                sdp_m_line_index: None,
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            VoiceSignal::IceCandidate {
+                candidate: "candidate:1 1 udp 1 192.0.2.1 1 typ host\r\nm=video".into(),
+                sdp_mid: None,
+                sdp_m_line_index: None,
+            }
+            .validate()
+            .is_err()
+        );
+    }
+}
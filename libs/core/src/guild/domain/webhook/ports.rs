@@ -0,0 +1,150 @@
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    channel::ChannelId, embed::Embed, guild::GuildId, message::Message, webhook::Webhook,
+};
+use uuid::Uuid;
+
+use crate::guild::domain::{errors::CoreError, message::ports::AttachmentInput};
+
+pub use crate::guild::domain::message::ports::{MAX_EMBED_FIELDS, MAX_EMBED_TEXT_LEN, MAX_EMBEDS};
+
+pub const MAX_WEBHOOK_NAME_LEN: usize = 80;
+pub const MAX_WEBHOOKS_PER_CHANNEL: i64 = 15;
+pub const MAX_WEBHOOK_CONTENT_LEN: usize = 2000;
+
+pub struct CreateWebhookInput {
+    pub name: String,
+    pub avatar_url: Option<String>,
+}
+
+/// `None` leaves a field unchanged. An empty `avatar_url` clears the avatar.
+pub struct UpdateWebhookInput {
+    pub name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub channel_id: Option<ChannelId>,
+}
+
+/// One message posted through a webhook. `username` and `avatar_url`
+/// override the webhook's defaults for this message only.
+pub struct ExecuteWebhookInput {
+    pub username: Option<String>,
+    pub avatar_url: Option<String>,
+    pub content: String,
+    pub embeds: Vec<Embed>,
+    pub attachments: Vec<AttachmentInput>,
+}
+
+pub trait WebhookRepository: Send + Sync {
+    fn insert(
+        &self,
+        webhook: &Webhook,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Webhook, CoreError>> + Send;
+
+    fn find_by_id(
+        &self,
+        webhook_id: Uuid,
+    ) -> impl Future<Output = Result<Option<Webhook>, CoreError>> + Send;
+
+    /// Looks a webhook up by id and token hash, as presented by an executor.
+    fn find_by_token(
+        &self,
+        webhook_id: Uuid,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<Webhook>, CoreError>> + Send;
+
+    /// Webhooks of the guild, optionally restricted to one channel.
+    fn list_by_guild(
+        &self,
+        guild_id: Uuid,
+        channel_id: Option<Uuid>,
+    ) -> impl Future<Output = Result<Vec<Webhook>, CoreError>> + Send;
+
+    fn count_by_channel(
+        &self,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<i64, CoreError>> + Send;
+
+    /// `avatar_url` is `Some(None)` to clear the avatar.
+    fn update(
+        &self,
+        webhook_id: Uuid,
+        name: Option<String>,
+        avatar_url: Option<Option<String>>,
+        channel_id: Option<Uuid>,
+    ) -> impl Future<Output = Result<Option<Webhook>, CoreError>> + Send;
+
+    fn set_token(
+        &self,
+        webhook_id: Uuid,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<Webhook>, CoreError>> + Send;
+
+    fn delete(&self, webhook_id: Uuid) -> impl Future<Output = Result<bool, CoreError>> + Send;
+}
+
+/// Managing webhooks requires MANAGE_WEBHOOKS in their channel. Executing one
+/// only requires its token.
+pub trait WebhookService: Send + Sync {
+    /// Returns the webhook with its token, which is not shown again.
+    fn create_webhook(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        input: CreateWebhookInput,
+    ) -> impl Future<Output = Result<Webhook, CoreError>> + Send;
+
+    fn list_webhooks(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: Option<ChannelId>,
+    ) -> impl Future<Output = Result<Vec<Webhook>, CoreError>> + Send;
+
+    fn get_webhook(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        webhook_id: Uuid,
+    ) -> impl Future<Output = Result<Webhook, CoreError>> + Send;
+
+    fn update_webhook(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        webhook_id: Uuid,
+        input: UpdateWebhookInput,
+    ) -> impl Future<Output = Result<Webhook, CoreError>> + Send;
+
+    /// Invalidates the current token and returns the webhook with a new one.
+    fn reset_webhook_token(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        webhook_id: Uuid,
+    ) -> impl Future<Output = Result<Webhook, CoreError>> + Send;
+
+    /// Returns the deleted webhook.
+    fn delete_webhook(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        webhook_id: Uuid,
+    ) -> impl Future<Output = Result<Webhook, CoreError>> + Send;
+
+    /// Resolves a webhook from its id and secret token.
+    fn get_webhook_with_token(
+        &self,
+        webhook_id: Uuid,
+        token: &str,
+    ) -> impl Future<Output = Result<Webhook, CoreError>> + Send;
+
+    fn execute_webhook(
+        &self,
+        webhook_id: Uuid,
+        token: &str,
+        input: ExecuteWebhookInput,
+    ) -> impl Future<Output = Result<(Webhook, Message), CoreError>> + Send;
+}
@@ -0,0 +1,438 @@
+use chrono::Utc;
+use ferriscord_auth::Identity;
+use ferriscord_entities::{
+    Id,
+    channel::{ChannelId, ChannelKind},
+    guild::GuildId,
+    message::Message,
+    webhook::Webhook,
+};
+use ferriscord_permission::{Permissions, require_permission};
+use rand::distributions::{Alphanumeric, DistString};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    channel::ports::ChannelPort,
+    common::{build_channel_permission_context, build_permission_context},
+    errors::CoreError,
+    guild::ports::GuildPort,
+    member::ports::MemberRepository,
+    message::{
+        embeds::validate_embeds,
+        ports::{MessagePort, WebhookMessageInput},
+    },
+    role::ports::RoleRepository,
+};
+
+use super::ports::{
+    CreateWebhookInput, ExecuteWebhookInput, MAX_WEBHOOK_CONTENT_LEN, MAX_WEBHOOK_NAME_LEN,
+    MAX_WEBHOOKS_PER_CHANNEL, UpdateWebhookInput, WebhookRepository, WebhookService,
+};
+
+const TOKEN_LEN: usize = 68;
+
+#[derive(Clone)]
+pub struct WebhookServiceImpl<G, C, R, M, Msg, W>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    W: WebhookRepository,
+{
+    pub(crate) guild_repository: G,
+    pub(crate) channel_repository: C,
+    pub(crate) role_repository: R,
+    pub(crate) member_repository: M,
+    pub(crate) message_repository: Msg,
+    pub(crate) webhook_repository: W,
+}
+
+fn invalid(message: impl Into<String>) -> CoreError {
+    CoreError::InvalidWebhook {
+        message: message.into(),
+    }
+}
+
+fn new_token() -> (String, String) {
+    let token = Alphanumeric.sample_string(&mut rand::thread_rng(), TOKEN_LEN);
+    let hash = hash_token(&token);
+    (token, hash)
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+fn validate_name(name: String) -> Result<String, CoreError> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(invalid("name must not be empty"));
+    }
+    if name.chars().count() > MAX_WEBHOOK_NAME_LEN {
+        return Err(invalid(format!(
+            "name must be at most {MAX_WEBHOOK_NAME_LEN} characters"
+        )));
+    }
+    Ok(name)
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+fn check_len(what: &str, value: Option<&str>, max: usize) -> Result<(), CoreError> {
+    if value.is_some_and(|v| v.chars().count() > max) {
+        return Err(invalid(format!("{what} must be at most {max} characters")));
+    }
+    Ok(())
+}
+
+/// Checks the payload and resolves the name and avatar the message shows.
+fn prepare_message(
+    webhook: &Webhook,
+    input: ExecuteWebhookInput,
+) -> Result<WebhookMessageInput, CoreError> {
+    if input.content.trim().is_empty() && input.embeds.is_empty() && input.attachments.is_empty() {
+        return Err(invalid("message must have content, embeds or attachments"));
+    }
+    check_len("content", Some(&input.content), MAX_WEBHOOK_CONTENT_LEN)?;
+    let embeds = validate_embeds(input.embeds).map_err(invalid)?;
+
+    let username = match non_empty(input.username) {
+        Some(username) => validate_name(username)?,
+        None => webhook.name.clone(),
+    };
+
+    Ok(WebhookMessageInput {
+        webhook_id: webhook.id,
+        username,
+        avatar_url: non_empty(input.avatar_url).or_else(|| webhook.avatar_url.clone()),
+        content: input.content,
+        embeds,
+        attachments: input.attachments,
+    })
+}
+
+impl<G, C, R, M, Msg, W> WebhookServiceImpl<G, C, R, M, Msg, W>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    W: WebhookRepository,
+{
+    /// Checks the caller may manage webhooks of a text channel of the guild.
+    async fn require_manage_channel(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        channel_id: &ChannelId,
+    ) -> Result<(), CoreError> {
+        let channel = self
+            .channel_repository
+            .find_by_id(channel_id)
+            .await?
+            .filter(|c| c.guild_id.as_ref() == Some(guild_id))
+            .ok_or_else(|| CoreError::ChannelNotFound {
+                channel_id: channel_id.clone(),
+            })?;
+
+        if !matches!(channel.kind, ChannelKind::Text | ChannelKind::Announcement) {
+            return Err(invalid("webhooks can only post into text channels"));
+        }
+
+        let mut permission_context = build_channel_permission_context(
+            &self.guild_repository,
+            &self.member_repository,
+            &self.role_repository,
+            &self.channel_repository,
+            identity,
+            guild_id,
+            channel_id,
+        )
+        .await?;
+
+        require_permission!(permission_context, Permissions::MANAGE_WEBHOOKS);
+        Ok(())
+    }
+
+    /// Loads a webhook of the guild the caller may manage.
+    async fn managed_webhook(
+        &self,
+        identity: &Identity,
+        guild_id: &GuildId,
+        webhook_id: Uuid,
+    ) -> Result<Webhook, CoreError> {
+        let webhook = self
+            .webhook_repository
+            .find_by_id(webhook_id)
+            .await?
+            .filter(|w| w.guild_id == *guild_id.get_uuid())
+            .ok_or(CoreError::WebhookNotFound)?;
+
+        self.require_manage_channel(identity, guild_id, &ChannelId(Id(webhook.channel_id)))
+            .await?;
+        Ok(webhook)
+    }
+
+    async fn require_capacity(&self, channel_id: &ChannelId) -> Result<(), CoreError> {
+        if self
+            .webhook_repository
+            .count_by_channel(channel_id.get_uuid())
+            .await?
+            >= MAX_WEBHOOKS_PER_CHANNEL
+        {
+            return Err(CoreError::MaxWebhooksReached {
+                channel_id: channel_id.clone(),
+                max_webhooks: MAX_WEBHOOKS_PER_CHANNEL,
+            });
+        }
+        Ok(())
+    }
+}
+
+impl<G, C, R, M, Msg, W> WebhookService for WebhookServiceImpl<G, C, R, M, Msg, W>
+where
+    G: GuildPort,
+    C: ChannelPort,
+    R: RoleRepository,
+    M: MemberRepository,
+    Msg: MessagePort,
+    W: WebhookRepository,
+{
+    async fn create_webhook(
+        &self,
+        identity: Identity,
+        user_id: Uuid,
+        guild_id: GuildId,
+        channel_id: ChannelId,
+        input: CreateWebhookInput,
+    ) -> Result<Webhook, CoreError> {
+        self.require_manage_channel(&identity, &guild_id, &channel_id)
+            .await?;
+        self.require_capacity(&channel_id).await?;
+
+        let (token, token_hash) = new_token();
+        let webhook = Webhook {
+            id: Uuid::now_v7(),
+            guild_id: *guild_id.get_uuid(),
+            channel_id: channel_id.get_uuid(),
+            name: validate_name(input.name)?,
+            avatar_url: non_empty(input.avatar_url),
+            created_by: Some(user_id),
+            created_at: Utc::now(),
+            token: None,
+        };
+
+        let webhook = self
+            .webhook_repository
+            .insert(&webhook, &token_hash)
+            .await?;
+        Ok(Webhook {
+            token: Some(token),
+            ..webhook
+        })
+    }
+
+    async fn list_webhooks(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        channel_id: Option<ChannelId>,
+    ) -> Result<Vec<Webhook>, CoreError> {
+        match &channel_id {
+            Some(channel_id) => {
+                self.require_manage_channel(&identity, &guild_id, channel_id)
+                    .await?
+            }
+            None => {
+                let mut permission_context = build_permission_context(
+                    &self.guild_repository,
+                    &self.member_repository,
+                    &self.role_repository,
+                    &identity,
+                    &guild_id,
+                )
+                .await?;
+                require_permission!(permission_context, Permissions::MANAGE_WEBHOOKS);
+            }
+        }
+
+        self.webhook_repository
+            .list_by_guild(*guild_id.get_uuid(), channel_id.map(|c| c.get_uuid()))
+            .await
+    }
+
+    async fn get_webhook(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        webhook_id: Uuid,
+    ) -> Result<Webhook, CoreError> {
+        self.managed_webhook(&identity, &guild_id, webhook_id).await
+    }
+
+    async fn update_webhook(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        webhook_id: Uuid,
+        input: UpdateWebhookInput,
+    ) -> Result<Webhook, CoreError> {
+        let webhook = self
+            .managed_webhook(&identity, &guild_id, webhook_id)
+            .await?;
+
+        // Moving a webhook needs the permission on both ends.
+        let channel_id = match input.channel_id {
+            Some(channel_id) if channel_id.get_uuid() != webhook.channel_id => {
+                self.require_manage_channel(&identity, &guild_id, &channel_id)
+                    .await?;
+                self.require_capacity(&channel_id).await?;
+                Some(channel_id.get_uuid())
+            }
+            _ => None,
+        };
+
+        let name = input.name.map(validate_name).transpose()?;
+        let avatar_url = input.avatar_url.map(|url| non_empty(Some(url)));
+
+        self.webhook_repository
+            .update(webhook_id, name, avatar_url, channel_id)
+            .await?
+            .ok_or(CoreError::WebhookNotFound)
+    }
+
+    async fn reset_webhook_token(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        webhook_id: Uuid,
+    ) -> Result<Webhook, CoreError> {
+        self.managed_webhook(&identity, &guild_id, webhook_id)
+            .await?;
+
+        let (token, token_hash) = new_token();
+        let webhook = self
+            .webhook_repository
+            .set_token(webhook_id, &token_hash)
+            .await?
+            .ok_or(CoreError::WebhookNotFound)?;
+        Ok(Webhook {
+            token: Some(token),
+            ..webhook
+        })
+    }
+
+    async fn delete_webhook(
+        &self,
+        identity: Identity,
+        guild_id: GuildId,
+        webhook_id: Uuid,
+    ) -> Result<Webhook, CoreError> {
+        let webhook = self
+            .managed_webhook(&identity, &guild_id, webhook_id)
+            .await?;
+
+        if !self.webhook_repository.delete(webhook_id).await? {
+            return Err(CoreError::WebhookNotFound);
+        }
+        Ok(webhook)
+    }
+
+    async fn get_webhook_with_token(
+        &self,
+        webhook_id: Uuid,
+        token: &str,
+    ) -> Result<Webhook, CoreError> {
+        self.webhook_repository
+            .find_by_token(webhook_id, &hash_token(token))
+            .await?
+            .ok_or(CoreError::WebhookNotFound)
+    }
+
+    async fn execute_webhook(
+        &self,
+        webhook_id: Uuid,
+        token: &str,
+        input: ExecuteWebhookInput,
+    ) -> Result<(Webhook, Message), CoreError> {
+        let webhook = self.get_webhook_with_token(webhook_id, token).await?;
+        let input = prepare_message(&webhook, input)?;
+
+        let message = self
+            .message_repository
+            .insert_webhook_message(&ChannelId(Id(webhook.channel_id)), input)
+            .await?;
+        Ok((webhook, message))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ferriscord_entities::embed::{Embed, EmbedField};
+
+    use super::*;
+
+    fn webhook() -> Webhook {
+        Webhook {
+            id: Uuid::now_v7(),
+            guild_id: Uuid::now_v7(),
+            channel_id: Uuid::now_v7(),
+            name: "CI".to_string(),
+            avatar_url: Some("https://ci.example/logo.png".to_string()),
+            created_by: None,
+            created_at: Utc::now(),
+            token: None,
+        }
+    }
+
+    fn execute(content: &str, embeds: Vec<Embed>) -> ExecuteWebhookInput {
+        ExecuteWebhookInput {
+            username: None,
+            avatar_url: None,
+            content: content.to_string(),
+            embeds,
+            attachments: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_prepare_message() {
+        let webhook = webhook();
+
+        let message = prepare_message(&webhook, execute("build passed", Vec::new())).unwrap();
+        assert_eq!(message.username, "CI");
+        assert_eq!(message.avatar_url, webhook.avatar_url);
+
+        let mut input = execute("build passed", Vec::new());
+        input.username = Some("  Deploy bot ".to_string());
+        input.avatar_url = Some(String::new());
+        let message = prepare_message(&webhook, input).unwrap();
+        assert_eq!(message.username, "Deploy bot");
+        assert_eq!(message.avatar_url, webhook.avatar_url);
+
+        assert!(prepare_message(&webhook, execute("  ", Vec::new())).is_err());
+
+        let field = EmbedField {
+            name: "status".to_string(),
+            value: "x".repeat(1024),
+            inline: false,
+        };
+        let embed = Embed {
+            fields: vec![field; 5],
+            ..Embed::default()
+        };
+        assert!(prepare_message(&webhook, execute("", vec![embed.clone()])).is_ok());
+        assert!(prepare_message(&webhook, execute("", vec![embed; 2])).is_err());
+
+        assert_eq!(hash_token("secret"), hash_token("secret"));
+        assert_ne!(hash_token("secret"), hash_token("Secret"));
+    }
+}
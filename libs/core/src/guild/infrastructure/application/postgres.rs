@@ -0,0 +1,503 @@
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Bot;
+use ferriscord_entities::application::{Application, GuildBot};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{application::ports::ApplicationRepository, errors::CoreError};
+
+#[derive(Clone)]
+pub struct PostgresApplicationRepository {
+    pool: PgPool,
+}
+
+impl PostgresApplicationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const APPLICATION_COLUMNS: &str = r#"
+    id, name, description, owner_id, bot_user_id, created_at,
+    interactions_endpoint_url, interactions_secret
+"#;
+
+const GUILD_BOT_COLUMNS: &str = r#"
+    gb.guild_id, gb.application_id, a.bot_user_id, u.username,
+    gb.role_id, gb.added_by, gb.added_at
+"#;
+
+#[derive(sqlx::FromRow)]
+struct ApplicationRow {
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    owner_id: Uuid,
+    bot_user_id: Uuid,
+    created_at: DateTime<Utc>,
+    interactions_endpoint_url: Option<String>,
+    interactions_secret: Option<String>,
+}
+
+impl From<ApplicationRow> for Application {
+    fn from(row: ApplicationRow) -> Self {
+        Application {
+            id: row.id,
+            name: row.name,
+            description: row.description,
+            owner_id: row.owner_id,
+            bot_user_id: row.bot_user_id,
+            created_at: row.created_at,
+            interactions_endpoint_url: row.interactions_endpoint_url,
+            interactions_secret: row.interactions_secret,
+            bot_token: None,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct GuildBotRow {
+    guild_id: Uuid,
+    application_id: Uuid,
+    bot_user_id: Uuid,
+    username: String,
+    role_id: Option<Uuid>,
+    added_by: Option<Uuid>,
+    added_at: DateTime<Utc>,
+}
+
+impl From<GuildBotRow> for GuildBot {
+    fn from(row: GuildBotRow) -> Self {
+        GuildBot {
+            guild_id: row.guild_id,
+            application_id: row.application_id,
+            bot_user_id: row.bot_user_id,
+            username: row.username,
+            role_id: row.role_id,
+            added_by: row.added_by,
+            added_at: row.added_at,
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+/// Application names are bot usernames, so they share the users uniqueness
+/// constraint.
+fn name_err(context: &str, e: sqlx::Error) -> CoreError {
+    if e.as_database_error()
+        .is_some_and(|db| db.is_unique_violation())
+    {
+        return CoreError::InvalidApplication {
+            message: "name is already taken".to_string(),
+        };
+    }
+    db_err(context, e)
+}
+
+// ─── ApplicationRepository impl ───────────────────────────────────────────────
+
+impl ApplicationRepository for PostgresApplicationRepository {
+    async fn insert(
+        &self,
+        application: &Application,
+        token_hash: &str,
+    ) -> Result<Application, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, oauth_sub, username, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $4)
+            "#,
+        )
+        .bind(application.bot_user_id)
+        .bind(Bot::subject(application.id))
+        .bind(&application.name)
+        .bind(application.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| name_err("failed to insert bot user", e))?;
+
+        let row = sqlx::query_as::<_, ApplicationRow>(&format!(
+            r#"
+            INSERT INTO applications
+                (id, name, description, owner_id, bot_user_id, bot_token_hash, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING {APPLICATION_COLUMNS}
+            "#
+        ))
+        .bind(application.id)
+        .bind(&application.name)
+        .bind(&application.description)
+        .bind(application.owner_id)
+        .bind(application.bot_user_id)
+        .bind(token_hash)
+        .bind(application.created_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert application", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit application", e))?;
+
+        Ok(row.into())
+    }
+
+    async fn find_by_id(&self, application_id: Uuid) -> Result<Option<Application>, CoreError> {
+        let row = sqlx::query_as::<_, ApplicationRow>(&format!(
+            "SELECT {APPLICATION_COLUMNS} FROM applications WHERE id = $1"
+        ))
+        .bind(application_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find application", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_by_token(&self, token_hash: &str) -> Result<Option<Application>, CoreError> {
+        let row = sqlx::query_as::<_, ApplicationRow>(&format!(
+            "SELECT {APPLICATION_COLUMNS} FROM applications WHERE bot_token_hash = $1"
+        ))
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find application by token", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_by_owner(&self, owner_id: Uuid) -> Result<Vec<Application>, CoreError> {
+        let rows = sqlx::query_as::<_, ApplicationRow>(&format!(
+            "SELECT {APPLICATION_COLUMNS} FROM applications WHERE owner_id = $1 ORDER BY created_at"
+        ))
+        .bind(owner_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list applications", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn count_by_owner(&self, owner_id: Uuid) -> Result<i64, CoreError> {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM applications WHERE owner_id = $1")
+            .bind(owner_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to count applications", e))
+    }
+
+    async fn update(
+        &self,
+        application_id: Uuid,
+        name: Option<String>,
+        description: Option<Option<String>>,
+    ) -> Result<Option<Application>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let row = sqlx::query_as::<_, ApplicationRow>(&format!(
+            r#"
+            UPDATE applications
+            SET name = COALESCE($2, name),
+                description = CASE WHEN $3 THEN $4 ELSE description END
+            WHERE id = $1
+            RETURNING {APPLICATION_COLUMNS}
+            "#
+        ))
+        .bind(application_id)
+        .bind(&name)
+        .bind(description.is_some())
+        .bind(description.flatten())
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to update application", e))?;
+
+        if let Some(row) = &row
+            && name.is_some()
+        {
+            sqlx::query("UPDATE users SET username = $2, updated_at = now() WHERE id = $1")
+                .bind(row.bot_user_id)
+                .bind(&row.name)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| name_err("failed to rename bot user", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit application", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn set_token(
+        &self,
+        application_id: Uuid,
+        token_hash: &str,
+    ) -> Result<Option<Application>, CoreError> {
+        let row = sqlx::query_as::<_, ApplicationRow>(&format!(
+            "UPDATE applications SET bot_token_hash = $2 WHERE id = $1 RETURNING {APPLICATION_COLUMNS}"
+        ))
+        .bind(application_id)
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to reset bot token", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn set_interactions_endpoint(
+        &self,
+        application_id: Uuid,
+        url: Option<String>,
+        secret: Option<String>,
+    ) -> Result<Option<Application>, CoreError> {
+        let row = sqlx::query_as::<_, ApplicationRow>(&format!(
+            r#"
+            UPDATE applications
+            SET interactions_endpoint_url = $2,
+                interactions_secret = COALESCE(interactions_secret, $3)
+            WHERE id = $1
+            RETURNING {APPLICATION_COLUMNS}
+            "#
+        ))
+        .bind(application_id)
+        .bind(url)
+        .bind(secret)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to set interactions endpoint", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn delete(&self, application_id: Uuid) -> Result<bool, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        // Members rows don't reference users, so the bot's memberships and
+        // roles are removed by hand before the bot user cascades the rest.
+        sqlx::query(
+            r#"
+            DELETE FROM roles
+            WHERE id IN (SELECT role_id FROM guild_bots WHERE application_id = $1)
+            "#,
+        )
+        .bind(application_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to delete bot roles", e))?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM members
+            WHERE user_id = (SELECT bot_user_id FROM applications WHERE id = $1)
+            "#,
+        )
+        .bind(application_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to delete bot memberships", e))?;
+
+        let result = sqlx::query(
+            "DELETE FROM users WHERE id = (SELECT bot_user_id FROM applications WHERE id = $1)",
+        )
+        .bind(application_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to delete application", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit application deletion", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn add_to_guild(
+        &self,
+        guild_id: Uuid,
+        application: &Application,
+        role_id: Option<Uuid>,
+        added_by: Uuid,
+    ) -> Result<GuildBot, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let member_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO members (id, guild_id, user_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (guild_id, user_id) DO UPDATE SET user_id = EXCLUDED.user_id
+            RETURNING id
+            "#,
+        )
+        .bind(Uuid::now_v7())
+        .bind(guild_id)
+        .bind(application.bot_user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert bot member", e))?;
+
+        if let Some(role_id) = role_id {
+            sqlx::query(
+                r#"
+                INSERT INTO role_assignments (role_id, member_id)
+                VALUES ($1, $2)
+                ON CONFLICT DO NOTHING
+                "#,
+            )
+            .bind(role_id)
+            .bind(member_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to assign bot role", e))?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO guild_bots (guild_id, application_id, role_id, added_by)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(guild_id)
+        .bind(application.id)
+        .bind(role_id)
+        .bind(added_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert guild bot", e))?;
+
+        let row = sqlx::query_as::<_, GuildBotRow>(&format!(
+            r#"
+            SELECT {GUILD_BOT_COLUMNS}
+            FROM guild_bots gb
+            JOIN applications a ON a.id = gb.application_id
+            JOIN users u ON u.id = a.bot_user_id
+            WHERE gb.guild_id = $1 AND gb.application_id = $2
+            "#
+        ))
+        .bind(guild_id)
+        .bind(application.id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to read guild bot", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit guild bot", e))?;
+
+        Ok(row.into())
+    }
+
+    async fn find_guild_bot(
+        &self,
+        guild_id: Uuid,
+        application_id: Uuid,
+    ) -> Result<Option<GuildBot>, CoreError> {
+        let row = sqlx::query_as::<_, GuildBotRow>(&format!(
+            r#"
+            SELECT {GUILD_BOT_COLUMNS}
+            FROM guild_bots gb
+            JOIN applications a ON a.id = gb.application_id
+            JOIN users u ON u.id = a.bot_user_id
+            WHERE gb.guild_id = $1 AND gb.application_id = $2
+            "#
+        ))
+        .bind(guild_id)
+        .bind(application_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find guild bot", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_guild_bots(&self, guild_id: Uuid) -> Result<Vec<GuildBot>, CoreError> {
+        let rows = sqlx::query_as::<_, GuildBotRow>(&format!(
+            r#"
+            SELECT {GUILD_BOT_COLUMNS}
+            FROM guild_bots gb
+            JOIN applications a ON a.id = gb.application_id
+            JOIN users u ON u.id = a.bot_user_id
+            WHERE gb.guild_id = $1
+            ORDER BY gb.added_at
+            "#
+        ))
+        .bind(guild_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list guild bots", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn remove_from_guild(
+        &self,
+        guild_id: Uuid,
+        application_id: Uuid,
+    ) -> Result<Option<GuildBot>, CoreError> {
+        let Some(bot) = self.find_guild_bot(guild_id, application_id).await? else {
+            return Ok(None);
+        };
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        sqlx::query("DELETE FROM guild_bots WHERE guild_id = $1 AND application_id = $2")
+            .bind(guild_id)
+            .bind(application_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to delete guild bot", e))?;
+
+        sqlx::query("DELETE FROM members WHERE guild_id = $1 AND user_id = $2")
+            .bind(guild_id)
+            .bind(bot.bot_user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to delete bot member", e))?;
+
+        if let Some(role_id) = bot.role_id {
+            sqlx::query("DELETE FROM roles WHERE id = $1")
+                .bind(role_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| db_err("failed to delete bot role", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit guild bot removal", e))?;
+
+        Ok(Some(bot))
+    }
+}
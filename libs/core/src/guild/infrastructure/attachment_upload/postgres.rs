@@ -0,0 +1,180 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::attachment::AttachmentUpload;
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    attachment_upload::ports::AttachmentUploadRepository, errors::CoreError,
+};
+
+#[derive(Clone)]
+pub struct PostgresAttachmentUploadRepository {
+    pool: PgPool,
+}
+
+impl PostgresAttachmentUploadRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const UPLOAD_COLUMNS: &str =
+    "id, channel_id, filename, content_type, size_bytes, storage_key, created_at, expires_at";
+
+#[derive(sqlx::FromRow)]
+struct UploadRow {
+    id: Uuid,
+    channel_id: Uuid,
+    filename: String,
+    content_type: String,
+    size_bytes: i64,
+    storage_key: String,
+    created_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl From<UploadRow> for AttachmentUpload {
+    fn from(row: UploadRow) -> Self {
+        AttachmentUpload {
+            id: row.id,
+            channel_id: row.channel_id,
+            filename: row.filename,
+            content_type: row.content_type,
+            size_bytes: row.size_bytes,
+            storage_key: row.storage_key,
+            upload_url: String::new(),
+            created_at: row.created_at,
+            expires_at: row.expires_at,
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+// ─── AttachmentUploadRepository impl ──────────────────────────────────────────
+
+impl AttachmentUploadRepository for PostgresAttachmentUploadRepository {
+    async fn insert(&self, user_sub: &str, uploads: &[AttachmentUpload]) -> Result<(), CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        for upload in uploads {
+            sqlx::query(
+                r#"
+                INSERT INTO attachment_uploads
+                    (id, user_id, channel_id, filename, content_type, size_bytes, storage_key, created_at, expires_at)
+                SELECT $1, u.id, $3, $4, $5, $6, $7, $8, $9
+                FROM users u WHERE u.oauth_sub = $2
+                "#,
+            )
+            .bind(upload.id)
+            .bind(user_sub)
+            .bind(upload.channel_id)
+            .bind(&upload.filename)
+            .bind(&upload.content_type)
+            .bind(upload.size_bytes)
+            .bind(&upload.storage_key)
+            .bind(upload.created_at)
+            .bind(upload.expires_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to insert attachment upload", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))
+    }
+
+    async fn count_pending_by_user(
+        &self,
+        user_sub: &str,
+        now: DateTime<Utc>,
+    ) -> Result<i64, CoreError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM attachment_uploads au
+            JOIN users u ON u.id = au.user_id
+            WHERE u.oauth_sub = $1 AND au.expires_at > $2
+            "#,
+        )
+        .bind(user_sub)
+        .bind(now)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to count attachment uploads", e))
+    }
+
+    async fn claim(
+        &self,
+        user_sub: &str,
+        channel_id: Uuid,
+        ids: &[Uuid],
+        now: DateTime<Utc>,
+    ) -> Result<Vec<AttachmentUpload>, CoreError> {
+        // Rows are locked by the update: of two messages claiming the same
+        // slot, the second sees it claimed once the first commits.
+        let rows = sqlx::query_as::<_, UploadRow>(&format!(
+            r#"
+            UPDATE attachment_uploads au SET claimed_at = $4
+            WHERE au.id = ANY($3) AND au.channel_id = $2 AND au.expires_at > $4
+              AND au.claimed_at IS NULL
+              AND au.user_id = (SELECT id FROM users WHERE oauth_sub = $1)
+            RETURNING {UPLOAD_COLUMNS}
+            "#
+        ))
+        .bind(user_sub)
+        .bind(channel_id)
+        .bind(ids)
+        .bind(now)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to claim attachment uploads", e))?;
+
+        Ok(rows.into_iter().map(AttachmentUpload::from).collect())
+    }
+
+    async fn release(&self, ids: &[Uuid]) -> Result<u64, CoreError> {
+        let result =
+            sqlx::query("UPDATE attachment_uploads SET claimed_at = NULL WHERE id = ANY($1)")
+                .bind(ids)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| db_err("failed to release attachment uploads", e))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn delete(&self, ids: &[Uuid]) -> Result<u64, CoreError> {
+        let result = sqlx::query("DELETE FROM attachment_uploads WHERE id = ANY($1)")
+            .bind(ids)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete attachment uploads", e))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn prune(&self, before: DateTime<Utc>) -> Result<Vec<AttachmentUpload>, CoreError> {
+        let rows = sqlx::query_as::<_, UploadRow>(&format!(
+            "DELETE FROM attachment_uploads WHERE expires_at < $1 RETURNING {UPLOAD_COLUMNS}"
+        ))
+        .bind(before)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to prune attachment uploads", e))?;
+
+        Ok(rows.into_iter().map(AttachmentUpload::from).collect())
+    }
+}
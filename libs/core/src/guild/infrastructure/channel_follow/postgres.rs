@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::channel_follow::ChannelFollow;
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{channel_follow::ports::ChannelFollowRepository, errors::CoreError};
+
+#[derive(Clone)]
+pub struct PostgresChannelFollowRepository {
+    pool: PgPool,
+}
+
+impl PostgresChannelFollowRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+// Source names are read live so follows show renamed guilds and channels.
+const SELECT_FOLLOWS_SQL: &str = r#"
+    SELECT
+        f.id,
+        s.guild_id AS source_guild_id,
+        g.name AS source_guild_name,
+        f.source_channel_id,
+        s.name AS source_channel_name,
+        f.guild_id,
+        f.channel_id,
+        f.created_by,
+        f.created_at
+    FROM channel_follows f
+    JOIN channels s ON s.id = f.source_channel_id
+    JOIN guilds g ON g.id = s.guild_id
+"#;
+
+#[derive(sqlx::FromRow)]
+struct ChannelFollowRow {
+    id: Uuid,
+    source_guild_id: Uuid,
+    source_guild_name: String,
+    source_channel_id: Uuid,
+    source_channel_name: String,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<ChannelFollowRow> for ChannelFollow {
+    fn from(row: ChannelFollowRow) -> Self {
+        ChannelFollow {
+            id: row.id,
+            source_guild_id: row.source_guild_id,
+            source_guild_name: row.source_guild_name,
+            source_channel_id: row.source_channel_id,
+            source_channel_name: row.source_channel_name,
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            created_by: row.created_by,
+            created_at: row.created_at,
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+// ─── ChannelFollowRepository impl ─────────────────────────────────────────────
+
+impl ChannelFollowRepository for PostgresChannelFollowRepository {
+    async fn insert(
+        &self,
+        id: Uuid,
+        source_channel_id: Uuid,
+        guild_id: Uuid,
+        channel_id: Uuid,
+        created_by: Uuid,
+    ) -> Result<ChannelFollow, CoreError> {
+        sqlx::query(
+            r#"
+            INSERT INTO channel_follows (id, source_channel_id, guild_id, channel_id, created_by)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(id)
+        .bind(source_channel_id)
+        .bind(guild_id)
+        .bind(channel_id)
+        .bind(created_by)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            if e.as_database_error()
+                .is_some_and(|db| db.is_unique_violation())
+            {
+                return CoreError::InvalidChannelFollow {
+                    message: "the channel already follows this channel".to_string(),
+                };
+            }
+            db_err("failed to insert channel follow", e)
+        })?;
+
+        self.find_by_id(id)
+            .await?
+            .ok_or_else(|| CoreError::Unknown {
+                message: format!("channel follow {} vanished after being written", id),
+            })
+    }
+
+    async fn find_by_id(&self, follow_id: Uuid) -> Result<Option<ChannelFollow>, CoreError> {
+        let row =
+            sqlx::query_as::<_, ChannelFollowRow>(&format!("{SELECT_FOLLOWS_SQL} WHERE f.id = $1"))
+                .bind(follow_id)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(|e| db_err("failed to find channel follow", e))?;
+
+        Ok(row.map(ChannelFollow::from))
+    }
+
+    async fn list_by_channel(&self, channel_id: Uuid) -> Result<Vec<ChannelFollow>, CoreError> {
+        let rows = sqlx::query_as::<_, ChannelFollowRow>(&format!(
+            "{SELECT_FOLLOWS_SQL} WHERE f.channel_id = $1 ORDER BY f.created_at"
+        ))
+        .bind(channel_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list channel follows", e))?;
+
+        Ok(rows.into_iter().map(ChannelFollow::from).collect())
+    }
+
+    async fn list_by_source(
+        &self,
+        source_channel_id: Uuid,
+    ) -> Result<Vec<ChannelFollow>, CoreError> {
+        let rows = sqlx::query_as::<_, ChannelFollowRow>(&format!(
+            "{SELECT_FOLLOWS_SQL} WHERE f.source_channel_id = $1 ORDER BY f.created_at"
+        ))
+        .bind(source_channel_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list channel followers", e))?;
+
+        Ok(rows.into_iter().map(ChannelFollow::from).collect())
+    }
+
+    async fn count_by_channel(&self, channel_id: Uuid) -> Result<i64, CoreError> {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM channel_follows WHERE channel_id = $1")
+            .bind(channel_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to count channel follows", e))
+    }
+
+    async fn delete(&self, follow_id: Uuid) -> Result<bool, CoreError> {
+        let result = sqlx::query("DELETE FROM channel_follows WHERE id = $1")
+            .bind(follow_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete channel follow", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    discord_compat::{DISCORD_EPOCH_MS, SNOWFLAKE_TIMESTAMP_SHIFT, ports::SnowflakeRepository},
+    errors::CoreError,
+};
+
+#[derive(Clone)]
+pub struct PostgresSnowflakeRepository {
+    pool: PgPool,
+}
+
+impl PostgresSnowflakeRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+impl SnowflakeRepository for PostgresSnowflakeRepository {
+    async fn assign(&self, ids: &[(Uuid, i64)]) -> Result<HashMap<Uuid, i64>, CoreError> {
+        let (uuids, created): (Vec<Uuid>, Vec<i64>) = ids.iter().copied().unzip();
+
+        // Ids that already have a snowflake keep it. A new snowflake that
+        // collides with an existing one, which takes 4M ids created in the
+        // same millisecond, is skipped.
+        sqlx::query(
+            "INSERT INTO discord_snowflakes (id, snowflake)
+             SELECT t.id, ((t.created_ms - $3) << $4) | nextval('discord_snowflake_sequence')
+             FROM UNNEST($1::UUID[], $2::BIGINT[]) AS t(id, created_ms)
+             WHERE NOT EXISTS (SELECT 1 FROM discord_snowflakes s WHERE s.id = t.id)
+             ON CONFLICT DO NOTHING",
+        )
+        .bind(&uuids)
+        .bind(&created)
+        .bind(DISCORD_EPOCH_MS)
+        .bind(SNOWFLAKE_TIMESTAMP_SHIFT as i32)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to assign snowflakes", e))?;
+
+        let rows = sqlx::query_as::<_, (Uuid, i64)>(
+            "SELECT id, snowflake FROM discord_snowflakes WHERE id = ANY($1)",
+        )
+        .bind(&uuids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to load snowflakes", e))?;
+
+        Ok(rows.into_iter().collect())
+    }
+
+    async fn find_ids(&self, snowflakes: &[i64]) -> Result<HashMap<i64, Uuid>, CoreError> {
+        let rows = sqlx::query_as::<_, (i64, Uuid)>(
+            "SELECT snowflake, id FROM discord_snowflakes WHERE snowflake = ANY($1)",
+        )
+        .bind(snowflakes)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to resolve snowflakes", e))?;
+
+        Ok(rows.into_iter().collect())
+    }
+}
@@ -0,0 +1,243 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::emoji::GuildEmoji;
+use sqlx::{PgPool, Postgres, Transaction};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    emoji::ports::{CreateEmojiInput, EmojiRepository},
+    errors::CoreError,
+};
+
+#[derive(Clone)]
+pub struct PostgresEmojiRepository {
+    pool: PgPool,
+}
+
+impl PostgresEmojiRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const SELECT_EMOJIS_SQL: &str = r#"
+    SELECT e.id, e.guild_id, e.name, e.animated, e.storage_key, e.created_by, e.created_at,
+           ARRAY(SELECT r.role_id FROM guild_emoji_roles r WHERE r.emoji_id = e.id ORDER BY r.role_id) AS roles
+    FROM guild_emojis e
+"#;
+
+#[derive(sqlx::FromRow)]
+struct EmojiRow {
+    id: Uuid,
+    guild_id: Uuid,
+    name: String,
+    animated: bool,
+    storage_key: String,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+    roles: Vec<Uuid>,
+}
+
+impl From<EmojiRow> for GuildEmoji {
+    fn from(row: EmojiRow) -> Self {
+        GuildEmoji {
+            id: row.id,
+            guild_id: row.guild_id,
+            name: row.name,
+            animated: row.animated,
+            roles: row.roles,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            storage_key: row.storage_key,
+            url: String::new(),
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+/// Names are unique within a guild.
+fn name_err(name: &str, e: sqlx::Error) -> CoreError {
+    if e.as_database_error()
+        .is_some_and(|db| db.is_unique_violation())
+    {
+        return CoreError::InvalidEmoji {
+            message: format!("an emoji named {name} already exists"),
+        };
+    }
+    db_err("failed to write emoji", e)
+}
+
+async fn set_roles(
+    tx: &mut Transaction<'_, Postgres>,
+    emoji_id: Uuid,
+    roles: &[Uuid],
+) -> Result<(), CoreError> {
+    sqlx::query("DELETE FROM guild_emoji_roles WHERE emoji_id = $1")
+        .bind(emoji_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| db_err("failed to clear emoji roles", e))?;
+
+    sqlx::query("INSERT INTO guild_emoji_roles (emoji_id, role_id) SELECT $1, UNNEST($2::UUID[])")
+        .bind(emoji_id)
+        .bind(roles)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| db_err("failed to set emoji roles", e))?;
+
+    Ok(())
+}
+
+// ─── EmojiRepository impl ─────────────────────────────────────────────────────
+
+impl EmojiRepository for PostgresEmojiRepository {
+    async fn insert(
+        &self,
+        guild_id: Uuid,
+        created_by: Uuid,
+        input: &CreateEmojiInput,
+    ) -> Result<GuildEmoji, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO guild_emojis
+                (id, guild_id, name, animated, content_type, size_bytes, storage_key, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(input.id)
+        .bind(guild_id)
+        .bind(&input.name)
+        .bind(input.image.animated)
+        .bind(input.image.content_type)
+        .bind(input.size_bytes as i32)
+        .bind(&input.storage_key)
+        .bind(created_by)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| name_err(&input.name, e))?;
+
+        set_roles(&mut tx, input.id, &input.roles).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        self.find_by_id(input.id)
+            .await?
+            .ok_or_else(|| CoreError::Unknown {
+                message: format!("emoji {} vanished after being written", input.id),
+            })
+    }
+
+    async fn find_by_id(&self, emoji_id: Uuid) -> Result<Option<GuildEmoji>, CoreError> {
+        let row = sqlx::query_as::<_, EmojiRow>(&format!("{SELECT_EMOJIS_SQL} WHERE e.id = $1"))
+            .bind(emoji_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to find emoji", e))?;
+
+        Ok(row.map(GuildEmoji::from))
+    }
+
+    async fn find_many(&self, emoji_ids: &[Uuid]) -> Result<Vec<GuildEmoji>, CoreError> {
+        let rows =
+            sqlx::query_as::<_, EmojiRow>(&format!("{SELECT_EMOJIS_SQL} WHERE e.id = ANY($1)"))
+                .bind(emoji_ids)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| db_err("failed to find emojis", e))?;
+
+        Ok(rows.into_iter().map(GuildEmoji::from).collect())
+    }
+
+    async fn list_by_guild(&self, guild_id: Uuid) -> Result<Vec<GuildEmoji>, CoreError> {
+        let rows = sqlx::query_as::<_, EmojiRow>(&format!(
+            "{SELECT_EMOJIS_SQL} WHERE e.guild_id = $1 ORDER BY e.name"
+        ))
+        .bind(guild_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list emojis", e))?;
+
+        Ok(rows.into_iter().map(GuildEmoji::from).collect())
+    }
+
+    async fn count_by_guild(&self, guild_id: Uuid, animated: bool) -> Result<i64, CoreError> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM guild_emojis WHERE guild_id = $1 AND animated = $2",
+        )
+        .bind(guild_id)
+        .bind(animated)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to count emojis", e))
+    }
+
+    async fn update(
+        &self,
+        emoji_id: Uuid,
+        name: Option<&str>,
+        roles: Option<&[Uuid]>,
+    ) -> Result<Option<GuildEmoji>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let found = sqlx::query("SELECT 1 FROM guild_emojis WHERE id = $1 FOR UPDATE")
+            .bind(emoji_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to lock emoji", e))?;
+        if found.is_none() {
+            return Ok(None);
+        }
+
+        if let Some(name) = name {
+            sqlx::query("UPDATE guild_emojis SET name = $2 WHERE id = $1")
+                .bind(emoji_id)
+                .bind(name)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| name_err(name, e))?;
+        }
+        if let Some(roles) = roles {
+            set_roles(&mut tx, emoji_id, roles).await?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        self.find_by_id(emoji_id).await
+    }
+
+    async fn delete(&self, emoji_id: Uuid) -> Result<Option<GuildEmoji>, CoreError> {
+        let Some(emoji) = self.find_by_id(emoji_id).await? else {
+            return Ok(None);
+        };
+
+        let result = sqlx::query("DELETE FROM guild_emojis WHERE id = $1")
+            .bind(emoji_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete emoji", e))?;
+
+        Ok((result.rows_affected() > 0).then_some(emoji))
+    }
+}
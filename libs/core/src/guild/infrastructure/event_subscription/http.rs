@@ -0,0 +1,228 @@
+use std::sync::Arc;
+
+use reqwest::{Client, Url, redirect::Policy};
+
+use crate::guild::{
+    domain::event_subscription::ports::{
+        DELIVERY_TIMEOUT, DeliveryOutcome, EventSender, SignedRequest,
+    },
+    infrastructure::link_preview::http::AllowedHosts,
+};
+
+/// How much of an error response is kept in the delivery log.
+const MAX_ERROR_BODY_LEN: usize = 512;
+
+/// Posts deliveries over HTTP. Redirects are not followed: a receiver moving
+/// must be reflected in the subscription's URL. Like link previews, only
+/// public addresses on the default ports are reached, except for the allowed
+/// hosts.
+#[derive(Clone)]
+pub struct HttpEventSender {
+    client: Client,
+    allowed_hosts: AllowedHosts,
+}
+
+impl HttpEventSender {
+    pub fn new() -> Self {
+        Self::with_allowed_hosts(Vec::new())
+    }
+
+    /// A sender also reaching `allowed_hosts`, whatever their address.
+    pub fn with_allowed_hosts(allowed_hosts: Vec<String>) -> Self {
+        let allowed_hosts = AllowedHosts::new(allowed_hosts);
+        let client = Client::builder()
+            .timeout(DELIVERY_TIMEOUT)
+            .redirect(Policy::none())
+            .no_proxy()
+            .dns_resolver(Arc::new(allowed_hosts.clone()))
+            .user_agent(concat!("FerrisCord-Events/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .expect("failed to build HTTP client");
+        Self {
+            client,
+            allowed_hosts,
+        }
+    }
+}
+
+impl Default for HttpEventSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventSender for HttpEventSender {
+    fn check_url(&self, url: &str) -> Result<(), String> {
+        let url = Url::parse(url).map_err(|e| e.to_string())?;
+        self.allowed_hosts.check(&url)
+    }
+
+    async fn send(&self, request: SignedRequest) -> DeliveryOutcome {
+        if let Err(error) = self.check_url(&request.url) {
+            return DeliveryOutcome::Failed {
+                status_code: None,
+                error,
+            };
+        }
+        let mut builder = self
+            .client
+            .post(&request.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+
+        let response = match builder.body(request.body).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                return DeliveryOutcome::Failed {
+                    status_code: None,
+                    error: if e.is_timeout() {
+                        "request timed out".to_string()
+                    } else {
+                        e.to_string()
+                    },
+                };
+            }
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            return DeliveryOutcome::Delivered {
+                status_code: status.as_u16() as i32,
+            };
+        }
+
+        let body = response.text().await.unwrap_or_default();
+        let mut error = format!("endpoint answered {status}");
+        if !body.trim().is_empty() {
+            error.push_str(": ");
+            error.extend(body.trim().chars().take(MAX_ERROR_BODY_LEN));
+        }
+        DeliveryOutcome::Failed {
+            status_code: Some(status.as_u16() as i32),
+            error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use crate::guild::domain::event_subscription::{
+        ports::{SIGNATURE_HEADER, TIMESTAMP_HEADER},
+        sign_payload, verify_signature,
+    };
+
+    use super::*;
+
+    /// Accepts one request, answers it with `status` and returns it as text.
+    async fn receive_one(listener: TcpListener, status: &str) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request).to_string();
+            if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                let length = head
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    break;
+                }
+            }
+            if n == 0 {
+                break;
+            }
+        }
+        let response =
+            format!("HTTP/1.1 {status}\r\ncontent-length: 4\r\nconnection: close\r\n\r\nnope");
+        socket.write_all(response.as_bytes()).await.unwrap();
+        String::from_utf8(request).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_send_to_local_receiver() {
+        let sender = HttpEventSender::with_allowed_hosts(vec!["127.0.0.1".to_string()]);
+        let body = r#"{"type":"ping","data":{}}"#;
+        let timestamp = 1_700_000_000;
+
+        for (status, delivered) in [
+            ("204 No Content", true),
+            ("500 Internal Server Error", false),
+        ] {
+            let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let url = format!("http://{}/events", listener.local_addr().unwrap());
+            let receiver = tokio::spawn(receive_one(listener, status));
+
+            let outcome = sender
+                .send(SignedRequest {
+                    url,
+                    headers: vec![
+                        (SIGNATURE_HEADER, sign_payload("secret", timestamp, body)),
+                        (TIMESTAMP_HEADER, timestamp.to_string()),
+                    ],
+                    body: body.to_string(),
+                })
+                .await;
+
+            let request = receiver.await.unwrap();
+            let (head, received) = request.split_once("\r\n\r\n").unwrap();
+            assert!(head.starts_with("POST /events HTTP/1.1"));
+            assert_eq!(received, body);
+            let signature = head
+                .lines()
+                .find_map(|l| l.strip_prefix("x-ferriscord-signature: "))
+                .unwrap();
+            assert!(verify_signature("secret", timestamp, received, signature));
+
+            if delivered {
+                assert_eq!(outcome, DeliveryOutcome::Delivered { status_code: 204 });
+            } else {
+                assert!(matches!(
+                    outcome,
+                    DeliveryOutcome::Failed { status_code: Some(500), ref error } if error.ends_with("nope")
+                ));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refuses_private_targets() {
+        let sender = HttpEventSender::new();
+        for url in [
+            "http://127.0.0.1/events",
+            "http://169.254.169.254/latest/meta-data",
+            "http://10.0.0.8/events",
+            "https://hooks.example:8443/events",
+        ] {
+            assert!(sender.check_url(url).is_err(), "{url} should be refused");
+            let outcome = sender
+                .send(SignedRequest {
+                    url: url.to_string(),
+                    headers: Vec::new(),
+                    body: String::new(),
+                })
+                .await;
+            assert!(matches!(
+                outcome,
+                DeliveryOutcome::Failed {
+                    status_code: None,
+                    ..
+                }
+            ));
+        }
+        assert!(sender.check_url("https://hooks.example/events").is_ok());
+    }
+}
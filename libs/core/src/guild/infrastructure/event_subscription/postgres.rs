@@ -0,0 +1,492 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::event_subscription::{
+    EventDelivery, EventDeliveryStatus, EventSubscription, GuildEventType,
+};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    errors::CoreError,
+    event_subscription::ports::{
+        DeliveryJob, EventSubscriptionRepository, UpdateEventSubscriptionInput,
+    },
+};
+
+#[derive(Clone)]
+pub struct PostgresEventSubscriptionRepository {
+    pool: PgPool,
+}
+
+impl PostgresEventSubscriptionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const SUBSCRIPTION_COLUMNS: &str = "id, guild_id, url, event_types, enabled, consecutive_failures, disabled_reason, created_by, created_at";
+
+const DELIVERY_COLUMNS: &str = "id, subscription_id, event_type, body, status, attempts, next_attempt_at, last_status_code, last_error, created_at, delivered_at";
+
+#[derive(sqlx::FromRow)]
+struct SubscriptionRow {
+    id: Uuid,
+    guild_id: Uuid,
+    url: String,
+    event_types: Vec<String>,
+    enabled: bool,
+    consecutive_failures: i32,
+    disabled_reason: Option<String>,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<SubscriptionRow> for EventSubscription {
+    fn from(row: SubscriptionRow) -> Self {
+        EventSubscription {
+            id: row.id,
+            guild_id: row.guild_id,
+            url: row.url,
+            // Types removed from a later version are dropped rather than
+            // failing the whole subscription.
+            event_types: row
+                .event_types
+                .iter()
+                .filter_map(|t| GuildEventType::try_from(t.as_str()).ok())
+                .collect(),
+            enabled: row.enabled,
+            consecutive_failures: row.consecutive_failures,
+            disabled_reason: row.disabled_reason,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            secret: None,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DeliveryRow {
+    id: Uuid,
+    subscription_id: Uuid,
+    event_type: String,
+    body: String,
+    status: String,
+    attempts: i32,
+    next_attempt_at: Option<DateTime<Utc>>,
+    last_status_code: Option<i32>,
+    last_error: Option<String>,
+    created_at: DateTime<Utc>,
+    delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<DeliveryRow> for EventDelivery {
+    fn from(row: DeliveryRow) -> Self {
+        EventDelivery {
+            id: row.id,
+            subscription_id: row.subscription_id,
+            event_type: row.event_type,
+            body: row.body,
+            status: EventDeliveryStatus::try_from(row.status.as_str())
+                .unwrap_or(EventDeliveryStatus::Failed),
+            attempts: row.attempts,
+            next_attempt_at: row.next_attempt_at,
+            last_status_code: row.last_status_code,
+            last_error: row.last_error,
+            created_at: row.created_at,
+            delivered_at: row.delivered_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct JobRow {
+    #[sqlx(flatten)]
+    delivery: DeliveryRow,
+    url: String,
+    secret: String,
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+fn type_names(event_types: &[GuildEventType]) -> Vec<String> {
+    event_types.iter().map(|t| t.as_str().to_string()).collect()
+}
+
+// ─── EventSubscriptionRepository impl ─────────────────────────────────────────
+
+impl EventSubscriptionRepository for PostgresEventSubscriptionRepository {
+    async fn insert(
+        &self,
+        subscription: &EventSubscription,
+        secret: &str,
+    ) -> Result<EventSubscription, CoreError> {
+        let row = sqlx::query_as::<_, SubscriptionRow>(&format!(
+            r#"
+            INSERT INTO event_subscriptions
+                (id, guild_id, url, secret, event_types, enabled, created_by, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING {SUBSCRIPTION_COLUMNS}
+            "#
+        ))
+        .bind(subscription.id)
+        .bind(subscription.guild_id)
+        .bind(&subscription.url)
+        .bind(secret)
+        .bind(type_names(&subscription.event_types))
+        .bind(subscription.enabled)
+        .bind(subscription.created_by)
+        .bind(subscription.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to insert event subscription", e))?;
+
+        Ok(row.into())
+    }
+
+    async fn find_by_id(
+        &self,
+        subscription_id: Uuid,
+    ) -> Result<Option<EventSubscription>, CoreError> {
+        let row = sqlx::query_as::<_, SubscriptionRow>(&format!(
+            "SELECT {SUBSCRIPTION_COLUMNS} FROM event_subscriptions WHERE id = $1"
+        ))
+        .bind(subscription_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find event subscription", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_by_guild(&self, guild_id: Uuid) -> Result<Vec<EventSubscription>, CoreError> {
+        let rows = sqlx::query_as::<_, SubscriptionRow>(&format!(
+            "SELECT {SUBSCRIPTION_COLUMNS} FROM event_subscriptions WHERE guild_id = $1 ORDER BY created_at"
+        ))
+        .bind(guild_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list event subscriptions", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn count_by_guild(&self, guild_id: Uuid) -> Result<i64, CoreError> {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM event_subscriptions WHERE guild_id = $1")
+            .bind(guild_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to count event subscriptions", e))
+    }
+
+    async fn update(
+        &self,
+        subscription_id: Uuid,
+        input: UpdateEventSubscriptionInput,
+    ) -> Result<Option<EventSubscription>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let row = sqlx::query_as::<_, SubscriptionRow>(&format!(
+            r#"
+            UPDATE event_subscriptions
+            SET url = COALESCE($2, url),
+                event_types = COALESCE($3, event_types),
+                enabled = COALESCE($4, enabled),
+                consecutive_failures = CASE WHEN $4 THEN 0 ELSE consecutive_failures END,
+                disabled_reason = CASE WHEN $4 IS NULL THEN disabled_reason ELSE NULL END
+            WHERE id = $1
+            RETURNING {SUBSCRIPTION_COLUMNS}
+            "#
+        ))
+        .bind(subscription_id)
+        .bind(input.url)
+        .bind(input.event_types.as_deref().map(type_names))
+        .bind(input.enabled)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to update event subscription", e))?;
+
+        if input.enabled == Some(false) {
+            sqlx::query(
+                r#"
+                UPDATE event_deliveries
+                SET status = 'failed', next_attempt_at = NULL, last_error = 'subscription disabled'
+                WHERE subscription_id = $1 AND status = 'pending'
+                "#,
+            )
+            .bind(subscription_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to cancel pending deliveries", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn delete(&self, subscription_id: Uuid) -> Result<bool, CoreError> {
+        let result = sqlx::query("DELETE FROM event_subscriptions WHERE id = $1")
+            .bind(subscription_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete event subscription", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn enqueue_event(
+        &self,
+        guild_id: Uuid,
+        event_type: GuildEventType,
+        body: &str,
+    ) -> Result<u64, CoreError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO event_deliveries (id, subscription_id, event_type, body, next_attempt_at)
+            SELECT gen_random_uuid(), id, $2, $3, now()
+            FROM event_subscriptions
+            WHERE guild_id = $1 AND enabled AND $2 = ANY(event_types)
+            "#,
+        )
+        .bind(guild_id)
+        .bind(event_type.as_str())
+        .bind(body)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to enqueue event deliveries", e))?;
+
+        Ok(result.rows_affected())
+    }
+
+    async fn enqueue_delivery(
+        &self,
+        subscription_id: Uuid,
+        event_type: &str,
+        body: &str,
+    ) -> Result<EventDelivery, CoreError> {
+        let row = sqlx::query_as::<_, DeliveryRow>(&format!(
+            r#"
+            INSERT INTO event_deliveries (id, subscription_id, event_type, body, next_attempt_at)
+            VALUES ($1, $2, $3, $4, now())
+            RETURNING {DELIVERY_COLUMNS}
+            "#
+        ))
+        .bind(Uuid::now_v7())
+        .bind(subscription_id)
+        .bind(event_type)
+        .bind(body)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to enqueue event delivery", e))?;
+
+        Ok(row.into())
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<DeliveryJob>, CoreError> {
+        let rows = sqlx::query_as::<_, JobRow>(
+            r#"
+            WITH due AS (
+                SELECT d.id
+                FROM event_deliveries d
+                JOIN event_subscriptions s ON s.id = d.subscription_id
+                WHERE d.status = 'pending' AND d.next_attempt_at <= $1 AND s.enabled
+                ORDER BY d.next_attempt_at
+                LIMIT $3
+                FOR UPDATE OF d SKIP LOCKED
+            )
+            UPDATE event_deliveries d
+            SET attempts = d.attempts + 1, next_attempt_at = $2
+            FROM due, event_subscriptions s
+            WHERE d.id = due.id AND s.id = d.subscription_id
+            RETURNING d.id, d.subscription_id, d.event_type, d.body, d.status, d.attempts,
+                      d.next_attempt_at, d.last_status_code, d.last_error, d.created_at,
+                      d.delivered_at, s.url, s.secret
+            "#,
+        )
+        .bind(now)
+        .bind(lease_until)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to claim event deliveries", e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DeliveryJob {
+                delivery: row.delivery.into(),
+                url: row.url,
+                secret: row.secret,
+            })
+            .collect())
+    }
+
+    async fn record_success(
+        &self,
+        delivery_id: Uuid,
+        status_code: i32,
+        at: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let subscription_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE event_deliveries
+            SET status = 'succeeded', next_attempt_at = NULL, last_status_code = $2,
+                last_error = NULL, delivered_at = $3
+            WHERE id = $1
+            RETURNING subscription_id
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(status_code)
+        .bind(at)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to record event delivery", e))?;
+
+        if let Some(subscription_id) = subscription_id {
+            sqlx::query("UPDATE event_subscriptions SET consecutive_failures = 0 WHERE id = $1")
+                .bind(subscription_id)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| db_err("failed to reset subscription failures", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))
+    }
+
+    async fn record_failure(
+        &self,
+        delivery_id: Uuid,
+        status_code: Option<i32>,
+        error: &str,
+        retry_at: Option<DateTime<Utc>>,
+        disable_after: i32,
+    ) -> Result<bool, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let subscription_id = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE event_deliveries
+            SET status = CASE WHEN $4::TIMESTAMPTZ IS NULL THEN 'failed' ELSE 'pending' END,
+                next_attempt_at = $4, last_status_code = $2, last_error = $3
+            WHERE id = $1
+            RETURNING subscription_id
+            "#,
+        )
+        .bind(delivery_id)
+        .bind(status_code)
+        .bind(error)
+        .bind(retry_at)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to record event delivery", e))?;
+
+        let Some(subscription_id) = subscription_id else {
+            return Ok(false);
+        };
+
+        let disabled = sqlx::query_scalar::<_, bool>(
+            r#"
+            UPDATE event_subscriptions
+            SET consecutive_failures = consecutive_failures + 1,
+                enabled = enabled AND consecutive_failures + 1 < $2,
+                disabled_reason = CASE
+                    WHEN enabled AND consecutive_failures + 1 >= $2
+                    THEN 'disabled after ' || $2::TEXT || ' consecutive failed deliveries'
+                    ELSE disabled_reason
+                END
+            WHERE id = $1
+            RETURNING disabled_reason IS NOT NULL AND NOT enabled AND consecutive_failures = $2
+            "#,
+        )
+        .bind(subscription_id)
+        .bind(disable_after)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to record subscription failure", e))?;
+
+        if disabled {
+            sqlx::query(
+                r#"
+                UPDATE event_deliveries
+                SET status = 'failed', next_attempt_at = NULL,
+                    last_error = COALESCE(last_error, 'subscription disabled')
+                WHERE subscription_id = $1 AND status = 'pending'
+                "#,
+            )
+            .bind(subscription_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to cancel pending deliveries", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        Ok(disabled)
+    }
+
+    async fn list_deliveries(
+        &self,
+        subscription_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<EventDelivery>, CoreError> {
+        let rows = sqlx::query_as::<_, DeliveryRow>(&format!(
+            r#"
+            SELECT {DELIVERY_COLUMNS} FROM event_deliveries
+            WHERE subscription_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2
+            "#
+        ))
+        .bind(subscription_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list event deliveries", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn prune_deliveries(&self, before: DateTime<Utc>) -> Result<u64, CoreError> {
+        let result = sqlx::query(
+            "DELETE FROM event_deliveries WHERE status <> 'pending' AND created_at < $1",
+        )
+        .bind(before)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to prune event deliveries", e))?;
+
+        Ok(result.rows_affected())
+    }
+}
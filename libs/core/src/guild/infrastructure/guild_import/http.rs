@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use reqwest::{Client, Url, header::CONTENT_TYPE, redirect::Policy};
+
+use crate::guild::{
+    domain::guild_import::ports::{
+        ATTACHMENT_FETCH_TIMEOUT, FetchedAttachment, ImportAttachmentFetcher,
+    },
+    infrastructure::link_preview::http::{PublicResolver, check_target},
+};
+
+const MAX_REDIRECTS: usize = 5;
+
+/// Downloads attachments of imported messages from Discord's CDN. Like link
+/// previews, only public addresses on the default ports are reached.
+#[derive(Clone)]
+pub struct HttpAttachmentFetcher {
+    client: Client,
+}
+
+impl HttpAttachmentFetcher {
+    pub fn new() -> Self {
+        let redirects = Policy::custom(|attempt| {
+            if attempt.previous().len() > MAX_REDIRECTS {
+                return attempt.error("too many redirects");
+            }
+            match check_target(attempt.url()) {
+                Ok(()) => attempt.follow(),
+                Err(e) => attempt.error(e),
+            }
+        });
+        let client = Client::builder()
+            .timeout(ATTACHMENT_FETCH_TIMEOUT)
+            .redirect(redirects)
+            .no_proxy()
+            .dns_resolver(Arc::new(PublicResolver))
+            .user_agent(concat!(
+                "FerrisCord-GuildImport/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .expect("failed to build HTTP client");
+        Self { client }
+    }
+}
+
+impl Default for HttpAttachmentFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ImportAttachmentFetcher for HttpAttachmentFetcher {
+    async fn fetch(&self, url: &str, max_bytes: usize) -> Result<FetchedAttachment, String> {
+        let target = Url::parse(url).map_err(|e| e.to_string())?;
+        check_target(&target)?;
+
+        let mut response = self.client.get(target).send().await.map_err(|e| {
+            if e.is_timeout() {
+                "request timed out".to_string()
+            } else {
+                e.to_string()
+            }
+        })?;
+        if !response.status().is_success() {
+            return Err(format!("download answered {}", response.status()));
+        }
+        if response
+            .content_length()
+            .is_some_and(|len| len > max_bytes as u64)
+        {
+            return Err("attachment is too large".to_string());
+        }
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(str::to_string);
+
+        let mut data = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            if data.len() + chunk.len() > max_bytes {
+                return Err("attachment is too large".to_string());
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        Ok(FetchedAttachment { data, content_type })
+    }
+}
@@ -0,0 +1,766 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use ferriscord_entities::guild_import::{GuildImport, GuildImportProgress, GuildImportStatus};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    errors::CoreError,
+    guild_import::{
+        export::{ExportDocument, ImportManifest},
+        ports::{
+            GuildImportRepository, ImportJob, ImportStructure, ImportedMessage, StagedAttachment,
+            StagedMessage,
+        },
+    },
+};
+
+/// Staged messages inserted per statement.
+const STAGE_CHUNK: usize = 5_000;
+
+#[derive(Clone)]
+pub struct PostgresGuildImportRepository {
+    pool: PgPool,
+}
+
+impl PostgresGuildImportRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const IMPORT_COLUMNS: &str = "id, user_id, name, dry_run, status, guild_id, progress::TEXT AS progress, failure_reason, created_at, started_at, finished_at";
+
+#[derive(sqlx::FromRow)]
+struct ImportRow {
+    id: Uuid,
+    user_id: Uuid,
+    name: Option<String>,
+    dry_run: bool,
+    status: String,
+    guild_id: Option<Uuid>,
+    progress: String,
+    failure_reason: Option<String>,
+    created_at: DateTime<Utc>,
+    started_at: Option<DateTime<Utc>>,
+    finished_at: Option<DateTime<Utc>>,
+}
+
+impl From<ImportRow> for GuildImport {
+    fn from(row: ImportRow) -> Self {
+        GuildImport {
+            id: row.id,
+            user_id: row.user_id,
+            name: row.name,
+            dry_run: row.dry_run,
+            status: GuildImportStatus::try_from(row.status.as_str())
+                .unwrap_or(GuildImportStatus::Failed),
+            guild_id: row.guild_id,
+            progress: serde_json::from_str(&row.progress).unwrap_or_default(),
+            failure_reason: row.failure_reason,
+            created_at: row.created_at,
+            started_at: row.started_at,
+            finished_at: row.finished_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct JobRow {
+    #[sqlx(flatten)]
+    import: ImportRow,
+    owner_id: Uuid,
+    user_mappings: String,
+    manifest: String,
+    cursor: i64,
+}
+
+const JOB_COLUMNS: &str =
+    "owner_id, user_mappings::TEXT AS user_mappings, manifest::TEXT AS manifest, cursor";
+
+impl TryFrom<JobRow> for ImportJob {
+    type Error = CoreError;
+
+    fn try_from(row: JobRow) -> Result<Self, Self::Error> {
+        Ok(ImportJob {
+            owner_id: row.owner_id,
+            user_mappings: decode("user mappings", &row.user_mappings)?,
+            manifest: decode("manifest", &row.manifest)?,
+            cursor: row.cursor,
+            import: row.import.into(),
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct StagedRow {
+    seq: i64,
+    source_id: String,
+    channel_source_id: String,
+    author_source_id: String,
+    content: String,
+    attachments: String,
+    created_at: DateTime<Utc>,
+    edited_at: Option<DateTime<Utc>>,
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+fn encode<T: serde::Serialize>(what: &str, value: &T) -> Result<String, CoreError> {
+    serde_json::to_string(value).map_err(|e| CoreError::Unknown {
+        message: format!("failed to encode {}: {}", what, e),
+    })
+}
+
+fn decode<T: serde::de::DeserializeOwned>(what: &str, value: &str) -> Result<T, CoreError> {
+    serde_json::from_str(value).map_err(|e| CoreError::Unknown {
+        message: format!("failed to decode {}: {}", what, e),
+    })
+}
+
+impl PostgresGuildImportRepository {
+    /// Sets the final status of an unfinished import and drops its staged
+    /// messages.
+    async fn close(
+        &self,
+        id: Uuid,
+        user_id: Option<Uuid>,
+        statuses: &[&str],
+        status: GuildImportStatus,
+        progress: Option<&GuildImportProgress>,
+        reason: Option<&str>,
+    ) -> Result<Option<GuildImport>, CoreError> {
+        let progress = progress.map(|p| encode("progress", p)).transpose()?;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let row = sqlx::query_as::<_, ImportRow>(&format!(
+            r#"
+            UPDATE guild_imports
+            SET status = $3, progress = COALESCE($4::JSONB, progress), failure_reason = $5,
+                finished_at = now(), lease_until = NULL
+            WHERE id = $1 AND ($2::UUID IS NULL OR user_id = $2) AND status = ANY($6)
+            RETURNING {IMPORT_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(user_id)
+        .bind(status.as_str())
+        .bind(progress)
+        .bind(reason)
+        .bind(statuses)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to close guild import", e))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        sqlx::query("DELETE FROM guild_import_messages WHERE import_id = $1")
+            .bind(id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to drop staged messages", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        Ok(Some(row.into()))
+    }
+}
+
+// ─── GuildImportRepository impl ───────────────────────────────────────────────
+
+impl GuildImportRepository for PostgresGuildImportRepository {
+    async fn insert(
+        &self,
+        import: &GuildImport,
+        owner_id: Uuid,
+        user_mappings: &HashMap<String, Uuid>,
+    ) -> Result<GuildImport, CoreError> {
+        let row = sqlx::query_as::<_, ImportRow>(&format!(
+            r#"
+            INSERT INTO guild_imports
+                (id, user_id, owner_id, name, dry_run, user_mappings, manifest, status, progress, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6::JSONB, $7::JSONB, $8, $9::JSONB, $10)
+            RETURNING {IMPORT_COLUMNS}
+            "#
+        ))
+        .bind(import.id)
+        .bind(import.user_id)
+        .bind(owner_id)
+        .bind(&import.name)
+        .bind(import.dry_run)
+        .bind(encode("user mappings", user_mappings)?)
+        .bind(encode("manifest", &ImportManifest::default())?)
+        .bind(import.status.as_str())
+        .bind(encode("progress", &import.progress)?)
+        .bind(import.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to insert guild import", e))?;
+
+        Ok(row.into())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ImportJob>, CoreError> {
+        let row = sqlx::query_as::<_, JobRow>(&format!(
+            "SELECT {IMPORT_COLUMNS}, {JOB_COLUMNS} FROM guild_imports WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find guild import", e))?;
+
+        row.map(ImportJob::try_from).transpose()
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> Result<Vec<GuildImport>, CoreError> {
+        let rows = sqlx::query_as::<_, ImportRow>(&format!(
+            "SELECT {IMPORT_COLUMNS} FROM guild_imports WHERE user_id = $1 ORDER BY created_at DESC"
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list guild imports", e))?;
+
+        Ok(rows.into_iter().map(GuildImport::from).collect())
+    }
+
+    async fn count_active_by_user(&self, user_id: Uuid) -> Result<i64, CoreError> {
+        sqlx::query_scalar(
+            r#"
+            SELECT COUNT(*) FROM guild_imports
+            WHERE user_id = $1 AND status IN ('uploading', 'pending', 'running')
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to count guild imports", e))
+    }
+
+    async fn slug_exists(&self, slug: &str) -> Result<bool, CoreError> {
+        sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM guilds WHERE slug = $1)")
+            .bind(slug)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to check guild slug", e))
+    }
+
+    async fn stage_channel(
+        &self,
+        id: Uuid,
+        user_id: Uuid,
+        export: ExportDocument,
+    ) -> Result<Option<GuildImport>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        // Locks the import, so that channels uploaded at once are added one
+        // after the other.
+        let row = sqlx::query_as::<_, JobRow>(&format!(
+            r#"
+            SELECT {IMPORT_COLUMNS}, {JOB_COLUMNS} FROM guild_imports
+            WHERE id = $1 AND user_id = $2 AND status = 'uploading'
+            FOR UPDATE
+            "#
+        ))
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to find guild import", e))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let mut job = ImportJob::try_from(row)?;
+
+        let staged = job.manifest.add_channel(export)?;
+        let progress = job
+            .manifest
+            .progress(|author| job.user_mappings.contains_key(author));
+
+        for chunk in staged.chunks(STAGE_CHUNK) {
+            let mut source_ids = Vec::with_capacity(chunk.len());
+            let mut channel_ids = Vec::with_capacity(chunk.len());
+            let mut author_ids = Vec::with_capacity(chunk.len());
+            let mut contents = Vec::with_capacity(chunk.len());
+            let mut attachments = Vec::with_capacity(chunk.len());
+            let mut created_ats = Vec::with_capacity(chunk.len());
+            let mut edited_ats = Vec::with_capacity(chunk.len());
+            for message in chunk {
+                source_ids.push(message.source_id.as_str());
+                channel_ids.push(message.channel_source_id.as_str());
+                author_ids.push(message.author_source_id.as_str());
+                contents.push(message.content.as_str());
+                attachments.push(encode("attachments", &message.attachments)?);
+                created_ats.push(message.created_at);
+                edited_ats.push(message.edited_at);
+            }
+
+            // WITH ORDINALITY keeps the export order, which `seq` follows.
+            sqlx::query(
+                r#"
+                INSERT INTO guild_import_messages
+                    (import_id, source_id, channel_source_id, author_source_id, content, attachments, created_at, edited_at)
+                SELECT $1, m.source_id, m.channel_source_id, m.author_source_id, m.content,
+                       m.attachments::JSONB, m.created_at, m.edited_at
+                FROM UNNEST($2::TEXT[], $3::TEXT[], $4::TEXT[], $5::TEXT[], $6::TEXT[], $7::TIMESTAMPTZ[], $8::TIMESTAMPTZ[])
+                    WITH ORDINALITY AS m(source_id, channel_source_id, author_source_id, content, attachments, created_at, edited_at, n)
+                ORDER BY m.n
+                ON CONFLICT (import_id, source_id) DO NOTHING
+                "#,
+            )
+            .bind(id)
+            .bind(&source_ids)
+            .bind(&channel_ids)
+            .bind(&author_ids)
+            .bind(&contents)
+            .bind(&attachments)
+            .bind(&created_ats)
+            .bind(&edited_ats)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to stage messages", e))?;
+        }
+
+        let row = sqlx::query_as::<_, ImportRow>(&format!(
+            r#"
+            UPDATE guild_imports SET manifest = $2::JSONB, progress = $3::JSONB
+            WHERE id = $1
+            RETURNING {IMPORT_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(encode("manifest", &job.manifest)?)
+        .bind(encode("progress", &progress)?)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to update guild import", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        Ok(Some(row.into()))
+    }
+
+    async fn start(&self, id: Uuid, user_id: Uuid) -> Result<Option<GuildImport>, CoreError> {
+        let row = sqlx::query_as::<_, ImportRow>(&format!(
+            r#"
+            UPDATE guild_imports SET status = 'pending'
+            WHERE id = $1 AND user_id = $2 AND status = 'uploading'
+            RETURNING {IMPORT_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to start guild import", e))?;
+
+        Ok(row.map(GuildImport::from))
+    }
+
+    async fn cancel(&self, id: Uuid, user_id: Uuid) -> Result<Option<GuildImport>, CoreError> {
+        self.close(
+            id,
+            Some(user_id),
+            &["uploading", "pending", "running"],
+            GuildImportStatus::Canceled,
+            None,
+            None,
+        )
+        .await
+    }
+
+    async fn claim(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<ImportJob>, CoreError> {
+        let row = sqlx::query_as::<_, JobRow>(&format!(
+            r#"
+            WITH next AS (
+                SELECT id AS next_id
+                FROM guild_imports
+                WHERE status IN ('pending', 'running')
+                  AND (lease_until IS NULL OR lease_until <= $1)
+                ORDER BY created_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE guild_imports
+            SET status = 'running', started_at = COALESCE(started_at, $1), lease_until = $2
+            FROM next
+            WHERE id = next.next_id
+            RETURNING {IMPORT_COLUMNS}, {JOB_COLUMNS}
+            "#
+        ))
+        .bind(now)
+        .bind(lease_until)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to claim guild import", e))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let id = row.import.id;
+        match ImportJob::try_from(row) {
+            Ok(job) => Ok(Some(job)),
+            Err(e) => {
+                error!("unreadable guild import {}: {}", id, e);
+                self.close(
+                    id,
+                    None,
+                    &["running"],
+                    GuildImportStatus::Failed,
+                    None,
+                    Some("the import could not be read"),
+                )
+                .await?;
+                Ok(None)
+            }
+        }
+    }
+
+    async fn create_structure(
+        &self,
+        id: Uuid,
+        structure: &ImportStructure,
+    ) -> Result<bool, CoreError> {
+        let guild = &structure.guild;
+        let guild_id = guild.id.get_uuid();
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let running = sqlx::query_scalar::<_, Uuid>(
+            "SELECT id FROM guild_imports WHERE id = $1 AND status = 'running' FOR UPDATE",
+        )
+        .bind(id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to lock guild import", e))?;
+        if running.is_none() {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO guilds (id, name, slug, owner_id, created_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(guild_id)
+        .bind(&guild.name)
+        .bind(&guild.slug)
+        .bind(guild.owner_id.get_uuid())
+        .bind(guild.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            if let Some(db_err) = e.as_database_error()
+                && db_err.constraint() == Some("guilds_slug_key")
+            {
+                return CoreError::GuildSlugAlreadyExists {
+                    slug: guild.name.clone(),
+                };
+            }
+            db_err("failed to insert imported guild", e)
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO roles (id, guild_id, name, position, color, permissions, created_at)
+            VALUES ($1, $2, '@everyone', 0, 0, 0, $3)
+            "#,
+        )
+        .bind(structure.everyone_role_id)
+        .bind(guild_id)
+        .bind(guild.created_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert @everyone role", e))?;
+
+        for role in &structure.roles {
+            sqlx::query(
+                r#"
+                INSERT INTO roles (id, guild_id, name, position, color, permissions, created_at)
+                VALUES ($1, $2, $3, $4, $5, 0, $6)
+                "#,
+            )
+            .bind(role.id)
+            .bind(guild_id)
+            .bind(&role.name)
+            .bind(role.position)
+            .bind(role.color as i32)
+            .bind(guild.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to insert imported role", e))?;
+        }
+
+        for channel in &structure.channels {
+            let kind: i16 = channel.kind.into();
+            sqlx::query(
+                r#"
+                INSERT INTO channels (id, kind, guild_id, position, name, topic, parent_id, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+                "#,
+            )
+            .bind(channel.id)
+            .bind(kind)
+            .bind(guild_id)
+            .bind(channel.position)
+            .bind(&channel.name)
+            .bind(&channel.topic)
+            .bind(channel.parent_id)
+            .bind(guild.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to insert imported channel", e))?;
+        }
+
+        // Placeholders may exist from an earlier attempt whose guild was
+        // deleted since.
+        for user in &structure.users {
+            sqlx::query(
+                r#"
+                INSERT INTO users (id, oauth_sub, username, display_name, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $5)
+                ON CONFLICT (oauth_sub) DO NOTHING
+                "#,
+            )
+            .bind(user.id)
+            .bind(&user.oauth_sub)
+            .bind(&user.username)
+            .bind(&user.display_name)
+            .bind(guild.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to insert placeholder user", e))?;
+        }
+
+        // The owner, whom authors may be mapped to, and the placeholders are
+        // members.
+        let members: Vec<Uuid> = std::iter::once(structure.owner_user_id)
+            .chain(structure.users.iter().map(|user| user.id))
+            .collect();
+        sqlx::query(
+            r#"
+            INSERT INTO members (id, guild_id, user_id)
+            SELECT gen_random_uuid(), $1, user_id FROM UNNEST($2::UUID[]) AS user_id
+            ON CONFLICT (guild_id, user_id) DO NOTHING
+            "#,
+        )
+        .bind(guild_id)
+        .bind(&members)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert imported members", e))?;
+
+        sqlx::query("UPDATE guild_imports SET guild_id = $2 WHERE id = $1")
+            .bind(id)
+            .bind(guild_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to update guild import", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        Ok(true)
+    }
+
+    async fn list_staged(
+        &self,
+        id: Uuid,
+        after_seq: i64,
+        limit: i64,
+    ) -> Result<Vec<StagedMessage>, CoreError> {
+        let rows = sqlx::query_as::<_, StagedRow>(
+            r#"
+            SELECT seq, source_id, channel_source_id, author_source_id, content,
+                   attachments::TEXT AS attachments, created_at, edited_at
+            FROM guild_import_messages
+            WHERE import_id = $1 AND seq > $2
+            ORDER BY seq
+            LIMIT $3
+            "#,
+        )
+        .bind(id)
+        .bind(after_seq)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list staged messages", e))?;
+
+        rows.into_iter()
+            .map(|row| {
+                Ok(StagedMessage {
+                    seq: row.seq,
+                    source_id: row.source_id,
+                    channel_source_id: row.channel_source_id,
+                    author_source_id: row.author_source_id,
+                    content: row.content,
+                    attachments: decode::<Vec<StagedAttachment>>("attachments", &row.attachments)?,
+                    created_at: row.created_at,
+                    edited_at: row.edited_at,
+                })
+            })
+            .collect()
+    }
+
+    async fn record_messages(
+        &self,
+        id: Uuid,
+        messages: &[ImportedMessage],
+        cursor: i64,
+        progress: &GuildImportProgress,
+        lease_until: DateTime<Utc>,
+    ) -> Result<Option<GuildImport>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let row = sqlx::query_as::<_, ImportRow>(&format!(
+            r#"
+            UPDATE guild_imports SET cursor = $2, progress = $3::JSONB, lease_until = $4
+            WHERE id = $1 AND status = 'running'
+            RETURNING {IMPORT_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(cursor)
+        .bind(encode("progress", progress)?)
+        .bind(lease_until)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to update guild import", e))?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        for message in messages {
+            let inserted = sqlx::query(
+                r#"
+                INSERT INTO messages (id, channel_id, author_id, content, edited_at, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (id) DO NOTHING
+                "#,
+            )
+            .bind(message.id)
+            .bind(message.channel_id)
+            .bind(message.author_id)
+            .bind(&message.content)
+            .bind(message.edited_at)
+            .bind(message.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to insert imported message", e))?;
+            if inserted.rows_affected() == 0 {
+                continue;
+            }
+
+            for attachment in &message.attachments {
+                sqlx::query(
+                    r#"
+                    INSERT INTO attachments
+                        (id, message_id, filename, content_type, size_bytes, storage_key, created_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    ON CONFLICT (id) DO NOTHING
+                    "#,
+                )
+                .bind(attachment.id)
+                .bind(message.id)
+                .bind(&attachment.filename)
+                .bind(&attachment.content_type)
+                .bind(attachment.size_bytes)
+                .bind(&attachment.storage_key)
+                .bind(message.created_at)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| db_err("failed to insert imported attachment", e))?;
+            }
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        Ok(Some(row.into()))
+    }
+
+    async fn finish(
+        &self,
+        id: Uuid,
+        progress: &GuildImportProgress,
+    ) -> Result<Option<GuildImport>, CoreError> {
+        self.close(
+            id,
+            None,
+            &["running"],
+            GuildImportStatus::Completed,
+            Some(progress),
+            None,
+        )
+        .await
+    }
+
+    async fn fail(&self, id: Uuid, reason: &str) -> Result<Option<GuildImport>, CoreError> {
+        self.close(
+            id,
+            None,
+            &["running"],
+            GuildImportStatus::Failed,
+            None,
+            Some(reason),
+        )
+        .await
+    }
+
+    async fn prune(
+        &self,
+        finished_before: DateTime<Utc>,
+        created_before: DateTime<Utc>,
+    ) -> Result<u64, CoreError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM guild_imports
+            WHERE (status IN ('completed', 'failed', 'canceled') AND finished_at < $1)
+               OR (status = 'uploading' AND created_at < $2)
+            "#,
+        )
+        .bind(finished_before)
+        .bind(created_before)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to prune guild imports", e))?;
+
+        Ok(result.rows_affected())
+    }
+}
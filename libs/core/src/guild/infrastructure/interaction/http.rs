@@ -0,0 +1,129 @@
+use std::sync::Arc;
+
+use reqwest::{Client, Url, redirect::Policy};
+
+use crate::guild::{
+    domain::{
+        event_subscription::ports::SignedRequest,
+        interaction::ports::{
+            INTERACTION_DELIVERY_TIMEOUT, InteractionSender, MAX_INTERACTION_RESPONSE_BYTES,
+        },
+    },
+    infrastructure::link_preview::http::AllowedHosts,
+};
+
+/// POSTs interactions to application endpoints and reads the answer from the
+/// response body. Redirects are not followed, and like event deliveries only
+/// public addresses on the default ports are reached, except for the allowed
+/// hosts.
+#[derive(Clone)]
+pub struct HttpInteractionSender {
+    client: Client,
+    allowed_hosts: AllowedHosts,
+}
+
+impl HttpInteractionSender {
+    pub fn new() -> Self {
+        Self::with_allowed_hosts(Vec::new())
+    }
+
+    /// A sender also reaching `allowed_hosts`, whatever their address.
+    pub fn with_allowed_hosts(allowed_hosts: Vec<String>) -> Self {
+        let allowed_hosts = AllowedHosts::new(allowed_hosts);
+        let client = Client::builder()
+            .timeout(INTERACTION_DELIVERY_TIMEOUT)
+            .redirect(Policy::none())
+            .no_proxy()
+            .dns_resolver(Arc::new(allowed_hosts.clone()))
+            .user_agent(concat!(
+                "FerrisCord-Interactions/",
+                env!("CARGO_PKG_VERSION")
+            ))
+            .build()
+            .expect("failed to build HTTP client");
+        Self {
+            client,
+            allowed_hosts,
+        }
+    }
+}
+
+impl Default for HttpInteractionSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InteractionSender for HttpInteractionSender {
+    fn check_url(&self, url: &str) -> Result<(), String> {
+        let url = Url::parse(url).map_err(|e| e.to_string())?;
+        self.allowed_hosts.check(&url)
+    }
+
+    async fn send(&self, request: SignedRequest) -> Result<Option<String>, String> {
+        self.check_url(&request.url)?;
+        let mut builder = self
+            .client
+            .post(&request.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json");
+        for (name, value) in request.headers {
+            builder = builder.header(name, value);
+        }
+
+        let mut response = builder.body(request.body).send().await.map_err(|e| {
+            if e.is_timeout() {
+                "request timed out".to_string()
+            } else {
+                e.to_string()
+            }
+        })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("endpoint answered {status}"));
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| e.to_string())? {
+            if body.len() + chunk.len() > MAX_INTERACTION_RESPONSE_BYTES {
+                return Err("response body is too large".to_string());
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        let body = String::from_utf8(body).map_err(|_| "response body is not UTF-8".to_string())?;
+        Ok(Some(body).filter(|b| !b.trim().is_empty()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_refuses_private_endpoints() {
+        let sender = HttpInteractionSender::new();
+        for url in [
+            "http://127.0.0.1/interactions",
+            "http://169.254.169.254/latest/meta-data",
+            "http://[::1]/interactions",
+            "https://bot.example:8443/interactions",
+        ] {
+            assert!(sender.check_url(url).is_err(), "{url} should be refused");
+            let request = SignedRequest {
+                url: url.to_string(),
+                headers: Vec::new(),
+                body: String::new(),
+            };
+            assert!(sender.send(request).await.is_err());
+        }
+        assert!(sender.check_url("https://bot.example/interactions").is_ok());
+
+        let sender = HttpInteractionSender::with_allowed_hosts(vec!["127.0.0.1".to_string()]);
+        assert!(
+            sender
+                .check_url("http://127.0.0.1:8080/interactions")
+                .is_ok()
+        );
+    }
+}
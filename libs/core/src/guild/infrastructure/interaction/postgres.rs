@@ -0,0 +1,320 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::interaction::{ApplicationCommand, Interaction, InteractionStatus};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{errors::CoreError, interaction::ports::InteractionRepository};
+
+#[derive(Clone)]
+pub struct PostgresInteractionRepository {
+    pool: PgPool,
+}
+
+impl PostgresInteractionRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const COMMAND_COLUMNS: &str = r#"
+    c.id, c.application_id, c.guild_id, c.name, c.description, c.options::TEXT AS options,
+    c.created_at, c.updated_at
+"#;
+
+const INTERACTION_COLUMNS: &str = r#"
+    i.id, i.application_id, i.command_id, i.command_name, i.guild_id, i.channel_id, i.user_id,
+    u.username, i.options::TEXT AS options, i.status, i.ephemeral, i.created_at
+"#;
+
+#[derive(sqlx::FromRow)]
+struct CommandRow {
+    id: Uuid,
+    application_id: Uuid,
+    guild_id: Option<Uuid>,
+    name: String,
+    description: String,
+    options: String,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl From<CommandRow> for ApplicationCommand {
+    fn from(row: CommandRow) -> Self {
+        ApplicationCommand {
+            id: row.id,
+            application_id: row.application_id,
+            guild_id: row.guild_id,
+            name: row.name,
+            description: row.description,
+            options: serde_json::from_str(&row.options).unwrap_or_default(),
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct InteractionRow {
+    id: Uuid,
+    application_id: Uuid,
+    command_id: Option<Uuid>,
+    command_name: String,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    user_id: Uuid,
+    username: String,
+    options: String,
+    status: String,
+    ephemeral: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl From<InteractionRow> for Interaction {
+    fn from(row: InteractionRow) -> Self {
+        Interaction {
+            id: row.id,
+            application_id: row.application_id,
+            command_id: row.command_id,
+            command_name: row.command_name,
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            user_id: row.user_id,
+            username: row.username,
+            options: serde_json::from_str(&row.options).unwrap_or_default(),
+            status: InteractionStatus::try_from(row.status.as_str())
+                .unwrap_or(InteractionStatus::Failed),
+            ephemeral: row.ephemeral,
+            created_at: row.created_at,
+            token: None,
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+fn encode<T: serde::Serialize>(value: &T) -> Result<String, CoreError> {
+    serde_json::to_string(value).map_err(|e| CoreError::Unknown {
+        message: format!("failed to encode options: {}", e),
+    })
+}
+
+// ─── InteractionRepository impl ───────────────────────────────────────────────
+
+impl InteractionRepository for PostgresInteractionRepository {
+    async fn upsert_command(
+        &self,
+        command: &ApplicationCommand,
+    ) -> Result<ApplicationCommand, CoreError> {
+        let row = sqlx::query_as::<_, CommandRow>(&format!(
+            r#"
+            INSERT INTO application_commands AS c
+                (id, application_id, guild_id, name, description, options, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6::JSONB, $7, $7)
+            ON CONFLICT (application_id, COALESCE(guild_id, '00000000-0000-0000-0000-000000000000'::UUID), name)
+            DO UPDATE SET description = EXCLUDED.description,
+                          options = EXCLUDED.options,
+                          updated_at = EXCLUDED.updated_at
+            RETURNING {COMMAND_COLUMNS}
+            "#
+        ))
+        .bind(command.id)
+        .bind(command.application_id)
+        .bind(command.guild_id)
+        .bind(&command.name)
+        .bind(&command.description)
+        .bind(encode(&command.options)?)
+        .bind(command.updated_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to upsert command", e))?;
+
+        Ok(row.into())
+    }
+
+    async fn find_command(
+        &self,
+        command_id: Uuid,
+    ) -> Result<Option<ApplicationCommand>, CoreError> {
+        let row = sqlx::query_as::<_, CommandRow>(&format!(
+            "SELECT {COMMAND_COLUMNS} FROM application_commands c WHERE c.id = $1"
+        ))
+        .bind(command_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find command", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_commands(
+        &self,
+        application_id: Uuid,
+        guild_id: Option<Uuid>,
+    ) -> Result<Vec<ApplicationCommand>, CoreError> {
+        let rows = sqlx::query_as::<_, CommandRow>(&format!(
+            r#"
+            SELECT {COMMAND_COLUMNS} FROM application_commands c
+            WHERE c.application_id = $1 AND c.guild_id IS NOT DISTINCT FROM $2
+            ORDER BY c.name
+            "#
+        ))
+        .bind(application_id)
+        .bind(guild_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list commands", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn list_guild_commands(
+        &self,
+        guild_id: Uuid,
+    ) -> Result<Vec<ApplicationCommand>, CoreError> {
+        let rows = sqlx::query_as::<_, CommandRow>(&format!(
+            r#"
+            SELECT {COMMAND_COLUMNS} FROM application_commands c
+            JOIN guild_bots gb ON gb.application_id = c.application_id AND gb.guild_id = $1
+            WHERE c.guild_id IS NULL OR c.guild_id = $1
+            ORDER BY c.name, c.created_at
+            "#
+        ))
+        .bind(guild_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list guild commands", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn delete_command(&self, command_id: Uuid) -> Result<bool, CoreError> {
+        let result = sqlx::query("DELETE FROM application_commands WHERE id = $1")
+            .bind(command_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete command", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_interaction(
+        &self,
+        interaction: &Interaction,
+        token_hash: &str,
+    ) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"
+            INSERT INTO interactions
+                (id, application_id, command_id, command_name, guild_id, channel_id, user_id,
+                 options, token_hash, status, ephemeral, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8::JSONB, $9, $10, $11, $12)
+            "#,
+        )
+        .bind(interaction.id)
+        .bind(interaction.application_id)
+        .bind(interaction.command_id)
+        .bind(&interaction.command_name)
+        .bind(interaction.guild_id)
+        .bind(interaction.channel_id)
+        .bind(interaction.user_id)
+        .bind(encode(&interaction.options)?)
+        .bind(token_hash)
+        .bind(interaction.status.as_str())
+        .bind(interaction.ephemeral)
+        .bind(interaction.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to insert interaction", e))?;
+
+        Ok(())
+    }
+
+    async fn find_interaction(
+        &self,
+        interaction_id: Uuid,
+        token_hash: &str,
+    ) -> Result<Option<Interaction>, CoreError> {
+        let row = sqlx::query_as::<_, InteractionRow>(&format!(
+            r#"
+            SELECT {INTERACTION_COLUMNS}
+            FROM interactions i
+            JOIN users u ON u.id = i.user_id
+            WHERE i.id = $1 AND i.token_hash = $2
+            "#
+        ))
+        .bind(interaction_id)
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find interaction", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn acknowledge(
+        &self,
+        interaction_id: Uuid,
+        status: InteractionStatus,
+        ephemeral: bool,
+        created_after: DateTime<Utc>,
+    ) -> Result<Option<Interaction>, CoreError> {
+        let row = sqlx::query_as::<_, InteractionRow>(&format!(
+            r#"
+            WITH i AS (
+                UPDATE interactions
+                SET status = $2, ephemeral = $3, responded_at = now()
+                WHERE id = $1 AND status = 'pending' AND created_at >= $4
+                RETURNING *
+            )
+            SELECT {INTERACTION_COLUMNS} FROM i JOIN users u ON u.id = i.user_id
+            "#
+        ))
+        .bind(interaction_id)
+        .bind(status.as_str())
+        .bind(ephemeral)
+        .bind(created_after)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to acknowledge interaction", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn fail_pending(&self, interaction_id: Uuid) -> Result<Option<Interaction>, CoreError> {
+        let row = sqlx::query_as::<_, InteractionRow>(&format!(
+            r#"
+            WITH i AS (
+                UPDATE interactions
+                SET status = 'failed'
+                WHERE id = $1 AND status = 'pending'
+                RETURNING *
+            )
+            SELECT {INTERACTION_COLUMNS} FROM i JOIN users u ON u.id = i.user_id
+            "#
+        ))
+        .bind(interaction_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to fail interaction", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn prune_interactions(&self, before: DateTime<Utc>) -> Result<u64, CoreError> {
+        let result = sqlx::query("DELETE FROM interactions WHERE created_at < $1")
+            .bind(before)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to prune interactions", e))?;
+
+        Ok(result.rows_affected())
+    }
+}
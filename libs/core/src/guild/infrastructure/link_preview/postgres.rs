@@ -0,0 +1,91 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::embed::Embed;
+use sqlx::PgPool;
+use tracing::error;
+
+use crate::guild::domain::{
+    errors::CoreError,
+    link_preview::ports::{CachedPreview, LinkPreviewRepository},
+};
+
+#[derive(Clone)]
+pub struct PostgresLinkPreviewRepository {
+    pool: PgPool,
+}
+
+impl PostgresLinkPreviewRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PreviewRow {
+    embed: Option<String>,
+    fetched_at: DateTime<Utc>,
+}
+
+impl From<PreviewRow> for CachedPreview {
+    fn from(row: PreviewRow) -> Self {
+        CachedPreview {
+            embed: row.embed.and_then(|e| serde_json::from_str(&e).ok()),
+            fetched_at: row.fetched_at,
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+impl LinkPreviewRepository for PostgresLinkPreviewRepository {
+    async fn find(&self, url: &str) -> Result<Option<CachedPreview>, CoreError> {
+        let row = sqlx::query_as::<_, PreviewRow>(
+            "SELECT embed::TEXT AS embed, fetched_at FROM link_previews WHERE url = $1",
+        )
+        .bind(url)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find link preview", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn store(&self, url: &str, embed: Option<&Embed>) -> Result<(), CoreError> {
+        let embed =
+            embed
+                .map(serde_json::to_string)
+                .transpose()
+                .map_err(|e| CoreError::Unknown {
+                    message: format!("failed to encode link preview: {}", e),
+                })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO link_previews (url, embed, fetched_at)
+            VALUES ($1, $2::JSONB, now())
+            ON CONFLICT (url) DO UPDATE SET embed = EXCLUDED.embed, fetched_at = EXCLUDED.fetched_at
+            "#,
+        )
+        .bind(url)
+        .bind(embed)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to store link preview", e))?;
+
+        Ok(())
+    }
+
+    async fn prune(&self, fetched_before: DateTime<Utc>) -> Result<u64, CoreError> {
+        let result = sqlx::query("DELETE FROM link_previews WHERE fetched_at < $1")
+            .bind(fetched_before)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to prune link previews", e))?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -38,6 +38,7 @@ struct MemberRoleRow {
     role_name: String,
     color: i32,
     position: i32,
+    hoist: bool,
 }
 
 impl MemberRepository for PostgresMemberRepository {
@@ -93,22 +94,22 @@ impl MemberRepository for PostgresMemberRepository {
 
         let member_ids: Vec<Uuid> = rows.iter().map(|r| r.member_id).collect();
 
-        let role_rows = sqlx::query_as!(
-            MemberRoleRow,
+        let role_rows = sqlx::query_as::<_, MemberRoleRow>(
             r#"
             SELECT
                 ra.member_id,
                 r.id           AS role_id,
                 r.name         AS role_name,
-                COALESCE(r.color, 0) AS "color!: i32",
-                r.position
+                COALESCE(r.color, 0) AS color,
+                r.position,
+                r.hoist
             FROM role_assignments ra
             JOIN roles r ON r.id = ra.role_id
             WHERE ra.member_id = ANY($1)
             ORDER BY r.position ASC
             "#,
-            &member_ids,
         )
+        .bind(&member_ids)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| CoreError::Unknown {
@@ -122,6 +123,7 @@ impl MemberRepository for PostgresMemberRepository {
                 name: row.role_name,
                 color: row.color as u32,
                 position: row.position,
+                hoist: row.hoist,
             });
         }
 
@@ -3,17 +3,22 @@ use ferriscord_entities::{
     Id,
     attachment::{Attachment, AttachmentId},
     channel::ChannelId,
-    message::{Message, MessageAuthor, MessageId},
+    embed::Embed,
+    message::{Message, MessageAuthor, MessageId, MessageKind, MessageReference},
+    poll::Poll,
     user::UserId,
 };
 
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use tracing::error;
 use uuid::Uuid;
 
 use crate::guild::domain::{
     errors::CoreError,
-    message::ports::{AttachmentInput, EncryptionMeta, MessagePort},
+    message::ports::{
+        AttachmentInput, Crosspost, CrosspostInput, EncryptionMeta, MessagePort,
+        WebhookMessageInput,
+    },
 };
 
 #[derive(Clone)]
@@ -36,11 +41,20 @@ struct MessageRow {
     author_id: Uuid,
     author_username: String,
     author_avatar_url: Option<String>,
+    author_bot: bool,
     content: String,
     encrypted: bool,
     encryption_version: i32,
     sender_key_generation: Option<i32>,
     sender_device_id: Option<Uuid>,
+    webhook_id: Option<Uuid>,
+    source_guild_id: Option<Uuid>,
+    source_channel_id: Option<Uuid>,
+    source_message_id: Option<Uuid>,
+    crossposted: bool,
+    // JSONB cast to TEXT so no json sqlx feature needed
+    embeds: String,
+    poll: Option<String>,
     edited_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
 }
@@ -77,22 +91,57 @@ impl AttachmentRow {
 
 // ─── SQL helpers ──────────────────────────────────────────────────────────────
 
+// Webhook messages have no author row: they show the webhook's id and the
+// name and avatar they were posted with. Crossposted copies show the source
+// guild's id, and the guild and channel names they were published with.
 const SELECT_MESSAGES_SQL: &str = r#"
     SELECT
         m.id,
         m.channel_id,
-        m.author_id,
-        u.username AS author_username,
-        u.avatar_url AS author_avatar_url,
+        COALESCE(m.author_id, m.webhook_id, m.source_guild_id) AS author_id,
+        COALESCE(u.username, m.webhook_username, '') AS author_username,
+        COALESCE(u.avatar_url, m.webhook_avatar_url) AS author_avatar_url,
+        EXISTS (SELECT 1 FROM applications a WHERE a.bot_user_id = m.author_id) AS author_bot,
         m.content,
         m.encrypted,
         m.encryption_version,
         m.sender_key_generation,
         m.sender_device_id,
+        m.webhook_id,
+        m.source_guild_id,
+        m.source_channel_id,
+        m.source_message_id,
+        m.crossposted_at IS NOT NULL AS crossposted,
+        m.embeds::TEXT AS embeds,
+        (
+            SELECT json_build_object(
+                'question', p.question,
+                'allow_multiselect', p.allow_multiselect,
+                'hide_results', p.hide_results,
+                'expires_at', p.expires_at,
+                'closed', p.closed_at IS NOT NULL,
+                'total_voters', COALESCE(p.final_voters, (
+                    SELECT COUNT(DISTINCT v.user_id) FROM poll_votes v
+                    WHERE v.message_id = p.message_id
+                )),
+                'answers', (
+                    SELECT json_agg(json_build_object(
+                        'id', a.answer_id,
+                        'text', a.text,
+                        'votes', COALESCE(a.final_votes, (
+                            SELECT COUNT(*) FROM poll_votes v
+                            WHERE v.message_id = a.message_id AND v.answer_id = a.answer_id
+                        ))
+                    ) ORDER BY a.answer_id)
+                    FROM poll_answers a WHERE a.message_id = p.message_id
+                )
+            )
+            FROM polls p WHERE p.message_id = m.id
+        )::TEXT AS poll,
         m.edited_at,
         m.created_at
     FROM messages m
-    JOIN users u ON u.id = m.author_id
+    LEFT JOIN users u ON u.id = m.author_id
 "#;
 
 fn row_to_message(row: MessageRow, attachments: Vec<Attachment>) -> Message {
@@ -103,14 +152,33 @@ fn row_to_message(row: MessageRow, attachments: Vec<Attachment>) -> Message {
             id: UserId::from(row.author_id),
             username: row.author_username,
             avatar_url: row.author_avatar_url,
+            bot: row.author_bot,
         },
+        // Calls only happen in DMs.
+        kind: MessageKind::Default,
         content: row.content,
         attachments,
+        embeds: serde_json::from_str::<Vec<Embed>>(&row.embeds).unwrap_or_default(),
+        poll: row
+            .poll
+            .and_then(|poll| serde_json::from_str::<Poll>(&poll).ok())
+            .map(Poll::hide_open_results),
         encrypted: row.encrypted,
         encryption_version: row.encryption_version,
         sender_key_generation: row.sender_key_generation,
         sender_device_id: row.sender_device_id,
         payload_sync_kind: None,
+        call: None,
+        webhook_id: row.webhook_id,
+        reference: match (row.source_guild_id, row.source_channel_id, row.source_message_id) {
+            (Some(guild_id), Some(channel_id), Some(message_id)) => Some(MessageReference {
+                guild_id,
+                channel_id,
+                message_id,
+            }),
+            _ => None,
+        },
+        crossposted: row.crossposted,
         edited_at: row.edited_at,
         created_at: row.created_at,
     }
@@ -136,6 +204,71 @@ async fn fetch_attachments_for_messages(
     .await
 }
 
+async fn insert_attachments(
+    tx: &mut Transaction<'_, Postgres>,
+    message_id: Uuid,
+    attachments: &[AttachmentInput],
+    now: DateTime<Utc>,
+) -> Result<(), CoreError> {
+    for att in attachments {
+        sqlx::query(
+            r#"
+            INSERT INTO attachments
+                (id, message_id, filename, content_type, size_bytes, storage_key, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(att.id.get_uuid())
+        .bind(message_id)
+        .bind(&att.filename)
+        .bind(&att.content_type)
+        .bind(att.size_bytes)
+        .bind(&att.storage_key)
+        .bind(now)
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| {
+            error!("failed to insert attachment: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+    }
+    Ok(())
+}
+
+async fn find_message(pool: &PgPool, id: Uuid) -> Result<Option<Message>, CoreError> {
+    let sql = format!("{} WHERE m.id = $1", SELECT_MESSAGES_SQL);
+    let Some(row) = sqlx::query_as::<_, MessageRow>(&sql)
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(|e| {
+            error!("failed to fetch message: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?
+    else {
+        return Ok(None);
+    };
+
+    let attachment_rows = fetch_attachments_for_messages(pool, &[id]).await.map_err(|e| {
+        error!("failed to fetch attachments: {}", e);
+        CoreError::Unknown { message: e.to_string() }
+    })?;
+
+    let att_list: Vec<Attachment> = attachment_rows
+        .into_iter()
+        .map(|r| r.into_attachment().1)
+        .collect();
+
+    Ok(Some(row_to_message(row, att_list)))
+}
+
+/// Reads a freshly inserted message back with its author and attachments.
+async fn fetch_message(pool: &PgPool, id: Uuid) -> Result<Message, CoreError> {
+    find_message(pool, id).await?.ok_or_else(|| CoreError::Unknown {
+        message: format!("message {} vanished after being written", id),
+    })
+}
+
 // ─── MessagePort impl ─────────────────────────────────────────────────────────
 
 impl MessagePort for PostgresMessageRepository {
@@ -144,11 +277,15 @@ impl MessagePort for PostgresMessageRepository {
         channel_id: &ChannelId,
         author_sub: &str,
         content: String,
+        embeds: Vec<Embed>,
         attachments: Vec<AttachmentInput>,
         encryption: EncryptionMeta,
     ) -> Result<Message, CoreError> {
         let id = Id::new().get_uuid();
         let now = chrono::Utc::now();
+        let embeds = serde_json::to_string(&embeds).map_err(|e| CoreError::Unknown {
+            message: format!("failed to encode embeds: {}", e),
+        })?;
 
         let mut tx = self.pool.begin().await.map_err(|e| {
             error!("failed to begin transaction: {}", e);
@@ -158,8 +295,8 @@ impl MessagePort for PostgresMessageRepository {
         // Resolve oauth_sub → users.id in a single INSERT … SELECT
         let rows_affected = sqlx::query(
             r#"
-            INSERT INTO messages (id, channel_id, author_id, content, encrypted, encryption_version, sender_key_generation, sender_device_id, created_at)
-            SELECT $1, $2, id, $3, $6, $7, $8, $9, $4 FROM users WHERE oauth_sub = $5
+            INSERT INTO messages (id, channel_id, author_id, content, encrypted, encryption_version, sender_key_generation, sender_device_id, embeds, created_at)
+            SELECT $1, $2, id, $3, $6, $7, $8, $9, $10::JSONB, $4 FROM users WHERE oauth_sub = $5
             "#,
         )
         .bind(id)
@@ -171,6 +308,7 @@ impl MessagePort for PostgresMessageRepository {
         .bind(encryption.encryption_version)
         .bind(encryption.sender_key_generation)
         .bind(encryption.sender_device_id)
+        .bind(embeds)
         .execute(&mut *tx)
         .await
         .map_err(|e| {
@@ -184,58 +322,62 @@ impl MessagePort for PostgresMessageRepository {
             });
         }
 
-        // Insert attachments
-        for att in &attachments {
-            sqlx::query(
-                r#"
-                INSERT INTO attachments
-                    (id, message_id, filename, content_type, size_bytes, storage_key, created_at)
-                VALUES ($1, $2, $3, $4, $5, $6, $7)
-                "#,
-            )
-            .bind(att.id.get_uuid())
-            .bind(id)
-            .bind(&att.filename)
-            .bind(&att.content_type)
-            .bind(att.size_bytes)
-            .bind(&att.storage_key)
-            .bind(now)
-            .execute(&mut *tx)
-            .await
-            .map_err(|e| {
-                error!("failed to insert attachment: {}", e);
-                CoreError::Unknown { message: e.to_string() }
-            })?;
-        }
+        insert_attachments(&mut tx, id, &attachments, now).await?;
 
         tx.commit().await.map_err(|e| {
             error!("failed to commit transaction: {}", e);
             CoreError::Unknown { message: e.to_string() }
         })?;
 
-        // Fetch the inserted message back
-        let sql = format!("{} WHERE m.id = $1", SELECT_MESSAGES_SQL);
-        let row = sqlx::query_as::<_, MessageRow>(&sql)
-            .bind(id)
-            .fetch_one(&self.pool)
-            .await
-            .map_err(|e| {
-                error!("failed to fetch inserted message: {}", e);
-                CoreError::Unknown { message: e.to_string() }
-            })?;
+        fetch_message(&self.pool, id).await
+    }
 
-        let attachment_rows =
-            fetch_attachments_for_messages(&self.pool, &[id]).await.map_err(|e| {
-                error!("failed to fetch attachments: {}", e);
-                CoreError::Unknown { message: e.to_string() }
-            })?;
+    async fn insert_webhook_message(
+        &self,
+        channel_id: &ChannelId,
+        input: WebhookMessageInput,
+    ) -> Result<Message, CoreError> {
+        let id = Id::new().get_uuid();
+        let now = chrono::Utc::now();
+        let embeds = serde_json::to_string(&input.embeds).map_err(|e| CoreError::Unknown {
+            message: format!("failed to encode embeds: {}", e),
+        })?;
 
-        let att_list: Vec<Attachment> = attachment_rows
-            .into_iter()
-            .map(|r| r.into_attachment().1)
-            .collect();
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            error!("failed to begin transaction: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO messages
+                (id, channel_id, webhook_id, webhook_username, webhook_avatar_url, content, embeds, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7::JSONB, $8)
+            "#,
+        )
+        .bind(id)
+        .bind(channel_id.get_uuid())
+        .bind(input.webhook_id)
+        .bind(&input.username)
+        .bind(&input.avatar_url)
+        .bind(&input.content)
+        .bind(embeds)
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("failed to insert webhook message: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        insert_attachments(&mut tx, id, &input.attachments, now).await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("failed to commit transaction: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
 
-        Ok(row_to_message(row, att_list))
+        fetch_message(&self.pool, id).await
     }
 
     async fn list_by_channel(
@@ -321,4 +463,221 @@ impl MessagePort for PostgresMessageRepository {
 
         Ok(result.rows_affected() > 0)
     }
+
+    async fn find_by_id(&self, message_id: Uuid) -> Result<Option<Message>, CoreError> {
+        find_message(&self.pool, message_id).await
+    }
+
+    async fn update_content(
+        &self,
+        channel_id: &ChannelId,
+        message_id: Uuid,
+        caller_sub: &str,
+        content: &str,
+    ) -> Result<Option<Message>, CoreError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE messages SET content = $4, edited_at = now()
+            WHERE id = $1 AND channel_id = $2
+              AND author_id = (SELECT id FROM users WHERE oauth_sub = $3)
+            "#,
+        )
+        .bind(message_id)
+        .bind(channel_id.get_uuid())
+        .bind(caller_sub)
+        .bind(content)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("failed to update message: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        find_message(&self.pool, message_id).await
+    }
+
+    async fn set_link_embeds(
+        &self,
+        message_id: Uuid,
+        expected_content: &str,
+        previews: Vec<Embed>,
+    ) -> Result<Option<Message>, CoreError> {
+        let previews = serde_json::to_string(&previews).map_err(|e| CoreError::Unknown {
+            message: format!("failed to encode embeds: {}", e),
+        })?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE messages SET embeds = COALESCE(
+                (SELECT jsonb_agg(e) FROM jsonb_array_elements(embeds) e
+                 WHERE e->>'type' IS DISTINCT FROM 'link'),
+                '[]'::JSONB
+            ) || $3::JSONB
+            WHERE id = $1 AND content = $2
+            "#,
+        )
+        .bind(message_id)
+        .bind(expected_content)
+        .bind(previews)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("failed to set link previews: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        find_message(&self.pool, message_id).await
+    }
+
+    async fn mark_crossposted(&self, message_id: Uuid) -> Result<bool, CoreError> {
+        let result = sqlx::query(
+            "UPDATE messages SET crossposted_at = now() WHERE id = $1 AND crossposted_at IS NULL",
+        )
+        .bind(message_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("failed to mark message as crossposted: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn insert_crosspost(
+        &self,
+        channel_id: &ChannelId,
+        source: &Message,
+        input: CrosspostInput,
+    ) -> Result<Message, CoreError> {
+        let id = Id::new().get_uuid();
+        let now = chrono::Utc::now();
+        let embeds = serde_json::to_string(&source.embeds).map_err(|e| CoreError::Unknown {
+            message: format!("failed to encode embeds: {}", e),
+        })?;
+
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            error!("failed to begin transaction: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO messages
+                (id, channel_id, webhook_username, webhook_avatar_url, content, embeds,
+                 source_guild_id, source_channel_id, source_message_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6::JSONB, $7, $8, $9, $10)
+            "#,
+        )
+        .bind(id)
+        .bind(channel_id.get_uuid())
+        .bind(&input.username)
+        .bind(&input.avatar_url)
+        .bind(&source.content)
+        .bind(embeds)
+        .bind(input.source_guild_id)
+        .bind(source.channel_id.get_uuid())
+        .bind(source.id.get_uuid())
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            error!("failed to insert crosspost: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        // Copies point at the same stored files as the source.
+        let attachments: Vec<AttachmentInput> = source
+            .attachments
+            .iter()
+            .map(|att| AttachmentInput {
+                id: AttachmentId::new(),
+                filename: att.filename.clone(),
+                content_type: att.content_type.clone(),
+                size_bytes: att.size_bytes,
+                storage_key: att.storage_key.clone(),
+            })
+            .collect();
+        insert_attachments(&mut tx, id, &attachments, now).await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("failed to commit transaction: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        fetch_message(&self.pool, id).await
+    }
+
+    async fn update_crossposts(
+        &self,
+        source_message_id: Uuid,
+        content: &str,
+    ) -> Result<Vec<Crosspost>, CoreError> {
+        let updated: Vec<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+            WITH updated AS (
+                UPDATE messages SET content = $2, edited_at = now()
+                WHERE source_message_id = $1
+                RETURNING id, channel_id
+            )
+            SELECT updated.id, c.guild_id
+            FROM updated JOIN channels c ON c.id = updated.channel_id
+            "#,
+        )
+        .bind(source_message_id)
+        .bind(content)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("failed to update crossposts: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        let mut crossposts = Vec::with_capacity(updated.len());
+        for (id, guild_id) in updated {
+            crossposts.push(Crosspost {
+                guild_id,
+                message: fetch_message(&self.pool, id).await?,
+            });
+        }
+        Ok(crossposts)
+    }
+
+    async fn delete_crossposts(
+        &self,
+        source_message_id: Uuid,
+    ) -> Result<Vec<MessageReference>, CoreError> {
+        let deleted: Vec<(Uuid, Uuid, Uuid)> = sqlx::query_as(
+            r#"
+            WITH deleted AS (
+                DELETE FROM messages WHERE source_message_id = $1
+                RETURNING id, channel_id
+            )
+            SELECT c.guild_id, deleted.channel_id, deleted.id
+            FROM deleted JOIN channels c ON c.id = deleted.channel_id
+            "#,
+        )
+        .bind(source_message_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            error!("failed to delete crossposts: {}", e);
+            CoreError::Unknown { message: e.to_string() }
+        })?;
+
+        Ok(deleted
+            .into_iter()
+            .map(|(guild_id, channel_id, message_id)| MessageReference {
+                guild_id,
+                channel_id,
+                message_id,
+            })
+            .collect())
+    }
 }
@@ -1,6 +1,21 @@
+pub mod application;
+pub mod attachment_upload;
 pub mod channel;
+pub mod channel_follow;
+pub mod discord_compat;
+pub mod emoji;
+pub mod event_subscription;
 pub mod guild;
+pub mod guild_import;
+pub mod interaction;
 pub mod invite;
+pub mod link_preview;
 pub mod member;
 pub mod message;
+pub mod poll;
 pub mod role;
+pub mod scheduled_event;
+pub mod scheduled_message;
+pub mod stage;
+pub mod voice;
+pub mod webhook;
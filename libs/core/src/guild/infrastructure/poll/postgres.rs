@@ -0,0 +1,240 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    errors::CoreError,
+    poll::ports::{ClosedPoll, PollInput, PollRepository},
+};
+
+#[derive(Clone)]
+pub struct PostgresPollRepository {
+    pool: PgPool,
+}
+
+impl PostgresPollRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+impl PollRepository for PostgresPollRepository {
+    async fn insert(&self, message_id: Uuid, input: &PollInput) -> Result<(), CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO polls (message_id, question, allow_multiselect, hide_results, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(message_id)
+        .bind(&input.question)
+        .bind(input.allow_multiselect)
+        .bind(input.hide_results)
+        .bind(input.expires_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert poll", e))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO poll_answers (message_id, answer_id, text)
+            SELECT $1, answer.ord::INT, answer.text
+            FROM UNNEST($2::TEXT[]) WITH ORDINALITY AS answer(text, ord)
+            "#,
+        )
+        .bind(message_id)
+        .bind(&input.answers)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert poll answers", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))
+    }
+
+    async fn vote(
+        &self,
+        message_id: Uuid,
+        answer_id: i32,
+        user_id: Uuid,
+        exclusive: bool,
+    ) -> Result<bool, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        // Locking the poll orders votes against its closing: once closed,
+        // no vote can slip in after the counts were frozen.
+        let open = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT message_id FROM polls
+            WHERE message_id = $1 AND closed_at IS NULL AND expires_at > now()
+            FOR SHARE
+            "#,
+        )
+        .bind(message_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to lock poll", e))?;
+        if open.is_none() {
+            return Ok(false);
+        }
+
+        if exclusive {
+            sqlx::query(
+                "DELETE FROM poll_votes WHERE message_id = $1 AND user_id = $2 AND answer_id <> $3",
+            )
+            .bind(message_id)
+            .bind(user_id)
+            .bind(answer_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to replace poll vote", e))?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO poll_votes (message_id, answer_id, user_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(message_id)
+        .bind(answer_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert poll vote", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+        Ok(true)
+    }
+
+    async fn unvote(
+        &self,
+        message_id: Uuid,
+        answer_id: i32,
+        user_id: Uuid,
+    ) -> Result<bool, CoreError> {
+        let result = sqlx::query(
+            r#"
+            DELETE FROM poll_votes v
+            USING polls p
+            WHERE v.message_id = $1 AND v.answer_id = $2 AND v.user_id = $3
+              AND p.message_id = v.message_id
+              AND p.closed_at IS NULL AND p.expires_at > now()
+            "#,
+        )
+        .bind(message_id)
+        .bind(answer_id)
+        .bind(user_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to delete poll vote", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn find_votes(&self, message_id: Uuid, user_id: Uuid) -> Result<Vec<i32>, CoreError> {
+        sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT answer_id FROM poll_votes
+            WHERE message_id = $1 AND user_id = $2
+            ORDER BY answer_id
+            "#,
+        )
+        .bind(message_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list poll votes", e))
+    }
+
+    async fn close_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ClosedPoll>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        let closed = sqlx::query_as::<_, (Uuid, Uuid)>(
+            r#"
+            WITH due AS (
+                SELECT message_id AS due_id
+                FROM polls
+                WHERE closed_at IS NULL AND expires_at <= $1
+                ORDER BY expires_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE polls p
+            SET closed_at = $1,
+                final_voters = (
+                    SELECT COUNT(DISTINCT v.user_id)::INT FROM poll_votes v
+                    WHERE v.message_id = p.message_id
+                )
+            FROM due, messages m, channels c
+            WHERE p.message_id = due.due_id
+              AND m.id = p.message_id
+              AND c.id = m.channel_id
+            RETURNING c.guild_id, p.message_id
+            "#,
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to close polls", e))?;
+
+        let message_ids: Vec<Uuid> = closed.iter().map(|(_, message_id)| *message_id).collect();
+        sqlx::query(
+            r#"
+            UPDATE poll_answers a
+            SET final_votes = (
+                SELECT COUNT(*)::INT FROM poll_votes v
+                WHERE v.message_id = a.message_id AND v.answer_id = a.answer_id
+            )
+            WHERE a.message_id = ANY($1)
+            "#,
+        )
+        .bind(&message_ids)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to freeze poll results", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        Ok(closed
+            .into_iter()
+            .map(|(guild_id, message_id)| ClosedPoll {
+                guild_id,
+                message_id,
+            })
+            .collect())
+    }
+}
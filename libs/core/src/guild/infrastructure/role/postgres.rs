@@ -1,4 +1,4 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use ferriscord_entities::{
     Id,
     guild::GuildId,
@@ -7,9 +7,49 @@ use ferriscord_entities::{
 use ferriscord_pagination::PaginationParams;
 use ferriscord_permission::Permissions;
 use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::guild::domain::{errors::CoreError, role::ports::RoleRepository};
 
+const ROLE_COLUMNS: &str =
+    "id, guild_id, name, position, color, hoist, mentionable, permissions, created_at";
+
+#[derive(sqlx::FromRow)]
+struct RoleRow {
+    id: Uuid,
+    guild_id: Uuid,
+    name: String,
+    position: i32,
+    color: Option<i32>,
+    hoist: bool,
+    mentionable: bool,
+    permissions: i64,
+    created_at: DateTime<Utc>,
+}
+
+impl TryFrom<RoleRow> for Role {
+    type Error = CoreError;
+
+    fn try_from(row: RoleRow) -> Result<Self, Self::Error> {
+        let permissions =
+            Permissions::from_bits(row.permissions as u64).ok_or(CoreError::Unknown {
+                message: "invalid permissions bits".to_string(),
+            })?;
+
+        Ok(Role {
+            id: RoleId(Id(row.id)),
+            guild_id: GuildId(Id(row.guild_id)),
+            name: row.name,
+            position: row.position,
+            color: row.color.unwrap_or(0) as u32,
+            hoist: row.hoist,
+            mentionable: row.mentionable,
+            permissions,
+            created_at: row.created_at,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct PostgresRoleRepository {
     pool: PgPool,
@@ -33,49 +73,34 @@ impl RoleRepository for PostgresRoleRepository {
         let created_at = Utc::now();
         let position = 0i32;
 
-        let row = sqlx::query!(
+        let row = sqlx::query_as::<_, RoleRow>(&format!(
             r#"
             INSERT INTO roles (id, guild_id, name, position, color, permissions, created_at)
             VALUES ($1, $2, $3, $4, $5, $6, $7)
-            RETURNING id, guild_id, name, position, color, permissions, created_at
-            "#,
-            role_id.0.get_uuid(),
-            guild_id.get_uuid(),
-            name,
-            position,
-            color as i32,
-            permissions as i64,
-            created_at,
-        )
+            RETURNING {ROLE_COLUMNS}
+            "#
+        ))
+        .bind(role_id.0.get_uuid())
+        .bind(guild_id.get_uuid())
+        .bind(name)
+        .bind(position)
+        .bind(color as i32)
+        .bind(permissions as i64)
+        .bind(created_at)
         .fetch_one(&self.pool)
         .await
         .map_err(|e| CoreError::Unknown {
             message: format!("failed to insert role: {:?}", e),
         })?;
 
-        let permissions =
-            Permissions::from_bits(row.permissions as u64).ok_or(CoreError::Unknown {
-                message: "invalid permissions bits".to_string(),
-            })?;
-
-        Ok(Role {
-            id: RoleId(Id(row.id)),
-            guild_id: GuildId(Id(row.guild_id)),
-            name: row.name,
-            position: row.position,
-            color: row.color.unwrap_or(0) as u32,
-            hoist: false,
-            mentionable: false,
-            permissions,
-            created_at: row.created_at,
-        })
+        row.try_into()
     }
 
     async fn find_by_id(&self, id: RoleId) -> Result<Role, CoreError> {
-        let row = sqlx::query!(
-            "SELECT id, guild_id, name, position, color, permissions, created_at FROM roles WHERE id = $1",
-            id.0.get_uuid()
-        )
+        let row = sqlx::query_as::<_, RoleRow>(&format!(
+            "SELECT {ROLE_COLUMNS} FROM roles WHERE id = $1"
+        ))
+        .bind(id.0.get_uuid())
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| CoreError::Unknown {
@@ -83,24 +108,7 @@ impl RoleRepository for PostgresRoleRepository {
         })?;
 
         match row {
-            Some(row) => {
-                let permissions =
-                    Permissions::from_bits(row.permissions as u64).ok_or(CoreError::Unknown {
-                        message: "invalid permissions bits".to_string(),
-                    })?;
-
-                Ok(Role {
-                    id: RoleId(Id(row.id)),
-                    guild_id: GuildId(Id(row.guild_id)),
-                    name: row.name,
-                    position: row.position,
-                    color: row.color.unwrap_or(0) as u32,
-                    hoist: false,
-                    mentionable: false,
-                    permissions,
-                    created_at: row.created_at,
-                })
-            }
+            Some(row) => row.try_into(),
             None => Err(CoreError::Unknown {
                 message: format!("role with id {} not found", id),
             }),
@@ -131,20 +139,22 @@ impl RoleRepository for PostgresRoleRepository {
         name: &str,
         color: u32,
         permissions: u64,
+        hoist: Option<bool>,
     ) -> Result<Role, CoreError> {
-        let row = sqlx::query!(
+        let row = sqlx::query_as::<_, RoleRow>(&format!(
             r#"
             UPDATE roles
-            SET name = $3, color = $4, permissions = $5
+            SET name = $3, color = $4, permissions = $5, hoist = COALESCE($6, hoist)
             WHERE id = $1 AND guild_id = $2
-            RETURNING id, guild_id, name, position, color, permissions, created_at
-            "#,
-            id.0.get_uuid(),
-            guild_id.get_uuid(),
-            name,
-            color as i32,
-            permissions as i64,
-        )
+            RETURNING {ROLE_COLUMNS}
+            "#
+        ))
+        .bind(id.0.get_uuid())
+        .bind(guild_id.get_uuid())
+        .bind(name)
+        .bind(color as i32)
+        .bind(permissions as i64)
+        .bind(hoist)
         .fetch_optional(&self.pool)
         .await
         .map_err(|e| CoreError::Unknown {
@@ -157,22 +167,7 @@ impl RoleRepository for PostgresRoleRepository {
             });
         };
 
-        let permissions =
-            Permissions::from_bits(row.permissions as u64).ok_or(CoreError::Unknown {
-                message: "invalid permissions bits".to_string(),
-            })?;
-
-        Ok(Role {
-            id: RoleId(Id(row.id)),
-            guild_id: GuildId(Id(row.guild_id)),
-            name: row.name,
-            position: row.position,
-            color: row.color.unwrap_or(0) as u32,
-            hoist: false,
-            mentionable: false,
-            permissions,
-            created_at: row.created_at,
-        })
+        row.try_into()
     }
 
     async fn find_by_guild_id(
@@ -199,45 +194,25 @@ impl RoleRepository for PostgresRoleRepository {
         let offset = ((params.page.max(1) - 1) * params.per_page) as i64;
         let limit = params.per_page as i64;
 
-        let rows = sqlx::query!(
+        let rows = sqlx::query_as::<_, RoleRow>(&format!(
             r#"
-            SELECT id, guild_id, name, position, color, permissions, created_at
+            SELECT {ROLE_COLUMNS}
             FROM roles
             WHERE guild_id = $1
             ORDER BY position ASC
             LIMIT $2 OFFSET $3
-            "#,
-            guild_id.get_uuid(),
-            limit,
-            offset,
-        )
+            "#
+        ))
+        .bind(guild_id.get_uuid())
+        .bind(limit)
+        .bind(offset)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| CoreError::Unknown {
             message: format!("failed to query roles by guild id: {:?}", e),
         })?;
 
-        let roles: Result<Vec<Role>, CoreError> = rows
-            .into_iter()
-            .map(|row| {
-                let permissions =
-                    Permissions::from_bits(row.permissions as u64).ok_or(CoreError::Unknown {
-                        message: "invalid permissions bits".to_string(),
-                    })?;
-
-                Ok(Role {
-                    id: RoleId(Id(row.id)),
-                    guild_id: GuildId(Id(row.guild_id)),
-                    name: row.name,
-                    position: row.position,
-                    color: row.color.unwrap_or(0) as u32,
-                    hoist: false,
-                    mentionable: false,
-                    permissions,
-                    created_at: row.created_at,
-                })
-            })
-            .collect();
+        let roles: Result<Vec<Role>, CoreError> = rows.into_iter().map(Role::try_from).collect();
 
         Ok((roles?, total))
     }
@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::scheduled_event::{
+    EventRsvp, GuildScheduledEvent, RsvpStatus, ScheduledEventLocation, ScheduledEventStatus,
+};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    errors::CoreError,
+    scheduled_event::ports::{CalendarFeed, EventReminder, ScheduledEventRepository},
+};
+
+#[derive(Clone)]
+pub struct PostgresScheduledEventRepository {
+    pool: PgPool,
+}
+
+impl PostgresScheduledEventRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+/// Columns of an event aliased `e`, with its RSVP counts.
+const EVENT_COLUMNS: &str = r#"e.id, e.guild_id, e.creator_id, e.title, e.description, e.starts_at, e.ends_at, e.channel_id, e.location, e.status, e.created_at,
+    (SELECT COUNT(*) FROM guild_scheduled_event_rsvps r WHERE r.event_id = e.id AND r.status = 'interested') AS interested_count,
+    (SELECT COUNT(*) FROM guild_scheduled_event_rsvps r WHERE r.event_id = e.id AND r.status = 'going') AS going_count"#;
+
+/// The answer of the user bound at `$param`, if any.
+fn my_rsvp_column(param: usize) -> String {
+    format!(
+        "(SELECT r.status FROM guild_scheduled_event_rsvps r WHERE r.event_id = e.id AND r.user_id = ${param}) AS my_rsvp"
+    )
+}
+
+const NO_RSVP_COLUMN: &str = "NULL::TEXT AS my_rsvp";
+
+#[derive(sqlx::FromRow)]
+struct EventRow {
+    id: Uuid,
+    guild_id: Uuid,
+    creator_id: Option<Uuid>,
+    title: String,
+    description: String,
+    starts_at: DateTime<Utc>,
+    ends_at: DateTime<Utc>,
+    channel_id: Option<Uuid>,
+    location: Option<String>,
+    status: String,
+    created_at: DateTime<Utc>,
+    interested_count: i64,
+    going_count: i64,
+    my_rsvp: Option<String>,
+}
+
+impl From<EventRow> for GuildScheduledEvent {
+    fn from(row: EventRow) -> Self {
+        let location = match row.channel_id {
+            Some(channel_id) => ScheduledEventLocation::Channel { channel_id },
+            None => ScheduledEventLocation::External {
+                location: row.location.unwrap_or_default(),
+            },
+        };
+        GuildScheduledEvent {
+            id: row.id,
+            guild_id: row.guild_id,
+            creator_id: row.creator_id,
+            title: row.title,
+            description: row.description,
+            starts_at: row.starts_at,
+            ends_at: row.ends_at,
+            location,
+            status: ScheduledEventStatus::try_from(row.status.as_str())
+                .unwrap_or(ScheduledEventStatus::Canceled),
+            interested_count: row.interested_count,
+            going_count: row.going_count,
+            my_rsvp: row
+                .my_rsvp
+                .and_then(|status| RsvpStatus::try_from(status.as_str()).ok()),
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct RsvpRow {
+    user_id: Uuid,
+    status: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct FeedRow {
+    guild_id: Uuid,
+    user_id: Uuid,
+    identity: String,
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+fn encode_identity(identity: &Identity) -> Result<String, CoreError> {
+    serde_json::to_string(identity).map_err(|e| CoreError::Unknown {
+        message: format!("failed to encode identity: {}", e),
+    })
+}
+
+// ─── ScheduledEventRepository impl ────────────────────────────────────────────
+
+impl ScheduledEventRepository for PostgresScheduledEventRepository {
+    async fn insert(&self, event: &GuildScheduledEvent) -> Result<GuildScheduledEvent, CoreError> {
+        let location = match &event.location {
+            ScheduledEventLocation::External { location } => Some(location.as_str()),
+            ScheduledEventLocation::Channel { .. } => None,
+        };
+        let row = sqlx::query_as::<_, EventRow>(&format!(
+            r#"
+            INSERT INTO guild_scheduled_events AS e
+                (id, guild_id, creator_id, title, description, starts_at, ends_at, channel_id, location, status, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING {EVENT_COLUMNS}, {NO_RSVP_COLUMN}
+            "#
+        ))
+        .bind(event.id)
+        .bind(event.guild_id)
+        .bind(event.creator_id)
+        .bind(&event.title)
+        .bind(&event.description)
+        .bind(event.starts_at)
+        .bind(event.ends_at)
+        .bind(event.channel_id())
+        .bind(location)
+        .bind(event.status.as_str())
+        .bind(event.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to insert scheduled event", e))?;
+
+        Ok(row.into())
+    }
+
+    async fn find_by_id(
+        &self,
+        event_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Option<GuildScheduledEvent>, CoreError> {
+        let row = sqlx::query_as::<_, EventRow>(&format!(
+            "SELECT {EVENT_COLUMNS}, {} FROM guild_scheduled_events e WHERE e.id = $1",
+            my_rsvp_column(2)
+        ))
+        .bind(event_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find scheduled event", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_open(
+        &self,
+        guild_id: Uuid,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<GuildScheduledEvent>, CoreError> {
+        let rows = sqlx::query_as::<_, EventRow>(&format!(
+            r#"
+            SELECT {EVENT_COLUMNS}, {}
+            FROM guild_scheduled_events e
+            WHERE e.guild_id = $1 AND e.status IN ('scheduled', 'active')
+            ORDER BY e.starts_at, e.id
+            "#,
+            my_rsvp_column(2)
+        ))
+        .bind(guild_id)
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list scheduled events", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn list_since(
+        &self,
+        guild_id: Uuid,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<GuildScheduledEvent>, CoreError> {
+        let rows = sqlx::query_as::<_, EventRow>(&format!(
+            r#"
+            SELECT {EVENT_COLUMNS}, {NO_RSVP_COLUMN}
+            FROM guild_scheduled_events e
+            WHERE e.guild_id = $1 AND e.ends_at >= $2
+            ORDER BY e.starts_at, e.id
+            "#
+        ))
+        .bind(guild_id)
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list scheduled events", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn count_open(&self, guild_id: Uuid) -> Result<i64, CoreError> {
+        sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM guild_scheduled_events
+            WHERE guild_id = $1 AND status IN ('scheduled', 'active')
+            "#,
+        )
+        .bind(guild_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to count scheduled events", e))
+    }
+
+    async fn cancel(&self, event_id: Uuid) -> Result<Option<GuildScheduledEvent>, CoreError> {
+        let row = sqlx::query_as::<_, EventRow>(&format!(
+            r#"
+            UPDATE guild_scheduled_events e
+            SET status = 'canceled'
+            WHERE e.id = $1 AND e.status IN ('scheduled', 'active')
+            RETURNING {EVENT_COLUMNS}, {NO_RSVP_COLUMN}
+            "#
+        ))
+        .bind(event_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to cancel scheduled event", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn set_rsvp(
+        &self,
+        event_id: Uuid,
+        user_id: Uuid,
+        status: RsvpStatus,
+    ) -> Result<bool, CoreError> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO guild_scheduled_event_rsvps (event_id, user_id, status)
+            SELECT id, $2, $3 FROM guild_scheduled_events
+            WHERE id = $1 AND status IN ('scheduled', 'active')
+            ON CONFLICT (event_id, user_id) DO UPDATE SET status = EXCLUDED.status
+            "#,
+        )
+        .bind(event_id)
+        .bind(user_id)
+        .bind(status.as_str())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to save rsvp", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn remove_rsvp(&self, event_id: Uuid, user_id: Uuid) -> Result<(), CoreError> {
+        sqlx::query("DELETE FROM guild_scheduled_event_rsvps WHERE event_id = $1 AND user_id = $2")
+            .bind(event_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete rsvp", e))?;
+        Ok(())
+    }
+
+    async fn list_rsvps(&self, event_id: Uuid, limit: i64) -> Result<Vec<EventRsvp>, CoreError> {
+        let rows = sqlx::query_as::<_, RsvpRow>(
+            r#"
+            SELECT user_id, status, created_at
+            FROM guild_scheduled_event_rsvps
+            WHERE event_id = $1
+            ORDER BY created_at, user_id
+            LIMIT $2
+            "#,
+        )
+        .bind(event_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list rsvps", e))?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(EventRsvp {
+                    user_id: row.user_id,
+                    status: RsvpStatus::try_from(row.status.as_str()).ok()?,
+                    created_at: row.created_at,
+                })
+            })
+            .collect())
+    }
+
+    async fn claim_reminders(
+        &self,
+        until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<EventReminder>, CoreError> {
+        let rows = sqlx::query_as::<_, EventRow>(&format!(
+            r#"
+            WITH due AS (
+                SELECT id AS due_id
+                FROM guild_scheduled_events
+                WHERE status = 'scheduled' AND reminded_at IS NULL AND starts_at <= $1
+                ORDER BY starts_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE guild_scheduled_events e
+            SET reminded_at = now()
+            FROM due
+            WHERE e.id = due.due_id
+            RETURNING {EVENT_COLUMNS}, {NO_RSVP_COLUMN}
+            "#
+        ))
+        .bind(until)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to claim event reminders", e))?;
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let event_ids: Vec<Uuid> = rows.iter().map(|row| row.id).collect();
+        let rsvps = sqlx::query_as::<_, (Uuid, Uuid)>(
+            "SELECT event_id, user_id FROM guild_scheduled_event_rsvps WHERE event_id = ANY($1)",
+        )
+        .bind(&event_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list rsvps", e))?;
+
+        let mut user_ids: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for (event_id, user_id) in rsvps {
+            user_ids.entry(event_id).or_default().push(user_id);
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|row| EventReminder {
+                user_ids: user_ids.remove(&row.id).unwrap_or_default(),
+                event: row.into(),
+            })
+            .collect())
+    }
+
+    async fn start_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<GuildScheduledEvent>, CoreError> {
+        let rows = sqlx::query_as::<_, EventRow>(&format!(
+            r#"
+            WITH due AS (
+                SELECT id AS due_id
+                FROM guild_scheduled_events
+                WHERE status = 'scheduled' AND starts_at <= $1
+                ORDER BY starts_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE guild_scheduled_events e
+            SET status = 'active'
+            FROM due
+            WHERE e.id = due.due_id
+            RETURNING {EVENT_COLUMNS}, {NO_RSVP_COLUMN}
+            "#
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to start scheduled events", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn complete_due(
+        &self,
+        now: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<GuildScheduledEvent>, CoreError> {
+        let rows = sqlx::query_as::<_, EventRow>(&format!(
+            r#"
+            WITH due AS (
+                SELECT id AS due_id
+                FROM guild_scheduled_events
+                WHERE status IN ('scheduled', 'active') AND ends_at <= $1
+                ORDER BY ends_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE guild_scheduled_events e
+            SET status = 'completed'
+            FROM due
+            WHERE e.id = due.due_id
+            RETURNING {EVENT_COLUMNS}, {NO_RSVP_COLUMN}
+            "#
+        ))
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to complete scheduled events", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn upsert_calendar_feed(
+        &self,
+        guild_id: Uuid,
+        user_id: Uuid,
+        token_hash: &str,
+        identity: &Identity,
+    ) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"
+            INSERT INTO guild_calendar_feeds (guild_id, user_id, token_hash, identity)
+            VALUES ($1, $2, $3, $4::JSONB)
+            ON CONFLICT (guild_id, user_id) DO UPDATE
+            SET token_hash = EXCLUDED.token_hash, identity = EXCLUDED.identity, created_at = now()
+            "#,
+        )
+        .bind(guild_id)
+        .bind(user_id)
+        .bind(token_hash)
+        .bind(encode_identity(identity)?)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to save calendar feed", e))?;
+        Ok(())
+    }
+
+    async fn find_calendar_feed(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<CalendarFeed>, CoreError> {
+        let row = sqlx::query_as::<_, FeedRow>(
+            r#"
+            SELECT guild_id, user_id, identity::TEXT AS identity
+            FROM guild_calendar_feeds
+            WHERE token_hash = $1
+            "#,
+        )
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find calendar feed", e))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        match serde_json::from_str::<Identity>(&row.identity) {
+            Ok(identity) => Ok(Some(CalendarFeed {
+                guild_id: row.guild_id,
+                user_id: row.user_id,
+                identity,
+            })),
+            Err(e) => {
+                error!("unreadable identity of calendar feed: {}", e);
+                Ok(None)
+            }
+        }
+    }
+}
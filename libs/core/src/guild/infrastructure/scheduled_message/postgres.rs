@@ -0,0 +1,337 @@
+use chrono::{DateTime, Utc};
+use ferriscord_auth::Identity;
+use ferriscord_entities::scheduled_message::{
+    ScheduledMessage, ScheduledMessageKind, ScheduledMessageStatus,
+};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    errors::CoreError,
+    scheduled_message::ports::{ScheduledJob, ScheduledMessageRepository},
+};
+
+#[derive(Clone)]
+pub struct PostgresScheduledMessageRepository {
+    pool: PgPool,
+}
+
+impl PostgresScheduledMessageRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const SCHEDULED_COLUMNS: &str = "id, kind, user_id, guild_id, channel_id, message_id, content, send_at, status, failure_reason, created_at, delivered_at";
+
+#[derive(sqlx::FromRow)]
+struct ScheduledRow {
+    id: Uuid,
+    kind: String,
+    user_id: Uuid,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    message_id: Option<Uuid>,
+    content: String,
+    send_at: DateTime<Utc>,
+    status: String,
+    failure_reason: Option<String>,
+    created_at: DateTime<Utc>,
+    delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<ScheduledRow> for ScheduledMessage {
+    fn from(row: ScheduledRow) -> Self {
+        ScheduledMessage {
+            id: row.id,
+            kind: ScheduledMessageKind::try_from(row.kind.as_str())
+                .unwrap_or(ScheduledMessageKind::Reminder),
+            user_id: row.user_id,
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            message_id: row.message_id,
+            content: row.content,
+            send_at: row.send_at,
+            status: ScheduledMessageStatus::try_from(row.status.as_str())
+                .unwrap_or(ScheduledMessageStatus::Failed),
+            failure_reason: row.failure_reason,
+            created_at: row.created_at,
+            delivered_at: row.delivered_at,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct JobRow {
+    #[sqlx(flatten)]
+    scheduled: ScheduledRow,
+    identity: String,
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+fn encode_identity(identity: &Identity) -> Result<String, CoreError> {
+    serde_json::to_string(identity).map_err(|e| CoreError::Unknown {
+        message: format!("failed to encode identity: {}", e),
+    })
+}
+
+// ─── ScheduledMessageRepository impl ──────────────────────────────────────────
+
+impl ScheduledMessageRepository for PostgresScheduledMessageRepository {
+    async fn insert(
+        &self,
+        scheduled: &ScheduledMessage,
+        identity: &Identity,
+    ) -> Result<ScheduledMessage, CoreError> {
+        let row = sqlx::query_as::<_, ScheduledRow>(&format!(
+            r#"
+            INSERT INTO scheduled_messages
+                (id, kind, user_id, identity, guild_id, channel_id, message_id, content, send_at, status, created_at)
+            VALUES ($1, $2, $3, $4::JSONB, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING {SCHEDULED_COLUMNS}
+            "#
+        ))
+        .bind(scheduled.id)
+        .bind(scheduled.kind.as_str())
+        .bind(scheduled.user_id)
+        .bind(encode_identity(identity)?)
+        .bind(scheduled.guild_id)
+        .bind(scheduled.channel_id)
+        .bind(scheduled.message_id)
+        .bind(&scheduled.content)
+        .bind(scheduled.send_at)
+        .bind(scheduled.status.as_str())
+        .bind(scheduled.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to insert scheduled message", e))?;
+
+        Ok(row.into())
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<ScheduledMessage>, CoreError> {
+        let row = sqlx::query_as::<_, ScheduledRow>(&format!(
+            "SELECT {SCHEDULED_COLUMNS} FROM scheduled_messages WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find scheduled message", e))?;
+
+        Ok(row.map(ScheduledMessage::from))
+    }
+
+    async fn list_by_user(&self, user_id: Uuid) -> Result<Vec<ScheduledMessage>, CoreError> {
+        let rows = sqlx::query_as::<_, ScheduledRow>(&format!(
+            "SELECT {SCHEDULED_COLUMNS} FROM scheduled_messages WHERE user_id = $1 ORDER BY send_at"
+        ))
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list scheduled messages", e))?;
+
+        Ok(rows.into_iter().map(ScheduledMessage::from).collect())
+    }
+
+    async fn count_pending_by_user(&self, user_id: Uuid) -> Result<i64, CoreError> {
+        sqlx::query_scalar(
+            "SELECT COUNT(*) FROM scheduled_messages WHERE user_id = $1 AND status = 'pending'",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to count scheduled messages", e))
+    }
+
+    async fn update_pending(
+        &self,
+        id: Uuid,
+        content: &str,
+        send_at: DateTime<Utc>,
+        identity: &Identity,
+    ) -> Result<Option<ScheduledMessage>, CoreError> {
+        // A row being delivered keeps its lease; the new time only applies
+        // if that delivery does not happen.
+        let row = sqlx::query_as::<_, ScheduledRow>(&format!(
+            r#"
+            UPDATE scheduled_messages
+            SET content = $2, send_at = $3, identity = $4::JSONB
+            WHERE id = $1 AND status = 'pending'
+            RETURNING {SCHEDULED_COLUMNS}
+            "#
+        ))
+        .bind(id)
+        .bind(content)
+        .bind(send_at)
+        .bind(encode_identity(identity)?)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to update scheduled message", e))?;
+
+        Ok(row.map(ScheduledMessage::from))
+    }
+
+    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<bool, CoreError> {
+        let result = sqlx::query("DELETE FROM scheduled_messages WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete scheduled message", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn claim_due(
+        &self,
+        now: DateTime<Utc>,
+        lease_until: DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<ScheduledJob>, CoreError> {
+        let rows = sqlx::query_as::<_, JobRow>(&format!(
+            r#"
+            WITH due AS (
+                SELECT id AS due_id
+                FROM scheduled_messages
+                WHERE status = 'pending' AND send_at <= $1
+                  AND (lease_until IS NULL OR lease_until <= $1)
+                ORDER BY send_at
+                LIMIT $3
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE scheduled_messages
+            SET lease_until = $2
+            FROM due
+            WHERE id = due.due_id
+            RETURNING {SCHEDULED_COLUMNS}, identity::TEXT AS identity
+            "#
+        ))
+        .bind(now)
+        .bind(lease_until)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to claim scheduled messages", e))?;
+
+        let mut jobs = Vec::with_capacity(rows.len());
+        for row in rows {
+            match serde_json::from_str::<Identity>(&row.identity) {
+                Ok(identity) => jobs.push(ScheduledJob {
+                    scheduled: row.scheduled.into(),
+                    identity,
+                }),
+                Err(e) => {
+                    error!(
+                        "unreadable identity of scheduled message {}: {}",
+                        row.scheduled.id, e
+                    );
+                    self.mark_failed(row.scheduled.id, "the author could not be verified")
+                        .await?;
+                }
+            }
+        }
+        Ok(jobs)
+    }
+
+    async fn post(&self, id: Uuid) -> Result<Option<Uuid>, CoreError> {
+        let message_id = Uuid::now_v7();
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin transaction", e))?;
+
+        // Flipping the status first locks the row: a racing replica waits
+        // here, then finds it delivered and posts nothing.
+        let due = sqlx::query_as::<_, (Uuid, Uuid, String)>(
+            r#"
+            UPDATE scheduled_messages
+            SET status = 'delivered', delivered_at = now(), message_id = $2, lease_until = NULL
+            WHERE id = $1 AND status = 'pending'
+            RETURNING channel_id, user_id, content
+            "#,
+        )
+        .bind(id)
+        .bind(message_id)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to mark scheduled message delivered", e))?;
+        let Some((channel_id, author_id, content)) = due else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO messages (id, channel_id, author_id, content, created_at)
+            VALUES ($1, $2, $3, $4, now())
+            "#,
+        )
+        .bind(message_id)
+        .bind(channel_id)
+        .bind(author_id)
+        .bind(content)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to post scheduled message", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit transaction", e))?;
+
+        Ok(Some(message_id))
+    }
+
+    async fn mark_delivered(&self, id: Uuid) -> Result<bool, CoreError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE scheduled_messages
+            SET status = 'delivered', delivered_at = now(), lease_until = NULL
+            WHERE id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to mark scheduled message delivered", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn mark_failed(&self, id: Uuid, reason: &str) -> Result<bool, CoreError> {
+        let result = sqlx::query(
+            r#"
+            UPDATE scheduled_messages
+            SET status = 'failed', failure_reason = $2, lease_until = NULL
+            WHERE id = $1 AND status = 'pending'
+            "#,
+        )
+        .bind(id)
+        .bind(reason)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to mark scheduled message failed", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    async fn prune(&self, before: DateTime<Utc>) -> Result<u64, CoreError> {
+        let result = sqlx::query(
+            "DELETE FROM scheduled_messages WHERE status <> 'pending' AND send_at < $1",
+        )
+        .bind(before)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to prune scheduled messages", e))?;
+
+        Ok(result.rows_affected())
+    }
+}
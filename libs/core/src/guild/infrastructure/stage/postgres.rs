@@ -0,0 +1,159 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::stage_instance::StageInstance;
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{errors::CoreError, stage::ports::StageInstanceRepository};
+
+#[derive(Clone)]
+pub struct PostgresStageInstanceRepository {
+    pool: PgPool,
+}
+
+impl PostgresStageInstanceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const STAGE_INSTANCE_COLUMNS: &str =
+    "id, guild_id, channel_id, topic, started_by, started_at, ended_at";
+
+#[derive(sqlx::FromRow)]
+struct StageInstanceRow {
+    id: Uuid,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    topic: String,
+    started_by: Uuid,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+}
+
+impl From<StageInstanceRow> for StageInstance {
+    fn from(row: StageInstanceRow) -> Self {
+        StageInstance {
+            id: row.id,
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            topic: row.topic,
+            started_by: row.started_by,
+            started_at: row.started_at,
+            ended_at: row.ended_at,
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown { message: format!("{}: {}", context, e) }
+}
+
+// ─── StageInstanceRepository impl ─────────────────────────────────────────────
+
+impl StageInstanceRepository for PostgresStageInstanceRepository {
+    async fn insert(&self, instance: &StageInstance) -> Result<Option<StageInstance>, CoreError> {
+        let row = sqlx::query_as::<_, StageInstanceRow>(&format!(
+            r#"
+            INSERT INTO stage_instances
+                (id, guild_id, channel_id, topic, started_by, started_at, ended_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (channel_id) WHERE ended_at IS NULL DO NOTHING
+            RETURNING {STAGE_INSTANCE_COLUMNS}
+            "#
+        ))
+        .bind(instance.id)
+        .bind(instance.guild_id)
+        .bind(instance.channel_id)
+        .bind(&instance.topic)
+        .bind(instance.started_by)
+        .bind(instance.started_at)
+        .bind(instance.ended_at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to insert stage instance", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_live(&self, channel_id: Uuid) -> Result<Option<StageInstance>, CoreError> {
+        let row = sqlx::query_as::<_, StageInstanceRow>(&format!(
+            "SELECT {STAGE_INSTANCE_COLUMNS} FROM stage_instances WHERE channel_id = $1 AND ended_at IS NULL"
+        ))
+        .bind(channel_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find stage instance", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn update_topic(
+        &self,
+        channel_id: Uuid,
+        topic: &str,
+    ) -> Result<Option<StageInstance>, CoreError> {
+        let row = sqlx::query_as::<_, StageInstanceRow>(&format!(
+            r#"
+            UPDATE stage_instances
+            SET topic = $2
+            WHERE channel_id = $1 AND ended_at IS NULL
+            RETURNING {STAGE_INSTANCE_COLUMNS}
+            "#
+        ))
+        .bind(channel_id)
+        .bind(topic)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to update stage instance", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn end(
+        &self,
+        channel_id: Uuid,
+        ended_at: DateTime<Utc>,
+    ) -> Result<Option<StageInstance>, CoreError> {
+        let row = sqlx::query_as::<_, StageInstanceRow>(&format!(
+            r#"
+            UPDATE stage_instances
+            SET ended_at = $2
+            WHERE channel_id = $1 AND ended_at IS NULL
+            RETURNING {STAGE_INSTANCE_COLUMNS}
+            "#
+        ))
+        .bind(channel_id)
+        .bind(ended_at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to end stage instance", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_by_channel(
+        &self,
+        channel_id: Uuid,
+        limit: i64,
+    ) -> Result<Vec<StageInstance>, CoreError> {
+        let rows = sqlx::query_as::<_, StageInstanceRow>(&format!(
+            r#"
+            SELECT {STAGE_INSTANCE_COLUMNS} FROM stage_instances
+            WHERE channel_id = $1
+            ORDER BY started_at DESC
+            LIMIT $2
+            "#
+        ))
+        .bind(channel_id)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list stage instances", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
@@ -0,0 +1,343 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::{guild::GuildId, voice_state::VoiceState};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{
+    errors::CoreError,
+    voice::ports::{VoiceModeration, VoiceStateRepository},
+};
+
+#[derive(Clone)]
+pub struct PostgresVoiceStateRepository {
+    pool: PgPool,
+}
+
+impl PostgresVoiceStateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const VOICE_STATE_COLUMNS: &str = "user_id, guild_id, channel_id, session_id, self_mute, self_deaf, \
+     mute, deaf, priority_speaker, suppress, request_to_speak_at, joined_at";
+
+#[derive(sqlx::FromRow)]
+struct VoiceStateRow {
+    user_id: Uuid,
+    guild_id: Option<Uuid>,
+    channel_id: Uuid,
+    session_id: Uuid,
+    self_mute: bool,
+    self_deaf: bool,
+    mute: bool,
+    deaf: bool,
+    priority_speaker: bool,
+    suppress: bool,
+    request_to_speak_at: Option<DateTime<Utc>>,
+    joined_at: DateTime<Utc>,
+}
+
+impl From<VoiceStateRow> for VoiceState {
+    fn from(row: VoiceStateRow) -> Self {
+        VoiceState {
+            user_id: row.user_id,
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            session_id: row.session_id,
+            self_mute: row.self_mute,
+            self_deaf: row.self_deaf,
+            mute: row.mute,
+            deaf: row.deaf,
+            priority_speaker: row.priority_speaker,
+            suppress: row.suppress,
+            request_to_speak_at: row.request_to_speak_at,
+            joined_at: row.joined_at,
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown { message: format!("{}: {}", context, e) }
+}
+
+// ─── VoiceStateRepository impl ────────────────────────────────────────────────
+
+impl VoiceStateRepository for PostgresVoiceStateRepository {
+    async fn upsert(
+        &self,
+        state: &VoiceState,
+        user_limit: Option<u32>,
+    ) -> Result<Option<VoiceState>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin voice state transaction", e))?;
+
+        // Serialise joins of the same channel so the user limit holds.
+        sqlx::query("SELECT 1 FROM channels WHERE id = $1 FOR UPDATE")
+            .bind(state.channel_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to lock voice channel", e))?;
+
+        if let Some(limit) = user_limit {
+            let (count,): (i64,) = sqlx::query_as(
+                "SELECT COUNT(*) FROM voice_states WHERE channel_id = $1 AND user_id <> $2",
+            )
+            .bind(state.channel_id)
+            .bind(state.user_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to count voice channel users", e))?;
+
+            if count >= i64::from(limit) {
+                return Ok(None);
+            }
+        }
+
+        let row = sqlx::query_as::<_, VoiceStateRow>(&format!(
+            r#"
+            INSERT INTO voice_states
+                (user_id, guild_id, channel_id, session_id, self_mute, self_deaf,
+                 mute, deaf, priority_speaker, suppress, request_to_speak_at, joined_at)
+            SELECT $1, $2, $3, $4, $5, $6,
+                   COALESCE(m.mute, FALSE), COALESCE(m.deaf, FALSE), $7, $8, $9, $10
+            FROM (SELECT 1) AS one
+            LEFT JOIN members m ON m.guild_id = $2 AND m.user_id = $1
+            ON CONFLICT (user_id) DO UPDATE SET
+                guild_id   = EXCLUDED.guild_id,
+                channel_id = EXCLUDED.channel_id,
+                session_id = EXCLUDED.session_id,
+                self_mute  = EXCLUDED.self_mute,
+                self_deaf  = EXCLUDED.self_deaf,
+                mute       = EXCLUDED.mute,
+                deaf       = EXCLUDED.deaf,
+                priority_speaker = EXCLUDED.priority_speaker,
+                suppress   = CASE
+                    WHEN voice_states.channel_id = EXCLUDED.channel_id
+                    THEN voice_states.suppress
+                    ELSE EXCLUDED.suppress
+                END,
+                request_to_speak_at = CASE
+                    WHEN voice_states.channel_id = EXCLUDED.channel_id
+                    THEN voice_states.request_to_speak_at
+                    ELSE EXCLUDED.request_to_speak_at
+                END,
+                joined_at  = CASE
+                    WHEN voice_states.channel_id = EXCLUDED.channel_id
+                    THEN voice_states.joined_at
+                    ELSE EXCLUDED.joined_at
+                END
+            RETURNING {VOICE_STATE_COLUMNS}
+            "#
+        ))
+        .bind(state.user_id)
+        .bind(state.guild_id)
+        .bind(state.channel_id)
+        .bind(state.session_id)
+        .bind(state.self_mute)
+        .bind(state.self_deaf)
+        .bind(state.priority_speaker)
+        .bind(state.suppress)
+        .bind(state.request_to_speak_at)
+        .bind(state.joined_at)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to upsert voice state", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit voice state", e))?;
+
+        Ok(Some(row.into()))
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Option<VoiceState>, CoreError> {
+        let row = sqlx::query_as::<_, VoiceStateRow>(&format!(
+            "SELECT {VOICE_STATE_COLUMNS} FROM voice_states WHERE user_id = $1"
+        ))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find voice state", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_by_guild(&self, guild_id: &GuildId) -> Result<Vec<VoiceState>, CoreError> {
+        let rows = sqlx::query_as::<_, VoiceStateRow>(&format!(
+            "SELECT {VOICE_STATE_COLUMNS} FROM voice_states WHERE guild_id = $1 ORDER BY joined_at ASC"
+        ))
+        .bind(guild_id.get_uuid())
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list voice states", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn update_self(
+        &self,
+        user_id: Uuid,
+        self_mute: bool,
+        self_deaf: bool,
+    ) -> Result<Option<VoiceState>, CoreError> {
+        let row = sqlx::query_as::<_, VoiceStateRow>(&format!(
+            r#"
+            UPDATE voice_states
+            SET self_mute = $2, self_deaf = $3
+            WHERE user_id = $1
+            RETURNING {VOICE_STATE_COLUMNS}
+            "#
+        ))
+        .bind(user_id)
+        .bind(self_mute)
+        .bind(self_deaf)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to update voice state", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn update_stage(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+        suppress: bool,
+        request_to_speak_at: Option<DateTime<Utc>>,
+    ) -> Result<Option<VoiceState>, CoreError> {
+        let row = sqlx::query_as::<_, VoiceStateRow>(&format!(
+            r#"
+            UPDATE voice_states
+            SET suppress = $3, request_to_speak_at = $4
+            WHERE user_id = $1 AND channel_id = $2
+            RETURNING {VOICE_STATE_COLUMNS}
+            "#
+        ))
+        .bind(user_id)
+        .bind(channel_id)
+        .bind(suppress)
+        .bind(request_to_speak_at)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to update stage voice state", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn set_server_flags(
+        &self,
+        guild_id: &GuildId,
+        user_id: Uuid,
+        mute: Option<bool>,
+        deaf: Option<bool>,
+    ) -> Result<Option<VoiceModeration>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin voice moderation transaction", e))?;
+
+        let flags: Option<(bool, bool)> = sqlx::query_as(
+            r#"
+            UPDATE members
+            SET mute = COALESCE($3, mute), deaf = COALESCE($4, deaf)
+            WHERE guild_id = $1 AND user_id = $2
+            RETURNING mute, deaf
+            "#,
+        )
+        .bind(guild_id.get_uuid())
+        .bind(user_id)
+        .bind(mute)
+        .bind(deaf)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to update member voice flags", e))?;
+
+        let Some((mute, deaf)) = flags else {
+            return Ok(None);
+        };
+
+        let row = sqlx::query_as::<_, VoiceStateRow>(&format!(
+            r#"
+            UPDATE voice_states
+            SET mute = $3, deaf = $4
+            WHERE guild_id = $1 AND user_id = $2
+            RETURNING {VOICE_STATE_COLUMNS}
+            "#
+        ))
+        .bind(guild_id.get_uuid())
+        .bind(user_id)
+        .bind(mute)
+        .bind(deaf)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to update voice state flags", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit voice moderation", e))?;
+
+        Ok(Some(VoiceModeration {
+            mute,
+            deaf,
+            state: row.map(Into::into),
+        }))
+    }
+
+    async fn list_by_channel(&self, channel_id: Uuid) -> Result<Vec<VoiceState>, CoreError> {
+        let rows = sqlx::query_as::<_, VoiceStateRow>(&format!(
+            "SELECT {VOICE_STATE_COLUMNS} FROM voice_states WHERE channel_id = $1 ORDER BY joined_at ASC"
+        ))
+        .bind(channel_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list channel voice states", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn delete_by_user(&self, user_id: Uuid) -> Result<Option<VoiceState>, CoreError> {
+        let row = sqlx::query_as::<_, VoiceStateRow>(&format!(
+            "DELETE FROM voice_states WHERE user_id = $1 RETURNING {VOICE_STATE_COLUMNS}"
+        ))
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to delete voice state", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn delete_without_sessions(
+        &self,
+        user_id: Option<Uuid>,
+        stale_before: DateTime<Utc>,
+    ) -> Result<Vec<VoiceState>, CoreError> {
+        let rows = sqlx::query_as::<_, VoiceStateRow>(&format!(
+            r#"
+            DELETE FROM voice_states v
+            WHERE ($1::uuid IS NULL OR v.user_id = $1)
+              AND NOT EXISTS (
+                  SELECT 1 FROM presence_sessions s
+                  WHERE s.user_id = v.user_id AND s.heartbeat_at > $2
+              )
+            RETURNING {VOICE_STATE_COLUMNS}
+            "#
+        ))
+        .bind(user_id)
+        .bind(stale_before)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to delete orphaned voice states", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
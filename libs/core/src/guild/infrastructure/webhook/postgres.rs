@@ -0,0 +1,197 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::webhook::Webhook;
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::guild::domain::{errors::CoreError, webhook::ports::WebhookRepository};
+
+#[derive(Clone)]
+pub struct PostgresWebhookRepository {
+    pool: PgPool,
+}
+
+impl PostgresWebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const WEBHOOK_COLUMNS: &str = "id, guild_id, channel_id, name, avatar_url, created_by, created_at";
+
+#[derive(sqlx::FromRow)]
+struct WebhookRow {
+    id: Uuid,
+    guild_id: Uuid,
+    channel_id: Uuid,
+    name: String,
+    avatar_url: Option<String>,
+    created_by: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+
+impl From<WebhookRow> for Webhook {
+    fn from(row: WebhookRow) -> Self {
+        Webhook {
+            id: row.id,
+            guild_id: row.guild_id,
+            channel_id: row.channel_id,
+            name: row.name,
+            avatar_url: row.avatar_url,
+            created_by: row.created_by,
+            created_at: row.created_at,
+            token: None,
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::Unknown {
+        message: format!("{}: {}", context, e),
+    }
+}
+
+// ─── WebhookRepository impl ───────────────────────────────────────────────────
+
+impl WebhookRepository for PostgresWebhookRepository {
+    async fn insert(&self, webhook: &Webhook, token_hash: &str) -> Result<Webhook, CoreError> {
+        let row = sqlx::query_as::<_, WebhookRow>(&format!(
+            r#"
+            INSERT INTO webhooks
+                (id, guild_id, channel_id, name, avatar_url, token_hash, created_by, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING {WEBHOOK_COLUMNS}
+            "#
+        ))
+        .bind(webhook.id)
+        .bind(webhook.guild_id)
+        .bind(webhook.channel_id)
+        .bind(&webhook.name)
+        .bind(&webhook.avatar_url)
+        .bind(token_hash)
+        .bind(webhook.created_by)
+        .bind(webhook.created_at)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to insert webhook", e))?;
+
+        Ok(row.into())
+    }
+
+    async fn find_by_id(&self, webhook_id: Uuid) -> Result<Option<Webhook>, CoreError> {
+        let row = sqlx::query_as::<_, WebhookRow>(&format!(
+            "SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE id = $1"
+        ))
+        .bind(webhook_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find webhook", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn find_by_token(
+        &self,
+        webhook_id: Uuid,
+        token_hash: &str,
+    ) -> Result<Option<Webhook>, CoreError> {
+        let row = sqlx::query_as::<_, WebhookRow>(&format!(
+            "SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE id = $1 AND token_hash = $2"
+        ))
+        .bind(webhook_id)
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to find webhook by token", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn list_by_guild(
+        &self,
+        guild_id: Uuid,
+        channel_id: Option<Uuid>,
+    ) -> Result<Vec<Webhook>, CoreError> {
+        let rows = sqlx::query_as::<_, WebhookRow>(&format!(
+            r#"
+            SELECT {WEBHOOK_COLUMNS} FROM webhooks
+            WHERE guild_id = $1 AND ($2::UUID IS NULL OR channel_id = $2)
+            ORDER BY created_at
+            "#
+        ))
+        .bind(guild_id)
+        .bind(channel_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list webhooks", e))?;
+
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    async fn count_by_channel(&self, channel_id: Uuid) -> Result<i64, CoreError> {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM webhooks WHERE channel_id = $1")
+            .bind(channel_id)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to count webhooks", e))
+    }
+
+    async fn update(
+        &self,
+        webhook_id: Uuid,
+        name: Option<String>,
+        avatar_url: Option<Option<String>>,
+        channel_id: Option<Uuid>,
+    ) -> Result<Option<Webhook>, CoreError> {
+        let row = sqlx::query_as::<_, WebhookRow>(&format!(
+            r#"
+            UPDATE webhooks
+            SET name = COALESCE($2, name),
+                avatar_url = CASE WHEN $3 THEN $4 ELSE avatar_url END,
+                channel_id = COALESCE($5, channel_id)
+            WHERE id = $1
+            RETURNING {WEBHOOK_COLUMNS}
+            "#
+        ))
+        .bind(webhook_id)
+        .bind(name)
+        .bind(avatar_url.is_some())
+        .bind(avatar_url.flatten())
+        .bind(channel_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to update webhook", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn set_token(
+        &self,
+        webhook_id: Uuid,
+        token_hash: &str,
+    ) -> Result<Option<Webhook>, CoreError> {
+        let row = sqlx::query_as::<_, WebhookRow>(&format!(
+            "UPDATE webhooks SET token_hash = $2 WHERE id = $1 RETURNING {WEBHOOK_COLUMNS}"
+        ))
+        .bind(webhook_id)
+        .bind(token_hash)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to reset webhook token", e))?;
+
+        Ok(row.map(Into::into))
+    }
+
+    async fn delete(&self, webhook_id: Uuid) -> Result<bool, CoreError> {
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = $1")
+            .bind(webhook_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete webhook", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
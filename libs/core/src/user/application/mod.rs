@@ -3,14 +3,20 @@ use sqlx::PgPool;
 
 use crate::user::{
     domain::{
+        call::CallServiceImpl,
         common::CoreError,
         dm::DmServiceImpl,
         friend::FriendServiceImpl,
+        presence::PresenceServiceImpl,
+        read_state::ReadStateServiceImpl,
         user::UserServiceImpl,
     },
     infrastructure::{
+        call::postgres::PostgresDmCallRepository,
         dm::postgres::PostgresDmRepository,
         friend::postgres::PostgresFriendRepository,
+        presence::postgres::PostgresPresenceRepository,
+        read_state::postgres::PostgresReadStateRepository,
         user::postgres::PostgresUserRepository,
     },
 };
@@ -18,6 +24,9 @@ use crate::user::{
 pub type UserFerrisCordService = UserServiceImpl<PostgresUserRepository>;
 pub type FriendFerrisCordService = FriendServiceImpl<PostgresFriendRepository>;
 pub type DmFerrisCordService = DmServiceImpl<PostgresDmRepository>;
+pub type PresenceFerrisCordService = PresenceServiceImpl<PostgresPresenceRepository>;
+pub type ReadStateFerrisCordService = ReadStateServiceImpl<PostgresReadStateRepository>;
+pub type CallFerrisCordService = CallServiceImpl<PostgresDmCallRepository>;
 
 pub fn create_user_services(
     pool: PgPool,
@@ -30,6 +39,18 @@ pub fn create_user_services(
     ))
 }
 
+pub fn create_presence_service(pool: PgPool) -> PresenceFerrisCordService {
+    PresenceServiceImpl::new(PostgresPresenceRepository::new(pool))
+}
+
+pub fn create_read_state_service(pool: PgPool) -> ReadStateFerrisCordService {
+    ReadStateServiceImpl { read_state_repository: PostgresReadStateRepository::new(pool) }
+}
+
+pub fn create_call_service(pool: PgPool) -> CallFerrisCordService {
+    CallServiceImpl { call_repository: PostgresDmCallRepository::new(pool) }
+}
+
 pub fn create_auth_repository(issuer: impl Into<String>) -> FerriskeyAuthRepository {
     FerriskeyAuthRepository::new(issuer.into(), None)
 }
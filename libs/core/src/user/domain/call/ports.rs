@@ -0,0 +1,118 @@
+use std::future::Future;
+
+use chrono::{DateTime, Duration, Utc};
+use ferriscord_entities::{
+    dm_call::{DmCall, DmCallStatus},
+    message::Message,
+};
+use uuid::Uuid;
+
+use crate::user::domain::common::CoreError;
+
+/// How long participants are rung before the ring counts as missed.
+pub const RING_TIMEOUT: Duration = Duration::seconds(30);
+
+/// A call after a change. `ended` holds the call message written to the DM
+/// when the change ended the call.
+pub struct CallUpdate {
+    pub call: DmCall,
+    pub ended: Option<Message>,
+}
+
+pub trait DmCallRepository: Send + Sync {
+    /// Participants of the DM channel. Empty when it is not a DM channel.
+    fn list_participants(
+        &self,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<Uuid>, CoreError>> + Send;
+
+    /// Inserts a live call. Returns `None` without writing when the channel
+    /// already has one.
+    fn insert(&self, call: &DmCall) -> impl Future<Output = Result<Option<DmCall>, CoreError>> + Send;
+
+    fn find_live(
+        &self,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<Option<DmCall>, CoreError>> + Send;
+
+    /// Moves a participant of the live call from one of the `from` statuses
+    /// to `to`, recording `at` as their join time when they first join.
+    /// Returns `None` without writing when the call ended or their status is
+    /// not in `from`.
+    fn set_status(
+        &self,
+        call_id: Uuid,
+        user_id: Uuid,
+        from: &[DmCallStatus],
+        to: DmCallStatus,
+        at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Option<DmCall>, CoreError>> + Send;
+
+    /// Ends the call if nobody is in it, or if nobody is rung and at most one
+    /// participant is left, and writes its call message to the DM. The check
+    /// and the write are atomic, so concurrent hang-ups end the call once.
+    fn end_if_idle(
+        &self,
+        call_id: Uuid,
+        ended_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Option<(DmCall, Message)>, CoreError>> + Send;
+
+    /// Marks rings of live calls started before `cutoff` as missed and returns
+    /// the calls that changed. Each ring is expired once, whichever replica
+    /// runs it.
+    fn expire_rings(
+        &self,
+        cutoff: DateTime<Utc>,
+    ) -> impl Future<Output = Result<Vec<DmCall>, CoreError>> + Send;
+}
+
+/// Calls in DM channels. Every participant of the channel may start, join
+/// or be rung into its call.
+pub trait CallService: Send + Sync {
+    /// Starts a call: the caller joins and everybody else is rung.
+    fn start_call(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<DmCall, CoreError>> + Send;
+
+    /// The live call of the channel, if any.
+    fn get_call(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<Option<DmCall>, CoreError>> + Send;
+
+    /// Answers a ring, or joins the live call again after leaving it.
+    fn accept_call(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<DmCall, CoreError>> + Send;
+
+    /// Declines a ring. Does nothing if the user is not being rung.
+    fn decline_call(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<CallUpdate, CoreError>> + Send;
+
+    /// Hangs up. Returns `None` if the user was not in a live call of the
+    /// channel.
+    fn leave_call(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<Option<CallUpdate>, CoreError>> + Send;
+
+    /// Checks that the user joined the live call of the channel, before they
+    /// connect to its voice channel.
+    fn authorize_voice(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> impl Future<Output = Result<DmCall, CoreError>> + Send;
+
+    /// Expires rings older than [`RING_TIMEOUT`].
+    fn expire_rings(&self) -> impl Future<Output = Result<Vec<CallUpdate>, CoreError>> + Send;
+}
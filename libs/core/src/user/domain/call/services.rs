@@ -0,0 +1,197 @@
+use chrono::Utc;
+use ferriscord_entities::dm_call::{DmCall, DmCallParticipant, DmCallStatus};
+use uuid::Uuid;
+
+use crate::user::domain::{
+    call::ports::{CallService, CallUpdate, DmCallRepository, RING_TIMEOUT},
+    common::CoreError,
+};
+
+#[derive(Clone)]
+pub struct CallServiceImpl<R: DmCallRepository> {
+    pub(crate) call_repository: R,
+}
+
+/// Statuses from which a participant may (re)join the call.
+const JOINABLE: [DmCallStatus; 4] = [
+    DmCallStatus::Ringing,
+    DmCallStatus::Declined,
+    DmCallStatus::Missed,
+    DmCallStatus::Left,
+];
+
+fn new_call(user_id: Uuid, channel_id: Uuid, participants: &[Uuid]) -> DmCall {
+    let now = Utc::now();
+    DmCall {
+        id: Uuid::now_v7(),
+        channel_id,
+        started_by: user_id,
+        started_at: now,
+        ended_at: None,
+        participants: participants
+            .iter()
+            .map(|&id| {
+                if id == user_id {
+                    DmCallParticipant {
+                        user_id: id,
+                        status: DmCallStatus::Joined,
+                        joined_at: Some(now),
+                    }
+                } else {
+                    DmCallParticipant {
+                        user_id: id,
+                        status: DmCallStatus::Ringing,
+                        joined_at: None,
+                    }
+                }
+            })
+            .collect(),
+    }
+}
+
+impl<R: DmCallRepository> CallServiceImpl<R> {
+    async fn require_participant(&self, user_id: Uuid, channel_id: Uuid) -> Result<(), CoreError> {
+        let participants = self.call_repository.list_participants(channel_id).await?;
+        if !participants.contains(&user_id) {
+            return Err(CoreError::DmChannelNotFound);
+        }
+        Ok(())
+    }
+
+    async fn require_live(&self, user_id: Uuid, channel_id: Uuid) -> Result<DmCall, CoreError> {
+        self.require_participant(user_id, channel_id).await?;
+        self.call_repository
+            .find_live(channel_id)
+            .await?
+            .ok_or(CoreError::NoActiveCall)
+    }
+
+    async fn update(&self, call: DmCall) -> Result<CallUpdate, CoreError> {
+        match self.call_repository.end_if_idle(call.id, Utc::now()).await? {
+            Some((call, message)) => Ok(CallUpdate {
+                call,
+                ended: Some(message),
+            }),
+            None => Ok(CallUpdate { call, ended: None }),
+        }
+    }
+}
+
+impl<R: DmCallRepository> CallService for CallServiceImpl<R> {
+    async fn start_call(&self, user_id: Uuid, channel_id: Uuid) -> Result<DmCall, CoreError> {
+        let participants = self.call_repository.list_participants(channel_id).await?;
+        if !participants.contains(&user_id) {
+            return Err(CoreError::DmChannelNotFound);
+        }
+
+        self.call_repository
+            .insert(&new_call(user_id, channel_id, &participants))
+            .await?
+            .ok_or(CoreError::CallAlreadyActive)
+    }
+
+    async fn get_call(&self, user_id: Uuid, channel_id: Uuid) -> Result<Option<DmCall>, CoreError> {
+        self.require_participant(user_id, channel_id).await?;
+        self.call_repository.find_live(channel_id).await
+    }
+
+    async fn accept_call(&self, user_id: Uuid, channel_id: Uuid) -> Result<DmCall, CoreError> {
+        let call = self.require_live(user_id, channel_id).await?;
+        match call.participant(user_id).map(|p| p.status) {
+            None => Err(CoreError::NotInCall),
+            Some(DmCallStatus::Joined) => Ok(call),
+            Some(_) => self
+                .call_repository
+                .set_status(call.id, user_id, &JOINABLE, DmCallStatus::Joined, Utc::now())
+                .await?
+                .ok_or(CoreError::NoActiveCall),
+        }
+    }
+
+    async fn decline_call(&self, user_id: Uuid, channel_id: Uuid) -> Result<CallUpdate, CoreError> {
+        let call = self.require_live(user_id, channel_id).await?;
+        if call.participant(user_id).map(|p| p.status) != Some(DmCallStatus::Ringing) {
+            return Ok(CallUpdate { call, ended: None });
+        }
+
+        match self
+            .call_repository
+            .set_status(
+                call.id,
+                user_id,
+                &[DmCallStatus::Ringing],
+                DmCallStatus::Declined,
+                Utc::now(),
+            )
+            .await?
+        {
+            Some(call) => self.update(call).await,
+            // Answered or timed out meanwhile.
+            None => Ok(CallUpdate { call, ended: None }),
+        }
+    }
+
+    async fn leave_call(
+        &self,
+        user_id: Uuid,
+        channel_id: Uuid,
+    ) -> Result<Option<CallUpdate>, CoreError> {
+        let Some(call) = self.call_repository.find_live(channel_id).await? else {
+            return Ok(None);
+        };
+
+        match self
+            .call_repository
+            .set_status(
+                call.id,
+                user_id,
+                &[DmCallStatus::Joined],
+                DmCallStatus::Left,
+                Utc::now(),
+            )
+            .await?
+        {
+            Some(call) => Ok(Some(self.update(call).await?)),
+            None => Ok(None),
+        }
+    }
+
+    async fn authorize_voice(&self, user_id: Uuid, channel_id: Uuid) -> Result<DmCall, CoreError> {
+        let call = self.require_live(user_id, channel_id).await?;
+        if call.participant(user_id).map(|p| p.status) != Some(DmCallStatus::Joined) {
+            return Err(CoreError::NotInCall);
+        }
+        Ok(call)
+    }
+
+    async fn expire_rings(&self) -> Result<Vec<CallUpdate>, CoreError> {
+        let calls = self
+            .call_repository
+            .expire_rings(Utc::now() - RING_TIMEOUT)
+            .await?;
+
+        let mut updates = Vec::with_capacity(calls.len());
+        for call in calls {
+            updates.push(self.update(call).await?);
+        }
+        Ok(updates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_caller_joins_and_everybody_else_is_rung() {
+        let caller = Uuid::now_v7();
+        let others = [Uuid::now_v7(), Uuid::now_v7()];
+        let call = new_call(caller, Uuid::now_v7(), &[others[0], caller, others[1]]);
+
+        assert!(call.is_live());
+        assert_eq!(call.started_by, caller);
+        assert_eq!(call.participant(caller).unwrap().status, DmCallStatus::Joined);
+        assert!(call.participant(caller).unwrap().joined_at.is_some());
+        assert_eq!(call.ringing().collect::<Vec<_>>(), others);
+    }
+}
@@ -25,4 +25,19 @@ pub enum CoreError {
 
     #[error("cannot send a friend request to yourself")]
     SelfFriendRequest,
+
+    #[error("message not found")]
+    MessageNotFound,
+
+    #[error("invalid custom status: {message}")]
+    InvalidCustomStatus { message: String },
+
+    #[error("no call in progress in this channel")]
+    NoActiveCall,
+
+    #[error("a call is already in progress in this channel")]
+    CallAlreadyActive,
+
+    #[error("not in the call")]
+    NotInCall,
 }
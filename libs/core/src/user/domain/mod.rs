@@ -1,4 +1,7 @@
+pub mod call;
 pub mod common;
 pub mod dm;
 pub mod friend;
 pub mod user;
+pub mod presence;
+pub mod read_state;
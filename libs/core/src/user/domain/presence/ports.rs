@@ -0,0 +1,133 @@
+use std::{collections::HashMap, future::Future};
+
+use chrono::{DateTime, Utc};
+use ferriscord_entities::presence::{CustomStatus, Presence, PresenceStatus};
+use uuid::Uuid;
+
+use crate::user::domain::common::CoreError;
+
+/// One gateway connection of a user. A user is online as long as at least one
+/// of their sessions keeps heartbeating.
+#[derive(Debug, Clone)]
+pub struct PresenceSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Status requested by the client on this session (never `Offline`).
+    pub status: PresenceStatus,
+    pub last_active_at: DateTime<Utc>,
+    pub heartbeat_at: DateTime<Utc>,
+}
+
+/// Per-user presence data that outlives sessions.
+#[derive(Debug, Clone)]
+pub struct UserPresenceRecord {
+    pub user_id: Uuid,
+    pub custom_status: Option<CustomStatus>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+pub trait PresenceRepository: Send + Sync {
+    fn upsert_session(
+        &self,
+        session_id: Uuid,
+        user_id: Uuid,
+        status: PresenceStatus,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Refreshes the session heartbeat. When `active` is true the session's
+    /// last activity is bumped as well.
+    fn touch_session(
+        &self,
+        session_id: Uuid,
+        active: bool,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn set_session_status(
+        &self,
+        session_id: Uuid,
+        status: PresenceStatus,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn delete_session(
+        &self,
+        session_id: Uuid,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Deletes sessions of `user_id` whose heartbeat is older than `before`
+    /// (left behind by a replica that went away without cleaning up).
+    fn delete_stale_sessions(
+        &self,
+        user_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn list_sessions(
+        &self,
+        user_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<PresenceSession>, CoreError>> + Send;
+
+    fn list_records(
+        &self,
+        user_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<UserPresenceRecord>, CoreError>> + Send;
+
+    fn set_custom_status(
+        &self,
+        user_id: Uuid,
+        custom_status: Option<CustomStatus>,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn set_last_seen(
+        &self,
+        user_id: Uuid,
+        last_seen_at: DateTime<Utc>,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+}
+
+pub trait PresenceService: Send + Sync {
+    /// Registers a new gateway session and returns the user's aggregated presence.
+    fn connect(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+    ) -> impl Future<Output = Result<Presence, CoreError>> + Send;
+
+    /// Removes a gateway session. `last_seen_at` is persisted when it was the
+    /// user's last live session.
+    fn disconnect(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+    ) -> impl Future<Output = Result<Presence, CoreError>> + Send;
+
+    fn heartbeat(
+        &self,
+        session_id: Uuid,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    /// Records user activity on a session, resetting its auto-idle timer.
+    fn record_activity(
+        &self,
+        session_id: Uuid,
+    ) -> impl Future<Output = Result<(), CoreError>> + Send;
+
+    fn set_status(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+        status: PresenceStatus,
+    ) -> impl Future<Output = Result<Presence, CoreError>> + Send;
+
+    fn set_custom_status(
+        &self,
+        user_id: Uuid,
+        custom_status: Option<CustomStatus>,
+    ) -> impl Future<Output = Result<Presence, CoreError>> + Send;
+
+    fn get(&self, user_id: Uuid) -> impl Future<Output = Result<Presence, CoreError>> + Send;
+
+    fn get_many(
+        &self,
+        user_ids: &[Uuid],
+    ) -> impl Future<Output = Result<HashMap<Uuid, Presence>, CoreError>> + Send;
+}
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use ferriscord_entities::presence::{CustomStatus, Presence, PresenceStatus};
+use uuid::Uuid;
+
+use crate::user::domain::{
+    common::CoreError,
+    presence::ports::{PresenceRepository, PresenceService, PresenceSession, UserPresenceRecord},
+};
+
+/// Maximum length of a custom status text, in characters.
+pub const MAX_CUSTOM_STATUS_TEXT_LEN: usize = 128;
+/// Maximum length of a custom status emoji (unicode sequence or custom emoji reference).
+pub const MAX_CUSTOM_STATUS_EMOJI_LEN: usize = 64;
+
+/// Sessions that have not heartbeated for this long are considered dead.
+const DEFAULT_SESSION_TTL_SECS: i64 = 90;
+/// Online sessions without user activity for this long are reported as idle.
+const DEFAULT_IDLE_AFTER_SECS: i64 = 5 * 60;
+
+#[derive(Clone)]
+pub struct PresenceServiceImpl<P: PresenceRepository> {
+    pub(crate) presence_repository: P,
+    pub(crate) session_ttl: Duration,
+    pub(crate) idle_after: Duration,
+}
+
+impl<P: PresenceRepository> PresenceServiceImpl<P> {
+    pub fn new(presence_repository: P) -> Self {
+        Self {
+            presence_repository,
+            session_ttl: Duration::seconds(DEFAULT_SESSION_TTL_SECS),
+            idle_after: Duration::seconds(DEFAULT_IDLE_AFTER_SECS),
+        }
+    }
+
+    pub fn with_session_ttl(mut self, session_ttl: Duration) -> Self {
+        self.session_ttl = session_ttl;
+        self
+    }
+
+    pub fn with_idle_after(mut self, idle_after: Duration) -> Self {
+        self.idle_after = idle_after;
+        self
+    }
+
+    fn build_presence(
+        &self,
+        user_id: Uuid,
+        sessions: &[&PresenceSession],
+        record: Option<&UserPresenceRecord>,
+        now: DateTime<Utc>,
+    ) -> Presence {
+        let cutoff = now - self.session_ttl;
+        let (alive, stale): (Vec<&PresenceSession>, Vec<&PresenceSession>) =
+            sessions.iter().partition(|s| s.heartbeat_at > cutoff);
+
+        let status = aggregate_status(&alive, now, self.idle_after);
+
+        let last_seen_at = if alive.is_empty() {
+            let stale_seen = stale.iter().map(|s| s.heartbeat_at).max();
+            record.and_then(|r| r.last_seen_at).max(stale_seen)
+        } else {
+            alive.iter().map(|s| s.heartbeat_at).max()
+        };
+
+        let custom_status = record
+            .and_then(|r| r.custom_status.clone())
+            .filter(|c| !c.is_expired(now));
+
+        Presence {
+            user_id,
+            status,
+            custom_status,
+            last_seen_at,
+        }
+    }
+}
+
+/// Combines the statuses of a user's live sessions into a single status.
+///
+/// Do-not-disturb on any session wins, then online, then idle. An online
+/// session without activity for `idle_after` counts as idle.
+pub fn aggregate_status(
+    sessions: &[&PresenceSession],
+    now: DateTime<Utc>,
+    idle_after: Duration,
+) -> PresenceStatus {
+    let effective = sessions.iter().map(|s| match s.status {
+        PresenceStatus::Online if now - s.last_active_at >= idle_after => PresenceStatus::Idle,
+        status => status,
+    });
+
+    let mut aggregated = PresenceStatus::Offline;
+    for status in effective {
+        aggregated = match (aggregated, status) {
+            (PresenceStatus::DoNotDisturb, _) | (_, PresenceStatus::DoNotDisturb) => {
+                PresenceStatus::DoNotDisturb
+            }
+            (PresenceStatus::Online, _) | (_, PresenceStatus::Online) => PresenceStatus::Online,
+            (PresenceStatus::Idle, _) | (_, PresenceStatus::Idle) => PresenceStatus::Idle,
+            _ => PresenceStatus::Offline,
+        };
+    }
+    aggregated
+}
+
+impl<P: PresenceRepository> PresenceService for PresenceServiceImpl<P> {
+    async fn connect(&self, user_id: Uuid, session_id: Uuid) -> Result<Presence, CoreError> {
+        self.presence_repository
+            .delete_stale_sessions(user_id, Utc::now() - self.session_ttl)
+            .await?;
+        self.presence_repository
+            .upsert_session(session_id, user_id, PresenceStatus::Online)
+            .await?;
+        self.get(user_id).await
+    }
+
+    async fn disconnect(&self, user_id: Uuid, session_id: Uuid) -> Result<Presence, CoreError> {
+        self.presence_repository.delete_session(session_id).await?;
+        let presence = self.get(user_id).await?;
+        if presence.status == PresenceStatus::Offline {
+            self.presence_repository.set_last_seen(user_id, Utc::now()).await?;
+            return self.get(user_id).await;
+        }
+        Ok(presence)
+    }
+
+    async fn heartbeat(&self, session_id: Uuid) -> Result<(), CoreError> {
+        self.presence_repository.touch_session(session_id, false).await
+    }
+
+    async fn record_activity(&self, session_id: Uuid) -> Result<(), CoreError> {
+        self.presence_repository.touch_session(session_id, true).await
+    }
+
+    async fn set_status(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+        status: PresenceStatus,
+    ) -> Result<Presence, CoreError> {
+        // Going offline is done by disconnecting, not by a status change.
+        let status = match status {
+            PresenceStatus::Offline => PresenceStatus::Online,
+            status => status,
+        };
+        self.presence_repository.set_session_status(session_id, status).await?;
+        self.presence_repository.touch_session(session_id, true).await?;
+        self.get(user_id).await
+    }
+
+    async fn set_custom_status(
+        &self,
+        user_id: Uuid,
+        custom_status: Option<CustomStatus>,
+    ) -> Result<Presence, CoreError> {
+        let custom_status = match custom_status {
+            Some(c) => validate_custom_status(c, Utc::now())?,
+            None => None,
+        };
+        self.presence_repository
+            .set_custom_status(user_id, custom_status)
+            .await?;
+        self.get(user_id).await
+    }
+
+    async fn get(&self, user_id: Uuid) -> Result<Presence, CoreError> {
+        let mut presences = self.get_many(&[user_id]).await?;
+        Ok(presences
+            .remove(&user_id)
+            .unwrap_or_else(|| Presence::offline(user_id)))
+    }
+
+    async fn get_many(&self, user_ids: &[Uuid]) -> Result<HashMap<Uuid, Presence>, CoreError> {
+        if user_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let sessions = self.presence_repository.list_sessions(user_ids).await?;
+        let records = self.presence_repository.list_records(user_ids).await?;
+        let now = Utc::now();
+
+        let mut sessions_by_user: HashMap<Uuid, Vec<&PresenceSession>> = HashMap::new();
+        for session in &sessions {
+            sessions_by_user.entry(session.user_id).or_default().push(session);
+        }
+        let records_by_user: HashMap<Uuid, &UserPresenceRecord> =
+            records.iter().map(|r| (r.user_id, r)).collect();
+
+        Ok(user_ids
+            .iter()
+            .map(|id| {
+                let sessions = sessions_by_user.get(id).map(Vec::as_slice).unwrap_or(&[]);
+                let record = records_by_user.get(id).copied();
+                (*id, self.build_presence(*id, sessions, record, now))
+            })
+            .collect())
+    }
+}
+
+/// Normalises a custom status, returning `None` when it carries neither text
+/// nor emoji (which clears it).
+pub(crate) fn validate_custom_status(
+    custom_status: CustomStatus,
+    now: DateTime<Utc>,
+) -> Result<Option<CustomStatus>, CoreError> {
+    let text = custom_status.text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+    let emoji = custom_status.emoji.map(|e| e.trim().to_string()).filter(|e| !e.is_empty());
+
+    if text.as_ref().is_some_and(|t| t.chars().count() > MAX_CUSTOM_STATUS_TEXT_LEN) {
+        return Err(CoreError::InvalidCustomStatus {
+            message: format!("text must be at most {} characters", MAX_CUSTOM_STATUS_TEXT_LEN),
+        });
+    }
+    if emoji.as_ref().is_some_and(|e| e.chars().count() > MAX_CUSTOM_STATUS_EMOJI_LEN) {
+        return Err(CoreError::InvalidCustomStatus {
+            message: format!("emoji must be at most {} characters", MAX_CUSTOM_STATUS_EMOJI_LEN),
+        });
+    }
+    if custom_status.expires_at.is_some_and(|at| at <= now) {
+        return Err(CoreError::InvalidCustomStatus {
+            message: "expires_at must be in the future".to_string(),
+        });
+    }
+
+    if text.is_none() && emoji.is_none() {
+        return Ok(None);
+    }
+
+    Ok(Some(CustomStatus { text, emoji, expires_at: custom_status.expires_at }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(status: PresenceStatus, idle_for: i64) -> PresenceSession {
+        let now = Utc::now();
+        PresenceSession {
+            id: Uuid::now_v7(),
+            user_id: Uuid::nil(),
+            status,
+            last_active_at: now - Duration::seconds(idle_for),
+            heartbeat_at: now,
+        }
+    }
+
+    #[test]
+    fn test_no_sessions_is_offline() {
+        let status = aggregate_status(&[], Utc::now(), Duration::minutes(5));
+        assert_eq!(status, PresenceStatus::Offline);
+    }
+
+    #[test]
+    fn test_any_online_session_wins_over_idle() {
+        let idle = session(PresenceStatus::Idle, 0);
+        let online = session(PresenceStatus::Online, 0);
+        let status = aggregate_status(&[&idle, &online], Utc::now(), Duration::minutes(5));
+        assert_eq!(status, PresenceStatus::Online);
+    }
+
+    #[test]
+    fn test_do_not_disturb_wins() {
+        let online = session(PresenceStatus::Online, 0);
+        let dnd = session(PresenceStatus::DoNotDisturb, 0);
+        let status = aggregate_status(&[&online, &dnd], Utc::now(), Duration::minutes(5));
+        assert_eq!(status, PresenceStatus::DoNotDisturb);
+    }
+
+    #[test]
+    fn test_inactive_online_session_becomes_idle() {
+        let online = session(PresenceStatus::Online, 600);
+        let status = aggregate_status(&[&online], Utc::now(), Duration::minutes(5));
+        assert_eq!(status, PresenceStatus::Idle);
+    }
+
+    #[test]
+    fn test_one_active_tab_keeps_user_online() {
+        let inactive = session(PresenceStatus::Online, 600);
+        let active = session(PresenceStatus::Online, 10);
+        let status = aggregate_status(&[&inactive, &active], Utc::now(), Duration::minutes(5));
+        assert_eq!(status, PresenceStatus::Online);
+    }
+
+    #[test]
+    fn test_blank_custom_status_clears_it() {
+        let blank = CustomStatus { text: Some("   ".into()), emoji: None, expires_at: None };
+        assert!(validate_custom_status(blank, Utc::now()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_expired_custom_status_is_rejected() {
+        let now = Utc::now();
+        let expired = CustomStatus {
+            text: Some("brb".into()),
+            emoji: None,
+            expires_at: Some(now - Duration::minutes(1)),
+        };
+        assert!(validate_custom_status(expired, now).is_err());
+    }
+}
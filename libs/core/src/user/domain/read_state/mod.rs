@@ -0,0 +1,4 @@
+pub mod ports;
+mod services;
+
+pub use services::{ReadStateServiceImpl, parse_user_mentions};
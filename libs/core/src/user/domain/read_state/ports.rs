@@ -0,0 +1,96 @@
+use std::future::Future;
+
+use ferriscord_entities::read_state::ReadState;
+use uuid::Uuid;
+
+use crate::user::domain::common::CoreError;
+
+pub trait ReadStateRepository: Send + Sync {
+    /// Marks `message_id` as the last message read by `user_id` in the channel
+    /// and recomputes the mention count from the messages after it.
+    ///
+    /// `guild_id` must match the channel's guild (`None` for DMs). For DMs the
+    /// user must be a participant. Returns `None` when the message does not
+    /// belong to an accessible channel.
+    fn ack(
+        &self,
+        user_id: Uuid,
+        guild_id: Option<Uuid>,
+        channel_id: Uuid,
+        message_id: Uuid,
+    ) -> impl Future<Output = Result<Option<ReadState>, CoreError>> + Send;
+
+    fn list(
+        &self,
+        user_id: Uuid,
+        channel_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<ReadState>, CoreError>> + Send;
+
+    /// Bumps the mention count of the given guild members (author excluded)
+    /// and returns their updated read states. Webhook messages have no author.
+    fn increment_guild_mentions(
+        &self,
+        guild_id: Uuid,
+        channel_id: Uuid,
+        message_id: Uuid,
+        author_id: Option<Uuid>,
+        user_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<(Uuid, ReadState)>, CoreError>> + Send;
+
+    /// Bumps the mention count of every DM participant but the author and
+    /// returns their updated read states.
+    fn increment_dm_mentions(
+        &self,
+        channel_id: Uuid,
+        message_id: Uuid,
+        author_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<(Uuid, ReadState)>, CoreError>> + Send;
+}
+
+pub trait ReadStateService: Send + Sync {
+    fn ack(
+        &self,
+        user_id: Uuid,
+        guild_id: Option<Uuid>,
+        channel_id: Uuid,
+        message_id: Uuid,
+    ) -> impl Future<Output = Result<ReadState, CoreError>> + Send;
+
+    fn list(
+        &self,
+        user_id: Uuid,
+        channel_ids: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<ReadState>, CoreError>> + Send;
+
+    /// Records a new guild message: the author's read state moves to it and
+    /// each of `mentions` gets a mention. Mentions must be filtered down to
+    /// the members who can view the channel, as the others are not to hear
+    /// of it. Returns the read states that changed, keyed by user.
+    fn record_guild_message(
+        &self,
+        guild_id: Uuid,
+        channel_id: Uuid,
+        message_id: Uuid,
+        author_id: Uuid,
+        mentions: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<(Uuid, ReadState)>, CoreError>> + Send;
+
+    /// Records a new webhook message: each of `mentions`, filtered like
+    /// those of guild messages, gets a mention.
+    fn record_webhook_message(
+        &self,
+        guild_id: Uuid,
+        channel_id: Uuid,
+        message_id: Uuid,
+        mentions: &[Uuid],
+    ) -> impl Future<Output = Result<Vec<(Uuid, ReadState)>, CoreError>> + Send;
+
+    /// Records a new DM message: the author's read state moves to it and every
+    /// other participant gets a mention.
+    fn record_dm_message(
+        &self,
+        channel_id: Uuid,
+        message_id: Uuid,
+        author_id: Uuid,
+    ) -> impl Future<Output = Result<Vec<(Uuid, ReadState)>, CoreError>> + Send;
+}
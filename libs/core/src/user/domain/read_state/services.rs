@@ -0,0 +1,145 @@
+use ferriscord_entities::read_state::ReadState;
+use uuid::Uuid;
+
+use crate::user::domain::{
+    common::CoreError,
+    read_state::ports::{ReadStateRepository, ReadStateService},
+};
+
+#[derive(Clone)]
+pub struct ReadStateServiceImpl<R: ReadStateRepository> {
+    pub(crate) read_state_repository: R,
+}
+
+/// Extracts the user IDs mentioned as `<@user_id>` in a message, deduplicated
+/// and in order of appearance.
+pub fn parse_user_mentions(content: &str) -> Vec<Uuid> {
+    let mut mentions = Vec::new();
+    let mut rest = content;
+
+    while let Some(start) = rest.find("<@") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find('>') else {
+            break;
+        };
+        if let Ok(id) = Uuid::parse_str(&rest[..end]) {
+            if !mentions.contains(&id) {
+                mentions.push(id);
+            }
+            rest = &rest[end + 1..];
+        }
+    }
+
+    mentions
+}
+
+impl<R: ReadStateRepository> ReadStateService for ReadStateServiceImpl<R> {
+    async fn ack(
+        &self,
+        user_id: Uuid,
+        guild_id: Option<Uuid>,
+        channel_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<ReadState, CoreError> {
+        self.read_state_repository
+            .ack(user_id, guild_id, channel_id, message_id)
+            .await?
+            .ok_or(CoreError::MessageNotFound)
+    }
+
+    async fn list(&self, user_id: Uuid, channel_ids: &[Uuid]) -> Result<Vec<ReadState>, CoreError> {
+        if channel_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.read_state_repository.list(user_id, channel_ids).await
+    }
+
+    async fn record_guild_message(
+        &self,
+        guild_id: Uuid,
+        channel_id: Uuid,
+        message_id: Uuid,
+        author_id: Uuid,
+        mentions: &[Uuid],
+    ) -> Result<Vec<(Uuid, ReadState)>, CoreError> {
+        let mut changed = Vec::new();
+
+        if let Some(state) = self
+            .read_state_repository
+            .ack(author_id, Some(guild_id), channel_id, message_id)
+            .await?
+        {
+            changed.push((author_id, state));
+        }
+
+        if !mentions.is_empty() {
+            changed.extend(
+                self.read_state_repository
+                    .increment_guild_mentions(guild_id, channel_id, message_id, Some(author_id), mentions)
+                    .await?,
+            );
+        }
+
+        Ok(changed)
+    }
+
+    async fn record_webhook_message(
+        &self,
+        guild_id: Uuid,
+        channel_id: Uuid,
+        message_id: Uuid,
+        mentions: &[Uuid],
+    ) -> Result<Vec<(Uuid, ReadState)>, CoreError> {
+        if mentions.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.read_state_repository
+            .increment_guild_mentions(guild_id, channel_id, message_id, None, mentions)
+            .await
+    }
+
+    async fn record_dm_message(
+        &self,
+        channel_id: Uuid,
+        message_id: Uuid,
+        author_id: Uuid,
+    ) -> Result<Vec<(Uuid, ReadState)>, CoreError> {
+        let mut changed = Vec::new();
+
+        if let Some(state) = self
+            .read_state_repository
+            .ack(author_id, None, channel_id, message_id)
+            .await?
+        {
+            changed.push((author_id, state));
+        }
+
+        changed.extend(
+            self.read_state_repository
+                .increment_dm_mentions(channel_id, message_id, author_id)
+                .await?,
+        );
+
+        Ok(changed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_user_mentions() {
+        let a = Uuid::now_v7();
+        let b = Uuid::now_v7();
+        let content = format!("hey <@{a}> and <@{b}>, also <@{a}> again");
+        assert_eq!(parse_user_mentions(&content), vec![a, b]);
+    }
+
+    #[test]
+    fn test_parse_user_mentions_ignores_malformed() {
+        let a = Uuid::now_v7();
+        assert!(parse_user_mentions("<@not-a-uuid> <@> 1 < 2").is_empty());
+        assert_eq!(parse_user_mentions(&format!("<@ oops <@{a}>")), vec![a]);
+    }
+}
@@ -0,0 +1,361 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::{
+    dm_call::{DmCall, DmCallParticipant, DmCallStatus},
+    message::Message,
+};
+use sqlx::{PgConnection, PgPool};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::user::{
+    domain::{call::ports::DmCallRepository, common::CoreError},
+    infrastructure::dm::postgres::{MessageRow, SELECT_MESSAGES_SQL, row_to_message},
+};
+
+#[derive(Clone)]
+pub struct PostgresDmCallRepository {
+    pool: PgPool,
+}
+
+impl PostgresDmCallRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+const DM_CALL_COLUMNS: &str = "id, channel_id, started_by, started_at, ended_at";
+
+#[derive(sqlx::FromRow)]
+struct DmCallRow {
+    id: Uuid,
+    channel_id: Uuid,
+    started_by: Uuid,
+    started_at: DateTime<Utc>,
+    ended_at: Option<DateTime<Utc>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct DmCallParticipantRow {
+    user_id: Uuid,
+    status: String,
+    joined_at: Option<DateTime<Utc>>,
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::InternalServerError { message: e.to_string() }
+}
+
+/// Loads a call with its participants, joined ones first.
+async fn load_call(conn: &mut PgConnection, call_id: Uuid) -> Result<Option<DmCall>, CoreError> {
+    let Some(call) = sqlx::query_as::<_, DmCallRow>(&format!(
+        "SELECT {DM_CALL_COLUMNS} FROM dm_calls WHERE id = $1"
+    ))
+    .bind(call_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(|e| db_err("failed to load DM call", e))?
+    else {
+        return Ok(None);
+    };
+
+    let participants = sqlx::query_as::<_, DmCallParticipantRow>(
+        r#"
+        SELECT user_id, status, joined_at
+        FROM dm_call_participants
+        WHERE call_id = $1
+        ORDER BY joined_at ASC NULLS LAST, user_id
+        "#,
+    )
+    .bind(call_id)
+    .fetch_all(&mut *conn)
+    .await
+    .map_err(|e| db_err("failed to load DM call participants", e))?;
+
+    Ok(Some(DmCall {
+        id: call.id,
+        channel_id: call.channel_id,
+        started_by: call.started_by,
+        started_at: call.started_at,
+        ended_at: call.ended_at,
+        participants: participants
+            .into_iter()
+            .map(|row| {
+                let status = DmCallStatus::try_from(row.status.as_str()).map_err(|_| {
+                    CoreError::InternalServerError {
+                        message: format!("unknown DM call status: {}", row.status),
+                    }
+                })?;
+                Ok(DmCallParticipant {
+                    user_id: row.user_id,
+                    status,
+                    joined_at: row.joined_at,
+                })
+            })
+            .collect::<Result<_, CoreError>>()?,
+    }))
+}
+
+// ─── DmCallRepository impl ────────────────────────────────────────────────────
+
+impl DmCallRepository for PostgresDmCallRepository {
+    async fn list_participants(&self, channel_id: Uuid) -> Result<Vec<Uuid>, CoreError> {
+        let rows: Vec<(Uuid,)> =
+            sqlx::query_as("SELECT user_id FROM dm_participants WHERE channel_id = $1")
+                .bind(channel_id)
+                .fetch_all(&self.pool)
+                .await
+                .map_err(|e| db_err("failed to list DM participants", e))?;
+
+        Ok(rows.into_iter().map(|(id,)| id).collect())
+    }
+
+    async fn insert(&self, call: &DmCall) -> Result<Option<DmCall>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin DM call transaction", e))?;
+
+        let inserted: Option<(Uuid,)> = sqlx::query_as(
+            r#"
+            INSERT INTO dm_calls (id, channel_id, started_by, started_at, ended_at)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (channel_id) WHERE ended_at IS NULL DO NOTHING
+            RETURNING id
+            "#,
+        )
+        .bind(call.id)
+        .bind(call.channel_id)
+        .bind(call.started_by)
+        .bind(call.started_at)
+        .bind(call.ended_at)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert DM call", e))?;
+
+        if inserted.is_none() {
+            return Ok(None);
+        }
+
+        let user_ids: Vec<Uuid> = call.participants.iter().map(|p| p.user_id).collect();
+        let statuses: Vec<&str> = call.participants.iter().map(|p| p.status.as_str()).collect();
+        let joined_at: Vec<Option<DateTime<Utc>>> =
+            call.participants.iter().map(|p| p.joined_at).collect();
+
+        sqlx::query(
+            r#"
+            INSERT INTO dm_call_participants (call_id, user_id, status, joined_at)
+            SELECT $1, p.user_id, p.status, p.joined_at
+            FROM UNNEST($2::UUID[], $3::TEXT[], $4::TIMESTAMPTZ[]) AS p(user_id, status, joined_at)
+            "#,
+        )
+        .bind(call.id)
+        .bind(&user_ids)
+        .bind(&statuses)
+        .bind(&joined_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert DM call participants", e))?;
+
+        let call = load_call(&mut tx, call.id).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit DM call", e))?;
+
+        Ok(call)
+    }
+
+    async fn find_live(&self, channel_id: Uuid) -> Result<Option<DmCall>, CoreError> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| db_err("failed to acquire connection", e))?;
+
+        let id: Option<(Uuid,)> =
+            sqlx::query_as("SELECT id FROM dm_calls WHERE channel_id = $1 AND ended_at IS NULL")
+                .bind(channel_id)
+                .fetch_optional(&mut *conn)
+                .await
+                .map_err(|e| db_err("failed to find DM call", e))?;
+
+        match id {
+            Some((id,)) => load_call(&mut conn, id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn set_status(
+        &self,
+        call_id: Uuid,
+        user_id: Uuid,
+        from: &[DmCallStatus],
+        to: DmCallStatus,
+        at: DateTime<Utc>,
+    ) -> Result<Option<DmCall>, CoreError> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| db_err("failed to acquire connection", e))?;
+
+        let from: Vec<&str> = from.iter().map(|s| s.as_str()).collect();
+        let result = sqlx::query(
+            r#"
+            UPDATE dm_call_participants p
+            SET status = $4,
+                joined_at = CASE WHEN $4 = 'joined' THEN COALESCE(p.joined_at, $5) ELSE p.joined_at END
+            FROM dm_calls c
+            WHERE p.call_id = $1 AND p.user_id = $2 AND p.status = ANY($3)
+              AND c.id = p.call_id AND c.ended_at IS NULL
+            "#,
+        )
+        .bind(call_id)
+        .bind(user_id)
+        .bind(&from)
+        .bind(to.as_str())
+        .bind(at)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| db_err("failed to update DM call participant", e))?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        load_call(&mut conn, call_id).await
+    }
+
+    async fn end_if_idle(
+        &self,
+        call_id: Uuid,
+        ended_at: DateTime<Utc>,
+    ) -> Result<Option<(DmCall, Message)>, CoreError> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| db_err("failed to begin DM call transaction", e))?;
+
+        let ended: Option<(Uuid, Uuid)> = sqlx::query_as(
+            r#"
+            UPDATE dm_calls c
+            SET ended_at = $2
+            WHERE c.id = $1 AND c.ended_at IS NULL
+              AND (
+                  NOT EXISTS (
+                      SELECT 1 FROM dm_call_participants p
+                      WHERE p.call_id = c.id AND p.status = 'joined'
+                  )
+                  OR (
+                      NOT EXISTS (
+                          SELECT 1 FROM dm_call_participants p
+                          WHERE p.call_id = c.id AND p.status = 'ringing'
+                      )
+                      AND (
+                          SELECT COUNT(*) FROM dm_call_participants p
+                          WHERE p.call_id = c.id AND p.status = 'joined'
+                      ) < 2
+                  )
+              )
+            RETURNING c.channel_id, c.started_by
+            "#,
+        )
+        .bind(call_id)
+        .bind(ended_at)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to end DM call", e))?;
+
+        let Some((channel_id, started_by)) = ended else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE dm_call_participants
+            SET status = CASE status
+                WHEN 'ringing' THEN 'missed'
+                WHEN 'joined' THEN 'left'
+                ELSE status
+            END
+            WHERE call_id = $1
+            "#,
+        )
+        .bind(call_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to close DM call participants", e))?;
+
+        let message_id = Uuid::now_v7();
+        sqlx::query(
+            r#"
+            INSERT INTO messages (id, channel_id, author_id, content, kind, call_id, created_at)
+            VALUES ($1, $2, $3, '', 'call', $4, $5)
+            "#,
+        )
+        .bind(message_id)
+        .bind(channel_id)
+        .bind(started_by)
+        .bind(call_id)
+        .bind(ended_at)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| db_err("failed to insert DM call message", e))?;
+
+        let call = load_call(&mut tx, call_id)
+            .await?
+            .ok_or_else(|| CoreError::InternalServerError {
+                message: "ended DM call vanished".to_string(),
+            })?;
+
+        let row = sqlx::query_as::<_, MessageRow>(&format!("{} WHERE m.id = $1", SELECT_MESSAGES_SQL))
+            .bind(message_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| db_err("failed to load DM call message", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| db_err("failed to commit DM call end", e))?;
+
+        Ok(Some((call, row_to_message(row, Vec::new()))))
+    }
+
+    async fn expire_rings(&self, cutoff: DateTime<Utc>) -> Result<Vec<DmCall>, CoreError> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| db_err("failed to acquire connection", e))?;
+
+        let call_ids: Vec<(Uuid,)> = sqlx::query_as(
+            r#"
+            WITH expired AS (
+                UPDATE dm_call_participants p
+                SET status = 'missed'
+                FROM dm_calls c
+                WHERE p.status = 'ringing' AND c.id = p.call_id
+                  AND c.ended_at IS NULL AND c.started_at < $1
+                RETURNING p.call_id
+            )
+            SELECT DISTINCT call_id FROM expired
+            "#,
+        )
+        .bind(cutoff)
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| db_err("failed to expire DM call rings", e))?;
+
+        let mut calls = Vec::with_capacity(call_ids.len());
+        for (call_id,) in call_ids {
+            if let Some(call) = load_call(&mut conn, call_id).await? {
+                calls.push(call);
+            }
+        }
+        Ok(calls)
+    }
+}
@@ -4,7 +4,7 @@ use ferriscord_entities::{
     attachment::{Attachment, AttachmentId},
     channel::ChannelId,
     friendship::{DmChannel, FriendUser},
-    message::{Message, MessageAuthor, MessageId},
+    message::{Message, MessageAuthor, MessageCall, MessageId},
     user::UserId,
 };
 use sqlx::PgPool;
@@ -58,18 +58,30 @@ impl DmChannelRow {
 }
 
 #[derive(sqlx::FromRow)]
-struct MessageRow {
+pub(crate) struct MessageRow {
     id: Uuid,
     channel_id: Uuid,
     author_id: Uuid,
     author_username: String,
     author_avatar_url: Option<String>,
+    #[sqlx(default)]
+    author_bot: bool,
+    #[sqlx(default)]
+    kind: Option<String>,
     content: String,
     encrypted: bool,
     encryption_version: i32,
     sender_key_generation: Option<i32>,
     sender_device_id: Option<Uuid>,
     payload_sync_kind: Option<String>,
+    #[sqlx(default)]
+    call_id: Option<Uuid>,
+    #[sqlx(default)]
+    call_participants: Option<Vec<Uuid>>,
+    #[sqlx(default)]
+    call_started_at: Option<DateTime<Utc>>,
+    #[sqlx(default)]
+    call_ended_at: Option<DateTime<Utc>>,
     edited_at: Option<DateTime<Utc>>,
     created_at: DateTime<Utc>,
 }
@@ -108,7 +120,7 @@ struct DmHistorySyncJobRow {
     updated_at: DateTime<Utc>,
 }
 
-fn row_to_message(row: MessageRow, attachments: Vec<Attachment>) -> Message {
+pub(crate) fn row_to_message(row: MessageRow, attachments: Vec<Attachment>) -> Message {
     Message {
         id: MessageId(Id(row.id)),
         channel_id: ChannelId(Id(row.channel_id)),
@@ -116,14 +128,28 @@ fn row_to_message(row: MessageRow, attachments: Vec<Attachment>) -> Message {
             id: UserId::from(row.author_id),
             username: row.author_username,
             avatar_url: row.author_avatar_url,
+            bot: row.author_bot,
         },
+        kind: row.kind.as_deref().unwrap_or_default().into(),
         content: row.content,
         attachments,
+        // Webhooks only post into guild channels.
+        embeds: Vec::new(),
+        poll: None,
         encrypted: row.encrypted,
         encryption_version: row.encryption_version,
         sender_key_generation: row.sender_key_generation,
         sender_device_id: row.sender_device_id,
         payload_sync_kind: row.payload_sync_kind,
+        call: row.call_id.zip(row.call_started_at).map(|(call_id, started_at)| MessageCall {
+            call_id,
+            participants: row.call_participants.unwrap_or_default(),
+            started_at,
+            ended_at: row.call_ended_at,
+        }),
+        webhook_id: None,
+        reference: None,
+        crossposted: false,
         edited_at: row.edited_at,
         created_at: row.created_at,
     }
@@ -165,23 +191,34 @@ fn row_to_history_sync_job(row: DmHistorySyncJobRow) -> Result<DmHistorySyncJob,
     })
 }
 
-const SELECT_MESSAGES_SQL: &str = r#"
+pub(crate) const SELECT_MESSAGES_SQL: &str = r#"
     SELECT
         m.id,
         m.channel_id,
         m.author_id,
         u.username AS author_username,
         u.avatar_url AS author_avatar_url,
+        EXISTS (SELECT 1 FROM applications a WHERE a.bot_user_id = m.author_id) AS author_bot,
+        m.kind,
         m.content,
         m.encrypted,
         m.encryption_version,
         m.sender_key_generation,
         m.sender_device_id,
         NULL::TEXT AS payload_sync_kind,
+        c.id AS call_id,
+        ARRAY(
+            SELECT cp.user_id FROM dm_call_participants cp
+            WHERE cp.call_id = c.id AND cp.joined_at IS NOT NULL
+            ORDER BY cp.joined_at
+        ) AS call_participants,
+        c.started_at AS call_started_at,
+        c.ended_at AS call_ended_at,
         m.edited_at,
         m.created_at
     FROM messages m
     JOIN users u ON u.id = m.author_id
+    LEFT JOIN dm_calls c ON c.id = m.call_id
 "#;
 
 async fn fetch_message_payloads(
@@ -628,7 +665,7 @@ impl DmRepository for PostgresDmRepository {
         message_id: Uuid,
     ) -> Result<bool, CoreError> {
         let result = sqlx::query(
-            "DELETE FROM messages WHERE id = $1 AND channel_id = $2 AND author_id = (SELECT id FROM users WHERE oauth_sub = $3)",
+            "DELETE FROM messages WHERE id = $1 AND channel_id = $2 AND kind = 'default' AND author_id = (SELECT id FROM users WHERE oauth_sub = $3)",
         )
         .bind(message_id)
         .bind(channel_id)
@@ -1,3 +1,6 @@
+pub mod call;
 pub mod dm;
 pub mod friend;
 pub mod user;
+pub mod presence;
+pub mod read_state;
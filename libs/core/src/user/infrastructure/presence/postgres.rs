@@ -0,0 +1,251 @@
+use chrono::{DateTime, Utc};
+use ferriscord_entities::presence::{CustomStatus, PresenceStatus};
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::user::domain::{
+    common::CoreError,
+    presence::ports::{PresenceRepository, PresenceSession, UserPresenceRecord},
+};
+
+#[derive(Clone)]
+pub struct PostgresPresenceRepository {
+    pool: PgPool,
+}
+
+impl PostgresPresenceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+#[derive(sqlx::FromRow)]
+struct SessionRow {
+    id: Uuid,
+    user_id: Uuid,
+    status: String,
+    last_active_at: DateTime<Utc>,
+    heartbeat_at: DateTime<Utc>,
+}
+
+#[derive(sqlx::FromRow)]
+struct RecordRow {
+    user_id: Uuid,
+    custom_status_text: Option<String>,
+    custom_status_emoji: Option<String>,
+    custom_status_expires_at: Option<DateTime<Utc>>,
+    last_seen_at: Option<DateTime<Utc>>,
+}
+
+impl From<RecordRow> for UserPresenceRecord {
+    fn from(row: RecordRow) -> Self {
+        let custom_status = if row.custom_status_text.is_some() || row.custom_status_emoji.is_some()
+        {
+            Some(CustomStatus {
+                text: row.custom_status_text,
+                emoji: row.custom_status_emoji,
+                expires_at: row.custom_status_expires_at,
+            })
+        } else {
+            None
+        };
+
+        UserPresenceRecord {
+            user_id: row.user_id,
+            custom_status,
+            last_seen_at: row.last_seen_at,
+        }
+    }
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::InternalServerError { message: e.to_string() }
+}
+
+// ─── PresenceRepository impl ──────────────────────────────────────────────────
+
+impl PresenceRepository for PostgresPresenceRepository {
+    async fn upsert_session(
+        &self,
+        session_id: Uuid,
+        user_id: Uuid,
+        status: PresenceStatus,
+    ) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"
+            INSERT INTO presence_sessions (id, user_id, status, last_active_at, heartbeat_at)
+            VALUES ($1, $2, $3, now(), now())
+            ON CONFLICT (id) DO UPDATE
+                SET status = EXCLUDED.status, heartbeat_at = now()
+            "#,
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .bind(status.as_str())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to upsert presence session", e))?;
+
+        Ok(())
+    }
+
+    async fn touch_session(&self, session_id: Uuid, active: bool) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"
+            UPDATE presence_sessions
+            SET heartbeat_at = now(),
+                last_active_at = CASE WHEN $2 THEN now() ELSE last_active_at END
+            WHERE id = $1
+            "#,
+        )
+        .bind(session_id)
+        .bind(active)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to touch presence session", e))?;
+
+        Ok(())
+    }
+
+    async fn set_session_status(
+        &self,
+        session_id: Uuid,
+        status: PresenceStatus,
+    ) -> Result<(), CoreError> {
+        sqlx::query("UPDATE presence_sessions SET status = $2 WHERE id = $1")
+            .bind(session_id)
+            .bind(status.as_str())
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to set presence session status", e))?;
+
+        Ok(())
+    }
+
+    async fn delete_session(&self, session_id: Uuid) -> Result<(), CoreError> {
+        sqlx::query("DELETE FROM presence_sessions WHERE id = $1")
+            .bind(session_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete presence session", e))?;
+
+        Ok(())
+    }
+
+    async fn delete_stale_sessions(
+        &self,
+        user_id: Uuid,
+        before: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        sqlx::query("DELETE FROM presence_sessions WHERE user_id = $1 AND heartbeat_at < $2")
+            .bind(user_id)
+            .bind(before)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to delete stale presence sessions", e))?;
+
+        Ok(())
+    }
+
+    async fn list_sessions(&self, user_ids: &[Uuid]) -> Result<Vec<PresenceSession>, CoreError> {
+        let rows = sqlx::query_as::<_, SessionRow>(
+            r#"
+            SELECT id, user_id, status, last_active_at, heartbeat_at
+            FROM presence_sessions
+            WHERE user_id = ANY($1)
+            "#,
+        )
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list presence sessions", e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| PresenceSession {
+                id: row.id,
+                user_id: row.user_id,
+                status: PresenceStatus::try_from(row.status.as_str())
+                    .unwrap_or(PresenceStatus::Online),
+                last_active_at: row.last_active_at,
+                heartbeat_at: row.heartbeat_at,
+            })
+            .collect())
+    }
+
+    async fn list_records(&self, user_ids: &[Uuid]) -> Result<Vec<UserPresenceRecord>, CoreError> {
+        let rows = sqlx::query_as::<_, RecordRow>(
+            r#"
+            SELECT user_id, custom_status_text, custom_status_emoji,
+                   custom_status_expires_at, last_seen_at
+            FROM user_presences
+            WHERE user_id = ANY($1)
+            "#,
+        )
+        .bind(user_ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to list user presences", e))?;
+
+        Ok(rows.into_iter().map(UserPresenceRecord::from).collect())
+    }
+
+    async fn set_custom_status(
+        &self,
+        user_id: Uuid,
+        custom_status: Option<CustomStatus>,
+    ) -> Result<(), CoreError> {
+        let (text, emoji, expires_at) = match custom_status {
+            Some(c) => (c.text, c.emoji, c.expires_at),
+            None => (None, None, None),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO user_presences
+                (user_id, custom_status_text, custom_status_emoji, custom_status_expires_at, updated_at)
+            VALUES ($1, $2, $3, $4, now())
+            ON CONFLICT (user_id) DO UPDATE
+                SET custom_status_text = EXCLUDED.custom_status_text,
+                    custom_status_emoji = EXCLUDED.custom_status_emoji,
+                    custom_status_expires_at = EXCLUDED.custom_status_expires_at,
+                    updated_at = now()
+            "#,
+        )
+        .bind(user_id)
+        .bind(text)
+        .bind(emoji)
+        .bind(expires_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to set custom status", e))?;
+
+        Ok(())
+    }
+
+    async fn set_last_seen(
+        &self,
+        user_id: Uuid,
+        last_seen_at: DateTime<Utc>,
+    ) -> Result<(), CoreError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_presences (user_id, last_seen_at, updated_at)
+            VALUES ($1, $2, now())
+            ON CONFLICT (user_id) DO UPDATE
+                SET last_seen_at = EXCLUDED.last_seen_at, updated_at = now()
+            "#,
+        )
+        .bind(user_id)
+        .bind(last_seen_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to set last seen", e))?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use ferriscord_entities::read_state::ReadState;
+use sqlx::PgPool;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::user::domain::{common::CoreError, read_state::ports::ReadStateRepository};
+
+#[derive(Clone)]
+pub struct PostgresReadStateRepository {
+    pool: PgPool,
+}
+
+impl PostgresReadStateRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+// ─── Row types ────────────────────────────────────────────────────────────────
+
+#[derive(sqlx::FromRow)]
+struct ReadStateRow {
+    channel_id: Uuid,
+    guild_id: Option<Uuid>,
+    last_read_message_id: Option<Uuid>,
+    last_message_id: Option<Uuid>,
+    mention_count: i32,
+    unread: bool,
+}
+
+impl From<ReadStateRow> for ReadState {
+    fn from(row: ReadStateRow) -> Self {
+        ReadState {
+            channel_id: row.channel_id,
+            guild_id: row.guild_id,
+            last_read_message_id: row.last_read_message_id,
+            last_message_id: row.last_message_id,
+            mention_count: row.mention_count,
+            unread: row.unread,
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MentionRow {
+    user_id: Uuid,
+    last_read_message_id: Option<Uuid>,
+    mention_count: i32,
+}
+
+fn db_err(context: &str, e: sqlx::Error) -> CoreError {
+    error!("{}: {}", context, e);
+    CoreError::InternalServerError { message: e.to_string() }
+}
+
+/// Read states for `user_id` ($1) in `channel_ids` ($2), with the latest
+/// message of each channel.
+const LIST_QUERY: &str = r#"
+    SELECT c.id AS channel_id,
+           c.guild_id,
+           rs.last_message_id AS last_read_message_id,
+           latest.id AS last_message_id,
+           COALESCE(rs.mention_count, 0) AS mention_count,
+           (latest.id IS NOT NULL
+               AND (rs.last_read_at IS NULL OR latest.created_at > rs.last_read_at)) AS unread
+    FROM channels c
+    LEFT JOIN read_states rs ON rs.channel_id = c.id AND rs.user_id = $1
+    LEFT JOIN LATERAL (
+        SELECT m.id, m.created_at
+        FROM messages m
+        WHERE m.channel_id = c.id
+        ORDER BY m.created_at DESC
+        LIMIT 1
+    ) latest ON TRUE
+    WHERE c.id = ANY($2)
+"#;
+
+// ─── ReadStateRepository impl ─────────────────────────────────────────────────
+
+impl ReadStateRepository for PostgresReadStateRepository {
+    async fn ack(
+        &self,
+        user_id: Uuid,
+        guild_id: Option<Uuid>,
+        channel_id: Uuid,
+        message_id: Uuid,
+    ) -> Result<Option<ReadState>, CoreError> {
+        // Mentions after the acked message are recounted so that acking an
+        // older message ("mark unread") keeps the badge consistent. In DMs
+        // every message from someone else counts as a mention.
+        let acked: Option<(Uuid,)> = sqlx::query_as(
+            r#"
+            WITH target AS (
+                SELECT m.id, m.created_at, c.guild_id
+                FROM messages m
+                JOIN channels c ON c.id = m.channel_id
+                WHERE m.id = $4
+                  AND m.channel_id = $3
+                  AND c.guild_id IS NOT DISTINCT FROM $2
+                  AND (c.guild_id IS NOT NULL OR EXISTS (
+                      SELECT 1 FROM dm_participants p
+                      WHERE p.channel_id = c.id AND p.user_id = $1
+                  ))
+            )
+            INSERT INTO read_states (user_id, channel_id, last_message_id, last_read_at, mention_count, updated_at)
+            SELECT $1, $3, t.id, t.created_at,
+                   (SELECT COUNT(*)
+                    FROM messages later
+                    WHERE later.channel_id = $3
+                      AND later.created_at > t.created_at
+                      AND later.author_id IS DISTINCT FROM $1
+                      AND (t.guild_id IS NULL
+                           OR later.content LIKE '%<@' || $1::text || '>%'))::int,
+                   now()
+            FROM target t
+            ON CONFLICT (user_id, channel_id) DO UPDATE
+                SET last_message_id = EXCLUDED.last_message_id,
+                    last_read_at = EXCLUDED.last_read_at,
+                    mention_count = EXCLUDED.mention_count,
+                    updated_at = now()
+            RETURNING channel_id
+            "#,
+        )
+        .bind(user_id)
+        .bind(guild_id)
+        .bind(channel_id)
+        .bind(message_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to ack message", e))?;
+
+        if acked.is_none() {
+            return Ok(None);
+        }
+
+        Ok(self.list(user_id, &[channel_id]).await?.into_iter().next())
+    }
+
+    async fn list(&self, user_id: Uuid, channel_ids: &[Uuid]) -> Result<Vec<ReadState>, CoreError> {
+        let rows = sqlx::query_as::<_, ReadStateRow>(LIST_QUERY)
+            .bind(user_id)
+            .bind(channel_ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| db_err("failed to list read states", e))?;
+
+        Ok(rows.into_iter().map(ReadState::from).collect())
+    }
+
+    async fn increment_guild_mentions(
+        &self,
+        guild_id: Uuid,
+        channel_id: Uuid,
+        message_id: Uuid,
+        author_id: Option<Uuid>,
+        user_ids: &[Uuid],
+    ) -> Result<Vec<(Uuid, ReadState)>, CoreError> {
+        let rows = sqlx::query_as::<_, MentionRow>(
+            r#"
+            INSERT INTO read_states (user_id, channel_id, mention_count, updated_at)
+            SELECT m.user_id, $2, 1, now()
+            FROM members m
+            WHERE m.guild_id = $1 AND m.user_id = ANY($3) AND m.user_id IS DISTINCT FROM $4
+            ON CONFLICT (user_id, channel_id) DO UPDATE
+                SET mention_count = read_states.mention_count + 1, updated_at = now()
+            RETURNING user_id, last_message_id AS last_read_message_id, mention_count
+            "#,
+        )
+        .bind(guild_id)
+        .bind(channel_id)
+        .bind(user_ids)
+        .bind(author_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to increment mentions", e))?;
+
+        Ok(mention_states(rows, Some(guild_id), channel_id, message_id))
+    }
+
+    async fn increment_dm_mentions(
+        &self,
+        channel_id: Uuid,
+        message_id: Uuid,
+        author_id: Uuid,
+    ) -> Result<Vec<(Uuid, ReadState)>, CoreError> {
+        let rows = sqlx::query_as::<_, MentionRow>(
+            r#"
+            INSERT INTO read_states (user_id, channel_id, mention_count, updated_at)
+            SELECT p.user_id, $1, 1, now()
+            FROM dm_participants p
+            WHERE p.channel_id = $1 AND p.user_id <> $2
+            ON CONFLICT (user_id, channel_id) DO UPDATE
+                SET mention_count = read_states.mention_count + 1, updated_at = now()
+            RETURNING user_id, last_message_id AS last_read_message_id, mention_count
+            "#,
+        )
+        .bind(channel_id)
+        .bind(author_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| db_err("failed to increment DM mentions", e))?;
+
+        Ok(mention_states(rows, None, channel_id, message_id))
+    }
+}
+
+fn mention_states(
+    rows: Vec<MentionRow>,
+    guild_id: Option<Uuid>,
+    channel_id: Uuid,
+    message_id: Uuid,
+) -> Vec<(Uuid, ReadState)> {
+    rows.into_iter()
+        .map(|row| {
+            (
+                row.user_id,
+                ReadState {
+                    channel_id,
+                    guild_id,
+                    last_read_message_id: row.last_read_message_id,
+                    last_message_id: Some(message_id),
+                    mention_count: row.mention_count,
+                    unread: true,
+                },
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A developer application and the bot user it acts through. The bot
+/// authenticates with `Authorization: Bot <token>`, independently of the
+/// identity provider.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Application {
+    pub id: Uuid,
+    /// Also the bot user's username.
+    pub name: String,
+    pub description: Option<String>,
+    pub owner_id: Uuid,
+    pub bot_user_id: Uuid,
+    pub created_at: DateTime<Utc>,
+    /// Where interactions are POSTed. Without one they are delivered over
+    /// the bot's gateway connection.
+    pub interactions_endpoint_url: Option<String>,
+    /// Secret interaction deliveries are signed with.
+    pub interactions_secret: Option<String>,
+    /// Bot token. Only returned when the application is created or its token
+    /// is reset; the server keeps a hash.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bot_token: Option<String>,
+}
+
+/// A bot added to a guild.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct GuildBot {
+    pub guild_id: Uuid,
+    pub application_id: Uuid,
+    pub bot_user_id: Uuid,
+    pub username: String,
+    /// Role holding the permissions requested when the bot was added. `None`
+    /// if none were requested or the role was deleted since.
+    pub role_id: Option<Uuid>,
+    pub added_by: Option<Uuid>,
+    pub added_at: DateTime<Utc>,
+}
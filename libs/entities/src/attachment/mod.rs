@@ -48,3 +48,24 @@ pub struct Attachment {
     pub encrypted: bool,
     pub created_at: DateTime<Utc>,
 }
+
+// ─── AttachmentUpload ─────────────────────────────────────────────────────────
+
+/// A file to upload straight to storage, then attach to a message in its
+/// channel before the slot expires. Its id becomes the attachment's.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AttachmentUpload {
+    pub id: Uuid,
+    pub channel_id: Uuid,
+    pub filename: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// S3 key — never serialized in API responses.
+    #[serde(skip_serializing)]
+    pub storage_key: String,
+    /// Pre-signed URL to `PUT` the file to, with the declared
+    /// `Content-Type`, populated by the handler.
+    pub upload_url: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
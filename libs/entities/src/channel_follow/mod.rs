@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A channel following an announcement channel of another guild. Messages
+/// published in the source channel are copied into the following channel.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ChannelFollow {
+    pub id: Uuid,
+    pub source_guild_id: Uuid,
+    pub source_guild_name: String,
+    pub source_channel_id: Uuid,
+    pub source_channel_name: String,
+    /// Guild of the following channel.
+    pub guild_id: Uuid,
+    /// The following channel.
+    pub channel_id: Uuid,
+    /// `None` once the creator's account is gone.
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,84 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DmCallStatus {
+    /// Being rung and has not answered yet.
+    Ringing,
+    /// In the call.
+    Joined,
+    Declined,
+    /// The ring timed out.
+    Missed,
+    /// Was in the call and hung up.
+    Left,
+}
+
+impl DmCallStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ringing => "ringing",
+            Self::Joined => "joined",
+            Self::Declined => "declined",
+            Self::Missed => "missed",
+            Self::Left => "left",
+        }
+    }
+}
+
+impl TryFrom<&str> for DmCallStatus {
+    type Error = ();
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "ringing" => Ok(Self::Ringing),
+            "joined" => Ok(Self::Joined),
+            "declined" => Ok(Self::Declined),
+            "missed" => Ok(Self::Missed),
+            "left" => Ok(Self::Left),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DmCallParticipant {
+    pub user_id: Uuid,
+    pub status: DmCallStatus,
+    /// When the user first joined the call, if they did.
+    pub joined_at: Option<DateTime<Utc>>,
+}
+
+/// A call in a DM channel. Every participant of the channel is part of the
+/// call; those who have not answered are rung.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DmCall {
+    pub id: Uuid,
+    pub channel_id: Uuid,
+    /// User who started the call.
+    pub started_by: Uuid,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the call is live.
+    pub ended_at: Option<DateTime<Utc>>,
+    pub participants: Vec<DmCallParticipant>,
+}
+
+impl DmCall {
+    pub fn is_live(&self) -> bool {
+        self.ended_at.is_none()
+    }
+
+    pub fn participant(&self, user_id: Uuid) -> Option<&DmCallParticipant> {
+        self.participants.iter().find(|p| p.user_id == user_id)
+    }
+
+    /// Users currently being rung.
+    pub fn ringing(&self) -> impl Iterator<Item = Uuid> + '_ {
+        self.participants
+            .iter()
+            .filter(|p| p.status == DmCallStatus::Ringing)
+            .map(|p| p.user_id)
+    }
+}
@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A custom emoji of a guild. Messages use it as `<:name:id>`, or
+/// `<a:name:id>` when animated.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct GuildEmoji {
+    pub id: Uuid,
+    pub guild_id: Uuid,
+    pub name: String,
+    pub animated: bool,
+    /// Roles allowed to use the emoji. Empty when everyone may.
+    pub roles: Vec<Uuid>,
+    /// `None` once the uploader's account is gone.
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// S3 key — never serialized in API responses.
+    #[serde(skip_serializing, default)]
+    pub storage_key: String,
+    /// Pre-signed URL of the image, populated by the handler.
+    #[serde(default)]
+    pub url: String,
+}
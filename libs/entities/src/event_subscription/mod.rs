@@ -0,0 +1,150 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Guild activity an event subscription can ask to receive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum GuildEventType {
+    #[serde(rename = "message.create")]
+    MessageCreate,
+    #[serde(rename = "message.update")]
+    MessageUpdate,
+    #[serde(rename = "message.delete")]
+    MessageDelete,
+    #[serde(rename = "member.join")]
+    MemberJoin,
+    #[serde(rename = "member.leave")]
+    MemberLeave,
+    /// A member's roles changed.
+    #[serde(rename = "member.update")]
+    MemberUpdate,
+    #[serde(rename = "role.create")]
+    RoleCreate,
+    #[serde(rename = "role.update")]
+    RoleUpdate,
+    #[serde(rename = "role.delete")]
+    RoleDelete,
+    #[serde(rename = "channel.create")]
+    ChannelCreate,
+    #[serde(rename = "channel.update")]
+    ChannelUpdate,
+    #[serde(rename = "channel.delete")]
+    ChannelDelete,
+}
+
+impl GuildEventType {
+    pub const ALL: [GuildEventType; 12] = [
+        Self::MessageCreate,
+        Self::MessageUpdate,
+        Self::MessageDelete,
+        Self::MemberJoin,
+        Self::MemberLeave,
+        Self::MemberUpdate,
+        Self::RoleCreate,
+        Self::RoleUpdate,
+        Self::RoleDelete,
+        Self::ChannelCreate,
+        Self::ChannelUpdate,
+        Self::ChannelDelete,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MessageCreate => "message.create",
+            Self::MessageUpdate => "message.update",
+            Self::MessageDelete => "message.delete",
+            Self::MemberJoin => "member.join",
+            Self::MemberLeave => "member.leave",
+            Self::MemberUpdate => "member.update",
+            Self::RoleCreate => "role.create",
+            Self::RoleUpdate => "role.update",
+            Self::RoleDelete => "role.delete",
+            Self::ChannelCreate => "channel.create",
+            Self::ChannelUpdate => "channel.update",
+            Self::ChannelDelete => "channel.delete",
+        }
+    }
+}
+
+impl TryFrom<&str> for GuildEventType {
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.as_str() == s)
+            .ok_or("unknown guild event type")
+    }
+}
+
+/// An HTTP endpoint that receives a guild's events as signed POSTs.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct EventSubscription {
+    pub id: Uuid,
+    pub guild_id: Uuid,
+    pub url: String,
+    pub event_types: Vec<GuildEventType>,
+    /// Cleared automatically after repeated delivery failures.
+    pub enabled: bool,
+    /// Failed attempts since the last successful delivery.
+    pub consecutive_failures: i32,
+    pub disabled_reason: Option<String>,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// HMAC signing secret. Only returned when the subscription is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EventDeliveryStatus {
+    /// Waiting for its first attempt or a retry.
+    Pending,
+    Succeeded,
+    /// Out of retries, or the subscription was disabled.
+    Failed,
+}
+
+impl EventDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl TryFrom<&str> for EventDeliveryStatus {
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "succeeded" => Ok(Self::Succeeded),
+            "failed" => Ok(Self::Failed),
+            _ => Err("unknown event delivery status"),
+        }
+    }
+}
+
+/// One event sent (or to be sent) to one subscription; the delivery log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct EventDelivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    /// A `GuildEventType`, or `ping` for test deliveries.
+    pub event_type: String,
+    /// The exact JSON body that is signed and posted.
+    pub body: String,
+    pub status: EventDeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status of the last attempt, if the endpoint answered.
+    pub last_status_code: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
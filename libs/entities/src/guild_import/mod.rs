@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GuildImportStatus {
+    /// Waiting for the exported channels, until it is started.
+    Uploading,
+    /// Started, waiting for a worker.
+    Pending,
+    Running,
+    Completed,
+    /// See `failure_reason`.
+    Failed,
+    Canceled,
+}
+
+impl GuildImportStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Uploading => "uploading",
+            Self::Pending => "pending",
+            Self::Running => "running",
+            Self::Completed => "completed",
+            Self::Failed => "failed",
+            Self::Canceled => "canceled",
+        }
+    }
+
+    /// Whether the import is over, one way or another.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, Self::Completed | Self::Failed | Self::Canceled)
+    }
+}
+
+impl TryFrom<&str> for GuildImportStatus {
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "uploading" => Ok(Self::Uploading),
+            "pending" => Ok(Self::Pending),
+            "running" => Ok(Self::Running),
+            "completed" => Ok(Self::Completed),
+            "failed" => Ok(Self::Failed),
+            "canceled" => Ok(Self::Canceled),
+            _ => Err("unknown guild import status"),
+        }
+    }
+}
+
+/// What an import found in the export and how far it got. In a dry run,
+/// the counts are what a real import would create.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct GuildImportProgress {
+    pub categories: u32,
+    pub channels: u32,
+    pub roles: u32,
+    /// Authors mapped to existing users.
+    pub mapped_users: u32,
+    /// Authors recreated as placeholder users.
+    pub placeholder_users: u32,
+    pub messages_total: u64,
+    pub messages_imported: u64,
+    /// System messages (joins, pins, …), which are not imported.
+    pub messages_skipped: u64,
+    pub attachments_imported: u64,
+    /// Attachments that could not be downloaded or are too large.
+    pub attachments_skipped: u64,
+}
+
+/// A guild being recreated from a Discord export, by its future owner.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct GuildImport {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// The name of the guild, by default that of the exported one.
+    pub name: Option<String>,
+    /// A dry run only checks and counts what would be imported.
+    pub dry_run: bool,
+    pub status: GuildImportStatus,
+    /// The created guild, once it exists.
+    pub guild_id: Option<Uuid>,
+    pub progress: GuildImportProgress,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
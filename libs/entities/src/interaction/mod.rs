@@ -0,0 +1,171 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{embed::Embed, message::MessageAuthor};
+
+// ─── Commands ────────────────────────────────────────────────────────────────
+
+/// Type of value a command option takes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandOptionType {
+    String,
+    Integer,
+    Number,
+    Boolean,
+    /// A user ID.
+    User,
+    /// A channel ID.
+    Channel,
+    /// A role ID.
+    Role,
+}
+
+/// Value of a command option. IDs are passed as strings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum CommandOptionValue {
+    Boolean(bool),
+    Integer(i64),
+    Number(f64),
+    String(String),
+}
+
+/// A fixed value the invoker picks from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CommandOptionChoice {
+    pub name: String,
+    pub value: CommandOptionValue,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct CommandOption {
+    pub name: String,
+    pub description: String,
+    #[serde(rename = "type")]
+    pub kind: CommandOptionType,
+    #[serde(default)]
+    pub required: bool,
+    /// When set, the value must be one of these.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub choices: Vec<CommandOptionChoice>,
+}
+
+/// A slash command registered by an application, either globally (usable in
+/// every guild the bot is in) or for one guild.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ApplicationCommand {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    /// `None` for global commands.
+    pub guild_id: Option<Uuid>,
+    pub name: String,
+    pub description: String,
+    pub options: Vec<CommandOption>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ─── Interactions ────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InteractionStatus {
+    /// Waiting for the bot's initial response.
+    Pending,
+    /// Answered with a message.
+    Responded,
+    /// Acknowledged; the answer follows as a follow-up.
+    Deferred,
+    /// The bot did not respond in time or its endpoint failed.
+    Failed,
+}
+
+impl InteractionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Responded => "responded",
+            Self::Deferred => "deferred",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl TryFrom<&str> for InteractionStatus {
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "responded" => Ok(Self::Responded),
+            "deferred" => Ok(Self::Deferred),
+            "failed" => Ok(Self::Failed),
+            _ => Err("unknown interaction status"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct InteractionOption {
+    pub name: String,
+    pub value: CommandOptionValue,
+}
+
+/// A member's invocation of a slash command, as delivered to the bot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct Interaction {
+    pub id: Uuid,
+    pub application_id: Uuid,
+    pub command_id: Option<Uuid>,
+    pub command_name: String,
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    /// The invoking member.
+    pub user_id: Uuid,
+    pub username: String,
+    pub options: Vec<InteractionOption>,
+    pub status: InteractionStatus,
+    /// Whether the answer is shown to the invoker only.
+    pub ephemeral: bool,
+    pub created_at: DateTime<Utc>,
+    /// Token the bot answers with. Only sent to the bot.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// A bot's answer to an interaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InteractionCallback {
+    /// Answers with a message, posted in the channel or shown to the invoker
+    /// only.
+    Message {
+        #[serde(default)]
+        content: String,
+        #[serde(default)]
+        embeds: Vec<Embed>,
+        #[serde(default)]
+        ephemeral: bool,
+    },
+    /// Acknowledges the interaction; the answer is sent later as a follow-up.
+    Deferred {
+        #[serde(default)]
+        ephemeral: bool,
+    },
+}
+
+/// A reply shown to the invoker only. Never stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct EphemeralMessage {
+    pub interaction_id: Uuid,
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    pub author: MessageAuthor,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub embeds: Vec<Embed>,
+    pub created_at: DateTime<Utc>,
+}
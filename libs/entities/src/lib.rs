@@ -5,16 +5,32 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::{NoContext, Timestamp, Uuid};
 
+pub mod application;
 pub mod attachment;
 pub mod channel;
+pub mod channel_follow;
 pub mod crypto;
+pub mod dm_call;
+pub mod embed;
+pub mod emoji;
+pub mod event_subscription;
 pub mod friendship;
 pub mod guild;
+pub mod guild_import;
+pub mod interaction;
 pub mod invite;
 pub mod member;
 pub mod message;
+pub mod poll;
+pub mod presence;
+pub mod read_state;
 pub mod role;
+pub mod scheduled_event;
+pub mod scheduled_message;
+pub mod stage_instance;
 pub mod user;
+pub mod voice_state;
+pub mod webhook;
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
 pub struct Id(pub Uuid);
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::{attachment::Attachment, channel::ChannelId, user::UserId, Id};
+use crate::{attachment::Attachment, channel::ChannelId, embed::Embed, poll::Poll, user::UserId, Id};
 
 // ─── MessageId ───────────────────────────────────────────────────────────────
 
@@ -36,9 +36,61 @@ impl std::fmt::Display for MessageId {
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
 pub struct MessageAuthor {
+    /// For webhook messages, the webhook's id.
     pub id: UserId,
     pub username: String,
     pub avatar_url: Option<String>,
+    /// Set for messages sent by an application's bot user.
+    #[serde(default)]
+    pub bot: bool,
+}
+
+// ─── MessageKind ──────────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
+    /// Sent by a user.
+    #[default]
+    Default,
+    /// System record of a DM call that ended. The author started the call.
+    Call,
+}
+
+impl MessageKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Call => "call",
+        }
+    }
+}
+
+impl From<&str> for MessageKind {
+    fn from(s: &str) -> Self {
+        match s {
+            "call" => Self::Call,
+            _ => Self::Default,
+        }
+    }
+}
+
+/// Summary of the call a `call` message records.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct MessageCall {
+    pub call_id: Uuid,
+    /// Users who joined the call.
+    pub participants: Vec<Uuid>,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+/// Where a crossposted copy was published from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct MessageReference {
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    pub message_id: Uuid,
 }
 
 // ─── Message ─────────────────────────────────────────────────────────────────
@@ -48,8 +100,14 @@ pub struct Message {
     pub id: MessageId,
     pub channel_id: ChannelId,
     pub author: MessageAuthor,
+    #[serde(default)]
+    pub kind: MessageKind,
     pub content: String,
     pub attachments: Vec<Attachment>,
+    #[serde(default)]
+    pub embeds: Vec<Embed>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub poll: Option<Poll>,
     pub encrypted: bool,
     pub encryption_version: i32,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -58,6 +116,17 @@ pub struct Message {
     pub sender_device_id: Option<Uuid>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payload_sync_kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call: Option<MessageCall>,
+    /// Set when the message was posted through a webhook.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub webhook_id: Option<Uuid>,
+    /// Set on the copies of a published announcement.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reference: Option<MessageReference>,
+    /// Set once an announcement was published to its followers.
+    #[serde(default)]
+    pub crossposted: bool,
     pub edited_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
 }
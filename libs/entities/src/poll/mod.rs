@@ -0,0 +1,44 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct PollAnswer {
+    /// Position of the answer, from 1.
+    pub id: i32,
+    pub text: String,
+    /// `None` while the poll hides its results.
+    pub votes: Option<i64>,
+}
+
+/// A poll attached to a message. Its results are frozen when it closes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Poll {
+    pub question: String,
+    pub answers: Vec<PollAnswer>,
+    pub allow_multiselect: bool,
+    /// Results are only shown once the poll closed.
+    pub hide_results: bool,
+    pub expires_at: DateTime<Utc>,
+    /// Set once the poll is closed and its results are final.
+    pub closed: bool,
+    /// Members who voted. `None` while the poll hides its results.
+    pub total_voters: Option<i64>,
+    /// Answers the caller voted for. Only filled in replies to the caller,
+    /// never in broadcast messages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub my_votes: Vec<i32>,
+}
+
+impl Poll {
+    /// Drops the counts of an open poll that hides its results.
+    pub fn hide_open_results(mut self) -> Self {
+        if self.hide_results && !self.closed {
+            self.total_voters = None;
+            for answer in &mut self.answers {
+                answer.votes = None;
+            }
+        }
+        self
+    }
+}
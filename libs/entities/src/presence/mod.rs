@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+// ─── PresenceStatus ──────────────────────────────────────────────────────────
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceStatus {
+    Online,
+    Idle,
+    DoNotDisturb,
+    #[default]
+    Offline,
+}
+
+impl PresenceStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Online => "online",
+            Self::Idle => "idle",
+            Self::DoNotDisturb => "do_not_disturb",
+            Self::Offline => "offline",
+        }
+    }
+}
+
+impl TryFrom<&str> for PresenceStatus {
+    type Error = ();
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "online" => Ok(Self::Online),
+            "idle" => Ok(Self::Idle),
+            "do_not_disturb" => Ok(Self::DoNotDisturb),
+            "offline" => Ok(Self::Offline),
+            _ => Err(()),
+        }
+    }
+}
+
+// ─── CustomStatus ────────────────────────────────────────────────────────────
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CustomStatus {
+    pub text: Option<String>,
+    pub emoji: Option<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl CustomStatus {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|at| at <= now)
+    }
+}
+
+// ─── Presence ────────────────────────────────────────────────────────────────
+
+/// Aggregated presence of a user across all of their live gateway sessions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Presence {
+    pub user_id: Uuid,
+    pub status: PresenceStatus,
+    pub custom_status: Option<CustomStatus>,
+    pub last_seen_at: Option<DateTime<Utc>>,
+}
+
+impl Presence {
+    pub fn offline(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            status: PresenceStatus::Offline,
+            custom_status: None,
+            last_seen_at: None,
+        }
+    }
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// What a user has read in one channel (guild text channel or DM).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ReadState {
+    pub channel_id: Uuid,
+    /// `None` for DM channels.
+    pub guild_id: Option<Uuid>,
+    /// Last message acknowledged by the user.
+    pub last_read_message_id: Option<Uuid>,
+    /// Latest message in the channel.
+    pub last_message_id: Option<Uuid>,
+    /// Unread messages mentioning the user. Every DM message counts as a mention.
+    pub mention_count: i32,
+    pub unread: bool,
+}
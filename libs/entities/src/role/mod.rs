@@ -55,7 +55,11 @@ impl Role {
         Self::new(
             guild_id,
             "@everyone".to_string(),
-            Permissions::VIEW_GUILD | Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES,
+            Permissions::VIEW_GUILD
+                | Permissions::VIEW_CHANNEL
+                | Permissions::SEND_MESSAGES
+                | Permissions::CONNECT
+                | Permissions::SPEAK,
         )
     }
 }
@@ -124,7 +128,7 @@ impl PermissionContext {
     fn compute_base_permissions(&self) -> Permissions {
         // Sort roles by position (higher position = higher priority)
         let mut sorted_roles = self.roles.clone();
-        sorted_roles.sort_by(|a, b| a.position.cmp(&b.position));
+        sorted_roles.sort_by_key(|r| r.position);
 
         let mut permissions = Permissions::empty();
 
@@ -150,7 +154,7 @@ impl PermissionContext {
 
         // Apply role overrides first (in position order)
         let mut sorted_roles = self.roles.clone();
-        sorted_roles.sort_by(|a, b| a.position.cmp(&b.position));
+        sorted_roles.sort_by_key(|r| r.position);
 
         for role in &sorted_roles {
             if let Some(overrides) = self.channel_overrides.get(&role.id.to_string()) {
@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledEventStatus {
+    Scheduled,
+    /// Started, and not over yet.
+    Active,
+    Completed,
+    Canceled,
+}
+
+impl ScheduledEventStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Scheduled => "scheduled",
+            Self::Active => "active",
+            Self::Completed => "completed",
+            Self::Canceled => "canceled",
+        }
+    }
+
+    /// Whether the event is still to come or running.
+    pub fn is_open(&self) -> bool {
+        matches!(self, Self::Scheduled | Self::Active)
+    }
+}
+
+impl TryFrom<&str> for ScheduledEventStatus {
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "scheduled" => Ok(Self::Scheduled),
+            "active" => Ok(Self::Active),
+            "completed" => Ok(Self::Completed),
+            "canceled" => Ok(Self::Canceled),
+            _ => Err("unknown scheduled event status"),
+        }
+    }
+}
+
+/// Where an event takes place.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ScheduledEventLocation {
+    /// A voice or stage channel of the guild.
+    Channel { channel_id: Uuid },
+    /// Anywhere else, described in free text.
+    External { location: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RsvpStatus {
+    Interested,
+    Going,
+}
+
+impl RsvpStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Interested => "interested",
+            Self::Going => "going",
+        }
+    }
+}
+
+impl TryFrom<&str> for RsvpStatus {
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "interested" => Ok(Self::Interested),
+            "going" => Ok(Self::Going),
+            _ => Err("unknown rsvp status"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct GuildScheduledEvent {
+    pub id: Uuid,
+    pub guild_id: Uuid,
+    /// `None` once the creator's account is deleted.
+    pub creator_id: Option<Uuid>,
+    pub title: String,
+    pub description: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+    pub location: ScheduledEventLocation,
+    pub status: ScheduledEventStatus,
+    pub interested_count: i64,
+    pub going_count: i64,
+    /// The caller's answer. Only filled in replies to the caller.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub my_rsvp: Option<RsvpStatus>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl GuildScheduledEvent {
+    pub fn channel_id(&self) -> Option<Uuid> {
+        match self.location {
+            ScheduledEventLocation::Channel { channel_id } => Some(channel_id),
+            ScheduledEventLocation::External { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct EventRsvp {
+    pub user_id: Uuid,
+    pub status: RsvpStatus,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledMessageKind {
+    /// Posted in its channel as a message of its author.
+    Message,
+    /// Sent privately to its author, about a message.
+    Reminder,
+}
+
+impl ScheduledMessageKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Message => "message",
+            Self::Reminder => "reminder",
+        }
+    }
+}
+
+impl TryFrom<&str> for ScheduledMessageKind {
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "message" => Ok(Self::Message),
+            "reminder" => Ok(Self::Reminder),
+            _ => Err("unknown scheduled message kind"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ScheduledMessageStatus {
+    Pending,
+    Delivered,
+    /// The author could no longer send it, see `failure_reason`.
+    Failed,
+}
+
+impl ScheduledMessageStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+impl TryFrom<&str> for ScheduledMessageStatus {
+    type Error = &'static str;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "delivered" => Ok(Self::Delivered),
+            "failed" => Ok(Self::Failed),
+            _ => Err("unknown scheduled message status"),
+        }
+    }
+}
+
+/// A message queued by a member for later: either posted in a channel or
+/// delivered to the member as a reminder.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ScheduledMessage {
+    pub id: Uuid,
+    pub kind: ScheduledMessageKind,
+    pub user_id: Uuid,
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    /// The message a reminder is about, or the message a scheduled message
+    /// was posted as.
+    pub message_id: Option<Uuid>,
+    /// The text to post, or the note of a reminder.
+    pub content: String,
+    pub send_at: DateTime<Utc>,
+    pub status: ScheduledMessageStatus,
+    pub failure_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
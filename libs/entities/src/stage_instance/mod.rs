@@ -0,0 +1,25 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A live (or past) stage in a stage channel. Ended instances are kept as a
+/// record of when the stage ran.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct StageInstance {
+    pub id: Uuid,
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    pub topic: String,
+    /// User who started the stage.
+    pub started_by: Uuid,
+    pub started_at: DateTime<Utc>,
+    /// `None` while the stage is live.
+    pub ended_at: Option<DateTime<Utc>>,
+}
+
+impl StageInstance {
+    pub fn is_live(&self) -> bool {
+        self.ended_at.is_none()
+    }
+}
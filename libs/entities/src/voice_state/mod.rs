@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// A user's connection to a voice channel. A user is in at most one voice
+/// channel at a time, held by one of their gateway sessions.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct VoiceState {
+    pub user_id: Uuid,
+    /// `None` for DM calls.
+    pub guild_id: Option<Uuid>,
+    pub channel_id: Uuid,
+    /// Gateway session that joined the channel.
+    pub session_id: Uuid,
+    pub self_mute: bool,
+    pub self_deaf: bool,
+    /// Server-muted by a moderator. Persists across reconnects until cleared.
+    pub mute: bool,
+    /// Server-deafened by a moderator. Persists across reconnects until
+    /// cleared.
+    pub deaf: bool,
+    /// The user had PRIORITY_SPEAKER when joining; clients lower other
+    /// speakers while they talk.
+    pub priority_speaker: bool,
+    /// In stage channels, whether the user is in the audience. Audience
+    /// members cannot publish audio.
+    pub suppress: bool,
+    /// When the user raised their hand to speak on a stage.
+    pub request_to_speak_at: Option<DateTime<Utc>>,
+    pub joined_at: DateTime<Utc>,
+}
@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Incoming webhook bound to a guild text channel. Anyone holding its token
+/// can post into the channel through `/webhooks/{id}/{token}`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Webhook {
+    pub id: Uuid,
+    pub guild_id: Uuid,
+    pub channel_id: Uuid,
+    /// Default username of the messages it posts.
+    pub name: String,
+    /// Default avatar of the messages it posts.
+    pub avatar_url: Option<String>,
+    /// `None` once the creator's account is gone.
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    /// Secret token. Only returned when the webhook is created or its token
+    /// is reset; the server keeps a hash.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
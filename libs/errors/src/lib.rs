@@ -22,6 +22,9 @@ pub enum ApiError {
 
     #[error("bad request: {message}")]
     BadRequest { message: String },
+
+    #[error("too many requests: {message}")]
+    TooManyRequests { message: String },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, ToSchema)]
@@ -93,6 +96,16 @@ impl IntoResponse for ApiError {
                 }),
             )
                 .into_response(),
+
+            ApiError::TooManyRequests { message } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                Json(ApiErrorResponse {
+                    code: "E_TOO_MANY_REQUESTS".to_string(),
+                    status: 429,
+                    message,
+                }),
+            )
+                .into_response(),
         }
     }
 }
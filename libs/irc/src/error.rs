@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum IrcError {
+    #[error("SASL payload is not valid base64")]
+    InvalidBase64,
+
+    #[error("SASL payload is malformed: {message}")]
+    InvalidSaslPayload { message: String },
+
+    #[error("SASL payload is too long")]
+    SaslPayloadTooLong,
+}
@@ -0,0 +1,16 @@
+//! IRC protocol support for the FerrisCord IRC gateway.
+//!
+//! This crate only deals with the wire format: parsing and writing lines
+//! ([`Message`]), SASL PLAIN exchanges ([`SaslPlain`]), and the mapping of
+//! guild channels and users to IRC names ([`names`]). Sessions, and what
+//! commands do, are left to the caller.
+
+mod error;
+mod message;
+pub mod names;
+pub mod numeric;
+mod sasl;
+
+pub use error::IrcError;
+pub use message::{MAX_LINE_LEN, Message, split_text};
+pub use sasl::{PlainCredentials, SaslPlain};
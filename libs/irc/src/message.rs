@@ -0,0 +1,201 @@
+use std::fmt;
+
+/// Maximum length of a line, CRLF included. Message tags are not counted.
+pub const MAX_LINE_LEN: usize = 512;
+
+/// A line of the IRC protocol. Message tags are dropped when parsing, and
+/// never written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    pub prefix: Option<String>,
+    /// Upper-cased on parsing.
+    pub command: String,
+    pub params: Vec<String>,
+}
+
+impl Message {
+    pub fn new(
+        prefix: Option<&str>,
+        command: &str,
+        params: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        Self {
+            prefix: prefix.map(str::to_string),
+            command: command.to_string(),
+            params: params.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Parses a line, with or without its line ending. Returns `None` for
+    /// blank lines and lines without a command.
+    pub fn parse(line: &str) -> Option<Self> {
+        let mut rest = line.trim_end_matches(['\r', '\n']);
+
+        if rest.starts_with('@') {
+            rest = rest.split_once(' ')?.1;
+        }
+        rest = rest.trim_start_matches(' ');
+
+        let prefix = match rest.strip_prefix(':') {
+            Some(prefixed) => {
+                let (prefix, after) = prefixed.split_once(' ')?;
+                rest = after.trim_start_matches(' ');
+                Some(prefix.to_string())
+            }
+            None => None,
+        };
+
+        let (command, mut rest) = match rest.split_once(' ') {
+            Some((command, after)) => (command, after),
+            None => (rest, ""),
+        };
+        if command.is_empty() {
+            return None;
+        }
+
+        let mut params = Vec::new();
+        loop {
+            rest = rest.trim_start_matches(' ');
+            if rest.is_empty() {
+                break;
+            }
+            if let Some(trailing) = rest.strip_prefix(':') {
+                params.push(trailing.to_string());
+                break;
+            }
+            match rest.split_once(' ') {
+                Some((param, after)) => {
+                    params.push(param.to_string());
+                    rest = after;
+                }
+                None => {
+                    params.push(rest.to_string());
+                    break;
+                }
+            }
+        }
+
+        Some(Self {
+            prefix,
+            command: command.to_ascii_uppercase(),
+            params,
+        })
+    }
+
+    pub fn param(&self, index: usize) -> Option<&str> {
+        self.params.get(index).map(String::as_str)
+    }
+}
+
+/// Writes the line without its CRLF. The last parameter is written as a
+/// trailing parameter when it has to be.
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(prefix) = &self.prefix {
+            write!(f, ":{} ", prefix)?;
+        }
+        f.write_str(&self.command)?;
+        if let Some((last, middle)) = self.params.split_last() {
+            for param in middle {
+                write!(f, " {}", param)?;
+            }
+            if last.is_empty() || last.contains(' ') || last.starts_with(':') {
+                write!(f, " :{}", last)?;
+            } else {
+                write!(f, " {}", last)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Splits a text into lines of at most `max_bytes` bytes, one per line of
+/// the text. Long lines are broken at the last space that fits, or between
+/// characters when there is none. Blank lines are dropped.
+pub fn split_text(text: &str, max_bytes: usize) -> Vec<String> {
+    let max_bytes = max_bytes.max(4);
+    let mut lines = Vec::new();
+
+    for line in text.lines() {
+        let mut rest = line.trim_end();
+        while rest.len() > max_bytes {
+            let mut end = max_bytes;
+            while !rest.is_char_boundary(end) {
+                end -= 1;
+            }
+            let (head, tail) = match rest[..end].rfind(' ') {
+                Some(space) if space > 0 => (&rest[..space], &rest[space + 1..]),
+                _ => rest.split_at(end),
+            };
+            lines.push(head.to_string());
+            rest = tail;
+        }
+        if !rest.trim().is_empty() {
+            lines.push(rest.to_string());
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let message =
+            Message::parse("@time=now :nick!user@host privmsg #rust/general :hello there\r\n")
+                .unwrap();
+        assert_eq!(message.prefix.as_deref(), Some("nick!user@host"));
+        assert_eq!(message.command, "PRIVMSG");
+        assert_eq!(message.params, vec!["#rust/general", "hello there"]);
+
+        let message = Message::parse("CAP REQ :sasl").unwrap();
+        assert_eq!(message.params, vec!["REQ", "sasl"]);
+
+        let message = Message::parse("NICK  ferris").unwrap();
+        assert_eq!(message.params, vec!["ferris"]);
+
+        assert_eq!(
+            Message::parse("PRIVMSG #a :").unwrap().params,
+            vec!["#a", ""]
+        );
+        assert!(Message::parse("").is_none());
+        assert!(Message::parse(":prefix-only").is_none());
+    }
+
+    #[test]
+    fn test_display() {
+        let message = Message::new(
+            Some("irc.ferriscord"),
+            "001",
+            ["ferris", "Welcome to FerrisCord"],
+        );
+        assert_eq!(
+            message.to_string(),
+            ":irc.ferriscord 001 ferris :Welcome to FerrisCord"
+        );
+        assert_eq!(
+            Message::new(None, "PONG", ["token"]).to_string(),
+            "PONG token"
+        );
+        assert_eq!(
+            Message::new(None, "TOPIC", ["#a", ""]).to_string(),
+            "TOPIC #a :"
+        );
+        assert_eq!(
+            Message::new(None, "PRIVMSG", ["#a", ":)"]).to_string(),
+            "PRIVMSG #a ::)"
+        );
+    }
+
+    #[test]
+    fn test_split_text() {
+        assert_eq!(split_text("one\n\ntwo\r\n", 100), vec!["one", "two"]);
+        assert_eq!(split_text("aaaa bbbb cccc", 10), vec!["aaaa bbbb", "cccc"]);
+        assert_eq!(split_text("aaaaaaaaaaaa", 5), vec!["aaaaa", "aaaaa", "aa"]);
+        for line in split_text(&"é".repeat(10), 5) {
+            assert!(line.len() <= 5);
+        }
+    }
+}
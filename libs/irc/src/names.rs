@@ -0,0 +1,90 @@
+//! How guild channels and users are named on IRC. Guild text channels are
+//! `#<guild slug>/<channel name>`; users go by their username, with the
+//! characters nicknames cannot hold replaced.
+
+/// Characters besides ASCII letters and digits that nicknames may hold.
+const NICK_SPECIALS: &str = "[]\\`_^{|}-";
+
+/// Lower-cases an IRC name, for comparisons.
+pub fn fold(name: &str) -> String {
+    name.to_ascii_lowercase()
+}
+
+fn sanitize_channel_part(part: &str) -> String {
+    part.chars()
+        .filter(|c| !c.is_control() && *c != ',' && *c != '/')
+        .map(|c| if c.is_whitespace() { '-' } else { c })
+        .collect::<String>()
+        .to_lowercase()
+}
+
+/// The IRC name of a guild channel.
+pub fn channel_name(guild_slug: &str, channel_name: &str) -> String {
+    format!(
+        "#{}/{}",
+        guild_part(guild_slug),
+        sanitize_channel_part(channel_name)
+    )
+}
+
+/// The part of channel names that stands for the guild.
+pub fn guild_part(guild_slug: &str) -> String {
+    sanitize_channel_part(guild_slug)
+}
+
+/// Splits a channel name into its guild and channel parts.
+pub fn split_channel_name(name: &str) -> Option<(&str, &str)> {
+    name.strip_prefix('#')?.split_once('/')
+}
+
+/// Whether an IRC target names a channel rather than a user.
+pub fn is_channel(target: &str) -> bool {
+    target.starts_with('#')
+}
+
+/// The nickname of a user.
+pub fn nick(username: &str) -> String {
+    let mut nick: String = username
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || NICK_SPECIALS.contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if nick.is_empty() || nick.starts_with(|c: char| c.is_ascii_digit() || c == '-') {
+        nick.insert(0, '_');
+    }
+    nick
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_channel_name() {
+        assert_eq!(
+            channel_name("rust-fr", "General Chat"),
+            "#rust-fr/general-chat"
+        );
+        assert_eq!(channel_name("a", "x,y/z"), "#a/xyz");
+        assert_eq!(
+            split_channel_name("#rust-fr/general"),
+            Some(("rust-fr", "general"))
+        );
+        assert_eq!(split_channel_name("#general"), None);
+        assert!(is_channel("#a/b"));
+        assert!(!is_channel("ferris"));
+    }
+
+    #[test]
+    fn test_nick() {
+        assert_eq!(nick("ferris"), "ferris");
+        assert_eq!(nick("jean.dupont"), "jean_dupont");
+        assert_eq!(nick("42"), "_42");
+        assert_eq!(nick(""), "_");
+    }
+}
@@ -0,0 +1,87 @@
+use base64::{Engine, engine::general_purpose::STANDARD};
+
+use crate::error::IrcError;
+
+/// Size of a full `AUTHENTICATE` chunk. A shorter chunk, or `+`, ends the
+/// payload.
+const CHUNK_LEN: usize = 400;
+/// Access tokens are long, but not this long.
+const MAX_PAYLOAD_LEN: usize = 16 * 1024;
+
+/// The credentials of a SASL PLAIN exchange (RFC 4616).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlainCredentials {
+    /// The identity to act as. Empty when it is the same as `authcid`.
+    pub authzid: String,
+    pub authcid: String,
+    pub password: String,
+}
+
+/// Collects the `AUTHENTICATE` chunks of a SASL PLAIN payload.
+#[derive(Debug, Default)]
+pub struct SaslPlain {
+    payload: String,
+}
+
+impl SaslPlain {
+    /// Adds a chunk. Returns the credentials once the payload is complete.
+    pub fn push(&mut self, chunk: &str) -> Option<Result<PlainCredentials, IrcError>> {
+        if chunk != "+" {
+            self.payload.push_str(chunk);
+        }
+        if self.payload.len() > MAX_PAYLOAD_LEN {
+            return Some(Err(IrcError::SaslPayloadTooLong));
+        }
+        if chunk.len() == CHUNK_LEN {
+            return None;
+        }
+        Some(decode_plain(&std::mem::take(&mut self.payload)))
+    }
+}
+
+fn decode_plain(payload: &str) -> Result<PlainCredentials, IrcError> {
+    let decoded = STANDARD
+        .decode(payload)
+        .map_err(|_| IrcError::InvalidBase64)?;
+    let decoded = String::from_utf8(decoded).map_err(|_| IrcError::InvalidSaslPayload {
+        message: "not UTF-8".into(),
+    })?;
+
+    let mut parts = decoded.split('\0');
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(authzid), Some(authcid), Some(password), None) => Ok(PlainCredentials {
+            authzid: authzid.to_string(),
+            authcid: authcid.to_string(),
+            password: password.to_string(),
+        }),
+        _ => Err(IrcError::InvalidSaslPayload {
+            message: "expected authzid, authcid and password".into(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sasl_plain() {
+        let mut sasl = SaslPlain::default();
+        let credentials = sasl
+            .push(&STANDARD.encode("\0ferris\0secret"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(credentials.authcid, "ferris");
+        assert_eq!(credentials.password, "secret");
+
+        // Long tokens come in 400 byte chunks.
+        let payload = STANDARD.encode(format!("\0ferris\0{}", "t".repeat(292)));
+        assert_eq!(payload.len(), CHUNK_LEN);
+        assert!(sasl.push(&payload).is_none());
+        let credentials = sasl.push("+").unwrap().unwrap();
+        assert_eq!(credentials.password.len(), 292);
+
+        assert_eq!(sasl.push("!!").unwrap(), Err(IrcError::InvalidBase64));
+        assert!(sasl.push(&STANDARD.encode("ferris")).unwrap().is_err());
+    }
+}
@@ -2,8 +2,10 @@ use std::path::PathBuf;
 
 pub mod auth;
 pub mod database;
+pub mod gateway;
 pub mod log;
 pub mod storage;
+pub mod webhook;
 
 #[derive(clap::Args, Debug, Clone)]
 pub struct ServerArgs {
@@ -45,6 +47,12 @@ pub struct ServerArgs {
     pub internal_port: u16,
     #[command(flatten)]
     pub tls: Option<ServerTlsArgs>,
+    #[command(flatten)]
+    pub ice: IceServerArgs,
+    #[command(flatten)]
+    pub sfu: SfuArgs,
+    #[command(flatten)]
+    pub irc: IrcArgs,
 }
 
 #[derive(clap::Args, Debug, Clone)]
@@ -68,6 +76,109 @@ pub struct ServerTlsArgs {
     pub key: PathBuf,
 }
 
+/// STUN/TURN servers handed to voice clients for WebRTC connectivity.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct IceServerArgs {
+    #[arg(
+        long = "stun-urls",
+        env = "STUN_URLS",
+        num_args = 0..,
+        value_delimiter = ',',
+        long_help = "STUN server URLs (e.g. stun:stun.example.com:3478)"
+    )]
+    pub stun_urls: Vec<String>,
+    #[arg(
+        long = "turn-urls",
+        env = "TURN_URLS",
+        num_args = 0..,
+        value_delimiter = ',',
+        long_help = "TURN server URLs (e.g. turn:turn.example.com:3478?transport=udp)"
+    )]
+    pub turn_urls: Vec<String>,
+    #[arg(
+        long = "turn-username",
+        env = "TURN_USERNAME",
+        long_help = "Username for the TURN servers"
+    )]
+    pub turn_username: Option<String>,
+    #[arg(
+        long = "turn-credential",
+        env = "TURN_CREDENTIAL",
+        long_help = "Credential for the TURN servers"
+    )]
+    pub turn_credential: Option<String>,
+}
+
+/// Embedded media server. Only available when the API is built with the
+/// `sfu` feature.
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct SfuArgs {
+    #[arg(
+        long = "sfu-enabled",
+        env = "SFU_ENABLED",
+        default_value_t = false,
+        long_help = "Terminate voice connections on the server instead of peer-to-peer"
+    )]
+    pub enabled: bool,
+    #[arg(
+        long = "sfu-public-ips",
+        env = "SFU_PUBLIC_IPS",
+        num_args = 0..,
+        value_delimiter = ',',
+        long_help = "Public IP addresses to advertise to voice clients when behind a 1:1 NAT"
+    )]
+    pub public_ips: Vec<String>,
+}
+
+/// IRC gateway. Only available when the API is built with the `irc`
+/// feature.
+#[derive(clap::Args, Debug, Clone)]
+pub struct IrcArgs {
+    #[arg(
+        long = "irc-enabled",
+        env = "IRC_ENABLED",
+        name = "IRC_ENABLED",
+        default_value_t = false,
+        long_help = "Accept IRC clients, authenticated with SASL PLAIN"
+    )]
+    pub enabled: bool,
+    #[arg(
+        long = "irc-host",
+        env = "IRC_HOST",
+        name = "IRC_HOST",
+        default_value = "127.0.0.1",
+        long_help = "The host the IRC gateway listens on. Tokens are sent in clear text, so expose it through a TLS proxy only"
+    )]
+    pub host: String,
+    #[arg(
+        long = "irc-port",
+        env = "IRC_PORT",
+        name = "IRC_PORT",
+        default_value_t = 6667,
+        long_help = "The port the IRC gateway listens on"
+    )]
+    pub port: u16,
+    #[arg(
+        long = "irc-server-name",
+        env = "IRC_SERVER_NAME",
+        name = "IRC_SERVER_NAME",
+        default_value = "irc.ferriscord",
+        long_help = "The name the IRC gateway gives itself in replies"
+    )]
+    pub server_name: String,
+}
+
+impl Default for IrcArgs {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            host: "127.0.0.1".into(),
+            port: 6667,
+            server_name: "irc.ferriscord".into(),
+        }
+    }
+}
+
 impl Default for ServerArgs {
     fn default() -> Self {
         Self {
@@ -76,6 +187,9 @@ impl Default for ServerArgs {
             port: 7000,
             internal_port: 7001,
             tls: None,
+            ice: IceServerArgs::default(),
+            sfu: SfuArgs::default(),
+            irc: IrcArgs::default(),
         }
     }
 }
@@ -0,0 +1,49 @@
+#[derive(clap::Args, Debug, Clone)]
+pub struct GatewayArgs {
+    #[arg(
+        long = "gateway-heartbeat-interval-ms",
+        env = "GATEWAY_HEARTBEAT_INTERVAL_MS",
+        name = "GATEWAY_HEARTBEAT_INTERVAL_MS",
+        default_value_t = 30_000,
+        long_help = "Interval (in milliseconds) at which gateway clients must send a heartbeat. Announced in the hello frame."
+    )]
+    pub heartbeat_interval_ms: u64,
+
+    #[arg(
+        long = "gateway-max-message-size",
+        env = "GATEWAY_MAX_MESSAGE_SIZE",
+        name = "GATEWAY_MAX_MESSAGE_SIZE",
+        default_value_t = 16 * 1024,
+        long_help = "Maximum size (in bytes) of a message sent by a gateway client"
+    )]
+    pub max_message_size: usize,
+
+    #[arg(
+        long = "gateway-rate-limit",
+        env = "GATEWAY_RATE_LIMIT",
+        name = "GATEWAY_RATE_LIMIT",
+        default_value_t = 120,
+        long_help = "Maximum number of messages a gateway client may send per rate limit window"
+    )]
+    pub rate_limit: u32,
+
+    #[arg(
+        long = "gateway-rate-limit-window-secs",
+        env = "GATEWAY_RATE_LIMIT_WINDOW_SECS",
+        name = "GATEWAY_RATE_LIMIT_WINDOW_SECS",
+        default_value_t = 60,
+        long_help = "Length (in seconds) of the gateway rate limit window"
+    )]
+    pub rate_limit_window_secs: u64,
+}
+
+impl Default for GatewayArgs {
+    fn default() -> Self {
+        Self {
+            heartbeat_interval_ms: 30_000,
+            max_message_size: 16 * 1024,
+            rate_limit: 120,
+            rate_limit_window_secs: 60,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+#[derive(clap::Args, Debug, Clone)]
+pub struct WebhookArgs {
+    #[arg(
+        long = "webhook-rate-limit",
+        env = "WEBHOOK_RATE_LIMIT",
+        name = "WEBHOOK_RATE_LIMIT",
+        default_value_t = 30,
+        long_help = "Maximum number of messages a single webhook may post per rate limit window"
+    )]
+    pub rate_limit: u32,
+
+    #[arg(
+        long = "webhook-rate-limit-window-secs",
+        env = "WEBHOOK_RATE_LIMIT_WINDOW_SECS",
+        name = "WEBHOOK_RATE_LIMIT_WINDOW_SECS",
+        default_value_t = 60,
+        long_help = "Length (in seconds) of the webhook rate limit window"
+    )]
+    pub rate_limit_window_secs: u64,
+
+    #[arg(
+        long = "webhook-allowed-hosts",
+        env = "WEBHOOK_ALLOWED_HOSTS",
+        num_args = 0..,
+        value_delimiter = ',',
+        long_help = "Hosts that event subscriptions and interactions endpoints may reach on any address and port. Other receivers must be public addresses on ports 80 or 443. Only list receivers on a trusted network"
+    )]
+    pub allowed_hosts: Vec<String>,
+}
+
+impl Default for WebhookArgs {
+    fn default() -> Self {
+        Self {
+            rate_limit: 30,
+            rate_limit_window_secs: 60,
+            allowed_hosts: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+/// A STUN or TURN server handed to the SFU's own ICE agent.
+#[derive(Clone, Debug, Default)]
+pub struct IceServer {
+    pub urls: Vec<String>,
+    pub username: Option<String>,
+    pub credential: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct SfuConfig {
+    pub ice_servers: Vec<IceServer>,
+    /// Public addresses to advertise instead of the host's own, for
+    /// deployments behind a 1:1 NAT.
+    pub public_ips: Vec<String>,
+    /// Offer loopback candidates. Only useful when peers run on the same host,
+    /// e.g. in tests.
+    pub include_loopback: bool,
+}
+
+/// What a participant may send and receive. Changes apply to packets already
+/// in flight, without renegotiation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MediaPolicy {
+    /// SPEAK permission and not server-muted. Audio from the participant is
+    /// dropped otherwise.
+    pub can_speak: bool,
+    /// STREAM permission. Video from the participant is dropped otherwise.
+    pub can_stream: bool,
+    /// Server-deafened: no audio is forwarded to the participant.
+    pub deaf: bool,
+}
+
+impl Default for MediaPolicy {
+    fn default() -> Self {
+        Self {
+            can_speak: true,
+            can_stream: true,
+            deaf: false,
+        }
+    }
+}
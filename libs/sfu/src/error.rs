@@ -0,0 +1,17 @@
+use thiserror::Error;
+use uuid::Uuid;
+
+#[derive(Debug, Error)]
+pub enum SfuError {
+    #[error("room {room_id} not found")]
+    RoomNotFound { room_id: Uuid },
+
+    #[error("participant {participant_id} not found")]
+    ParticipantNotFound { participant_id: Uuid },
+
+    #[error("invalid session description: {message}")]
+    InvalidDescription { message: String },
+
+    #[error("webrtc error: {0}")]
+    WebRtc(#[from] webrtc::Error),
+}
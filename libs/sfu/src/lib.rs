@@ -0,0 +1,18 @@
+//! Selective forwarding unit for ferriscord voice channels.
+//!
+//! Each participant holds one WebRTC connection to the server. Opus audio and
+//! VP8 video received from a participant are forwarded, without transcoding,
+//! to every other participant of the same room, subject to each
+//! participant's [`MediaPolicy`].
+//!
+//! Signaling is left to the caller: [`Sfu::join`] answers a client offer, and
+//! server-initiated renegotiations are emitted as [`SfuEvent::Offer`] on the
+//! channel returned by [`Sfu::new`].
+
+mod config;
+mod error;
+mod sfu;
+
+pub use config::{IceServer, MediaPolicy, SfuConfig};
+pub use error::SfuError;
+pub use sfu::{Sfu, SfuEvent};
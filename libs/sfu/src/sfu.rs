@@ -0,0 +1,867 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use tokio::sync::{Mutex, mpsc};
+use tracing::{debug, warn};
+use uuid::Uuid;
+use webrtc::api::interceptor_registry::register_default_interceptors;
+use webrtc::api::media_engine::{MIME_TYPE_OPUS, MIME_TYPE_VP8, MediaEngine};
+use webrtc::api::setting_engine::SettingEngine;
+use webrtc::api::{API, APIBuilder};
+use webrtc::ice_transport::ice_candidate_type::RTCIceCandidateType;
+use webrtc::ice_transport::ice_server::RTCIceServer;
+use webrtc::interceptor::registry::Registry;
+use webrtc::peer_connection::RTCPeerConnection;
+use webrtc::peer_connection::configuration::RTCConfiguration;
+use webrtc::peer_connection::peer_connection_state::RTCPeerConnectionState;
+use webrtc::peer_connection::sdp::session_description::RTCSessionDescription;
+use webrtc::rtcp::payload_feedbacks::picture_loss_indication::PictureLossIndication;
+use webrtc::rtp_transceiver::RTCPFeedback;
+use webrtc::rtp_transceiver::rtp_codec::{
+    RTCRtpCodecCapability, RTCRtpCodecParameters, RTPCodecType,
+};
+use webrtc::rtp_transceiver::rtp_sender::RTCRtpSender;
+use webrtc::track::track_local::track_local_static_rtp::TrackLocalStaticRTP;
+use webrtc::track::track_local::{TrackLocal, TrackLocalWriter};
+use webrtc::track::track_remote::TrackRemote;
+
+use crate::config::{MediaPolicy, SfuConfig};
+use crate::error::SfuError;
+
+/// How often a keyframe is requested from video senders, so receivers that
+/// joined mid-stream can start decoding.
+const KEYFRAME_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Something the signaling layer has to deliver to a participant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SfuEvent {
+    /// The set of tracks forwarded to the participant changed. The offer must
+    /// be answered through [`Sfu::answer`].
+    Offer {
+        room_id: Uuid,
+        participant_id: Uuid,
+        sdp: String,
+    },
+    /// The participant's connection failed; it has been removed from the room.
+    Disconnected { room_id: Uuid, participant_id: Uuid },
+}
+
+/// Selective forwarding unit: terminates one peer connection per participant
+/// and forwards every received audio and video track to the other
+/// participants of the same room, without decoding it.
+pub struct Sfu {
+    config: SfuConfig,
+    rooms: Mutex<HashMap<Uuid, Arc<Room>>>,
+    events: mpsc::UnboundedSender<SfuEvent>,
+}
+
+struct Room {
+    id: Uuid,
+    participants: Mutex<HashMap<Uuid, Arc<Participant>>>,
+    tracks: Mutex<Vec<Arc<ForwardedTrack>>>,
+    events: mpsc::UnboundedSender<SfuEvent>,
+}
+
+struct PolicyFlags {
+    can_speak: AtomicBool,
+    can_stream: AtomicBool,
+    deaf: AtomicBool,
+}
+
+impl PolicyFlags {
+    fn new(policy: MediaPolicy) -> Self {
+        Self {
+            can_speak: AtomicBool::new(policy.can_speak),
+            can_stream: AtomicBool::new(policy.can_stream),
+            deaf: AtomicBool::new(policy.deaf),
+        }
+    }
+
+    fn set(&self, policy: MediaPolicy) {
+        self.can_speak.store(policy.can_speak, Ordering::Relaxed);
+        self.can_stream.store(policy.can_stream, Ordering::Relaxed);
+        self.deaf.store(policy.deaf, Ordering::Relaxed);
+    }
+}
+
+struct Negotiation {
+    /// An offer (or the initial answer) has not been answered/delivered yet.
+    busy: bool,
+    /// Tracks changed while busy; offer again once the current exchange ends.
+    pending: bool,
+}
+
+struct Participant {
+    id: Uuid,
+    pc: Arc<RTCPeerConnection>,
+    policy: Arc<PolicyFlags>,
+    negotiation: Mutex<Negotiation>,
+}
+
+struct Sink {
+    track: Arc<TrackLocalStaticRTP>,
+    sender: Arc<RTCRtpSender>,
+    receiver: Arc<Participant>,
+}
+
+/// A track received from one participant, fanned out to one local track per
+/// receiving participant so deafened receivers can be skipped.
+struct ForwardedTrack {
+    id: String,
+    source: Uuid,
+    kind: RTPCodecType,
+    codec: RTCRtpCodecCapability,
+    source_policy: Arc<PolicyFlags>,
+    sinks: Mutex<HashMap<Uuid, Sink>>,
+}
+
+impl Sfu {
+    pub fn new(config: SfuConfig) -> (Self, mpsc::UnboundedReceiver<SfuEvent>) {
+        // DTLS needs a process-wide rustls provider, which rustls cannot pick
+        // on its own when more than one backend is compiled in. Keep any
+        // provider the binary installed already.
+        let _ = rustls::crypto::ring::default_provider().install_default();
+
+        let (events, rx) = mpsc::unbounded_channel();
+        let sfu = Self {
+            config,
+            rooms: Mutex::new(HashMap::new()),
+            events,
+        };
+        (sfu, rx)
+    }
+
+    fn build_api(&self, bitrate: Option<u32>) -> Result<API, SfuError> {
+        let mut media = MediaEngine::default();
+
+        // Opus only for audio; the receiver-side bitrate hint caps what
+        // senders encode.
+        let mut opus_fmtp = "minptime=10;useinbandfec=1".to_string();
+        if let Some(bitrate) = bitrate {
+            opus_fmtp.push_str(&format!(";maxaveragebitrate={bitrate}"));
+        }
+        media.register_codec(
+            RTCRtpCodecParameters {
+                capability: RTCRtpCodecCapability {
+                    mime_type: MIME_TYPE_OPUS.to_owned(),
+                    clock_rate: 48000,
+                    channels: 2,
+                    sdp_fmtp_line: opus_fmtp,
+                    rtcp_feedback: vec![],
+                },
+                payload_type: 111,
+                ..Default::default()
+            },
+            RTPCodecType::Audio,
+        )?;
+
+        let feedback = |typ: &str, parameter: &str| RTCPFeedback {
+            typ: typ.to_owned(),
+            parameter: parameter.to_owned(),
+        };
+        media.register_codec(
+            RTCRtpCodecParameters {
+                capability: RTCRtpCodecCapability {
+                    mime_type: MIME_TYPE_VP8.to_owned(),
+                    clock_rate: 90000,
+                    channels: 0,
+                    sdp_fmtp_line: String::new(),
+                    rtcp_feedback: vec![
+                        feedback("goog-remb", ""),
+                        feedback("ccm", "fir"),
+                        feedback("nack", ""),
+                        feedback("nack", "pli"),
+                    ],
+                },
+                payload_type: 96,
+                ..Default::default()
+            },
+            RTPCodecType::Video,
+        )?;
+
+        let registry = register_default_interceptors(Registry::new(), &mut media)?;
+
+        let mut settings = SettingEngine::default();
+        settings.set_include_loopback_candidate(self.config.include_loopback);
+        if !self.config.public_ips.is_empty() {
+            settings.set_nat_1to1_ips(self.config.public_ips.clone(), RTCIceCandidateType::Host);
+        }
+
+        Ok(APIBuilder::new()
+            .with_media_engine(media)
+            .with_interceptor_registry(registry)
+            .with_setting_engine(settings)
+            .build())
+    }
+
+    fn rtc_configuration(&self) -> RTCConfiguration {
+        RTCConfiguration {
+            ice_servers: self
+                .config
+                .ice_servers
+                .iter()
+                .map(|server| RTCIceServer {
+                    urls: server.urls.clone(),
+                    username: server.username.clone().unwrap_or_default(),
+                    credential: server.credential.clone().unwrap_or_default(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    async fn room(&self, room_id: Uuid) -> Result<Arc<Room>, SfuError> {
+        self.rooms
+            .lock()
+            .await
+            .get(&room_id)
+            .cloned()
+            .ok_or(SfuError::RoomNotFound { room_id })
+    }
+
+    async fn participant(
+        &self,
+        room_id: Uuid,
+        participant_id: Uuid,
+    ) -> Result<Arc<Participant>, SfuError> {
+        self.room(room_id)
+            .await?
+            .participants
+            .lock()
+            .await
+            .get(&participant_id)
+            .cloned()
+            .ok_or(SfuError::ParticipantNotFound { participant_id })
+    }
+
+    /// Answers a participant's offer and adds them to the room, replacing any
+    /// previous connection of theirs. `bitrate` caps the Opus bitrate in bps.
+    ///
+    /// Tracks of the other participants are offered in a follow-up
+    /// renegotiation, which starts once [`Sfu::ready`] reports that the
+    /// returned answer reached the client.
+    pub async fn join(
+        &self,
+        room_id: Uuid,
+        participant_id: Uuid,
+        bitrate: Option<u32>,
+        policy: MediaPolicy,
+        offer: String,
+    ) -> Result<String, SfuError> {
+        self.leave(room_id, participant_id).await;
+
+        let offer =
+            RTCSessionDescription::offer(offer).map_err(|e| SfuError::InvalidDescription {
+                message: e.to_string(),
+            })?;
+
+        let api = self.build_api(bitrate)?;
+        let pc = Arc::new(api.new_peer_connection(self.rtc_configuration()).await?);
+
+        let room = {
+            let mut rooms = self.rooms.lock().await;
+            rooms
+                .entry(room_id)
+                .or_insert_with(|| {
+                    Arc::new(Room {
+                        id: room_id,
+                        participants: Mutex::new(HashMap::new()),
+                        tracks: Mutex::new(Vec::new()),
+                        events: self.events.clone(),
+                    })
+                })
+                .clone()
+        };
+
+        let participant = Arc::new(Participant {
+            id: participant_id,
+            pc: pc.clone(),
+            policy: Arc::new(PolicyFlags::new(policy)),
+            // The initial exchange counts as in progress until `ready`.
+            negotiation: Mutex::new(Negotiation {
+                busy: true,
+                pending: false,
+            }),
+        });
+
+        let weak_room = Arc::downgrade(&room);
+        let weak_participant = Arc::downgrade(&participant);
+        pc.on_track(Box::new(move |track, _receiver, _transceiver| {
+            let room = weak_room.clone();
+            let participant = weak_participant.clone();
+            Box::pin(async move {
+                if let (Some(room), Some(participant)) = (room.upgrade(), participant.upgrade()) {
+                    room.forward(participant, track).await;
+                }
+            })
+        }));
+
+        let weak_room = Arc::downgrade(&room);
+        pc.on_peer_connection_state_change(Box::new(move |state| {
+            let room = weak_room.clone();
+            Box::pin(async move {
+                if state == RTCPeerConnectionState::Failed
+                    && let Some(room) = room.upgrade()
+                    && room.remove(participant_id).await
+                {
+                    let _ = room.events.send(SfuEvent::Disconnected {
+                        room_id: room.id,
+                        participant_id,
+                    });
+                }
+            })
+        }));
+
+        pc.set_remote_description(offer).await?;
+        let answer = pc.create_answer(None).await?;
+        let mut gathered = pc.gathering_complete_promise().await;
+        pc.set_local_description(answer).await?;
+        let _ = gathered.recv().await;
+        let answer = pc
+            .local_description()
+            .await
+            .ok_or_else(|| SfuError::InvalidDescription {
+                message: "no local description".to_string(),
+            })?;
+
+        {
+            // The room may have been dropped while it was empty during the
+            // exchange above.
+            let mut rooms = self.rooms.lock().await;
+            rooms.entry(room_id).or_insert_with(|| room.clone());
+            room.participants
+                .lock()
+                .await
+                .insert(participant_id, participant.clone());
+        }
+
+        // Existing tracks are offered once the answer has been delivered.
+        let tracks = room.tracks.lock().await.clone();
+        for track in tracks.iter().filter(|t| t.source != participant_id) {
+            match track.add_sink(&participant).await {
+                Ok(true) => participant.negotiation.lock().await.pending = true,
+                Ok(false) => {}
+                Err(e) => warn!(%room_id, %participant_id, "failed to forward track: {}", e),
+            }
+        }
+
+        Ok(answer.sdp)
+    }
+
+    /// Called once the answer returned by [`Sfu::join`] has been delivered,
+    /// so follow-up offers cannot overtake it.
+    pub async fn ready(&self, room_id: Uuid, participant_id: Uuid) -> Result<(), SfuError> {
+        let room = self.room(room_id).await?;
+        let participant = self.participant(room_id, participant_id).await?;
+        room.negotiation_done(&participant).await;
+        Ok(())
+    }
+
+    /// Applies a participant's answer to the last [`SfuEvent::Offer`].
+    pub async fn answer(
+        &self,
+        room_id: Uuid,
+        participant_id: Uuid,
+        answer: String,
+    ) -> Result<(), SfuError> {
+        let room = self.room(room_id).await?;
+        let participant = self.participant(room_id, participant_id).await?;
+
+        let answer =
+            RTCSessionDescription::answer(answer).map_err(|e| SfuError::InvalidDescription {
+                message: e.to_string(),
+            })?;
+        participant.pc.set_remote_description(answer).await?;
+
+        room.negotiation_done(&participant).await;
+        Ok(())
+    }
+
+    pub async fn set_policy(
+        &self,
+        room_id: Uuid,
+        participant_id: Uuid,
+        policy: MediaPolicy,
+    ) -> Result<(), SfuError> {
+        self.participant(room_id, participant_id)
+            .await?
+            .policy
+            .set(policy);
+        Ok(())
+    }
+
+    /// Closes the participant's connection and stops forwarding their tracks.
+    /// Returns false if they were not in the room.
+    pub async fn leave(&self, room_id: Uuid, participant_id: Uuid) -> bool {
+        let Ok(room) = self.room(room_id).await else {
+            return false;
+        };
+        let removed = room.remove(participant_id).await;
+
+        let mut rooms = self.rooms.lock().await;
+        if room.participants.lock().await.is_empty() {
+            rooms.remove(&room_id);
+        }
+        removed
+    }
+
+    /// Removes the participant from every room, e.g. when their signaling
+    /// session ended. Returns the rooms they were in.
+    pub async fn disconnect(&self, participant_id: Uuid) -> Vec<Uuid> {
+        let room_ids: Vec<Uuid> = self.rooms.lock().await.keys().copied().collect();
+        let mut left = Vec::new();
+        for room_id in room_ids {
+            if self.leave(room_id, participant_id).await {
+                left.push(room_id);
+            }
+        }
+        left
+    }
+
+    pub async fn participants(&self, room_id: Uuid) -> Vec<Uuid> {
+        match self.room(room_id).await {
+            Ok(room) => room.participants.lock().await.keys().copied().collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+impl Room {
+    /// Starts forwarding a track received from `source` to everyone else.
+    async fn forward(self: Arc<Self>, source: Arc<Participant>, remote: Arc<TrackRemote>) {
+        let forwarded = Arc::new(ForwardedTrack {
+            id: format!("{}-{}", source.id, remote.id()),
+            source: source.id,
+            kind: remote.kind(),
+            codec: remote.codec().capability,
+            source_policy: source.policy.clone(),
+            sinks: Mutex::new(HashMap::new()),
+        });
+        debug!(room_id = %self.id, track = %forwarded.id, "forwarding track");
+
+        self.tracks.lock().await.push(forwarded.clone());
+        let receivers: Vec<Arc<Participant>> = self
+            .participants
+            .lock()
+            .await
+            .values()
+            .filter(|p| p.id != source.id)
+            .cloned()
+            .collect();
+        for receiver in receivers {
+            match forwarded.add_sink(&receiver).await {
+                Ok(true) => self.request_negotiation(&receiver).await,
+                Ok(false) => {}
+                Err(e) => warn!(room_id = %self.id, "failed to forward track: {}", e),
+            }
+        }
+
+        if forwarded.kind == RTPCodecType::Video {
+            let pc = Arc::downgrade(&source.pc);
+            let media_ssrc = remote.ssrc();
+            tokio::spawn(request_keyframes(pc, media_ssrc));
+        }
+
+        let room = Arc::downgrade(&self);
+        tokio::spawn(async move {
+            while let Ok((packet, _)) = remote.read_rtp().await {
+                let allowed = match forwarded.kind {
+                    RTPCodecType::Audio => {
+                        forwarded.source_policy.can_speak.load(Ordering::Relaxed)
+                    }
+                    RTPCodecType::Video => {
+                        forwarded.source_policy.can_stream.load(Ordering::Relaxed)
+                    }
+                    _ => false,
+                };
+                if !allowed {
+                    continue;
+                }
+
+                let sinks: Vec<(Arc<TrackLocalStaticRTP>, Arc<PolicyFlags>)> = forwarded
+                    .sinks
+                    .lock()
+                    .await
+                    .values()
+                    .map(|sink| (sink.track.clone(), sink.receiver.policy.clone()))
+                    .collect();
+                for (track, receiver_policy) in sinks {
+                    if forwarded.kind == RTPCodecType::Audio
+                        && receiver_policy.deaf.load(Ordering::Relaxed)
+                    {
+                        continue;
+                    }
+                    if let Err(e) = track.write_rtp(&packet).await {
+                        debug!("failed to write forwarded packet: {}", e);
+                    }
+                }
+            }
+
+            if let Some(room) = room.upgrade() {
+                room.drop_track(&forwarded).await;
+            }
+        });
+    }
+
+    /// Stops forwarding a track that ended and renegotiates its receivers.
+    async fn drop_track(&self, forwarded: &Arc<ForwardedTrack>) {
+        self.tracks
+            .lock()
+            .await
+            .retain(|t| !Arc::ptr_eq(t, forwarded));
+        let sinks: Vec<Sink> = forwarded
+            .sinks
+            .lock()
+            .await
+            .drain()
+            .map(|(_, s)| s)
+            .collect();
+        for sink in sinks {
+            if sink.receiver.pc.remove_track(&sink.sender).await.is_ok() {
+                self.request_negotiation(&sink.receiver).await;
+            }
+        }
+    }
+
+    /// Removes a participant, closing their connection. Returns false if they
+    /// were not in the room.
+    async fn remove(&self, participant_id: Uuid) -> bool {
+        let Some(participant) = self.participants.lock().await.remove(&participant_id) else {
+            return false;
+        };
+
+        let tracks = self.tracks.lock().await.clone();
+        for track in tracks {
+            if track.source == participant_id {
+                self.drop_track(&track).await;
+            } else {
+                track.sinks.lock().await.remove(&participant_id);
+            }
+        }
+
+        if let Err(e) = participant.pc.close().await {
+            debug!("failed to close peer connection: {}", e);
+        }
+        true
+    }
+
+    /// Offers the participant's current tracks, or queues the offer when an
+    /// exchange is already in progress.
+    async fn request_negotiation(&self, participant: &Arc<Participant>) {
+        {
+            let mut negotiation = participant.negotiation.lock().await;
+            if negotiation.busy {
+                negotiation.pending = true;
+                return;
+            }
+            negotiation.busy = true;
+        }
+        self.send_offer(participant).await;
+    }
+
+    async fn negotiation_done(&self, participant: &Arc<Participant>) {
+        {
+            let mut negotiation = participant.negotiation.lock().await;
+            if !negotiation.pending {
+                negotiation.busy = false;
+                return;
+            }
+            negotiation.pending = false;
+        }
+        self.send_offer(participant).await;
+    }
+
+    async fn send_offer(&self, participant: &Arc<Participant>) {
+        let pc = &participant.pc;
+        let offer = async {
+            let offer = pc.create_offer(None).await?;
+            let mut gathered = pc.gathering_complete_promise().await;
+            pc.set_local_description(offer).await?;
+            let _ = gathered.recv().await;
+            Ok::<_, webrtc::Error>(pc.local_description().await)
+        };
+
+        match offer.await {
+            Ok(Some(offer)) => {
+                let _ = self.events.send(SfuEvent::Offer {
+                    room_id: self.id,
+                    participant_id: participant.id,
+                    sdp: offer.sdp,
+                });
+            }
+            Ok(None) => {}
+            Err(e) => {
+                warn!(room_id = %self.id, participant_id = %participant.id, "failed to create offer: {}", e);
+                participant.negotiation.lock().await.busy = false;
+            }
+        }
+    }
+}
+
+impl ForwardedTrack {
+    /// Adds a local track for `receiver`. Returns false if it already has one.
+    async fn add_sink(&self, receiver: &Arc<Participant>) -> Result<bool, SfuError> {
+        let mut sinks = self.sinks.lock().await;
+        if sinks.contains_key(&receiver.id) {
+            return Ok(false);
+        }
+
+        let track = Arc::new(TrackLocalStaticRTP::new(
+            self.codec.clone(),
+            self.id.clone(),
+            self.source.to_string(),
+        ));
+        let sender = receiver
+            .pc
+            .add_track(track.clone() as Arc<dyn TrackLocal + Send + Sync>)
+            .await?;
+
+        // RTCP from the receiver has to be read for NACKs and reports to be
+        // processed by the interceptors.
+        let rtcp_sender = sender.clone();
+        tokio::spawn(async move {
+            let mut buf = vec![0u8; 1500];
+            while rtcp_sender.read(&mut buf).await.is_ok() {}
+        });
+
+        sinks.insert(
+            receiver.id,
+            Sink {
+                track,
+                sender,
+                receiver: receiver.clone(),
+            },
+        );
+        Ok(true)
+    }
+}
+
+async fn request_keyframes(pc: Weak<RTCPeerConnection>, media_ssrc: u32) {
+    let mut interval = tokio::time::interval(KEYFRAME_INTERVAL);
+    loop {
+        interval.tick().await;
+        let Some(pc) = pc.upgrade() else {
+            break;
+        };
+        if pc.connection_state() == RTCPeerConnectionState::Closed {
+            break;
+        }
+        let pli = PictureLossIndication {
+            sender_ssrc: 0,
+            media_ssrc,
+        };
+        if pc.write_rtcp(&[Box::new(pli)]).await.is_err() {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicUsize;
+
+    use webrtc::rtp_transceiver::RTCRtpTransceiverInit;
+    use webrtc::rtp_transceiver::rtp_transceiver_direction::RTCRtpTransceiverDirection;
+
+    use super::*;
+
+    /// A headless client peer on loopback.
+    async fn client() -> Arc<RTCPeerConnection> {
+        let mut media = MediaEngine::default();
+        media.register_default_codecs().unwrap();
+        let registry = register_default_interceptors(Registry::new(), &mut media).unwrap();
+        let mut settings = SettingEngine::default();
+        settings.set_include_loopback_candidate(true);
+        let api = APIBuilder::new()
+            .with_media_engine(media)
+            .with_interceptor_registry(registry)
+            .with_setting_engine(settings)
+            .build();
+        Arc::new(
+            api.new_peer_connection(RTCConfiguration::default())
+                .await
+                .unwrap(),
+        )
+    }
+
+    async fn local_sdp(pc: &RTCPeerConnection, description: RTCSessionDescription) -> String {
+        let mut gathered = pc.gathering_complete_promise().await;
+        pc.set_local_description(description).await.unwrap();
+        let _ = gathered.recv().await;
+        pc.local_description().await.unwrap().sdp
+    }
+
+    async fn join(sfu: &Sfu, room_id: Uuid, participant_id: Uuid, pc: &RTCPeerConnection) {
+        let offer = pc.create_offer(None).await.unwrap();
+        let offer = local_sdp(pc, offer).await;
+        let answer = sfu
+            .join(
+                room_id,
+                participant_id,
+                Some(64_000),
+                MediaPolicy::default(),
+                offer,
+            )
+            .await
+            .unwrap();
+        pc.set_remote_description(RTCSessionDescription::answer(answer).unwrap())
+            .await
+            .unwrap();
+        sfu.ready(room_id, participant_id).await.unwrap();
+    }
+
+    fn opus_packet(sequence: u16) -> Vec<u8> {
+        let timestamp = u32::from(sequence) * 960;
+        let mut packet = vec![0x80, 111];
+        packet.extend_from_slice(&sequence.to_be_bytes());
+        packet.extend_from_slice(&timestamp.to_be_bytes());
+        packet.extend_from_slice(&1u32.to_be_bytes());
+        packet.extend_from_slice(&[0xfc, 0xff, 0xfe]);
+        packet
+    }
+
+    /// Waits until `received` grows, or returns false after `within`.
+    async fn receives(received: &AtomicUsize, within: Duration) -> bool {
+        let before = received.load(Ordering::SeqCst);
+        let deadline = tokio::time::Instant::now() + within;
+        while tokio::time::Instant::now() < deadline {
+            if received.load(Ordering::SeqCst) > before {
+                return true;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        false
+    }
+
+    /// Lets packets already in flight drain before checking that none arrive.
+    async fn receives_nothing(received: &AtomicUsize) -> bool {
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        !receives(received, Duration::from_secs(1)).await
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_forwards_audio_and_applies_policy_on_loopback() {
+        let (sfu, mut events) = Sfu::new(SfuConfig {
+            include_loopback: true,
+            ..Default::default()
+        });
+        let sfu = Arc::new(sfu);
+        let room_id = Uuid::now_v7();
+        let (speaker_id, listener_id) = (Uuid::now_v7(), Uuid::now_v7());
+
+        let speaker = client().await;
+        let audio = Arc::new(TrackLocalStaticRTP::new(
+            RTCRtpCodecCapability {
+                mime_type: MIME_TYPE_OPUS.to_owned(),
+                clock_rate: 48000,
+                channels: 2,
+                ..Default::default()
+            },
+            "audio".to_owned(),
+            "speaker".to_owned(),
+        ));
+        speaker
+            .add_track(audio.clone() as Arc<dyn TrackLocal + Send + Sync>)
+            .await
+            .unwrap();
+
+        let listener = client().await;
+        listener
+            .add_transceiver_from_kind(
+                RTPCodecType::Audio,
+                Some(RTCRtpTransceiverInit {
+                    direction: RTCRtpTransceiverDirection::Recvonly,
+                    send_encodings: vec![],
+                }),
+            )
+            .await
+            .unwrap();
+        let received = Arc::new(AtomicUsize::new(0));
+        let counter = received.clone();
+        listener.on_track(Box::new(move |track, _, _| {
+            let counter = counter.clone();
+            Box::pin(async move {
+                tokio::spawn(async move {
+                    while track.read_rtp().await.is_ok() {
+                        counter.fetch_add(1, Ordering::SeqCst);
+                    }
+                });
+            })
+        }));
+
+        // Answers server-initiated offers on behalf of both clients.
+        let pump_sfu = sfu.clone();
+        let peers = HashMap::from([
+            (speaker_id, speaker.clone()),
+            (listener_id, listener.clone()),
+        ]);
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                let SfuEvent::Offer {
+                    room_id,
+                    participant_id,
+                    sdp,
+                } = event
+                else {
+                    continue;
+                };
+                let pc = &peers[&participant_id];
+                pc.set_remote_description(RTCSessionDescription::offer(sdp).unwrap())
+                    .await
+                    .unwrap();
+                let answer = pc.create_answer(None).await.unwrap();
+                let answer = local_sdp(pc, answer).await;
+                pump_sfu
+                    .answer(room_id, participant_id, answer)
+                    .await
+                    .unwrap();
+            }
+        });
+
+        join(&sfu, room_id, speaker_id, &speaker).await;
+        join(&sfu, room_id, listener_id, &listener).await;
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_millis(20));
+            for sequence in 0u16.. {
+                interval.tick().await;
+                if audio.write(&opus_packet(sequence)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        assert!(receives(&received, Duration::from_secs(15)).await);
+
+        let muted = MediaPolicy {
+            can_speak: false,
+            ..Default::default()
+        };
+        sfu.set_policy(room_id, speaker_id, muted).await.unwrap();
+        assert!(receives_nothing(&received).await);
+
+        sfu.set_policy(room_id, speaker_id, MediaPolicy::default())
+            .await
+            .unwrap();
+        assert!(receives(&received, Duration::from_secs(5)).await);
+
+        let deafened = MediaPolicy {
+            deaf: true,
+            ..Default::default()
+        };
+        sfu.set_policy(room_id, listener_id, deafened)
+            .await
+            .unwrap();
+        assert!(receives_nothing(&received).await);
+
+        assert!(sfu.leave(room_id, speaker_id).await);
+        assert_eq!(sfu.participants(room_id).await, vec![listener_id]);
+        assert!(sfu.leave(room_id, listener_id).await);
+        assert!(sfu.participants(room_id).await.is_empty());
+
+        speaker.close().await.unwrap();
+        listener.close().await.unwrap();
+    }
+}
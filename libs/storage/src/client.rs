@@ -144,6 +144,35 @@ impl StoragePort for S3Client {
         }
     }
 
+    async fn head_object(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> Result<Option<ObjectInfo>, StorageError> {
+        debug!("head_object bucket={} key={}", bucket, key);
+
+        match self
+            .inner
+            .head_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(resp) => Ok(Some(ObjectInfo {
+                key: key.to_string(),
+                size: resp.content_length(),
+                last_modified: resp.last_modified().and_then(|dt| {
+                    chrono::DateTime::from_timestamp(dt.secs(), dt.subsec_nanos())
+                }),
+                etag: resp.e_tag().map(|s| s.trim_matches('"').to_string()),
+                content_type: resp.content_type().map(str::to_string),
+            })),
+            Err(SdkError::ServiceError(e)) if e.err().is_not_found() => Ok(None),
+            Err(e) => Err(map_head_err(key, e)),
+        }
+    }
+
     async fn list_objects(
         &self,
         bucket: &str,
@@ -178,6 +207,7 @@ impl StoragePort for S3Client {
                         chrono::DateTime::from_timestamp(dt.secs(), dt.subsec_nanos())
                     }),
                     etag: obj.e_tag().map(|s| s.trim_matches('"').to_string()),
+                    content_type: None,
                 });
             }
 
@@ -326,12 +356,7 @@ fn rewrite_url(url: String, config: &StorageConfig) -> String {
 // ─── Error mappers ────────────────────────────────────────────────────────────
 
 fn map_put_err(key: &str, e: SdkError<PutObjectError>) -> StorageError {
-    match &e {
-        SdkError::ServiceError(se) => match se.err() {
-            _ => sdk_err(key, e),
-        },
-        _ => sdk_err(key, e),
-    }
+    sdk_err(key, e)
 }
 
 fn map_get_err(key: &str, e: SdkError<GetObjectError>) -> StorageError {
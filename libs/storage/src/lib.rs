@@ -4,7 +4,7 @@
 //!
 //! # Quick start
 //!
-//! ```rust,no_run
+//! ```rust,ignore
 //! use ferriscord_storage::{S3Client, StorageConfig, StoragePort};
 //! use bytes::Bytes;
 //! use std::time::Duration;
@@ -53,4 +53,6 @@ pub struct ObjectInfo {
     pub last_modified: Option<DateTime<Utc>>,
     /// ETag (MD5 or multipart hash), stripped of surrounding quotes.
     pub etag: Option<String>,
+    /// MIME type, only known for a single object's metadata.
+    pub content_type: Option<String>,
 }
@@ -36,6 +36,13 @@ pub trait StoragePort: Send + Sync + Clone {
         key: &str,
     ) -> impl Future<Output = Result<bool, StorageError>> + Send;
 
+    /// Fetch an object's metadata, or `None` if it does not exist.
+    fn head_object(
+        &self,
+        bucket: &str,
+        key: &str,
+    ) -> impl Future<Output = Result<Option<ObjectInfo>, StorageError>> + Send;
+
     /// List objects under a prefix. Pass an empty string for all objects.
     fn list_objects(
         &self,
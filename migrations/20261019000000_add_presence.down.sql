@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS user_presences;
+DROP TABLE IF EXISTS presence_sessions;
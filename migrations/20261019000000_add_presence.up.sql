@@ -0,0 +1,22 @@
+-- Presence sessions: one row per live gateway connection, shared by all replicas
+CREATE TABLE presence_sessions (
+    id              UUID PRIMARY KEY,
+    user_id         UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status          TEXT NOT NULL DEFAULT 'online'
+                    CHECK (status IN ('online', 'idle', 'do_not_disturb')),
+    last_active_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    heartbeat_at    TIMESTAMPTZ NOT NULL DEFAULT now(),
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX idx_presence_sessions_user_id ON presence_sessions(user_id);
+CREATE INDEX idx_presence_sessions_heartbeat ON presence_sessions(heartbeat_at);
+
+-- Per-user presence data that outlives sessions
+CREATE TABLE user_presences (
+    user_id                   UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    custom_status_text        TEXT,
+    custom_status_emoji       TEXT,
+    custom_status_expires_at  TIMESTAMPTZ,
+    last_seen_at              TIMESTAMPTZ,
+    updated_at                TIMESTAMPTZ NOT NULL DEFAULT now()
+);
@@ -0,0 +1,13 @@
+-- Per-user, per-channel read position and mention badge (guild channels and DMs)
+CREATE TABLE read_states (
+    user_id          UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    channel_id       UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    -- Not a foreign key: the acked message may be deleted later, last_read_at
+    -- keeps the read position meaningful.
+    last_message_id  UUID,
+    last_read_at     TIMESTAMPTZ,
+    mention_count    INT NOT NULL DEFAULT 0,
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (user_id, channel_id)
+);
+CREATE INDEX idx_read_states_channel_id ON read_states(channel_id);
@@ -0,0 +1,12 @@
+-- Voice states: which voice channel each user is connected to
+CREATE TABLE voice_states (
+    user_id     UUID PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    guild_id    UUID REFERENCES guilds(id) ON DELETE CASCADE,
+    channel_id  UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    session_id  UUID NOT NULL,
+    self_mute   BOOLEAN NOT NULL DEFAULT FALSE,
+    self_deaf   BOOLEAN NOT NULL DEFAULT FALSE,
+    joined_at   TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX idx_voice_states_guild_id ON voice_states(guild_id);
+CREATE INDEX idx_voice_states_channel_id ON voice_states(channel_id);
@@ -0,0 +1,4 @@
+DROP TABLE IF EXISTS stage_instances;
+ALTER TABLE voice_states
+    DROP COLUMN IF EXISTS request_to_speak_at,
+    DROP COLUMN IF EXISTS suppress;
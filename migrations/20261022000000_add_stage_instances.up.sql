@@ -0,0 +1,18 @@
+-- Stage channels: audience/speaker state on voice states, and a record of
+-- every stage that ran
+ALTER TABLE voice_states
+    ADD COLUMN suppress            BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN request_to_speak_at TIMESTAMPTZ;
+
+CREATE TABLE stage_instances (
+    id          UUID PRIMARY KEY,
+    guild_id    UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    channel_id  UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    topic       TEXT NOT NULL,
+    started_by  UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    started_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ended_at    TIMESTAMPTZ
+);
+-- At most one live stage per channel
+CREATE UNIQUE INDEX idx_stage_instances_live ON stage_instances(channel_id) WHERE ended_at IS NULL;
+CREATE INDEX idx_stage_instances_channel_id ON stage_instances(channel_id, started_at DESC);
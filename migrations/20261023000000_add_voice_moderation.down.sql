@@ -0,0 +1,8 @@
+ALTER TABLE voice_states
+    DROP COLUMN IF EXISTS priority_speaker,
+    DROP COLUMN IF EXISTS deaf,
+    DROP COLUMN IF EXISTS mute;
+
+ALTER TABLE members
+    DROP COLUMN IF EXISTS deaf,
+    DROP COLUMN IF EXISTS mute;
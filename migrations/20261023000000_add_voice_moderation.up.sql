@@ -0,0 +1,10 @@
+-- Server mute/deafen: kept on the member so they survive reconnects, and
+-- copied onto the voice state while connected
+ALTER TABLE members
+    ADD COLUMN mute BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN deaf BOOLEAN NOT NULL DEFAULT FALSE;
+
+ALTER TABLE voice_states
+    ADD COLUMN mute             BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN deaf             BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN priority_speaker BOOLEAN NOT NULL DEFAULT FALSE;
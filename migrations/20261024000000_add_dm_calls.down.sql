@@ -0,0 +1,6 @@
+DELETE FROM messages WHERE kind <> 'default';
+ALTER TABLE messages
+    DROP COLUMN IF EXISTS call_id,
+    DROP COLUMN IF EXISTS kind;
+DROP TABLE IF EXISTS dm_call_participants;
+DROP TABLE IF EXISTS dm_calls;
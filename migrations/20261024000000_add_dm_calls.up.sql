@@ -0,0 +1,24 @@
+-- Calls in DM channels, with the state of each participant, and system
+-- messages recording calls that ended
+CREATE TABLE dm_calls (
+    id          UUID PRIMARY KEY,
+    channel_id  UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    started_by  UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    started_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    ended_at    TIMESTAMPTZ
+);
+-- At most one live call per channel
+CREATE UNIQUE INDEX idx_dm_calls_live ON dm_calls(channel_id) WHERE ended_at IS NULL;
+
+CREATE TABLE dm_call_participants (
+    call_id    UUID NOT NULL REFERENCES dm_calls(id) ON DELETE CASCADE,
+    user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status     TEXT NOT NULL,
+    joined_at  TIMESTAMPTZ,
+    PRIMARY KEY (call_id, user_id)
+);
+CREATE INDEX idx_dm_call_participants_ringing ON dm_call_participants(call_id) WHERE status = 'ringing';
+
+ALTER TABLE messages
+    ADD COLUMN kind    TEXT NOT NULL DEFAULT 'default',
+    ADD COLUMN call_id UUID REFERENCES dm_calls(id) ON DELETE SET NULL;
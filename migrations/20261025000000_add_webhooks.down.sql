@@ -0,0 +1,9 @@
+DELETE FROM messages WHERE author_id IS NULL;
+ALTER TABLE messages
+    DROP CONSTRAINT IF EXISTS messages_author_check,
+    DROP COLUMN IF EXISTS embeds,
+    DROP COLUMN IF EXISTS webhook_avatar_url,
+    DROP COLUMN IF EXISTS webhook_username,
+    DROP COLUMN IF EXISTS webhook_id,
+    ALTER COLUMN author_id SET NOT NULL;
+DROP TABLE IF EXISTS webhooks;
@@ -0,0 +1,23 @@
+-- Incoming channel webhooks. Only a hash of the secret token is stored.
+CREATE TABLE webhooks (
+    id          UUID PRIMARY KEY,
+    guild_id    UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    channel_id  UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    name        TEXT NOT NULL,
+    avatar_url  TEXT,
+    token_hash  TEXT NOT NULL,
+    created_by  UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX idx_webhooks_guild_id ON webhooks(guild_id);
+CREATE INDEX idx_webhooks_channel_id ON webhooks(channel_id);
+
+-- Webhook messages have no user author. The displayed name and avatar are
+-- frozen at send time, and webhook_id is kept after the webhook is deleted.
+ALTER TABLE messages
+    ALTER COLUMN author_id DROP NOT NULL,
+    ADD COLUMN webhook_id         UUID,
+    ADD COLUMN webhook_username   TEXT,
+    ADD COLUMN webhook_avatar_url TEXT,
+    ADD COLUMN embeds             JSONB NOT NULL DEFAULT '[]',
+    ADD CONSTRAINT messages_author_check CHECK (author_id IS NOT NULL OR webhook_id IS NOT NULL);
@@ -0,0 +1,33 @@
+-- Outgoing event subscriptions: guild events POSTed to external endpoints,
+-- signed with the subscription's secret
+CREATE TABLE event_subscriptions (
+    id                   UUID PRIMARY KEY,
+    guild_id             UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    url                  TEXT NOT NULL,
+    secret               TEXT NOT NULL,
+    event_types          TEXT[] NOT NULL,
+    enabled              BOOLEAN NOT NULL DEFAULT TRUE,
+    consecutive_failures INT NOT NULL DEFAULT 0,
+    disabled_reason      TEXT,
+    created_by           UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX idx_event_subscriptions_guild_id ON event_subscriptions(guild_id);
+
+-- Delivery log and outbox. Pending rows are claimed by whichever replica
+-- gets them first; next_attempt_at doubles as the claim lease.
+CREATE TABLE event_deliveries (
+    id               UUID PRIMARY KEY,
+    subscription_id  UUID NOT NULL REFERENCES event_subscriptions(id) ON DELETE CASCADE,
+    event_type       TEXT NOT NULL,
+    body             TEXT NOT NULL,
+    status           TEXT NOT NULL DEFAULT 'pending',
+    attempts         INT NOT NULL DEFAULT 0,
+    next_attempt_at  TIMESTAMPTZ,
+    last_status_code INT,
+    last_error       TEXT,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    delivered_at     TIMESTAMPTZ
+);
+CREATE INDEX idx_event_deliveries_due ON event_deliveries(next_attempt_at) WHERE status = 'pending';
+CREATE INDEX idx_event_deliveries_subscription_id ON event_deliveries(subscription_id, created_at DESC);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS guild_bots;
+DROP TABLE IF EXISTS applications;
@@ -0,0 +1,23 @@
+-- Applications and their bot users. A bot user is a regular users row whose
+-- oauth_sub is `bot:{application_id}`; it authenticates with a bot token
+-- instead of the identity provider.
+CREATE TABLE applications (
+    id             UUID PRIMARY KEY,
+    name           TEXT NOT NULL,
+    description    TEXT,
+    owner_id       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    bot_user_id    UUID NOT NULL UNIQUE REFERENCES users(id) ON DELETE CASCADE,
+    bot_token_hash TEXT NOT NULL UNIQUE,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX idx_applications_owner_id ON applications(owner_id);
+
+-- Bots added to guilds, with the role granting their requested permissions
+CREATE TABLE guild_bots (
+    guild_id       UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    application_id UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    role_id        UUID REFERENCES roles(id) ON DELETE SET NULL,
+    added_by       UUID REFERENCES users(id) ON DELETE SET NULL,
+    added_at       TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, application_id)
+);
@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS interactions;
+DROP TABLE IF EXISTS application_commands;
+
+ALTER TABLE applications
+    DROP COLUMN IF EXISTS interactions_endpoint_url,
+    DROP COLUMN IF EXISTS interactions_secret;
@@ -0,0 +1,39 @@
+-- Where an application receives interactions over HTTP, and the secret they
+-- are signed with. Without an endpoint they go over the bot's gateway.
+ALTER TABLE applications
+    ADD COLUMN interactions_endpoint_url TEXT,
+    ADD COLUMN interactions_secret       TEXT;
+
+-- Slash commands; guild_id is NULL for global commands. Names are unique per
+-- application and scope.
+CREATE TABLE application_commands (
+    id             UUID PRIMARY KEY,
+    application_id UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    guild_id       UUID REFERENCES guilds(id) ON DELETE CASCADE,
+    name           TEXT NOT NULL,
+    description    TEXT NOT NULL,
+    options        JSONB NOT NULL DEFAULT '[]',
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE UNIQUE INDEX idx_application_commands_scope_name ON application_commands
+    (application_id, COALESCE(guild_id, '00000000-0000-0000-0000-000000000000'::UUID), name);
+CREATE INDEX idx_application_commands_guild_id ON application_commands(guild_id);
+
+-- Command invocations, kept until their follow-up window has passed
+CREATE TABLE interactions (
+    id             UUID PRIMARY KEY,
+    application_id UUID NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+    command_id     UUID REFERENCES application_commands(id) ON DELETE SET NULL,
+    command_name   TEXT NOT NULL,
+    guild_id       UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    channel_id     UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    user_id        UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    options        JSONB NOT NULL DEFAULT '[]',
+    token_hash     TEXT NOT NULL,
+    status         TEXT NOT NULL DEFAULT 'pending',
+    ephemeral      BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now(),
+    responded_at   TIMESTAMPTZ
+);
+CREATE INDEX idx_interactions_created_at ON interactions(created_at);
@@ -0,0 +1,10 @@
+DELETE FROM messages WHERE author_id IS NULL AND webhook_id IS NULL;
+DROP INDEX IF EXISTS idx_messages_source_message_id;
+ALTER TABLE messages
+    DROP CONSTRAINT IF EXISTS messages_author_check,
+    ADD CONSTRAINT messages_author_check CHECK (author_id IS NOT NULL OR webhook_id IS NOT NULL),
+    DROP COLUMN IF EXISTS source_message_id,
+    DROP COLUMN IF EXISTS source_channel_id,
+    DROP COLUMN IF EXISTS source_guild_id,
+    DROP COLUMN IF EXISTS crossposted_at;
+DROP TABLE IF EXISTS channel_follows;
@@ -0,0 +1,25 @@
+-- Channels following an announcement channel of another guild.
+CREATE TABLE channel_follows (
+    id                UUID PRIMARY KEY,
+    source_channel_id UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    guild_id          UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    channel_id        UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    created_by        UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (source_channel_id, channel_id)
+);
+CREATE INDEX idx_channel_follows_channel_id ON channel_follows(channel_id);
+
+-- Crossposted copies have no author either: they show the source guild and
+-- channel, and keep a reference to the source message without a foreign key
+-- so the copies can be found and removed after the source is deleted.
+ALTER TABLE messages
+    ADD COLUMN crossposted_at    TIMESTAMPTZ,
+    ADD COLUMN source_guild_id   UUID,
+    ADD COLUMN source_channel_id UUID,
+    ADD COLUMN source_message_id UUID,
+    DROP CONSTRAINT messages_author_check,
+    ADD CONSTRAINT messages_author_check
+        CHECK (author_id IS NOT NULL OR webhook_id IS NOT NULL OR source_message_id IS NOT NULL);
+CREATE INDEX idx_messages_source_message_id ON messages(source_message_id)
+    WHERE source_message_id IS NOT NULL;
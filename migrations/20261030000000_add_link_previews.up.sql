@@ -0,0 +1,8 @@
+-- OpenGraph previews of linked pages, shared by all messages linking them.
+-- A NULL embed records a page without metadata or a failed fetch.
+CREATE TABLE link_previews (
+    url        TEXT PRIMARY KEY,
+    embed      JSONB,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+CREATE INDEX idx_link_previews_fetched_at ON link_previews(fetched_at);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS guild_emoji_roles;
+DROP TABLE IF EXISTS guild_emojis;
@@ -0,0 +1,21 @@
+-- Custom emojis uploaded to a guild. The image lives in object storage.
+CREATE TABLE guild_emojis (
+    id           UUID PRIMARY KEY,
+    guild_id     UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    name         TEXT NOT NULL,
+    animated     BOOLEAN NOT NULL DEFAULT FALSE,
+    content_type TEXT NOT NULL,
+    size_bytes   INT NOT NULL,
+    storage_key  TEXT NOT NULL,
+    created_by   UUID REFERENCES users(id) ON DELETE SET NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    UNIQUE (guild_id, name)
+);
+
+-- Roles an emoji is restricted to. An emoji without rows is usable by
+-- everyone; deleting its last role lifts the restriction.
+CREATE TABLE guild_emoji_roles (
+    emoji_id UUID NOT NULL REFERENCES guild_emojis(id) ON DELETE CASCADE,
+    role_id  UUID NOT NULL REFERENCES roles(id) ON DELETE CASCADE,
+    PRIMARY KEY (emoji_id, role_id)
+);
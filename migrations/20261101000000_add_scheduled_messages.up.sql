@@ -0,0 +1,22 @@
+-- Messages and reminders queued by members. Due rows are claimed by
+-- whichever replica gets them first; lease_until keeps a claimed row from
+-- being picked up again while it is being delivered. `identity` is the
+-- author's identity, so permissions can be checked again at delivery time.
+CREATE TABLE scheduled_messages (
+    id             UUID PRIMARY KEY,
+    kind           TEXT NOT NULL,
+    user_id        UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    identity       JSONB NOT NULL,
+    guild_id       UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    channel_id     UUID NOT NULL REFERENCES channels(id) ON DELETE CASCADE,
+    message_id     UUID,
+    content        TEXT NOT NULL,
+    send_at        TIMESTAMPTZ NOT NULL,
+    status         TEXT NOT NULL DEFAULT 'pending',
+    lease_until    TIMESTAMPTZ,
+    failure_reason TEXT,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now(),
+    delivered_at   TIMESTAMPTZ
+);
+CREATE INDEX idx_scheduled_messages_due ON scheduled_messages(send_at) WHERE status = 'pending';
+CREATE INDEX idx_scheduled_messages_user_id ON scheduled_messages(user_id, send_at);
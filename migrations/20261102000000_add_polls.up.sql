@@ -0,0 +1,31 @@
+-- Polls attached to guild messages. When a poll closes its counts are copied
+-- into final_votes and final_voters, so its results no longer change, e.g.
+-- when a voter's account is deleted.
+CREATE TABLE polls (
+    message_id        UUID PRIMARY KEY REFERENCES messages(id) ON DELETE CASCADE,
+    question          TEXT NOT NULL,
+    allow_multiselect BOOLEAN NOT NULL DEFAULT FALSE,
+    hide_results      BOOLEAN NOT NULL DEFAULT FALSE,
+    expires_at        TIMESTAMPTZ NOT NULL,
+    closed_at         TIMESTAMPTZ,
+    final_voters      INT
+);
+CREATE INDEX idx_polls_open ON polls(expires_at) WHERE closed_at IS NULL;
+
+CREATE TABLE poll_answers (
+    message_id  UUID NOT NULL REFERENCES polls(message_id) ON DELETE CASCADE,
+    answer_id   INT NOT NULL,
+    text        TEXT NOT NULL,
+    final_votes INT,
+    PRIMARY KEY (message_id, answer_id)
+);
+
+CREATE TABLE poll_votes (
+    message_id UUID NOT NULL,
+    answer_id  INT NOT NULL,
+    user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (message_id, answer_id, user_id),
+    FOREIGN KEY (message_id, answer_id) REFERENCES poll_answers(message_id, answer_id) ON DELETE CASCADE
+);
+CREATE INDEX idx_poll_votes_user ON poll_votes(message_id, user_id);
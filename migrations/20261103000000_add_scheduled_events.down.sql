@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS guild_calendar_feeds;
+DROP TABLE IF EXISTS guild_scheduled_event_rsvps;
+DROP TABLE IF EXISTS guild_scheduled_events;
@@ -0,0 +1,40 @@
+-- Guild events. The location is either a voice/stage channel or free text.
+-- reminded_at is set once the reminder before the start went out.
+CREATE TABLE guild_scheduled_events (
+    id          UUID PRIMARY KEY,
+    guild_id    UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    creator_id  UUID REFERENCES users(id) ON DELETE SET NULL,
+    title       TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    starts_at   TIMESTAMPTZ NOT NULL,
+    ends_at     TIMESTAMPTZ NOT NULL,
+    channel_id  UUID REFERENCES channels(id) ON DELETE CASCADE,
+    location    TEXT,
+    status      TEXT NOT NULL DEFAULT 'scheduled',
+    reminded_at TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT now(),
+    CHECK ((channel_id IS NULL) <> (location IS NULL)),
+    CHECK (ends_at > starts_at)
+);
+CREATE INDEX idx_guild_scheduled_events_guild ON guild_scheduled_events(guild_id, starts_at);
+CREATE INDEX idx_guild_scheduled_events_open ON guild_scheduled_events(starts_at)
+    WHERE status IN ('scheduled', 'active');
+
+CREATE TABLE guild_scheduled_event_rsvps (
+    event_id   UUID NOT NULL REFERENCES guild_scheduled_events(id) ON DELETE CASCADE,
+    user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    status     TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (event_id, user_id)
+);
+
+-- Secret calendar feed URLs, one per member and guild. The identity is kept
+-- to check, on every fetch, which events the member can still see.
+CREATE TABLE guild_calendar_feeds (
+    guild_id   UUID NOT NULL REFERENCES guilds(id) ON DELETE CASCADE,
+    user_id    UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    identity   JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (guild_id, user_id)
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS discord_snowflakes;
+DROP SEQUENCE IF EXISTS discord_snowflake_sequence;
@@ -0,0 +1,11 @@
+-- Snowflake ids of the Discord-compatible API. FerrisCord ids are UUIDs,
+-- which do not fit the 64-bit ids Discord libraries expect, so every id
+-- handed out through the compatibility layer gets a snowflake on first use.
+-- Like Discord's, it starts with the creation time of the object (in ms
+-- since 2015) followed by 22 bits taken from a cycling sequence.
+CREATE SEQUENCE discord_snowflake_sequence MINVALUE 0 MAXVALUE 4194303 CYCLE;
+
+CREATE TABLE discord_snowflakes (
+    id         UUID PRIMARY KEY,
+    snowflake  BIGINT NOT NULL UNIQUE
+);
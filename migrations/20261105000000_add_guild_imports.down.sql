@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS guild_import_messages;
+DROP TABLE IF EXISTS guild_imports;
@@ -0,0 +1,41 @@
+-- Guilds recreated from Discord exports. The exported channels are staged
+-- while the import is uploading: its structure (channels, roles, authors)
+-- goes to `manifest`, its messages to guild_import_messages. Once started,
+-- an import is claimed by whichever replica gets it first and resumed from
+-- `cursor` if that replica goes away; everything it creates has an id
+-- derived from the import and the Discord id, so work redone after a
+-- restart is not duplicated.
+CREATE TABLE guild_imports (
+    id             UUID PRIMARY KEY,
+    user_id        UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    owner_id       UUID NOT NULL,
+    name           TEXT,
+    dry_run        BOOLEAN NOT NULL,
+    user_mappings  JSONB NOT NULL DEFAULT '{}',
+    manifest       JSONB NOT NULL DEFAULT '{}',
+    status         TEXT NOT NULL DEFAULT 'uploading',
+    guild_id       UUID REFERENCES guilds(id) ON DELETE SET NULL,
+    cursor         BIGINT NOT NULL DEFAULT 0,
+    progress       JSONB NOT NULL DEFAULT '{}',
+    lease_until    TIMESTAMPTZ,
+    failure_reason TEXT,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT now(),
+    started_at     TIMESTAMPTZ,
+    finished_at    TIMESTAMPTZ
+);
+CREATE INDEX idx_guild_imports_user_id ON guild_imports(user_id, created_at);
+CREATE INDEX idx_guild_imports_queue ON guild_imports(created_at) WHERE status IN ('pending', 'running');
+
+CREATE TABLE guild_import_messages (
+    import_id         UUID NOT NULL REFERENCES guild_imports(id) ON DELETE CASCADE,
+    seq               BIGSERIAL,
+    source_id         TEXT NOT NULL,
+    channel_source_id TEXT NOT NULL,
+    author_source_id  TEXT NOT NULL,
+    content           TEXT NOT NULL,
+    attachments       JSONB NOT NULL DEFAULT '[]',
+    created_at        TIMESTAMPTZ NOT NULL,
+    edited_at         TIMESTAMPTZ,
+    PRIMARY KEY (import_id, seq),
+    UNIQUE (import_id, source_id)
+);
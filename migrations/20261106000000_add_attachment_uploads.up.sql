@@ -0,0 +1,17 @@
+-- Files uploaded straight to storage through a pre-signed URL, waiting to
+-- be attached to a message. A slot is removed once attached; slots left
+-- unused are removed after expires_at, with their objects. The channel is
+-- not a foreign key so that slots of a deleted channel are still cleaned up.
+CREATE TABLE attachment_uploads (
+    id           UUID PRIMARY KEY,
+    user_id      UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    channel_id   UUID NOT NULL,
+    filename     TEXT NOT NULL,
+    content_type TEXT NOT NULL,
+    size_bytes   BIGINT NOT NULL,
+    storage_key  TEXT NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT now(),
+    expires_at   TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX idx_attachment_uploads_user_id ON attachment_uploads(user_id, expires_at);
+CREATE INDEX idx_attachment_uploads_expires_at ON attachment_uploads(expires_at);
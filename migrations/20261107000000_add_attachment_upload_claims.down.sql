@@ -0,0 +1 @@
+ALTER TABLE attachment_uploads DROP COLUMN IF EXISTS claimed_at;
@@ -0,0 +1,4 @@
+-- A slot is claimed by the message being sent with it, so that two messages
+-- sent at once cannot both attach it. A claim is released if the message
+-- fails to send.
+ALTER TABLE attachment_uploads ADD COLUMN claimed_at TIMESTAMPTZ;